@@ -0,0 +1,399 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use crate::utils::FileUtils;
+use crate::config::{A11yRuleLevel, Config};
+use crate::common::jsx::{jsx_elements, AttrValue};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, Severity, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct A11yReport {
+    pub issues: Vec<A11yIssue>,
+    pub summary: A11ySummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct A11yIssue {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub rule: A11yRule,
+    pub severity: Severity,
+    /// The element as written, e.g. `<div>`
+    pub element: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct A11ySummary {
+    pub files_scanned: usize,
+    pub total_issues: usize,
+    pub high_severity: usize,
+    pub medium_severity: usize,
+    pub low_severity: usize,
+    pub by_rule: BTreeMap<String, usize>,
+    /// Rules turned off in `[a11y.rules]`
+    pub disabled_rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum A11yRule {
+    ImgAlt,
+    ClickEvents,
+    FormLabel,
+    PositiveTabindex,
+    HtmlLang,
+    PreferButton,
+}
+
+impl A11yRule {
+    pub const ALL: [A11yRule; 6] = [
+        A11yRule::ImgAlt,
+        A11yRule::ClickEvents,
+        A11yRule::FormLabel,
+        A11yRule::PositiveTabindex,
+        A11yRule::HtmlLang,
+        A11yRule::PreferButton,
+    ];
+
+    /// The id used in `[a11y.rules]` and in reports
+    pub fn id(self) -> &'static str {
+        match self {
+            A11yRule::ImgAlt => "img-alt",
+            A11yRule::ClickEvents => "click-events",
+            A11yRule::FormLabel => "form-label",
+            A11yRule::PositiveTabindex => "positive-tabindex",
+            A11yRule::HtmlLang => "html-lang",
+            A11yRule::PreferButton => "prefer-button",
+        }
+    }
+
+    /// The stable id of findings of this rule, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            A11yRule::ImgAlt => "SNF-A11Y-IMG-ALT",
+            A11yRule::ClickEvents => "SNF-A11Y-CLICK-EVENTS",
+            A11yRule::FormLabel => "SNF-A11Y-FORM-LABEL",
+            A11yRule::PositiveTabindex => "SNF-A11Y-POSITIVE-TABINDEX",
+            A11yRule::HtmlLang => "SNF-A11Y-HTML-LANG",
+            A11yRule::PreferButton => "SNF-A11Y-PREFER-BUTTON",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            A11yRule::ImgAlt => "Image without alt text",
+            A11yRule::ClickEvents => "Click handler without keyboard access",
+            A11yRule::FormLabel => "Form control without a label",
+            A11yRule::PositiveTabindex => "Positive tabIndex",
+            A11yRule::HtmlLang => "Document without a language",
+            A11yRule::PreferButton => "Element used as a button",
+        }
+    }
+
+    fn default_severity(self) -> Severity {
+        match self {
+            A11yRule::ImgAlt | A11yRule::FormLabel | A11yRule::HtmlLang => Severity::High,
+            A11yRule::ClickEvents | A11yRule::PositiveTabindex | A11yRule::PreferButton => Severity::Medium,
+        }
+    }
+
+    fn fix(self) -> &'static str {
+        match self {
+            A11yRule::ImgAlt => "Describe the image in alt, or use alt=\"\" when it is decorative",
+            A11yRule::ClickEvents => "Use a <button>, or add a role, tabIndex={0} and an onKeyDown handler",
+            A11yRule::FormLabel => "Wrap the control in a <label>, point a label's htmlFor at its id, or add aria-label",
+            A11yRule::PositiveTabindex => "Use tabIndex={0} and order the elements in the DOM instead",
+            A11yRule::HtmlLang => "Add lang=\"en\" (or the page's language) to <html>",
+            A11yRule::PreferButton => "Use a native <button>, which is focusable and handles Enter and Space",
+        }
+    }
+}
+
+/// Elements that are not focusable and have no implicit interactive role
+const NON_INTERACTIVE: &[&str] = &[
+    "div", "span", "p", "li", "ul", "ol", "section", "article", "aside", "header", "footer", "main", "nav",
+    "img", "td", "tr", "th", "h1", "h2", "h3", "h4", "h5", "h6", "figure", "strong", "em", "i",
+];
+
+/// `<input>` types that need no label
+const UNLABELED_INPUT_TYPES: &[&str] = &["hidden", "submit", "button", "reset", "image"];
+
+const KEYBOARD_HANDLERS: &[&str] = &["onKeyDown", "onKeyUp", "onKeyPress"];
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<A11yReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running accessibility analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "a11y",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = report.summary.total_issues == 0, "accessibility analysis completed");
+
+    Ok(outcome)
+}
+
+/// Scan JSX for accessibility issues. High-severity issues fail the check.
+pub fn check(target: &ScanTarget, config: &Config, quiet: bool) -> Result<CommandOutcome<A11yReport>> {
+    let scanner = FileScanner::for_command(target, "a11y")?;
+    let files = scanner.find_target_files(target, &["tsx", "jsx", "js"]);
+    let levels = &config.a11y.rules;
+
+    let results: Vec<Vec<A11yIssue>> = FileUtils::process_files_parallel(
+        &files,
+        |path| scan_file(path, levels),
+        "Checking accessibility",
+        quiet
+    )?;
+    let mut issues: Vec<A11yIssue> = results.into_iter().flatten().collect();
+    target.ignores()?.retain(&mut issues, |issue| (issue.rule.rule_id(), &issue.file));
+
+    let mut by_rule = BTreeMap::new();
+    for issue in &issues {
+        *by_rule.entry(issue.rule.id().to_string()).or_insert(0) += 1;
+    }
+    let count = |severity: Severity| issues.iter().filter(|issue| issue.severity == severity).count();
+    let summary = A11ySummary {
+        files_scanned: files.len(),
+        total_issues: issues.len(),
+        high_severity: count(Severity::High),
+        medium_severity: count(Severity::Medium),
+        low_severity: count(Severity::Low),
+        by_rule,
+        disabled_rules: levels.iter()
+            .filter(|(_, level)| **level == A11yRuleLevel::Off)
+            .map(|(rule, _)| rule.clone())
+            .collect(),
+    };
+
+    let exit_code = check_failure_threshold(summary.high_severity > 0, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(A11yReport { issues, summary }, exit_code))
+}
+
+fn scan_file(path: &Path, levels: &BTreeMap<String, A11yRuleLevel>) -> Result<Vec<A11yIssue>> {
+    let content = fs::read_to_string(path)?;
+    let file = paths::display(path);
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+
+    let issues = check_elements(&content).into_iter().filter_map(|(offset, element, rule, message)| {
+        let severity = match levels.get(rule.id()) {
+            Some(A11yRuleLevel::Off) => return None,
+            Some(A11yRuleLevel::Low) => Severity::Low,
+            Some(A11yRuleLevel::Medium) => Severity::Medium,
+            Some(A11yRuleLevel::High) => Severity::High,
+            None => rule.default_severity(),
+        };
+        let line = line_starts.partition_point(|start| *start <= offset);
+        let column = content[line_starts[line - 1]..offset].chars().count() + 1;
+        Some(A11yIssue { file: file.clone(), line, column, rule, severity, element: format!("<{}>", element), message })
+    }).collect();
+    Ok(issues)
+}
+
+/// Every rule violation in a file as (byte offset, element name, rule, message)
+fn check_elements(content: &str) -> Vec<(usize, String, A11yRule, String)> {
+    let elements = jsx_elements(content);
+    // Ids that some label's `htmlFor` points to; a dynamic `htmlFor` may point to any id
+    let mut label_targets = HashSet::new();
+    let mut dynamic_label_target = false;
+    for element in elements.iter().filter(|element| element.name == "label") {
+        match element.attribute("htmlFor") {
+            Some(Some(AttrValue::Literal(id))) => { label_targets.insert(id); }
+            Some(Some(AttrValue::Expression(_))) => dynamic_label_target = true,
+            _ => {}
+        }
+    }
+
+    let mut issues = Vec::new();
+    for element in &elements {
+        let mut report = |rule: A11yRule, message: String| issues.push((element.offset, element.name.to_string(), rule, message));
+        let name = element.name;
+
+        if let Some(Some(value)) = element.attribute("tabIndex").or_else(|| element.attribute("tabindex")) {
+            if value.text().trim().parse::<i64>().is_ok_and(|index| index > 0) {
+                report(A11yRule::PositiveTabindex, format!("tabIndex={} takes the element out of the natural tab order", value.text().trim()));
+            }
+        }
+        // Anything may come in through spread props, so only tabIndex is checked
+        if element.spread {
+            continue;
+        }
+
+        let role = element.literal("role");
+        let hidden = matches!(role, Some("presentation" | "none")) || element.literal("aria-hidden") == Some("true");
+        let has = |attribute: &str| element.attribute(attribute).is_some();
+        let named = has("aria-label") || has("aria-labelledby");
+
+        if name == "img" && !has("alt") && !named && !hidden {
+            report(A11yRule::ImgAlt, "<img> has no alt attribute".to_string());
+        }
+
+        if (name == "html" || name == "Html") && !has("lang") {
+            report(A11yRule::HtmlLang, format!("<{}> has no lang attribute, so screen readers guess the language", name));
+        }
+
+        let is_form_control = match name {
+            "input" => !element.literal("type").is_some_and(|kind| UNLABELED_INPUT_TYPES.contains(&kind)),
+            "select" | "textarea" => true,
+            _ => false,
+        };
+        if is_form_control && !element.inside_label && !named && !has("title") {
+            let labeled_by_id = match element.attribute("id") {
+                Some(Some(AttrValue::Literal(id))) => label_targets.contains(id) || dynamic_label_target,
+                Some(Some(AttrValue::Expression(_))) => dynamic_label_target || !label_targets.is_empty(),
+                _ => false,
+            };
+            if !labeled_by_id {
+                report(A11yRule::FormLabel, format!("<{}> has no associated <label> or aria-label", name));
+            }
+        }
+
+        if !has("onClick") {
+            continue;
+        }
+        if name == "a" && !has("href") {
+            report(A11yRule::PreferButton, "<a> with onClick but no href is not focusable and is announced as a link".to_string());
+        } else if role == Some("button") && name != "button" {
+            report(A11yRule::PreferButton, format!("<{} role=\"button\"> reimplements a native <button>", name));
+        } else if NON_INTERACTIVE.contains(&name) && !hidden {
+            if role.is_none() && !has("role") {
+                report(A11yRule::ClickEvents, format!("onClick on <{}> cannot be reached or used with the keyboard", name));
+            } else if !KEYBOARD_HANDLERS.iter().any(|handler| has(handler)) {
+                report(A11yRule::ClickEvents, format!("<{}> has onClick but no keyboard handler", name));
+            }
+        }
+    }
+    issues
+}
+
+impl Annotate for A11yReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            Annotation::new(
+                AnnotationLevel::for_severity(&issue.severity),
+                &issue.file,
+                issue.line,
+                issue.rule.title(),
+                format!("{}. {}", issue.message, issue.rule.fix()),
+            )
+            .with_rule(issue.rule.rule_id())
+        }).collect()
+    }
+}
+
+fn print_report(report: &A11yReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("♿ Accessibility Report".bold().blue());
+        output::line("=======================".blue());
+        output::blank();
+    }
+
+    if report.issues.is_empty() {
+        output::line("✅ No accessibility issues found!".green());
+        return;
+    }
+
+    let mut current_file = "";
+    for issue in &report.issues {
+        if issue.file != current_file {
+            current_file = &issue.file;
+            output::line(issue.file.cyan().bold());
+        }
+        output::line(format!("  {}:{}  {}  {}", issue.line, issue.column, issue.severity.paint(issue.rule.id()), issue.message));
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Issues: {}", summary.total_issues.to_string().red()));
+    output::line(format!("  High severity: {}", summary.high_severity.to_string().red()));
+    output::line(format!("  Medium severity: {}", summary.medium_severity.to_string().yellow()));
+    output::line(format!("  Low severity: {}", summary.low_severity));
+    if !summary.disabled_rules.is_empty() {
+        output::line(format!("  Disabled rules: {}", summary.disabled_rules.join(", ").dimmed()));
+    }
+
+    if !quiet {
+        output::blank();
+        output::line("🔧 HOW TO FIX".bold().white());
+        output::line("────────────".white());
+        for rule in A11yRule::ALL.iter().filter(|rule| summary.by_rule.contains_key(rule.id())) {
+            output::line(format!("  {} ({}): {}", rule.id().bold(), summary.by_rule[rule.id()], rule.fix()));
+        }
+        output::blank();
+        output::line("💡 TIP: Set a rule to \"off\", \"low\", \"medium\" or \"high\" in [a11y.rules]".dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(content: &str) -> Vec<A11yRule> {
+        check_elements(content).into_iter().map(|(_, _, rule, _)| rule).collect()
+    }
+
+    #[test]
+    fn test_images_and_documents() {
+        assert_eq!(rules(r#"<img src="/logo.png" />"#), vec![A11yRule::ImgAlt]);
+        assert!(rules(r#"<img src="/divider.png" alt="" />"#).is_empty());
+        assert!(rules(r#"<img {...props} />"#).is_empty());
+        assert!(rules(r#"<img src={src} role="presentation" />"#).is_empty());
+        assert_eq!(rules("return (<html><body>{children}</body></html>)"), vec![A11yRule::HtmlLang]);
+        assert!(rules(r#"<html lang="en">"#).is_empty());
+    }
+
+    #[test]
+    fn test_click_handlers() {
+        assert_eq!(rules("<div onClick={() => setOpen(true)}>Open</div>"), vec![A11yRule::ClickEvents]);
+        assert_eq!(rules(r#"<li role="option" onClick={select}>One</li>"#), vec![A11yRule::ClickEvents]);
+        assert!(rules(r#"<li role="option" onClick={select} onKeyDown={select}>One</li>"#).is_empty());
+        assert_eq!(rules(r#"<div role="button" tabIndex={0} onClick={save}>Save</div>"#), vec![A11yRule::PreferButton]);
+        assert_eq!(rules("<a onClick={back}>Back</a>"), vec![A11yRule::PreferButton]);
+        assert!(rules(r#"<a href="/home" onClick={track}>Home</a>"#).is_empty());
+        assert!(rules("<button onClick={save}>Save</button>").is_empty());
+    }
+
+    #[test]
+    fn test_form_labels() {
+        assert_eq!(rules(r#"<input type="text" value={name} />"#), vec![A11yRule::FormLabel]);
+        assert!(rules(r#"<label>Name <input type="text" /></label>"#).is_empty());
+        assert!(rules(r#"<label htmlFor="email">Email</label><input id="email" />"#).is_empty());
+        assert!(rules(r#"<input type="hidden" name="csrf" /><select aria-label="Country"></select>"#).is_empty());
+        assert_eq!(rules(r#"<label>Name</label><textarea />"#), vec![A11yRule::FormLabel]);
+    }
+
+    #[test]
+    fn test_positive_tabindex() {
+        assert_eq!(rules(r#"<span tabIndex={3}>x</span><div tabIndex="-1" />"#), vec![A11yRule::PositiveTabindex]);
+        assert!(rules(r#"<button tabIndex={0}>Save</button>"#).is_empty());
+    }
+
+    #[test]
+    fn test_rule_ids_match_config() {
+        let ids: Vec<&str> = A11yRule::ALL.iter().map(|rule| rule.id()).collect();
+        assert_eq!(ids, crate::config::A11Y_RULES);
+    }
+}
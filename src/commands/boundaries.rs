@@ -0,0 +1,572 @@
+/// React Server/Client boundaries of the Next.js app router. Walks the imports of every route
+/// file (`page`, `layout`, ...) as Server Components until a `'use client'` module, which opens a
+/// boundary: it and everything it imports is bundled for the browser.
+use anyhow::{anyhow, Result};
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::config::BoundariesConfig;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+use crate::common::source::strip_comments;
+use super::history::Metrics;
+use super::routes::discovery::{APP_DIRS, SOURCE_EXTENSIONS};
+use super::imports_analyzer::{PathAliasResolver, dynamic_imports, is_node_builtin, resolve_local_import, split_package};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BoundariesReport {
+    /// `app` or `src/app`
+    pub app_dir: String,
+    pub boundaries: Vec<Boundary>,
+    pub issues: Vec<BoundaryIssue>,
+    pub summary: BoundariesSummary,
+}
+
+/// A `'use client'` module rendered from server code, and the client code it pulls in
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Boundary {
+    pub file: String,
+    /// Server modules importing it; empty when it is a route file itself
+    pub imported_by: Vec<String>,
+    /// Route files whose tree renders it
+    pub routes: Vec<String>,
+    /// Project files bundled for the browser with it, itself first
+    pub modules: Vec<String>,
+    pub packages: Vec<PackageWeight>,
+    /// Size of the project files, comments left out
+    pub local_bytes: u64,
+    /// `local_bytes` plus the installed packages
+    pub estimated_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageWeight {
+    pub name: String,
+    /// The package's entry and the files it imports in node_modules; `None` when not installed
+    pub bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BoundaryIssue {
+    pub kind: BoundaryIssueKind,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BoundaryIssueKind {
+    /// Client code importing a package or module that only runs on the server
+    ServerPackage,
+    /// Server code using browser globals or client-only React hooks
+    BrowserApi,
+    /// A boundary shipping more JavaScript than `[boundaries] max_boundary_kb`
+    LargeBoundary,
+}
+
+impl BoundaryIssueKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            BoundaryIssueKind::ServerPackage => "SNF-RSC-SERVER-PACKAGE",
+            BoundaryIssueKind::BrowserApi => "SNF-RSC-BROWSER-API",
+            BoundaryIssueKind::LargeBoundary => "SNF-RSC-LARGE-BOUNDARY",
+        }
+    }
+
+    fn level(self) -> AnnotationLevel {
+        match self {
+            BoundaryIssueKind::ServerPackage | BoundaryIssueKind::BrowserApi => AnnotationLevel::Error,
+            BoundaryIssueKind::LargeBoundary => AnnotationLevel::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BoundariesSummary {
+    pub route_files: usize,
+    pub server_modules: usize,
+    pub client_modules: usize,
+    pub boundaries: usize,
+    /// Client JavaScript of all boundaries, counting shared modules and packages once
+    pub estimated_client_bytes: u64,
+    pub total_issues: usize,
+    /// Issues dropped by `[[ignore]]` entries
+    pub ignored: usize,
+}
+
+impl Annotate for BoundariesReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            Annotation::new(issue.kind.level(), &issue.file, issue.line, format!("{:?}", issue.kind), &issue.message)
+                .with_rule(issue.kind.rule_id())
+        }).collect()
+    }
+}
+
+impl Metrics for BoundariesReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![("client_js_kb", self.summary.estimated_client_bytes as f64 / 1024.0)]
+    }
+}
+
+/// App router files rendered as Server Components unless they say `'use client'`
+const ROUTE_FILES: [&str; 8] = ["page", "layout", "template", "loading", "error", "not-found", "default", "global-error"];
+
+/// Packages and modules that only work on the server, matched on the import path or its package
+const SERVER_PACKAGES: &[&str] = &[
+    "server-only", "next/headers", "@prisma/client", "mongoose", "mongodb", "pg", "mysql2", "sqlite3",
+    "better-sqlite3", "ioredis", "redis", "bcrypt", "argon2", "sharp", "nodemailer", "firebase-admin",
+    "stripe", "jsonwebtoken", "aws-sdk", "puppeteer", "playwright",
+];
+
+/// Node.js core modules that cannot run in the browser; `buffer`, `events`, `path` and the
+/// like have browser replacements in Next.js
+const SERVER_BUILTINS: &[&str] = &[
+    "fs", "child_process", "net", "tls", "dns", "dgram", "cluster", "worker_threads", "http2", "inspector",
+    "module", "repl", "v8",
+];
+
+/// Files followed inside one package before its weight is left as is
+const MAX_PACKAGE_FILES: usize = 2000;
+
+struct BoundaryPatterns {
+    /// `import x from '...'`, `import '...'`, `export { x } from '...'`, across lines
+    static_import: Regex,
+    /// Relative `import`/`export ... from`, `import()` and `require()` inside a package
+    package_import: Regex,
+    browser_global: Regex,
+    client_hook: Regex,
+    window_guard: Regex,
+}
+
+static BOUNDARY_PATTERNS: OnceLock<BoundaryPatterns> = OnceLock::new();
+
+fn patterns() -> &'static BoundaryPatterns {
+    BOUNDARY_PATTERNS.get_or_init(|| BoundaryPatterns {
+        static_import: Regex::new(r#"(?m)^[ \t]*(?:import|export)\s+(type\s+)?(?:[\w\s{},*$]*?\s*from\s*)?['"]([^'"\n]+)['"]"#).unwrap(),
+        package_import: Regex::new(r#"(?:\bfrom\s*|\bimport\s*\(\s*|\brequire\s*\(\s*|\bimport\s+)['"](\.[^'"\n]*)['"]"#).unwrap(),
+        browser_global: Regex::new(r"(?:^|[^\w$.])(window|document|localStorage|sessionStorage|navigator)\s*\.").unwrap(),
+        client_hook: Regex::new(r"(?:^|[^\w$.])(useState|useEffect|useLayoutEffect|useReducer|useSyncExternalStore)\s*[(<]").unwrap(),
+        window_guard: Regex::new(r#"typeof\s+(?:window|document)\s*[!=]==?\s*['"]undefined['"]"#).unwrap(),
+    })
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<BoundariesReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!("Running Server/Client boundary audit");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.boundaries)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "boundaries",
+        report,
+        report.summary.server_modules + report.summary.client_modules,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(boundaries = report.summary.boundaries, issues = report.summary.total_issues, "Server/Client boundary audit completed");
+
+    Ok(outcome)
+}
+
+/// Map the `'use client'` boundaries below the app router's route files. Server-only packages in
+/// client code and browser APIs in server code fail the check; large boundaries are warnings
+pub fn check(target: &ScanTarget, settings: &BoundariesConfig) -> Result<CommandOutcome<BoundariesReport>> {
+    let project_dir = target.project_dir("boundaries")?;
+    let app_dir = APP_DIRS.iter()
+        .map(|dir| project_dir.join(dir))
+        .find(|dir| dir.is_dir())
+        .ok_or_else(|| anyhow!("No app router directory (app/ or src/app/) in {}", project_dir.display()))?;
+
+    let scanner = FileScanner::for_command(target, "boundaries")?;
+    let mut routes: Vec<PathBuf> = scanner.find_files_with_extensions(&app_dir, &SOURCE_EXTENSIONS).into_iter()
+        .filter(|path| path.file_stem().and_then(|stem| stem.to_str()).is_some_and(|stem| ROUTE_FILES.contains(&stem)))
+        .collect();
+    routes.sort();
+
+    let mut graph = Graph { target, resolver: PathAliasResolver::from_project_root(project_dir), modules: HashMap::new() };
+
+    // Server traversal: each route's tree down to the first 'use client' module on every path
+    let mut server_modules: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut found: BTreeMap<PathBuf, (BTreeSet<String>, BTreeSet<String>)> = BTreeMap::new();
+    for route in &routes {
+        let mut visited = BTreeSet::new();
+        let mut stack: Vec<(PathBuf, Option<PathBuf>)> = vec![(route.clone(), None)];
+        while let Some((file, importer)) = stack.pop() {
+            let Some(module) = graph.module(&file) else { continue };
+            if module.client {
+                let (imported_by, rendered_by) = found.entry(file.clone()).or_default();
+                imported_by.extend(importer.as_deref().map(paths::display));
+                rendered_by.insert(paths::display(route));
+                continue;
+            }
+            if !visited.insert(file.clone()) {
+                continue;
+            }
+            server_modules.insert(file.clone());
+            for target_file in module.local.clone() {
+                stack.push((target_file, Some(file.clone())));
+            }
+        }
+    }
+
+    let mut issues = Vec::new();
+    for file in &server_modules {
+        if let Some(module) = graph.module(file) {
+            issues.extend(browser_apis(file, &module.code));
+        }
+    }
+
+    let server_packages: Vec<&str> = SERVER_PACKAGES.iter().copied().chain(settings.server_packages.iter().map(String::as_str)).collect();
+    let mut weights: HashMap<String, Option<u64>> = HashMap::new();
+    let mut client_modules: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut client_packages: BTreeSet<String> = BTreeSet::new();
+    let mut reported: BTreeSet<(PathBuf, usize)> = BTreeSet::new();
+    let mut boundaries = Vec::new();
+    for (file, (imported_by, rendered_by)) in found {
+        // Everything a client module imports is client code, 'use client' or not
+        let mut modules: Vec<PathBuf> = Vec::new();
+        let mut packages: BTreeSet<String> = BTreeSet::new();
+        let mut stack = vec![file.clone()];
+        let mut seen = BTreeSet::new();
+        while let Some(current) = stack.pop() {
+            if !seen.insert(current.clone()) {
+                continue;
+            }
+            let Some(module) = graph.module(&current) else { continue };
+            for (line, specifier) in &module.packages {
+                let server = server_builtin(specifier)
+                    .or_else(|| server_packages.iter().copied().find(|server| is_server_import(specifier, server)));
+                if let Some(server) = server {
+                    if reported.insert((current.clone(), *line)) {
+                        let through = if current == file { String::new() } else { format!(", which the 'use client' boundary {} bundles for the browser", paths::display(&file)) };
+                        issues.push(BoundaryIssue {
+                            kind: BoundaryIssueKind::ServerPackage,
+                            file: paths::display(&current),
+                            line: *line,
+                            message: format!("Client code imports {}, which only runs on the server{}", server, through),
+                        });
+                    }
+                    continue;
+                }
+                if !is_node_builtin(specifier) {
+                    packages.insert(split_package(specifier).0.to_string());
+                }
+            }
+            stack.extend(module.local.iter().rev().cloned());
+            modules.push(current);
+        }
+
+        let local_bytes: u64 = modules.iter().map(|module| graph.bytes(module)).sum();
+        let packages: Vec<PackageWeight> = packages.into_iter().map(|name| {
+            let bytes = *weights.entry(name.clone()).or_insert_with(|| package_bytes(project_dir, &name));
+            PackageWeight { name, bytes }
+        }).collect();
+        let estimated_bytes = local_bytes + packages.iter().filter_map(|package| package.bytes).sum::<u64>();
+
+        if estimated_bytes > settings.max_boundary_kb * 1024 {
+            let heaviest = packages.iter().filter(|package| package.bytes.is_some()).max_by_key(|package| package.bytes)
+                .map(|package| format!("; the largest package is {} ({})", package.name, kb(package.bytes.unwrap_or_default())))
+                .unwrap_or_default();
+            issues.push(BoundaryIssue {
+                kind: BoundaryIssueKind::LargeBoundary,
+                file: paths::display(&file),
+                line: 1,
+                message: format!("Ships about {} of JavaScript to the browser ({} in {} project files{}), over {} KB",
+                    kb(estimated_bytes), kb(local_bytes), modules.len(), heaviest, settings.max_boundary_kb),
+            });
+        }
+
+        client_packages.extend(packages.iter().map(|package| package.name.clone()));
+        client_modules.extend(modules.iter().cloned());
+        boundaries.push(Boundary {
+            file: paths::display(&file),
+            imported_by: imported_by.into_iter().collect(),
+            routes: rendered_by.into_iter().collect(),
+            modules: modules.iter().map(|module| paths::display(module)).collect(),
+            packages,
+            local_bytes,
+            estimated_bytes,
+        });
+    }
+
+    let ignored = target.ignores()?.retain(&mut issues, |issue| (issue.kind.rule_id(), &issue.file));
+    issues.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    boundaries.sort_by(|a, b| b.estimated_bytes.cmp(&a.estimated_bytes).then_with(|| a.file.cmp(&b.file)));
+
+    let estimated_client_bytes = client_modules.iter().map(|module| graph.bytes(module)).sum::<u64>()
+        + client_packages.iter().filter_map(|name| weights.get(name).copied().flatten()).sum::<u64>();
+    let summary = BoundariesSummary {
+        route_files: routes.len(),
+        server_modules: server_modules.len(),
+        client_modules: client_modules.len(),
+        boundaries: boundaries.len(),
+        estimated_client_bytes,
+        total_issues: issues.len(),
+        ignored,
+    };
+    let failed = issues.iter().any(|issue| issue.kind.level() == AnnotationLevel::Error);
+    let exit_code = check_failure_threshold(failed, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(BoundariesReport { app_dir: paths::display(&app_dir), boundaries, issues, summary }, exit_code))
+}
+
+/// What the audit needs from one source file
+struct Module {
+    /// Starts with a `'use client'` directive
+    client: bool,
+    /// Project files it imports, in source order
+    local: Vec<PathBuf>,
+    /// Package imports with their line; type-only imports are left out
+    packages: Vec<(usize, String)>,
+    /// Source with comments blanked
+    code: String,
+    /// Size without comments
+    bytes: u64,
+}
+
+/// Source files parsed on first use
+struct Graph<'a> {
+    target: &'a ScanTarget,
+    resolver: Option<PathAliasResolver>,
+    modules: HashMap<PathBuf, Option<Module>>,
+}
+
+impl Graph<'_> {
+    fn module(&mut self, file: &Path) -> Option<&Module> {
+        if !self.modules.contains_key(file) {
+            let module = self.target.context.read(file).ok().map(|content| self.parse(file, &content));
+            self.modules.insert(file.to_path_buf(), module);
+        }
+        self.modules.get(file)?.as_ref()
+    }
+
+    fn bytes(&mut self, file: &Path) -> u64 {
+        self.module(file).map_or(0, |module| module.bytes)
+    }
+
+    fn parse(&self, file: &Path, content: &str) -> Module {
+        let code = strip_comments(content);
+        let mut specifiers: Vec<(usize, &str)> = patterns().static_import.captures_iter(&code)
+            .filter(|caps| caps.get(1).is_none())
+            .filter_map(|caps| Some((caps.get(0)?.start(), caps.get(2)?.as_str())))
+            .collect();
+        specifiers.extend(dynamic_imports(&code));
+        specifiers.sort();
+
+        let mut local = Vec::new();
+        let mut packages = Vec::new();
+        for (offset, specifier) in specifiers {
+            let resolved = resolve_local_import(file, specifier, &self.resolver);
+            match resolved {
+                Some(path) if SOURCE_EXTENSIONS.iter().any(|ext| path.extension().is_some_and(|e| e == *ext)) => local.push(path),
+                // Styles, JSON and other assets
+                Some(_) => {}
+                None if !specifier.starts_with('.') && !specifier.starts_with('/') => packages.push((line_of(&code, offset), specifier.to_string())),
+                None => {}
+            }
+        }
+        let bytes = code.bytes().filter(|byte| !byte.is_ascii_whitespace()).count() as u64;
+        Module { client: is_client_module(&code), local, packages, code, bytes }
+    }
+}
+
+/// A module whose leading directives include `'use client'`
+fn is_client_module(code: &str) -> bool {
+    has_directive(code, "use client")
+}
+
+/// Whether the directives at the top of the module (comments blanked) include `directive`,
+/// e.g. `use client` or `use server`
+pub(crate) fn has_directive(code: &str, directive: &str) -> bool {
+    for statement in code.split([';', '\n']).map(str::trim).filter(|statement| !statement.is_empty()) {
+        let Some(name) = statement.strip_prefix(['\'', '"']).and_then(|rest| rest.strip_suffix(['\'', '"'])) else {
+            return false;
+        };
+        if name == directive {
+            return true;
+        }
+        if !name.starts_with("use ") {
+            return false;
+        }
+    }
+    false
+}
+
+fn is_server_import(specifier: &str, server: &str) -> bool {
+    specifier == server || split_package(specifier).0 == server
+        || specifier.strip_prefix(server).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Node.js modules bundlers have no browser replacement for, e.g. `fs` or `node:child_process`
+fn server_builtin(specifier: &str) -> Option<&str> {
+    let module = specifier.trim_start_matches("node:").split('/').next()?;
+    SERVER_BUILTINS.contains(&module).then_some(specifier)
+}
+
+/// Browser globals outside a `typeof window` guard, and hooks that only run in Client Components
+fn browser_apis(file: &Path, code: &str) -> Vec<BoundaryIssue> {
+    let patterns = patterns();
+    let guarded = patterns.window_guard.is_match(code);
+    let mut issues = Vec::new();
+    for (index, line) in code.lines().enumerate() {
+        let global = (!guarded).then(|| patterns.browser_global.captures(line)).flatten();
+        let hook = patterns.client_hook.captures(line);
+        let message = match (global, hook) {
+            (Some(caps), _) => format!("Server code uses `{}`, which does not exist during server rendering; move it into a 'use client' component", &caps[1]),
+            (None, Some(caps)) => format!("Server code calls {}, which only works in Client Components; add 'use client' or move the state into a client component", &caps[1]),
+            (None, None) => continue,
+        };
+        issues.push(BoundaryIssue { kind: BoundaryIssueKind::BrowserApi, file: paths::display(file), line: index + 1, message });
+    }
+    issues
+}
+
+fn line_of(code: &str, offset: usize) -> usize {
+    code[..offset].matches('\n').count() + 1
+}
+
+/// Bytes of an installed package's browser entry and the package files it imports, without
+/// following other packages
+fn package_bytes(project_dir: &Path, name: &str) -> Option<u64> {
+    let dir = project_dir.join("node_modules").join(name);
+    let manifest: Value = serde_json::from_str(&fs::read_to_string(dir.join("package.json")).ok()?).ok()?;
+    let entry = ["browser", "module", "main"].iter()
+        .find_map(|field| manifest[*field].as_str())
+        .unwrap_or("index.js");
+
+    let mut total = 0;
+    let mut seen = BTreeSet::new();
+    let mut stack: Vec<PathBuf> = resolve_package_file(&dir.join(entry)).into_iter().collect();
+    while let Some(file) = stack.pop() {
+        if seen.len() >= MAX_PACKAGE_FILES || !seen.insert(file.clone()) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&file) else { continue };
+        total += content.len() as u64;
+        let parent = file.parent().unwrap_or(&dir);
+        stack.extend(patterns().package_import.captures_iter(&content)
+            .filter_map(|caps| resolve_package_file(&parent.join(&caps[1]))));
+    }
+    Some(total)
+}
+
+fn resolve_package_file(base: &Path) -> Option<PathBuf> {
+    if base.is_file() {
+        return Some(base.to_path_buf());
+    }
+    let file_name = base.file_name()?.to_string_lossy().into_owned();
+    ["js", "mjs", "cjs"].iter()
+        .map(|ext| base.with_file_name(format!("{}.{}", file_name, ext)))
+        .chain(["index.js", "index.mjs", "index.cjs"].iter().map(|index| base.join(index)))
+        .find(|candidate| candidate.is_file())
+}
+
+fn kb(bytes: u64) -> String {
+    format!("{:.1} KB", bytes as f64 / 1024.0)
+}
+
+fn print_report(report: &BoundariesReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🧭 Server/Client Boundary Report".bold().blue());
+        output::line("================================".blue());
+        output::line(format!("  App router: {}", report.app_dir.cyan()));
+        output::blank();
+    }
+
+    if report.boundaries.is_empty() {
+        output::line("  No 'use client' boundaries: every route renders on the server".dimmed());
+    } else {
+        output::line("🌳 CLIENT BOUNDARIES".bold().white());
+        output::line("───────────────────".white());
+        for boundary in &report.boundaries {
+            output::line(format!("  {} {}", boundary.file.cyan(), format!("~{}", kb(boundary.estimated_bytes)).bold()));
+            let from = if boundary.imported_by.is_empty() { "route file".to_string() } else { boundary.imported_by.join(", ") };
+            output::line(format!("    {} {}", "from".dimmed(), from.dimmed()));
+            output::line(format!("    {} {} project files, {}", "├─".dimmed(), boundary.modules.len(), kb(boundary.local_bytes)));
+            for package in &boundary.packages {
+                let size = package.bytes.map(kb).unwrap_or_else(|| "not installed".to_string());
+                output::line(format!("    {} {} {}", "├─".dimmed(), package.name, size.dimmed()));
+            }
+        }
+    }
+    output::blank();
+
+    if report.issues.is_empty() {
+        output::line("✅ No boundary issues found!".green());
+    } else {
+        output::line("🔍 ISSUES".bold().yellow());
+        output::line("─────────".yellow());
+        for issue in &report.issues {
+            let rule = match issue.kind.level() {
+                AnnotationLevel::Critical | AnnotationLevel::Error => issue.kind.rule_id().red(),
+                _ => issue.kind.rule_id().yellow(),
+            };
+            output::line(format!("  {}:{} - {} {}", issue.file.cyan(), issue.line, rule, issue.message));
+        }
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Route files: {}", summary.route_files));
+    output::line(format!("  Server modules: {}", summary.server_modules));
+    output::line(format!("  Client modules: {}", summary.client_modules));
+    output::line(format!("  Boundaries: {}", summary.boundaries));
+    output::line(format!("  Estimated client JavaScript: {}", kb(summary.estimated_client_bytes)));
+    output::line(format!("  Issues: {}", summary.total_issues));
+    if summary.ignored > 0 {
+        output::line(format!("  Ignored: {}", summary.ignored.to_string().dimmed()));
+    }
+    output::blank();
+    output::line("💡 TIP: Push 'use client' down to the interactive leaves so layouts and pages stay on the server".dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_directive_must_come_first() {
+        assert!(is_client_module("'use client';\n\nexport function Button() {}\n"));
+        assert!(is_client_module("  \n\"use strict\"\n\"use client\"\nimport x from 'y';\n"));
+        assert!(!is_client_module("import x from 'y';\n'use client';\n"));
+        assert!(!is_client_module("'use server';\nexport async function save() {}\n"));
+    }
+
+    #[test]
+    fn test_browser_apis_in_server_code() {
+        let code = "import { useState } from 'react';\nexport default function Page() {\n  const [open] = useState(false);\n  const width = window.innerWidth;\n  return <div data-open={open} />;\n}\n";
+        let found: Vec<(usize, bool)> = browser_apis(Path::new("page.tsx"), code).iter()
+            .map(|issue| (issue.line, issue.message.contains("useState")))
+            .collect();
+        assert_eq!(found, vec![(3, true), (4, false)]);
+
+        // Guarded code only runs in the browser
+        let guarded = "export const width = () => typeof window !== 'undefined' ? window.innerWidth : 0;\n";
+        assert!(browser_apis(Path::new("lib/width.ts"), guarded).is_empty());
+    }
+
+    #[test]
+    fn test_server_imports_match_packages_and_subpaths() {
+        assert!(is_server_import("@prisma/client", "@prisma/client"));
+        assert!(is_server_import("next/headers", "next/headers"));
+        assert!(is_server_import("firebase-admin/auth", "firebase-admin"));
+        assert!(!is_server_import("next/link", "next/headers"));
+        assert!(!is_server_import("pgp", "pg"));
+        assert_eq!(server_builtin("node:fs/promises"), Some("node:fs/promises"));
+        assert_eq!(server_builtin("path"), None);
+    }
+}
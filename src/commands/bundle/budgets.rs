@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::common::glob_matches;
+use crate::config::BundleConfig;
+use super::{BundleChunk, ChunkType};
+
+/// Prefixes Next.js puts in front of the app/pages paths in `.next/`
+const ROUTE_PREFIXES: &[&str] = &["static/chunks/", "server/", "static/"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetResult {
+    pub kind: BudgetKind,
+    /// `total`, `largest chunk`, the chunk type, or the route glob
+    pub name: String,
+    pub limit_bytes: u64,
+    pub actual_bytes: u64,
+    pub exceeded: bool,
+    /// Chunks counted against the budget (for `chunk`, only those over it)
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetKind {
+    Total,
+    Chunk,
+    ChunkType,
+    Route,
+}
+
+/// Check the chunks of one build against every configured budget
+pub fn evaluate(chunks: &[BundleChunk], build_dir: &Path, config: &BundleConfig) -> Vec<BudgetResult> {
+    let budgets = &config.budgets;
+    let mut results = Vec::new();
+
+    let total_limit = budgets.total_kb.map(kb).unwrap_or(mb(config.max_bundle_size_mb));
+    let total: u64 = chunks.iter().map(|c| c.size_bytes).sum();
+    results.push(result(BudgetKind::Total, "total", total_limit, total, Vec::new()));
+
+    let chunk_limit = budgets.chunk_kb.map(kb).unwrap_or(mb(config.max_chunk_size_mb));
+    let largest = chunks.iter().map(|c| c.size_bytes).max().unwrap_or(0);
+    let oversized = chunks.iter().filter(|c| c.size_bytes > chunk_limit).map(|c| c.name.clone()).collect();
+    results.push(result(BudgetKind::Chunk, "largest chunk", chunk_limit, largest, oversized));
+
+    for (chunk_type, limit) in &budgets.chunk_types {
+        let matching: Vec<&BundleChunk> = chunks.iter().filter(|c| type_key(&c.chunk_type) == chunk_type).collect();
+        results.push(sum_result(BudgetKind::ChunkType, chunk_type, kb(*limit), &matching));
+    }
+
+    for (pattern, limit) in &budgets.routes {
+        let matching: Vec<&BundleChunk> = chunks.iter()
+            .filter(|c| glob_matches(pattern, &route_path(Path::new(&c.path), build_dir)))
+            .collect();
+        results.push(sum_result(BudgetKind::Route, pattern, kb(*limit), &matching));
+    }
+
+    results
+}
+
+fn kb(value: u64) -> u64 {
+    value * 1024
+}
+
+fn mb(value: f64) -> u64 {
+    (value * 1024.0 * 1024.0) as u64
+}
+
+fn result(kind: BudgetKind, name: &str, limit_bytes: u64, actual_bytes: u64, chunks: Vec<String>) -> BudgetResult {
+    BudgetResult {
+        kind,
+        name: name.to_string(),
+        limit_bytes,
+        actual_bytes,
+        exceeded: actual_bytes > limit_bytes,
+        chunks,
+    }
+}
+
+fn sum_result(kind: BudgetKind, name: &str, limit_bytes: u64, chunks: &[&BundleChunk]) -> BudgetResult {
+    let actual = chunks.iter().map(|c| c.size_bytes).sum();
+    result(kind, name, limit_bytes, actual, chunks.iter().map(|c| c.name.clone()).collect())
+}
+
+fn type_key(chunk_type: &ChunkType) -> &'static str {
+    match chunk_type {
+        ChunkType::Main => "main",
+        ChunkType::Page => "page",
+        ChunkType::Component => "component",
+        ChunkType::Vendor => "vendor",
+        ChunkType::Runtime => "runtime",
+        ChunkType::Static => "static",
+        ChunkType::Shared => "shared",
+    }
+}
+
+/// Chunk path relative to the build output, without the `.next/static/chunks/`-style
+/// prefix, so `app/dashboard/**` matches `.next/static/chunks/app/dashboard/page-1a2b.js`
+pub(super) fn route_path(path: &Path, build_dir: &Path) -> String {
+    let relative = path.strip_prefix(build_dir).unwrap_or(path);
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    ROUTE_PREFIXES.iter()
+        .find_map(|prefix| relative.strip_prefix(prefix))
+        .unwrap_or(&relative)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn chunk(path: &str, size_bytes: u64, chunk_type: ChunkType) -> BundleChunk {
+        BundleChunk {
+            id: path.to_string(),
+            name: path.rsplit('/').next().unwrap().to_string(),
+            size_bytes,
+            size_compressed: None,
+            chunk_type,
+            path: format!(".next/{}", path),
+        }
+    }
+
+    #[test]
+    fn test_budgets_by_type_and_route() {
+        let chunks = vec![
+            chunk("static/chunks/app/dashboard/page-1a.js", 200 * 1024, ChunkType::Page),
+            chunk("static/chunks/app/dashboard/settings/page-2b.js", 150 * 1024, ChunkType::Page),
+            chunk("static/chunks/vendor-3c.js", 700 * 1024, ChunkType::Vendor),
+        ];
+        let mut config = Config::default().bundle;
+        config.budgets.total_kb = Some(2048);
+        config.budgets.chunk_types.insert("vendor".to_string(), 500);
+        config.budgets.routes.insert("app/dashboard/**".to_string(), 300);
+
+        let results = evaluate(&chunks, Path::new(".next"), &config);
+        let exceeded: Vec<&str> = results.iter().filter(|r| r.exceeded).map(|r| r.name.as_str()).collect();
+
+        assert_eq!(exceeded, vec!["largest chunk", "vendor", "app/dashboard/**"]);
+        let route = results.iter().find(|r| r.kind == BudgetKind::Route).unwrap();
+        assert_eq!(route.actual_bytes, 350 * 1024);
+        assert_eq!(route.chunks, vec!["page-1a.js", "page-2b.js"]);
+    }
+}
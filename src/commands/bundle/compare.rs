@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use crate::config::BundleConfig;
+use super::{BundleChunk, BundleReport};
+use super::budgets::route_path;
+
+/// Size changes between a saved baseline report and the current build
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleComparison {
+    pub baseline: String,
+    pub total_before: u64,
+    pub total_after: u64,
+    pub total_delta: i64,
+    /// Chunks whose size changed, were added or were removed, largest increase first
+    pub changes: Vec<ChunkDiff>,
+    pub regressions: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ChunkDiff {
+    pub id: String,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub delta_bytes: i64,
+    pub delta_percent: Option<f64>,
+    pub regression: bool,
+}
+
+/// The parts of a saved report needed for a comparison; older reports have no `id`
+#[derive(Deserialize)]
+struct SavedChunk {
+    #[serde(default)]
+    id: String,
+    name: String,
+    size_bytes: u64,
+}
+
+static HASH_SUFFIX: OnceLock<Regex> = OnceLock::new();
+
+/// Build-independent identifier: the path inside the build output with content hashes
+/// removed, so `static/chunks/app/page-8f2a1b3c4d5e6f70.js` becomes `app/page.js`
+pub(super) fn chunk_id(path: &Path, build_dir: &Path) -> String {
+    strip_hash(&route_path(path, build_dir))
+}
+
+fn strip_hash(path: &str) -> String {
+    let (rest, extension) = match path.rfind('.') {
+        Some(index) if !path[index..].contains('/') => path.split_at(index),
+        _ => (path, ""),
+    };
+    // `page-8f2a1b3c.js`, `index.BQ2x7pFz.js`; a file name that is only a hash is kept
+    let regex = HASH_SUFFIX.get_or_init(|| Regex::new(r"^(.*[^/])[.-]([A-Za-z0-9_]{8,})$").unwrap());
+    match regex.captures(rest) {
+        Some(caps) if caps[2].chars().any(|c| c.is_ascii_digit()) => format!("{}{}", &caps[1], extension),
+        _ => path.to_string(),
+    }
+}
+
+/// Diff the current chunks against the report saved at `baseline_path`
+pub fn compare(report: &BundleReport, baseline_path: &Path, config: &BundleConfig) -> Result<BundleComparison> {
+    let content = fs::read_to_string(baseline_path)
+        .with_context(|| format!("Cannot read baseline report {}", baseline_path.display()))?;
+    let mut value: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a bundle report", baseline_path.display()))?;
+    // Accept both a `--save` file and the `--json` envelope
+    if let Some(data) = value.get_mut("data").map(serde_json::Value::take) {
+        value = data;
+    }
+    let saved: Vec<SavedChunk> = serde_json::from_value(value.get("chunks").cloned().unwrap_or_default())
+        .with_context(|| format!("{} has no bundle chunks", baseline_path.display()))?;
+
+    let before = sizes_by_id(saved.into_iter().map(|chunk| {
+        let id = if chunk.id.is_empty() { strip_hash(&chunk.name) } else { chunk.id };
+        (id, chunk.size_bytes)
+    }));
+    let after = sizes_by_id(report.chunks.iter().map(|chunk: &BundleChunk| (chunk.id.clone(), chunk.size_bytes)));
+
+    let ids: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    let mut changes: Vec<ChunkDiff> = ids.into_iter()
+        .filter_map(|id| {
+            let before = before.get(id).copied();
+            let after = after.get(id).copied();
+            let delta = after.unwrap_or(0) as i64 - before.unwrap_or(0) as i64;
+            (delta != 0).then(|| diff(id, before, after, delta, config))
+        })
+        .collect();
+    changes.sort_by(|a, b| b.delta_bytes.cmp(&a.delta_bytes).then_with(|| a.id.cmp(&b.id)));
+
+    let total_before: u64 = before.values().sum();
+    let total_after: u64 = after.values().sum();
+    let total_delta = total_after as i64 - total_before as i64;
+
+    Ok(BundleComparison {
+        baseline: baseline_path.display().to_string(),
+        total_before,
+        total_after,
+        total_delta,
+        regressions: changes.iter().filter(|change| change.regression).count(),
+        changes,
+    })
+}
+
+fn sizes_by_id(chunks: impl Iterator<Item = (String, u64)>) -> BTreeMap<String, u64> {
+    let mut sizes = BTreeMap::new();
+    for (id, size) in chunks {
+        *sizes.entry(id).or_insert(0) += size;
+    }
+    sizes
+}
+
+fn diff(id: &str, before: Option<u64>, after: Option<u64>, delta: i64, config: &BundleConfig) -> ChunkDiff {
+    let delta_percent = before.filter(|&size| size > 0).map(|size| {
+        ((delta as f64 / size as f64) * 1000.0).round() / 10.0
+    });
+    let thresholds = &config.regression;
+    let over_kb = thresholds.max_increase_kb.is_some_and(|kb| delta > (kb * 1024) as i64);
+    let over_percent = match (thresholds.max_increase_percent, delta_percent) {
+        (Some(limit), Some(percent)) => percent > limit,
+        _ => false,
+    };
+
+    ChunkDiff {
+        id: id.to_string(),
+        before,
+        after,
+        delta_bytes: delta,
+        delta_percent,
+        regression: delta > 0 && (over_kb || over_percent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_stripping() {
+        assert_eq!(strip_hash("app/dashboard/page-8f2a1b3c4d5e6f70.js"), "app/dashboard/page.js");
+        assert_eq!(strip_hash("assets/index.BQ2x7pFz.js"), "assets/index.js");
+        assert_eq!(strip_hash("app/dashboard-settings.js"), "app/dashboard-settings.js");
+        assert_eq!(strip_hash("css/a1b2c3d4e5f6a7b8.css"), "css/a1b2c3d4e5f6a7b8.css");
+    }
+
+    #[test]
+    fn test_regressions_against_saved_report() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let baseline = dir.path().join("baseline.json");
+        fs::write(&baseline, r#"{"data": {"chunks": [
+            {"id": "app/page.js", "name": "page-1a2b3c4d.js", "size_bytes": 100000},
+            {"name": "main-9f8e7d6c.js", "size_bytes": 50000},
+            {"id": "old.js", "name": "old.js", "size_bytes": 2000}
+        ]}}"#).unwrap();
+
+        let chunk = |id: &str, size_bytes| BundleChunk {
+            id: id.to_string(),
+            name: id.to_string(),
+            size_bytes,
+            size_compressed: None,
+            chunk_type: super::super::ChunkType::Page,
+            path: id.to_string(),
+        };
+        let mut report: BundleReport = serde_json::from_str(r#"{"chunks": [], "summary": {"total_size": 0, "total_compressed": 0, "chunk_count": 0, "largest_chunk": null, "compression_ratio": 1.0, "warnings": []}, "budgets": [], "recommendations": []}"#).unwrap();
+        report.chunks = vec![chunk("app/page.js", 120000), chunk("main.js", 50500)];
+
+        let mut config = crate::config::Config::default().bundle;
+        config.regression.max_increase_percent = Some(5.0);
+        let comparison = compare(&report, &baseline, &config).unwrap();
+
+        let ids: Vec<&str> = comparison.changes.iter().map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, vec!["app/page.js", "main.js", "old.js"]);
+        assert_eq!(comparison.regressions, 1);
+        assert_eq!(comparison.changes[0].delta_percent, Some(20.0));
+        assert_eq!(comparison.changes[2].after, None);
+        assert_eq!(comparison.total_delta, 18500);
+    }
+}
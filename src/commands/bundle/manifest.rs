@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use super::{BundleChunk, ChunkType};
+
+/// Per-route JavaScript from `.next/build-manifest.json` and `.next/app-build-manifest.json`,
+/// computed the way `next build` prints its route table
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteManifestReport {
+    /// JS loaded by every route
+    pub shared_bytes: u64,
+    pub shared_chunks: Vec<String>,
+    pub routes: Vec<RouteBundle>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RouteBundle {
+    pub route: String,
+    pub router: Router,
+    /// JS only this route loads, beyond the shared chunks
+    pub route_bytes: u64,
+    /// Everything the browser downloads on a first visit to this route
+    pub first_load_bytes: u64,
+    pub chunks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Router {
+    App,
+    Pages,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct BuildManifest {
+    root_main_files: Vec<String>,
+    pages: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct AppBuildManifest {
+    pages: BTreeMap<String, Vec<String>>,
+}
+
+/// Map chunks to routes using the build manifests and reclassify them as shared
+/// or route-specific. Returns `None` when `.next` has no manifests (e.g. dev builds).
+pub fn analyze_routes(next_dir: &Path, chunks: &mut [BundleChunk]) -> Option<RouteManifestReport> {
+    let build: Option<BuildManifest> = read_manifest(&next_dir.join("build-manifest.json"));
+    let app: Option<AppBuildManifest> = read_manifest(&next_dir.join("app-build-manifest.json"));
+    if build.is_none() && app.is_none() {
+        return None;
+    }
+    let build = build.unwrap_or_default();
+    let app = app.unwrap_or_default();
+
+    let sizes: HashMap<String, u64> = chunks.iter()
+        .filter_map(|chunk| {
+            let relative = Path::new(&chunk.path).strip_prefix(next_dir).ok()?;
+            Some((relative.to_string_lossy().replace('\\', "/"), chunk.size_bytes))
+        })
+        .collect();
+
+    let mut route_files: Vec<(String, Router, BTreeSet<String>)> = Vec::new();
+
+    let app_shell = build.pages.get("/_app").cloned().unwrap_or_default();
+    for (page, files) in &build.pages {
+        if page.starts_with("/_") {
+            continue;
+        }
+        let all = app_shell.iter().chain(files).filter(|f| f.ends_with(".js")).cloned().collect();
+        route_files.push((page.clone(), Router::Pages, all));
+    }
+
+    for (entry, files) in &app.pages {
+        let Some(route) = app_route(entry) else { continue };
+        let mut all: BTreeSet<String> = build.root_main_files.iter().chain(files).cloned().collect();
+        // Layouts wrapping the page are part of its first load
+        for layout in enclosing_layouts(entry) {
+            all.extend(app.pages.get(&layout).into_iter().flatten().cloned());
+        }
+        all.retain(|f| f.ends_with(".js"));
+        route_files.push((route, Router::App, all));
+    }
+
+    if route_files.is_empty() {
+        return None;
+    }
+
+    let shared: BTreeSet<String> = route_files.iter()
+        .map(|(_, _, files)| files.clone())
+        .reduce(|acc, files| acc.intersection(&files).cloned().collect())
+        .unwrap_or_default();
+
+    let mut usage: HashMap<&String, usize> = HashMap::new();
+    for (_, _, files) in &route_files {
+        for file in files {
+            *usage.entry(file).or_insert(0) += 1;
+        }
+    }
+
+    for chunk in chunks.iter_mut() {
+        let Ok(relative) = Path::new(&chunk.path).strip_prefix(next_dir) else { continue };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        let Some(&count) = usage.get(&relative) else { continue };
+        chunk.chunk_type = if shared.contains(&relative) {
+            match chunk.chunk_type {
+                ChunkType::Runtime | ChunkType::Vendor => chunk.chunk_type.clone(),
+                _ => ChunkType::Main,
+            }
+        } else if count > 1 {
+            ChunkType::Shared
+        } else {
+            ChunkType::Page
+        };
+    }
+
+    let mut routes: Vec<RouteBundle> = route_files.iter()
+        .map(|(route, router, files)| RouteBundle {
+            route: route.clone(),
+            router: *router,
+            route_bytes: total_size(files.iter().filter(|f| !shared.contains(*f)), &sizes),
+            first_load_bytes: total_size(files.iter(), &sizes),
+            chunks: files.iter().filter(|f| !shared.contains(*f)).cloned().collect(),
+        })
+        .collect();
+    routes.sort_by(|a, b| a.route.cmp(&b.route).then_with(|| (a.router as u8).cmp(&(b.router as u8))));
+
+    Some(RouteManifestReport {
+        shared_bytes: total_size(shared.iter(), &sizes),
+        shared_chunks: shared.into_iter().collect(),
+        routes,
+    })
+}
+
+fn total_size<'a>(files: impl Iterator<Item = &'a String>, sizes: &HashMap<String, u64>) -> u64 {
+    files.map(|f| sizes.get(f).copied().unwrap_or(0)).sum()
+}
+
+fn read_manifest<T: for<'de> Deserialize<'de>>(path: &Path) -> Option<T> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// `/dashboard/(admin)/users/page` -> `/dashboard/users`; non-page entries are skipped
+fn app_route(entry: &str) -> Option<String> {
+    let path = entry.strip_suffix("/page")?;
+    let is_group = |segment: &str| segment.starts_with('(') && segment.ends_with(')');
+    let segments: Vec<&str> = path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .filter(|segment| !is_group(segment))
+        .collect();
+    Some(format!("/{}", segments.join("/")))
+}
+
+/// `/dashboard/users/page` -> `/layout`, `/dashboard/layout`, `/dashboard/users/layout`
+fn enclosing_layouts(entry: &str) -> Vec<String> {
+    let dir = entry.rsplit_once('/').map(|(dir, _)| dir).unwrap_or("");
+    let mut layouts = vec!["/layout".to_string()];
+    let mut current = String::new();
+    for segment in dir.split('/').filter(|s| !s.is_empty()) {
+        current.push('/');
+        current.push_str(segment);
+        layouts.push(format!("{}/layout", current));
+    }
+    layouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_route_names() {
+        assert_eq!(app_route("/page").as_deref(), Some("/"));
+        assert_eq!(app_route("/(marketing)/pricing/page").as_deref(), Some("/pricing"));
+        assert_eq!(app_route("/api/users/route"), None);
+        assert_eq!(enclosing_layouts("/dashboard/page"), vec!["/layout", "/dashboard/layout"]);
+    }
+
+    #[test]
+    fn test_first_load_js_per_route() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let next = dir.path();
+        fs::write(next.join("build-manifest.json"), r#"{"rootMainFiles": ["static/chunks/webpack-1.js", "static/chunks/main-app-2.js"], "pages": {"/_app": []}}"#).unwrap();
+        fs::write(next.join("app-build-manifest.json"), r#"{"pages": {
+            "/layout": ["static/chunks/app/layout-3.js", "static/css/app.css"],
+            "/page": ["static/chunks/app/page-4.js", "static/chunks/ui-5.js"],
+            "/dashboard/page": ["static/chunks/app/dashboard/page-6.js", "static/chunks/ui-5.js"],
+            "/api/health/route": ["static/chunks/app/api/health/route-7.js"]
+        }}"#).unwrap();
+
+        let chunk = |file: &str, size_bytes, chunk_type| BundleChunk {
+            id: String::new(),
+            name: file.rsplit('/').next().unwrap().to_string(),
+            size_bytes,
+            size_compressed: None,
+            chunk_type,
+            path: next.join(file).to_string_lossy().to_string(),
+        };
+        let mut chunks = vec![
+            chunk("static/chunks/webpack-1.js", 1000, ChunkType::Runtime),
+            chunk("static/chunks/main-app-2.js", 2000, ChunkType::Main),
+            chunk("static/chunks/app/layout-3.js", 500, ChunkType::Component),
+            chunk("static/chunks/app/page-4.js", 300, ChunkType::Page),
+            chunk("static/chunks/ui-5.js", 4000, ChunkType::Component),
+            chunk("static/chunks/app/dashboard/page-6.js", 700, ChunkType::Page),
+        ];
+
+        let report = analyze_routes(next, &mut chunks).unwrap();
+
+        assert_eq!(report.shared_bytes, 1000 + 2000 + 500 + 4000);
+        let routes: Vec<(&str, u64, u64)> = report.routes.iter().map(|r| (r.route.as_str(), r.route_bytes, r.first_load_bytes)).collect();
+        assert_eq!(routes, vec![("/", 300, 7800), ("/dashboard", 700, 8200)]);
+        assert!(matches!(chunks[0].chunk_type, ChunkType::Runtime));
+        assert!(matches!(chunks[2].chunk_type, ChunkType::Main));
+        assert!(matches!(chunks[5].chunk_type, ChunkType::Page));
+    }
+}
@@ -0,0 +1,933 @@
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::fs;
+use walkdir::WalkDir;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, ExitCode, CommandOutcome, Ignores, ScanTarget, check_failure_threshold, create_annotated_json_output, output_result};
+use crate::config::{BundleConfig, Config};
+use crate::commands::history::Metrics;
+
+mod budgets;
+mod compare;
+mod manifest;
+mod stats;
+pub use budgets::{BudgetKind, BudgetResult};
+pub use compare::BundleComparison;
+pub use manifest::RouteManifestReport;
+pub use stats::StatsReport;
+
+/// Baseline handling enabled from the command line
+#[derive(Debug, Clone, Default)]
+pub struct BundleOptions {
+    /// Report saved by an earlier `--save` (or `--json`) run to diff against (`--compare`)
+    pub compare: Option<PathBuf>,
+    /// Where to write this build's report for later comparisons (`--save`)
+    pub save: Option<PathBuf>,
+    /// webpack or rollup-plugin-visualizer stats file to analyze instead of the build output (`--stats`)
+    pub stats: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleReport {
+    pub chunks: Vec<BundleChunk>,
+    pub summary: BundleSummary,
+    pub budgets: Vec<BudgetResult>,
+    /// First-load JS per route, from the Next.js build manifests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub routes: Option<RouteManifestReport>,
+    /// Module-level attribution from `--stats`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stats: Option<StatsReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<BundleComparison>,
+    pub recommendations: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleChunk {
+    /// Path inside the build output without content hashes; stable across builds
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub size_bytes: u64,
+    pub size_compressed: Option<u64>,
+    pub chunk_type: ChunkType,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ChunkType {
+    Main,
+    Page,
+    Component,
+    Vendor,
+    Runtime,
+    Static,
+    /// Loaded by several routes, but not all (from the Next.js build manifests)
+    Shared,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Framework {
+    NextJs,
+    React,
+    Vue,
+    Angular,
+    Svelte,
+    Vite,
+    Webpack,
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BundleSummary {
+    pub total_size: u64,
+    pub total_compressed: u64,
+    pub chunk_count: usize,
+    pub largest_chunk: Option<String>,
+    pub compression_ratio: f64,
+    pub warnings: Vec<String>,
+}
+
+pub async fn run(target: &ScanTarget, options: &BundleOptions, json: bool, quiet: bool) -> Result<CommandOutcome<BundleReport>> {
+    tracing::info!("Running bundle analysis");
+    
+    let start_time = std::time::Instant::now();
+    let outcome = check(target, options).await?;
+    
+    if let Some(path) = &options.save {
+        fs::write(path, serde_json::to_string_pretty(&outcome.report)?)
+            .with_context(|| format!("Cannot save bundle report to {}", path.display()))?;
+        if !quiet && !json {
+            output::line(format!("💾 Saved bundle report to {}", path.display()));
+        }
+    }
+    
+    let report = &outcome.report;
+    let response = create_annotated_json_output(
+        "bundle",
+        report,
+        report.summary.chunk_count,
+        report.annotations().len(),
+        Some(start_time.elapsed().as_millis() as u64),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+    
+    Ok(outcome)
+}
+
+/// Analyze the build output and decide the exit status, without printing a report
+pub async fn check(target: &ScanTarget, options: &BundleOptions) -> Result<CommandOutcome<BundleReport>> {
+    let project_dir = target.project_dir("bundle")?;
+    let mut report = match &options.stats {
+        Some(stats_path) => analyze_stats_bundle(project_dir, stats_path)?,
+        None => analyze_bundle(project_dir).await?,
+    };
+    
+    let config = Config::load_from_dir(project_dir)?;
+    if let Some(baseline) = &options.compare {
+        report.comparison = Some(compare::compare(&report, baseline, &config.bundle)?);
+    }
+    
+    // Budgets are project-wide, so only `[[ignore]]` entries without paths match them; regressions match by chunk
+    let ignores = Ignores::new(project_dir, &config.ignore);
+    report.budgets.retain(|budget| !budget.exceeded || !ignores.ignores("SNF-BUNDLE-BUDGET", ""));
+    if let Some(comparison) = &mut report.comparison {
+        for change in comparison.changes.iter_mut().filter(|change| change.regression) {
+            change.regression = !ignores.ignores("SNF-BUNDLE-REGRESSION", &change.id);
+        }
+        comparison.regressions = comparison.changes.iter().filter(|change| change.regression).count();
+    }
+    
+    // Fail if any size budget is exceeded, or on size regressions against the baseline
+    let regressions = report.comparison.as_ref().is_some_and(|comparison| comparison.regressions > 0);
+    let exit_code = if report.budgets.iter().any(|budget| budget.exceeded) {
+        ExitCode::GeneralError
+    } else {
+        check_failure_threshold(regressions, ExitCode::ThresholdExceeded)
+    };
+    
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+async fn analyze_bundle(project_dir: &Path) -> Result<BundleReport> {
+    let config = Config::load_from_dir(project_dir)?.bundle;
+    
+    tracing::info!("Searching for build output directories");
+    
+    // Look for Next.js build output
+    let next_build_dir = project_dir.join(".next");
+    if next_build_dir.exists() {
+        tracing::info!("Found Next.js build output in .next/");
+        analyze_nextjs_bundle(&next_build_dir, &config).await
+    } else {
+        // Look for other common build outputs
+        let potential_dirs = vec!["dist", "build", "out"];
+        
+        for dir_name in potential_dirs {
+            let build_dir = project_dir.join(dir_name);
+            if build_dir.exists() {
+                tracing::info!("Found build output in {}/", dir_name);
+                return analyze_generic_bundle(&build_dir, &config).await;
+            }
+        }
+        
+        Err(anyhow!("No build output found. Please run 'npm run build' or equivalent first."))
+    }
+}
+
+async fn analyze_nextjs_bundle(next_dir: &Path, config: &BundleConfig) -> Result<BundleReport> {
+    let mut chunks = Vec::new();
+    let mut total_size = 0u64;
+    let mut total_compressed = 0u64;
+    
+    tracing::info!("Analyzing Next.js bundle structure");
+    
+    // Analyze static chunks
+    let static_dir = next_dir.join("static");
+    if static_dir.exists() {
+        tracing::debug!("Analyzing static chunks");
+        chunks.extend(analyze_static_chunks(&static_dir)?);
+    }
+    
+    // Analyze pages
+    let pages_dir = next_dir.join("server").join("pages");
+    if pages_dir.exists() {
+        tracing::debug!("Analyzing page chunks");
+        chunks.extend(analyze_pages_chunks(&pages_dir)?);
+    }
+    
+    for chunk in &mut chunks {
+        chunk.id = compare::chunk_id(Path::new(&chunk.path), next_dir);
+    }
+    
+    // The manifests know which route loads which chunk; file names are only a guess
+    let routes = manifest::analyze_routes(next_dir, &mut chunks);
+    if routes.is_some() {
+        tracing::debug!("Mapped chunks to routes using the build manifests");
+    }
+    
+    // Calculate totals
+    for chunk in &chunks {
+        total_size += chunk.size_bytes;
+        if let Some(compressed) = chunk.size_compressed {
+            total_compressed += compressed;
+        }
+    }
+    
+    if chunks.is_empty() {
+        return Err(anyhow!("No bundle chunks found in .next directory. Please run 'npm run build' first."));
+    }
+    
+    tracing::info!(chunks = chunks.len(), "Bundle analysis completed");
+    
+    let compression_ratio = if total_size > 0 {
+        (total_compressed as f64) / (total_size as f64)
+    } else {
+        1.0
+    };
+    
+    let largest_chunk = chunks
+        .iter()
+        .max_by_key(|c| c.size_bytes)
+        .map(|c| c.name.clone());
+    
+    let budgets = budgets::evaluate(&chunks, next_dir, config);
+    let warnings = generate_warnings(&budgets);
+    let recommendations = generate_recommendations(&chunks, next_dir, &budgets);
+    
+    let chunk_count = chunks.len();
+    
+    Ok(BundleReport {
+        chunks,
+        summary: BundleSummary {
+            total_size,
+            total_compressed,
+            chunk_count,
+            largest_chunk,
+            compression_ratio,
+            warnings,
+        },
+        budgets,
+        routes,
+        stats: None,
+        comparison: None,
+        recommendations,
+    })
+}
+
+async fn analyze_generic_bundle(build_dir: &Path, config: &BundleConfig) -> Result<BundleReport> {
+    let mut chunks = Vec::new();
+    let mut total_size = 0u64;
+    
+    // Walk through build directory
+    for entry in WalkDir::new(build_dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            if let Some(extension) = entry.path().extension() {
+                let ext = extension.to_string_lossy().to_lowercase();
+                if matches!(ext.as_str(), "js" | "css" | "html" | "json") {
+                    let size = entry.metadata()?.len();
+                    total_size += size;
+                    
+                    let chunk_type = determine_chunk_type_from_path(entry.path());
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    
+                    chunks.push(BundleChunk {
+                        id: compare::chunk_id(entry.path(), build_dir),
+                        name,
+                        size_bytes: size,
+                        size_compressed: None,
+                        chunk_type,
+                        path: entry.path().to_string_lossy().to_string(),
+                    });
+                }
+            }
+        }
+    }
+    
+    if chunks.is_empty() {
+        return Err(anyhow!("No bundle files found in build directory."));
+    }
+    
+    let largest_chunk = chunks
+        .iter()
+        .max_by_key(|c| c.size_bytes)
+        .map(|c| c.name.clone());
+    
+    let budgets = budgets::evaluate(&chunks, build_dir, config);
+    let warnings = generate_warnings(&budgets);
+    let recommendations = generate_recommendations(&chunks, build_dir, &budgets);
+    
+    let chunk_count = chunks.len();
+    
+    Ok(BundleReport {
+        chunks,
+        summary: BundleSummary {
+            total_size,
+            total_compressed: 0,
+            chunk_count,
+            largest_chunk,
+            compression_ratio: 1.0,
+            warnings,
+        },
+        budgets,
+        routes: None,
+        stats: None,
+        comparison: None,
+        recommendations,
+    })
+}
+
+/// Build the report from a stats file: its output assets become the chunks
+fn analyze_stats_bundle(project_dir: &Path, stats_path: &Path) -> Result<BundleReport> {
+    let config = Config::load_from_dir(project_dir)?.bundle;
+    
+    tracing::info!("Reading bundle stats from {}", stats_path.display());
+    let (stats, assets) = stats::analyze_stats(stats_path)?;
+    
+    let chunks: Vec<BundleChunk> = assets.into_iter()
+        .map(|asset| BundleChunk {
+            id: compare::chunk_id(Path::new(&asset.name), Path::new("")),
+            chunk_type: determine_chunk_type(&asset.name),
+            size_compressed: None,
+            size_bytes: asset.size_bytes,
+            path: asset.name.clone(),
+            name: asset.name,
+        })
+        .collect();
+    
+    let total_size = chunks.iter().map(|c| c.size_bytes).sum();
+    let largest_chunk = chunks
+        .iter()
+        .max_by_key(|c| c.size_bytes)
+        .map(|c| c.name.clone());
+    
+    let budgets = budgets::evaluate(&chunks, Path::new(""), &config);
+    let warnings = generate_warnings(&budgets);
+    // The framework is detected from the project that produced the stats
+    let mut recommendations = generate_stats_recommendations(&stats);
+    recommendations.extend(generate_recommendations(&chunks, &project_dir.join("dist"), &budgets));
+    
+    let chunk_count = chunks.len();
+    
+    Ok(BundleReport {
+        chunks,
+        summary: BundleSummary {
+            total_size,
+            total_compressed: 0,
+            chunk_count,
+            largest_chunk,
+            compression_ratio: 1.0,
+            warnings,
+        },
+        budgets,
+        routes: None,
+        stats: Some(stats),
+        comparison: None,
+        recommendations,
+    })
+}
+
+fn generate_stats_recommendations(stats: &StatsReport) -> Vec<String> {
+    let mut recommendations = Vec::new();
+    for duplicate in &stats.duplicates {
+        recommendations.push(format!("{} is bundled {} times - align versions or run npm dedupe",
+            duplicate.name, duplicate.locations.len()));
+    }
+    for hint in &stats.tree_shaking_hints {
+        recommendations.push(format!("{}: {}", hint.package, hint.hint));
+    }
+    recommendations
+}
+
+fn analyze_static_chunks(static_dir: &Path) -> Result<Vec<BundleChunk>> {
+    let mut chunks = Vec::new();
+    
+    for entry in WalkDir::new(static_dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() {
+            if let Some(extension) = entry.path().extension() {
+                let ext = extension.to_string_lossy().to_lowercase();
+                if matches!(ext.as_str(), "js" | "css") {
+                    let size = entry.metadata()?.len();
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let chunk_type = determine_chunk_type(&name);
+                    
+                    chunks.push(BundleChunk {
+                        id: String::new(),
+                        name: name.clone(),
+                        size_bytes: size,
+                        size_compressed: estimate_compressed_size(size),
+                        chunk_type,
+                        path: entry.path().to_string_lossy().to_string(),
+                    });
+                }
+            }
+        }
+    }
+    
+    Ok(chunks)
+}
+
+fn analyze_pages_chunks(pages_dir: &Path) -> Result<Vec<BundleChunk>> {
+    let mut chunks = Vec::new();
+    
+    for entry in WalkDir::new(pages_dir) {
+        let entry = entry?;
+        if entry.file_type().is_file() && entry.path().extension().map_or(false, |e| e == "js") {
+            let size = entry.metadata()?.len();
+            let name = entry.file_name().to_string_lossy().to_string();
+            
+            chunks.push(BundleChunk {
+                id: String::new(),
+                name: name.clone(),
+                size_bytes: size,
+                size_compressed: estimate_compressed_size(size),
+                chunk_type: ChunkType::Page,
+                path: entry.path().to_string_lossy().to_string(),
+            });
+        }
+    }
+    
+    Ok(chunks)
+}
+
+fn determine_chunk_type(filename: &str) -> ChunkType {
+    let filename_lower = filename.to_lowercase();
+    
+    if filename_lower.contains("main") {
+        ChunkType::Main
+    } else if filename_lower.contains("vendor") || filename_lower.contains("node_modules") {
+        ChunkType::Vendor
+    } else if filename_lower.contains("runtime") {
+        ChunkType::Runtime
+    } else if filename_lower.contains("page") {
+        ChunkType::Page
+    } else if filename_lower.ends_with(".css") {
+        ChunkType::Static
+    } else {
+        ChunkType::Component
+    }
+}
+
+fn determine_chunk_type_from_path(path: &Path) -> ChunkType {
+    let path_str = path.to_string_lossy().to_lowercase();
+    
+    if path_str.contains("vendor") || path_str.contains("node_modules") {
+        ChunkType::Vendor
+    } else if path_str.contains("page") {
+        ChunkType::Page
+    } else if path_str.ends_with(".css") {
+        ChunkType::Static
+    } else {
+        ChunkType::Component
+    }
+}
+
+fn estimate_compressed_size(original_size: u64) -> Option<u64> {
+    // Rough estimation: gzip typically achieves 60-80% compression for JS/CSS
+    Some((original_size as f64 * 0.35) as u64)
+}
+
+fn generate_warnings(budgets: &[BudgetResult]) -> Vec<String> {
+    budgets.iter()
+        .filter(|budget| budget.exceeded)
+        .map(|budget| match budget.kind {
+            BudgetKind::Total => format!("Total bundle size ({} KB) exceeds budget ({} KB)",
+                budget.actual_bytes / 1024, budget.limit_bytes / 1024),
+            BudgetKind::Chunk => format!("{} chunks exceed the per-chunk budget ({} KB): {}",
+                budget.chunks.len(), budget.limit_bytes / 1024, budget.chunks.join(", ")),
+            BudgetKind::ChunkType => format!("{} chunks ({} KB) exceed budget ({} KB)",
+                budget.name, budget.actual_bytes / 1024, budget.limit_bytes / 1024),
+            BudgetKind::Route => format!("Route {} ({} KB) exceeds budget ({} KB)",
+                budget.name, budget.actual_bytes / 1024, budget.limit_bytes / 1024),
+        })
+        .collect()
+}
+
+fn generate_recommendations(chunks: &[BundleChunk], build_dir: &Path, budgets: &[BudgetResult]) -> Vec<String> {
+    let mut recommendations = Vec::new();
+    
+    // Analyze chunk distribution
+    let mut chunk_types: HashMap<String, u64> = HashMap::new();
+    for chunk in chunks {
+        let type_name = format!("{:?}", chunk.chunk_type);
+        *chunk_types.entry(type_name).or_insert(0) += chunk.size_bytes;
+    }
+    
+    // Check for vendor chunk size
+    if let Some(vendor_size) = chunk_types.get("Vendor") {
+        if *vendor_size > 800_000 {
+            recommendations.push("Consider code splitting to reduce vendor bundle size".to_string());
+            recommendations.push("Use dynamic imports for heavy libraries".to_string());
+        }
+    }
+    
+    // Check for main chunk size
+    if let Some(main_size) = chunk_types.get("Main") {
+        if *main_size > 300_000 {
+            recommendations.push("Main bundle is large - consider lazy loading routes".to_string());
+        }
+    }
+    
+    // General recommendations
+    recommendations.push("Enable gzip/brotli compression on your server".to_string());
+    recommendations.push("Consider using a CDN for static assets".to_string());
+    
+    if chunks.len() > 20 {
+        recommendations.push("High number of chunks - consider optimizing chunk splitting strategy".to_string());
+    }
+    
+    // Add framework-specific recommendations
+    let framework = detect_framework(build_dir);
+    let over_budget = budgets.iter().any(|budget| budget.exceeded);
+    let framework_recommendations = generate_framework_recommendations(&framework, over_budget);
+    recommendations.extend(framework_recommendations);
+    
+    recommendations
+}
+
+impl Metrics for BundleReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![("bundle_size_kb", (self.summary.total_size as f64 / 1024.0).round())]
+    }
+}
+
+impl Annotate for BundleReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let budgets = self.budgets.iter().filter(|budget| budget.exceeded).map(|budget| {
+            let label = match budget.kind {
+                BudgetKind::Total => "Total size".to_string(),
+                BudgetKind::Chunk => "Largest chunk".to_string(),
+                BudgetKind::ChunkType => format!("{} chunks", budget.name),
+                BudgetKind::Route => format!("Route {}", budget.name),
+            };
+            Annotation::project(
+                AnnotationLevel::Error,
+                "Bundle budget exceeded",
+                format!("{} is {} KB, over the {} KB budget", label, budget.actual_bytes / 1024, budget.limit_bytes / 1024),
+            )
+            .with_rule("SNF-BUNDLE-BUDGET")
+        });
+        let regressions = self.comparison.iter()
+            .flat_map(|comparison| comparison.changes.iter().map(move |change| (comparison, change)))
+            .filter(|(_, change)| change.regression)
+            .map(|(comparison, change)| {
+                Annotation::project(
+                    AnnotationLevel::Warning,
+                    "Bundle size regression",
+                    format!("{} grew by {} KB since {}", change.id, change.delta_bytes / 1024, comparison.baseline),
+                )
+                .with_rule("SNF-BUNDLE-REGRESSION")
+            });
+        budgets.chain(regressions).collect()
+    }
+}
+
+fn print_report(report: &BundleReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("📊 Bundle Analysis Report".bold().blue());
+        output::line("========================".blue());
+        output::blank();
+    }
+    
+    if report.chunks.is_empty() {
+        output::line("⚠️ No bundle chunks found.".yellow());
+        return;
+    }
+    
+    // Sort chunks by size (largest first)
+    let mut sorted_chunks = report.chunks.clone();
+    sorted_chunks.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+    
+    // Group chunks by type
+    let mut chunks_by_type: HashMap<String, Vec<&BundleChunk>> = HashMap::new();
+    for chunk in &sorted_chunks {
+        let type_name = format!("{:?}", chunk.chunk_type);
+        chunks_by_type.entry(type_name).or_default().push(chunk);
+    }
+    
+    // Print largest chunks first
+    output::line("📦 LARGEST CHUNKS".bold().white());
+    output::line("─────────────────".white());
+    
+    for (i, chunk) in sorted_chunks.iter().take(10).enumerate() {
+        let size_kb = chunk.size_bytes / 1024;
+        let size_color = if chunk.size_bytes > 500_000 {
+            size_kb.to_string().red()
+        } else if chunk.size_bytes > 200_000 {
+            size_kb.to_string().yellow()
+        } else {
+            size_kb.to_string().green()
+        };
+        
+        output::line(format!("  {}. {} - {} KB", (i + 1), chunk.name.cyan(), size_color));
+        
+        if let Some(compressed) = chunk.size_compressed {
+            let compressed_kb = compressed / 1024;
+            output::line(format!("     {} Compressed: {} KB", "💾".dimmed(), compressed_kb.to_string().dimmed()));
+        }
+    }
+    
+    output::blank();
+    
+    if let Some(routes) = &report.routes {
+        print_routes(routes);
+    }
+    if let Some(stats) = &report.stats {
+        print_stats(stats);
+    }
+        print_budgets(&report.budgets);
+    if let Some(comparison) = &report.comparison {
+        print_comparison(comparison);
+    }
+    
+    // Print warnings
+    if !report.summary.warnings.is_empty() {
+        output::line("⚠️  WARNINGS".bold().yellow());
+        output::line("───────────".yellow());
+        for warning in &report.summary.warnings {
+            output::line(format!("  • {}", warning.yellow()));
+        }
+        output::blank();
+    }
+    
+    // Print recommendations
+    if !report.recommendations.is_empty() {
+        output::line("💡 RECOMMENDATIONS".bold().green());
+        output::line("──────────────────".green());
+        for rec in &report.recommendations {
+            output::line(format!("  • {}", rec.green()));
+        }
+        output::blank();
+    }
+    
+    // Print summary
+    print_summary(&report.summary);
+}
+
+fn print_routes(manifest: &RouteManifestReport) {
+    output::line("🗺️  ROUTES (first-load JS)".bold().white());
+    output::line("─────────────────────────".white());
+    
+    let width = manifest.routes.iter().map(|r| r.route.len()).max().unwrap_or(5).max(5);
+    output::line(format!("  {:<width$}  {:>10}  {:>14}", "Route".dimmed(), "Size".dimmed(), "First Load JS".dimmed(), width = width));
+    for route in &manifest.routes {
+        let first_load = format!("{:.1} KB", route.first_load_bytes as f64 / 1024.0);
+        let first_load = if route.first_load_bytes > 250 * 1024 {
+            first_load.red()
+        } else if route.first_load_bytes > 130 * 1024 {
+            first_load.yellow()
+        } else {
+            first_load.green()
+        };
+        output::line(format!("  {:<width$}  {:>10}  {:>14}", route.route.cyan(), format!("{:.1} KB", route.route_bytes as f64 / 1024.0), first_load, width = width));
+    }
+    output::line(format!("  {} {:.1} KB ({} chunks)", "Shared by all:".dimmed(), manifest.shared_bytes as f64 / 1024.0, manifest.shared_chunks.len()));
+    output::blank();
+}
+
+fn print_stats(stats: &StatsReport) {
+    output::line("📦 LARGEST PACKAGES".bold().white());
+    output::line("───────────────────".white());
+    for package in stats.packages.iter().take(10) {
+        output::line(format!("  {} - {} KB ({} modules)", package.name.cyan(), package.size_bytes / 1024, package.module_count));
+    }
+    output::line(format!("  {}", format!("Total module size: {} KB", stats.total_module_bytes / 1024).dimmed()));
+    output::blank();
+    
+    if !stats.duplicates.is_empty() {
+        output::line("👯 DUPLICATE PACKAGES".bold().yellow());
+        output::line("────────────────────".yellow());
+        for duplicate in &stats.duplicates {
+            output::line(format!("  {} - {} copies, {} KB", duplicate.name.yellow(), duplicate.locations.len(), duplicate.total_bytes / 1024));
+            for location in &duplicate.locations {
+                output::line(format!("     {} {}", "•".dimmed(), location.dimmed()));
+            }
+        }
+        output::blank();
+    }
+    
+    if !stats.tree_shaking_hints.is_empty() {
+        output::line("🌳 TREE-SHAKING HINTS".bold().green());
+        output::line("────────────────────".green());
+        for hint in &stats.tree_shaking_hints {
+            output::line(format!("  {} ({} KB)", hint.package.cyan(), hint.size_bytes / 1024));
+            output::line(format!("     💡 {}", hint.hint.dimmed()));
+        }
+        output::blank();
+    }
+}
+
+fn print_budgets(budgets: &[BudgetResult]) {
+    if budgets.is_empty() {
+        return;
+    }
+    
+    output::line("💰 BUDGETS".bold().white());
+    output::line("──────────".white());
+    
+    for budget in budgets {
+        let label = match budget.kind {
+            BudgetKind::Total => "Total".to_string(),
+            BudgetKind::Chunk => "Largest chunk".to_string(),
+            BudgetKind::ChunkType => format!("{} chunks", budget.name),
+            BudgetKind::Route => format!("Route {}", budget.name),
+        };
+        let percent = if budget.limit_bytes > 0 {
+            budget.actual_bytes as f64 / budget.limit_bytes as f64 * 100.0
+        } else {
+            0.0
+        };
+        let sizes = format!("{} KB / {} KB ({:.0}%)", budget.actual_bytes / 1024, budget.limit_bytes / 1024, percent);
+        
+        if budget.exceeded {
+            output::line(format!("  {} {} {}", "❌".red(), label.red(), sizes.red()));
+            let over: Vec<&String> = budget.chunks.iter().take(5).collect();
+            for chunk in over {
+                output::line(format!("     {} {}", "•".dimmed(), chunk.dimmed()));
+            }
+            if budget.chunks.len() > 5 {
+                output::line(format!("     {}", format!("... and {} more", budget.chunks.len() - 5).dimmed()));
+            }
+        } else {
+            output::line(format!("  {} {} {}", "✅".green(), label, sizes.dimmed()));
+        }
+    }
+    output::blank();
+}
+
+fn print_comparison(comparison: &BundleComparison) {
+    output::line(format!("📉 CHANGES SINCE {}", comparison.baseline).bold().white());
+    output::line("─────────────────".white());
+    
+    let total = format!("{} KB → {} KB ({})", comparison.total_before / 1024, comparison.total_after / 1024, format_delta(comparison.total_delta));
+    output::line(format!("  Total: {}", if comparison.total_delta > 0 { total.yellow() } else { total.green() }));
+    
+    if comparison.changes.is_empty() {
+        output::line(format!("  {}", "No chunk sizes changed".dimmed()));
+    }
+    for change in comparison.changes.iter().take(15) {
+        let status = match (change.before, change.after) {
+            (None, _) => "added".to_string(),
+            (_, None) => "removed".to_string(),
+            _ => change.delta_percent.map(|p| format!("{:+.1}%", p)).unwrap_or_default(),
+        };
+        let line = format!("{} {} {}", change.id, format_delta(change.delta_bytes), status);
+        if change.regression {
+            output::line(format!("  {} {}", "❌".red(), line.red()));
+        } else if change.delta_bytes > 0 {
+            output::line(format!("  {} {}", "⚠️".yellow(), line.yellow()));
+        } else {
+            output::line(format!("  {} {}", "✅".green(), line.green()));
+        }
+    }
+    if comparison.changes.len() > 15 {
+        output::line(format!("  {}", format!("... and {} more", comparison.changes.len() - 15).dimmed()));
+    }
+    
+    if comparison.regressions > 0 {
+        output::line(format!("  {}", format!("{} chunks grew beyond the [bundle.regression] threshold", comparison.regressions).red()));
+    }
+    output::blank();
+}
+
+fn format_delta(bytes: i64) -> String {
+    format!("{}{:.1} KB", if bytes >= 0 { "+" } else { "-" }, bytes.unsigned_abs() as f64 / 1024.0)
+}
+
+fn print_summary(summary: &BundleSummary) {
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    
+    let total_mb = summary.total_size as f64 / 1_000_000.0;
+    let total_color = if summary.total_size > 2_000_000 {
+        format!("{:.2} MB", total_mb).red()
+    } else if summary.total_size > 1_000_000 {
+        format!("{:.2} MB", total_mb).yellow()
+    } else {
+        format!("{:.2} MB", total_mb).green()
+    };
+    
+    output::line(format!("  Total bundle size: {}", total_color));
+    
+    if summary.total_compressed > 0 {
+        let compressed_mb = summary.total_compressed as f64 / 1_000_000.0;
+        output::line(format!("  Compressed size: {:.2} MB", compressed_mb));
+        output::line(format!("  Compression ratio: {:.1}%", (1.0 - summary.compression_ratio) * 100.0));
+    }
+    
+    output::line(format!("  Number of chunks: {}", summary.chunk_count));
+    
+    if let Some(ref largest) = summary.largest_chunk {
+        output::line(format!("  Largest chunk: {}", largest.cyan()));
+    }
+    
+    output::blank();
+    
+    // Performance tips
+    if summary.total_size > 1_000_000 {
+        output::line("🚀 PERFORMANCE IMPACT".bold().red());
+        output::line("────────────────────".red());
+        output::line(format!("  {} Large bundle size may impact loading performance", "⚠️".red()));
+        output::line(format!("  {} Consider implementing code splitting and lazy loading", "💡".yellow()));
+        output::blank();
+    }
+    
+    output::line("💡 TIP: Use tools like webpack-bundle-analyzer for detailed analysis".dimmed());
+}
+
+/// Detect the framework being used based on build output and package.json
+fn detect_framework(build_dir: &Path) -> Framework {
+    let project_root = build_dir.parent().unwrap_or(build_dir);
+    
+    // Check package.json for framework dependencies
+    if let Ok(package_json) = fs::read_to_string(project_root.join("package.json")) {
+        if package_json.contains("\"next\"") || package_json.contains("\"@next/") {
+            return Framework::NextJs;
+        }
+        if package_json.contains("\"@angular/") {
+            return Framework::Angular;
+        }
+        if package_json.contains("\"vue\"") && package_json.contains("\"@vue/") {
+            return Framework::Vue;
+        }
+        if package_json.contains("\"svelte\"") || package_json.contains("\"@sveltejs/") {
+            return Framework::Svelte;
+        }
+        if package_json.contains("\"vite\"") || package_json.contains("\"@vitejs/") {
+            return Framework::Vite;
+        }
+        if package_json.contains("\"react\"") {
+            return Framework::React;
+        }
+        if package_json.contains("\"webpack\"") {
+            return Framework::Webpack;
+        }
+    }
+    
+    // Check build directory structure for framework-specific patterns
+    if build_dir.join("_next").exists() || build_dir.join("server").exists() {
+        return Framework::NextJs;
+    }
+    if build_dir.join("dist").join("index.html").exists() && 
+       project_root.join("angular.json").exists() {
+        return Framework::Angular;
+    }
+    if build_dir.join("assets").exists() && 
+       (project_root.join("vite.config.js").exists() || project_root.join("vite.config.ts").exists()) {
+        return Framework::Vite;
+    }
+    
+    Framework::Unknown
+}
+
+/// Generate framework-specific recommendations
+fn generate_framework_recommendations(framework: &Framework, over_budget: bool) -> Vec<String> {
+    let mut recommendations = Vec::new();
+    
+    // Framework-specific optimization tips
+    match framework {
+        Framework::NextJs => {
+            recommendations.push("Use Next.js Image optimization for assets".to_string());
+            recommendations.push("Enable compression in next.config.js".to_string());
+            recommendations.push("Consider using Next.js dynamic imports for code splitting".to_string());
+            if over_budget {
+                recommendations.push("Use Next.js Bundle Analyzer: npm install @next/bundle-analyzer".to_string());
+            }
+        },
+        Framework::React => {
+            recommendations.push("Use React.lazy() for component-level code splitting".to_string());
+            recommendations.push("Consider using React.memo() for expensive components".to_string());
+            if over_budget {
+                recommendations.push("Use webpack-bundle-analyzer to identify large dependencies".to_string());
+            }
+        },
+        Framework::Vue => {
+            recommendations.push("Use Vue's async components for code splitting".to_string());
+            recommendations.push("Consider tree-shaking with ES modules".to_string());
+            if over_budget {
+                recommendations.push("Use Vue CLI Bundle Analyzer plugin".to_string());
+            }
+        },
+        Framework::Angular => {
+            recommendations.push("Use Angular's lazy loading for feature modules".to_string());
+            recommendations.push("Enable Angular CLI's build optimizer".to_string());
+            recommendations.push("Use OnPush change detection strategy".to_string());
+            if over_budget {
+                recommendations.push("Use Angular CLI bundle analyzer: ng build --stats-json".to_string());
+            }
+        },
+        Framework::Svelte => {
+            recommendations.push("Leverage Svelte's compile-time optimizations".to_string());
+            recommendations.push("Use SvelteKit for automatic code splitting".to_string());
+            if over_budget {
+                recommendations.push("Check for unnecessary dependencies - Svelte apps should be very small".to_string());
+            }
+        },
+        Framework::Vite => {
+            recommendations.push("Use Vite's dynamic imports for code splitting".to_string());
+            recommendations.push("Enable Vite's build optimizations".to_string());
+            if over_budget {
+                recommendations.push("Use vite-bundle-analyzer plugin".to_string());
+            }
+        },
+        Framework::Webpack => {
+            recommendations.push("Use webpack's SplitChunksPlugin for optimization".to_string());
+            recommendations.push("Enable webpack's TerserPlugin for minification".to_string());
+            if over_budget {
+                recommendations.push("Use webpack-bundle-analyzer plugin".to_string());
+            }
+        },
+        Framework::Unknown => {
+            recommendations.push("Consider implementing code splitting".to_string());
+            recommendations.push("Use tree-shaking to eliminate dead code".to_string());
+        },
+    }
+    
+    recommendations
+}
\ No newline at end of file
@@ -0,0 +1,341 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Module-level attribution from a webpack `--json` stats file or a
+/// rollup-plugin-visualizer `raw-data` file
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StatsReport {
+    pub source: StatsSource,
+    pub total_module_bytes: u64,
+    /// Largest modules first
+    pub modules: Vec<ModuleSize>,
+    /// Installed packages by total size, largest first
+    pub packages: Vec<PackageSize>,
+    /// Packages bundled from more than one install location (usually different versions)
+    pub duplicates: Vec<DuplicatePackage>,
+    pub tree_shaking_hints: Vec<TreeShakingHint>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatsSource {
+    Webpack,
+    RollupVisualizer,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleSize {
+    pub path: String,
+    pub size_bytes: u64,
+    pub package: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageSize {
+    pub name: String,
+    pub size_bytes: u64,
+    pub module_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicatePackage {
+    pub name: String,
+    /// `node_modules/...` directories the package was bundled from
+    pub locations: Vec<String>,
+    pub total_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TreeShakingHint {
+    pub package: String,
+    pub size_bytes: u64,
+    pub hint: String,
+}
+
+/// Output asset from the stats file, used as a bundle chunk
+pub struct StatsAsset {
+    pub name: String,
+    pub size_bytes: u64,
+}
+
+/// Modules kept in the report; the rest still count towards package totals
+const MAX_MODULES: usize = 50;
+
+/// CommonJS modules in a package above this size get a tree-shaking hint
+const COMMONJS_HINT_BYTES: u64 = 10 * 1024;
+
+/// Packages that are commonly imported whole, with the usual fix
+const WHOLE_PACKAGE_HINTS: &[(&str, &str)] = &[
+    ("lodash", "Use lodash-es or per-method imports (lodash/debounce) so unused functions are dropped"),
+    ("moment", "moment bundles every locale and cannot be tree-shaken; consider date-fns or dayjs"),
+    ("@mui/icons-material", "Import icons by path (@mui/icons-material/Delete) instead of from the package root"),
+    ("react-icons", "Import from the specific icon set (react-icons/fa) rather than re-exporting many sets"),
+    ("rxjs", "Import operators from rxjs directly and avoid rxjs/compat"),
+    ("aws-sdk", "Switch to the modular AWS SDK v3 clients (@aws-sdk/client-*)"),
+];
+
+/// Read a stats file and return its module report and output assets
+pub fn analyze_stats(path: &Path) -> Result<(StatsReport, Vec<StatsAsset>)> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Cannot read stats file {}", path.display()))?;
+    let stats: Value = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?;
+
+    let (source, modules, assets) = if stats.get("nodeMetas").is_some() && stats.get("nodeParts").is_some() {
+        let (modules, assets) = read_visualizer(&stats);
+        (StatsSource::RollupVisualizer, modules, assets)
+    } else if stats.get("modules").is_some() || stats.get("children").is_some() || stats.get("assets").is_some() {
+        let mut modules = Vec::new();
+        let mut assets = Vec::new();
+        read_webpack(&stats, &mut modules, &mut assets);
+        (StatsSource::Webpack, modules, assets)
+    } else {
+        return Err(anyhow!(
+            "{} is neither a webpack stats file (webpack --json) nor a rollup-plugin-visualizer raw-data file",
+            path.display()
+        ));
+    };
+
+    Ok((build_report(source, modules), assets))
+}
+
+/// A bundled module; `commonjs` is set when webpack could not concatenate it as an ES module
+struct RawModule {
+    path: String,
+    size_bytes: u64,
+    commonjs: bool,
+}
+
+fn read_webpack(stats: &Value, modules: &mut Vec<RawModule>, assets: &mut Vec<StatsAsset>) {
+    for asset in stats.get("assets").and_then(Value::as_array).into_iter().flatten() {
+        if let (Some(name), Some(size)) = (asset.get("name").and_then(Value::as_str), asset.get("size").and_then(Value::as_u64)) {
+            if name.ends_with(".js") || name.ends_with(".css") {
+                assets.push(StatsAsset { name: name.to_string(), size_bytes: size });
+            }
+        }
+    }
+    for module in stats.get("modules").and_then(Value::as_array).into_iter().flatten() {
+        read_webpack_module(module, modules);
+    }
+    // Multi-compiler stats nest one stats object per compilation
+    for child in stats.get("children").and_then(Value::as_array).into_iter().flatten() {
+        read_webpack(child, modules, assets);
+    }
+}
+
+fn read_webpack_module(module: &Value, modules: &mut Vec<RawModule>) {
+    // Concatenated modules ("./src/index.js + 12 modules") list their parts
+    if let Some(children) = module.get("modules").and_then(Value::as_array) {
+        for child in children {
+            read_webpack_module(child, modules);
+        }
+        return;
+    }
+    let Some(name) = module.get("name").and_then(Value::as_str) else { return };
+    let size_bytes = module.get("size").and_then(Value::as_u64).unwrap_or(0);
+    let commonjs = module.get("optimizationBailout").and_then(Value::as_array).into_iter().flatten()
+        .filter_map(Value::as_str)
+        .any(|reason| reason.contains("not an ECMAScript module"));
+    modules.push(RawModule {
+        path: clean_module_path(name),
+        size_bytes,
+        commonjs,
+    });
+}
+
+fn read_visualizer(stats: &Value) -> (Vec<RawModule>, Vec<StatsAsset>) {
+    let parts = stats.get("nodeParts").and_then(Value::as_object);
+    let rendered = |uid: &str| -> u64 {
+        parts.and_then(|parts| parts.get(uid))
+            .and_then(|part| part.get("renderedLength"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+    };
+
+    let mut modules = Vec::new();
+    let mut asset_sizes: BTreeMap<String, u64> = BTreeMap::new();
+    for meta in stats.get("nodeMetas").and_then(Value::as_object).into_iter().flat_map(|metas| metas.values()) {
+        let Some(id) = meta.get("id").and_then(Value::as_str) else { continue };
+        let mut size_bytes = 0;
+        for (asset, uid) in meta.get("moduleParts").and_then(Value::as_object).into_iter().flatten() {
+            let part = uid.as_str().map(rendered).unwrap_or(0);
+            *asset_sizes.entry(asset.clone()).or_insert(0) += part;
+            size_bytes += part;
+        }
+        modules.push(RawModule { path: clean_module_path(id), size_bytes, commonjs: false });
+    }
+
+    let assets = asset_sizes.into_iter()
+        .map(|(name, size_bytes)| StatsAsset { name, size_bytes })
+        .collect();
+    (modules, assets)
+}
+
+/// Drop loader prefixes and query strings; keep paths from `node_modules/` or `./` on
+fn clean_module_path(name: &str) -> String {
+    let name = name.rsplit('!').next().unwrap_or(name);
+    let name = name.split('?').next().unwrap_or(name).replace('\\', "/");
+    let name = name.trim_start_matches('\0');
+    match name.find("node_modules/") {
+        Some(index) if name.starts_with('/') || name.contains(':') => name[index..].to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// The package a module belongs to, and the directory it was installed in:
+/// `node_modules/a/node_modules/@scope/b/index.js` -> (`@scope/b`, `node_modules/a/node_modules/@scope/b`)
+fn package_of(path: &str) -> Option<(String, String)> {
+    let index = path.rfind("node_modules/")?;
+    let rest = &path[index + "node_modules/".len()..];
+    let mut segments = rest.split('/');
+    let first = segments.next()?;
+    let name = if first.starts_with('@') {
+        format!("{}/{}", first, segments.next()?)
+    } else {
+        first.to_string()
+    };
+    let location = format!("{}{}", &path[..index + "node_modules/".len()], name);
+    let location = location.trim_start_matches("./").to_string();
+    Some((name, location))
+}
+
+fn build_report(source: StatsSource, raw: Vec<RawModule>) -> StatsReport {
+    let mut packages: BTreeMap<String, (u64, usize)> = BTreeMap::new();
+    let mut locations: BTreeMap<String, BTreeMap<String, u64>> = BTreeMap::new();
+    let mut commonjs_bytes: BTreeMap<String, u64> = BTreeMap::new();
+
+    let mut modules: Vec<ModuleSize> = raw.iter()
+        .map(|module| {
+            let package = package_of(&module.path);
+            if let Some((name, location)) = &package {
+                let entry = packages.entry(name.clone()).or_insert((0, 0));
+                entry.0 += module.size_bytes;
+                entry.1 += 1;
+                *locations.entry(name.clone()).or_default().entry(location.clone()).or_insert(0) += module.size_bytes;
+                if module.commonjs {
+                    *commonjs_bytes.entry(name.clone()).or_insert(0) += module.size_bytes;
+                }
+            }
+            ModuleSize {
+                path: module.path.clone(),
+                size_bytes: module.size_bytes,
+                package: package.map(|(name, _)| name),
+            }
+        })
+        .collect();
+
+    let total_module_bytes = modules.iter().map(|m| m.size_bytes).sum();
+    modules.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.path.cmp(&b.path)));
+    modules.truncate(MAX_MODULES);
+
+    let duplicates = locations.iter()
+        .filter(|(_, found)| found.len() > 1)
+        .map(|(name, found)| DuplicatePackage {
+            name: name.clone(),
+            locations: found.keys().cloned().collect(),
+            total_bytes: found.values().sum(),
+        })
+        .collect();
+
+    let mut tree_shaking_hints = Vec::new();
+    let mut hinted = BTreeSet::new();
+    for (package, hint) in WHOLE_PACKAGE_HINTS {
+        if let Some((size, _)) = packages.get(*package) {
+            hinted.insert(package.to_string());
+            tree_shaking_hints.push(TreeShakingHint { package: package.to_string(), size_bytes: *size, hint: hint.to_string() });
+        }
+    }
+    for (package, size) in &commonjs_bytes {
+        if *size >= COMMONJS_HINT_BYTES && !hinted.contains(package) {
+            tree_shaking_hints.push(TreeShakingHint {
+                package: package.clone(),
+                size_bytes: *size,
+                hint: "Bundled as CommonJS, so unused exports cannot be removed; look for an ES module build of this package".to_string(),
+            });
+        }
+    }
+    tree_shaking_hints.sort_by_key(|hint| std::cmp::Reverse(hint.size_bytes));
+
+    let mut packages: Vec<PackageSize> = packages.into_iter()
+        .map(|(name, (size_bytes, module_count))| PackageSize { name, size_bytes, module_count })
+        .collect();
+    packages.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes).then_with(|| a.name.cmp(&b.name)));
+
+    StatsReport {
+        source,
+        total_module_bytes,
+        modules,
+        packages,
+        duplicates,
+        tree_shaking_hints,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_names() {
+        assert_eq!(package_of("./node_modules/react-dom/cjs/react-dom.production.min.js"),
+            Some(("react-dom".to_string(), "node_modules/react-dom".to_string())));
+        assert_eq!(package_of("./node_modules/a/node_modules/@scope/b/index.js"),
+            Some(("@scope/b".to_string(), "node_modules/a/node_modules/@scope/b".to_string())));
+        assert_eq!(package_of("./src/index.js"), None);
+        assert_eq!(clean_module_path("/home/me/app/node_modules/lodash/lodash.js"), "node_modules/lodash/lodash.js");
+    }
+
+    #[test]
+    fn test_webpack_stats() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("stats.json");
+        fs::write(&path, r#"{
+            "assets": [{"name": "main.3f2a1b9c.js", "size": 90000}, {"name": "main.js.map", "size": 300000}],
+            "modules": [
+                {"name": "./src/index.js + 2 modules", "size": 3000, "modules": [
+                    {"name": "./src/index.js", "size": 1000},
+                    {"name": "./node_modules/lodash-es/debounce.js", "size": 2000}
+                ]},
+                {"name": "./node_modules/lodash/lodash.js", "size": 70000, "optimizationBailout": ["ModuleConcatenation bailout: Module is not an ECMAScript module"]},
+                {"name": "./node_modules/qs/lib/index.js", "size": 12000, "optimizationBailout": ["ModuleConcatenation bailout: Module is not an ECMAScript module"]},
+                {"name": "./node_modules/react-router/node_modules/qs/lib/index.js", "size": 11000}
+            ]
+        }"#).unwrap();
+
+        let (report, assets) = analyze_stats(&path).unwrap();
+
+        assert_eq!(report.source, StatsSource::Webpack);
+        assert_eq!(assets.len(), 1);
+        assert_eq!(report.total_module_bytes, 96000);
+        assert_eq!(report.packages[0].name, "lodash");
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].locations, vec!["node_modules/qs", "node_modules/react-router/node_modules/qs"]);
+        let hinted: Vec<&str> = report.tree_shaking_hints.iter().map(|h| h.package.as_str()).collect();
+        assert_eq!(hinted, vec!["lodash", "qs"]);
+    }
+
+    #[test]
+    fn test_visualizer_stats() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("stats.json");
+        fs::write(&path, r#"{
+            "version": 2,
+            "tree": {},
+            "nodeParts": {"p1": {"renderedLength": 5000}, "p2": {"renderedLength": 800}},
+            "nodeMetas": {
+                "m1": {"id": "/app/node_modules/dayjs/dayjs.min.js", "moduleParts": {"assets/index-Bx2c9fQa.js": "p1"}},
+                "m2": {"id": "/app/src/main.ts", "moduleParts": {"assets/index-Bx2c9fQa.js": "p2"}}
+            }
+        }"#).unwrap();
+
+        let (report, assets) = analyze_stats(&path).unwrap();
+
+        assert_eq!(report.source, StatsSource::RollupVisualizer);
+        assert_eq!(assets[0].size_bytes, 5800);
+        assert_eq!(report.packages[0].name, "dayjs");
+    }
+}
@@ -0,0 +1,559 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::{CiConfig, CiStep};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+use super::docker::{node_version, satisfies};
+use super::init::PackageManager;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CiReport {
+    pub workflows: Vec<Workflow>,
+    pub package_manager: PackageManager,
+    /// `engines.node` in package.json
+    pub engines_node: Option<String>,
+    /// The version in `.nvmrc` or `.node-version`
+    pub nvmrc: Option<String>,
+    /// The workflow that runs each required step, if any does
+    pub steps: BTreeMap<CiStep, Option<String>>,
+    pub issues: Vec<CiIssue>,
+    pub summary: CiSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Workflow {
+    pub path: String,
+    pub provider: CiProvider,
+    /// Node.js versions set with `node-version`, a matrix or a `node` image
+    pub node_versions: Vec<String>,
+    pub installs: bool,
+    pub cached: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CiProvider {
+    Github,
+    Gitlab,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CiIssue {
+    pub kind: CiIssueKind,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CiIssueKind {
+    /// No workflow builds, type-checks, tests or runs sniff
+    MissingStep,
+    /// Packages are installed without a cache, or with the cache of another package manager
+    NoCache,
+    /// A Node.js version outside `engines.node` or different from `.nvmrc`
+    NodeVersion,
+}
+
+impl CiIssueKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            CiIssueKind::MissingStep => "SNF-CI-MISSING-STEP",
+            CiIssueKind::NoCache => "SNF-CI-NO-CACHE",
+            CiIssueKind::NodeVersion => "SNF-CI-NODE-VERSION",
+        }
+    }
+
+    fn level(self) -> AnnotationLevel {
+        match self {
+            CiIssueKind::NodeVersion => AnnotationLevel::Error,
+            CiIssueKind::MissingStep => AnnotationLevel::Warning,
+            CiIssueKind::NoCache => AnnotationLevel::Notice,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CiSummary {
+    pub workflows: usize,
+    pub missing_steps: usize,
+    pub total_issues: usize,
+    /// Issues dropped by `[[ignore]]` entries
+    pub ignored: usize,
+}
+
+impl Annotate for CiReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            Annotation::new(issue.kind.level(), &issue.file, issue.line, format!("{:?}", issue.kind), &issue.message)
+                .with_rule(issue.kind.rule_id())
+        }).collect()
+    }
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<CiReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!("Running CI workflow check");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.ci)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "ci",
+        report,
+        report.summary.workflows,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(issues = report.summary.total_issues, "CI workflow check completed");
+
+    Ok(outcome)
+}
+
+/// Check the GitHub Actions workflows and `.gitlab-ci.yml`. Node.js versions outside `engines.node`
+/// or different from `.nvmrc` fail the check; missing steps and caches are warnings. Projects
+/// without CI configuration are an error, which `sniff deploy` reports as a skipped check
+pub fn check(target: &ScanTarget, settings: &CiConfig) -> Result<CommandOutcome<CiReport>> {
+    let project_dir = target.project_dir("ci")?;
+    let files = find_workflows(project_dir);
+    if files.is_empty() {
+        return Err(anyhow!("No .github/workflows or .gitlab-ci.yml in {}", project_dir.display()));
+    }
+
+    let package: Value = fs::read_to_string(project_dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(Value::Null);
+    let scripts: BTreeMap<String, String> = package["scripts"].as_object().into_iter().flatten()
+        .filter_map(|(name, script)| script.as_str().map(|script| (name.clone(), script.to_string())))
+        .collect();
+    let engines_node = package["engines"]["node"].as_str().map(str::to_string);
+    let nvmrc = [".nvmrc", ".node-version"].iter()
+        .find_map(|name| fs::read_to_string(project_dir.join(name)).ok())
+        .map(|content| content.trim().to_string())
+        .filter(|version| !version.is_empty());
+    let package_manager = PackageManager::detect(project_dir);
+
+    let mut workflows = Vec::new();
+    let mut issues = Vec::new();
+    let mut steps: BTreeMap<CiStep, Option<String>> = settings.required_steps.iter().map(|step| (*step, None)).collect();
+    for (path, provider) in &files {
+        let content = fs::read_to_string(path)?;
+        let file = paths::display(path);
+        let parsed = parse(&content);
+        let mut issue = |kind, line, message: String| issues.push(CiIssue { kind, file: file.clone(), line, message });
+
+        for (line, command) in &parsed.commands {
+            for expanded in expand(command, &scripts, 0) {
+                for (step, found) in steps.iter_mut().filter(|(_, found)| found.is_none()) {
+                    if runs_step(*step, &expanded) {
+                        *found = Some(format!("{}:{}", file, line));
+                    }
+                }
+            }
+        }
+
+        for version in &parsed.node_versions {
+            if let Some(range) = &engines_node {
+                if satisfies(&version.value, range) == Some(false) {
+                    issue(CiIssueKind::NodeVersion, version.line,
+                        format!("Runs Node.js {}, outside engines.node \"{}\" in package.json", version.value, range));
+                    continue;
+                }
+            }
+            // A matrix tests several versions on purpose; a single version should be the one developers use
+            let pinned = nvmrc.as_deref().and_then(major);
+            if let (false, Some(pinned), Some(used)) = (version.in_list, pinned, major(&version.value)) {
+                if pinned != used {
+                    issue(CiIssueKind::NodeVersion, version.line,
+                        format!("Runs Node.js {}, but .nvmrc pins {}", version.value, nvmrc.as_deref().unwrap_or_default()));
+                }
+            }
+        }
+
+        let install = parsed.commands.iter().find(|(_, command)| installs_packages(command));
+        if let Some((line, _)) = install {
+            let expected = cache_name(package_manager);
+            match &parsed.setup_node_cache {
+                Some((cache_line, cache)) if expected.is_some_and(|expected| expected != cache) => {
+                    issue(CiIssueKind::NoCache, *cache_line,
+                        format!("setup-node caches {}, but the project uses {}", cache, expected.unwrap_or_default()));
+                }
+                _ if !parsed.cached => issue(CiIssueKind::NoCache, *line,
+                    "Installs packages without a cache; set `cache` on actions/setup-node or add a `cache:` entry".to_string()),
+                _ => {}
+            }
+        }
+
+        workflows.push(Workflow {
+            path: file.clone(),
+            provider: *provider,
+            node_versions: parsed.node_versions.iter().map(|version| version.value.clone()).collect(),
+            installs: install.is_some(),
+            cached: parsed.cached,
+        });
+    }
+
+    let first = workflows[0].path.clone();
+    for (step, found) in &steps {
+        if found.is_none() {
+            issues.push(CiIssue {
+                kind: CiIssueKind::MissingStep,
+                file: first.clone(),
+                line: 1,
+                message: format!("No workflow {}, e.g. `{}`", step.describe(), package_manager.run_script(step.script())),
+            });
+        }
+    }
+
+    let ignored = target.ignores()?.retain(&mut issues, |issue| (issue.kind.rule_id(), &issue.file));
+    issues.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    let summary = CiSummary {
+        workflows: workflows.len(),
+        missing_steps: steps.values().filter(|found| found.is_none()).count(),
+        total_issues: issues.len(),
+        ignored,
+    };
+    let failed = issues.iter().any(|issue| issue.kind == CiIssueKind::NodeVersion);
+    let exit_code = check_failure_threshold(failed, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(CiReport { workflows, package_manager, engines_node, nvmrc, steps, issues, summary }, exit_code))
+}
+
+/// `.github/workflows/*.yml` and `*.yaml`, then `.gitlab-ci.yml`
+fn find_workflows(project_dir: &Path) -> Vec<(PathBuf, CiProvider)> {
+    let mut workflows: Vec<(PathBuf, CiProvider)> = fs::read_dir(project_dir.join(".github/workflows")).into_iter().flatten().flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|extension| extension == "yml" || extension == "yaml"))
+        .map(|path| (path, CiProvider::Github))
+        .collect();
+    workflows.sort_by(|a, b| a.0.cmp(&b.0));
+    let gitlab = project_dir.join(".gitlab-ci.yml");
+    if gitlab.is_file() {
+        workflows.push((gitlab, CiProvider::Gitlab));
+    }
+    workflows
+}
+
+/// What the checks need from a workflow
+#[derive(Debug, Default)]
+struct Parsed {
+    /// Shell commands of `run:` steps and GitLab `script:` sections, with their line
+    commands: Vec<(usize, String)>,
+    node_versions: Vec<NodeVersion>,
+    /// `cache:` on setup-node or a GitLab job, or an actions/cache step
+    cached: bool,
+    /// The package manager setup-node caches for
+    setup_node_cache: Option<(usize, String)>,
+}
+
+#[derive(Debug)]
+struct NodeVersion {
+    value: String,
+    line: usize,
+    /// One of several versions of a matrix
+    in_list: bool,
+}
+
+/// Keys whose value, or indented block, is shell commands
+const COMMAND_KEYS: &[&str] = &["run", "script", "before_script", "after_script"];
+/// Keys holding Node.js versions, in a setup-node step or a matrix
+const VERSION_KEYS: &[&str] = &["node-version", "node_version", "node"];
+/// Keys holding a container image, which may be `node:<version>`
+const IMAGE_KEYS: &[&str] = &["image", "container", "name"];
+
+/// A line-based reading of the YAML: enough for keys, scalars, flow lists and indented blocks
+fn parse(content: &str) -> Parsed {
+    let mut parsed = Parsed::default();
+    // The key whose indented block is being read, and its indent
+    let mut block: Option<(String, usize)> = None;
+    for (index, raw) in content.lines().enumerate() {
+        let line = index + 1;
+        let text = strip_comment(raw);
+        if text.trim().is_empty() {
+            continue;
+        }
+        let mut indent = text.len() - text.trim_start().len();
+        let mut item = text.trim();
+        let mut list_item = false;
+        while let Some(rest) = item.strip_prefix("- ").or_else(|| (item == "-").then_some("")) {
+            indent += item.len() - rest.len();
+            item = rest.trim_start();
+            list_item = true;
+        }
+
+        if let Some((key, block_indent)) = &block {
+            if indent > *block_indent || (list_item && indent >= *block_indent && !item.contains(": ")) {
+                let value = unquote(item);
+                if COMMAND_KEYS.contains(&key.as_str()) {
+                    parsed.commands.push((line, value.to_string()));
+                } else if VERSION_KEYS.contains(&key.as_str()) && list_item {
+                    push_version(&mut parsed, value, line, true);
+                }
+                continue;
+            }
+            block = None;
+        }
+
+        let Some((key, value)) = split_key(item) else { continue };
+        let value = value.trim();
+        if value.is_empty() || value.starts_with('|') || value.starts_with('>') {
+            if key == "cache" {
+                parsed.cached = true;
+            } else if COMMAND_KEYS.contains(&key) || VERSION_KEYS.contains(&key) {
+                block = Some((key.to_string(), indent));
+            }
+            continue;
+        }
+
+        let values: Vec<&str> = match value.strip_prefix('[').and_then(|list| list.strip_suffix(']')) {
+            Some(list) => list.split(',').map(|value| unquote(value.trim())).filter(|value| !value.is_empty()).collect(),
+            None => vec![unquote(value)],
+        };
+        match key {
+            _ if COMMAND_KEYS.contains(&key) => parsed.commands.extend(values.iter().map(|value| (line, value.to_string()))),
+            _ if VERSION_KEYS.contains(&key) => {
+                let in_list = values.len() > 1 || value.starts_with('[');
+                for value in values {
+                    push_version(&mut parsed, value, line, in_list);
+                }
+            }
+            _ if IMAGE_KEYS.contains(&key) => {
+                if let Some(version) = node_version(values[0]) {
+                    parsed.node_versions.push(NodeVersion { value: version, line, in_list: false });
+                }
+            }
+            "uses" if value.starts_with("actions/cache") => parsed.cached = true,
+            "cache" if !values[0].is_empty() && values[0] != "false" => {
+                parsed.cached = true;
+                if matches!(values[0], "npm" | "yarn" | "pnpm") {
+                    parsed.setup_node_cache = Some((line, values[0].to_string()));
+                }
+            }
+            _ => {}
+        }
+    }
+    parsed
+}
+
+/// Versions from expressions (`${{ matrix.node }}`) and aliases (`lts/*`) cannot be checked
+fn push_version(parsed: &mut Parsed, value: &str, line: usize, in_list: bool) {
+    if value.starts_with(|c: char| c.is_ascii_digit() || c == 'v') && !value.contains("${{") {
+        parsed.node_versions.push(NodeVersion { value: value.trim_start_matches('v').to_string(), line, in_list });
+    }
+}
+
+/// `key: value` or `key:`, where the key is a plain YAML key
+fn split_key(item: &str) -> Option<(&str, &str)> {
+    let (key, value) = item.split_once(':')?;
+    let key = unquote(key.trim());
+    let plain = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    (plain && (value.is_empty() || value.starts_with(' '))).then_some((key, value))
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim().trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// The line without a trailing ` # comment`, unless the `#` is inside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut previous = ' ';
+    for (index, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(open), _) if c == open => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..index],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+/// `command` and the package.json scripts it runs, followed a few levels deep
+fn expand(command: &str, scripts: &BTreeMap<String, String>, depth: usize) -> Vec<String> {
+    let mut expanded = vec![command.to_string()];
+    if depth >= 3 {
+        return expanded;
+    }
+    let words: Vec<&str> = command.split(|c: char| c.is_whitespace() || c == ';' || c == '&' || c == '|').filter(|word| !word.is_empty()).collect();
+    for pair in words.windows(2) {
+        let script = match pair {
+            ["npm" | "pnpm" | "yarn" | "bun", "test" | "t"] => Some("test"),
+            ["run" | "run-script", name] | ["pnpm" | "yarn", name] => Some(*name),
+            _ => None,
+        };
+        if let Some(body) = script.and_then(|script| scripts.get(script)) {
+            expanded.extend(expand(body, scripts, depth + 1));
+        }
+    }
+    expanded
+}
+
+fn runs_step(step: CiStep, command: &str) -> bool {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    let has = |candidates: &[&str]| words.iter().any(|word| candidates.contains(word));
+    let follows = |first: &[&str], second: &[&str]| words.windows(2).any(|pair| first.contains(&pair[0]) && second.contains(&pair[1]));
+    match step {
+        CiStep::Build => follows(&["next", "vite", "nuxt", "nuxi", "astro", "remix", "svelte-kit"], &["build"])
+            || follows(&["run", "pnpm", "yarn"], &["build"]) || has(&["webpack", "tsup", "rollup"]),
+        CiStep::Typecheck => has(&["tsc", "vue-tsc", "svelte-check"]) || follows(&["sniff", "sniff-check"], &["types"])
+            || follows(&["astro"], &["check"])
+            || words.iter().any(|word| ["typecheck", "type-check", "check-types", "types:check", "tsc:check"].contains(&word.trim_matches(['"', '\'']))),
+        // npm's placeholder script fails on purpose and tests nothing
+        CiStep::Test => !command.contains("no test specified")
+            && (has(&["jest", "vitest", "mocha", "ava", "playwright", "cypress", "test"]) || follows(&["node", "bun"], &["--test", "test"])),
+        CiStep::Sniff => has(&["sniff", "sniff-check"]) || words.iter().any(|word| word.starts_with("sniff-check@")),
+    }
+}
+
+fn installs_packages(command: &str) -> bool {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    words.windows(2).any(|pair| matches!(pair, ["npm", "ci" | "install" | "i"] | ["pnpm" | "yarn" | "bun", "install" | "i"]))
+        || words == ["yarn"]
+}
+
+/// The `cache` value of actions/setup-node for the package manager
+fn cache_name(package_manager: PackageManager) -> Option<&'static str> {
+    match package_manager {
+        PackageManager::Npm => Some("npm"),
+        PackageManager::Yarn => Some("yarn"),
+        PackageManager::Pnpm => Some("pnpm"),
+        PackageManager::Bun => None,
+    }
+}
+
+/// `20` of `20.11.1`, `v20` or `20.x`; `None` for aliases such as `lts/*`
+fn major(version: &str) -> Option<u64> {
+    version.trim().trim_start_matches('v').split('.').next()?.parse().ok()
+}
+
+fn print_report(report: &CiReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("⚙️  CI Workflow Report".bold().blue());
+        output::line("=====================".blue());
+        for workflow in &report.workflows {
+            let versions = if workflow.node_versions.is_empty() { "Node.js version not set".to_string() } else { format!("Node.js {}", workflow.node_versions.join(", ")) };
+            let cache = if !workflow.installs { "" } else if workflow.cached { ", cached" } else { ", no cache" };
+            output::line(format!("  {}: {}{}", workflow.path.cyan(), versions.dimmed(), cache.dimmed()));
+        }
+        output::blank();
+    }
+
+    output::line("✔️  STEPS".bold().white());
+    output::line("────────".white());
+    for (step, found) in &report.steps {
+        match found {
+            Some(location) => output::line(format!("  {} {:<10} {}", "✓".green(), step.script(), location.dimmed())),
+            None => output::line(format!("  {} {:<10} {}", "✗".red(), step.script(), "not run".red())),
+        }
+    }
+    output::blank();
+
+    if report.issues.is_empty() {
+        output::line("✅ No CI issues found!".green());
+    } else {
+        output::line("🔍 ISSUES".bold().yellow());
+        output::line("─────────".yellow());
+        for issue in &report.issues {
+            let rule = match issue.kind.level() {
+                AnnotationLevel::Critical | AnnotationLevel::Error => issue.kind.rule_id().red(),
+                AnnotationLevel::Warning => issue.kind.rule_id().yellow(),
+                AnnotationLevel::Notice => issue.kind.rule_id().dimmed(),
+            };
+            output::line(format!("  {}:{} - {} {}", issue.file.cyan(), issue.line, rule, issue.message));
+        }
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Workflows: {}", summary.workflows));
+    output::line(format!("  Missing steps: {}", summary.missing_steps));
+    output::line(format!("  Issues: {}", summary.total_issues));
+    if summary.ignored > 0 {
+        output::line(format!("  Ignored: {}", summary.ignored.to_string().dimmed()));
+    }
+    output::blank();
+    output::line("💡 TIP: `node-version-file: .nvmrc` keeps CI on the version developers use".dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workflows_are_read() {
+        let github = r#"name: CI
+on: [push]
+jobs:
+  test:
+    runs-on: ubuntu-latest
+    strategy:
+      matrix:
+        node: [18.x, 20.x]
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: ${{ matrix.node }}
+          cache: 'npm'   # the lockfile is package-lock.json
+      - run: npm ci
+      - name: Checks
+        run: |
+          npm run lint
+          npx tsc --noEmit
+  deploy:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-node@v4
+        with:
+          node-version: "20"
+"#;
+        let parsed = parse(github);
+        let commands: Vec<(usize, &str)> = parsed.commands.iter().map(|(line, command)| (*line, command.as_str())).collect();
+        assert_eq!(commands, vec![(15, "npm ci"), (18, "npm run lint"), (19, "npx tsc --noEmit")]);
+        let versions: Vec<(&str, usize, bool)> = parsed.node_versions.iter().map(|v| (v.value.as_str(), v.line, v.in_list)).collect();
+        assert_eq!(versions, vec![("18.x", 8, true), ("20.x", 8, true), ("20", 25, false)]);
+        assert!(parsed.cached);
+        assert_eq!(parsed.setup_node_cache, Some((14, "npm".to_string())));
+
+        let gitlab = "image: node:18-alpine\n\ncache:\n  paths:\n    - node_modules/\n\ntest:\n  script:\n    - npm ci\n    - npm test\n  after_script: echo done\n";
+        let parsed = parse(gitlab);
+        let commands: Vec<&str> = parsed.commands.iter().map(|(_, command)| command.as_str()).collect();
+        assert_eq!(commands, vec!["npm ci", "npm test", "echo done"]);
+        assert_eq!(parsed.node_versions[0].value, "18");
+        assert!(parsed.cached);
+    }
+
+    #[test]
+    fn test_steps_are_found_through_scripts() {
+        let scripts: BTreeMap<String, String> = [
+            ("ci", "npm run typecheck && npm test"),
+            ("typecheck", "tsc --noEmit"),
+            ("test", "vitest run"),
+        ].iter().map(|(name, script)| (name.to_string(), script.to_string())).collect();
+        let expanded = expand("npm run ci", &scripts, 0);
+        assert!(expanded.iter().any(|command| runs_step(CiStep::Typecheck, command)));
+        assert!(expanded.iter().any(|command| runs_step(CiStep::Test, command)));
+        assert!(!expanded.iter().any(|command| runs_step(CiStep::Build, command)));
+
+        assert!(runs_step(CiStep::Build, "pnpm build"));
+        assert!(runs_step(CiStep::Sniff, "npx sniff-check deploy --format github"));
+        assert!(!runs_step(CiStep::Test, "echo \"Error: no test specified\" && exit 1"));
+        assert_eq!(major("v20.11.1"), Some(20));
+        assert_eq!(major("lts/*"), None);
+    }
+}
@@ -0,0 +1,389 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::utils::FileUtils;
+use crate::config::{Config, ComplexityConfig};
+use crate::common::function_parser::{find_functions, FunctionKind, FunctionSpan};
+use crate::common::source::{blank_strings, strip_comments};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ComplexityReport {
+    /// Functions over either threshold, most complex first
+    pub functions: Vec<FunctionComplexity>,
+    pub summary: ComplexitySummary,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FunctionComplexity {
+    pub path: String,
+    pub name: String,
+    pub kind: FunctionKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub lines: usize,
+    pub cyclomatic: usize,
+    pub cognitive: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ComplexitySummary {
+    pub files_scanned: usize,
+    pub functions: usize,
+    pub average_cyclomatic: f64,
+    pub average_cognitive: f64,
+    pub max_cognitive: usize,
+    pub cyclomatic_threshold: usize,
+    pub cognitive_threshold: usize,
+    pub over_threshold: usize,
+    /// Share of functions within both thresholds, in percent
+    pub score: f64,
+}
+
+pub async fn run(target: &ScanTarget, cyclomatic: Option<usize>, cognitive: Option<usize>, json: bool, quiet: bool) -> Result<CommandOutcome<ComplexityReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running complexity analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.complexity, cyclomatic, cognitive, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "complexity",
+        report,
+        report.summary.files_scanned,
+        report.summary.over_threshold,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = response.summary.issues_found == 0, "complexity analysis completed");
+
+    Ok(outcome)
+}
+
+/// Measure every function and report those over the cyclomatic or cognitive threshold.
+/// `cyclomatic`/`cognitive` override the `[complexity]` thresholds.
+pub fn check(target: &ScanTarget, config: &ComplexityConfig, cyclomatic: Option<usize>, cognitive: Option<usize>, quiet: bool) -> Result<CommandOutcome<ComplexityReport>> {
+    let config = ComplexityConfig {
+        cyclomatic: cyclomatic.unwrap_or(config.cyclomatic),
+        cognitive: cognitive.unwrap_or(config.cognitive),
+        ..config.clone()
+    };
+    let files = source_files(FileScanner::for_command(target, "complexity")?.find_target_files(target, &["ts", "tsx", "js", "jsx"]), &target.root);
+    let mut functions = measure_files(&files, quiet)?;
+    let ignores = target.ignores()?;
+    functions.retain(|function| !is_over(function, &config) || !ignores.ignores("SNF-COMPLEXITY-001", &function.path));
+    let summary = summarize_functions(&functions, files.len(), &config);
+
+    let mut offenders: Vec<FunctionComplexity> = functions.into_iter()
+        .filter(|function| is_over(function, &config))
+        .collect();
+    offenders.sort_by(|a, b| b.cognitive.cmp(&a.cognitive).then(b.cyclomatic.cmp(&a.cyclomatic)).then(a.path.cmp(&b.path)));
+    offenders.truncate(config.max_reported);
+
+    let exit_code = check_failure_threshold(summary.over_threshold > 0, ExitCode::ThresholdExceeded);
+    Ok(CommandOutcome::new(ComplexityReport { functions: offenders, summary }, exit_code))
+}
+
+/// Aggregate complexity of every function under `root`, for `sniff context`
+pub fn summarize(root: &Path) -> Result<ComplexitySummary> {
+    let config = Config::load_from_dir(root)?.complexity;
+    let files = source_files(FileScanner::for_root(root)?.find_files_with_extensions(root, &["ts", "tsx", "js", "jsx"]), root);
+    let functions = measure_files(&files, true).unwrap_or_default();
+    Ok(summarize_functions(&functions, files.len(), &config))
+}
+
+fn source_files(files: Vec<PathBuf>, root: &Path) -> Vec<PathBuf> {
+    files.into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(root).unwrap_or(path)))
+        .filter(|path| !path.to_string_lossy().ends_with(".d.ts"))
+        .collect()
+}
+
+fn measure_files(files: &[PathBuf], quiet: bool) -> Result<Vec<FunctionComplexity>> {
+    let results: Vec<Vec<FunctionComplexity>> = FileUtils::process_files_parallel(
+        files,
+        |path| {
+            let content = fs::read_to_string(path)?;
+            Ok(measure_functions(&content, &paths::display(path)))
+        },
+        "Measuring complexity",
+        quiet
+    )?;
+    Ok(results.into_iter().flatten().collect())
+}
+
+fn is_over(function: &FunctionComplexity, config: &ComplexityConfig) -> bool {
+    function.cyclomatic > config.cyclomatic || function.cognitive > config.cognitive
+}
+
+fn summarize_functions(functions: &[FunctionComplexity], files_scanned: usize, config: &ComplexityConfig) -> ComplexitySummary {
+    let count = functions.len();
+    let average = |value: fn(&FunctionComplexity) -> usize| {
+        if count == 0 { 0.0 } else { functions.iter().map(value).sum::<usize>() as f64 / count as f64 }
+    };
+    let over_threshold = functions.iter().filter(|function| is_over(function, config)).count();
+    ComplexitySummary {
+        files_scanned,
+        functions: count,
+        average_cyclomatic: average(|function| function.cyclomatic),
+        average_cognitive: average(|function| function.cognitive),
+        max_cognitive: functions.iter().map(|function| function.cognitive).max().unwrap_or(0),
+        cyclomatic_threshold: config.cyclomatic,
+        cognitive_threshold: config.cognitive,
+        over_threshold,
+        score: if count == 0 { 100.0 } else { (count - over_threshold) as f64 * 100.0 / count as f64 },
+    }
+}
+
+/// Complexity of each function in `content`. Lines of nested functions count
+/// towards the nested function only.
+fn measure_functions(content: &str, path: &str) -> Vec<FunctionComplexity> {
+    let code = blank_strings(&strip_comments(content));
+    let lines: Vec<&str> = code.lines().collect();
+    let spans = find_functions(content);
+
+    spans.iter().map(|span| {
+        let nested: Vec<&FunctionSpan> = spans.iter()
+            .filter(|other| other.start_line > span.start_line && other.end_line <= span.end_line)
+            .collect();
+        let body: Vec<&str> = (span.start_line..=span.end_line)
+            .filter(|line| !nested.iter().any(|other| (other.start_line..=other.end_line).contains(line)))
+            .filter_map(|line| lines.get(line - 1).copied())
+            .collect();
+        let (cyclomatic, cognitive) = measure(&body.join("\n"));
+        FunctionComplexity {
+            path: path.to_string(),
+            name: span.name.clone(),
+            kind: span.kind.clone(),
+            start_line: span.start_line,
+            end_line: span.end_line,
+            lines: span.lines,
+            cyclomatic,
+            cognitive,
+        }
+    }).collect()
+}
+
+/// A `{` the scanner has entered
+struct Block {
+    /// The body of a branch, loop, `catch` or callback: code inside is one level deeper
+    nests: bool,
+    /// The body of `do { } while (...)`, whose `while` is not a second loop
+    is_do: bool,
+}
+
+/// Cyclomatic complexity (1 + branches and boolean operators) and cognitive complexity
+/// (branches weighted by nesting, plus `else` and each change of boolean operator)
+/// of code with comments and strings blanked out
+fn measure(code: &str) -> (usize, usize) {
+    let bytes = code.as_bytes();
+    let is_ident = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$';
+    let next_word = |from: usize| {
+        let rest = code[from..].trim_start();
+        let end = rest.bytes().position(|byte| !is_ident(byte)).unwrap_or(rest.len());
+        &rest[..end]
+    };
+
+    let mut cyclomatic = 1;
+    let mut cognitive = 0;
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut parens = 0usize;
+    // Paren depth at which the next `{` opens a nesting body
+    let mut pending: Option<(usize, bool)> = None;
+    let mut after_else = false;
+    let mut after_do = false;
+    let mut last_operator: Option<&str> = None;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let nesting = blocks.iter().filter(|block| block.nests).count();
+
+        if is_ident(byte) && (i == 0 || !(is_ident(bytes[i - 1]) || bytes[i - 1] == b'.')) {
+            let word = next_word(i);
+            match word {
+                "if" => {
+                    cyclomatic += 1;
+                    cognitive += if after_else { 1 } else { 1 + nesting };
+                    pending = Some((parens, false));
+                }
+                "else" if next_word(i + word.len()) != "if" => {
+                    cognitive += 1;
+                    pending = Some((parens, false));
+                }
+                "for" | "switch" => {
+                    cyclomatic += usize::from(word == "for");
+                    cognitive += 1 + nesting;
+                    pending = Some((parens, false));
+                }
+                "while" if !after_do => {
+                    cyclomatic += 1;
+                    cognitive += 1 + nesting;
+                    pending = Some((parens, false));
+                }
+                "do" => {
+                    cyclomatic += 1;
+                    cognitive += 1 + nesting;
+                    pending = Some((parens, true));
+                }
+                "catch" => {
+                    cyclomatic += 1;
+                    cognitive += 1 + nesting;
+                    pending = Some((parens, false));
+                }
+                "case" => cyclomatic += 1,
+                _ => {}
+            }
+            after_else = word == "else";
+            after_do = false;
+            i += word.len();
+            continue;
+        }
+
+        let pair = bytes.get(i..i + 2);
+        match byte {
+            b'&' | b'|' | b'?' if pair.is_some_and(|pair| pair[0] == pair[1]) => {
+                let operator = &code[i..i + 2];
+                cyclomatic += 1;
+                if last_operator != Some(operator) {
+                    cognitive += 1;
+                }
+                last_operator = Some(operator);
+                i += 2;
+                continue;
+            }
+            // Ternary, but not `?.`, optional parameters (`x?: T`, `x?)`) or `??`
+            b'?' if !matches!(bytes.get(i + 1), Some(b'.' | b':' | b')' | b',' | b'=')) => {
+                cyclomatic += 1;
+                cognitive += 1 + nesting;
+            }
+            b'=' if bytes.get(i + 1) == Some(&b'>') => {
+                pending = Some((parens, false));
+                i += 2;
+                continue;
+            }
+            b'(' => parens += 1,
+            b')' => parens = parens.saturating_sub(1),
+            b'{' => {
+                let opens_body = pending.filter(|(depth, _)| *depth == parens);
+                if opens_body.is_some() {
+                    pending = None;
+                }
+                // The first block is the function's own body, even for arrow functions
+                let nests = opens_body.is_some() && !blocks.is_empty();
+                blocks.push(Block { nests, is_do: opens_body.is_some_and(|(_, is_do)| is_do) });
+                last_operator = None;
+            }
+            b'}' => {
+                after_do = blocks.pop().is_some_and(|block| block.is_do);
+                last_operator = None;
+                i += 1;
+                continue;
+            }
+            b';' => {
+                pending = None;
+                last_operator = None;
+            }
+            _ => {}
+        }
+        if !byte.is_ascii_whitespace() {
+            after_else = false;
+            after_do = false;
+        }
+        i += 1;
+    }
+
+    (cyclomatic, cognitive)
+}
+
+impl Annotate for ComplexityReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.functions.iter().map(|function| {
+            Annotation::new(
+                AnnotationLevel::Warning,
+                &function.path,
+                function.start_line,
+                "Complex function",
+                format!(
+                    "`{}` has cyclomatic complexity {} and cognitive complexity {} (limits {} and {}); consider splitting it",
+                    function.name, function.cyclomatic, function.cognitive, self.summary.cyclomatic_threshold, self.summary.cognitive_threshold
+                ),
+            )
+            .with_end_line(function.end_line)
+            .with_rule("SNF-COMPLEXITY-001")
+        }).collect()
+    }
+}
+
+fn print_report(report: &ComplexityReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🧠 Complexity Report".bold().blue());
+        output::line("====================".blue());
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    if report.functions.is_empty() {
+        output::line(format!(
+            "✅ No function exceeds cyclomatic {} or cognitive {} complexity.",
+            summary.cyclomatic_threshold, summary.cognitive_threshold
+        ).green());
+    } else {
+        output::line("⚠️  MOST COMPLEX FUNCTIONS".bold().yellow());
+        for function in &report.functions {
+            let cognitive = format!("cognitive {}", function.cognitive);
+            let cognitive = if function.cognitive > summary.cognitive_threshold { cognitive.red() } else { cognitive.normal() };
+            let cyclomatic = format!("cyclomatic {}", function.cyclomatic);
+            let cyclomatic = if function.cyclomatic > summary.cyclomatic_threshold { cyclomatic.red() } else { cyclomatic.normal() };
+            output::line(format!("  {}  {} · {}", function.name.bold(), cognitive, cyclomatic));
+            output::line(format!("     {}:{}-{} {}", function.path.cyan(), function.start_line, function.end_line, format!("({} lines)", function.lines).dimmed()));
+        }
+        if summary.over_threshold > report.functions.len() {
+            output::line(format!("  {}", format!("... and {} more", summary.over_threshold - report.functions.len()).dimmed()));
+        }
+    }
+    output::blank();
+
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}  Functions: {}", summary.files_scanned, summary.functions));
+    output::line(format!("  Average complexity: cyclomatic {:.1}, cognitive {:.1} (max {})", summary.average_cyclomatic, summary.average_cognitive, summary.max_cognitive));
+    output::line(format!("  Over thresholds: {} ({:.0}% of functions within limits)", summary.over_threshold, summary.score));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure_branches_and_nesting() {
+        // if (+1), for nested once (+2), if nested twice (+3), else (+1), && then || (+2)
+        let code = "function f(items, a, b, c) {\n  if (a && b || c) {\n    for (const item of items) {\n      if (item) {\n        return 1;\n      } else {\n        return 2;\n      }\n    }\n  }\n}";
+        assert_eq!(measure(code), (6, 9));
+
+        // `else if` is not nested, `?.` and optional parameters are not ternaries
+        let code = "function g(x?: number) {\n  if (x) { return a?.b; } else if (y) { return 1; }\n  return x ? 1 : 2;\n}";
+        assert_eq!(measure(code), (4, 3));
+
+        // do/while is one loop; a callback body nests
+        let code = "function h() {\n  do { n++; } while (n < 3);\n  items.forEach((item) => {\n    if (item) { n++; }\n  });\n}";
+        assert_eq!(measure(code), (3, 3));
+    }
+
+    #[test]
+    fn test_nested_functions_are_measured_separately() {
+        let content = "export function Outer(props) {\n  const handle = (event) => {\n    if (event) {\n      return 1;\n    }\n  };\n  return props.a ? 1 : 2;\n}\n";
+        let functions = measure_functions(content, "a.tsx");
+        assert_eq!(functions.len(), 2);
+        assert_eq!((functions[0].name.as_str(), functions[0].cyclomatic, functions[0].cognitive), ("Outer", 2, 1));
+        assert_eq!((functions[1].name.as_str(), functions[1].cyclomatic, functions[1].cognitive), ("handle", 2, 1));
+    }
+}
@@ -1,13 +1,20 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
 use std::sync::OnceLock;
 use walkdir::WalkDir;
-use crate::config::Config;
-use crate::common::{ExitCode, check_failure_threshold, init_command, complete_command, create_standard_json_output, output_result};
+use crate::config::{Config, CountMode};
+use crate::utils::FileUtils;
+use crate::common::function_parser::{find_functions, FunctionKind};
+use crate::common::jsx::{expression_end, jsx_elements, AttrValue};
+use crate::common::source::{blank_strings, strip_comments};
+use crate::common::report_formatter::{Row, print_listing};
+use crate::common::{output, paths, Annotate, Annotation, AnnotationLevel, ExitCode, CommandOutcome, ListingOptions, ScanTarget, Severity, check_failure_threshold, create_annotated_json_output, output_result};
 
 struct ComponentPatterns {
     hook_patterns: Vec<Regex>,
@@ -16,6 +23,16 @@ struct ComponentPatterns {
     conditional_patterns: Vec<Regex>,
     loop_patterns: Vec<Regex>,
     internal_fn_patterns: Vec<Regex>,
+    vue_state: Regex,
+    vue_hooks: Regex,
+    vue_props_option: Regex,
+    svelte_export: Regex,
+    svelte_rune_props: Regex,
+    svelte_stores: Regex,
+    svelte_hooks: Regex,
+    angular_io: Regex,
+    angular_field: Regex,
+    angular_hooks: Regex,
 }
 
 static COMPONENT_PATTERNS: OnceLock<ComponentPatterns> = OnceLock::new();
@@ -47,6 +64,10 @@ fn get_component_patterns() -> &'static ComponentPatterns {
             r"if\s*\(",
             r"\?\s*[^:]+\s*:",
             r"&&\s*[^&]",
+            r"\bv-(?:else-)?if\b",
+            r"\bv-show\b",
+            r"\*ngIf\b",
+            r"\{(?:#|:else\s+)if\b",
         ]
         .iter()
         .filter_map(|p| Regex::new(p).ok())
@@ -58,6 +79,9 @@ fn get_component_patterns() -> &'static ComponentPatterns {
             r"\.forEach\s*\(",
             r"\.filter\s*\(",
             r"\.reduce\s*\(",
+            r"\bv-for\b",
+            r"\*ngFor\b",
+            r"\{#each\b",
         ]
         .iter()
         .filter_map(|p| Regex::new(p).ok())
@@ -70,12 +94,26 @@ fn get_component_patterns() -> &'static ComponentPatterns {
         .iter()
         .filter_map(|p| Regex::new(p).ok())
         .collect(),
+        vue_state: Regex::new(r"\b(?:ref|shallowRef|reactive|shallowReactive|computed)\s*[<(]").expect("valid regex"),
+        vue_hooks: Regex::new(r"\b(?:watch|watchEffect|onBeforeMount|onMounted|onBeforeUpdate|onUpdated|onBeforeUnmount|onUnmounted|onActivated|onDeactivated|onErrorCaptured|use[A-Z][a-zA-Z0-9]*)\s*\(").expect("valid regex"),
+        vue_props_option: Regex::new(r"\bprops\s*:").expect("valid regex"),
+        svelte_export: Regex::new(r"\bexport\s+let\s+[a-zA-Z_$]").expect("valid regex"),
+        svelte_rune_props: Regex::new(r"let\s*\{([^}]*)\}[^=;]*=\s*\$props\s*\(").expect("valid regex"),
+        svelte_stores: Regex::new(r"\b(?:writable|readable|derived)\s*[<(]").expect("valid regex"),
+        svelte_hooks: Regex::new(r"(?:^|[^\w$.])(?:onMount|onDestroy|beforeUpdate|afterUpdate|\$effect(?:\.pre)?)\s*\(").expect("valid regex"),
+        angular_io: Regex::new(r"@(?:Input|Output)\s*\(|=\s*(?:input|output|model)(?:\.required)?\s*[<(]").expect("valid regex"),
+        angular_field: Regex::new(r"^\s*(?:(?:private|protected|public|readonly)\s+)*[a-zA-Z_$][\w$]*[!?]?\s*(?::[^=;]+)?=[^=>]").expect("valid regex"),
+        angular_hooks: Regex::new(r"\b(?:ngOnChanges|ngOnInit|ngDoCheck|ngAfterContentInit|ngAfterContentChecked|ngAfterViewInit|ngAfterViewChecked|ngOnDestroy|effect|inject)\s*\(").expect("valid regex"),
     })
 }
 
+/// Components a prop has to pass through before it is used
+const PROP_DRILLING_DEPTH: usize = 3;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ComponentReport {
     pub components: Vec<ComponentAnalysis>,
+    pub prop_drilling: Vec<PropDrilling>,
     pub summary: ComponentSummary,
     pub recommendations: Vec<String>,
 }
@@ -130,6 +168,19 @@ pub enum IssueType {
     DuplicatedCode,
 }
 
+impl IssueType {
+    /// The stable id of this kind of issue, see `rule_catalog`; kinds that are never reported have none
+    pub fn rule_id(&self) -> Option<&'static str> {
+        match self {
+            IssueType::TooManyLines => Some("SNF-COMP-LINES"),
+            IssueType::TooManyHooks => Some("SNF-COMP-HOOKS"),
+            IssueType::TooManyProps => Some("SNF-COMP-PROPS"),
+            IssueType::DeepNesting => Some("SNF-COMP-NESTING"),
+            IssueType::ComplexLogic | IssueType::MultipleConcerns | IssueType::DuplicatedCode => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IssueSeverity {
     Warning,
@@ -157,6 +208,28 @@ pub enum ExtractableType {
     BusinessLogic,
 }
 
+/// A prop forwarded unchanged through `PROP_DRILLING_DEPTH` or more components
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropDrilling {
+    /// The prop's name where the chain starts
+    pub prop: String,
+    /// From the component that first passes the value down to the last one receiving it
+    pub chain: Vec<DrillingStep>,
+    /// The components in between only forward the prop and never use it
+    pub pass_through: bool,
+    pub suggestion: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrillingStep {
+    pub component: String,
+    pub file: String,
+    /// Where the component passes the prop on, or its definition for the last step
+    pub line: usize,
+    /// The prop's name in this component
+    pub prop: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ComponentSummary {
     pub total_components: usize,
@@ -164,70 +237,124 @@ pub struct ComponentSummary {
     pub complex_components: usize,
     pub components_needing_refactor: usize,
     pub potential_extractions: usize,
+    pub prop_drilling_chains: usize,
+}
+
+impl Annotate for ComponentReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let mut findings: Vec<Annotation> = self.components.iter().flat_map(|component| component.issues.iter().map(move |issue| {
+            let level = match issue.severity {
+                IssueSeverity::Critical => AnnotationLevel::Critical,
+                IssueSeverity::Error => AnnotationLevel::Error,
+                IssueSeverity::Warning => AnnotationLevel::Warning,
+            };
+            let finding = Annotation::new(level, &component.file_path, issue.line_number, format!("Component {}", component.component_name), &issue.description);
+            match issue.issue_type.rule_id() {
+                Some(rule) => finding.with_rule(rule),
+                None => finding,
+            }
+        })).collect();
+        for drilling in &self.prop_drilling {
+            let Some(origin) = drilling.chain.first() else { continue };
+            let components: Vec<&str> = drilling.chain.iter().map(|step| step.component.as_str()).collect();
+            findings.push(Annotation::new(
+                AnnotationLevel::Notice,
+                &origin.file,
+                origin.line,
+                "Prop drilling",
+                format!("`{}` is passed through {}. {}", drilling.prop, components.join(" → "), drilling.suggestion),
+            ).with_rule("SNF-COMP-PROP-DRILLING"));
+        }
+        findings
+    }
 }
 
-pub async fn run(threshold: usize, json: bool, quiet: bool) -> Result<()> {
+pub async fn run(target: &ScanTarget, threshold: usize, scaffold: Option<&Path>, listing: ListingOptions, json: bool, quiet: bool) -> Result<CommandOutcome<ComponentReport>> {
     let start_time = std::time::Instant::now();
-    init_command("component analysis", quiet);
-    
-    let config = Config::load().unwrap_or_default();
-    let effective_threshold = if threshold == 100 {
-        config.large_files.severity_levels.warning  // Use warning threshold from large files config
-    } else {
-        threshold
-    };
+    let suppress = quiet || json;
+    tracing::info!("Running component analysis analysis");
     
-    let report = analyze_components(effective_threshold, quiet)?;
+    let config = target.config()?;
+    let outcome = check(target, threshold, &config)?;
+    let report = &outcome.report;
     let duration_ms = start_time.elapsed().as_millis() as u64;
     
-    let response = create_standard_json_output(
+    let response = create_annotated_json_output(
         "components",
-        &report,
+        report,
         report.summary.total_components,
         report.summary.components_needing_refactor,
         Some(duration_ms),
     );
     
-    output_result(&response, json, quiet, |report, quiet| print_component_report(report, &config, quiet))?;
+    output_result(&response, json, quiet, |report, quiet| print_component_report(report, &config, listing, quiet))?;
     
-    complete_command("component analysis", report.summary.components_needing_refactor == 0, quiet);
-    check_failure_threshold(report.summary.components_needing_refactor > 0, ExitCode::ThresholdExceeded);
+    if let Some(dir) = scaffold {
+        let written = write_scaffold(report, dir)?;
+        if !suppress {
+            output::line(format!("📦 Wrote {} scaffold file(s) to {}", written, dir.display()).green());
+        }
+    }
+    
+    tracing::info!(clean = report.summary.components_needing_refactor == 0, "component analysis analysis completed");
     
-    Ok(())
+    Ok(outcome)
 }
 
-fn analyze_components(threshold: usize, quiet: bool) -> Result<ComponentReport> {
-    let current_dir = std::env::current_dir()?;
+/// Analyze components and decide the exit status, without printing a report
+pub fn check(target: &ScanTarget, threshold: usize, config: &Config) -> Result<CommandOutcome<ComponentReport>> {
+    let effective_threshold = if threshold == 100 {
+        config.large_files.severity_levels.warning  // Use warning threshold from large files config
+    } else {
+        threshold
+    };
+    
+    let report = analyze_components(target, effective_threshold, config.large_files.count_mode)?;
+    let exit_code = check_failure_threshold(report.summary.components_needing_refactor > 0, ExitCode::ThresholdExceeded);
+    
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+fn analyze_components(target: &ScanTarget, threshold: usize, count_mode: CountMode) -> Result<ComponentReport> {
     let mut components = Vec::new();
     
-    if !quiet {
-        println!("🔍 Scanning for React, Vue, Angular, and Svelte components...");
-    }
+    tracing::debug!("Scanning for React, Vue, Angular, and Svelte components");
     
     // Find component files
-    let component_files = find_component_files(&current_dir);
+    let mut component_files: Vec<_> = target.paths.iter()
+        .flat_map(|path| find_component_files(path))
+        .collect();
+    component_files.sort();
+    component_files.dedup();
     
-    if !quiet {
-        println!("📊 Analyzing {} components for size and complexity...", component_files.len());
-    }
+    tracing::debug!(files = component_files.len(), "Analyzing components for size and complexity");
     
+    let mut flows = Vec::new();
     for file_path in component_files {
         if let Ok(content) = fs::read_to_string(&file_path) {
-            let line_count = content.lines().count();
+            let line_count = count_mode.lines(&content);
             
             if line_count >= threshold {
                 if let Some(analysis) = analyze_single_component(&file_path, &content, line_count) {
                     components.push(analysis);
                 }
             }
+            
+            let relative = file_path.strip_prefix(&target.root).unwrap_or(&file_path);
+            if !FileUtils::is_test_file(relative) {
+                flows.extend(prop_flows(&content, &paths::display(&file_path)));
+            }
         }
     }
     
-    let summary = create_component_summary(&components);
-    let recommendations = generate_global_recommendations(&components);
+    let prop_drilling = find_prop_drilling(&flows);
+    let mut summary = create_component_summary(&components);
+    summary.prop_drilling_chains = prop_drilling.len();
+    let recommendations = generate_global_recommendations(&components, &prop_drilling);
     
     Ok(ComponentReport {
         components,
+        prop_drilling,
         summary,
         recommendations,
     })
@@ -278,7 +405,11 @@ fn find_component_files(dir: &Path) -> Vec<std::path::PathBuf> {
 }
 
 fn analyze_single_component(file_path: &Path, content: &str, line_count: usize) -> Option<ComponentAnalysis> {
-    let framework = detect_framework_from_content(content);
+    let framework = match file_path.extension().and_then(|ext| ext.to_str()) {
+        Some("vue") => Framework::Vue,
+        Some("svelte") => Framework::Svelte,
+        _ => detect_framework_from_content(content),
+    };
     let component_type = detect_component_type(content, &framework);
     let component_name = extract_component_name(file_path, content, &framework);
     
@@ -288,7 +419,7 @@ fn analyze_single_component(file_path: &Path, content: &str, line_count: usize)
     let extractable_parts = find_extractable_parts(content, &framework);
     
     Some(ComponentAnalysis {
-        file_path: file_path.to_string_lossy().to_string(),
+        file_path: paths::display(file_path),
         component_name,
         component_type,
         framework,
@@ -314,6 +445,28 @@ fn detect_framework_from_content(content: &str) -> Framework {
     }
 }
 
+/// The code of a component: the `<script>` blocks of a Vue or Svelte file with the
+/// template and styles blanked, so line numbers still match; other files as they are
+fn component_script<'a>(content: &'a str, framework: &Framework) -> Cow<'a, str> {
+    if !matches!(framework, Framework::Vue | Framework::Svelte) {
+        return Cow::Borrowed(content);
+    }
+    let mut script = content.as_bytes().to_vec();
+    let mut kept = 0;
+    let blank = |script: &mut Vec<u8>, from: usize, to: usize| {
+        script[from..to].iter_mut().filter(|byte| **byte != b'\n').for_each(|byte| *byte = b' ');
+    };
+    while let Some(open) = content[kept..].find("<script").map(|index| kept + index) {
+        let Some(start) = content[open..].find('>').map(|index| open + index + 1) else { break };
+        let end = content[start..].find("</script>").map_or(content.len(), |index| start + index);
+        blank(&mut script, kept, start);
+        kept = end;
+    }
+    blank(&mut script, kept, content.len());
+    // Only whole characters outside the scripts were replaced
+    Cow::Owned(strip_comments(&String::from_utf8(script).unwrap_or_default()))
+}
+
 fn detect_component_type(content: &str, framework: &Framework) -> ComponentType {
     match framework {
         Framework::React => {
@@ -382,7 +535,7 @@ fn calculate_complexity_score(content: &str, framework: &Framework) -> u32 {
     let mut score = 0u32;
     
     // Count various complexity indicators
-    let hooks_count = count_react_hooks(content);
+    let hooks_count = count_hooks(content, framework);
     let props_count = count_props(content, framework);
     let state_vars_count = count_state_variables(content, framework);
     let conditional_count = count_conditionals(content);
@@ -399,9 +552,27 @@ fn calculate_complexity_score(content: &str, framework: &Framework) -> u32 {
     score
 }
 
-fn count_react_hooks(content: &str) -> u32 {
-    let pats = &get_component_patterns().hook_patterns;
-    pats.iter().map(|re| re.find_iter(content).count() as u32).sum()
+/// Hooks in React; composables and watchers in Vue; lifecycle callbacks and effects in
+/// Svelte; lifecycle hooks, effects and injections in Angular
+fn count_hooks(content: &str, framework: &Framework) -> u32 {
+    let patterns = get_component_patterns();
+    let script = component_script(content, framework);
+    let count = |re: &Regex| re.find_iter(&script).count() as u32;
+    match framework {
+        Framework::Vue => count(&patterns.vue_hooks),
+        Framework::Svelte => count(&patterns.svelte_hooks),
+        Framework::Angular => count(&patterns.angular_hooks),
+        _ => patterns.hook_patterns.iter().map(count).sum(),
+    }
+}
+
+fn hooks_label(framework: &Framework) -> &'static str {
+    match framework {
+        Framework::Vue => "composables and watchers",
+        Framework::Svelte => "lifecycle callbacks and effects",
+        Framework::Angular => "lifecycle hooks, effects and injections",
+        _ => "hooks",
+    }
 }
 
 fn count_props(content: &str, framework: &Framework) -> u32 {
@@ -419,17 +590,123 @@ fn count_props(content: &str, framework: &Framework) -> u32 {
             }
             props.len() as u32
         },
-        _ => 0, // TODO: Implement for other frameworks
+        Framework::Vue => vue_props(&component_script(content, framework)).len() as u32,
+        Framework::Svelte => {
+            let script = component_script(content, framework);
+            let patterns = get_component_patterns();
+            let runes = patterns.svelte_rune_props.captures_iter(&script)
+                .flat_map(|cap| cap[1].split(',').map(|entry| entry.split([':', '=']).next().unwrap_or("").trim().to_string()).collect::<Vec<_>>())
+                .filter(|prop| is_identifier(prop))
+                .count();
+            (patterns.svelte_export.find_iter(&script).count() + runes) as u32
+        },
+        Framework::Angular => get_component_patterns().angular_io.find_iter(content).count() as u32,
+        Framework::Unknown => 0,
     }
 }
 
+/// Props declared by `defineProps` (runtime object, string array or type literal/interface)
+/// or by an Options API `props` option
+fn vue_props(script: &str) -> Vec<String> {
+    let start = script.find("defineProps").map(|index| index + "defineProps".len())
+        .or_else(|| get_component_patterns().vue_props_option.find(script).map(|m| m.end()));
+    let Some(start) = start else { return Vec::new() };
+    let rest = script[start..].trim_start();
+    let rest = rest.strip_prefix(['<', '(']).map_or(rest, str::trim_start);
+
+    if let Some(list) = rest.strip_prefix('[') {
+        let list = &list[..list.find(']').unwrap_or(list.len())];
+        return list.split(',')
+            .map(|entry| entry.trim().trim_matches(['\'', '"']).to_string())
+            .filter(|prop| is_identifier(prop))
+            .collect();
+    }
+
+    // `defineProps<Props>()` declares them in an interface or type alias
+    let body = if rest.starts_with('{') {
+        rest
+    } else {
+        let name: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$').collect();
+        let declaration = [format!("interface {} ", name), format!("type {} ", name)].iter()
+            .find_map(|declaration| script.find(declaration.as_str()));
+        match declaration.and_then(|index| script[index..].find('{').map(|open| &script[index + open..])) {
+            Some(body) if !name.is_empty() => body,
+            _ => return Vec::new(),
+        }
+    };
+    let Some(close) = expression_end(body.as_bytes(), 0) else { return Vec::new() };
+    member_names(&body[1..close])
+}
+
+/// Keys of an object literal or type literal body
+fn member_names(body: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut previous = ' ';
+    for (index, c) in body.char_indices() {
+        match c {
+            '(' | '{' | '[' | '<' => depth += 1,
+            '>' if previous == '=' => {}
+            ')' | '}' | ']' | '>' => depth -= 1,
+            ',' | ';' | '\n' if depth == 0 => {
+                names.push(&body[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+        previous = c;
+    }
+    names.push(&body[start..]);
+    names.into_iter()
+        .filter_map(|entry| entry.split(':').next())
+        .map(|key| key.trim().trim_end_matches('?').trim_matches(['\'', '"']).to_string())
+        .filter(|key| is_identifier(key))
+        .collect()
+}
+
 fn count_state_variables(content: &str, framework: &Framework) -> u32 {
+    let patterns = get_component_patterns();
     match framework {
-        Framework::React => get_component_patterns().state_pattern.find_iter(content).count() as u32,
-        _ => 0, // TODO: Implement for other frameworks
+        Framework::React => patterns.state_pattern.find_iter(content).count() as u32,
+        Framework::Vue => patterns.vue_state.find_iter(&component_script(content, framework)).count() as u32,
+        Framework::Svelte => {
+            // Top-level `let`s are reactive, plus stores and `$:` statements
+            let script = component_script(content, framework);
+            let lets = top_level_lines(&script, 0)
+                .filter(|line| line.trim_start().starts_with("let ") && !line.contains("$props"))
+                .count();
+            let reactive = script.lines().filter(|line| line.trim_start().starts_with("$:")).count();
+            (lets + reactive + patterns.svelte_stores.find_iter(&script).count()) as u32
+        },
+        Framework::Angular => {
+            // Fields of the component class that hold values, not inputs, injections or handlers
+            let code = blank_strings(&strip_comments(content));
+            let lines: Vec<&str> = top_level_lines(&code, 1).collect();
+            lines.iter().enumerate()
+                .filter(|(i, line)| {
+                    let decorated = line.contains('@') || i.checked_sub(1).is_some_and(|previous| lines[previous].trim_start().starts_with('@'));
+                    patterns.angular_field.is_match(line) && !line.contains("=>") && !decorated
+                        && !patterns.angular_io.is_match(line) && !patterns.angular_hooks.is_match(line)
+                })
+                .count() as u32
+        },
+        Framework::Unknown => 0,
     }
 }
 
+/// Lines that start at brace depth `depth`, ignoring braces in strings
+fn top_level_lines(code: &str, depth: i32) -> impl Iterator<Item = &str> {
+    let blanked = blank_strings(code);
+    let mut current = 0i32;
+    let depths: Vec<i32> = blanked.lines().map(|line| {
+        let start = current;
+        current += line.matches('{').count() as i32 - line.matches('}').count() as i32;
+        start
+    }).collect();
+    code.lines().zip(depths).filter(move |(_, start)| *start == depth).map(|(line, _)| line)
+}
+
 fn count_conditionals(content: &str) -> u32 {
     get_component_patterns()
         .conditional_patterns
@@ -474,14 +751,13 @@ fn detect_component_issues(content: &str, line_count: usize, framework: &Framewo
         });
     }
     
-    // Check React-specific issues
-    if matches!(framework, Framework::React) {
-        let hooks_count = count_react_hooks(content);
+    if !matches!(framework, Framework::Unknown) {
+        let hooks_count = count_hooks(content, framework);
         if hooks_count > 10 {
             issues.push(ComponentIssue {
                 issue_type: IssueType::TooManyHooks,
                 line_number: 1,
-                description: format!("Component uses {} hooks (>10 is too many)", hooks_count),
+                description: format!("Component uses {} {} (>10 is too many)", hooks_count, hooks_label(framework)),
                 severity: IssueSeverity::Error,
             });
         }
@@ -537,9 +813,14 @@ fn generate_refactor_suggestions(issues: &[ComponentIssue], framework: &Framewor
                     suggestions.push("🎯 Extract complex logic into separate functions".to_string());
                 }
             },
-            IssueType::TooManyHooks => {
-                suggestions.push("🪝 Extract related hooks into custom hooks".to_string());
-                suggestions.push("📋 Group useState calls into useReducer if managing related state".to_string());
+            IssueType::TooManyHooks => match framework {
+                Framework::Vue => suggestions.push("🧩 Move related refs and watchers into composables".to_string()),
+                Framework::Svelte => suggestions.push("🗃️ Move shared state and its effects into stores".to_string()),
+                Framework::Angular => suggestions.push("🛠️ Move data access and side effects into a service".to_string()),
+                _ => {
+                    suggestions.push("🪝 Extract related hooks into custom hooks".to_string());
+                    suggestions.push("📋 Group useState calls into useReducer if managing related state".to_string());
+                }
             },
             IssueType::TooManyProps => {
                 suggestions.push("📦 Group related props into objects".to_string());
@@ -562,7 +843,13 @@ fn generate_refactor_suggestions(issues: &[ComponentIssue], framework: &Framewor
         Framework::Vue => {
             suggestions.push("🎯 Use Vue composition API for better logic organization".to_string());
         },
-        _ => {}
+        Framework::Svelte => {
+            suggestions.push("🗃️ Keep state shared between components in stores".to_string());
+        },
+        Framework::Angular => {
+            suggestions.push("🛠️ Keep components thin and move business logic into injectable services".to_string());
+        },
+        Framework::Unknown => {}
     }
     
     suggestions
@@ -570,50 +857,235 @@ fn generate_refactor_suggestions(issues: &[ComponentIssue], framework: &Framewor
 
 fn find_extractable_parts(content: &str, framework: &Framework) -> Vec<ExtractablePart> {
     let mut parts = Vec::new();
+    let code = blank_strings(&strip_comments(&component_script(content, framework)));
+    let lines: Vec<&str> = code.lines().collect();
+    let patterns = get_component_patterns();
+    let hook_name = |run: &StatementRun, fallback: &str| {
+        run.starts.iter().flat_map(|&line| declared_names(lines[line])).next()
+            .map_or_else(|| fallback.to_string(), |name| format!("use{}{}", name[..1].to_uppercase(), &name[1..]))
+    };
     
     match framework {
         Framework::React => {
-            // Find potential custom hooks
-            if let Ok(re) = Regex::new(r"(useState|useEffect|useCallback|useMemo)\s*\([^)]*\)") {
-                for (i, line) in content.lines().enumerate() {
-                    if re.is_match(line) {
-                        // Look for groups of hooks that could be extracted
+            // Adjacent hook calls can move into a custom hook together
+            if let Ok(re) = Regex::new(r"\b(?:useState|useReducer|useEffect|useLayoutEffect|useCallback|useMemo|useRef)\s*[<(]") {
+                for run in statement_runs(&code, |line| re.is_match(line)).iter().filter(|run| run.starts.len() >= 2) {
+                    let name = hook_name(run, "useCustomHook");
+                    parts.push(ExtractablePart {
+                        suggested_filename: format!("{}.ts", name),
+                        name,
+                        part_type: ExtractableType::CustomHook,
+                        start_line: run.start + 1,
+                        end_line: run.end + 1,
+                        description: format!("Extract {} related hooks into a custom hook", run.starts.len()),
+                    });
+                }
+            }
+        },
+        Framework::Vue => {
+            // Runs of refs, computed values and watchers make a composable
+            let runs = statement_runs(&code, |line| patterns.vue_state.is_match(line) || patterns.vue_hooks.is_match(line));
+            for run in runs.iter().filter(|run| run.starts.len() >= 2) {
+                let name = hook_name(run, "useState");
+                parts.push(ExtractablePart {
+                    suggested_filename: format!("composables/{}.ts", name),
+                    name,
+                    part_type: ExtractableType::CustomHook,
+                    start_line: run.start + 1,
+                    end_line: run.end + 1,
+                    description: format!("Move {} refs, computed values and watchers into a composable", run.starts.len()),
+                });
+            }
+        },
+        Framework::Svelte => {
+            // Stores created in a component can only be shared by passing them down
+            if let Ok(re) = Regex::new(r"(?:const|let)\s+([a-zA-Z_$][\w$]*)\s*=\s*(?:writable|readable|derived)\s*[<(]") {
+                for (i, line) in lines.iter().enumerate() {
+                    if let Some(cap) = re.captures(line) {
                         parts.push(ExtractablePart {
-                            name: "CustomHook".to_string(),
-                            part_type: ExtractableType::CustomHook,
+                            name: cap[1].to_string(),
+                            part_type: ExtractableType::BusinessLogic,
                             start_line: i + 1,
-                            end_line: i + 5, // Approximate
-                            suggested_filename: "useCustomHook.ts".to_string(),
-                            description: "Extract related hooks into a custom hook".to_string(),
+                            end_line: statement_end(&code, i) + 1,
+                            suggested_filename: format!("stores/{}.ts", &cap[1]),
+                            description: format!("Move the {} store into a module so other components can subscribe to it", &cap[1]),
                         });
                     }
                 }
             }
-            
-            // Find utility functions
-            if let Ok(re) = Regex::new(r"const\s+([a-z][a-zA-Z0-9]*)\s*=\s*\([^)]*\)\s*=>\s*\{") {
-                for (i, line) in content.lines().enumerate() {
-                    if let Some(cap) = re.captures(line) {
-                        if let Some(func_name) = cap.get(1) {
-                            parts.push(ExtractablePart {
-                                name: func_name.as_str().to_string(),
-                                part_type: ExtractableType::UtilityFunction,
-                                start_line: i + 1,
-                                end_line: i + 10, // Approximate
-                                suggested_filename: format!("{}.utils.ts", func_name.as_str()),
-                                description: format!("Extract {} utility function", func_name.as_str()),
-                            });
-                        }
-                    }
-                }
+        },
+        Framework::Angular => {
+            // HTTP calls belong in a service
+            let calls: Vec<usize> = lines.iter().enumerate()
+                .filter(|(_, line)| line.contains("this.http.") || line.contains("fetch("))
+                .map(|(i, _)| i)
+                .collect();
+            if let (Some(&first), Some(&last)) = (calls.first(), calls.last()) {
+                let class = Regex::new(r"class\s+([A-Z][a-zA-Z0-9]*?)(?:Component)?\b").ok()
+                    .and_then(|re| re.captures(&code).map(|cap| cap[1].to_string()))
+                    .unwrap_or_else(|| "Data".to_string());
+                parts.push(ExtractablePart {
+                    name: format!("{}Service", class),
+                    part_type: ExtractableType::BusinessLogic,
+                    start_line: first + 1,
+                    end_line: statement_end(&code, last) + 1,
+                    suggested_filename: format!("{}.service.ts", kebab_case(&class)),
+                    description: format!("Move {} HTTP call(s) into an injectable service", calls.len()),
+                });
             }
         },
-        _ => {}
+        Framework::Unknown => {}
+    }
+
+    // Find utility functions
+    if let Ok(re) = Regex::new(r"const\s+([a-z][a-zA-Z0-9]*)\s*=\s*\([^)]*\)\s*=>\s*\{") {
+        for (i, line) in lines.iter().enumerate() {
+            if let Some(cap) = re.captures(line) {
+                if let Some(func_name) = cap.get(1) {
+                    parts.push(ExtractablePart {
+                        name: func_name.as_str().to_string(),
+                        part_type: ExtractableType::UtilityFunction,
+                        start_line: i + 1,
+                        end_line: statement_end(&code, i) + 1,
+                        suggested_filename: format!("{}.utils.ts", func_name.as_str()),
+                        description: format!("Extract {} utility function", func_name.as_str()),
+                    });
+                }
+            }
+        }
     }
     
     parts
 }
 
+/// Write the code of each extractable part under `dir/<component>/` as a starting point for
+/// the refactor; returns the number of files written
+pub fn write_scaffold(report: &ComponentReport, dir: &Path) -> Result<usize> {
+    let mut written = HashSet::new();
+    for component in &report.components {
+        if component.extractable_parts.is_empty() {
+            continue;
+        }
+        let content = fs::read_to_string(paths::resolve(&component.file_path))
+            .with_context(|| format!("Failed to read {}", component.file_path))?;
+        let lines: Vec<&str> = content.lines().collect();
+        for part in &component.extractable_parts {
+            let mut path = dir.join(&component.component_name).join(&part.suggested_filename);
+            if written.contains(&path) {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+                let extension = path.extension().unwrap_or_default().to_string_lossy().to_string();
+                path.set_file_name(format!("{}-{}.{}", stem, part.start_line, extension));
+            }
+            let end = part.end_line.min(lines.len());
+            let code = scaffold_code(part, &lines[part.start_line.saturating_sub(1).min(end)..end]);
+            let source = format!("// Extracted from {}:{}-{}\n{}", component.file_path, part.start_line, part.end_line, code);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, source).with_context(|| format!("Failed to write {}", path.display()))?;
+            written.insert(path);
+        }
+    }
+    Ok(written.len())
+}
+
+/// The extracted lines, dedented; hooks and composables are wrapped in a function that
+/// returns what they declare, and top-level declarations are exported
+fn scaffold_code(part: &ExtractablePart, lines: &[&str]) -> String {
+    let indent = lines.iter().filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines.iter().map(|line| line.get(indent..).unwrap_or("")).collect();
+
+    if matches!(part.part_type, ExtractableType::CustomHook) {
+        let names: Vec<String> = lines.iter()
+            .filter(|line| !line.starts_with(char::is_whitespace))
+            .flat_map(|line| declared_names(line))
+            .collect();
+        let body: Vec<String> = lines.iter().map(|line| if line.is_empty() { String::new() } else { format!("  {}", line) }).collect();
+        return format!("export function {}() {{\n{}\n\n  return {{ {} }};\n}}\n", part.name, body.join("\n"), names.join(", "));
+    }
+    let code = lines.join("\n");
+    if ["const ", "let ", "function ", "async function "].iter().any(|keyword| code.starts_with(keyword)) {
+        format!("export {}\n", code)
+    } else {
+        format!("{}\n", code)
+    }
+}
+
+/// Adjacent statements (blank lines between them allowed), as 0-based lines
+struct StatementRun {
+    start: usize,
+    end: usize,
+    starts: Vec<usize>,
+}
+
+/// Runs of adjacent statements whose first line matches
+fn statement_runs(code: &str, matches: impl Fn(&str) -> bool) -> Vec<StatementRun> {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut runs: Vec<StatementRun> = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        if !matches(lines[i]) {
+            i += 1;
+            continue;
+        }
+        let end = statement_end(code, i);
+        match runs.last_mut() {
+            Some(run) if lines[run.end + 1..i].iter().all(|line| line.trim().is_empty()) => {
+                run.end = end;
+                run.starts.push(i);
+            }
+            _ => runs.push(StatementRun { start: i, end, starts: vec![i] }),
+        }
+        i = end + 1;
+    }
+    runs
+}
+
+/// The 0-based line on which the statement starting on `line` ends: the first `;` or
+/// line break outside brackets. `code` must have comments and strings blanked
+fn statement_end(code: &str, line: usize) -> usize {
+    let start: usize = code.split_inclusive('\n').take(line).map(str::len).sum();
+    let mut depth = 0i32;
+    let mut end = line;
+    for byte in code[start..].bytes() {
+        match byte {
+            b'(' | b'{' | b'[' => depth += 1,
+            b')' | b'}' | b']' => depth -= 1,
+            b';' if depth <= 0 => break,
+            b'\n' if depth <= 0 => break,
+            b'\n' => end += 1,
+            _ => {}
+        }
+    }
+    end
+}
+
+/// Names a statement declares: `const [a, setA] = ...`, `const { a } = ...` or `const a = ...`
+fn declared_names(line: &str) -> Vec<String> {
+    let Some(cap) = Regex::new(r"^\s*(?:export\s+)?(?:const|let|var)\s+(\[[^\]]*\]|\{[^}]*\}|[a-zA-Z_$][\w$]*)").ok().and_then(|re| re.captures(line)) else {
+        return Vec::new();
+    };
+    cap[1].trim_matches(['[', ']', '{', '}']).split(',')
+        .filter_map(|entry| entry.split('=').next())
+        .map(|entry| entry.rsplit(':').next().unwrap_or(entry).trim().trim_start_matches("...").to_string())
+        .filter(|name| is_identifier(name))
+        .collect()
+}
+
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            kebab.push('-');
+        }
+        kebab.push(c.to_ascii_lowercase());
+    }
+    kebab
+}
+
 fn create_component_summary(components: &[ComponentAnalysis]) -> ComponentSummary {
     let total_components = components.len();
     let large_components = components.iter().filter(|c| c.line_count > 100).count();
@@ -629,12 +1101,271 @@ fn create_component_summary(components: &[ComponentAnalysis]) -> ComponentSummar
         complex_components,
         components_needing_refactor,
         potential_extractions,
+        prop_drilling_chains: 0,
+    }
+}
+
+/// A React function component: the props it takes and the values it passes to other components
+struct PropFlow {
+    name: String,
+    file: String,
+    line: usize,
+    /// Local name and prop name: `u` and `user` for `({ user: u })`, `props.user` and `user` for `(props)`
+    props: Vec<(String, String)>,
+    forwards: Vec<Forward>,
+    /// Uses of each local prop name other than forwarding it
+    other_uses: HashMap<String, usize>,
+}
+
+/// `<Child prop={value} />` where `value` is a plain identifier or `props.x`
+struct Forward {
+    value: String,
+    child: String,
+    prop: String,
+    line: usize,
+}
+
+impl PropFlow {
+    fn local_for(&self, prop: &str) -> Option<&str> {
+        self.props.iter().find(|(_, name)| name == prop).map(|(local, _)| local.as_str())
+    }
+
+    fn prop_for(&self, local: &str) -> Option<&str> {
+        self.props.iter().find(|(name, _)| name == local).map(|(_, prop)| prop.as_str())
+    }
+}
+
+/// The function components in a file and how they pass values to other components
+fn prop_flows(content: &str, file: &str) -> Vec<PropFlow> {
+    let code = blank_strings(&strip_comments(content));
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(code.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    let line_of = |offset: usize| line_starts.partition_point(|&start| start <= offset);
+    let elements = jsx_elements(content);
+
+    find_functions(content).into_iter()
+        .filter(|span| span.kind == FunctionKind::Component)
+        .filter_map(|span| {
+            let start = line_starts[span.start_line - 1];
+            let end = line_starts.get(span.end_line).copied().unwrap_or(code.len());
+            let (props, body_start) = component_props(&code[start..end], &span.name)?;
+            let body = &code[start + body_start..end];
+
+            let forwards: Vec<Forward> = elements.iter()
+                .filter(|element| (start + body_start..end).contains(&element.offset))
+                .filter(|element| element.name.starts_with(|c: char| c.is_ascii_uppercase()))
+                .flat_map(|element| element.attributes.iter().filter_map(move |(prop, value)| match value {
+                    Some(AttrValue::Expression(value)) if is_reference(value.trim()) => Some(Forward {
+                        value: value.trim().to_string(),
+                        child: element.name.to_string(),
+                        prop: prop.to_string(),
+                        line: line_of(element.offset),
+                    }),
+                    _ => None,
+                }))
+                .collect();
+            let props: Vec<(String, String)> = match props {
+                ComponentProps::Destructured(props) => props,
+                // `(props)`: every `props.x` in the body
+                ComponentProps::Object(object) => {
+                    let prefix = format!("{}.", object);
+                    let mut accessed: Vec<(String, String)> = word_offsets(body, &object)
+                        .filter_map(|offset| {
+                            let name: String = body[offset..].strip_prefix(&prefix)?
+                                .chars().take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$').collect();
+                            (!name.is_empty()).then(|| (format!("{}{}", prefix, name), name))
+                        })
+                        .collect();
+                    accessed.sort();
+                    accessed.dedup();
+                    accessed
+                }
+            };
+            let other_uses = props.iter().map(|(local, _)| {
+                let forwarded: usize = forwards.iter()
+                    .filter(|forward| forward.value == *local)
+                    .map(|forward| 1 + usize::from(forward.prop == *local))
+                    .sum();
+                (local.clone(), word_offsets(body, local).count().saturating_sub(forwarded))
+            }).collect();
+
+            Some(PropFlow { name: span.name, file: file.to_string(), line: span.start_line, props, forwards, other_uses })
+        })
+        .collect()
+}
+
+enum ComponentProps {
+    Destructured(Vec<(String, String)>),
+    Object(String),
+}
+
+/// The props of the component whose code starts `code`, and where its parameter list ends
+fn component_props(code: &str, name: &str) -> Option<(ComponentProps, usize)> {
+    let open = code.find(name).map_or(0, |index| index + name.len());
+    let open = open + code[open..].find('(')?;
+    let mut depth = 0;
+    let close = open + code[open..].char_indices().find(|(_, c)| {
+        match c {
+            '(' | '{' | '[' => depth += 1,
+            ')' | '}' | ']' => depth -= 1,
+            _ => {}
+        }
+        depth == 0
+    })?.0;
+    let Some(first) = top_level_split(&code[open + 1..close]).into_iter().next() else {
+        return Some((ComponentProps::Destructured(Vec::new()), close + 1));
+    };
+
+    if let Some(inner) = first.trim().strip_prefix('{') {
+        let inner = &inner[..inner.rfind('}')?];
+        let props = top_level_split(inner).into_iter()
+            .filter(|entry| !entry.contains('{') && !entry.trim_start().starts_with("..."))
+            .filter_map(|entry| {
+                let entry = entry.split('=').next()?.trim();
+                let (prop, local) = entry.split_once(':').map_or((entry, entry), |(prop, local)| (prop.trim(), local.trim()));
+                is_identifier(prop).then(|| (local.to_string(), prop.to_string()))
+            })
+            .collect();
+        Some((ComponentProps::Destructured(props), close + 1))
+    } else {
+        let object = first.split(':').next()?.trim();
+        is_identifier(object).then(|| (ComponentProps::Object(object.to_string()), close + 1))
+    }
+}
+
+/// Split at commas outside brackets
+fn top_level_split(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' | '{' | '[' | '<' => depth += 1,
+            ')' | '}' | ']' | '>' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.into_iter().filter(|part| !part.trim().is_empty()).collect()
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && text.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+        && !matches!(text, "true" | "false" | "null" | "undefined" | "this")
+}
+
+/// A value that can be passed on unchanged: `user` or `props.user`
+fn is_reference(value: &str) -> bool {
+    match value.split_once('.') {
+        Some((object, property)) => is_identifier(object) && is_identifier(property),
+        None => is_identifier(value),
+    }
+}
+
+/// Offsets of `word` in `code` that are not part of a longer identifier or a member access
+fn word_offsets<'a>(code: &'a str, word: &'a str) -> impl Iterator<Item = usize> + 'a {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    code.match_indices(word).map(|(offset, _)| offset).filter(move |&offset| {
+        let before = code[..offset].chars().next_back();
+        let after = code[offset + word.len()..].chars().next();
+        !before.is_some_and(|c| is_ident(c) || c == '.') && !after.is_some_and(is_ident)
+    })
+}
+
+/// Follow values from the component that first passes them down; chains through
+/// `PROP_DRILLING_DEPTH` or more components are prop drilling
+fn find_prop_drilling(flows: &[PropFlow]) -> Vec<PropDrilling> {
+    let mut by_name: HashMap<&str, usize> = HashMap::new();
+    for (index, flow) in flows.iter().enumerate() {
+        by_name.entry(flow.name.as_str()).or_insert(index);
+    }
+    let received: HashSet<(usize, &str)> = flows.iter()
+        .flat_map(|flow| flow.forwards.iter())
+        .filter_map(|forward| by_name.get(forward.child.as_str()).map(|&child| (child, forward.prop.as_str())))
+        .collect();
+
+    let mut chains = Vec::new();
+    for (index, flow) in flows.iter().enumerate() {
+        for forward in &flow.forwards {
+            let prop = flow.prop_for(&forward.value);
+            // The value came from a parent: the chain starts there
+            if prop.is_some_and(|prop| received.contains(&(index, prop))) {
+                continue;
+            }
+            let Some(&child) = by_name.get(forward.child.as_str()) else { continue };
+            let step = DrillingStep {
+                component: flow.name.clone(),
+                file: flow.file.clone(),
+                line: forward.line,
+                prop: prop.unwrap_or(&forward.value).to_string(),
+            };
+            follow(flows, &by_name, child, &forward.prop, &mut vec![(index, step)], &mut chains);
+        }
     }
+    chains.sort_by(|a, b| b.chain.len().cmp(&a.chain.len()).then(a.chain[0].file.cmp(&b.chain[0].file)).then(a.chain[0].line.cmp(&b.chain[0].line)));
+    chains
+}
+
+fn follow(flows: &[PropFlow], by_name: &HashMap<&str, usize>, index: usize, prop: &str, path: &mut Vec<(usize, DrillingStep)>, chains: &mut Vec<PropDrilling>) {
+    let flow = &flows[index];
+    let local = flow.local_for(prop);
+    let next: Vec<(&Forward, usize)> = flow.forwards.iter()
+        .filter(|forward| Some(forward.value.as_str()) == local)
+        .filter_map(|forward| by_name.get(forward.child.as_str()).map(|&child| (forward, child)))
+        .filter(|(_, child)| !path.iter().any(|(visited, _)| visited == child) && *child != index)
+        .collect();
+
+    if next.is_empty() {
+        if path.len() + 1 >= PROP_DRILLING_DEPTH {
+            let last = DrillingStep { component: flow.name.clone(), file: flow.file.clone(), line: flow.line, prop: prop.to_string() };
+            chains.push(drilling(flows, path, last));
+        }
+        return;
+    }
+    for (forward, child) in next {
+        let step = DrillingStep { component: flow.name.clone(), file: flow.file.clone(), line: forward.line, prop: prop.to_string() };
+        path.push((index, step));
+        follow(flows, by_name, child, &forward.prop, path, chains);
+        path.pop();
+    }
+}
+
+fn drilling(flows: &[PropFlow], path: &[(usize, DrillingStep)], last: DrillingStep) -> PropDrilling {
+    let pass_through = path[1..].iter().all(|(index, step)| {
+        let flow = &flows[*index];
+        flow.local_for(&step.prop).is_some_and(|local| flow.other_uses.get(local) == Some(&0))
+    });
+    let origin = &path[0].1;
+    let between: Vec<&str> = path[1..].iter().map(|(_, step)| step.component.as_str()).collect();
+    let suggestion = if pass_through && between.len() == 1 {
+        format!(
+            "{} only passes `{}` on: let {} render <{} {}={{{}}} /> and hand it to {} as children or a slot prop",
+            between[0], last.prop, origin.component, last.component, last.prop, origin.prop, between[0]
+        )
+    } else {
+        format!(
+            "Provide `{}` through a React context from {} and read it with useContext in {}, instead of passing it through {}",
+            origin.prop, origin.component, last.component, between.join(" → ")
+        )
+    };
+    let mut chain: Vec<DrillingStep> = path.iter().map(|(_, step)| step.clone()).collect();
+    chain.push(last);
+    PropDrilling { prop: origin.prop.clone(), chain, pass_through, suggestion }
 }
 
-fn generate_global_recommendations(components: &[ComponentAnalysis]) -> Vec<String> {
+fn generate_global_recommendations(components: &[ComponentAnalysis], prop_drilling: &[PropDrilling]) -> Vec<String> {
     let mut recommendations = Vec::new();
     
+    if !prop_drilling.is_empty() {
+        recommendations.push(format!("🕳️ {} props are drilled through {}+ components - use context or composition for shared state", prop_drilling.len(), PROP_DRILLING_DEPTH));
+    }
+    
     let avg_lines = if !components.is_empty() {
         components.iter().map(|c| c.line_count).sum::<usize>() / components.len()
     } else {
@@ -662,16 +1393,25 @@ fn generate_global_recommendations(components: &[ComponentAnalysis]) -> Vec<Stri
     recommendations
 }
 
-fn print_component_report(report: &ComponentReport, config: &Config, quiet: bool) {
+fn print_component_report(report: &ComponentReport, config: &Config, listing: ListingOptions, quiet: bool) {
     if !quiet {
-        println!();
-        println!("{}", "🧩 Component Analysis Report".bold().blue());
-        println!("{}", "==========================".blue());
-        println!();
+        output::blank();
+        output::line("🧩 Component Analysis Report".bold().blue());
+        output::line("==========================".blue());
+        output::blank();
     }
     
-    if report.components.is_empty() {
-        println!("{}", "✅ No large components found! Your components are well-sized.".green());
+    if report.components.is_empty() && report.prop_drilling.is_empty() {
+        output::line("✅ No large components found! Your components are well-sized.".green());
+        return;
+    }
+    
+    if !listing.is_default() {
+        print_listing(listing_rows(report), listing);
+        if !report.prop_drilling.is_empty() {
+            print_prop_drilling(&report.prop_drilling);
+        }
+        print_component_summary(&report.summary, config);
         return;
     }
     
@@ -708,20 +1448,41 @@ fn print_component_report(report: &ComponentReport, config: &Config, quiet: bool
         print_component_analysis(component, "warning");
     }
     
+    if !report.prop_drilling.is_empty() {
+        print_prop_drilling(&report.prop_drilling);
+    }
+    
     // Print summary
     print_component_summary(&report.summary, config);
     
     // Print recommendations
     if !report.recommendations.is_empty() {
-        println!("{}", "💡 RECOMMENDATIONS".bold().yellow());
-        println!("{}", "─────────────────".yellow());
+        output::line("💡 RECOMMENDATIONS".bold().yellow());
+        output::line("─────────────────".yellow());
         for recommendation in &report.recommendations {
-            println!("  {}", recommendation);
+            output::line(format!("  {}", recommendation));
         }
-        println!();
+        output::blank();
     }
 }
 
+/// Each component's issues for `--group-by` and `--sort`, with the component's length
+fn listing_rows(report: &ComponentReport) -> Vec<Row> {
+    report.components.iter().flat_map(|component| component.issues.iter().map(move |issue| Row {
+        path: component.file_path.clone(),
+        line: Some(issue.line_number),
+        rule: format!("{:?}", issue.issue_type),
+        severity: match issue.severity {
+            IssueSeverity::Critical => Severity::Critical,
+            IssueSeverity::Error => Severity::High,
+            IssueSeverity::Warning => Severity::Medium,
+        },
+        lines: Some(component.line_count),
+        size_bytes: None,
+        text: format!("{}: {}", component.component_name, issue.description),
+    })).collect()
+}
+
 fn print_component_analysis(component: &ComponentAnalysis, severity: &str) {
     let (emoji, color) = match severity {
         "critical" => ("🚨", "red"),
@@ -730,7 +1491,7 @@ fn print_component_analysis(component: &ComponentAnalysis, severity: &str) {
         _ => ("📄", "white"),
     };
     
-    println!("{} {}: {} ({} lines, complexity: {})", 
+    output::line(format!("{} {}: {} ({} lines, complexity: {})", 
         emoji,
         match color {
             "red" => severity.red(),
@@ -741,13 +1502,13 @@ fn print_component_analysis(component: &ComponentAnalysis, severity: &str) {
         component.component_name.bold(),
         component.line_count,
         component.complexity_score
-    );
+    ));
     
-    println!("   📁 {}", component.file_path.dimmed());
-    println!("   🏗️  {} {:?} component", 
+    output::line(format!("   📁 {}", component.file_path.dimmed()));
+    output::line(format!("   🏗️  {} {:?} component", 
         format!("{:?}", component.framework).dimmed(),
         component.component_type
-    );
+    ));
     
     // Print issues
     for issue in &component.issues {
@@ -756,56 +1517,185 @@ fn print_component_analysis(component: &ComponentAnalysis, severity: &str) {
             IssueSeverity::Error => "❌",
             IssueSeverity::Warning => "⚠️",
         };
-        println!("   {} {}", issue_icon, issue.description);
+        output::line(format!("   {} {}", issue_icon, issue.description));
     }
     
     // Print refactor suggestions
     if !component.refactor_suggestions.is_empty() {
-        println!("   💡 Refactor suggestions:");
+        output::line("   💡 Refactor suggestions:");
         for suggestion in &component.refactor_suggestions {
-            println!("     • {}", suggestion);
+            output::line(format!("     • {}", suggestion));
         }
     }
     
     // Print extractable parts
     if !component.extractable_parts.is_empty() {
-        println!("   📦 Extractable parts:");
+        output::line("   📦 Extractable parts:");
         for part in &component.extractable_parts {
-            println!("     • {} → {}", part.description, part.suggested_filename.bold());
+            output::line(format!("     • {} → {}", part.description, part.suggested_filename.bold()));
         }
     }
     
-    println!();
+    output::blank();
+}
+
+fn print_prop_drilling(chains: &[PropDrilling]) {
+    output::line("🕳️  PROP DRILLING".bold().yellow());
+    output::line("────────────────".yellow());
+    for drilling in chains {
+        let chain: Vec<String> = drilling.chain.iter().map(|step| {
+            if step.prop == drilling.prop { step.component.clone() } else { format!("{} ({})", step.component, step.prop) }
+        }).collect();
+        output::line(format!("  {} {}", format!("`{}`", drilling.prop).bold(), chain.join(" → ")));
+        for step in &drilling.chain {
+            output::line(format!("     {}", format!("{}:{}", step.file, step.line).dimmed()));
+        }
+        output::line(format!("     💡 {}", drilling.suggestion));
+    }
+    output::blank();
 }
 
 fn print_component_summary(summary: &ComponentSummary, config: &Config) {
-    println!("{}", "📈 SUMMARY".bold().white());
-    println!("{}", "─────────".white());
-    println!("  Components analyzed: {}", summary.total_components);
-    println!("  Large components (>100 lines): {}", 
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Components analyzed: {}", summary.total_components));
+    output::line(format!("  Large components (>100 lines): {}", 
         if summary.large_components > 0 { 
             summary.large_components.to_string().yellow() 
         } else { 
             summary.large_components.to_string().green() 
         }
-    );
-    println!("  Complex components (high complexity): {}", 
+    ));
+    output::line(format!("  Complex components (high complexity): {}", 
         if summary.complex_components > 0 { 
             summary.complex_components.to_string().yellow() 
         } else { 
             summary.complex_components.to_string().green() 
         }
-    );
-    println!("  Components needing refactor: {}", 
+    ));
+    output::line(format!("  Components needing refactor: {}", 
         if summary.components_needing_refactor > 0 { 
             summary.components_needing_refactor.to_string().red() 
         } else { 
             summary.components_needing_refactor.to_string().green() 
         }
-    );
-    println!("  Potential extractions found: {}", summary.potential_extractions);
+    ));
+    output::line(format!("  Potential extractions found: {}", summary.potential_extractions));
+    if summary.prop_drilling_chains > 0 {
+        output::line(format!("  Drilled props: {}", summary.prop_drilling_chains.to_string().yellow()));
+    }
     
-    println!();
+    output::blank();
     let threshold = config.large_files.severity_levels.warning;
-    println!("{}", format!("💡 TIP: Keep components under {} lines for better maintainability", threshold).dimmed());
-}
\ No newline at end of file
+    output::line(format!("💡 TIP: Keep components under {} lines for better maintainability", threshold).dimmed());
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drilling(files: &[(&str, &str)]) -> Vec<PropDrilling> {
+        let flows: Vec<PropFlow> = files.iter().flat_map(|(file, content)| prop_flows(content, file)).collect();
+        find_prop_drilling(&flows)
+    }
+
+    #[test]
+    fn test_prop_drilled_through_components() {
+        let chains = drilling(&[
+            ("app/page.tsx", "export default function Page() {\n  const user = useUser();\n  return <Layout user={user} title=\"Home\" />;\n}\n"),
+            ("components/Layout.tsx", "export function Layout({ user, title }) {\n  return (\n    <div>\n      <h1>{title}</h1>\n      <Sidebar user={user} />\n    </div>\n  );\n}\n"),
+            ("components/Sidebar.tsx", "export const Sidebar = ({ user: account }: Props) => (\n  <nav>\n    <Avatar user={account} />\n  </nav>\n);\n"),
+            ("components/Avatar.tsx", "export function Avatar(props) {\n  return <img src={props.user.avatar} alt=\"\" />;\n}\n"),
+        ]);
+
+        assert_eq!(chains.len(), 1);
+        let chain: Vec<(&str, &str, usize)> = chains[0].chain.iter().map(|step| (step.component.as_str(), step.prop.as_str(), step.line)).collect();
+        assert_eq!(chain, vec![("Page", "user", 3), ("Layout", "user", 5), ("Sidebar", "user", 3), ("Avatar", "user", 1)]);
+        assert!(chains[0].pass_through);
+        assert!(chains[0].suggestion.contains("useContext in Avatar"));
+    }
+
+    #[test]
+    fn test_short_chains_and_composition() {
+        let shell = "export function Shell() {\n  const [theme] = useState('dark');\n  return <Panel theme={theme} />;\n}\n";
+        let panel = "export function Panel({ theme }) {\n  return <section><Button theme={theme} /></section>;\n}\n";
+        let button = "export function Button({ theme }) {\n  return <button className={theme}>Go</button>;\n}\n";
+
+        let chains = drilling(&[("Shell.tsx", shell), ("Panel.tsx", panel), ("Button.tsx", button)]);
+        assert_eq!(chains.len(), 1);
+        assert!(chains[0].suggestion.starts_with("Panel only passes `theme` on"));
+
+        // Two levels are ordinary props
+        assert!(drilling(&[("Panel.tsx", panel), ("Button.tsx", button)]).is_empty());
+
+        // An intermediate that also uses the prop still drills it, but composition would not help
+        let panel = "export function Panel({ theme }) {\n  return <section className={theme}><Button theme={theme} /></section>;\n}\n";
+        let chains = drilling(&[("Shell.tsx", shell), ("Panel.tsx", panel), ("Button.tsx", button)]);
+        assert!(!chains[0].pass_through);
+        assert!(chains[0].suggestion.contains("React context"));
+    }
+
+    #[test]
+    fn test_vue_single_file_component() {
+        let vue = r#"<template>
+  <div v-if="open">
+    <li v-for="item in items">{{ item }}</li>
+  </div>
+</template>
+
+<script setup lang="ts">
+// const ignored = ref(0)
+interface Props {
+  title: string;
+  items?: string[];
+  onSelect: (id: number) => void;
+  meta: Record<string, number>;
+}
+const props = withDefaults(defineProps<Props>(), { items: () => [] });
+const open = ref(false);
+const query = ref('');
+const filtered = computed(() => props.items.filter(i => i.includes(query.value)));
+watch(query, () => { open.value = true; });
+const { user } = useAuth();
+</script>
+"#;
+        let framework = Framework::Vue;
+        assert_eq!(count_props(vue, &framework), 4);
+        assert_eq!(count_state_variables(vue, &framework), 3);
+        assert_eq!(count_hooks(vue, &framework), 2);
+        let parts = find_extractable_parts(vue, &framework);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].name, "useOpen");
+        assert_eq!((parts[0].start_line, parts[0].end_line), (16, 20));
+
+        assert_eq!(vue_props("const props = defineProps(['title', \"count\"])"), vec!["title", "count"]);
+        assert_eq!(vue_props("export default { props: { title: String, size: { type: Number, default: 1 } } }"), vec!["title", "size"]);
+    }
+
+    #[test]
+    fn test_svelte_and_angular_components() {
+        let svelte = "<script>\n  import { writable } from 'svelte/store';\n  export let title;\n  export let count = 0;\n  let open = false;\n  const cart = writable([]);\n  $: doubled = count * 2;\n  function toggle() {\n    let next = !open;\n    open = next;\n  }\n  onMount(() => {});\n</script>\n\n{#if open}<h1>{title}</h1>{/if}\n";
+        let framework = Framework::Svelte;
+        assert_eq!(count_props(svelte, &framework), 2);
+        assert_eq!(count_state_variables(svelte, &framework), 3);
+        assert_eq!(count_hooks(svelte, &framework), 1);
+        assert_eq!(find_extractable_parts(svelte, &framework)[0].suggested_filename, "stores/cart.ts");
+        assert_eq!(count_props("<script>\n  let { title, count = 0, ...rest } = $props();\n</script>\n", &framework), 2);
+
+        let angular = "@Component({\n  selector: 'app-user-list',\n  template: `<li *ngFor=\"let u of users\">{{ u }}</li>`,\n})\nexport class UserListComponent implements OnInit {\n  @Input() title = '';\n  @Output() selected = new EventEmitter<string>();\n  filter = input<string>();\n  users: string[] = [];\n  loading = signal(false);\n  private http = inject(HttpClient);\n  select = (user: string) => this.selected.emit(user);\n\n  ngOnInit(): void {\n    const url = '/api/users';\n    this.http.get<string[]>(url).subscribe(users => this.users = users);\n  }\n}\n";
+        let framework = Framework::Angular;
+        assert_eq!(count_props(angular, &framework), 3);
+        assert_eq!(count_state_variables(angular, &framework), 2);
+        assert_eq!(count_hooks(angular, &framework), 2);
+        let parts = find_extractable_parts(angular, &framework);
+        assert_eq!(parts[0].name, "UserListService");
+        assert_eq!(parts[0].suggested_filename, "user-list.service.ts");
+    }
+
+    #[test]
+    fn test_statement_end_follows_brackets() {
+        let code = "const a = 1; const b = 2;\nuseEffect(() => {\n  run();\n}, [a]);\nconst c = [\n  1,\n];\n";
+        assert_eq!(statement_end(code, 0), 0);
+        assert_eq!(statement_end(code, 1), 3);
+        assert_eq!(statement_end(code, 4), 6);
+    }
+}
@@ -0,0 +1,401 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use crate::utils::FileUtils;
+use crate::config::{Config, ConsoleConfig};
+use crate::common::{output, AnalysisContext, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, matches_any_glob, create_annotated_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsoleReport {
+    /// Files with at least one finding, most findings first
+    pub files: Vec<FileStatements>,
+    pub summary: ConsoleSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileStatements {
+    pub file: String,
+    pub count: usize,
+    pub statements: Vec<DebugStatement>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DebugStatement {
+    pub line: usize,
+    pub column: usize,
+    pub kind: StatementKind,
+    /// What was found: `console.log`, `debugger`, `alert`, `TODO`, ...
+    pub name: String,
+    /// The source line, trimmed and shortened
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatementKind {
+    Console,
+    Debugger,
+    Alert,
+    /// A `TODO`/`FIXME` comment
+    Marker,
+}
+
+impl StatementKind {
+    /// The stable id of findings of this kind, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            StatementKind::Console => "SNF-CONSOLE-LOG",
+            StatementKind::Debugger => "SNF-CONSOLE-DEBUGGER",
+            StatementKind::Alert => "SNF-CONSOLE-ALERT",
+            StatementKind::Marker => "SNF-CONSOLE-TODO",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConsoleSummary {
+    pub files_scanned: usize,
+    pub files_with_findings: usize,
+    pub total_findings: usize,
+    /// Findings per name, e.g. `console.log: 12`
+    pub by_name: BTreeMap<String, usize>,
+    /// Files skipped because they match `[console] allowlist`
+    pub allowlisted_files: usize,
+}
+
+/// Longest source excerpt shown per finding
+const MAX_TEXT_LENGTH: usize = 80;
+
+struct Patterns {
+    console: Option<Regex>,
+    debugger: Regex,
+    alert: Regex,
+    markers: Option<Regex>,
+}
+
+impl Patterns {
+    fn new(config: &ConsoleConfig) -> Self {
+        let alternatives = |words: &[String]| words.iter().map(|word| regex::escape(word)).collect::<Vec<_>>().join("|");
+        Self {
+            console: (!config.methods.is_empty())
+                .then(|| Regex::new(&format!(r"\bconsole\s*\.\s*({})\s*\(", alternatives(&config.methods))).unwrap()),
+            debugger: Regex::new(r"(?:^|[^\w$.])(debugger)\s*(?:;|$)").unwrap(),
+            alert: Regex::new(r"(?:^|[^\w$.])((?:window\s*\.\s*)?alert)\s*\(").unwrap(),
+            markers: (!config.markers.is_empty())
+                .then(|| Regex::new(&format!(r"\b({})\b", alternatives(&config.markers))).unwrap()),
+        }
+    }
+}
+
+pub async fn run(target: &ScanTarget, fail_on_found: bool, json: bool, quiet: bool) -> Result<CommandOutcome<ConsoleReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running debug statement analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, fail_on_found, &config, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "console",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_findings,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, fail_on_found, quiet))?;
+
+    tracing::info!(clean = report.summary.total_findings == 0, "debug statement analysis completed");
+
+    Ok(outcome)
+}
+
+/// Find stray debugging statements outside test files. Findings only fail the check with `fail_on_found`.
+pub fn check(target: &ScanTarget, fail_on_found: bool, config: &Config, quiet: bool) -> Result<CommandOutcome<ConsoleReport>> {
+    let scanner = FileScanner::for_command(target, "console")?;
+    let (files, allowlisted): (Vec<PathBuf>, Vec<PathBuf>) = scanner
+        .find_target_files(target, &["ts", "tsx", "js", "jsx", "mjs", "cjs", "vue", "svelte"])
+        .into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(&target.root).unwrap_or(path)))
+        .partition(|path| !is_allowlisted(path, &target.root, &config.console.allowlist));
+
+    let patterns = Patterns::new(&config.console);
+    let results: Vec<FileStatements> = FileUtils::process_files_parallel(
+        &files,
+        |path| scan_file(&target.context, path, &patterns),
+        "Scanning for debug statements",
+        quiet
+    )?;
+
+    let ignores = target.ignores()?;
+    let mut files_with_findings: Vec<FileStatements> = results.into_iter()
+        .map(|mut file| {
+            file.statements.retain(|statement| !ignores.ignores(statement.kind.rule_id(), &file.file));
+            file.count = file.statements.len();
+            file
+        })
+        .filter(|file| file.count > 0)
+        .collect();
+    files_with_findings.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.file.cmp(&b.file)));
+
+    let mut by_name = BTreeMap::new();
+    for statement in files_with_findings.iter().flat_map(|file| &file.statements) {
+        *by_name.entry(statement.name.clone()).or_insert(0) += 1;
+    }
+    let summary = ConsoleSummary {
+        files_scanned: files.len(),
+        files_with_findings: files_with_findings.len(),
+        total_findings: by_name.values().sum(),
+        by_name,
+        allowlisted_files: allowlisted.len(),
+    };
+
+    let exit_code = check_failure_threshold(fail_on_found && summary.total_findings > 0, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(ConsoleReport { files: files_with_findings, summary }, exit_code))
+}
+
+/// Patterns with a `/` match the path from the project root; others match the file name anywhere
+fn is_allowlisted(path: &Path, root: &Path, allowlist: &[String]) -> bool {
+    matches_any_glob(&FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path)), allowlist)
+}
+
+fn scan_file(context: &AnalysisContext, path: &Path, patterns: &Patterns) -> Result<FileStatements> {
+    let content = context.read(path)?;
+    let statements = scan_content(&content, patterns);
+    Ok(FileStatements {
+        file: paths::display(path),
+        count: statements.len(),
+        statements,
+    })
+}
+
+fn scan_content(content: &str, patterns: &Patterns) -> Vec<DebugStatement> {
+    let mut state = LexState::default();
+    let mut statements = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let (code, comments) = split_line(line, &mut state);
+        let mut found = |kind: StatementKind, name: String, byte: usize| {
+            statements.push(DebugStatement {
+                line: index + 1,
+                column: line[..byte].chars().count() + 1,
+                kind,
+                name,
+                text: excerpt(line),
+            });
+        };
+
+        if let Some(console) = &patterns.console {
+            for caps in console.captures_iter(&code) {
+                let start = caps.get(0).map_or(0, |m| m.start());
+                found(StatementKind::Console, format!("console.{}", &caps[1]), start);
+            }
+        }
+        for caps in patterns.debugger.captures_iter(&code) {
+            let statement = caps.get(1).unwrap();
+            found(StatementKind::Debugger, "debugger".to_string(), statement.start());
+        }
+        for caps in patterns.alert.captures_iter(&code) {
+            let call = caps.get(1).unwrap();
+            found(StatementKind::Alert, "alert".to_string(), call.start());
+        }
+        if let Some(markers) = &patterns.markers {
+            for (start, text) in &comments {
+                for caps in markers.captures_iter(text) {
+                    let marker = caps.get(1).unwrap();
+                    found(StatementKind::Marker, marker.as_str().to_string(), start + marker.start());
+                }
+            }
+        }
+    }
+    statements.sort_by_key(|statement| (statement.line, statement.column));
+    statements
+}
+
+/// Lexer state carried from one line to the next
+#[derive(Default)]
+struct LexState {
+    in_block_comment: bool,
+    in_template: bool,
+}
+
+/// Split a line into its code, with string contents blanked so columns stay put, and its
+/// comments as (byte offset, text)
+fn split_line(line: &str, state: &mut LexState) -> (String, Vec<(usize, String)>) {
+    let bytes = line.as_bytes();
+    let mut code = String::with_capacity(line.len());
+    let mut comments = Vec::new();
+    let mut quote: Option<u8> = state.in_template.then_some(b'`');
+    let mut comment_start = state.in_block_comment.then_some(0);
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if let Some(start) = comment_start {
+            if byte == b'*' && bytes.get(i + 1) == Some(&b'/') {
+                comments.push((start, line[start..i].to_string()));
+                comment_start = None;
+                code.push_str("  ");
+                i += 2;
+                continue;
+            }
+        } else if let Some(open) = quote {
+            if byte == b'\\' {
+                code.push(' ');
+                i += 1;
+            } else if byte == open {
+                quote = None;
+                code.push(byte as char);
+                i += 1;
+                continue;
+            }
+        } else if byte == b'/' && bytes.get(i + 1) == Some(&b'/') {
+            comments.push((i, line[i..].to_string()));
+            break;
+        } else if byte == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            comment_start = Some(i);
+            code.push_str("  ");
+            i += 2;
+            continue;
+        } else if matches!(byte, b'"' | b'\'' | b'`') {
+            quote = Some(byte);
+            code.push(byte as char);
+            i += 1;
+            continue;
+        } else {
+            // Copy the whole character so multi-byte text stays valid
+            let length = line[i..].chars().next().map_or(1, char::len_utf8);
+            code.push_str(&line[i..i + length]);
+            i += length;
+            continue;
+        }
+        // Inside a comment or string: keep the width, drop the text
+        let length = line[i..].chars().next().map_or(1, char::len_utf8);
+        code.push_str(&" ".repeat(length));
+        i += length;
+    }
+
+    if let Some(start) = comment_start {
+        comments.push((start, line[start..].to_string()));
+    }
+    state.in_block_comment = comment_start.is_some();
+    // Only template literals span lines; an unclosed quote is a syntax error or JSX text
+    state.in_template = quote == Some(b'`');
+    (code, comments)
+}
+
+fn excerpt(line: &str) -> String {
+    let text = line.trim();
+    if text.chars().count() <= MAX_TEXT_LENGTH {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(MAX_TEXT_LENGTH - 1).collect::<String>())
+    }
+}
+
+impl Annotate for ConsoleReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.files.iter().flat_map(|file| file.statements.iter().map(move |statement| {
+            let (level, message) = match statement.kind {
+                StatementKind::Marker => (AnnotationLevel::Notice, format!("Unfinished work: {}", statement.text)),
+                _ => (AnnotationLevel::Warning, format!("Remove `{}` before deploying", statement.name)),
+            };
+            Annotation::new(level, &file.file, statement.line, format!("Debug statement: {}", statement.name), message)
+                .with_rule(statement.kind.rule_id())
+        })).collect()
+    }
+}
+
+fn print_report(report: &ConsoleReport, fail_on_found: bool, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🐛 Debug Statement Report".bold().blue());
+        output::line("=========================".blue());
+        output::blank();
+    }
+
+    if report.files.is_empty() {
+        output::line("✅ No stray debugging statements found!".green());
+        return;
+    }
+
+    for file in &report.files {
+        output::line(format!("{} {}", file.file.cyan().bold(), format!("({})", file.count).dimmed()));
+        if quiet {
+            continue;
+        }
+        for statement in &file.statements {
+            let name = match statement.kind {
+                StatementKind::Marker => statement.name.yellow(),
+                _ => statement.name.red(),
+            };
+            output::line(format!("  {:>4}:{:<3} {}  {}", statement.line, statement.column, name, statement.text.dimmed()));
+        }
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Files with findings: {}", summary.files_with_findings));
+    let counts: Vec<String> = summary.by_name.iter().map(|(name, count)| format!("{} {}", count, name)).collect();
+    output::line(format!("  Findings: {} ({})", summary.total_findings.to_string().red(), counts.join(", ")));
+    if summary.allowlisted_files > 0 {
+        output::line(format!("  Allowlisted files: {}", summary.allowlisted_files.to_string().dimmed()));
+    }
+    output::blank();
+    output::line("💡 TIP: Route intentional logging through a logger and add it to [console] allowlist".dimmed());
+    if !fail_on_found {
+        output::line("💡 TIP: Use --fail-on-found to fail CI when debugging statements are found".dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(content: &str) -> Vec<(usize, String)> {
+        scan_content(content, &Patterns::new(&ConsoleConfig::default()))
+            .into_iter()
+            .map(|statement| (statement.line, statement.name))
+            .collect()
+    }
+
+    #[test]
+    fn test_statements_in_code_are_found() {
+        let content = "console.log('user', user);\nconsole.error('failed');\nif (x) { debugger; }\nwindow.alert(\"Saved\");\nconsole.dir(obj)\n";
+        assert_eq!(scan(content), vec![
+            (1, "console.log".to_string()),
+            (3, "debugger".to_string()),
+            (4, "alert".to_string()),
+            (5, "console.dir".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_strings_and_comments_are_not_code() {
+        let content = "const help = 'call console.log() or alert()';\n// console.log(debug)\n/* debugger;\n   TODO: remove */\nconst t = `multi\nconsole.log(x)`;\nshowAlert(); toast.alert(); const debuggerEnabled = true;\n";
+        assert_eq!(scan(content), vec![(4, "TODO".to_string())]);
+    }
+
+    #[test]
+    fn test_markers_in_comments() {
+        let statements = scan_content("const a = 1; // FIXME: handle null\n{/* TODO wire up */}\nconst TODO_LIST = [];\n", &Patterns::new(&ConsoleConfig::default()));
+        let found: Vec<(&str, usize)> = statements.iter().map(|statement| (statement.name.as_str(), statement.column)).collect();
+        assert_eq!(found, vec![("FIXME", 17), ("TODO", 5)]);
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let root = Path::new("/app");
+        let allowlist = vec!["src/lib/logger.ts".to_string(), "*.worker.js".to_string()];
+        assert!(is_allowlisted(Path::new("/app/src/lib/logger.ts"), root, &allowlist));
+        assert!(is_allowlisted(Path::new("/app/public/sw/cache.worker.js"), root, &allowlist));
+        assert!(!is_allowlisted(Path::new("/app/src/logger.ts"), root, &allowlist));
+    }
+}
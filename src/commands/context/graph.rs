@@ -0,0 +1,484 @@
+/// Module import graph for `sniff context`. Every local import (relative paths and
+/// tsconfig aliases, through the imports resolver) is resolved to a project file.
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use crate::common::{AnalysisContext, FileScanner, read_source};
+use crate::commands::imports_analyzer::{PathAliasResolver, dynamic_imports, resolve_local_import};
+use crate::common::source::strip_comments;
+use crate::utils::FileUtils;
+use super::{FileCoupling, FileRelationships, extract_jsx_children};
+
+const SOURCE_EXTENSIONS: [&str; 4] = ["ts", "tsx", "js", "jsx"];
+
+/// Files whose imports count when looking for used exports
+const IMPORTER_EXTENSIONS: [&str; 9] = ["ts", "tsx", "js", "jsx", "mjs", "cjs", "vue", "svelte", "astro"];
+
+/// Entries kept in `most_imported`
+const MOST_IMPORTED: usize = 10;
+
+struct ImportPatterns {
+    /// `import X, { Y as Z } from '...'` and `export { X } from '...'`, across lines
+    from_clause: Regex,
+    /// `import './styles.css'`
+    bare: Regex,
+}
+
+static IMPORT_PATTERNS: OnceLock<ImportPatterns> = OnceLock::new();
+
+fn patterns() -> &'static ImportPatterns {
+    IMPORT_PATTERNS.get_or_init(|| ImportPatterns {
+        from_clause: Regex::new(r#"(?m)^\s*(import|export)\s+([^'";]*?)\s*from\s*['"]([^'"]+)['"]"#).unwrap(),
+        bare: Regex::new(r#"(?m)^\s*import\s*['"]([^'"]+)['"]"#).unwrap(),
+    })
+}
+
+struct Import {
+    specifier: String,
+    /// Local names bound by the import, used to map rendered JSX back to files
+    bindings: Vec<String>,
+    /// Exported names taken from the module; `None` when it is used as a whole
+    names: Option<Vec<String>>,
+}
+
+pub(super) fn analyze(project_dir: &Path) -> Result<FileRelationships> {
+    let resolver = PathAliasResolver::from_project_root(project_dir);
+    let mut files = FileScanner::for_root(project_dir)?.find_files_with_extensions(project_dir, &SOURCE_EXTENSIONS);
+    files.sort();
+
+    let mut import_graph: HashMap<String, Vec<String>> = HashMap::new();
+    let mut component_hierarchy: HashMap<String, Vec<String>> = HashMap::new();
+
+    for file in &files {
+        let Ok(Some(content)) = read_source(file) else {
+            continue;
+        };
+        let from = relative(file, project_dir);
+        let mut targets = BTreeSet::new();
+        let mut components: HashMap<String, String> = HashMap::new();
+
+        for (to, import) in local_imports(file, &parse_imports(&content), project_dir, &resolver) {
+            for binding in &import.bindings {
+                components.insert(binding.clone(), to.clone());
+            }
+            targets.insert(to);
+        }
+
+        if matches!(file.extension().and_then(|ext| ext.to_str()), Some("tsx" | "jsx")) {
+            let children: BTreeSet<String> = extract_jsx_children(&content)
+                .iter()
+                .filter_map(|child| components.get(child).cloned())
+                .collect();
+            if !children.is_empty() {
+                component_hierarchy.insert(from.clone(), children.into_iter().collect());
+            }
+        }
+        import_graph.insert(from, targets.into_iter().collect());
+    }
+
+    let coupling = coupling(&import_graph);
+    let mut most_imported: Vec<(String, usize)> = coupling.iter()
+        .filter(|(_, counts)| counts.fan_in > 0)
+        .map(|(file, counts)| (file.clone(), counts.fan_in))
+        .collect();
+    most_imported.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    most_imported.truncate(MOST_IMPORTED);
+
+    Ok(FileRelationships {
+        circular_dependencies: cycles(&import_graph),
+        import_graph,
+        component_hierarchy,
+        most_imported,
+        coupling,
+    })
+}
+
+/// The names each file takes from the project files it imports, keyed by the imported file
+/// relative to `project_dir`; `None` when some file uses it as a whole (`import * as`,
+/// `export *`, `import()` or `require`)
+pub fn imported_symbols(project_dir: &Path, context: &Arc<AnalysisContext>) -> Result<HashMap<String, Option<HashSet<String>>>> {
+    let resolver = PathAliasResolver::from_project_root(project_dir);
+    let files = FileScanner::for_root(project_dir)?.sharing(context).find_files_with_extensions(project_dir, &IMPORTER_EXTENSIONS);
+
+    let mut symbols: HashMap<String, Option<HashSet<String>>> = HashMap::new();
+    for file in &files {
+        let Ok(Some(content)) = context.read_source(file) else {
+            continue;
+        };
+        let imports = context.parsed(file, &content, parse_imports);
+        for (to, import) in local_imports(file, &imports, project_dir, &resolver) {
+            let used = symbols.entry(to).or_insert_with(|| Some(HashSet::new()));
+            match (used.as_mut(), &import.names) {
+                (Some(used), Some(names)) => used.extend(names.iter().cloned()),
+                _ => *used = None,
+            }
+        }
+    }
+    Ok(symbols)
+}
+
+/// The imports of `file` that resolve to files inside `project_dir`, with the imported path
+fn local_imports<'a>(file: &Path, imports: &'a [Import], project_dir: &Path, resolver: &Option<PathAliasResolver>) -> Vec<(String, &'a Import)> {
+    imports.iter()
+        .filter_map(|import| {
+            let resolved = resolve_local_import(file, &import.specifier, resolver)?;
+            // Aliases can point outside the project (e.g. a sibling workspace package)
+            let inside = resolved.strip_prefix(project_dir).ok()?;
+            Some((FileUtils::slash_path(inside), import))
+        })
+        .collect()
+}
+
+fn relative(file: &Path, project_dir: &Path) -> String {
+    FileUtils::slash_path(file.strip_prefix(project_dir).unwrap_or(file))
+}
+
+/// Static, side-effect and dynamic imports (`import()`, `require()`, also inside `next/dynamic`
+/// and `React.lazy`), outside comments
+fn parse_imports(content: &str) -> Vec<Import> {
+    let patterns = patterns();
+    let code = strip_comments(content);
+    let content = code.as_str();
+    let mut imports: Vec<Import> = patterns.from_clause.captures_iter(content)
+        .map(|caps| Import {
+            specifier: caps[3].to_string(),
+            bindings: if &caps[1] == "import" { bindings(&caps[2]) } else { Vec::new() },
+            names: imported_names(&caps[2]),
+        })
+        .collect();
+    let bare = patterns.bare.captures_iter(content).filter_map(|caps| caps.get(1)).map(|specifier| specifier.as_str());
+    imports.extend(bare.chain(dynamic_imports(content).into_iter().map(|(_, specifier)| specifier)).map(|specifier| Import {
+        specifier: specifier.to_string(),
+        bindings: Vec::new(),
+        names: None,
+    }));
+    imports
+}
+
+/// `Default, { Named, Other as Alias }` and `* as Namespace` → the local names
+fn bindings(clause: &str) -> Vec<String> {
+    clause.trim_start_matches("type ")
+        .split([',', '{', '}'])
+        .filter_map(|item| {
+            let name = item.split_whitespace().last()?;
+            (name != "type" && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')).then(|| name.to_string())
+        })
+        .collect()
+}
+
+/// `Default, { Named, Other as Alias }` → `default`, `Named`, `Other`; `None` for `*` clauses
+fn imported_names(clause: &str) -> Option<Vec<String>> {
+    let clause = clause.strip_prefix("type ").unwrap_or(clause).trim();
+    if clause.starts_with('*') {
+        return None;
+    }
+    let (default, named) = match clause.split_once('{') {
+        Some((default, named)) => (default, named.trim_end_matches(|c: char| c == '}' || c.is_whitespace())),
+        None => (clause, ""),
+    };
+    let mut names: Vec<String> = named.split(',')
+        .filter_map(|item| {
+            let item = item.trim();
+            let item = item.strip_prefix("type ").unwrap_or(item);
+            item.split_whitespace().next().map(str::to_string)
+        })
+        .collect();
+    if !default.trim().trim_end_matches(',').trim().is_empty() {
+        names.push("default".to_string());
+    }
+    Some(names)
+}
+
+/// Fan-in (files importing this one) and fan-out (local files it imports) for every file
+fn coupling(graph: &HashMap<String, Vec<String>>) -> HashMap<String, FileCoupling> {
+    let mut coupling: HashMap<String, FileCoupling> = graph.iter()
+        .map(|(file, targets)| (file.clone(), FileCoupling { fan_in: 0, fan_out: targets.len() }))
+        .collect();
+    for targets in graph.values() {
+        for target in targets {
+            coupling.entry(target.clone()).or_insert(FileCoupling { fan_in: 0, fan_out: 0 }).fan_in += 1;
+        }
+    }
+    coupling
+}
+
+/// Import cycles as the strongly connected components of the graph (Tarjan),
+/// each listed from its alphabetically first file
+fn cycles(graph: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        graph: &'a HashMap<String, Vec<String>>,
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: BTreeSet<&'a str>,
+        components: Vec<Vec<String>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, node: &'a str) {
+            let next = self.index.len();
+            self.index.insert(node, next);
+            self.low.insert(node, next);
+            self.stack.push(node);
+            self.on_stack.insert(node);
+
+            for target in self.graph.get(node).into_iter().flatten() {
+                let target = target.as_str();
+                if !self.index.contains_key(target) {
+                    self.visit(target);
+                    let low = self.low[node].min(self.low[target]);
+                    self.low.insert(node, low);
+                } else if self.on_stack.contains(target) {
+                    let low = self.low[node].min(self.index[target]);
+                    self.low.insert(node, low);
+                }
+            }
+
+            if self.low[node] == self.index[node] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == node {
+                        break;
+                    }
+                }
+                let self_import = self.graph.get(node).is_some_and(|targets| targets.iter().any(|target| target == node));
+                if component.len() > 1 || self_import {
+                    component.sort();
+                    self.components.push(component);
+                }
+            }
+        }
+    }
+
+    let mut nodes: Vec<&str> = graph.keys().map(String::as_str).collect();
+    nodes.sort();
+    let mut tarjan = Tarjan {
+        graph,
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: BTreeSet::new(),
+        components: Vec::new(),
+    };
+    for node in nodes {
+        if !tarjan.index.contains_key(node) {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components.sort();
+    tarjan.components
+}
+
+#[derive(Serialize)]
+struct GraphFile<'a> {
+    nodes: Vec<GraphNode<'a>>,
+    edges: Vec<GraphEdge<'a>>,
+    cycles: &'a [Vec<String>],
+}
+
+#[derive(Serialize)]
+struct GraphNode<'a> {
+    id: &'a str,
+    fan_in: usize,
+    fan_out: usize,
+}
+
+#[derive(Serialize)]
+struct GraphEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+/// Write the graph as `{ nodes, edges, cycles }` JSON, sorted so runs diff cleanly
+pub fn write_json(relationships: &FileRelationships, path: &Path) -> Result<()> {
+    let mut nodes: Vec<GraphNode> = relationships.coupling.iter()
+        .map(|(id, counts)| GraphNode { id, fan_in: counts.fan_in, fan_out: counts.fan_out })
+        .collect();
+    nodes.sort_by(|a, b| a.id.cmp(b.id));
+    let mut edges: Vec<GraphEdge> = relationships.import_graph.iter()
+        .flat_map(|(from, targets)| targets.iter().map(move |to| GraphEdge { from, to }))
+        .collect();
+    edges.sort_by(|a, b| (a.from, a.to).cmp(&(b.from, b.to)));
+
+    let graph = GraphFile { nodes, edges, cycles: &relationships.circular_dependencies };
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&graph)?).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Diagram formats for `sniff context --graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    /// Mermaid flowchart, renders in GitHub Markdown
+    Mermaid,
+    /// Graphviz DOT
+    Dot,
+}
+
+/// Limit a diagram to files under `dir` and/or files reachable from `entry`
+#[derive(Debug, Default)]
+pub struct GraphScope {
+    pub dir: Option<String>,
+    pub entry: Option<String>,
+}
+
+/// Render the import graph as a diagram; files in an import cycle are highlighted
+pub fn render(relationships: &FileRelationships, format: GraphFormat, scope: &GraphScope) -> Result<String> {
+    let nodes = scoped_nodes(relationships, scope)?;
+    let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, node)| (*node, i)).collect();
+    let edges: Vec<(usize, usize)> = nodes.iter()
+        .flat_map(|from| {
+            let index = &index;
+            relationships.import_graph.get(*from).into_iter().flatten()
+                .filter_map(move |to| Some((index[from], *index.get(to.as_str())?)))
+        })
+        .collect();
+    let in_cycle: BTreeSet<usize> = relationships.circular_dependencies.iter()
+        .flatten()
+        .filter_map(|file| index.get(file.as_str()).copied())
+        .collect();
+
+    let mut out = String::new();
+    match format {
+        GraphFormat::Mermaid => {
+            out.push_str("graph LR\n");
+            for (i, node) in nodes.iter().enumerate() {
+                out.push_str(&format!("  n{}[\"{}\"]\n", i, node.replace('"', "#quot;")));
+            }
+            for (from, to) in &edges {
+                out.push_str(&format!("  n{} --> n{}\n", from, to));
+            }
+            if !in_cycle.is_empty() {
+                let ids: Vec<String> = in_cycle.iter().map(|i| format!("n{}", i)).collect();
+                out.push_str("  classDef cycle stroke:#cf222e,stroke-width:2px\n");
+                out.push_str(&format!("  class {} cycle\n", ids.join(",")));
+            }
+        }
+        GraphFormat::Dot => {
+            let quote = |node: &str| format!("\"{}\"", node.replace('\\', "\\\\").replace('"', "\\\""));
+            out.push_str("digraph imports {\n  rankdir=LR;\n  node [shape=box, fontname=\"Helvetica\"];\n");
+            for (i, node) in nodes.iter().enumerate() {
+                let style = if in_cycle.contains(&i) { " [color=\"#cf222e\"]" } else { "" };
+                out.push_str(&format!("  {}{};\n", quote(node), style));
+            }
+            for (from, to) in &edges {
+                out.push_str(&format!("  {} -> {};\n", quote(nodes[*from]), quote(nodes[*to])));
+            }
+            out.push_str("}\n");
+        }
+    }
+    Ok(out)
+}
+
+/// Files to draw, sorted
+fn scoped_nodes<'a>(relationships: &'a FileRelationships, scope: &GraphScope) -> Result<Vec<&'a str>> {
+    let graph = &relationships.import_graph;
+    let mut nodes: BTreeSet<&str> = match &scope.entry {
+        Some(entry) => {
+            let entry = normalize(entry);
+            let (start, _) = graph.get_key_value(entry.as_str())
+                .ok_or_else(|| anyhow!("Entry point '{}' is not a source file in the import graph", entry))?;
+            // Breadth-first over imports from the entry point
+            let mut reachable = BTreeSet::from([start.as_str()]);
+            let mut queue = VecDeque::from([start.as_str()]);
+            while let Some(file) = queue.pop_front() {
+                for target in graph.get(file).into_iter().flatten() {
+                    if reachable.insert(target.as_str()) {
+                        queue.push_back(target.as_str());
+                    }
+                }
+            }
+            reachable
+        }
+        None => graph.iter()
+            .flat_map(|(from, targets)| std::iter::once(from).chain(targets))
+            .map(String::as_str)
+            .collect(),
+    };
+    if let Some(dir) = &scope.dir {
+        let dir = normalize(dir);
+        let prefix = format!("{}/", dir.trim_end_matches('/'));
+        nodes.retain(|node| dir.is_empty() || node.starts_with(&prefix));
+    }
+    Ok(nodes.into_iter().collect())
+}
+
+/// `./src/app/` → `src/app`, matching the graph's project-relative paths
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let path = path.trim_start_matches("./").trim_end_matches('/');
+    if path == "." { String::new() } else { path.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        edges.iter()
+            .map(|(from, targets)| (from.to_string(), targets.iter().map(|to| to.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_imports_handles_multiline_and_bindings() {
+        let content = "import React, {\n  useState,\n  Button as PrimaryButton,\n} from './ui';\nimport * as api from \"../api\";\nimport './globals.css';\nconst Chart = dynamic(() => import('./Chart'));\nexport { helper } from './helpers';\nconst Page = React.lazy(() => import(`./Page`));\nconst config = require('./config');\n// const Old = import('./Old');\nconst route = import(`./routes/${name}`);\n";
+        let imports = parse_imports(content);
+        let specifiers: Vec<&str> = imports.iter().map(|import| import.specifier.as_str()).collect();
+        assert_eq!(specifiers, vec!["./ui", "../api", "./helpers", "./globals.css", "./Chart", "./Page", "./config"]);
+        assert_eq!(imports[0].bindings, vec!["React", "useState", "PrimaryButton"]);
+        assert_eq!(imports[1].bindings, vec!["api"]);
+    }
+
+    #[test]
+    fn test_coupling_and_cycles() {
+        let graph = graph(&[
+            ("a.ts", &["b.ts", "c.ts"]),
+            ("b.ts", &["c.ts"]),
+            ("c.ts", &["a.ts"]),
+            ("d.ts", &["c.ts", "d.ts"]),
+        ]);
+        let coupling = coupling(&graph);
+        assert_eq!((coupling["c.ts"].fan_in, coupling["c.ts"].fan_out), (3, 1));
+        assert_eq!((coupling["a.ts"].fan_in, coupling["a.ts"].fan_out), (1, 2));
+        assert_eq!(cycles(&graph), vec![vec!["a.ts", "b.ts", "c.ts"], vec!["d.ts"]]);
+    }
+
+    #[test]
+    fn test_render_diagrams_with_scope() {
+        let import_graph = graph(&[
+            ("app/page.tsx", &["components/Card.tsx"]),
+            ("components/Card.tsx", &["components/Button.tsx"]),
+            ("components/Button.tsx", &[]),
+            ("lib/unused.ts", &["components/Button.tsx"]),
+        ]);
+        let relationships = FileRelationships {
+            coupling: coupling(&import_graph),
+            circular_dependencies: cycles(&import_graph),
+            import_graph,
+            component_hierarchy: HashMap::new(),
+            most_imported: Vec::new(),
+        };
+
+        let entry = GraphScope { dir: None, entry: Some("./app/page.tsx".to_string()) };
+        let mermaid = render(&relationships, GraphFormat::Mermaid, &entry).unwrap();
+        assert_eq!(mermaid, "graph LR\n  n0[\"app/page.tsx\"]\n  n1[\"components/Button.tsx\"]\n  n2[\"components/Card.tsx\"]\n  n0 --> n2\n  n2 --> n1\n");
+
+        let dir = GraphScope { dir: Some("components/".to_string()), entry: None };
+        let dot = render(&relationships, GraphFormat::Dot, &dir).unwrap();
+        assert!(dot.starts_with("digraph imports {"));
+        assert!(dot.contains("  \"components/Card.tsx\" -> \"components/Button.tsx\";\n"));
+        assert!(!dot.contains("app/page.tsx"));
+
+        let missing = GraphScope { dir: None, entry: Some("nope.ts".to_string()) };
+        assert!(render(&relationships, GraphFormat::Dot, &missing).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+/// Compact Markdown digest of the context report for `sniff context --format llm`.
+/// Output is deterministic (everything sorted, no timestamps) and capped at a token budget.
+use std::collections::HashMap;
+use super::{ContextReport, DirectoryPurpose, PackageJsonInfo, Rendering};
+
+/// Rough characters per token for source-like English text
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Directories deeper than this are left out of the file tree
+const TREE_DEPTH: usize = 2;
+
+struct Section {
+    title: &'static str,
+    lines: Vec<String>,
+}
+
+pub fn render(report: &ContextReport, max_tokens: usize) -> String {
+    let budget = max_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let mut output = header(report);
+
+    for section in sections(report) {
+        if section.lines.is_empty() {
+            continue;
+        }
+        let title = format!("\n## {}\n", section.title);
+        // Room for the title, one line and a "more" marker, or the section is dropped
+        if output.len() + title.len() + 40 > budget {
+            break;
+        }
+        output.push_str(&title);
+        let total = section.lines.len();
+        for (shown, line) in section.lines.into_iter().enumerate() {
+            let marker = format!("- … {} more\n", total - shown);
+            if output.len() + line.len() + 1 + marker.len() > budget {
+                output.push_str(&marker);
+                break;
+            }
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+fn header(report: &ContextReport) -> String {
+    let info = &report.project_info;
+    let mut languages: Vec<String> = info.languages.iter().map(|language| format!("{:?}", language)).collect();
+    languages.sort();
+    let mut header = format!("# {}", info.name);
+    if let Some(version) = &info.version {
+        header.push_str(&format!(" {}", version));
+    }
+    header.push('\n');
+    if let Some(description) = info.description.as_deref().filter(|description| !description.is_empty()) {
+        header.push_str(&format!("{}\n", description));
+    }
+    header.push_str(&format!(
+        "{:?} · {} · {} files, {} lines · {:?} complexity\n",
+        info.framework,
+        languages.join(", "),
+        info.total_files,
+        info.total_lines,
+        report.architecture.complexity_level
+    ));
+    header
+}
+
+/// Sections in priority order; later ones are cut first when the budget runs out
+fn sections(report: &ContextReport) -> Vec<Section> {
+    vec![
+        Section { title: "Conventions", lines: conventions(report) },
+        Section { title: "Routes", lines: routes(report) },
+        Section { title: "Key components", lines: key_components(report) },
+        Section { title: "File tree", lines: file_tree(report) },
+        Section { title: "Dependencies", lines: dependencies(report.dependencies.package_json.as_ref()) },
+    ]
+}
+
+fn conventions(report: &ContextReport) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut patterns: Vec<String> = report.architecture.patterns.iter().map(|pattern| format!("{:?}", pattern)).collect();
+    patterns.sort();
+    if !patterns.is_empty() {
+        lines.push(format!("- Patterns: {}", patterns.join(", ")));
+    }
+    if let Some(package) = &report.dependencies.package_json {
+        let mut scripts: Vec<(&String, &String)> = package.scripts.iter().collect();
+        scripts.sort();
+        for (name, command) in scripts {
+            lines.push(format!("- `npm run {}`: `{}`", name, command));
+        }
+    }
+    let mut hooks: HashMap<&str, usize> = HashMap::new();
+    for component in &report.structure.components {
+        for hook in &component.hooks_used {
+            *hooks.entry(hook.as_str()).or_default() += 1;
+        }
+    }
+    let mut hooks: Vec<(&str, usize)> = hooks.into_iter().collect();
+    hooks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    if !hooks.is_empty() {
+        let hooks: Vec<&str> = hooks.iter().take(8).map(|(hook, _)| *hook).collect();
+        lines.push(format!("- Common hooks: {}", hooks.join(", ")));
+    }
+    lines
+}
+
+fn routes(report: &ContextReport) -> Vec<String> {
+    let mut pages: Vec<String> = report.structure.pages.iter().map(|page| {
+        let rendering = match page.rendering {
+            Rendering::Static => String::new(),
+            rendering => format!(" ({:?})", rendering).to_uppercase(),
+        };
+        format!("- `{}` → {}{}{}", page.route, clean_path(&page.path), rendering, middleware(&page.middleware))
+    }).collect();
+    pages.sort();
+
+    let mut api: Vec<String> = report.structure.api_routes.iter().map(|route| {
+        format!("- API `{}` [{}] → {}{}", route.route, route.methods.join(", "), clean_path(&route.path), middleware(&route.middleware))
+    }).collect();
+    api.sort();
+
+    pages.extend(api);
+    pages
+}
+
+fn middleware(names: &[String]) -> String {
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!(" via {}", names.join(", "))
+    }
+}
+
+/// Components ordered by how many files import them, then by name
+fn key_components(report: &ContextReport) -> Vec<String> {
+    let coupling = &report.relationships.coupling;
+    let import_count = |path: &str| coupling.iter()
+        .find(|(file, _)| path == file.as_str() || path.ends_with(&format!("/{}", file)))
+        .map_or(0, |(_, counts)| counts.fan_in);
+
+    let mut components: Vec<(usize, String)> = report.structure.components.iter().map(|component| {
+        let mut details = vec![format!("{:?}", component.component_type)];
+        if component.props_count > 0 {
+            details.push(format!("{} props", component.props_count));
+        }
+        if !component.hooks_used.is_empty() {
+            let mut hooks = component.hooks_used.clone();
+            hooks.sort();
+            hooks.dedup();
+            details.push(hooks.join(", "));
+        }
+        let line = format!("- {} ({}): {}", component.name, clean_path(&component.path), details.join("; "));
+        (import_count(&component.path), line)
+    }).collect();
+    components.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    components.into_iter().map(|(_, line)| line).collect()
+}
+
+fn file_tree(report: &ContextReport) -> Vec<String> {
+    let mut directories: Vec<_> = report.structure.directories.iter()
+        .filter(|directory| !matches!(directory.purpose, DirectoryPurpose::Build))
+        // Tool state such as `.sniff/` changes between runs and says nothing about the code
+        .filter(|directory| !directory.path.split('/').any(|part| part.starts_with('.')))
+        .filter(|directory| directory.path.split('/').count() <= TREE_DEPTH)
+        .collect();
+    directories.sort_by(|a, b| a.path.cmp(&b.path));
+    directories.into_iter().map(|directory| {
+        let depth = directory.path.split('/').count() - 1;
+        let name = directory.path.rsplit('/').next().unwrap_or(&directory.path);
+        let purpose = match directory.purpose {
+            DirectoryPurpose::Other => String::new(),
+            ref purpose => format!(" — {:?}", purpose),
+        };
+        format!("{}- {}/ ({} files){}", "  ".repeat(depth), name, directory.file_count, purpose)
+    }).collect()
+}
+
+fn dependencies(package: Option<&PackageJsonInfo>) -> Vec<String> {
+    let Some(package) = package else {
+        return Vec::new();
+    };
+    let list = |dependencies: &HashMap<String, String>| {
+        let mut names: Vec<String> = dependencies.iter().map(|(name, version)| format!("{}@{}", name, version)).collect();
+        names.sort();
+        names.join(", ")
+    };
+    let mut lines = Vec::new();
+    if !package.dependencies.is_empty() {
+        lines.push(format!("- Runtime: {}", list(&package.dependencies)));
+    }
+    if !package.dev_dependencies.is_empty() {
+        lines.push(format!("- Dev: {}", list(&package.dev_dependencies)));
+    }
+    lines
+}
+
+fn clean_path(path: &str) -> &str {
+    path.strip_prefix("./").unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::*;
+    use crate::commands::routes::{Router, Side};
+
+    fn report(pages: usize) -> ContextReport {
+        ContextReport {
+            project_info: ProjectInfo {
+                name: "shop".to_string(),
+                version: Some("1.0.0".to_string()),
+                description: None,
+                framework: Framework::NextJs,
+                languages: vec![Language::TypeScript],
+                total_files: 10,
+                total_lines: 500,
+            },
+            structure: ProjectStructure {
+                directories: Vec::new(),
+                components: Vec::new(),
+                pages: (0..pages).map(|i| PageInfo {
+                    name: format!("page{}", i),
+                    path: format!("app/page{:03}/page.tsx", i),
+                    route: format!("/page{:03}", i),
+                    router: Router::App,
+                    rendering: Rendering::Static,
+                    side: Side::Server,
+                    has_ssr: false,
+                    has_ssg: false,
+                    dynamic_segments: Vec::new(),
+                    route_groups: Vec::new(),
+                    api_calls: Vec::new(),
+                    middleware: Vec::new(),
+                    auth: Vec::new(),
+                }).collect(),
+                api_routes: Vec::new(),
+                middleware: None,
+                utilities: Vec::new(),
+            },
+            dependencies: DependencyAnalysis {
+                package_json: None,
+                imports: HashMap::new(),
+                exports: HashMap::new(),
+                external_dependencies: Vec::new(),
+            },
+            architecture: ArchitectureInsights {
+                patterns: Vec::new(),
+                organization_score: 80.0,
+                complexity_level: ComplexityLevel::Simple,
+                complexity: Default::default(),
+                recommendations: Vec::new(),
+            },
+            relationships: FileRelationships {
+                import_graph: HashMap::new(),
+                component_hierarchy: HashMap::new(),
+                most_imported: Vec::new(),
+                circular_dependencies: Vec::new(),
+                coupling: HashMap::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_render_is_compact_and_respects_token_budget() {
+        let digest = render(&report(2), 1000);
+        assert!(digest.starts_with("# shop 1.0.0\nNextJs · TypeScript · 10 files, 500 lines · Simple complexity\n"));
+        assert!(digest.contains("## Routes\n- `/page000` → app/page000/page.tsx\n- `/page001` → app/page001/page.tsx\n"));
+
+        let digest = render(&report(500), 200);
+        assert!(digest.len() <= 200 * CHARS_PER_TOKEN);
+        assert!(digest.trim_end().ends_with("more"));
+        assert_eq!(digest, render(&report(500), 200));
+    }
+}
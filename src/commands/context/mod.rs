@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -6,7 +6,15 @@ use std::fs;
 use std::path::Path;
 use walkdir::WalkDir;
 use crate::utils::FileUtils;
-use crate::common::OptimizedFileWalker;
+use crate::common::{output, OptimizedFileWalker, ExitCode, CommandOutcome, ScanTarget, count_lines_optimized, create_standard_json_output, output_result, read_source};
+use crate::commands::history::Metrics;
+use crate::commands::routes;
+use crate::commands::complexity::{self, ComplexitySummary};
+pub use crate::commands::routes::{ApiRouteInfo, MiddlewareInfo, PageInfo, Rendering};
+
+pub mod llm;
+mod graph;
+pub use graph::{GraphFormat, GraphScope, imported_symbols};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ContextReport {
@@ -34,6 +42,7 @@ pub struct ProjectStructure {
     pub components: Vec<ComponentInfo>,
     pub pages: Vec<PageInfo>,
     pub api_routes: Vec<ApiRouteInfo>,
+    pub middleware: Option<MiddlewareInfo>,
     pub utilities: Vec<UtilityInfo>,
 }
 
@@ -50,15 +59,29 @@ pub struct ArchitectureInsights {
     pub patterns: Vec<ArchitecturePattern>,
     pub organization_score: f64,
     pub complexity_level: ComplexityLevel,
+    /// Function complexity across the project, as measured by `sniff complexity`
+    pub complexity: ComplexitySummary,
     pub recommendations: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FileRelationships {
+    /// Each file and the local files it imports, relative to the project directory
     pub import_graph: HashMap<String, Vec<String>>,
+    /// Each component file and the imported component files it renders
     pub component_hierarchy: HashMap<String, Vec<String>>,
+    /// Files with the highest fan-in
     pub most_imported: Vec<(String, usize)>,
     pub circular_dependencies: Vec<Vec<String>>,
+    pub coupling: HashMap<String, FileCoupling>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileCoupling {
+    /// Files that import this one
+    pub fan_in: usize,
+    /// Local files this one imports
+    pub fan_out: usize,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -80,24 +103,6 @@ pub struct ComponentInfo {
     pub children_components: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct PageInfo {
-    pub name: String,
-    pub path: String,
-    pub route: String,
-    pub has_ssr: bool,
-    pub has_ssg: bool,
-    pub api_calls: Vec<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ApiRouteInfo {
-    pub path: String,
-    pub methods: Vec<String>,
-    pub middleware: Vec<String>,
-    pub database_operations: Vec<String>,
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UtilityInfo {
     pub path: String,
@@ -219,58 +224,88 @@ pub enum ComplexityLevel {
     VeryComplex,
 }
 
-pub async fn run(json: bool, quiet: bool) -> Result<()> {
-    if !quiet {
-        println!("{}", "🔍 Analyzing project structure and context...".bold().blue());
+pub async fn run(target: &ScanTarget, graph_out: Option<&Path>, json: bool, quiet: bool) -> Result<CommandOutcome<ContextReport>> {
+    tracing::info!("Running context analysis");
+    
+    let start_time = std::time::Instant::now();
+    let outcome = check(target).await?;
+    if let Some(path) = graph_out {
+        write_graph(&outcome.report, path)?;
     }
+    let report = &outcome.report;
     
-    let report = analyze_project_context(quiet).await?;
+    let response = create_standard_json_output(
+        "context",
+        report,
+        report.project_info.total_files,
+        0,
+        Some(start_time.elapsed().as_millis() as u64),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
     
-    if json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        print_report(&report, quiet);
+    Ok(outcome)
+}
+
+/// Write the import graph (`--graph-out`) as JSON
+pub fn write_graph(report: &ContextReport, path: &Path) -> Result<()> {
+    graph::write_json(&report.relationships, path)
+}
+
+/// Print the import graph as a Mermaid or DOT diagram (`--graph`), or write it to `out`
+pub fn export_graph(report: &ContextReport, format: GraphFormat, scope: &GraphScope, out: Option<&Path>, quiet: bool) -> Result<()> {
+    let diagram = graph::render(&report.relationships, format, scope)?;
+    match out {
+        Some(path) => {
+            if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, diagram).with_context(|| format!("Failed to write {}", path.display()))?;
+            if !quiet {
+                output::line(format!("📄 Graph written to {}", path.display()).green());
+            }
+        }
+        None => print!("{}", diagram),
     }
-    
     Ok(())
 }
 
-async fn analyze_project_context(quiet: bool) -> Result<ContextReport> {
-    let current_dir = std::env::current_dir()?;
+/// Analyze the project without printing the report
+pub async fn check(target: &ScanTarget) -> Result<CommandOutcome<ContextReport>> {
+    let report = analyze_project_context(target.project_dir("context")?).await?;
+    // Context analysis is informational and never fails the run
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+impl Metrics for ContextReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![("organization_score", self.architecture.organization_score)]
+    }
+}
+
+async fn analyze_project_context(project_dir: &Path) -> Result<ContextReport> {
     
     // Analyze project info
-    if !quiet {
-        println!("📁 Analyzing project information...");
-    }
-    let project_info = analyze_project_info(&current_dir).await?;
+    tracing::debug!("Analyzing project information");
+    let project_info = analyze_project_info(project_dir).await?;
     
     // Analyze project structure
-    if !quiet {
-        println!("🏢 Analyzing project structure...");
-    }
-    let structure = analyze_project_structure(&current_dir).await?;
+    tracing::debug!("Analyzing project structure");
+    let structure = analyze_project_structure(project_dir).await?;
     
     // Analyze dependencies
-    if !quiet {
-        println!("📦 Analyzing dependencies...");
-    }
-    let dependencies = analyze_dependencies(&current_dir).await?;
+    tracing::debug!("Analyzing dependencies");
+    let dependencies = analyze_dependencies(project_dir).await?;
     
     // Generate architecture insights
-    if !quiet {
-        println!("🎨 Generating architecture insights...");
-    }
-    let architecture = generate_architecture_insights(&structure, &dependencies).await?;
+    tracing::debug!("Generating architecture insights");
+    let complexity = complexity::summarize(project_dir)?;
+    let architecture = generate_architecture_insights(&structure, &dependencies, complexity).await?;
     
     // Analyze file relationships
-    if !quiet {
-        println!("🔗 Analyzing file relationships...");
-    }
-    let relationships = analyze_file_relationships(&current_dir).await?;
+    tracing::debug!("Analyzing file relationships");
+    let relationships = graph::analyze(project_dir)?;
     
-    if !quiet {
-        println!("✅ Context analysis completed");
-    }
+    tracing::info!("Context analysis completed");
     
     Ok(ContextReport {
         project_info,
@@ -317,7 +352,7 @@ async fn analyze_project_info(project_dir: &Path) -> Result<ProjectInfo> {
     })
 }
 
-async fn detect_framework(project_dir: &Path) -> Result<Framework> {
+pub async fn detect_framework(project_dir: &Path) -> Result<Framework> {
     let package_json_path = project_dir.join("package.json");
     
     if package_json_path.exists() {
@@ -384,22 +419,22 @@ async fn detect_languages(project_dir: &Path) -> Result<Vec<Language>> {
 async fn analyze_project_structure(project_dir: &Path) -> Result<ProjectStructure> {
     let directories = analyze_directories(project_dir).await?;
     let components = analyze_components(project_dir).await?;
-    let pages = analyze_pages(project_dir).await?;
-    let api_routes = analyze_api_routes(project_dir).await?;
+    let routes = routes::discover(project_dir);
     let utilities = analyze_utilities(project_dir).await?;
     
     Ok(ProjectStructure {
         directories,
         components,
-        pages,
-        api_routes,
+        pages: routes.pages,
+        api_routes: routes.api_routes,
+        middleware: routes.middleware,
         utilities,
     })
 }
 
 async fn analyze_directories(project_dir: &Path) -> Result<Vec<DirectoryInfo>> {
     let mut directories = Vec::new();
-    let config = crate::config::Config::load().unwrap_or_default();
+    let config = crate::config::Config::load_from_dir(project_dir)?;
 
     for entry in WalkDir::new(project_dir).max_depth(3) {
         let entry = entry?;
@@ -463,9 +498,7 @@ async fn count_directory_contents(dir_path: &Path) -> Result<(usize, usize, Vec<
                 
                 // Count lines for code files
                 if ["ts", "tsx", "js", "jsx", "css", "scss"].contains(&ext.to_string_lossy().as_ref()) {
-                    if let Ok(content) = fs::read_to_string(entry.path()) {
-                        line_count += content.lines().count();
-                    }
+                    line_count += count_lines_optimized(entry.path()).unwrap_or(0);
                 }
             }
         }
@@ -516,7 +549,7 @@ async fn analyze_components(project_dir: &Path) -> Result<Vec<ComponentInfo>> {
     let files = FileUtils::find_files_with_progress(project_dir, &extensions, true)?;
     
     for file in files {
-        if let Ok(content) = fs::read_to_string(&file) {
+        if let Ok(Some(content)) = read_source(&file) {
             if is_component_file(&content) {
                 let component_info = analyze_component_file(&file, &content)?;
                 components.push(component_info);
@@ -641,140 +674,6 @@ fn extract_child_components(content: &str) -> Vec<String> {
     components
 }
 
-async fn analyze_pages(project_dir: &Path) -> Result<Vec<PageInfo>> {
-    let mut pages = Vec::new();
-    
-    // Common page directories for different frameworks
-    let page_dirs = [
-        "pages",           // Next.js pages directory
-        "app",             // Next.js app directory
-        "src/pages",       // Next.js with src
-        "src/app",         // Next.js app with src
-        "src/routes",      // SvelteKit routes
-        "routes",          // SvelteKit routes
-    ];
-    
-    for dir_name in &page_dirs {
-        let dir_path = project_dir.join(dir_name);
-        if dir_path.exists() && dir_path.is_dir() {
-            let walker = OptimizedFileWalker::new()
-                .parallel_threshold(10);
-            
-            let files = walker.walk(&dir_path)
-                .into_iter()
-                .filter(|path| {
-                    if let Some(ext) = path.extension() {
-                        matches!(ext.to_str(), Some("ts") | Some("tsx") | Some("js") | Some("jsx"))
-                    } else {
-                        false
-                    }
-                })
-                .collect::<Vec<_>>();
-            
-            for file_path in files {
-                if let Ok(content) = std::fs::read_to_string(&file_path) {
-                    let relative_path = file_path.strip_prefix(project_dir)
-                        .unwrap_or(&file_path)
-                        .to_string_lossy()
-                        .to_string();
-                    
-                    let _line_count = content.lines().count();
-                    let _is_dynamic = relative_path.contains('[') && relative_path.contains(']');
-                    let has_getstaticprops = content.contains("getStaticProps");
-                    let has_getserversideprops = content.contains("getServerSideProps");
-                    
-                    let name = file_path.file_stem()
-                        .map(|n| n.to_string_lossy().to_string())
-                        .unwrap_or_default();
-                    let route = relative_path.replace(".tsx", "").replace(".ts", "").replace(".jsx", "").replace(".js", "");
-                    
-                    pages.push(PageInfo {
-                        name,
-                        path: relative_path,
-                        route,
-                        has_ssr: has_getserversideprops,
-                        has_ssg: has_getstaticprops,
-                        api_calls: Vec::new(), // Could be enhanced to detect API calls
-                    });
-                }
-            }
-        }
-    }
-    
-    Ok(pages)
-}
-
-async fn analyze_api_routes(project_dir: &Path) -> Result<Vec<ApiRouteInfo>> {
-    let mut api_routes = Vec::new();
-    
-    // Common API directories
-    let api_dirs = [
-        "pages/api",
-        "src/pages/api", 
-        "app/api",
-        "src/app/api",
-        "api",
-        "src/api",
-    ];
-    
-    for dir_name in &api_dirs {
-        let dir_path = project_dir.join(dir_name);
-        if dir_path.exists() && dir_path.is_dir() {
-            let walker = OptimizedFileWalker::new()
-                .parallel_threshold(10);
-            
-            let files = walker.walk(&dir_path)
-                .into_iter()
-                .filter(|path| {
-                    if let Some(ext) = path.extension() {
-                        matches!(ext.to_str(), Some("ts") | Some("tsx") | Some("js") | Some("jsx"))
-                    } else {
-                        false
-                    }
-                })
-                .collect::<Vec<_>>();
-            
-            for file_path in files {
-                if let Ok(content) = std::fs::read_to_string(&file_path) {
-                    let relative_path = file_path.strip_prefix(project_dir)
-                        .unwrap_or(&file_path)
-                        .to_string_lossy()
-                        .to_string();
-                    
-                    let _line_count = content.lines().count();
-                    
-                    // Detect HTTP methods
-                    let mut methods = Vec::new();
-                    if content.contains("req.method === 'GET'") || content.contains("method: 'GET'") {
-                        methods.push("GET".to_string());
-                    }
-                    if content.contains("req.method === 'POST'") || content.contains("method: 'POST'") {
-                        methods.push("POST".to_string());
-                    }
-                    if content.contains("req.method === 'PUT'") || content.contains("method: 'PUT'") {
-                        methods.push("PUT".to_string());
-                    }
-                    if content.contains("req.method === 'DELETE'") || content.contains("method: 'DELETE'") {
-                        methods.push("DELETE".to_string());
-                    }
-                    
-                    let has_middleware = content.contains("middleware") || content.contains("cors") || content.contains("auth");
-                    let has_validation = content.contains("validate") || content.contains("schema") || content.contains("joi") || content.contains("yup");
-                    
-                    api_routes.push(ApiRouteInfo {
-                        path: relative_path,
-                        methods: if methods.is_empty() { vec!["GET".to_string()] } else { methods },
-                        middleware: if has_middleware { vec!["middleware".to_string()] } else { Vec::new() },
-                        database_operations: if has_validation { vec!["validation".to_string()] } else { Vec::new() },
-                    });
-                }
-            }
-        }
-    }
-    
-    Ok(api_routes)
-}
-
 async fn analyze_utilities(project_dir: &Path) -> Result<Vec<UtilityInfo>> {
     let mut utilities = Vec::new();
     
@@ -808,7 +707,7 @@ async fn analyze_utilities(project_dir: &Path) -> Result<Vec<UtilityInfo>> {
                 .collect::<Vec<_>>();
             
             for file_path in files {
-                if let Ok(content) = std::fs::read_to_string(&file_path) {
+                if let Ok(Some(content)) = read_source(&file_path) {
                     let relative_path = file_path.strip_prefix(project_dir)
                         .unwrap_or(&file_path)
                         .to_string_lossy()
@@ -948,8 +847,8 @@ async fn analyze_imports_exports(project_dir: &Path) -> Result<(HashMap<String,
     let extensions = vec!["ts", "tsx", "js", "jsx"];
     let files = FileUtils::find_files_with_progress(project_dir, &extensions, true)?;
     
-    for file in files.iter().take(50) { // Limit to first 50 files for performance
-        if let Ok(content) = fs::read_to_string(file) {
+    for file in &files {
+        if let Ok(Some(content)) = read_source(file) {
             let relative_path = FileUtils::get_relative_path(file);
             
             let file_imports = extract_imports_from_content(&content);
@@ -1056,16 +955,17 @@ fn extract_external_dependencies(imports: &HashMap<String, Vec<ImportInfo>>) ->
     deps
 }
 
-async fn generate_architecture_insights(structure: &ProjectStructure, dependencies: &DependencyAnalysis) -> Result<ArchitectureInsights> {
+async fn generate_architecture_insights(structure: &ProjectStructure, dependencies: &DependencyAnalysis, complexity: ComplexitySummary) -> Result<ArchitectureInsights> {
     let patterns = detect_architecture_patterns(structure, dependencies);
-    let organization_score = calculate_organization_score(structure);
-    let complexity_level = determine_complexity_level(structure, dependencies);
-    let recommendations = generate_recommendations(structure, dependencies, organization_score);
+    let organization_score = calculate_organization_score(structure, &complexity);
+    let complexity_level = determine_complexity_level(&complexity);
+    let recommendations = generate_recommendations(structure, dependencies, organization_score, &complexity);
     
     Ok(ArchitectureInsights {
         patterns,
         organization_score,
         complexity_level,
+        complexity,
         recommendations,
     })
 }
@@ -1132,7 +1032,7 @@ fn detect_architecture_patterns(structure: &ProjectStructure, dependencies: &Dep
     patterns
 }
 
-fn calculate_organization_score(structure: &ProjectStructure) -> f64 {
+fn calculate_organization_score(structure: &ProjectStructure, complexity: &ComplexitySummary) -> f64 {
     let mut score = 0.0;
     let mut max_score = 0.0;
     
@@ -1171,14 +1071,9 @@ fn calculate_organization_score(structure: &ProjectStructure) -> f64 {
         score += (consistent_naming as f64 / structure.directories.len() as f64) * 15.0;
     }
     
-    // Complexity management score (10%)
+    // Complexity management score (10%): share of functions within the complexity thresholds
     max_score += 10.0;
-    let reasonable_complexity = structure.directories.iter().filter(|d| {
-        d.file_count < 20 // Reasonable number of files per directory
-    }).count();
-    if !structure.directories.is_empty() {
-        score += (reasonable_complexity as f64 / structure.directories.len() as f64) * 10.0;
-    }
+    score += complexity.score / 100.0 * 10.0;
     
     if max_score > 0.0 {
         (score / max_score) * 100.0
@@ -1187,22 +1082,17 @@ fn calculate_organization_score(structure: &ProjectStructure) -> f64 {
     }
 }
 
-fn determine_complexity_level(structure: &ProjectStructure, dependencies: &DependencyAnalysis) -> ComplexityLevel {
-    let total_files: usize = structure.directories.iter().map(|d| d.file_count).sum();
-    let total_components = structure.components.len();
-    let external_deps = dependencies.external_dependencies.len();
-    
-    let complexity_score = total_files + (total_components * 2) + external_deps;
-    
-    match complexity_score {
-        0..=20 => ComplexityLevel::Simple,
-        21..=50 => ComplexityLevel::Moderate,
-        51..=100 => ComplexityLevel::Complex,
+/// Based on how many functions exceed the `[complexity]` thresholds, not on project size
+fn determine_complexity_level(complexity: &ComplexitySummary) -> ComplexityLevel {
+    match complexity.score {
+        score if score >= 95.0 => ComplexityLevel::Simple,
+        score if score >= 85.0 => ComplexityLevel::Moderate,
+        score if score >= 70.0 => ComplexityLevel::Complex,
         _ => ComplexityLevel::VeryComplex,
     }
 }
 
-fn generate_recommendations(structure: &ProjectStructure, dependencies: &DependencyAnalysis, org_score: f64) -> Vec<String> {
+fn generate_recommendations(structure: &ProjectStructure, dependencies: &DependencyAnalysis, org_score: f64, complexity: &ComplexitySummary) -> Vec<String> {
     let mut recommendations = Vec::new();
     
     // Organization recommendations
@@ -1210,6 +1100,10 @@ fn generate_recommendations(structure: &ProjectStructure, dependencies: &Depende
         recommendations.push("🏗️ Consider reorganizing project structure for better maintainability".to_string());
     }
     
+    if complexity.over_threshold > 0 {
+        recommendations.push(format!("🧠 {} functions exceed the complexity thresholds - run `sniff complexity` to find them", complexity.over_threshold));
+    }
+    
     // Directory recommendations
     let has_components = structure.directories.iter().any(|d| matches!(d.purpose, DirectoryPurpose::Components));
     if !has_components && !structure.components.is_empty() {
@@ -1253,115 +1147,6 @@ fn generate_recommendations(structure: &ProjectStructure, dependencies: &Depende
     recommendations
 }
 
-async fn analyze_file_relationships(project_dir: &Path) -> Result<FileRelationships> {
-    let mut import_graph: HashMap<String, Vec<String>> = HashMap::new();
-    let mut component_hierarchy: HashMap<String, Vec<String>> = HashMap::new();
-    let mut import_counts: HashMap<String, usize> = HashMap::new();
-    
-    // Find all TypeScript/JavaScript files
-    let walker = OptimizedFileWalker::new()
-        .parallel_threshold(20);
-    let files = walker.walk(project_dir)
-        .into_iter()
-        .filter(|path| {
-            if let Some(ext) = path.extension() {
-                matches!(ext.to_str(), Some("ts") | Some("tsx") | Some("js") | Some("jsx"))
-            } else {
-                false
-            }
-        })
-        .collect::<Vec<_>>();
-    
-    // Parse imports from each file
-    for file_path in &files {
-        if let Ok(content) = std::fs::read_to_string(file_path) {
-            let relative_path = file_path.strip_prefix(project_dir)
-                .unwrap_or(file_path)
-                .to_string_lossy()
-                .to_string();
-            
-            let imports = extract_imports(&content);
-            import_graph.insert(relative_path.clone(), imports.clone());
-            
-            // Count imports for popularity analysis
-            for import in imports {
-                *import_counts.entry(import).or_insert(0) += 1;
-            }
-            
-            // Analyze component hierarchy for React components
-            if file_path.extension().map_or(false, |ext| ext == "tsx" || ext == "jsx") {
-                let children = extract_jsx_children(&content);
-                if !children.is_empty() {
-                    component_hierarchy.insert(relative_path, children);
-                }
-            }
-        }
-    }
-    
-    // Find most imported files (top 10)
-    let mut most_imported: Vec<(String, usize)> = import_counts.into_iter().collect();
-    most_imported.sort_by(|a, b| b.1.cmp(&a.1));
-    most_imported.truncate(10);
-    // Keep as Vec<(String, usize)> for the struct
-    // Don't convert to strings here
-    
-    // Basic circular dependency detection (simplified)
-    let circular_dependencies = detect_circular_dependencies(&import_graph)
-        .into_iter()
-        .map(|file| vec![file]) // Convert single files to cycles
-        .collect();
-    
-    Ok(FileRelationships {
-        import_graph,
-        component_hierarchy,
-        most_imported: most_imported.into_iter().take(10).collect(),
-        circular_dependencies,
-    })
-}
-
-fn extract_imports(content: &str) -> Vec<String> {
-    let mut imports = Vec::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        
-        // Match import statements
-        if line.starts_with("import ") && line.contains(" from ") {
-            if let Some(from_pos) = line.rfind(" from ") {
-                let import_path = &line[from_pos + 6..];
-                let import_path = import_path.trim()
-                    .trim_matches('\'')
-                    .trim_matches('"')
-                    .trim_matches(';');
-                
-                // Only include relative imports
-                if import_path.starts_with('.') {
-                    imports.push(import_path.to_string());
-                }
-            }
-        }
-        
-        // Match require statements
-        if line.contains("require(") {
-            if let Some(start) = line.find("require(") {
-                let after_require = &line[start + 8..];
-                if let Some(end) = after_require.find(')') {
-                    let import_path = &after_require[..end]
-                        .trim()
-                        .trim_matches('\'')
-                        .trim_matches('"');
-                    
-                    if import_path.starts_with('.') {
-                        imports.push(import_path.to_string());
-                    }
-                }
-            }
-        }
-    }
-    
-    imports
-}
-
 fn extract_jsx_children(content: &str) -> Vec<String> {
     let mut children = Vec::new();
     
@@ -1401,122 +1186,76 @@ fn extract_jsx_children(content: &str) -> Vec<String> {
     children
 }
 
-fn detect_circular_dependencies(import_graph: &HashMap<String, Vec<String>>) -> Vec<String> {
-    let mut circular_deps = Vec::new();
-    let mut visited = std::collections::HashSet::new();
-    let mut rec_stack = std::collections::HashSet::new();
-    
-    for file in import_graph.keys() {
-        if !visited.contains(file) {
-            if has_cycle(file, import_graph, &mut visited, &mut rec_stack, &mut Vec::new()) {
-                // Found a cycle involving this file
-                circular_deps.push(file.clone());
-            }
-        }
-    }
-    
-    circular_deps
-}
-
-fn has_cycle(
-    file: &str,
-    graph: &HashMap<String, Vec<String>>,
-    visited: &mut std::collections::HashSet<String>,
-    rec_stack: &mut std::collections::HashSet<String>,
-    _path: &mut Vec<String>,
-) -> bool {
-    visited.insert(file.to_string());
-    rec_stack.insert(file.to_string());
-    
-    if let Some(imports) = graph.get(file) {
-        for import in imports {
-            // Resolve relative import to absolute path (simplified)
-            let resolved_import = resolve_import_path(file, import);
-            
-            if !visited.contains(&resolved_import) {
-                if has_cycle(&resolved_import, graph, visited, rec_stack, _path) {
-                    return true;
-                }
-            } else if rec_stack.contains(&resolved_import) {
-                return true; // Found cycle
-            }
-        }
-    }
-    
-    rec_stack.remove(file);
-    false
-}
-
-fn resolve_import_path(current_file: &str, import_path: &str) -> String {
-    // Simplified import resolution
-    if import_path.starts_with("./") {
-        // Same directory
-        let dir = std::path::Path::new(current_file).parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default();
-        format!("{}/{}", dir, &import_path[2..])
-    } else if import_path.starts_with("../") {
-        // Parent directory - simplified resolution
-        let mut parts: Vec<&str> = current_file.split('/').collect();
-        parts.pop(); // Remove filename
-        
-        let mut import_parts: Vec<&str> = import_path.split('/').collect();
-        while import_parts.first() == Some(&"..") {
-            import_parts.remove(0);
-            if !parts.is_empty() {
-                parts.pop();
-            }
-        }
-        
-        parts.extend(import_parts);
-        parts.join("/")
-    } else {
-        import_path.to_string()
-    }
-}
-
-
 fn print_report(report: &ContextReport, quiet: bool) {
     if !quiet {
-        println!();
-        println!("{}", "📊 Project Context Report".bold().blue());
-        println!("{}", "========================".blue());
-        println!();
+        output::blank();
+        output::line("📊 Project Context Report".bold().blue());
+        output::line("========================".blue());
+        output::blank();
     }
     
     print_project_info(&report.project_info);
     print_project_structure(&report.structure);
+    print_routes(&report.structure);
     print_dependencies_summary(&report.dependencies);
     print_architecture_insights(&report.architecture);
     print_component_analysis(&report.structure);
+    print_relationships(&report.relationships);
+}
+
+fn print_relationships(relationships: &FileRelationships) {
+    if relationships.import_graph.is_empty() {
+        return;
+    }
+    output::line("🔗 FILE RELATIONSHIPS".bold().blue());
+    output::line("─────────────────────".blue());
+    let edges: usize = relationships.import_graph.values().map(Vec::len).sum();
+    output::line(format!("  {} files, {} local imports", relationships.import_graph.len(), edges));
+
+    if !relationships.most_imported.is_empty() {
+        output::line("  Most imported:");
+        for (file, fan_in) in relationships.most_imported.iter().take(5) {
+            let fan_out = relationships.coupling.get(file).map_or(0, |counts| counts.fan_out);
+            output::line(format!("    {} {}", file.bold(), format!("(imported by {}, imports {})", fan_in, fan_out).dimmed()));
+        }
+    }
+    if relationships.circular_dependencies.is_empty() {
+        output::line(format!("  {}", "✅ No circular imports".green()));
+    } else {
+        output::line(format!("  {}", format!("⚠️  {} circular import chain(s):", relationships.circular_dependencies.len()).yellow()));
+        for cycle in relationships.circular_dependencies.iter().take(5) {
+            output::line(format!("    {}", cycle.join(" ↔ ")));
+        }
+    }
+    output::blank();
 }
 
 fn print_project_info(info: &ProjectInfo) {
-    println!("{}", "🏗️  PROJECT OVERVIEW".bold().green());
-    println!("{}", "─────────────────────".green());
-    println!("  Name: {}", info.name.bold());
+    output::line("🏗️  PROJECT OVERVIEW".bold().green());
+    output::line("─────────────────────".green());
+    output::line(format!("  Name: {}", info.name.bold()));
     
     if let Some(version) = &info.version {
-        println!("  Version: {}", version);
+        output::line(format!("  Version: {}", version));
     }
     
     if let Some(description) = &info.description {
-        println!("  Description: {}", description.dimmed());
+        output::line(format!("  Description: {}", description.dimmed()));
     }
     
-    println!("  Framework: {:?}", info.framework);
-    println!("  Languages: {:?}", info.languages);
-    println!("  Total Files: {}", info.total_files);
-    println!("  Total Lines: {}", info.total_lines);
-    println!();
+    output::line(format!("  Framework: {:?}", info.framework));
+    output::line(format!("  Languages: {:?}", info.languages));
+    output::line(format!("  Total Files: {}", info.total_files));
+    output::line(format!("  Total Lines: {}", info.total_lines));
+    output::blank();
 }
 
 fn print_project_structure(structure: &ProjectStructure) {
-    println!("{}", "📁 PROJECT STRUCTURE".bold().cyan());
-    println!("{}", "──────────────────────".cyan());
+    output::line("📁 PROJECT STRUCTURE".bold().cyan());
+    output::line("──────────────────────".cyan());
     
     if !structure.directories.is_empty() {
-        println!("  Key Directories:");
+        output::line("  Key Directories:");
         
         // Sort directories by file count (most important first)
         let mut sorted_dirs = structure.directories.clone();
@@ -1550,61 +1289,79 @@ fn print_project_structure(structure: &ProjectStructure) {
                 DirectoryPurpose::Other => "Other",
             };
             
-            println!("    {} {} ({} files, {} lines)", 
+            output::line(format!("    {} {} ({} files, {} lines)", 
                 purpose_emoji, 
                 dir.path.bold(), 
                 dir.file_count, 
                 dir.line_count
-            );
-            println!("      {} | File types: {}", 
+            ));
+            output::line(format!("      {} | File types: {}", 
                 purpose_str.dimmed(),
                 dir.main_file_types.join(", ").dimmed()
-            );
+            ));
         }
     }
     
-    println!();
+    output::blank();
+}
+
+fn print_routes(structure: &ProjectStructure) {
+    if structure.pages.is_empty() && structure.api_routes.is_empty() {
+        return;
+    }
+    let server_rendered = structure.pages.iter().filter(|page| page.has_ssr).count();
+    let prerendered = structure.pages.iter().filter(|page| page.has_ssg).count();
+    output::line("🗺️  ROUTES".bold().cyan());
+    output::line("──────────".cyan());
+    output::line(format!("  📄 {} pages ({} SSR, {} SSG/ISR)", structure.pages.len(), server_rendered, prerendered));
+    output::line(format!("  🔌 {} API routes", structure.api_routes.len()));
+    if let Some(middleware) = &structure.middleware {
+        let scope = if middleware.matchers.is_empty() { "all routes".to_string() } else { middleware.matchers.join(", ") };
+        output::line(format!("  🛡️  {} → {}", middleware.path, scope.dimmed()));
+    }
+    output::line(format!("  {}", "Run `sniff routes` for the full route table".dimmed()));
+    output::blank();
 }
 
 fn print_dependencies_summary(dependencies: &DependencyAnalysis) {
-    println!("{}", "📦 DEPENDENCIES OVERVIEW".bold().magenta());
-    println!("{}", "────────────────────────".magenta());
+    output::line("📦 DEPENDENCIES OVERVIEW".bold().magenta());
+    output::line("────────────────────────".magenta());
     
     if let Some(package_info) = &dependencies.package_json {
-        println!("  Production Dependencies: {}", package_info.dependencies.len());
-        println!("  Development Dependencies: {}", package_info.dev_dependencies.len());
+        output::line(format!("  Production Dependencies: {}", package_info.dependencies.len()));
+        output::line(format!("  Development Dependencies: {}", package_info.dev_dependencies.len()));
         
         if !package_info.main_dependencies.is_empty() {
-            println!("  Key Frameworks/Tools:");
+            output::line("  Key Frameworks/Tools:");
             for dep in &package_info.main_dependencies {
                 if let Some(version) = package_info.dependencies.get(dep) {
-                    println!("    📚 {} ({})", dep.bold(), version.dimmed());
+                    output::line(format!("    📚 {} ({})", dep.bold(), version.dimmed()));
                 }
             }
         }
         
         if !dependencies.external_dependencies.is_empty() {
             let external_count = dependencies.external_dependencies.len();
-            println!("  External packages used in code: {}", external_count);
+            output::line(format!("  External packages used in code: {}", external_count));
             if external_count > 10 {
-                println!("    Top imports: {}", 
+                output::line(format!("    Top imports: {}", 
                     dependencies.external_dependencies.iter().take(8).cloned().collect::<Vec<_>>().join(", ").dimmed()
-                );
+                ));
             }
         }
     } else {
-        println!("  {} No package.json found", "⚠️".yellow());
+        output::line(format!("  {} No package.json found", "⚠️".yellow()));
     }
     
-    println!();
+    output::blank();
 }
 
 fn print_component_analysis(structure: &ProjectStructure) {
     if !structure.components.is_empty() {
-        println!("{}", "⚛️  COMPONENT ANALYSIS".bold().green());
-        println!("{}", "────────────────────────".green());
+        output::line("⚛️  COMPONENT ANALYSIS".bold().green());
+        output::line("────────────────────────".green());
         
-        println!("  Total Components: {}", structure.components.len());
+        output::line(format!("  Total Components: {}", structure.components.len()));
         
         // Group by component type
         let mut type_counts: HashMap<String, usize> = HashMap::new();
@@ -1621,7 +1378,7 @@ fn print_component_analysis(structure: &ProjectStructure) {
         }
         
         for (component_type, count) in type_counts {
-            println!("    {}: {}", component_type, count);
+            output::line(format!("    {}: {}", component_type, count));
         }
         
         // Show hooks usage summary
@@ -1633,12 +1390,12 @@ fn print_component_analysis(structure: &ProjectStructure) {
         }
         
         if !all_hooks.is_empty() {
-            println!("  Most Used Hooks:");
+            output::line("  Most Used Hooks:");
             let mut hook_vec: Vec<(String, usize)> = all_hooks.into_iter().collect();
             hook_vec.sort_by(|a, b| b.1.cmp(&a.1));
             
             for (hook, count) in hook_vec.iter().take(5) {
-                println!("    🎣 {} (used in {} components)", hook.bold(), count);
+                output::line(format!("    🎣 {} (used in {} components)", hook.bold(), count));
             }
         }
         
@@ -1648,19 +1405,19 @@ fn print_component_analysis(structure: &ProjectStructure) {
             .collect();
         
         if !complex_components.is_empty() {
-            println!("  Complex Components (>5 children):");
+            output::line("  Complex Components (>5 children):");
             for component in complex_components.iter().take(3) {
-                println!("    🏗️  {} ({} children)", component.name.bold(), component.children_components.len());
+                output::line(format!("    🏗️  {} ({} children)", component.name.bold(), component.children_components.len()));
             }
         }
         
-        println!();
+        output::blank();
     }
 }
 
 fn print_architecture_insights(insights: &ArchitectureInsights) {
-    println!("{}", "🏛️  ARCHITECTURE INSIGHTS".bold().yellow());
-    println!("{}", "─────────────────────────".yellow());
+    output::line("🏛️  ARCHITECTURE INSIGHTS".bold().yellow());
+    output::line("─────────────────────────".yellow());
     
     // Color-coded organization score
     let score_color = if insights.organization_score >= 80.0 {
@@ -1671,7 +1428,7 @@ fn print_architecture_insights(insights: &ArchitectureInsights) {
         insights.organization_score.to_string().red()
     };
     
-    println!("  Organization Score: {:.1}%", score_color);
+    output::line(format!("  Organization Score: {:.1}%", score_color));
     
     let complexity_display = match insights.complexity_level {
         ComplexityLevel::Simple => "Simple 🟢".green(),
@@ -1679,10 +1436,17 @@ fn print_architecture_insights(insights: &ArchitectureInsights) {
         ComplexityLevel::Complex => "Complex 🟠".yellow(),
         ComplexityLevel::VeryComplex => "Very Complex 🔴".red(),
     };
-    println!("  Complexity Level: {}", complexity_display);
+    output::line(format!("  Complexity Level: {}", complexity_display));
+    let complexity = &insights.complexity;
+    if complexity.functions > 0 {
+        output::line(format!(
+            "  Function Complexity: avg cyclomatic {:.1}, cognitive {:.1}; {} of {} functions over the thresholds",
+            complexity.average_cyclomatic, complexity.average_cognitive, complexity.over_threshold, complexity.functions
+        ));
+    }
     
     if !insights.patterns.is_empty() {
-        println!("  Detected Patterns:");
+        output::line("  Detected Patterns:");
         for pattern in &insights.patterns {
             let pattern_emoji = match pattern {
                 ArchitecturePattern::LayeredArchitecture => "🏗️",
@@ -1704,18 +1468,18 @@ fn print_architecture_insights(insights: &ArchitectureInsights) {
                 ArchitecturePattern::ConfigDriven => "Config Driven",
             };
             
-            println!("    {} {}", pattern_emoji, pattern_name);
+            output::line(format!("    {} {}", pattern_emoji, pattern_name));
         }
     }
     
     if !insights.recommendations.is_empty() {
-        println!();
-        println!("{}", "💡 RECOMMENDATIONS".bold().blue());
-        println!("{}", "──────────────────".blue());
+        output::blank();
+        output::line("💡 RECOMMENDATIONS".bold().blue());
+        output::line("──────────────────".blue());
         for (i, rec) in insights.recommendations.iter().enumerate() {
-            println!("  {}. {}", i + 1, rec);
+            output::line(format!("  {}. {}", i + 1, rec));
         }
     }
     
-    println!();
+    output::blank();
 }
\ No newline at end of file
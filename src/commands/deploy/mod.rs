@@ -0,0 +1,500 @@
+mod notify;
+
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Instant;
+use crate::config::{Config, DeployGate, GateLevel, DEPLOY_CHECKS};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, ExitCode, CommandOutcome, ScanTarget, create_annotated_json_output, output_result};
+use crate::common::junit::TestSuite;
+use crate::common::ndjson::LevelCounts;
+use super::{env, types, large, imports_analyzer as imports, bundle, console, images, memory, security, secrets, lint, outdated, vulns, docker, ci};
+use super::report::project_name;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeployReport {
+    pub checks: Vec<CheckResult>,
+    pub summary: DeploySummary,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub status: CheckStatus,
+    pub blocking: bool,
+    pub exit_code: i32,
+    pub message: Option<String>,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub issues_found: usize,
+    /// `issues_found` by level
+    #[serde(default)]
+    pub issues: LevelCounts,
+    /// What the sub-check's report counted, e.g. "14 unused imports, 2 broken imports"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// The files with the most findings, most first
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_files: Vec<FileFindings>,
+    /// The sub-check's findings, for `--format github` and `--format junit`
+    #[serde(skip)]
+    pub findings: Vec<Annotation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileFindings {
+    pub file: String,
+    pub findings: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CheckStatus {
+    Passed,
+    Warning,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeploySummary {
+    pub total_checks: usize,
+    pub passed: usize,
+    pub warnings: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub ready_to_deploy: bool,
+}
+
+/// How many of a check's worst files are listed
+const TOP_FILES: usize = 3;
+
+pub async fn run(target: &ScanTarget, with: &[String], json: bool, quiet: bool) -> Result<CommandOutcome<DeployReport>> {
+    if !quiet && !json {
+        output::line("🚀 Running pre-deployment validation...".bold().blue());
+        output::blank();
+    }
+
+    let outcome = check(target, with, |check| {
+        if !quiet && !json {
+            print_check_line(check);
+        }
+    }).await?;
+    let report = &outcome.report;
+
+    let response = create_annotated_json_output(
+        "deploy",
+        report,
+        report.summary.total_checks,
+        report.summary.failed + report.summary.warnings,
+        Some(report.duration_ms),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_summary(report, quiet))?;
+
+    Ok(outcome)
+}
+
+/// Run every check of the pipeline, calling `on_check` as each one finishes; `with` turns
+/// checks that are off into warnings
+pub async fn check(target: &ScanTarget, with: &[String], mut on_check: impl FnMut(&CheckResult)) -> Result<CommandOutcome<DeployReport>> {
+    let start_time = Instant::now();
+    // Every check reads the same tree, so each file is walked and read once for all of them
+    let target = &target.clone().with_shared_context();
+    // The pipeline validates one deployable project
+    let project_dir = target.project_dir("deploy")?;
+    let root = project_dir.canonicalize().unwrap_or_else(|_| project_dir.to_path_buf());
+
+    let config = target.config()?;
+    let mut checks = Vec::new();
+
+    // Checks run in `DEPLOY_CHECKS` order; `[deploy.gates]` decides which ones block
+    for name in DEPLOY_CHECKS {
+        let gate = match config.deploy.gate(name) {
+            DeployGate::Level(GateLevel::Off) if with.iter().any(|check| check == name) => DeployGate::Level(GateLevel::Warn),
+            gate => gate,
+        };
+        if gate == DeployGate::Level(GateLevel::Off) {
+            let check = skipped(name, false, format!("Turned off; run it with --with {} or in [deploy.gates]", name), 0);
+            on_check(&check);
+            checks.push(check);
+            continue;
+        }
+
+        let check_start = Instant::now();
+        let result = run_check(name, target, &config).await;
+        let duration_ms = check_start.elapsed().as_millis() as u64;
+
+        let check = match result {
+            Ok(sub_check) => {
+                let over_limit = over_limit(&gate, &sub_check);
+                let blocking = gate == DeployGate::Level(GateLevel::Block) || over_limit.is_some();
+                CheckResult {
+                    name: name.to_string(),
+                    status: if over_limit.is_some() { CheckStatus::Failed } else { status_for(sub_check.exit_code, blocking) },
+                    blocking,
+                    exit_code: sub_check.exit_code.code(),
+                    message: over_limit,
+                    duration_ms,
+                issues_found: sub_check.findings.len(),
+                issues: LevelCounts::from_findings(&sub_check.findings),
+                detail: Some(sub_check.detail),
+                    top_files: top_files(&sub_check.findings, &root),
+                    findings: sub_check.findings,
+                }
+            }
+            // A check that cannot run (e.g. no build output for `bundle`) is skipped, not failed
+            Err(e) => skipped(name, gate == DeployGate::Level(GateLevel::Block), e.to_string(), duration_ms),
+        };
+
+        on_check(&check);
+        checks.push(check);
+    }
+
+    let summary = create_summary(&checks);
+    let report = DeployReport {
+        checks,
+        summary,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    };
+
+    // A post that fails is reported but does not change the outcome
+    if let Err(e) = notify::send(&config.notify, &report, &project_name(project_dir)) {
+        tracing::warn!("Could not post to the [notify] webhook: {}", e);
+    }
+
+    let exit_code = if report.summary.ready_to_deploy {
+        ExitCode::Success
+    } else {
+        ExitCode::ValidationFailed
+    };
+
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+fn skipped(name: &str, blocking: bool, reason: String, duration_ms: u64) -> CheckResult {
+    CheckResult {
+        name: name.to_string(),
+        status: CheckStatus::Skipped,
+        blocking,
+        exit_code: ExitCode::GeneralError.code(),
+        message: Some(reason),
+        duration_ms,
+        issues_found: 0,
+        issues: LevelCounts::default(),
+        detail: None,
+        top_files: Vec::new(),
+        findings: Vec::new(),
+    }
+}
+
+/// Why a check is past its `[deploy.gates]` limit, if it is
+fn over_limit(gate: &DeployGate, sub_check: &SubCheck) -> Option<String> {
+    let DeployGate::Limit(limit) = gate else { return None };
+    if let Some(max) = limit.block_over.filter(|max| sub_check.findings.len() > *max) {
+        return Some(format!("{} findings, over the limit of {} in [deploy.gates]", sub_check.findings.len(), max));
+    }
+    let size_mb = sub_check.size_bytes? as f64 / (1024.0 * 1024.0);
+    limit.block_over_mb
+        .filter(|max| size_mb > *max)
+        .map(|max| format!("{:.1} MB, over the limit of {} MB in [deploy.gates]", size_mb, max))
+}
+
+/// What a sub-check reported: its exit status, its findings and the counts from its report
+struct SubCheck {
+    exit_code: ExitCode,
+    findings: Vec<Annotation>,
+    detail: String,
+    /// Total bundle size, for `block_over_mb`
+    size_bytes: Option<u64>,
+}
+
+impl SubCheck {
+    fn new<R: Annotate>(outcome: CommandOutcome<R>, detail: impl FnOnce(&R) -> String) -> Self {
+        SubCheck { exit_code: outcome.exit_code, findings: outcome.report.findings(), detail: detail(&outcome.report), size_bytes: None }
+    }
+}
+
+/// Run a single sub-check silently and return what its report found
+async fn run_check(name: &str, target: &ScanTarget, config: &Config) -> Result<SubCheck> {
+    let result = match name {
+        "env" => SubCheck::new(env::check(target, None, false).await?, |report| {
+            let summary = &report.summary;
+            counted(&[
+                (summary.missing, "missing variable"),
+                (summary.empty, "empty variable"),
+                (summary.invalid, "invalid value"),
+                (summary.security_issues, "security issue"),
+                (report.usage.undefined.len(), "undefined variable"),
+            ])
+        }),
+        "types" => SubCheck::new(types::check(target, types::TypesOptions::default(), true)?, |report| {
+            let summary = &report.summary;
+            counted(&[
+                (summary.any_usage_count, "`any` usage"),
+                (summary.ts_ignore_count, "@ts-ignore"),
+                (summary.missing_return_types, "missing return type"),
+                (summary.untyped_parameters, "untyped parameter"),
+            ])
+        }),
+        "large" => SubCheck::new(large::check(target, 100, None, false, config)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.large_files_found, "large file"), (summary.large_functions_found, "large function")])
+        }),
+        "imports" => SubCheck::new(imports::check(target, false, true)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.unused_imports, "unused import"), (summary.broken_imports, "broken import")])
+        }),
+        "bundle" => {
+            let outcome = bundle::check(target, &bundle::BundleOptions::default()).await?;
+            let size_bytes = outcome.report.summary.total_size;
+            let sub_check = SubCheck::new(outcome, |report| {
+                let exceeded = report.budgets.iter().filter(|budget| budget.exceeded).count();
+                let size = format!("{} KB in {} chunks", report.summary.total_size / 1024, report.summary.chunk_count);
+                match exceeded {
+                    0 => size,
+                    _ => format!("{}, {}", size, counted(&[(exceeded, "budget exceeded")])),
+                }
+            });
+            SubCheck { size_bytes: Some(size_bytes), ..sub_check }
+        }
+        "console" => SubCheck::new(console::check(target, true, config, true)?, |report| {
+            let calls: Vec<String> = report.summary.by_name.iter().map(|(name, count)| format!("{} × {}", name, count)).collect();
+            if calls.is_empty() { "no issues".to_string() } else { calls.join(", ") }
+        }),
+        "images" => {
+            let outcome = images::check(target, &config.images, true)?;
+            if outcome.report.summary.images_scanned == 0 {
+                anyhow::bail!("No images in public/ or imported from source");
+            }
+            SubCheck::new(outcome, |report| {
+                let summary = &report.summary;
+                let issues = counted(&[(summary.images_with_issues, "image with issues")]);
+                match summary.potential_savings {
+                    0 => issues,
+                    savings => format!("{}, {} KB to save", issues, savings / 1024),
+                }
+            })
+        }
+        "memory" => {
+            let sub_check = SubCheck::new(memory::check(target, &memory::MemoryOptions::default()).await?, |report| {
+                let summary = &report.summary;
+                counted(&[(summary.critical_issues, "critical issue"), (summary.high_issues, "high issue"), (summary.medium_issues, "medium issue"), (summary.low_issues, "low issue")])
+            });
+            // Node processes running on the deploying machine say nothing about the code
+            let exit_code = if sub_check.findings.iter().any(|finding| finding.level == AnnotationLevel::Critical) {
+                ExitCode::GeneralError
+            } else {
+                ExitCode::Success
+            };
+            SubCheck { exit_code, ..sub_check }
+        }
+        "security" => SubCheck::new(security::check(target, true)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.critical, "critical issue"), (summary.high, "high issue"), (summary.medium, "medium issue"), (summary.low, "low issue")])
+        }),
+        "secrets" => SubCheck::new(secrets::check(target, true, config, true)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.high_severity, "high-severity secret"), (summary.medium_severity, "medium-severity secret")])
+        }),
+        "lint" => SubCheck::new(lint::check(target, None, config)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.errors, "lint error"), (summary.warnings, "lint warning")])
+        }),
+        "outdated" => SubCheck::new(outdated::check(target, &outdated::OutdatedOptions::default(), config)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.deprecated, "deprecated package"), (summary.major, "major upgrade"), (summary.minor + summary.patch, "minor upgrade")])
+        }),
+        "vulns" => SubCheck::new(vulns::check(target, &vulns::VulnsOptions::default(), config)?, |report| {
+            let summary = &report.summary;
+            counted(&[(summary.critical, "critical advisory"), (summary.high, "high advisory"), (summary.moderate, "moderate advisory"), (summary.low, "low advisory")])
+        }),
+        "docker" => SubCheck::new(docker::check(target, &config.docker)?, |report| {
+            let count = |kind: &str| report.summary.by_kind.get(kind).copied().unwrap_or(0);
+            counted(&[
+                (count("NodeVersion"), "wrong Node.js image"),
+                (count("DevDependencies"), "devDependency install"),
+                (count("Dockerignore"), ".dockerignore issue"),
+                (count("SingleStage"), "single-stage build"),
+            ])
+        }),
+        "ci" => SubCheck::new(ci::check(target, &config.ci)?, |report| {
+            let count = |kind| report.issues.iter().filter(|issue| issue.kind == kind).count();
+            counted(&[
+                (count(ci::CiIssueKind::NodeVersion), "wrong Node.js version"),
+                (report.summary.missing_steps, "missing step"),
+                (count(ci::CiIssueKind::NoCache), "uncached install"),
+            ])
+        }),
+        _ => SubCheck { exit_code: ExitCode::Success, findings: Vec::new(), detail: String::new(), size_bytes: None },
+    };
+    Ok(result)
+}
+
+/// "14 unused imports, 2 broken imports", leaving out zeros; "no issues" when all are zero
+fn counted(counts: &[(usize, &str)]) -> String {
+    let parts: Vec<String> = counts.iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, noun)| match count {
+            1 => format!("1 {}", noun),
+            _ => format!("{} {}", count, plural(noun)),
+        })
+        .collect();
+    if parts.is_empty() { "no issues".to_string() } else { parts.join(", ") }
+}
+
+/// "image with issues" -> "images with issues", "budget exceeded" -> "budgets exceeded"
+fn plural(noun: &str) -> String {
+    let (head, tail) = match noun.split_once(' ') {
+        Some((first, rest)) if matches!(rest, "with issues" | "exceeded") => (first, format!(" {}", rest)),
+        _ => (noun, String::new()),
+    };
+    if head.ends_with("ss") || head.ends_with('x') || head.ends_with("sh") {
+        format!("{}es{}", head, tail)
+    } else if head.ends_with('s') || head.starts_with('@') {
+        format!("{}{}", head, tail)
+    } else {
+        format!("{}s{}", head, tail)
+    }
+}
+
+/// The files with the most findings, most first, then by path; relative to `root` where
+/// a sub-check reports absolute paths
+fn top_files(findings: &[Annotation], root: &Path) -> Vec<FileFindings> {
+    let mut by_file: BTreeMap<&str, usize> = BTreeMap::new();
+    for file in findings.iter().filter_map(|finding| finding.file.as_deref()) {
+        let file = Path::new(file).strip_prefix(root).ok().and_then(Path::to_str).unwrap_or(file);
+        *by_file.entry(file).or_default() += 1;
+    }
+    let mut files: Vec<FileFindings> = by_file.into_iter()
+        .map(|(file, findings)| FileFindings { file: file.to_string(), findings })
+        .collect();
+    files.sort_by(|a, b| b.findings.cmp(&a.findings).then_with(|| a.file.cmp(&b.file)));
+    files.truncate(TOP_FILES);
+    files
+}
+
+impl Annotate for DeployReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.checks.iter().flat_map(|check| check.findings.iter().cloned()).collect()
+    }
+}
+
+/// One JUnit suite per sub-check
+pub fn junit_suites(report: &DeployReport) -> Vec<TestSuite> {
+    report.checks.iter()
+        .map(|check| match (check.status, &check.message) {
+            (CheckStatus::Skipped, Some(reason)) => TestSuite::skipped(&check.name, reason, check.duration_ms),
+            _ => TestSuite::from_findings(&check.name, ExitCode::from_code(check.exit_code), check.findings.clone(), check.duration_ms),
+        })
+        .collect()
+}
+
+fn status_for(exit_code: ExitCode, blocking: bool) -> CheckStatus {
+    if exit_code.is_success() {
+        CheckStatus::Passed
+    } else if blocking {
+        CheckStatus::Failed
+    } else {
+        CheckStatus::Warning
+    }
+}
+
+fn create_summary(checks: &[CheckResult]) -> DeploySummary {
+    let count = |status: CheckStatus| checks.iter().filter(|c| c.status == status).count();
+    let failed = count(CheckStatus::Failed);
+
+    DeploySummary {
+        total_checks: checks.len(),
+        passed: count(CheckStatus::Passed),
+        warnings: count(CheckStatus::Warning),
+        failed,
+        skipped: count(CheckStatus::Skipped),
+        ready_to_deploy: failed == 0,
+    }
+}
+
+fn print_check_line(check: &CheckResult) {
+    let (icon, label) = match check.status {
+        CheckStatus::Passed => ("✅", "passed".green()),
+        CheckStatus::Warning => ("⚠️ ", "warning".yellow()),
+        CheckStatus::Failed => ("❌", "failed".red()),
+        CheckStatus::Skipped => ("⏭️ ", "skipped".dimmed()),
+    };
+
+    output::line(format!("  {} {:<10} {:<10} {}",
+        icon,
+        check.name.bold(),
+        label,
+        format!("({}ms)", check.duration_ms).dimmed()
+    ));
+    if let Some(message) = &check.message {
+        output::line(format!("     {}", message.dimmed()));
+    }
+    if let Some(detail) = &check.detail {
+        let levels = match (check.issues.critical, check.issues.errors + check.issues.warnings) {
+            (0, _) => String::new(),
+            (critical, rest) => format!(" ({} critical, {} other)", critical, rest),
+        };
+        output::line(format!("     {}", format!("{}{}", detail, levels).dimmed()));
+    }
+    if matches!(check.status, CheckStatus::Warning | CheckStatus::Failed) && !check.top_files.is_empty() {
+        let files: Vec<String> = check.top_files.iter().map(|top| format!("{} ({})", top.file, top.findings)).collect();
+        output::line(format!("     {} {}", "Top files:".dimmed(), files.join(", ").dimmed()));
+    }
+}
+
+fn print_summary(report: &DeployReport, quiet: bool) {
+    let summary = &report.summary;
+
+    if !quiet {
+        output::blank();
+        output::line("📈 SUMMARY".bold().white());
+        output::line("─────────".white());
+        output::line(format!("  Checks run: {}", summary.total_checks.to_string().bold()));
+        output::line(format!("  Passed: {}", summary.passed.to_string().green()));
+        output::line(format!("  Warnings: {}", summary.warnings.to_string().yellow()));
+        output::line(format!("  Failed: {}", summary.failed.to_string().red()));
+        if summary.skipped > 0 {
+            output::line(format!("  Skipped: {}", summary.skipped.to_string().dimmed()));
+        }
+        output::line(format!("  Duration: {}ms", report.duration_ms));
+        output::blank();
+    }
+
+    if summary.ready_to_deploy {
+        output::line("🚀 Ready to deploy".bold().green());
+        if summary.warnings > 0 {
+            output::line("💡 TIP: Non-blocking warnings found — run the individual commands for details".dimmed());
+        }
+    } else {
+        let failed: Vec<&str> = report.checks.iter()
+            .filter(|c| c.status == CheckStatus::Failed)
+            .map(|c| c.name.as_str())
+            .collect();
+        output::line("🛑 Not ready to deploy".bold().red());
+        output::line(format!("💡 TIP: Run `sniff {}` to see what is blocking", failed.join("`, `sniff ")).dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counted_leaves_out_zeros_and_pluralizes() {
+        assert_eq!(counted(&[(14, "unused import"), (0, "broken import")]), "14 unused imports");
+        assert_eq!(counted(&[(1, "missing variable"), (2, "security issue")]), "1 missing variable, 2 security issues");
+        assert_eq!(counted(&[(3, "@ts-ignore"), (2, "image with issues")]), "3 @ts-ignore, 2 images with issues");
+        assert_eq!(counted(&[(0, "large file")]), "no issues");
+    }
+
+    #[test]
+    fn test_top_files_orders_by_finding_count() {
+        let finding = |file: &str| Annotation::new(AnnotationLevel::Warning, file, 1, "Unused import", "Unused: x");
+        let findings: Vec<Annotation> = ["b.ts", "/app/a.ts", "c.ts", "/app/c.ts", "d.ts", "b.ts", "c.ts"].iter().map(|file| finding(file)).collect();
+        let top: Vec<(String, usize)> = top_files(&findings, Path::new("/app")).into_iter().map(|top| (top.file, top.findings)).collect();
+        assert_eq!(top, vec![("c.ts".to_string(), 3), ("b.ts".to_string(), 2), ("a.ts".to_string(), 1)]);
+    }
+}
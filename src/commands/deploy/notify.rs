@@ -0,0 +1,249 @@
+/// Posting the pipeline's result to a chat webhook, as configured in `[notify]`
+use anyhow::Result;
+use serde_json::{Value, json};
+use crate::common::{http, in_ci};
+use crate::config::{NotifyConfig, NotifyService};
+use super::{CheckResult, CheckStatus, DeployReport};
+
+/// Post `report` to the webhook in `config`. Does nothing without a webhook, or outside CI
+/// when `ci_only` is set
+pub fn send(config: &NotifyConfig, report: &DeployReport, project: &str) -> Result<()> {
+    let Some(url) = config.webhook_url.as_deref().filter(|url| !url.trim().is_empty()) else {
+        return Ok(());
+    };
+    if config.ci_only && !in_ci() {
+        return Ok(());
+    }
+    let card = Card::new(report, project, config.mention_on_failure.as_deref(), CiRun::from_env());
+    let payload = match config.service.unwrap_or_else(|| service_for(url)) {
+        NotifyService::Slack => card.slack(config.channel.as_deref()),
+        NotifyService::Discord => card.discord(),
+        NotifyService::Teams => card.teams(),
+    };
+    http::post(url, &payload, 15)
+}
+
+/// The service a webhook URL belongs to; Slack's format for anything else, which
+/// Mattermost and Rocket.Chat accept as well
+fn service_for(url: &str) -> NotifyService {
+    if url.contains("discord.com/api/webhooks") || url.contains("discordapp.com/api/webhooks") {
+        NotifyService::Discord
+    } else if url.contains(".webhook.office.com") || url.contains(".logic.azure.com") || url.contains(".powerplatform.com") {
+        NotifyService::Teams
+    } else {
+        NotifyService::Slack
+    }
+}
+
+/// The CI run that produced the report, linked from the card
+#[derive(Debug, Default)]
+struct CiRun {
+    url: Option<String>,
+    branch: Option<String>,
+    commit: Option<String>,
+}
+
+impl CiRun {
+    fn from_env() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let github = match (var("GITHUB_SERVER_URL"), var("GITHUB_REPOSITORY"), var("GITHUB_RUN_ID")) {
+            (Some(server), Some(repository), Some(run)) => Some(format!("{}/{}/actions/runs/{}", server, repository, run)),
+            _ => None,
+        };
+        CiRun {
+            url: github.or_else(|| var("CI_PIPELINE_URL")).or_else(|| var("CIRCLE_BUILD_URL")).or_else(|| var("BUILD_URL")),
+            branch: var("GITHUB_HEAD_REF").or_else(|| var("GITHUB_REF_NAME")).or_else(|| var("CI_COMMIT_REF_NAME")).or_else(|| var("CIRCLE_BRANCH")),
+            commit: var("GITHUB_SHA").or_else(|| var("CI_COMMIT_SHA")).or_else(|| var("CIRCLE_SHA1"))
+                .map(|sha| sha.chars().take(7).collect()),
+        }
+    }
+}
+
+/// What every service's message shows, in plain text
+struct Card<'a> {
+    ready: bool,
+    headline: String,
+    mention: Option<&'a str>,
+    checks: Vec<(&'a str, String)>,
+    footer: String,
+    run_url: Option<String>,
+}
+
+impl<'a> Card<'a> {
+    fn new(report: &'a DeployReport, project: &str, mention_on_failure: Option<&'a str>, run: CiRun) -> Self {
+        let summary = &report.summary;
+        let headline = if summary.ready_to_deploy {
+            format!("✅ {}: ready to deploy", project)
+        } else {
+            format!("🛑 {}: not ready to deploy", project)
+        };
+        let mut footer = format!(
+            "{} passed, {} warnings, {} failed, {} skipped in {:.1}s",
+            summary.passed, summary.warnings, summary.failed, summary.skipped, report.duration_ms as f64 / 1000.0
+        );
+        match (&run.branch, &run.commit) {
+            (Some(branch), Some(commit)) => footer.push_str(&format!(" · {} @ {}", branch, commit)),
+            (Some(ref_name), None) | (None, Some(ref_name)) => footer.push_str(&format!(" · {}", ref_name)),
+            (None, None) => {}
+        }
+        Card {
+            ready: summary.ready_to_deploy,
+            headline,
+            mention: mention_on_failure.filter(|_| !summary.ready_to_deploy),
+            checks: report.checks.iter().map(|check| (check.name.as_str(), check_line(check))).collect(),
+            footer,
+            run_url: run.url,
+        }
+    }
+
+    fn lines(&self) -> String {
+        self.checks.iter().map(|(name, line)| format!("{} {}", name, line)).collect::<Vec<_>>().join("\n")
+    }
+
+    fn slack(&self, channel: Option<&str>) -> Value {
+        let headline = match self.mention {
+            Some(mention) => format!("*{}* {}", self.headline, mention),
+            None => format!("*{}*", self.headline),
+        };
+        let mut footer = self.footer.clone();
+        if let Some(url) = &self.run_url {
+            footer.push_str(&format!(" · <{}|View run>", url));
+        }
+        let mut payload = json!({
+            "text": self.headline,
+            "blocks": [
+                { "type": "section", "text": { "type": "mrkdwn", "text": headline } },
+                { "type": "section", "text": { "type": "mrkdwn", "text": self.lines() } },
+                { "type": "context", "elements": [{ "type": "mrkdwn", "text": footer }] },
+            ],
+        });
+        if let Some(channel) = channel {
+            payload["channel"] = json!(channel);
+        }
+        payload
+    }
+
+    fn discord(&self) -> Value {
+        let mut embed = json!({
+            "title": self.headline,
+            "description": self.lines(),
+            "color": if self.ready { 0x2EB886 } else { 0xD92D20 },
+            "footer": { "text": self.footer },
+        });
+        if let Some(url) = &self.run_url {
+            embed["url"] = json!(url);
+        }
+        let mut payload = json!({ "embeds": [embed] });
+        if let Some(mention) = self.mention {
+            payload["content"] = json!(mention);
+        }
+        payload
+    }
+
+    /// An Adaptive Card, which Teams workflows and connectors both accept
+    fn teams(&self) -> Value {
+        let mut body = vec![json!({
+            "type": "TextBlock",
+            "text": self.headline,
+            "weight": "Bolder",
+            "size": "Medium",
+            "color": if self.ready { "Good" } else { "Attention" },
+            "wrap": true,
+        })];
+        if let Some(mention) = self.mention {
+            body.push(json!({ "type": "TextBlock", "text": mention, "wrap": true }));
+        }
+        let facts: Vec<Value> = self.checks.iter().map(|(name, line)| json!({ "title": name, "value": line })).collect();
+        body.push(json!({ "type": "FactSet", "facts": facts }));
+        body.push(json!({ "type": "TextBlock", "text": self.footer, "isSubtle": true, "wrap": true }));
+        let actions: Vec<Value> = self.run_url.iter()
+            .map(|url| json!({ "type": "Action.OpenUrl", "title": "View run", "url": url }))
+            .collect();
+        json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": body,
+                    "actions": actions,
+                },
+            }],
+        })
+    }
+}
+
+/// `❌ failed: 14 unused imports (120ms)`, with the reason for a skipped check
+fn check_line(check: &CheckResult) -> String {
+    let (icon, status) = match check.status {
+        CheckStatus::Passed => ("✅", "passed"),
+        CheckStatus::Warning => ("⚠️", "warning"),
+        CheckStatus::Failed => ("❌", "failed"),
+        CheckStatus::Skipped => ("⏭️", "skipped"),
+    };
+    match (&check.message, &check.detail) {
+        (Some(reason), _) if check.status == CheckStatus::Skipped => format!("{} {}: {}", icon, status, reason),
+        (_, Some(detail)) if check.status != CheckStatus::Passed => format!("{} {}: {} ({}ms)", icon, status, detail, check.duration_ms),
+        _ => format!("{} {} ({}ms)", icon, status, check.duration_ms),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::deploy::DeploySummary;
+
+    fn report(ready: bool) -> DeployReport {
+        let check = |name: &str, status, message: Option<&str>| CheckResult {
+            name: name.to_string(),
+            status,
+            blocking: name == "types",
+            exit_code: 0,
+            message: message.map(str::to_string),
+            duration_ms: 120,
+            issues_found: 16,
+            issues: Default::default(),
+            detail: Some("14 `any` usages, 2 @ts-ignore".to_string()),
+            top_files: Vec::new(),
+            findings: Vec::new(),
+        };
+        DeployReport {
+            checks: vec![
+                check("types", if ready { CheckStatus::Passed } else { CheckStatus::Failed }, None),
+                check("bundle", CheckStatus::Skipped, Some("No build output")),
+            ],
+            summary: DeploySummary { total_checks: 2, passed: usize::from(ready), warnings: 0, failed: usize::from(!ready), skipped: 1, ready_to_deploy: ready },
+            duration_ms: 2400,
+        }
+    }
+
+    #[test]
+    fn test_service_is_guessed_from_the_webhook_url() {
+        assert_eq!(service_for("https://hooks.slack.com/services/T0/B0/x"), NotifyService::Slack);
+        assert_eq!(service_for("https://discord.com/api/webhooks/1/abc"), NotifyService::Discord);
+        assert_eq!(service_for("https://contoso.webhook.office.com/webhookb2/x"), NotifyService::Teams);
+        assert_eq!(service_for("https://chat.example.com/hooks/x"), NotifyService::Slack);
+    }
+
+    #[test]
+    fn test_card_mentions_only_on_failure() {
+        let run = || CiRun { url: Some("https://github.com/acme/web/actions/runs/7".to_string()), branch: Some("main".to_string()), commit: Some("abc1234".to_string()) };
+        let failed = report(false);
+        let card = Card::new(&failed, "web", Some("<!here>"), run());
+        let slack = card.slack(Some("#deploys"));
+        assert_eq!(slack["channel"], "#deploys");
+        assert_eq!(slack["blocks"][0]["text"]["text"], "*🛑 web: not ready to deploy* <!here>");
+        assert_eq!(slack["blocks"][1]["text"]["text"], "types ❌ failed: 14 `any` usages, 2 @ts-ignore (120ms)\nbundle ⏭️ skipped: No build output");
+        assert_eq!(slack["blocks"][2]["elements"][0]["text"], "0 passed, 0 warnings, 1 failed, 1 skipped in 2.4s · main @ abc1234 · <https://github.com/acme/web/actions/runs/7|View run>");
+        assert_eq!(card.discord()["content"], "<!here>");
+        assert_eq!(card.teams()["attachments"][0]["content"]["body"][0]["color"], "Attention");
+
+        let passed = report(true);
+        let card = Card::new(&passed, "web", Some("<!here>"), CiRun::default());
+        assert_eq!(card.slack(None)["blocks"][0]["text"]["text"], "*✅ web: ready to deploy*");
+        assert!(card.discord().get("content").is_none());
+        assert!(card.teams()["attachments"][0]["content"]["actions"].as_array().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::common::annotations::sort_findings;
+use crate::common::json_output::StandardResponse;
+use crate::common::report_formatter::location;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, CommandOutcome, ExitCode, Severity, check_failure_threshold, create_annotated_json_output, output_result};
+
+/// `sniff diff <old> <new>`: the findings two `--json` reports of one command do not share
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiffReport {
+    /// The command both reports come from
+    pub command: String,
+    pub old_report: String,
+    pub new_report: String,
+    /// Findings only the new report has
+    pub introduced: Vec<Annotation>,
+    /// Findings only the old report has
+    pub resolved: Vec<Annotation>,
+    /// Findings of the new report that the old one has too, possibly on another line
+    pub persisting: Vec<Annotation>,
+    pub summary: DiffSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub introduced: usize,
+    pub resolved: usize,
+    pub persisting: usize,
+}
+
+/// Only introduced findings count, for `--format github`, `--fail-on` and the exit code
+impl Annotate for DiffReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.introduced.clone()
+    }
+}
+
+pub async fn run(old: &Path, new: &Path, json: bool, quiet: bool) -> Result<CommandOutcome<DiffReport>> {
+    let outcome = check(old, new)?;
+    let report = &outcome.report;
+    let response = create_annotated_json_output("diff", report, report.summary.introduced + report.summary.persisting, report.summary.introduced, None);
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+    Ok(outcome)
+}
+
+/// Compare the reports; fails when the new report has findings the old one does not
+pub fn check(old: &Path, new: &Path) -> Result<CommandOutcome<DiffReport>> {
+    let (old_command, old_findings) = read_report(old)?;
+    let (new_command, new_findings) = read_report(new)?;
+    if old_command != new_command {
+        return Err(anyhow!("Cannot compare a '{}' report with a '{}' report", old_command, new_command));
+    }
+
+    let (introduced, resolved, persisting) = diff_findings(old_findings, new_findings);
+    let summary = DiffSummary { introduced: introduced.len(), resolved: resolved.len(), persisting: persisting.len() };
+    let exit_code = check_failure_threshold(!introduced.is_empty(), ExitCode::ValidationFailed);
+    let report = DiffReport {
+        command: new_command,
+        old_report: old.display().to_string(),
+        new_report: new.display().to_string(),
+        introduced,
+        resolved,
+        persisting,
+        summary,
+    };
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+/// The command and findings of a report written with `--json`
+fn read_report(path: &Path) -> Result<(String, Vec<Annotation>)> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Cannot read report {}", path.display()))?;
+    let response: StandardResponse<serde_json::Value> = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a report written with --json", path.display()))?;
+    Ok((response.command, response.findings))
+}
+
+/// Split the findings into introduced, resolved and persisting ones. Lines move as code is
+/// edited, so findings are paired by rule, file and message wherever they are, and then
+/// by rule and file alone for messages that changed (e.g. a file's line count). Within
+/// a group the closest line wins
+fn diff_findings(old: Vec<Annotation>, new: Vec<Annotation>) -> (Vec<Annotation>, Vec<Annotation>, Vec<Annotation>) {
+    type Key = (String, Option<String>, Option<String>);
+    let exact = |finding: &Annotation| -> Key {
+        (finding.rule.clone().unwrap_or_else(|| finding.title.clone()), finding.file.clone(), Some(finding.message.clone()))
+    };
+    let loose = |finding: &Annotation| -> Key { (exact(finding).0, finding.file.clone(), None) };
+
+    let mut unmatched = new;
+    let mut remaining = old;
+    let mut persisting = Vec::new();
+    for key in [&exact as &dyn Fn(&Annotation) -> Key, &loose] {
+        let mut by_key: BTreeMap<Key, Vec<Annotation>> = BTreeMap::new();
+        for finding in remaining {
+            by_key.entry(key(&finding)).or_default().push(finding);
+        }
+        let mut still_unmatched = Vec::new();
+        for finding in unmatched {
+            let candidates = by_key.get_mut(&key(&finding)).filter(|candidates| !candidates.is_empty());
+            match candidates {
+                Some(candidates) => {
+                    let line = finding.line.unwrap_or(0);
+                    let closest = (0..candidates.len())
+                        .min_by_key(|&i| candidates[i].line.unwrap_or(0).abs_diff(line))
+                        .unwrap_or(0);
+                    candidates.remove(closest);
+                    persisting.push(finding);
+                }
+                None => still_unmatched.push(finding),
+            }
+        }
+        unmatched = still_unmatched;
+        remaining = by_key.into_values().flatten().collect();
+    }
+
+    sort_findings(&mut unmatched);
+    sort_findings(&mut remaining);
+    sort_findings(&mut persisting);
+    (unmatched, remaining, persisting)
+}
+
+fn print_report(report: &DiffReport, quiet: bool) {
+    let summary = &report.summary;
+    output::blank();
+    output::line(format!("🔀 {} findings: {} → {}", report.command, report.old_report, report.new_report).bold().blue());
+    output::line("─".repeat(40).dimmed());
+    print_findings(&format!("🆕 Introduced ({})", summary.introduced), &report.introduced, Color::Red);
+    if !quiet {
+        print_findings(&format!("✅ Resolved ({})", summary.resolved), &report.resolved, Color::Green);
+    }
+    output::line(format!("  {} introduced, {} resolved, {} persisting", summary.introduced, summary.resolved, summary.persisting));
+    if summary.introduced == 0 {
+        output::line("✅ No new findings".green());
+    }
+    output::blank();
+}
+
+fn print_findings(title: &str, findings: &[Annotation], color: Color) {
+    if findings.is_empty() {
+        return;
+    }
+    output::line(title.color(color).bold());
+    for finding in findings {
+        let level = match finding.level {
+            AnnotationLevel::Critical => Severity::Critical.paint("critical"),
+            AnnotationLevel::Error => Severity::High.paint("error"),
+            AnnotationLevel::Warning => Severity::Medium.paint("warning"),
+            AnnotationLevel::Notice => Severity::Low.paint("notice"),
+        };
+        output::line(format!("  {} {}  {}: {}", level, location(finding).cyan(), finding.labeled_title().bold(), finding.message));
+    }
+    output::blank();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(file: &str, line: usize, rule: &str, message: &str) -> Annotation {
+        Annotation::new(AnnotationLevel::Warning, file, line, "Finding", message).with_rule(rule)
+    }
+
+    #[test]
+    fn test_findings_are_paired_across_moved_lines_and_changed_messages() {
+        let old = vec![
+            finding("src/a.ts", 3, "SNF-IMP-UNUSED", "Unused: x"),
+            finding("src/a.ts", 9, "SNF-IMP-UNUSED", "Unused: y"),
+            finding("src/big.ts", 0, "SNF-LARGE-001", "320 lines"),
+            finding("src/b.ts", 1, "SNF-MEM-TIMER", "setInterval is never cleared"),
+        ];
+        let new = vec![
+            // Two lines were added above both imports
+            finding("src/a.ts", 5, "SNF-IMP-UNUSED", "Unused: x"),
+            finding("src/a.ts", 11, "SNF-IMP-UNUSED", "Unused: y"),
+            finding("src/a.ts", 12, "SNF-IMP-UNUSED", "Unused: z"),
+            finding("src/big.ts", 0, "SNF-LARGE-001", "334 lines"),
+        ];
+        let (introduced, resolved, persisting) = diff_findings(old, new);
+        assert_eq!(introduced.iter().map(|f| f.message.as_str()).collect::<Vec<_>>(), vec!["Unused: z"]);
+        assert_eq!(resolved.iter().map(|f| f.rule.as_deref().unwrap()).collect::<Vec<_>>(), vec!["SNF-MEM-TIMER"]);
+        assert_eq!(persisting.iter().map(|f| f.line.unwrap()).collect::<Vec<_>>(), vec![5, 11, 0]);
+    }
+}
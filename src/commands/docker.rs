@@ -0,0 +1,641 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::utils::FileUtils;
+use crate::config::DockerConfig;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, glob_matches, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DockerReport {
+    pub dockerfiles: Vec<Dockerfile>,
+    /// `engines.node` in package.json
+    pub engines_node: Option<String>,
+    pub issues: Vec<DockerIssue>,
+    pub summary: DockerSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Dockerfile {
+    pub path: String,
+    pub stages: Vec<Stage>,
+    /// The `.dockerignore` that applies to it, if any
+    pub dockerignore: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Stage {
+    /// The `AS` name
+    pub name: Option<String>,
+    /// The base image with build arguments substituted
+    pub image: String,
+    pub line: usize,
+    /// The Node.js version of a `node` base image, e.g. `20` for `node:20-alpine`
+    pub node_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DockerIssue {
+    pub kind: DockerIssueKind,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DockerIssueKind {
+    /// A `node` base image outside package.json `engines.node`
+    NodeVersion,
+    /// No `.dockerignore`, or one that lets node_modules, `.next/cache` or `.env` into the context
+    Dockerignore,
+    /// The final stage installs, or copies in, devDependencies
+    DevDependencies,
+    /// Build tools and sources ship in the image because there is only one stage
+    SingleStage,
+}
+
+impl DockerIssueKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            DockerIssueKind::NodeVersion => "SNF-DOCKER-NODE-VERSION",
+            DockerIssueKind::Dockerignore => "SNF-DOCKER-IGNORE",
+            DockerIssueKind::DevDependencies => "SNF-DOCKER-DEV-DEPS",
+            DockerIssueKind::SingleStage => "SNF-DOCKER-SINGLE-STAGE",
+        }
+    }
+
+    fn level(self) -> AnnotationLevel {
+        match self {
+            DockerIssueKind::NodeVersion => AnnotationLevel::Error,
+            DockerIssueKind::Dockerignore | DockerIssueKind::DevDependencies => AnnotationLevel::Warning,
+            DockerIssueKind::SingleStage => AnnotationLevel::Notice,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DockerSummary {
+    pub dockerfiles: usize,
+    pub stages: usize,
+    pub total_issues: usize,
+    pub by_kind: BTreeMap<String, usize>,
+    /// Issues dropped by `[[ignore]]` entries
+    pub ignored: usize,
+}
+
+impl Annotate for DockerReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            Annotation::new(issue.kind.level(), &issue.file, issue.line, format!("{:?}", issue.kind), &issue.message)
+                .with_rule(issue.kind.rule_id())
+        }).collect()
+    }
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<DockerReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!("Running Docker artifact check");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.docker)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "docker",
+        report,
+        report.summary.dockerfiles,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(issues = report.summary.total_issues, "Docker artifact check completed");
+
+    Ok(outcome)
+}
+
+/// Check the project's Dockerfiles and their `.dockerignore`. A `node` base image outside
+/// `engines.node` fails the check; the other issues are warnings. Projects without a Dockerfile
+/// are an error, which `sniff deploy` reports as a skipped check
+pub fn check(target: &ScanTarget, settings: &DockerConfig) -> Result<CommandOutcome<DockerReport>> {
+    let project_dir = target.project_dir("docker")?;
+    let dockerfiles = find_dockerfiles(project_dir, settings);
+    if dockerfiles.is_empty() {
+        return Err(anyhow!("No Dockerfile in {}", project_dir.display()));
+    }
+
+    let package: Value = fs::read_to_string(project_dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(Value::Null);
+    let engines_node = package["engines"]["node"].as_str().map(str::to_string);
+    let required_ignores: Vec<&String> = settings.dockerignore.iter()
+        // `.next` only exists in Next.js projects
+        .filter(|entry| !entry.starts_with(".next") || FileUtils::has_dependency(project_dir, "next"))
+        .collect();
+
+    let mut reports = Vec::new();
+    let mut issues = Vec::new();
+    for path in dockerfiles {
+        let content = fs::read_to_string(&path)?;
+        let file = paths::display(&path);
+        let parsed = parse(&content);
+        let mut issue = |kind, file: &str, line, message: String| issues.push(DockerIssue { kind, file: file.to_string(), line, message });
+
+        if let Some(range) = &engines_node {
+            for stage in &parsed.stages {
+                let Some(version) = &stage.node_version else { continue };
+                if satisfies(version, range) == Some(false) {
+                    issue(DockerIssueKind::NodeVersion, &file, stage.line,
+                        format!("{} runs Node.js {}, outside engines.node \"{}\" in package.json", stage.image, version, range));
+                }
+            }
+        }
+
+        for (line, message) in &parsed.dev_installs {
+            issue(DockerIssueKind::DevDependencies, &file, *line, message.clone());
+        }
+
+        if parsed.stages.len() == 1 && settings.require_multi_stage {
+            issue(DockerIssueKind::SingleStage, &file, parsed.stages[0].line,
+                "Single-stage build: compilers, sources and devDependencies ship in the image. Build in one stage and copy the output into a slim runtime stage".to_string());
+        }
+
+        let dockerignore = dockerignore_for(project_dir, &path);
+        match &dockerignore {
+            Some(ignore_path) => {
+                let patterns = fs::read_to_string(ignore_path).unwrap_or_default();
+                let missing: Vec<&str> = required_ignores.iter()
+                    .filter(|entry| !excludes(&patterns, entry))
+                    .map(|entry| entry.as_str())
+                    .collect();
+                if !missing.is_empty() {
+                    issue(DockerIssueKind::Dockerignore, &paths::display(ignore_path), 1,
+                        format!("Does not exclude {}; they are sent with the build context and can end up in the image", missing.join(", ")));
+                }
+            }
+            None => issue(DockerIssueKind::Dockerignore, &file, 1,
+                "No .dockerignore: node_modules, build caches and .env files are sent with the build context".to_string()),
+        }
+
+        reports.push(Dockerfile { path: file, stages: parsed.stages, dockerignore: dockerignore.as_deref().map(paths::display) });
+    }
+
+    let ignored = target.ignores()?.retain(&mut issues, |issue| (issue.kind.rule_id(), &issue.file));
+    issues.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    let mut by_kind: BTreeMap<String, usize> = BTreeMap::new();
+    for issue in &issues {
+        *by_kind.entry(format!("{:?}", issue.kind)).or_default() += 1;
+    }
+    let summary = DockerSummary {
+        dockerfiles: reports.len(),
+        stages: reports.iter().map(|dockerfile| dockerfile.stages.len()).sum(),
+        total_issues: issues.len(),
+        by_kind,
+        ignored,
+    };
+
+    let failed = issues.iter().any(|issue| issue.kind == DockerIssueKind::NodeVersion);
+    let exit_code = check_failure_threshold(failed, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(DockerReport { dockerfiles: reports, engines_node, issues, summary }, exit_code))
+}
+
+/// `[docker] dockerfiles`, else `Dockerfile`, `Dockerfile.*` and `*.Dockerfile` in the project root
+fn find_dockerfiles(project_dir: &Path, settings: &DockerConfig) -> Vec<PathBuf> {
+    if !settings.dockerfiles.is_empty() {
+        return settings.dockerfiles.iter().map(|path| project_dir.join(path)).filter(|path| path.is_file()).collect();
+    }
+    let mut found: Vec<PathBuf> = fs::read_dir(project_dir).into_iter().flatten().flatten()
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name == "Dockerfile" || name.starts_with("Dockerfile.") || name.to_ascii_lowercase().ends_with(".dockerfile")
+        })
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && !path.to_string_lossy().ends_with(".dockerignore"))
+        .collect();
+    found.sort();
+    found
+}
+
+/// BuildKit reads `<Dockerfile>.dockerignore` next to the Dockerfile before the context's `.dockerignore`
+fn dockerignore_for(project_dir: &Path, dockerfile: &Path) -> Option<PathBuf> {
+    let own = PathBuf::from(format!("{}.dockerignore", dockerfile.display()));
+    [own, project_dir.join(".dockerignore")].into_iter().find(|path| path.is_file())
+}
+
+/// Whether the `.dockerignore` `patterns` exclude `entry`; like Docker, the last matching line wins
+fn excludes(patterns: &str, entry: &str) -> bool {
+    let mut excluded = false;
+    for line in patterns.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let (negated, pattern) = match line.strip_prefix('!') {
+            Some(pattern) => (true, pattern.trim()),
+            None => (false, line),
+        };
+        let pattern = pattern.trim_start_matches("./").trim_start_matches('/').trim_start_matches("**/")
+            .trim_end_matches("/**").trim_end_matches('/');
+        // A parent directory excludes everything in it
+        let matches = |path: &str| pattern == path || glob_matches(pattern, path);
+        let mut prefix = String::new();
+        let mut matched = false;
+        for part in entry.split('/') {
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(part);
+            matched |= matches(&prefix);
+        }
+        if matched {
+            excluded = !negated;
+        }
+    }
+    excluded
+}
+
+/// What the checks need from a Dockerfile
+#[derive(Debug, Default)]
+struct Parsed {
+    stages: Vec<Stage>,
+    /// Lines of the final stage that bring devDependencies into the image, and why
+    dev_installs: Vec<(usize, String)>,
+}
+
+/// What a stage leaves behind for the stages built from it
+#[derive(Debug, Default, Clone)]
+struct StageState {
+    node_version: Option<String>,
+    node_env_production: bool,
+    /// Line of an install that keeps devDependencies in node_modules
+    full_install: Option<usize>,
+}
+
+fn parse(content: &str) -> Parsed {
+    let mut parsed = Parsed::default();
+    let mut args: HashMap<String, String> = HashMap::new();
+    let mut states: Vec<StageState> = Vec::new();
+    let mut names: HashMap<String, usize> = HashMap::new();
+    // (line, message) per stage, for copies and installs of devDependencies
+    let mut dev_installs: Vec<Vec<(usize, String)>> = Vec::new();
+
+    for (line, instruction) in instructions(content) {
+        let (keyword, rest) = instruction.split_once(char::is_whitespace).unwrap_or((&instruction, ""));
+        let rest = rest.trim();
+        match keyword.to_ascii_uppercase().as_str() {
+            // Only build arguments declared before the first FROM can be used in FROM
+            "ARG" if states.is_empty() => {
+                if let Some((name, value)) = rest.split_once('=') {
+                    args.insert(name.trim().to_string(), value.trim().trim_matches('"').to_string());
+                }
+            }
+            "FROM" => {
+                let words: Vec<&str> = rest.split_whitespace().filter(|word| !word.starts_with("--")).collect();
+                let image = substitute(words.first().copied().unwrap_or_default(), &args);
+                let name = match words.as_slice() {
+                    [_, keyword, name, ..] if keyword.eq_ignore_ascii_case("as") => Some(name.to_string()),
+                    _ => None,
+                };
+                let state = match names.get(&image.to_ascii_lowercase()) {
+                    Some(&index) => states[index].clone(),
+                    None => StageState { node_version: node_version(&image), ..StageState::default() },
+                };
+                if let Some(name) = &name {
+                    names.insert(name.to_ascii_lowercase(), states.len());
+                }
+                parsed.stages.push(Stage { name, image, line, node_version: state.node_version.clone() });
+                states.push(state);
+                dev_installs.push(Vec::new());
+            }
+            "ENV" if !states.is_empty() => {
+                let production = rest.split_whitespace().any(|word| word.trim_matches('"') == "NODE_ENV=production")
+                    || rest.starts_with("NODE_ENV production");
+                if production {
+                    states.last_mut().unwrap().node_env_production = true;
+                }
+            }
+            "RUN" if !states.is_empty() => {
+                let state = states.last_mut().unwrap();
+                if installs_dev_dependencies(rest, state.node_env_production) {
+                    state.full_install = Some(line);
+                    dev_installs.last_mut().unwrap().push((line,
+                        "Installs devDependencies in the final stage; use `npm ci --omit=dev` (`pnpm install --prod`, `yarn install --production`) or set NODE_ENV=production".to_string()));
+                }
+                // `npm ci && npm run build && npm prune --omit=dev` leaves only production packages
+                if rest.contains("npm prune") || rest.contains("pnpm prune") {
+                    state.full_install = None;
+                    dev_installs.last_mut().unwrap().retain(|(install, _)| *install != line);
+                }
+            }
+            "COPY" if !states.is_empty() => {
+                let from = rest.split_whitespace().find_map(|word| word.strip_prefix("--from="));
+                let source = from.and_then(|from| names.get(&from.to_ascii_lowercase()));
+                if let (Some(from), Some(&index)) = (from, source) {
+                    if rest.contains("node_modules") && states[index].full_install.is_some() {
+                        dev_installs.last_mut().unwrap().push((line,
+                            format!("Copies node_modules from `{}`, which installs devDependencies; run `npm prune --omit=dev` there first", from)));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // The final stage is the image that ships
+    parsed.dev_installs = dev_installs.pop().unwrap_or_default();
+    parsed
+}
+
+/// Instructions with their first line, continuation lines joined and comments dropped
+fn instructions(content: &str) -> Vec<(usize, String)> {
+    let mut instructions = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') || (trimmed.is_empty() && current.is_none()) {
+            continue;
+        }
+        let (start, text) = current.get_or_insert_with(|| (index + 1, String::new()));
+        let continued = trimmed.ends_with('\\');
+        text.push_str(trimmed.trim_end_matches('\\'));
+        text.push(' ');
+        if !continued {
+            instructions.push((*start, text.trim().to_string()));
+            current = None;
+        }
+    }
+    instructions.extend(current.map(|(start, text)| (start, text.trim().to_string())));
+    instructions
+}
+
+/// `$NAME`, `${NAME}` and `${NAME:-default}` replaced with the build argument's default
+fn substitute(image: &str, args: &HashMap<String, String>) -> String {
+    let mut result = String::new();
+    let mut rest = image;
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (expression, remaining) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => (braced, ""),
+            },
+            None => {
+                let end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+                (&after[..end], &after[end..])
+            }
+        };
+        let (name, default) = expression.split_once(":-").unwrap_or((expression, ""));
+        result.push_str(args.get(name).map(String::as_str).unwrap_or(default));
+        rest = remaining;
+    }
+    result.push_str(rest);
+    result
+}
+
+/// The Node.js version of `node:20.11-alpine` or `docker.io/library/node:18`; `None` for other
+/// images and for tags such as `lts` that name no version
+pub(crate) fn node_version(image: &str) -> Option<String> {
+    let image = image.split('@').next().unwrap_or(image);
+    let (name, tag) = image.rsplit_once(':').filter(|(_, tag)| !tag.contains('/'))?;
+    if name.rsplit('/').next() != Some("node") {
+        return None;
+    }
+    let version: String = tag.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+    let version = version.trim_end_matches('.');
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Whether `command` installs packages without leaving devDependencies out
+fn installs_dev_dependencies(command: &str, node_env_production: bool) -> bool {
+    command.split("&&").any(|part| {
+        let words: Vec<&str> = part.split_whitespace().collect();
+        let install = words.windows(2).any(|pair| matches!(pair, ["npm", "ci" | "install" | "i"] | ["pnpm", "install" | "i"] | ["yarn", "install"]))
+            || words.last() == Some(&"yarn");
+        // `npm install <package>` adds one package rather than installing package.json
+        let adds_package = words.iter().skip_while(|word| !matches!(**word, "install" | "i")).skip(1).any(|word| !word.starts_with('-'));
+        let production = node_env_production || part.contains("NODE_ENV=production")
+            || ["--omit=dev", "--omit dev", "--production", "--only=production", "--only=prod", "--prod", " -P"].iter().any(|flag| part.contains(flag));
+        install && !adds_package && !production
+    })
+}
+
+/// Whether `version` (`20`, `18.17` or `18.17.1`) is in the `engines` `range`; `None` when the
+/// range cannot be read. A partial version stands for the newest release of that line, which is
+/// what an image tag such as `node:20` installs
+pub fn satisfies(version: &str, range: &str) -> Option<bool> {
+    let version = {
+        let mut parts = version.split('.').map(|part| part.parse::<u64>().ok());
+        let major = parts.next()??;
+        (major, parts.next().flatten().unwrap_or(u64::MAX), parts.next().flatten().unwrap_or(u64::MAX))
+    };
+    let mut any = false;
+    for alternative in range.split("||") {
+        let bounds = bounds(alternative.trim())?;
+        any |= bounds.iter().all(|(op, bound)| match *op {
+            ">=" => version >= *bound,
+            ">" => version > *bound,
+            "<=" => version <= *bound,
+            _ => version < *bound,
+        });
+    }
+    Some(any)
+}
+
+type Version = (u64, u64, u64);
+
+/// The comparators of one `||` alternative, with carets, tildes, x-ranges and hyphen ranges spelled out
+fn bounds(range: &str) -> Option<Vec<(&'static str, Version)>> {
+    // `>= 18` is written with a space as often as without
+    let mut tokens: Vec<String> = Vec::new();
+    for word in range.split_whitespace() {
+        match tokens.last_mut() {
+            Some(last) if matches!(last.as_str(), ">=" | "<=" | ">" | "<" | "=" | "^" | "~") => last.push_str(word),
+            _ => tokens.push(word.to_string()),
+        }
+    }
+    if let [from, dash, to] = tokens.as_slice() {
+        if dash == "-" {
+            let (low, _) = partial(from)?;
+            let (high, parts) = partial(to)?;
+            return Some(vec![(">=", low), upper(high, parts, true)]);
+        }
+    }
+
+    let mut bounds = Vec::new();
+    for token in &tokens {
+        let op_len = token.find(|c: char| c.is_ascii_digit() || c == 'x' || c == 'X' || c == '*').unwrap_or(token.len());
+        let (op, version) = token.split_at(op_len);
+        let (base, parts) = partial(version.trim_start_matches(['v', '=']))?;
+        match op.trim_end_matches('v') {
+            ">=" => bounds.push((">=", base)),
+            ">" => bounds.push(upper(base, parts, false)),
+            "<" => bounds.push(("<", base)),
+            "<=" => bounds.push(upper(base, parts, true)),
+            "^" => {
+                bounds.push((">=", base));
+                let next = match base {
+                    (0, 0, _) if parts == 3 => (0, 0, base.2 + 1),
+                    (0, minor, _) if parts >= 2 => (0, minor + 1, 0),
+                    (major, _, _) => (major + 1, 0, 0),
+                };
+                bounds.push(("<", next));
+            }
+            "~" => {
+                bounds.push((">=", base));
+                bounds.push(if parts >= 2 { ("<", (base.0, base.1 + 1, 0)) } else { ("<", (base.0 + 1, 0, 0)) });
+            }
+            "" | "=" if parts == 0 => {}
+            "" | "=" => {
+                bounds.push((">=", base));
+                bounds.push(upper(base, parts, true));
+            }
+            _ => return None,
+        }
+    }
+    Some(bounds)
+}
+
+/// `18.2` as `(18, 2, 0)` with the number of parts given; `x` and `*` end the version
+fn partial(version: &str) -> Option<(Version, usize)> {
+    let mut numbers = Vec::new();
+    for part in version.split(['-', '+']).next()?.split('.') {
+        match part {
+            "x" | "X" | "*" | "" => break,
+            part => numbers.push(part.parse::<u64>().ok()?),
+        }
+    }
+    let at = |index: usize| numbers.get(index).copied().unwrap_or(0);
+    Some(((at(0), at(1), at(2)), numbers.len().min(3)))
+}
+
+/// The bound below every version in `base` with `parts` given: `<= 18` and `18` allow all of 18.x.
+/// `inclusive = false` turns it into the lower bound of `> 18`
+fn upper(base: Version, parts: usize, inclusive: bool) -> (&'static str, Version) {
+    let next = match parts {
+        0 => return if inclusive { (">=", (0, 0, 0)) } else { ("<", (0, 0, 0)) },
+        1 => (base.0 + 1, 0, 0),
+        2 => (base.0, base.1 + 1, 0),
+        _ => return if inclusive { ("<=", base) } else { (">", base) },
+    };
+    if inclusive { ("<", next) } else { (">=", next) }
+}
+
+fn print_report(report: &DockerReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🐳 Docker Artifact Report".bold().blue());
+        output::line("=========================".blue());
+        for dockerfile in &report.dockerfiles {
+            let stages: Vec<String> = dockerfile.stages.iter()
+                .map(|stage| match &stage.name {
+                    Some(name) => format!("{} ({})", name, stage.image),
+                    None => stage.image.clone(),
+                })
+                .collect();
+            output::line(format!("  {}: {}", dockerfile.path.cyan(), stages.join(" → ").dimmed()));
+        }
+        if let Some(engines) = &report.engines_node {
+            output::line(format!("  engines.node: {}", engines.dimmed()));
+        }
+        output::blank();
+    }
+
+    if report.issues.is_empty() {
+        output::line("✅ No Docker issues found!".green());
+    } else {
+        output::line("🔍 ISSUES".bold().yellow());
+        output::line("─────────".yellow());
+        for issue in &report.issues {
+            let rule = match issue.kind.level() {
+                AnnotationLevel::Critical | AnnotationLevel::Error => issue.kind.rule_id().red(),
+                AnnotationLevel::Warning => issue.kind.rule_id().yellow(),
+                AnnotationLevel::Notice => issue.kind.rule_id().dimmed(),
+            };
+            output::line(format!("  {}:{} - {} {}", issue.file.cyan(), issue.line, rule, issue.message));
+        }
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Dockerfiles: {}", summary.dockerfiles));
+    output::line(format!("  Stages: {}", summary.stages));
+    output::line(format!("  Issues: {}", summary.total_issues));
+    if summary.ignored > 0 {
+        output::line(format!("  Ignored: {}", summary.ignored.to_string().dimmed()));
+    }
+    output::blank();
+    output::line("💡 TIP: Next.js `output: \"standalone\"` gives a small runtime stage without node_modules".dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_ranges_and_image_versions() {
+        assert_eq!(node_version("node:20.11-alpine").as_deref(), Some("20.11"));
+        assert_eq!(node_version("docker.io/library/node:18@sha256:abc").as_deref(), Some("18"));
+        assert_eq!(node_version("node:lts-alpine"), None);
+        assert_eq!(node_version("nginx:1.25"), None);
+
+        assert_eq!(satisfies("20", ">=18.17.0"), Some(true));
+        assert_eq!(satisfies("16", ">=18.17.0"), Some(false));
+        assert_eq!(satisfies("18", ">= 18.17 < 21"), Some(true));
+        assert_eq!(satisfies("21.1", ">=18 <21"), Some(false));
+        assert_eq!(satisfies("20.5.1", "^20.9.0"), Some(false));
+        assert_eq!(satisfies("22", "^18 || ^20"), Some(false));
+        assert_eq!(satisfies("20", "18.x || 20.x"), Some(true));
+        assert_eq!(satisfies("20", "18 - 20"), Some(true));
+        assert_eq!(satisfies("20", "latest"), None);
+    }
+
+    #[test]
+    fn test_dockerfile_stages_and_dev_installs() {
+        let dockerfile = "ARG NODE_VERSION=18\n\
+            FROM node:${NODE_VERSION}-alpine AS deps\n\
+            RUN npm ci\n\
+            \n\
+            FROM deps AS builder\n\
+            RUN npm run build\n\
+            \n\
+            # runtime\n\
+            FROM node:${NODE_VERSION}-alpine AS runner\n\
+            COPY --from=builder /app/node_modules ./node_modules\n\
+            RUN npm install \\\n  --omit=dev\n\
+            RUN npm install -g pm2\n";
+        let parsed = parse(dockerfile);
+        let stages: Vec<(Option<&str>, &str, usize, Option<&str>)> = parsed.stages.iter()
+            .map(|stage| (stage.name.as_deref(), stage.image.as_str(), stage.line, stage.node_version.as_deref()))
+            .collect();
+        assert_eq!(stages, vec![
+            (Some("deps"), "node:18-alpine", 2, Some("18")),
+            (Some("builder"), "deps", 5, Some("18")),
+            (Some("runner"), "node:18-alpine", 9, Some("18")),
+        ]);
+        let lines: Vec<usize> = parsed.dev_installs.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![10]);
+
+        let single = parse("FROM node:20\nWORKDIR /app\nRUN yarn\nCMD [\"node\", \"server.js\"]\n");
+        assert_eq!(single.stages.len(), 1);
+        assert_eq!(single.dev_installs.len(), 1);
+        assert!(parse("FROM node:20\nENV NODE_ENV=production\nRUN npm ci\n").dev_installs.is_empty());
+    }
+
+    #[test]
+    fn test_dockerignore_patterns() {
+        let patterns = "# deps\nnode_modules/\n.next\n.env*\n!.env.example\n";
+        assert!(excludes(patterns, "node_modules"));
+        assert!(excludes(patterns, ".next/cache"));
+        assert!(excludes(patterns, ".env"));
+        assert!(!excludes(patterns, ".env.example"));
+        assert!(!excludes("**/node_modules\n", ".env"));
+        assert!(excludes("*\n!src\n", "node_modules"));
+        assert!(excludes("**/node_modules\n", "node_modules"));
+    }
+}
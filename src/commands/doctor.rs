@@ -0,0 +1,239 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+use crate::config::{Config, IssueLevel, layer_issues};
+use crate::common::{output, ExitCode, CommandOutcome, create_standard_json_output, git, output_result};
+use super::init::PackageManager;
+use super::types::find_tsc;
+
+/// Oldest Node.js the npm package and Lighthouse support
+const MIN_NODE_MAJOR: u32 = 18;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DoctorReport {
+    pub probes: Vec<Probe>,
+    pub summary: DoctorSummary,
+}
+
+/// One thing the analyzers depend on, and how to fix it when it is missing
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Probe {
+    pub name: String,
+    pub status: ProbeStatus,
+    /// The version found, or what is wrong
+    pub detail: String,
+    /// The commands that need it
+    pub needed_by: Vec<String>,
+    pub remedy: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProbeStatus {
+    Ok,
+    /// Some commands are skipped or limited without it
+    Warning,
+    /// Every command is affected
+    Failed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DoctorSummary {
+    pub total_probes: usize,
+    pub ok: usize,
+    pub warnings: usize,
+    pub failed: usize,
+}
+
+pub async fn run(root: &Path, json: bool, quiet: bool) -> Result<CommandOutcome<DoctorReport>> {
+    let start_time = Instant::now();
+    let outcome = check(root)?;
+    let report = &outcome.report;
+
+    let response = create_standard_json_output(
+        "doctor",
+        report,
+        report.summary.total_probes,
+        report.summary.warnings + report.summary.failed,
+        Some(start_time.elapsed().as_millis() as u64),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+    Ok(outcome)
+}
+
+/// Run every probe against the project in `root`
+pub fn check(root: &Path) -> Result<CommandOutcome<DoctorReport>> {
+    let paths = Config::discover(root);
+    let config = Config::load_layers(&paths).unwrap_or_default();
+    let package_manager = PackageManager::detect(root);
+
+    let probes = vec![
+        node_probe(),
+        tool_probe("npm", Command::new("npm"), &["perf", "types --tsc"], "npm comes with Node.js: https://nodejs.org"),
+        tsc_probe(root),
+        tool_probe("lighthouse", Command::new("lighthouse"), &["perf"], "Install it with `npm install -g lighthouse`; `sniff perf` is skipped without it"),
+        file_probe(root, "package.json", &["env", "imports", "context", "deploy"], "Run sniff from the project root, or pass --root <DIR>"),
+        file_probe(root, "tsconfig.json", &["types", "imports"], "Add one with `npx tsc --init` to check types and resolve path aliases"),
+        build_probe(root, &config.bundle.build_dirs, package_manager),
+        git_probe(root),
+        config_probe(&paths)?,
+    ];
+
+    let summary = DoctorSummary {
+        total_probes: probes.len(),
+        ok: probes.iter().filter(|probe| probe.status == ProbeStatus::Ok).count(),
+        warnings: probes.iter().filter(|probe| probe.status == ProbeStatus::Warning).count(),
+        failed: probes.iter().filter(|probe| probe.status == ProbeStatus::Failed).count(),
+    };
+    let exit_code = if summary.failed > 0 { ExitCode::GeneralError } else { ExitCode::Success };
+    Ok(CommandOutcome::new(DoctorReport { probes, summary }, exit_code))
+}
+
+fn probe(name: &str, needed_by: &[&str], result: Result<String, (ProbeStatus, String, String)>) -> Probe {
+    let needed_by = needed_by.iter().map(|command| command.to_string()).collect();
+    match result {
+        Ok(detail) => Probe { name: name.to_string(), status: ProbeStatus::Ok, detail, needed_by, remedy: None },
+        Err((status, detail, remedy)) => Probe { name: name.to_string(), status, detail, needed_by, remedy: Some(remedy) },
+    }
+}
+
+/// First line of `<program> --version`, when it runs
+fn version(mut command: Command) -> Option<String> {
+    let output = command.arg("--version").output().ok().filter(|output| output.status.success())?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(stdout.lines().next().unwrap_or_default().trim().to_string())
+}
+
+fn tool_probe(name: &str, command: Command, needed_by: &[&str], remedy: &str) -> Probe {
+    let result = version(command).ok_or_else(|| (ProbeStatus::Warning, "not found on PATH".to_string(), remedy.to_string()));
+    probe(name, needed_by, result)
+}
+
+fn node_probe() -> Probe {
+    let needed_by = &["perf", "types --tsc", "bundle"];
+    let remedy = format!("Install Node.js {} or later: https://nodejs.org", MIN_NODE_MAJOR);
+    let result = match version(Command::new("node")) {
+        None => Err((ProbeStatus::Warning, "not found on PATH".to_string(), remedy)),
+        Some(found) => match node_major(&found) {
+            Some(major) if major < MIN_NODE_MAJOR => Err((ProbeStatus::Warning, format!("{} is older than {}", found, MIN_NODE_MAJOR), remedy)),
+            _ => Ok(found),
+        },
+    };
+    probe("node", needed_by, result)
+}
+
+/// The major version of `v20.11.1`
+fn node_major(version: &str) -> Option<u32> {
+    version.trim_start_matches('v').split('.').next()?.parse().ok()
+}
+
+fn tsc_probe(root: &Path) -> Probe {
+    let result = match find_tsc(root) {
+        Some(tsc) => version(Command::new(&tsc))
+            .ok_or_else(|| (ProbeStatus::Warning, format!("{} does not run", tsc.display()), "Reinstall it with `npm install --save-dev typescript`".to_string())),
+        None => Err((ProbeStatus::Warning, "not installed".to_string(), "Install it with `npm install --save-dev typescript`".to_string())),
+    };
+    probe("tsc", &["types --tsc"], result)
+}
+
+fn file_probe(root: &Path, name: &str, needed_by: &[&str], remedy: &str) -> Probe {
+    let path = root.join(name);
+    let result = if !path.is_file() {
+        Err((ProbeStatus::Warning, format!("no {} in {}", name, root.display()), remedy.to_string()))
+    } else if name == "package.json" && !is_json(&path) {
+        // tsconfig.json may hold comments, so only package.json has to be strict JSON
+        Err((ProbeStatus::Warning, "is not valid JSON".to_string(), format!("Fix the syntax of {}", name)))
+    } else {
+        Ok("found".to_string())
+    };
+    probe(name, needed_by, result)
+}
+
+fn is_json(path: &Path) -> bool {
+    std::fs::read_to_string(path).ok()
+        .is_some_and(|content| serde_json::from_str::<serde_json::Value>(&content).is_ok())
+}
+
+fn build_probe(root: &Path, build_dirs: &[String], package_manager: PackageManager) -> Probe {
+    let found: Vec<&str> = build_dirs.iter().filter(|dir| root.join(dir).is_dir()).map(String::as_str).collect();
+    let result = if found.is_empty() {
+        Err((
+            ProbeStatus::Warning,
+            format!("none of {} exists", build_dirs.join(", ")),
+            format!("Build the project with `{}`, or list its output in `[bundle] build_dirs`", package_manager.run_script("build")),
+        ))
+    } else {
+        Ok(found.join(", "))
+    };
+    probe("build output", &["bundle", "deploy"], result)
+}
+
+fn git_probe(root: &Path) -> Probe {
+    let result = git::top_level(root)
+        .map(|top_level| format!("repository at {}", top_level.display()))
+        .map_err(|error| (ProbeStatus::Warning, error.to_string(), "Run `git init`, or run sniff inside the repository".to_string()));
+    probe("git", &["imports --staged", "hooks", "history"], result)
+}
+
+fn config_probe(paths: &[PathBuf]) -> Result<Probe> {
+    let issues = layer_issues(paths)?;
+    let errors = issues.iter().filter(|issue| issue.level == IssueLevel::Error).count();
+    let files = if paths.is_empty() {
+        "defaults (no sniff.toml)".to_string()
+    } else {
+        paths.iter().map(|path| path.display().to_string()).collect::<Vec<_>>().join(", ")
+    };
+    let result = if errors > 0 {
+        Err((ProbeStatus::Failed, format!("{} error(s) in {}", errors, files), "Run `sniff config doctor` for each problem and its fix".to_string()))
+    } else if !issues.is_empty() {
+        Err((ProbeStatus::Warning, format!("{} warning(s) in {}", issues.len(), files), "Run `sniff config doctor` for each problem and its fix".to_string()))
+    } else {
+        Ok(files)
+    };
+    Ok(probe("config", &["every command"], result))
+}
+
+fn print_report(report: &DoctorReport, quiet: bool) {
+    if !quiet {
+        output::line("🩺 sniff doctor".bold().blue());
+        output::blank();
+    }
+    for probe in &report.probes {
+        if quiet && probe.status == ProbeStatus::Ok {
+            continue;
+        }
+        let icon = match probe.status {
+            ProbeStatus::Ok => "✅",
+            ProbeStatus::Warning => "⚠️ ",
+            ProbeStatus::Failed => "❌",
+        };
+        output::line(format!("{} {:<13} {}", icon, probe.name.bold(), probe.detail));
+        if let Some(remedy) = &probe.remedy {
+            output::line(format!("   {} {}", format!("needed by {}:", probe.needed_by.join(", ")).dimmed(), remedy));
+        }
+    }
+
+    let summary = &report.summary;
+    output::blank();
+    if summary.failed > 0 {
+        output::line(format!("❌ {} of {} probes failed", summary.failed, summary.total_probes).bold().red());
+    } else if summary.warnings > 0 {
+        output::line(format!("⚠️  {} of {} probes need attention; the commands listed above are skipped or limited", summary.warnings, summary.total_probes).bold().yellow());
+    } else {
+        output::line("✅ Everything sniff depends on is in place".bold().green());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_major_reads_node_versions() {
+        assert_eq!(node_major("v20.11.1"), Some(20));
+        assert_eq!(node_major("16.0.0"), Some(16));
+        assert_eq!(node_major("unknown"), None);
+    }
+}
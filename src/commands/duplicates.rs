@@ -0,0 +1,445 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use crate::utils::FileUtils;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicatesReport {
+    pub clones: Vec<ClonePair>,
+    pub summary: DuplicatesSummary,
+}
+
+/// Two copies of the same token sequence
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClonePair {
+    pub lines: usize,
+    pub tokens: usize,
+    pub first: CloneLocation,
+    pub second: CloneLocation,
+    pub suggestion: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CloneLocation {
+    pub file: String,
+    pub start_line: usize,
+    pub end_line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicatesSummary {
+    pub files_scanned: usize,
+    pub total_lines: usize,
+    pub clone_pairs: usize,
+    pub duplicated_lines: usize,
+    pub duplication_percent: f64,
+    pub min_lines: usize,
+    pub min_tokens: usize,
+}
+
+impl Annotate for DuplicatesReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.clones.iter().map(|clone| {
+            Annotation::new(
+                AnnotationLevel::Warning,
+                &clone.first.file,
+                clone.first.start_line,
+                "Duplicated code",
+                format!(
+                    "{} lines ({} tokens) repeated in {}:{}-{}. {}",
+                    clone.lines, clone.tokens, clone.second.file, clone.second.start_line, clone.second.end_line, clone.suggestion
+                ),
+            )
+            .with_end_line(clone.first.end_line)
+            .with_rule("SNF-DUP-001")
+        }).collect()
+    }
+}
+
+struct Token {
+    hash: u64,
+    line: usize,
+}
+
+struct TokenizedFile {
+    path: PathBuf,
+    tokens: Vec<Token>,
+    total_lines: usize,
+}
+
+/// Base of the polynomial rolling hash over token hashes
+const HASH_BASE: u64 = 1_000_003;
+
+pub async fn run(target: &ScanTarget, min_lines: usize, min_tokens: usize, json: bool, quiet: bool) -> Result<CommandOutcome<DuplicatesReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running duplicate code analysis");
+
+    let outcome = check(target, min_lines, min_tokens, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "duplicates",
+        report,
+        report.summary.files_scanned,
+        report.summary.clone_pairs,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = report.summary.clone_pairs == 0, "duplicate code analysis completed");
+
+    Ok(outcome)
+}
+
+/// Detect copy-pasted blocks and decide the exit status, without printing a report
+pub fn check(target: &ScanTarget, min_lines: usize, min_tokens: usize, quiet: bool) -> Result<CommandOutcome<DuplicatesReport>> {
+    let scanner = FileScanner::for_command(target, "duplicates")?;
+    let files = scanner.find_target_files(target, &["ts", "tsx"]);
+
+    let tokenized: Vec<TokenizedFile> = FileUtils::process_files_parallel(
+        &files,
+        tokenize_file,
+        "Tokenizing files",
+        quiet
+    )?;
+
+    let clones = find_clones(&tokenized, min_lines, min_tokens.max(1));
+    let summary = create_summary(&tokenized, &clones, min_lines, min_tokens);
+    let exit_code = check_failure_threshold(!clones.is_empty(), ExitCode::ThresholdExceeded);
+
+    Ok(CommandOutcome::new(DuplicatesReport { clones, summary }, exit_code))
+}
+
+fn tokenize_file(path: &Path) -> Result<TokenizedFile> {
+    let content = fs::read_to_string(path)?;
+    Ok(TokenizedFile {
+        path: path.to_path_buf(),
+        tokens: tokenize(&content),
+        total_lines: content.lines().count(),
+    })
+}
+
+/// Split source into tokens, dropping whitespace, comments and import statements.
+///
+/// Identifiers keep their names, so only literal copy-paste (modulo formatting
+/// and comments) is detected, not renamed clones.
+fn tokenize(content: &str) -> Vec<Token> {
+    let chars: Vec<char> = content.chars().collect();
+    let mut tokens: Vec<(String, usize)> = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+        } else if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && next == Some('/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                if chars[i] == '\n' {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+        } else if c == '"' || c == '\'' || c == '`' {
+            let start = i;
+            let start_line = line;
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                } else if chars[i] == '\n' {
+                    line += 1;
+                    // Unterminated quote: stop at the end of the line
+                    if c != '`' {
+                        break;
+                    }
+                }
+                i += 1;
+            }
+            i += 1;
+            let end = i.min(chars.len());
+            tokens.push((chars[start..end].iter().collect(), start_line));
+        } else if c.is_alphanumeric() || c == '_' || c == '$' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '$') {
+                i += 1;
+            }
+            tokens.push((chars[start..i].iter().collect(), line));
+        } else {
+            tokens.push((c.to_string(), line));
+            i += 1;
+        }
+    }
+
+    strip_imports(tokens)
+        .into_iter()
+        .map(|(text, line)| {
+            let mut hasher = DefaultHasher::new();
+            text.hash(&mut hasher);
+            Token { hash: hasher.finish(), line }
+        })
+        .collect()
+}
+
+/// Import blocks are identical across many files by nature; they are not duplication
+fn strip_imports(tokens: Vec<(String, usize)>) -> Vec<(String, usize)> {
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut in_import = false;
+    let mut after_import = false;
+
+    for (index, (text, line)) in tokens.iter().enumerate() {
+        let is_string = text.starts_with(['"', '\'', '`']);
+        if in_import {
+            // The module specifier ends the statement
+            if is_string {
+                in_import = false;
+                after_import = true;
+            }
+            continue;
+        }
+        if after_import {
+            after_import = false;
+            if text == ";" {
+                continue;
+            }
+        }
+        // `import(...)` and `import.meta` are expressions, not import statements
+        if text == "import" && tokens.get(index + 1).is_some_and(|(next, _)| next != "(" && next != ".") {
+            in_import = true;
+            continue;
+        }
+        result.push((text.clone(), *line));
+    }
+
+    result
+}
+
+/// Rolling hash of every window of `window` consecutive tokens
+fn window_hashes(tokens: &[Token], window: usize) -> Vec<u64> {
+    if tokens.len() < window {
+        return Vec::new();
+    }
+
+    let top_power = (1..window).fold(1u64, |acc, _| acc.wrapping_mul(HASH_BASE));
+    let mut hashes = Vec::with_capacity(tokens.len() - window + 1);
+    let mut hash = tokens[..window].iter()
+        .fold(0u64, |acc, token| acc.wrapping_mul(HASH_BASE).wrapping_add(token.hash));
+    hashes.push(hash);
+
+    for i in window..tokens.len() {
+        hash = hash
+            .wrapping_sub(tokens[i - window].hash.wrapping_mul(top_power))
+            .wrapping_mul(HASH_BASE)
+            .wrapping_add(tokens[i].hash);
+        hashes.push(hash);
+    }
+
+    hashes
+}
+
+/// Match windows against earlier occurrences, then extend each match as far as the
+/// tokens agree. Every region is reported once, paired with its first occurrence.
+fn find_clones(files: &[TokenizedFile], min_lines: usize, min_tokens: usize) -> Vec<ClonePair> {
+    let mut seen: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+    let mut clones = Vec::new();
+
+    for (file_index, file) in files.iter().enumerate() {
+        let hashes = window_hashes(&file.tokens, min_tokens);
+        let mut position = 0;
+        let mut covered_until = 0;
+
+        while position < hashes.len() {
+            let hash = hashes[position];
+            let best = if position >= covered_until {
+                seen.get(&hash)
+                    .into_iter()
+                    .flatten()
+                    .map(|&(other_file, other_position)| {
+                        let length = match_length(files, (other_file, other_position), (file_index, position));
+                        (other_file, other_position, length)
+                    })
+                    .filter(|&(_, _, length)| length >= min_tokens)
+                    .max_by_key(|&(_, _, length)| length)
+            } else {
+                None
+            };
+
+            if let Some((other_file, other_position, length)) = best {
+                let first = location(&files[other_file], other_position, length);
+                let second = location(file, position, length);
+                let lines = second.end_line - second.start_line + 1;
+                if lines >= min_lines {
+                    clones.push(ClonePair {
+                        lines,
+                        tokens: length,
+                        suggestion: suggest_consolidation(&files[other_file].path, &file.path),
+                        first,
+                        second,
+                    });
+                }
+                covered_until = position + length;
+            }
+
+            seen.entry(hash).or_default().push((file_index, position));
+            position += 1;
+        }
+    }
+
+    clones.sort_by(|a, b| b.lines.cmp(&a.lines)
+        .then_with(|| a.second.file.cmp(&b.second.file))
+        .then_with(|| a.second.start_line.cmp(&b.second.start_line)));
+    clones
+}
+
+/// Number of equal tokens starting at both positions. Within one file the copies may not overlap.
+fn match_length(files: &[TokenizedFile], earlier: (usize, usize), later: (usize, usize)) -> usize {
+    let a = &files[earlier.0].tokens;
+    let b = &files[later.0].tokens;
+    let mut limit = (a.len() - earlier.1).min(b.len() - later.1);
+    if earlier.0 == later.0 {
+        limit = limit.min(later.1 - earlier.1);
+    }
+
+    (0..limit)
+        .take_while(|&offset| a[earlier.1 + offset].hash == b[later.1 + offset].hash)
+        .count()
+}
+
+fn location(file: &TokenizedFile, position: usize, length: usize) -> CloneLocation {
+    CloneLocation {
+        file: paths::display(&file.path),
+        start_line: file.tokens[position].line,
+        end_line: file.tokens[position + length - 1].line,
+    }
+}
+
+fn suggest_consolidation(first: &Path, second: &Path) -> String {
+    if first == second {
+        "Extract the repeated block into a function in this file".to_string()
+    } else if first.parent() == second.parent() {
+        let dir = first.parent().map(paths::display).unwrap_or_default();
+        format!("Move the shared logic into a helper module in {}/", dir)
+    } else {
+        "Extract the shared logic into a common module (e.g. lib/ or utils/) and import it from both files".to_string()
+    }
+}
+
+fn create_summary(files: &[TokenizedFile], clones: &[ClonePair], min_lines: usize, min_tokens: usize) -> DuplicatesSummary {
+    let total_lines: usize = files.iter().map(|f| f.total_lines).sum();
+
+    // Duplicated LOC counts each line of a later copy once, however many clones cover it
+    let duplicated: HashSet<(&str, usize)> = clones.iter()
+        .flat_map(|clone| (clone.second.start_line..=clone.second.end_line)
+            .map(move |line| (clone.second.file.as_str(), line)))
+        .collect();
+    let duplicated_lines = duplicated.len();
+
+    DuplicatesSummary {
+        files_scanned: files.len(),
+        total_lines,
+        clone_pairs: clones.len(),
+        duplicated_lines,
+        duplication_percent: if total_lines > 0 {
+            duplicated_lines as f64 / total_lines as f64 * 100.0
+        } else {
+            0.0
+        },
+        min_lines,
+        min_tokens,
+    }
+}
+
+fn print_report(report: &DuplicatesReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🧬 Duplicate Code Report".bold().blue());
+        output::line("========================".blue());
+        output::blank();
+    }
+
+    if report.clones.is_empty() {
+        output::line(format!("✅ No duplicated blocks of {}+ lines found!", report.summary.min_lines).green());
+        return;
+    }
+
+    output::line("📋 CLONE PAIRS".bold().yellow());
+    output::line("──────────────".yellow());
+    for clone in report.clones.iter().take(20) {
+        output::line(format!("  {} ({} tokens)", format!("{} lines", clone.lines).bold().yellow(), clone.tokens));
+        output::line(format!("    {}:{}-{}", clone.first.file.cyan(), clone.first.start_line, clone.first.end_line));
+        output::line(format!("    {}:{}-{}", clone.second.file.cyan(), clone.second.start_line, clone.second.end_line));
+        output::line(format!("    💡 {}", clone.suggestion.dimmed()));
+        output::blank();
+    }
+    if report.clones.len() > 20 {
+        output::line(format!("  {} {} more clone pairs...", "...and".dimmed(), report.clones.len() - 20));
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Clone pairs: {}", summary.clone_pairs.to_string().yellow()));
+    output::line(format!("  Duplicated lines: {} of {} ({:.1}%)",
+        summary.duplicated_lines.to_string().yellow(),
+        summary.total_lines,
+        summary.duplication_percent
+    ));
+    output::blank();
+    output::line("💡 TIP: Consolidate the largest clones first — they give the biggest reduction".dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, content: &str) -> TokenizedFile {
+        TokenizedFile {
+            path: PathBuf::from(name),
+            tokens: tokenize(content),
+            total_lines: content.lines().count(),
+        }
+    }
+
+    #[test]
+    fn test_reformatted_copy_is_detected() {
+        let original = "import { a } from './a';\nexport function total(items) {\n  let sum = 0;\n  for (const item of items) {\n    sum += item.price * item.quantity;\n  }\n  return sum;\n}\n";
+        let copy = "// copied\nfunction other() {}\nexport function total(items) {\n  let sum = 0;\n  for (const item of items) { sum += item.price * item.quantity; }\n  return sum;\n}\n";
+        let files = vec![file("a.ts", original), file("b.ts", copy)];
+
+        let clones = find_clones(&files, 3, 15);
+        assert_eq!(clones.len(), 1);
+        assert_eq!((clones[0].first.start_line, clones[0].first.end_line), (2, 8));
+        assert_eq!((clones[0].second.start_line, clones[0].second.end_line), (3, 7));
+    }
+
+    #[test]
+    fn test_imports_and_short_matches_are_ignored() {
+        let a = "import { useState, useEffect } from 'react';\nimport { api } from './api';\nconst x = 1;\n";
+        let b = "import { useState, useEffect } from 'react';\nimport { api } from './api';\nconst y = 2;\n";
+        let files = vec![file("a.ts", a), file("b.ts", b)];
+
+        assert!(find_clones(&files, 1, 5).is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use crate::commands::secrets::redact;
+use crate::common::{paths, Ignores};
+use super::parser;
+use super::usage::{collect_references, is_built_in};
+
+/// Result of `sniff env --compare`: how the variables of the env files of each environment differ
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnvCompareReport {
+    /// The compared files, in the order given
+    pub files: Vec<String>,
+    /// Variables some of the files define and others do not
+    pub missing: Vec<MissingVariable>,
+    /// Variables defined with different values, which are redacted
+    pub differing: Vec<DifferingVariable>,
+    /// Variables defined in the files but read nowhere in code
+    pub unused: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MissingVariable {
+    pub name: String,
+    pub defined_in: Vec<String>,
+    pub missing_from: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DifferingVariable {
+    pub name: String,
+    pub values: Vec<FileValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileValue {
+    pub file: String,
+    pub value: String,
+}
+
+/// Compare `files` (relative to `project_dir`), or every `.env*` file but `.env.example` when none are given
+pub fn compare_env_files(project_dir: &Path, files: &[String], ignores: &Ignores) -> Result<EnvCompareReport> {
+    let files = if files.is_empty() { discover_env_files(project_dir)? } else { files.to_vec() };
+    if files.len() < 2 {
+        return Err(anyhow!("Nothing to compare: `sniff env --compare` needs at least two env files, found {}", files.len()));
+    }
+
+    let mut definitions: Vec<(String, BTreeMap<String, String>)> = Vec::new();
+    for file in &files {
+        let path = project_dir.join(file);
+        let content = fs::read_to_string(&path).with_context(|| format!("Cannot read env file {}", path.display()))?;
+        let values = parser::parse(&content, &HashMap::new()).entries.into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect();
+        definitions.push((paths::display(&path), values));
+    }
+
+    let names: BTreeSet<&String> = definitions.iter().flat_map(|(_, values)| values.keys()).collect();
+    let mut missing = Vec::new();
+    let mut differing = Vec::new();
+    for name in &names {
+        let (defined, absent): (Vec<_>, Vec<_>) = definitions.iter().partition(|(_, values)| values.contains_key(*name));
+        let missing_from: Vec<String> = absent.iter()
+            .map(|(file, _)| file.clone())
+            .filter(|file| !ignores.ignores("SNF-ENV-DRIFT", file))
+            .collect();
+        if !missing_from.is_empty() {
+            missing.push(MissingVariable {
+                name: name.to_string(),
+                defined_in: defined.iter().map(|(file, _)| file.clone()).collect(),
+                missing_from,
+            });
+        }
+
+        let values: BTreeSet<&String> = defined.iter().map(|(_, values)| &values[*name]).collect();
+        if values.len() > 1 {
+            differing.push(DifferingVariable {
+                name: name.to_string(),
+                values: defined.iter()
+                    .map(|(file, values)| FileValue { file: file.clone(), value: redact(&values[*name]) })
+                    .collect(),
+            });
+        }
+    }
+
+    let (_, references) = collect_references(project_dir)?;
+    let unused = names.into_iter()
+        .filter(|name| !references.contains_key(*name) && !is_built_in(name))
+        .cloned()
+        .collect();
+
+    Ok(EnvCompareReport {
+        files: definitions.into_iter().map(|(file, _)| file).collect(),
+        missing,
+        differing,
+        unused,
+    })
+}
+
+fn discover_env_files(project_dir: &Path) -> Result<Vec<String>> {
+    let mut env_files: Vec<String> = fs::read_dir(project_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(".env") && name != ".env.example")
+        .collect();
+    env_files.sort();
+    Ok(env_files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_files_are_compared_by_variable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join(".env.production"), "API_URL=https://api.example.com\nSENTRY_DSN=https://key@sentry.io/1\nLEGACY=1\n").unwrap();
+        fs::write(dir.path().join(".env.staging"), "API_URL=https://staging.example.com\nLEGACY=1\n").unwrap();
+        fs::write(dir.path().join(".env.example"), "API_URL=\n").unwrap();
+        fs::write(dir.path().join("app.ts"), "fetch(process.env.API_URL);\nlog(process.env.SENTRY_DSN);\n").unwrap();
+
+        let report = compare_env_files(dir.path(), &[], &Ignores::default()).unwrap();
+
+        assert_eq!(report.files.len(), 2);
+        assert_eq!(report.missing.len(), 1);
+        assert_eq!(report.missing[0].name, "SENTRY_DSN");
+        assert!(report.missing[0].missing_from[0].ends_with(".env.staging"));
+        assert_eq!(report.differing.len(), 1);
+        assert_eq!(report.differing[0].name, "API_URL");
+        assert_eq!(report.differing[0].values[0].value, "http************");
+        assert_eq!(report.unused, vec!["LEGACY"]);
+
+        let only_one = compare_env_files(dir.path(), &[".env.staging".to_string()], &Ignores::default());
+        assert!(only_one.is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use super::usage::{collect_references, is_built_in};
+
+const EXAMPLE_FILE: &str = ".env.example";
+
+/// Result of `sniff env --init-example`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnvExampleReport {
+    pub path: String,
+    pub created: bool,
+    pub added: Vec<ExampleEntry>,
+    pub already_documented: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExampleEntry {
+    pub name: String,
+    /// Where the variable was found: an env file name or the first `file:line` reading it
+    pub source: String,
+}
+
+struct Candidate {
+    name: String,
+    source: String,
+    comments: Vec<String>,
+}
+
+static DEFINITION: OnceLock<Regex> = OnceLock::new();
+
+/// Matches `KEY=...`, `export KEY=...` and commented-out `# KEY=...`
+fn definition_regex() -> &'static Regex {
+    DEFINITION.get_or_init(|| Regex::new(r"^\s*(#\s*)?(?:export\s+)?([A-Za-z_][A-Za-z0-9_]*)\s*=").unwrap())
+}
+
+/// Add every variable defined in a `.env*` file or read in code to `.env.example`,
+/// without values. Existing lines, including comments, are kept as they are.
+pub fn update_example(project_dir: &Path) -> Result<EnvExampleReport> {
+    let example_path = project_dir.join(EXAMPLE_FILE);
+    let existing = if example_path.exists() {
+        Some(fs::read_to_string(&example_path)?)
+    } else {
+        None
+    };
+
+    // Commented-out keys count as documented: they were disabled on purpose
+    let documented: HashSet<String> = existing.as_deref().unwrap_or("").lines()
+        .filter_map(|line| definition_regex().captures(line))
+        .map(|caps| caps[2].to_string())
+        .collect();
+
+    let mut seen = documented.clone();
+    let new_entries: Vec<Candidate> = collect_candidates(project_dir)?
+        .into_iter()
+        .filter(|candidate| seen.insert(candidate.name.clone()))
+        .collect();
+
+    if !new_entries.is_empty() {
+        let mut content = match &existing {
+            Some(text) => {
+                let mut text = text.trim_end().to_string();
+                text.push_str("\n\n# Added by sniff env --init-example\n");
+                text
+            }
+            None => "# Environment variables used by this project.\n# Copy this file to .env.local and fill in the values.\n\n".to_string(),
+        };
+        for entry in &new_entries {
+            for comment in &entry.comments {
+                content.push_str(comment);
+                content.push('\n');
+            }
+            content.push_str(&entry.name);
+            content.push_str("=\n");
+        }
+        fs::write(&example_path, content)?;
+    }
+
+    Ok(EnvExampleReport {
+        path: EXAMPLE_FILE.to_string(),
+        created: existing.is_none() && !new_entries.is_empty(),
+        added: new_entries.into_iter()
+            .map(|entry| ExampleEntry { name: entry.name, source: entry.source })
+            .collect(),
+        already_documented: documented.len(),
+    })
+}
+
+/// Variables from env files first (in file order, with the comments above them),
+/// then variables that are only read in code
+fn collect_candidates(project_dir: &Path) -> Result<Vec<Candidate>> {
+    let mut env_files: Vec<String> = fs::read_dir(project_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(".env") && name != EXAMPLE_FILE)
+        .collect();
+    env_files.sort();
+
+    let mut candidates = Vec::new();
+    for file_name in env_files {
+        let Ok(content) = fs::read_to_string(project_dir.join(&file_name)) else { continue };
+        let mut comments = Vec::new();
+        for line in content.lines() {
+            let trimmed = line.trim();
+            match definition_regex().captures(line) {
+                Some(caps) if caps.get(1).is_none() => candidates.push(Candidate {
+                    name: caps[2].to_string(),
+                    source: file_name.clone(),
+                    comments: std::mem::take(&mut comments),
+                }),
+                _ if trimmed.starts_with('#') => comments.push(trimmed.to_string()),
+                _ => comments.clear(),
+            }
+        }
+    }
+
+    let (_, references) = collect_references(project_dir)?;
+    for (name, references) in references {
+        if is_built_in(&name) {
+            continue;
+        }
+        let source = references.first()
+            .map(|r| format!("{}:{}", r.file, r.line))
+            .unwrap_or_default();
+        candidates.push(Candidate { name, source, comments: Vec::new() });
+    }
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example_is_updated_without_values() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join(".env.local"), "# Stripe secret key\nSTRIPE_KEY=sk_test_123\n\nAPI_URL=http://localhost\n").unwrap();
+        fs::write(dir.path().join(".env.example"), "# Public API endpoint\nAPI_URL=https://api.example.com\n# LEGACY=\n").unwrap();
+        fs::write(dir.path().join("app.ts"), "init(process.env.SENTRY_DSN, process.env.LEGACY, process.env.NODE_ENV);\n").unwrap();
+
+        let report = update_example(dir.path()).unwrap();
+        let names: Vec<&str> = report.added.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["STRIPE_KEY", "SENTRY_DSN"]);
+        assert!(!report.created);
+
+        let content = fs::read_to_string(dir.path().join(".env.example")).unwrap();
+        assert!(content.starts_with("# Public API endpoint\nAPI_URL=https://api.example.com\n"));
+        assert!(content.contains("# Stripe secret key\nSTRIPE_KEY=\n"));
+        assert!(!content.contains("sk_test_123"));
+
+        // Running again adds nothing
+        assert!(update_example(dir.path()).unwrap().added.is_empty());
+    }
+}
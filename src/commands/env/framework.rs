@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// The framework whose conventions decide which environment variables reach the browser
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Framework {
+    #[default]
+    NextJs,
+    Remix,
+    Astro,
+    Nuxt,
+    Vite,
+}
+
+impl Framework {
+    /// Detected from `package.json`; meta-frameworks built on Vite are checked before Vite itself.
+    /// Projects without a recognised dependency keep the Next.js conventions
+    pub fn detect(project_dir: &Path) -> Self {
+        let Ok(content) = fs::read_to_string(project_dir.join("package.json")) else { return Self::default() };
+        let Ok(package) = serde_json::from_str::<serde_json::Value>(&content) else { return Self::default() };
+        let has = |name: &str| {
+            ["dependencies", "devDependencies"].iter().any(|section| {
+                package.get(section).and_then(|deps| deps.as_object()).is_some_and(|deps| {
+                    deps.keys().any(|dep| dep == name || (name.ends_with('/') && dep.starts_with(name)))
+                })
+            })
+        };
+
+        if has("next") {
+            Self::NextJs
+        } else if has("@remix-run/") || has("@react-router/") {
+            Self::Remix
+        } else if has("astro") {
+            Self::Astro
+        } else if has("nuxt") {
+            Self::Nuxt
+        } else if has("vite") {
+            Self::Vite
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Variables with this prefix are inlined into client bundles; Remix exposes none
+    pub fn public_prefix(self) -> Option<&'static str> {
+        match self {
+            Self::NextJs => Some("NEXT_PUBLIC_"),
+            Self::Remix => None,
+            Self::Astro => Some("PUBLIC_"),
+            Self::Nuxt => Some("NUXT_PUBLIC_"),
+            Self::Vite => Some("VITE_"),
+        }
+    }
+
+    pub fn is_public(self, name: &str) -> bool {
+        self.public_prefix().is_some_and(|prefix| name.starts_with(prefix))
+    }
+
+    /// Whether a source file (path relative to the project root) runs in the browser
+    pub fn is_client_file(self, relative: &str, content: &str) -> bool {
+        let file_name = relative.rsplit('/').next().unwrap_or(relative);
+        let extension = file_name.rsplit('.').next().unwrap_or("");
+        match self {
+            Self::NextJs => is_client_component(content),
+            // `*.client.ts` modules, including `entry.client.tsx`, are browser-only
+            Self::Remix => file_name.contains(".client."),
+            // UI framework components are hydrated with `client:*` directives
+            Self::Astro => matches!(extension, "jsx" | "tsx" | "vue" | "svelte"),
+            Self::Nuxt => {
+                let top = relative.split('/').next().unwrap_or("");
+                !file_name.contains(".server.")
+                    && top != "server"
+                    && (extension == "vue" || matches!(top, "components" | "composables" | "layouts" | "pages" | "plugins"))
+            }
+            // A Vite app's `src/` is the browser bundle
+            Self::Vite => relative.starts_with("src/"),
+        }
+    }
+
+    /// Where client code reads variables from, for messages
+    pub fn client_rule(self) -> String {
+        match (self, self.public_prefix()) {
+            (Self::Remix, _) | (_, None) => "Remix exposes no variables to the browser; return them from a loader".to_string(),
+            (_, Some(prefix)) => format!("{} only exposes {}* variables to the browser", self, prefix),
+        }
+    }
+}
+
+impl fmt::Display for Framework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::NextJs => "Next.js",
+            Self::Remix => "Remix",
+            Self::Astro => "Astro",
+            Self::Nuxt => "Nuxt",
+            Self::Vite => "Vite",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A file whose leading directives include `'use client'` (and not `'use server'`)
+fn is_client_component(content: &str) -> bool {
+    let mut is_client = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") || trimmed.starts_with("/*") || trimmed.starts_with('*') {
+            continue;
+        }
+        match trimmed.trim_end_matches(';') {
+            "'use client'" | "\"use client\"" => is_client = true,
+            "'use server'" | "\"use server\"" => return false,
+            // Directives must come before any other statement
+            _ => break,
+        }
+    }
+    is_client
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_directive_detection() {
+        assert!(is_client_component("// Button\n'use client';\n\nexport function Button() {}\n"));
+        assert!(is_client_component("\"use client\"\nimport x from 'y';\n"));
+        assert!(!is_client_component("'use server';\nexport async function save() {}\n"));
+        assert!(!is_client_component("import x from 'y';\n'use client';\n"));
+    }
+
+    #[test]
+    fn test_framework_detection_and_client_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        assert_eq!(Framework::detect(dir.path()), Framework::NextJs);
+        for (dependencies, expected) in [
+            (r#""@remix-run/react": "2", "vite": "5""#, Framework::Remix),
+            (r#""astro": "4", "vite": "5""#, Framework::Astro),
+            (r#""nuxt": "3""#, Framework::Nuxt),
+            (r#""react": "18", "vite": "5""#, Framework::Vite),
+            (r#""next": "14""#, Framework::NextJs),
+        ] {
+            fs::write(dir.path().join("package.json"), format!(r#"{{"dependencies": {{{}}}}}"#, dependencies)).unwrap();
+            assert_eq!(Framework::detect(dir.path()), expected);
+        }
+
+        assert!(Framework::Vite.is_public("VITE_API_URL"));
+        assert!(!Framework::Astro.is_public("SECRET"));
+        assert!(!Framework::Remix.is_public("PUBLIC_URL"));
+        assert!(Framework::Remix.is_client_file("app/entry.client.tsx", ""));
+        assert!(!Framework::Remix.is_client_file("app/routes/_index.tsx", ""));
+        assert!(Framework::Astro.is_client_file("src/components/Counter.tsx", ""));
+        assert!(!Framework::Astro.is_client_file("src/pages/index.astro", ""));
+        assert!(Framework::Nuxt.is_client_file("app.vue", ""));
+        assert!(!Framework::Nuxt.is_client_file("server/api/users.ts", ""));
+        assert!(Framework::Vite.is_client_file("src/main.ts", ""));
+        assert!(!Framework::Vite.is_client_file("vite.config.ts", ""));
+    }
+}
@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use crate::commands::secrets::{classify_value, redact, SecretKind};
+use crate::common::{git, paths};
+use super::parser;
+
+/// Env files git can see: the way credentials in `.env` files usually leak
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GitEnvReport {
+    /// Env files in the project that no `.gitignore` rule excludes (templates like `.env.example` aside)
+    pub not_ignored: Vec<String>,
+    /// Env files a commit on any branch added, even if they were deleted since
+    pub committed: Vec<CommittedEnvFile>,
+    /// Values in tracked env files, templates included, that look like credentials
+    pub tracked_secrets: Vec<TrackedSecret>,
+}
+
+impl GitEnvReport {
+    pub fn total_issues(&self) -> usize {
+        self.not_ignored.len() + self.committed.len() + self.tracked_secrets.len()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CommittedEnvFile {
+    pub file: String,
+    /// The commit that first added the file
+    pub commit: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrackedSecret {
+    pub name: String,
+    pub file: String,
+    pub line: usize,
+    pub kind: SecretKind,
+    pub redacted: String,
+}
+
+const ENV_FILES: &str = ".env*";
+
+/// `sniff env --check-git`: gitignore coverage, history and tracked contents of the `.env*` files
+pub fn analyze_git(project_dir: &Path) -> Result<GitEnvReport> {
+    git::top_level(project_dir).context("--check-git needs a git repository")?;
+    let report_path = |file: &str| paths::display(&project_dir.join(file));
+
+    let env_files: Vec<String> = fs::read_dir(project_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| name.starts_with(".env") && !is_template(name))
+        .collect();
+    let ignored = git::ignored(project_dir, &env_files)?;
+    let mut not_ignored: Vec<String> = env_files.iter()
+        .filter(|file| !ignored.contains(*file))
+        .map(|file| report_path(file))
+        .collect();
+    not_ignored.sort();
+
+    // Newest first, so the last commit seen for a file is the one that first added it
+    let mut first_added: BTreeMap<String, String> = BTreeMap::new();
+    for (commit, file) in git::added_in_history(project_dir, ENV_FILES)? {
+        if !is_template(&file) {
+            first_added.insert(report_path(&file), commit);
+        }
+    }
+    let committed = first_added.into_iter()
+        .map(|(file, commit)| CommittedEnvFile { file, commit })
+        .collect();
+
+    let mut tracked_secrets = Vec::new();
+    for file in git::tracked_files(project_dir, ENV_FILES)? {
+        let Ok(content) = fs::read_to_string(project_dir.join(&file)) else { continue };
+        for entry in parser::parse(&content, &HashMap::new()).entries {
+            if let Some(kind) = classify_value(&entry.value) {
+                tracked_secrets.push(TrackedSecret {
+                    name: entry.key,
+                    file: report_path(&file),
+                    line: entry.line,
+                    kind,
+                    redacted: redact(&entry.value),
+                });
+            }
+        }
+    }
+
+    Ok(GitEnvReport { not_ignored, committed, tracked_secrets })
+}
+
+/// `.env.example` and friends document variables and are meant to be committed
+fn is_template(file: &str) -> bool {
+    let name = file.rsplit('/').next().unwrap_or(file);
+    [".example", ".sample", ".template"].iter().any(|suffix| name.ends_with(suffix))
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::env;
+
+/// One `KEY=value` definition of a `.env` file
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvEntry {
+    pub key: String,
+    /// Unquoted, with escapes and `${VAR}` references resolved
+    pub value: String,
+    /// 1-based line the definition starts on
+    pub line: usize,
+    /// The quote the value was wrapped in, if any
+    pub quote: Option<char>,
+    /// Whether the value referenced other variables
+    pub interpolated: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedEnvFile {
+    pub entries: Vec<EnvEntry>,
+    /// Lines that are not valid dotenv syntax, with what is wrong
+    pub errors: Vec<(usize, String)>,
+}
+
+/// Parse a `.env` file the way dotenv loaders do: `export KEY=value`, single-quoted literals,
+/// double-quoted values with escapes that may span lines, inline ` #` comments after unquoted
+/// values, and `${VAR}`, `${VAR:-default}` and `$VAR` references. References resolve to earlier
+/// entries of the file, then `context` (files loaded before), then the process environment
+pub fn parse(content: &str, context: &HashMap<String, String>) -> ParsedEnvFile {
+    let mut parsed = ParsedEnvFile::default();
+    let mut defined: HashMap<String, String> = HashMap::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line_number = index + 1;
+        let line = lines[index].trim();
+        index += 1;
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export").filter(|rest| rest.starts_with(char::is_whitespace)).map_or(line, str::trim_start);
+        let Some((key, rest)) = line.split_once('=') else {
+            parsed.errors.push((line_number, "Invalid format - should be KEY=value".to_string()));
+            continue;
+        };
+        let key = key.trim_end();
+        if !is_valid_key(key) {
+            parsed.errors.push((line_number, format!("Invalid variable name '{}'", key)));
+            continue;
+        }
+
+        let rest = rest.trim_start();
+        let quote = rest.chars().next().filter(|c| matches!(c, '"' | '\'' | '`'));
+        let raw = match quote {
+            Some(quote) => {
+                // The value continues on the following lines until the closing quote
+                let mut text = rest[1..].to_string();
+                let end = loop {
+                    if let Some(end) = closing_quote(&text, quote) {
+                        break Some(end);
+                    }
+                    if index == lines.len() {
+                        break None;
+                    }
+                    text.push('\n');
+                    text.push_str(lines[index]);
+                    index += 1;
+                };
+                let Some(end) = end else {
+                    // The following lines are definitions of their own, not part of the value
+                    parsed.errors.push((line_number, format!("Unterminated quoted value for {}", key)));
+                    index = line_number;
+                    continue;
+                };
+                let trailing = text[end + 1..].trim();
+                if !trailing.is_empty() && !trailing.starts_with('#') {
+                    parsed.errors.push((line_number, format!("Unexpected text after the quoted value of {}", key)));
+                }
+                text.truncate(end);
+                text
+            }
+            None => strip_inline_comment(rest).trim_end().to_string(),
+        };
+
+        let mut interpolated = false;
+        let value = match quote {
+            Some('\'') => raw,
+            _ => expand(&raw, quote == Some('"'), &mut interpolated, |name| {
+                defined.get(name).or_else(|| context.get(name)).cloned().or_else(|| env::var(name).ok())
+            }),
+        };
+
+        defined.insert(key.to_string(), value.clone());
+        parsed.entries.push(EnvEntry { key: key.to_string(), value, line: line_number, quote, interpolated });
+    }
+
+    parsed
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// Byte offset of the quote closing a value; backslashes escape a `"` only
+fn closing_quote(text: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (offset, c) in text.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quote == '"' => escaped = true,
+            _ if c == quote => return Some(offset),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// An unquoted value ends where a `#` preceded by whitespace starts a comment
+fn strip_inline_comment(value: &str) -> &str {
+    let mut previous = ' ';
+    for (offset, c) in value.char_indices() {
+        if c == '#' && previous.is_whitespace() && offset > 0 {
+            return &value[..offset];
+        }
+        previous = c;
+    }
+    value
+}
+
+/// Resolve escapes (in double quotes) and variable references in one pass, so `\$` stays literal
+fn expand(raw: &str, escapes: bool, interpolated: &mut bool, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if escapes => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some('r') => value.push('\r'),
+                Some('t') => value.push('\t'),
+                Some(escaped @ ('"' | '\\' | '$')) => value.push(escaped),
+                Some(other) => {
+                    value.push('\\');
+                    value.push(other);
+                }
+                None => value.push('\\'),
+            },
+            '$' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let mut reference = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    reference.push(c);
+                }
+                let (name, default) = match reference.split_once(":-") {
+                    Some((name, default)) => (name, Some(default)),
+                    None => (reference.as_str(), None),
+                };
+                *interpolated = true;
+                let resolved = lookup(name).filter(|resolved| !resolved.is_empty() || default.is_none());
+                value.push_str(&resolved.or(default.map(str::to_string)).unwrap_or_default());
+            }
+            '$' if chars.peek().is_some_and(|c| c.is_ascii_alphabetic() || *c == '_') => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric() || **c == '_') {
+                    name.push(c);
+                    chars.next();
+                }
+                *interpolated = true;
+                value.push_str(&lookup(&name).unwrap_or_default());
+            }
+            _ => value.push(c),
+        }
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(content: &str) -> Vec<(String, String)> {
+        parse(content, &HashMap::new()).entries.into_iter().map(|entry| (entry.key, entry.value)).collect()
+    }
+
+    #[test]
+    fn test_quotes_comments_and_export() {
+        let content = "# comment\nexport API_URL=https://api.example.com # trailing\nCOLOR=\"#ff0000\"\nHASH=abc#123\nSINGLE='no $expansion\\n here'\nSPACED = \"a b\"\nESCAPED=\"say \\\"hi\\\"\\n\"\n";
+        assert_eq!(values(content), vec![
+            ("API_URL".to_string(), "https://api.example.com".to_string()),
+            ("COLOR".to_string(), "#ff0000".to_string()),
+            ("HASH".to_string(), "abc#123".to_string()),
+            ("SINGLE".to_string(), "no $expansion\\n here".to_string()),
+            ("SPACED".to_string(), "a b".to_string()),
+            ("ESCAPED".to_string(), "say \"hi\"\n".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_multiline_values_keep_line_numbers() {
+        let content = "PRIVATE_KEY=\"-----BEGIN KEY-----\nMIIBOgIBAAJBAK\n-----END KEY-----\"\nNEXT=1\nBROKEN='never closed\nLAST=2\n";
+        let parsed = parse(content, &HashMap::new());
+        assert_eq!(parsed.entries.len(), 3);
+        assert_eq!(parsed.entries[0].value, "-----BEGIN KEY-----\nMIIBOgIBAAJBAK\n-----END KEY-----");
+        assert_eq!(parsed.entries[1].line, 4);
+        // An unclosed quote only costs its own line
+        assert_eq!((parsed.entries[2].key.as_str(), parsed.entries[2].value.as_str(), parsed.entries[2].line), ("LAST", "2", 6));
+        assert_eq!(parsed.errors, vec![(5, "Unterminated quoted value for BROKEN".to_string())]);
+    }
+
+    #[test]
+    fn test_interpolation() {
+        let context = HashMap::from([("HOST".to_string(), "db.internal".to_string())]);
+        let parsed = parse("PORT=5432\nURL=postgres://${HOST}:$PORT/app\nMODE=${SNIFF_UNSET_MODE:-dev}\nLITERAL=\"\\${HOST}\"\n", &context);
+        let values: Vec<&str> = parsed.entries.iter().map(|entry| entry.value.as_str()).collect();
+        assert_eq!(values, vec!["5432", "postgres://db.internal:5432/app", "dev", "${HOST}"]);
+        assert!(parsed.entries[1].interpolated);
+        assert!(!parsed.entries[3].interpolated);
+    }
+
+    #[test]
+    fn test_invalid_lines() {
+        let parsed = parse("JUST_A_WORD\n1BAD=x\nOK=1\n", &HashMap::new());
+        assert_eq!(parsed.entries.len(), 1);
+        assert_eq!(parsed.errors.iter().map(|(line, _)| *line).collect::<Vec<_>>(), vec![1, 2]);
+    }
+}
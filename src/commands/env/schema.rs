@@ -0,0 +1,173 @@
+use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
+use std::env;
+use std::fs;
+use std::path::Path;
+use crate::config::{EnvVarSchema, EnvVarType};
+use super::{parser, EnvVariable, VarStatus, IssueType};
+
+/// Normalize `dev`/`prod` shorthands to the names used in env file suffixes
+pub fn normalize_environment(name: &str) -> String {
+    match name {
+        "dev" => "development".to_string(),
+        "prod" => "production".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Environment to validate: `--environment`, then `NODE_ENV`, then development
+pub fn resolve_environment(requested: Option<&str>) -> String {
+    requested.map(str::to_string)
+        .or_else(|| env::var("NODE_ENV").ok())
+        .map(|name| normalize_environment(&name))
+        .unwrap_or_else(|| "development".to_string())
+}
+
+/// Validate every declared variable against the values that apply in `environment`
+pub fn validate_schema(project_dir: &Path, schema: &BTreeMap<String, EnvVarSchema>, environment: &str) -> Vec<EnvVariable> {
+    let values = load_values_for_environment(project_dir, environment);
+
+    schema.iter()
+        .filter_map(|(name, spec)| {
+            let required = spec.required && applies_to(spec, environment);
+            let value = env::var(name).ok()
+                .map(|value| (value, "environment".to_string()))
+                .or_else(|| values.get(name).cloned());
+
+            match value {
+                Some((value, source)) => Some(check_value(name, &value, &source, spec)),
+                None if required => Some(EnvVariable {
+                    name: name.clone(),
+                    status: VarStatus::Missing,
+                    source: None,
+                    issue_type: Some(IssueType::MissingRequired),
+                    suggestion: Some(format!("Add {} to .env.{} or .env.local", name, environment)),
+                }),
+                // Optional or out-of-scope variables are only checked when set
+                None => None,
+            }
+        })
+        .collect()
+}
+
+fn applies_to(spec: &EnvVarSchema, environment: &str) -> bool {
+    spec.environments.is_empty()
+        || spec.environments.iter().any(|env| normalize_environment(env) == environment)
+}
+
+/// Values from the files Next.js/Vite load for `environment`, later files winning:
+/// `.env`, `.env.local` (not for test), `.env.<environment>`, `.env.<environment>.local`
+fn load_values_for_environment(project_dir: &Path, environment: &str) -> HashMap<String, (String, String)> {
+    let mut files = vec![".env".to_string()];
+    if environment != "test" {
+        files.push(".env.local".to_string());
+    }
+    files.push(format!(".env.{}", environment));
+    files.push(format!(".env.{}.local", environment));
+
+    let mut values = HashMap::new();
+    let mut context = HashMap::new();
+
+    for file_name in files {
+        let Ok(content) = fs::read_to_string(project_dir.join(&file_name)) else { continue };
+        for entry in parser::parse(&content, &context).entries {
+            context.insert(entry.key.clone(), entry.value.clone());
+            values.insert(entry.key, (entry.value, file_name.clone()));
+        }
+    }
+
+    values
+}
+
+fn check_value(name: &str, value: &str, source: &str, spec: &EnvVarSchema) -> EnvVariable {
+    let (status, issue_type, suggestion) = if value.is_empty() {
+        (VarStatus::Empty, Some(IssueType::EmptyValue), Some("Set a non-empty value for this variable".to_string()))
+    } else if let Some(problem) = type_error(value, spec) {
+        (VarStatus::Invalid, Some(IssueType::InvalidFormat), Some(problem))
+    } else {
+        (VarStatus::Present, None, None)
+    };
+
+    EnvVariable {
+        name: name.to_string(),
+        status,
+        source: Some(source.to_string()),
+        issue_type,
+        suggestion,
+    }
+}
+
+/// Describe why `value` does not satisfy `spec`, if it doesn't
+fn type_error(value: &str, spec: &EnvVarSchema) -> Option<String> {
+    let valid = match spec.var_type {
+        EnvVarType::String => true,
+        EnvVarType::Url => Regex::new(r"^[A-Za-z][A-Za-z0-9+.-]*://\S+$").unwrap().is_match(value),
+        EnvVarType::Port => value.parse::<u16>().is_ok_and(|port| port > 0),
+        EnvVarType::Boolean => matches!(value.to_lowercase().as_str(), "true" | "false" | "1" | "0" | "yes" | "no"),
+        EnvVarType::Number => value.parse::<f64>().is_ok(),
+        EnvVarType::Enum => spec.values.iter().any(|allowed| allowed == value),
+    };
+    if !valid {
+        return Some(match spec.var_type {
+            EnvVarType::Url => "Should be a URL with a scheme (e.g. https://example.com)".to_string(),
+            EnvVarType::Port => "Should be a port number between 1 and 65535".to_string(),
+            EnvVarType::Boolean => "Should be true/false, 1/0 or yes/no".to_string(),
+            EnvVarType::Number => "Should be a number".to_string(),
+            EnvVarType::Enum => format!("Should be one of: {}", spec.values.join(", ")),
+            EnvVarType::String => unreachable!(),
+        });
+    }
+
+    let pattern = spec.pattern.as_ref()?;
+    match Regex::new(pattern) {
+        Ok(regex) if !regex.is_match(value) => Some(format!("Should match the pattern {}", pattern)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec(var_type: EnvVarType) -> EnvVarSchema {
+        EnvVarSchema { var_type, required: true, environments: Vec::new(), values: Vec::new(), pattern: None }
+    }
+
+    #[test]
+    fn test_type_checks() {
+        assert!(type_error("https://example.com", &spec(EnvVarType::Url)).is_none());
+        assert!(type_error("example.com", &spec(EnvVarType::Url)).is_some());
+        assert!(type_error("3000", &spec(EnvVarType::Port)).is_none());
+        assert!(type_error("70000", &spec(EnvVarType::Port)).is_some());
+        assert!(type_error("maybe", &spec(EnvVarType::Boolean)).is_some());
+
+        let mut level = spec(EnvVarType::Enum);
+        level.values = vec!["debug".to_string(), "info".to_string()];
+        assert!(type_error("info", &level).is_none());
+        assert_eq!(type_error("trace", &level).unwrap(), "Should be one of: debug, info");
+    }
+
+    #[test]
+    fn test_environment_scoping_and_file_precedence() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join(".env"), "PORT=3000\nAPI_URL=http://localhost\n").unwrap();
+        fs::write(dir.path().join(".env.production"), "API_URL=not-a-url\n").unwrap();
+
+        let mut schema = BTreeMap::new();
+        schema.insert("SNIFF_TEST_API_URL_UNSET".to_string(), spec(EnvVarType::Url));
+        schema.insert("API_URL".to_string(), spec(EnvVarType::Url));
+        let mut sentry = spec(EnvVarType::Url);
+        sentry.environments = vec!["prod".to_string()];
+        schema.insert("SNIFF_TEST_SENTRY_DSN".to_string(), sentry);
+
+        let development = validate_schema(dir.path(), &schema, "development");
+        let names: Vec<&str> = development.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["API_URL", "SNIFF_TEST_API_URL_UNSET"]);
+        assert!(matches!(development[0].status, VarStatus::Present));
+
+        let production = validate_schema(dir.path(), &schema, "production");
+        assert!(matches!(production[0].status, VarStatus::Invalid));
+        assert_eq!(production[0].source.as_deref(), Some(".env.production"));
+        assert_eq!(production.len(), 3);
+    }
+}
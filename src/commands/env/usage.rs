@@ -0,0 +1,188 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::Path;
+use std::sync::OnceLock;
+use crate::common::{paths, FileScanner};
+use super::parser;
+
+/// Cross-check between variables read in code and variables defined in `.env*` files
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EnvUsageReport {
+    pub files_scanned: usize,
+    pub referenced: Vec<ReferencedVariable>,
+    /// Read in code but not defined in any `.env*` file (including `.env.example`)
+    pub undefined: Vec<String>,
+    /// Defined in a `.env*` file but never read in code
+    pub unused: Vec<UnusedVariable>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReferencedVariable {
+    pub name: String,
+    pub references: Vec<EnvReference>,
+    pub defined_in: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvReference {
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnusedVariable {
+    pub name: String,
+    pub defined_in: Vec<String>,
+}
+
+/// Set by Node, bundlers or the hosting platform rather than by `.env` files
+const BUILT_IN_VARIABLES: &[&str] = &[
+    "NODE_ENV", "NEXT_RUNTIME", "MODE", "DEV", "PROD", "SSR", "BASE_URL", "SITE", "ASSETS_PREFIX", "CI",
+];
+const BUILT_IN_PREFIXES: &[&str] = &["VERCEL_", "npm_"];
+
+pub(super) const SOURCE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs", "vue", "svelte"];
+
+struct UsagePatterns {
+    member: Regex,
+    bracket: Regex,
+    destructure: Regex,
+    identifier: Regex,
+}
+
+static USAGE_PATTERNS: OnceLock<UsagePatterns> = OnceLock::new();
+
+fn get_usage_patterns() -> &'static UsagePatterns {
+    USAGE_PATTERNS.get_or_init(|| UsagePatterns {
+        member: Regex::new(r"\b(?:process\.env|import\.meta\.env)\.([A-Za-z_][A-Za-z0-9_]*)").unwrap(),
+        bracket: Regex::new(r#"\b(?:process\.env|import\.meta\.env)\[\s*["'`]([A-Za-z_][A-Za-z0-9_]*)["'`]\s*\]"#).unwrap(),
+        destructure: Regex::new(r"\{([^}]*)\}\s*=\s*(?:process\.env|import\.meta\.env)\b").unwrap(),
+        identifier: Regex::new(r"^\s*([A-Za-z_][A-Za-z0-9_]*)").unwrap(),
+    })
+}
+
+/// Scan source files under `project_dir` for environment variable reads and compare
+/// them with every `.env*` file in the project root
+pub fn analyze_usage(project_dir: &Path) -> Result<EnvUsageReport> {
+    let (files_scanned, references) = collect_references(project_dir)?;
+    let definitions = load_env_definitions(project_dir)?;
+
+    let undefined = references.keys()
+        .filter(|name| !definitions.contains_key(*name) && !is_built_in(name))
+        .cloned()
+        .collect();
+    let unused = definitions.iter()
+        .filter(|(name, _)| !references.contains_key(*name))
+        .map(|(name, files)| UnusedVariable { name: name.clone(), defined_in: files.iter().cloned().collect() })
+        .collect();
+    let referenced = references.into_iter()
+        .map(|(name, references)| {
+            let defined_in = definitions.get(&name).map(|files| files.iter().cloned().collect()).unwrap_or_default();
+            ReferencedVariable { name, references, defined_in }
+        })
+        .collect();
+
+    Ok(EnvUsageReport {
+        files_scanned,
+        referenced,
+        undefined,
+        unused,
+    })
+}
+
+/// Every environment variable read in source files under `project_dir`, and the number of files scanned
+pub(super) fn collect_references(project_dir: &Path) -> Result<(usize, BTreeMap<String, Vec<EnvReference>>)> {
+    let scanner = FileScanner::for_root(project_dir)?;
+    let files = scanner.find_files_with_extensions(project_dir, SOURCE_EXTENSIONS);
+
+    let mut references: BTreeMap<String, Vec<EnvReference>> = BTreeMap::new();
+    for path in &files {
+        let Ok(content) = fs::read_to_string(path) else { continue };
+        let file = paths::display(path);
+        for (name, line) in find_env_references(&content) {
+            references.entry(name).or_default().push(EnvReference { file: file.clone(), line });
+        }
+    }
+
+    Ok((files.len(), references))
+}
+
+/// Variable names read in `content`, with 1-based line numbers
+pub(super) fn find_env_references(content: &str) -> Vec<(String, usize)> {
+    let patterns = get_usage_patterns();
+    let mut found = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') {
+            continue;
+        }
+
+        for caps in patterns.member.captures_iter(line).chain(patterns.bracket.captures_iter(line)) {
+            found.push((caps[1].to_string(), index + 1));
+        }
+        // const { API_URL, SECRET: secret = 'x' } = process.env
+        for caps in patterns.destructure.captures_iter(line) {
+            for binding in caps[1].split(',') {
+                if let Some(name) = patterns.identifier.captures(binding) {
+                    found.push((name[1].to_string(), index + 1));
+                }
+            }
+        }
+    }
+
+    found
+}
+
+/// Every `KEY=` defined in a `.env*` file in `project_dir`, with the files defining it
+fn load_env_definitions(project_dir: &Path) -> Result<BTreeMap<String, BTreeSet<String>>> {
+    let mut definitions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for entry in fs::read_dir(project_dir)?.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !file_name.starts_with(".env") || !entry.path().is_file() {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path()) else { continue };
+        for entry in parser::parse(&content, &HashMap::new()).entries {
+            definitions.entry(entry.key).or_default().insert(file_name.clone());
+        }
+    }
+
+    Ok(definitions)
+}
+
+pub(super) fn is_built_in(name: &str) -> bool {
+    BUILT_IN_VARIABLES.contains(&name) || BUILT_IN_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reference_forms() {
+        let content = "const url = process.env.API_URL;\nconst key = import.meta.env.VITE_KEY;\nconst s = process.env['SECRET'];\nconst { DB_HOST, DB_PORT: port = '5432' } = process.env;\n// process.env.COMMENTED\n";
+        let names: Vec<String> = find_env_references(content).into_iter().map(|(name, _)| name).collect();
+
+        assert_eq!(names, vec!["API_URL", "VITE_KEY", "SECRET", "DB_HOST", "DB_PORT"]);
+    }
+
+    #[test]
+    fn test_cross_check_against_env_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join(".env"), "API_URL=https://api.example.com\nLEGACY_FLAG=1\n").unwrap();
+        fs::write(dir.path().join(".env.example"), "# documented\nSTRIPE_KEY=\n").unwrap();
+        fs::write(dir.path().join("app.ts"), "fetch(process.env.API_URL);\nstripe(process.env.STRIPE_KEY);\nlog(process.env.SENTRY_DSN, process.env.NODE_ENV);\n").unwrap();
+
+        let report = analyze_usage(dir.path()).unwrap();
+
+        assert_eq!(report.undefined, vec!["SENTRY_DSN"]);
+        assert_eq!(report.unused.len(), 1);
+        assert_eq!(report.unused[0].name, "LEGACY_FLAG");
+        assert_eq!(report.referenced.len(), 4);
+    }
+}
@@ -0,0 +1,242 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::commands::imports_analyzer::{find_import_order, find_type_imports};
+use crate::common::{output, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_standard_json_output, output_result};
+
+/// Which fixers to run. With none selected, all of them run, import ordering only when
+/// `[imports.order]` is enabled
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FixOptions {
+    pub type_imports: bool,
+    pub organize_imports: bool,
+    /// Report the changes without writing them
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixKind {
+    /// `import type` for imports only used as types, and the reverse
+    TypeImports,
+    /// The leading import block in `[imports.order]`
+    OrganizeImports,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FixReport {
+    pub files: Vec<FileFixes>,
+    pub summary: FixSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileFixes {
+    pub file: String,
+    pub edits: Vec<LineEdit>,
+}
+
+/// Lines `start_line..=end_line` replaced with `after`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LineEdit {
+    pub kind: FixKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FixSummary {
+    pub files_changed: usize,
+    pub edits: usize,
+    /// Edits left out because the lines changed since they were found, or overlap another edit
+    pub skipped: usize,
+    pub dry_run: bool,
+}
+
+pub async fn run(target: &ScanTarget, options: FixOptions, json: bool, quiet: bool) -> Result<CommandOutcome<FixReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!("Running fix analysis");
+
+    let outcome = check(target, options)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_standard_json_output(
+        "fix",
+        report,
+        report.summary.files_changed,
+        report.summary.edits,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = outcome.exit_code.is_success(), "fix analysis completed");
+
+    Ok(outcome)
+}
+
+/// Collect the selected fixes and write them, unless `dry_run`. Each fixer runs on the files
+/// the previous one wrote. A dry run with pending changes fails, so CI can check that nothing
+/// is left to fix.
+pub fn check(target: &ScanTarget, options: FixOptions) -> Result<CommandOutcome<FixReport>> {
+    let all = !options.type_imports && !options.organize_imports;
+    let config = target.config()?;
+    let mut changes: BTreeMap<String, Vec<LineEdit>> = BTreeMap::new();
+    let mut skipped = 0;
+
+    if all || options.type_imports {
+        let edits = find_type_imports(target)?.into_iter().map(|issue| (issue.file, LineEdit {
+            kind: FixKind::TypeImports,
+            start_line: issue.line,
+            end_line: issue.end_line,
+            before: issue.import_statement,
+            after: issue.fix,
+        }));
+        skipped += apply(edits, options.dry_run, &mut changes)?;
+    }
+    if options.organize_imports || (all && config.imports.order.enabled) {
+        let edits = find_import_order(target, &config.imports.order)?.into_iter().map(|issue| (issue.file, LineEdit {
+            kind: FixKind::OrganizeImports,
+            start_line: issue.start_line,
+            end_line: issue.end_line,
+            before: issue.block,
+            after: issue.fix,
+        }));
+        skipped += apply(edits, options.dry_run, &mut changes)?;
+    }
+
+    let files: Vec<FileFixes> = changes.into_iter().map(|(file, edits)| FileFixes { file, edits }).collect();
+    let summary = FixSummary {
+        files_changed: files.len(),
+        edits: files.iter().map(|file| file.edits.len()).sum(),
+        skipped,
+        dry_run: options.dry_run,
+    };
+    let exit_code = check_failure_threshold(options.dry_run && summary.edits > 0, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(FixReport { files, summary }, exit_code))
+}
+
+/// Apply one fixer's edits file by file, recording those that applied in `changes`;
+/// returns how many were skipped
+fn apply(edits: impl Iterator<Item = (String, LineEdit)>, dry_run: bool, changes: &mut BTreeMap<String, Vec<LineEdit>>) -> Result<usize> {
+    let mut by_file: BTreeMap<String, Vec<LineEdit>> = BTreeMap::new();
+    for (file, edit) in edits {
+        by_file.entry(file).or_default().push(edit);
+    }
+    let mut skipped = 0;
+    for (file, edits) in by_file {
+        let count = edits.len();
+        let path = paths::resolve(&file);
+        let content = fs::read_to_string(&path)?;
+        let (fixed, applied) = apply_edits(&content, edits);
+        skipped += count - applied.len();
+        if applied.is_empty() {
+            continue;
+        }
+        if !dry_run {
+            fs::write(&path, fixed)?;
+        }
+        changes.entry(file).or_default().extend(applied);
+    }
+    Ok(skipped)
+}
+
+/// `content` with `edits` applied, and the edits that were. An edit applies when its lines
+/// still read `before`, ignoring whitespace, and no earlier edit touched them; the replacement
+/// keeps the first line's indentation and line ending.
+fn apply_edits(content: &str, mut edits: Vec<LineEdit>) -> (String, Vec<LineEdit>) {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    edits.sort_by_key(|edit| edit.start_line);
+
+    let mut output = String::with_capacity(content.len());
+    let mut applied = Vec::new();
+    let mut next_line = 1;
+    for edit in edits {
+        let (start, end) = (edit.start_line, edit.end_line);
+        if start < next_line || end < start || end > lines.len() {
+            continue;
+        }
+        let words = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if words(&lines[start - 1..end].concat()) != words(&edit.before) {
+            continue;
+        }
+        output.extend(lines[next_line - 1..start - 1].iter().copied());
+        let first = lines[start - 1];
+        let indent = &first[..first.len() - first.trim_start().len()];
+        let ending = if lines[end - 1].ends_with("\r\n") { "\r\n" } else if lines[end - 1].ends_with('\n') { "\n" } else { "" };
+        let replacement: Vec<String> = edit.after.lines().map(|line| format!("{}{}", indent, line)).collect();
+        output.push_str(&replacement.join(if ending.is_empty() { "\n" } else { ending }));
+        output.push_str(ending);
+        next_line = end + 1;
+        applied.push(edit);
+    }
+    output.extend(lines[(next_line - 1).min(lines.len())..].iter().copied());
+    (output, applied)
+}
+
+fn print_report(report: &FixReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🔧 Fix Report".bold().blue());
+        output::line("=============".blue());
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    if report.files.is_empty() {
+        output::line("✅ Nothing to fix.".green());
+    }
+
+    for file in &report.files {
+        output::line(file.file.cyan().bold());
+        for edit in &file.edits {
+            output::line(format!("  Line {}:", edit.start_line.to_string().yellow()));
+            for line in edit.before.lines() {
+                output::line(format!("    {} {}", "-".red(), line.red()));
+            }
+            for line in edit.after.lines() {
+                output::line(format!("    {} {}", "+".green(), line.green()));
+            }
+        }
+        output::blank();
+    }
+
+    if summary.edits > 0 {
+        let verb = if summary.dry_run { "Would apply" } else { "Applied" };
+        output::line(format!("{} {} fix(es) in {} file(s)", verb.bold(), summary.edits, summary.files_changed));
+        if summary.dry_run {
+            output::line("💡 Run without --dry-run to write the changes".dimmed());
+        }
+    }
+    if summary.skipped > 0 {
+        output::line(format!("⚠️  Skipped {} edit(s) whose lines changed or overlap another edit", summary.skipped).yellow());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(start_line: usize, end_line: usize, before: &str, after: &str) -> LineEdit {
+        LineEdit { kind: FixKind::TypeImports, start_line, end_line, before: before.to_string(), after: after.to_string() }
+    }
+
+    #[test]
+    fn test_apply_edits() {
+        let content = "import {\n  User,\n  load,\n} from './user';\n  import Config from './config'\nexport {};\n";
+        let edits = vec![
+            edit(5, 5, "import Config from './config'", "import type Config from './config'"),
+            edit(1, 4, "import { User, load, } from './user';", "import type { User } from './user';\nimport { load } from './user';"),
+            // Out of date: the line no longer reads like this
+            edit(6, 6, "export { x };", "export type { x };"),
+        ];
+        let (fixed, applied) = apply_edits(content, edits);
+        assert_eq!(applied.len(), 2);
+        assert_eq!(fixed, "import type { User } from './user';\nimport { load } from './user';\n  import type Config from './config'\nexport {};\n");
+    }
+}
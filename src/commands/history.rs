@@ -0,0 +1,327 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use crate::config::Config;
+use crate::common::{output, ExitCode, CommandOutcome, ScanTarget, create_standard_json_output};
+
+/// Known metrics: key, label, and whether a higher value is better
+const METRICS: &[(&str, &str, bool)] = &[
+    ("large_files", "Large files", false),
+    ("large_functions", "Large functions", false),
+    ("any_usages", "'any' usages", false),
+    ("type_coverage", "Type coverage score", true),
+    ("unused_imports", "Unused imports", false),
+    ("broken_imports", "Broken imports", false),
+    ("bundle_size_kb", "Bundle size (KB)", false),
+    ("lint_errors", "Lint errors", false),
+    ("lint_warnings", "Lint warnings", false),
+    ("outdated_major", "Packages a major version behind", false),
+    ("deprecated_packages", "Deprecated packages", false),
+    ("critical_vulns", "Critical vulnerabilities", false),
+    ("high_vulns", "High vulnerabilities", false),
+    ("client_js_kb", "Client boundary JavaScript (KB)", false),
+    ("organization_score", "Organization score", true),
+    ("health_score", "Health score", true),
+];
+
+/// Regressions listed by `sniff history show`
+const REGRESSIONS_SHOWN: usize = 10;
+
+/// Reports that contribute summary metrics to the project history
+pub trait Metrics {
+    fn metrics(&self) -> Vec<(&'static str, f64)>;
+}
+
+/// One line of `.sniff/history.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub command: String,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HistoryReport {
+    pub runs: usize,
+    pub since: Option<DateTime<Utc>>,
+    pub trends: Vec<MetricTrend>,
+    pub regressions: Vec<Regression>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MetricTrend {
+    pub metric: String,
+    pub label: String,
+    pub higher_is_better: bool,
+    /// Recorded values, oldest first
+    pub values: Vec<f64>,
+    pub first: f64,
+    pub latest: f64,
+    pub change: f64,
+    /// The latest value is worse than the one before it
+    pub regressed: bool,
+}
+
+/// A run where a metric got worse than in the previous run that recorded it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Regression {
+    pub timestamp: DateTime<Utc>,
+    pub metric: String,
+    pub previous: f64,
+    pub value: f64,
+}
+
+/// Append the metrics of a whole-project run to the history file. Runs over
+/// selected paths or staged files are not comparable and are not recorded.
+pub fn record<R: Metrics>(target: &ScanTarget, command: &str, report: &R) -> Result<()> {
+    if target.paths != [target.root.clone()] {
+        return Ok(());
+    }
+    let config = target.config()?;
+    if !config.history.enabled {
+        return Ok(());
+    }
+
+    let entry = HistoryEntry {
+        timestamp: Utc::now(),
+        command: command.to_string(),
+        metrics: report.metrics().into_iter().map(|(name, value)| (name.to_string(), value)).collect(),
+    };
+    let path = target.root.join(&config.history.path);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Trends and regressions for every metric recorded since `since`
+pub async fn show(root: &Path, since: Option<&str>, chart: bool, json: bool, quiet: bool) -> Result<CommandOutcome<HistoryReport>> {
+    let config = Config::load_from_dir(root)?;
+    let since = since.map(|since| parse_since(since, Utc::now())).transpose()?;
+    let entries: Vec<HistoryEntry> = load(&root.join(&config.history.path))?
+        .into_iter()
+        .filter(|entry| since.is_none_or(|since| entry.timestamp >= since))
+        .collect();
+    let report = HistoryReport {
+        runs: entries.len(),
+        since,
+        trends: trends(&entries),
+        regressions: regressions(&entries),
+    };
+
+    if json {
+        let response = create_standard_json_output("history", &report, report.runs, report.regressions.len(), None);
+        println!("{}", response.to_json_pretty()?);
+    } else {
+        print_report(&report, chart, quiet);
+    }
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+fn load(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(Vec::new());
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).with_context(|| format!("Invalid history entry at {}:{}", path.display(), index + 1))
+        })
+        .collect()
+}
+
+/// `7d`, `12h` and `2w` count back from `now`; `2024-05-01` or an RFC 3339 time are absolute
+fn parse_since(since: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let since = since.trim();
+    if let Some(amount) = since.get(..since.len().saturating_sub(1)).and_then(|amount| amount.parse::<u32>().ok()) {
+        let amount = i64::from(amount);
+        match since.chars().last() {
+            Some('h') => return Ok(now - Duration::hours(amount)),
+            Some('d') => return Ok(now - Duration::days(amount)),
+            Some('w') => return Ok(now - Duration::weeks(amount)),
+            _ => {}
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc());
+    }
+    DateTime::parse_from_rfc3339(since)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| anyhow!("Invalid --since '{}': use a duration like 7d, 12h or 2w, or a date like 2024-05-01", since))
+}
+
+fn describe(metric: &str) -> (String, bool) {
+    METRICS.iter()
+        .find(|(key, _, _)| *key == metric)
+        .map(|(_, label, higher_is_better)| (label.to_string(), *higher_is_better))
+        .unwrap_or_else(|| (metric.to_string(), false))
+}
+
+fn is_worse(metric: &str, previous: f64, value: f64) -> bool {
+    let (_, higher_is_better) = describe(metric);
+    if higher_is_better { value < previous } else { value > previous }
+}
+
+/// Each metric's recorded values with the time of the run, oldest first
+type Series<'a> = BTreeMap<&'a str, Vec<(DateTime<Utc>, f64)>>;
+
+fn series(entries: &[HistoryEntry]) -> Series<'_> {
+    let mut series = Series::new();
+    for entry in entries {
+        for (metric, value) in &entry.metrics {
+            series.entry(metric.as_str()).or_default().push((entry.timestamp, *value));
+        }
+    }
+    series
+}
+
+fn trends(entries: &[HistoryEntry]) -> Vec<MetricTrend> {
+    let series = series(entries);
+    // Known metrics first, in their usual order
+    let rank = |metric: &str| METRICS.iter().position(|(key, _, _)| *key == metric).unwrap_or(METRICS.len());
+    let mut trends: Vec<MetricTrend> = series.into_iter().map(|(metric, points)| {
+        let values: Vec<f64> = points.iter().map(|(_, value)| *value).collect();
+        let (label, higher_is_better) = describe(metric);
+        let (first, latest) = (values[0], values[values.len() - 1]);
+        let regressed = values.len() >= 2 && is_worse(metric, values[values.len() - 2], latest);
+        MetricTrend { metric: metric.to_string(), label, higher_is_better, first, latest, change: latest - first, regressed, values }
+    }).collect();
+    trends.sort_by_key(|trend| rank(&trend.metric));
+    trends
+}
+
+fn regressions(entries: &[HistoryEntry]) -> Vec<Regression> {
+    let mut regressions: Vec<Regression> = series(entries).into_iter()
+        .flat_map(|(metric, points)| {
+            points.windows(2)
+                .filter(|pair| is_worse(metric, pair[0].1, pair[1].1))
+                .map(|pair| Regression { timestamp: pair[1].0, metric: metric.to_string(), previous: pair[0].1, value: pair[1].1 })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    regressions.sort_by_key(|regression| regression.timestamp);
+    regressions
+}
+
+fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    values.iter().map(|value| {
+        if max > min {
+            BARS[(((value - min) / (max - min)) * 7.0).round() as usize]
+        } else {
+            BARS[0]
+        }
+    }).collect()
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+fn print_report(report: &HistoryReport, chart: bool, quiet: bool) {
+    let window = match report.since {
+        Some(since) => format!(" since {}", since.format("%Y-%m-%d %H:%M")),
+        None => String::new(),
+    };
+    output::line(format!("📈 History: {} runs{}", report.runs, window).bold().blue());
+    if report.trends.is_empty() {
+        output::line("No runs recorded yet; run `sniff large`, `types`, `imports`, `bundle`, `context` or `report` on the whole project".dimmed());
+        return;
+    }
+
+    output::blank();
+    for trend in &report.trends {
+        let change = if trend.change == 0.0 {
+            "±0".dimmed()
+        } else {
+            let text = format!("{}{}", if trend.change > 0.0 { "+" } else { "" }, format_value(trend.change));
+            let improved = (trend.change > 0.0) == trend.higher_is_better;
+            if improved { text.green() } else { text.red() }
+        };
+        let chart = if chart { format!("  {}", sparkline(&trend.values).cyan()) } else { String::new() };
+        let flag = if trend.regressed { format!("  {}", "⚠️  regressed".yellow()) } else { String::new() };
+        output::line(format!(
+            "  {:<22} {:>8} → {:<8} {:>8}{}{}",
+            trend.label,
+            format_value(trend.first),
+            format_value(trend.latest),
+            change,
+            chart,
+            flag
+        ));
+    }
+
+    if !quiet && !report.regressions.is_empty() {
+        output::blank();
+        output::line("⚠️  Regressions".bold().yellow());
+        for regression in report.regressions.iter().rev().take(REGRESSIONS_SHOWN) {
+            output::line(format!(
+                "  {}  {:<22} {} → {}",
+                regression.timestamp.format("%Y-%m-%d %H:%M").to_string().dimmed(),
+                describe(&regression.metric).0,
+                format_value(regression.previous),
+                format_value(regression.value)
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u32, metrics: &[(&str, f64)]) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: NaiveDate::from_ymd_opt(2024, 5, day).unwrap().and_hms_opt(12, 0, 0).unwrap().and_utc(),
+            command: "large".to_string(),
+            metrics: metrics.iter().map(|(name, value)| (name.to_string(), *value)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_trends_flag_regressions_by_metric_direction() {
+        let entries = vec![
+            entry(1, &[("large_files", 3.0), ("type_coverage", 80.0)]),
+            entry(2, &[("large_files", 5.0), ("type_coverage", 85.0)]),
+            entry(3, &[("large_files", 4.0), ("type_coverage", 82.0)]),
+        ];
+        let trends = trends(&entries);
+        assert_eq!(trends[0].metric, "large_files");
+        assert_eq!((trends[0].first, trends[0].latest, trends[0].change), (3.0, 4.0, 1.0));
+        assert!(!trends[0].regressed);
+        assert!(trends[1].regressed);
+
+        let regressions = regressions(&entries);
+        assert_eq!(regressions.len(), 2);
+        assert_eq!((regressions[0].metric.as_str(), regressions[0].value), ("large_files", 5.0));
+        assert_eq!((regressions[1].metric.as_str(), regressions[1].value), ("type_coverage", 82.0));
+        assert_eq!(sparkline(&[3.0, 5.0, 4.0]), "▁█▅");
+    }
+
+    #[test]
+    fn test_parse_since_accepts_durations_and_dates() {
+        let now = entry(10, &[]).timestamp;
+        assert_eq!(parse_since("7d", now).unwrap(), entry(3, &[]).timestamp);
+        assert_eq!(parse_since("2024-05-01", now).unwrap().to_rfc3339(), "2024-05-01T00:00:00+00:00");
+        assert!(parse_since("last week", now).is_err());
+    }
+}
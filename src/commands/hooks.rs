@@ -0,0 +1,397 @@
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Instant;
+use crate::config::{Config, HooksConfig};
+use crate::common::{output, ExitCode, CommandOutcome, create_standard_json_output, git};
+
+/// Lefthook reads the first of these it finds
+const LEFTHOOK_CONFIGS: &[&str] = &["lefthook.yml", "lefthook.yaml", ".lefthook.yml", ".lefthook.yaml"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitHook {
+    PreCommit,
+    PrePush,
+}
+
+impl GitHook {
+    pub const ALL: [GitHook; 2] = [GitHook::PreCommit, GitHook::PrePush];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GitHook::PreCommit => "pre-commit",
+            GitHook::PrePush => "pre-push",
+        }
+    }
+
+    fn commands(self, config: &HooksConfig) -> &[String] {
+        match self {
+            GitHook::PreCommit => &config.pre_commit,
+            GitHook::PrePush => &config.pre_push,
+        }
+    }
+}
+
+/// Who owns the hook files: git itself, husky (`.husky/`) or lefthook (`lefthook.yml`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookManager {
+    Git,
+    Husky,
+    Lefthook,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HooksReport {
+    pub manager: HookManager,
+    pub hooks: Vec<HookFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HookFile {
+    pub hook: GitHook,
+    pub path: PathBuf,
+    pub change: HookChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HookChange {
+    Created,
+    /// Added to a hook file that already runs other commands
+    Appended,
+    AlreadyInstalled,
+    /// Lefthook already defines the hook; the sniff command has to be added by hand
+    ManualStepRequired,
+    Removed,
+    NotInstalled,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HookRunReport {
+    pub hook: GitHook,
+    pub commands: Vec<HookCommandResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HookCommandResult {
+    pub command: String,
+    pub exit_code: i32,
+    pub duration_ms: u64,
+}
+
+/// Add the sniff hooks, leaving whatever else the hook files run in place
+pub async fn install(root: &Path, json: bool, quiet: bool) -> Result<CommandOutcome<HooksReport>> {
+    let top_level = git::top_level(root)?;
+    let manager = detect_manager(&top_level);
+    let mut hooks = Vec::new();
+    for hook in GitHook::ALL {
+        let path = hook_path(&top_level, manager, hook)?;
+        let change = install_hook(&path, manager, hook)?;
+        hooks.push(HookFile { hook, path, change });
+    }
+    let report = HooksReport { manager, hooks };
+
+    if json {
+        print_json(&report)?;
+    } else if !quiet {
+        print_report(&report, &top_level);
+    }
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+/// Remove only the sniff blocks; a hook file left empty is deleted
+pub async fn uninstall(root: &Path, json: bool, quiet: bool) -> Result<CommandOutcome<HooksReport>> {
+    let top_level = git::top_level(root)?;
+    let manager = detect_manager(&top_level);
+    let mut hooks = Vec::new();
+    for hook in GitHook::ALL {
+        let path = hook_path(&top_level, manager, hook)?;
+        let change = uninstall_hook(&path, hook)?;
+        hooks.push(HookFile { hook, path, change });
+    }
+    let report = HooksReport { manager, hooks };
+
+    if json {
+        print_json(&report)?;
+    } else if !quiet {
+        print_report(&report, &top_level);
+    }
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+/// Run the sniff commands configured for `hook`, stopping at the first failure
+pub async fn run(root: &Path, hook: GitHook, json: bool, quiet: bool) -> Result<CommandOutcome<HookRunReport>> {
+    let config = Config::load_from_dir(root)?;
+    let sniff = std::env::current_exe().context("Failed to locate the sniff executable")?;
+    let mut commands = Vec::new();
+    let mut exit_code = ExitCode::Success;
+
+    for command in hook.commands(&config.hooks) {
+        if !quiet && !json {
+            output::line(format!("{} {}", format!("🪝 {}:", hook.name()).bold().blue(), format!("sniff {}", command).cyan()));
+        }
+        let start = Instant::now();
+        // The JSON report must be the only thing on stdout
+        let stdout = if json { Stdio::null() } else { Stdio::inherit() };
+        let status = Command::new(&sniff)
+            .args(command.split_whitespace())
+            .current_dir(root)
+            .stdout(stdout)
+            .status()
+            .with_context(|| format!("Failed to run `sniff {}`", command))?;
+        let code = status.code().unwrap_or(ExitCode::GeneralError.code());
+        commands.push(HookCommandResult {
+            command: command.clone(),
+            exit_code: code,
+            duration_ms: start.elapsed().as_millis() as u64,
+        });
+        if code != 0 {
+            exit_code = ExitCode::from_code(code);
+            break;
+        }
+    }
+
+    let report = HookRunReport { hook, commands };
+    if json {
+        let failed = report.commands.iter().filter(|command| command.exit_code != 0).count();
+        let response = create_standard_json_output("hooks", &report, report.commands.len(), failed, None);
+        println!("{}", response.to_json_pretty()?);
+    } else if !quiet && !exit_code.is_success() {
+        output::line(format!("❌ {} hook failed; fix the issues above or bypass with --no-verify", hook.name()).red().bold());
+    }
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+fn print_json(report: &HooksReport) -> Result<()> {
+    let manual = report.hooks.iter().filter(|file| file.change == HookChange::ManualStepRequired).count();
+    let response = create_standard_json_output("hooks", report, report.hooks.len(), manual, None);
+    println!("{}", response.to_json_pretty()?);
+    Ok(())
+}
+
+fn detect_manager(top_level: &Path) -> HookManager {
+    if LEFTHOOK_CONFIGS.iter().any(|name| top_level.join(name).is_file()) {
+        HookManager::Lefthook
+    } else if top_level.join(".husky").is_dir() {
+        HookManager::Husky
+    } else {
+        HookManager::Git
+    }
+}
+
+fn hook_path(top_level: &Path, manager: HookManager, hook: GitHook) -> Result<PathBuf> {
+    Ok(match manager {
+        HookManager::Git => git::hooks_dir(top_level)?.join(hook.name()),
+        HookManager::Husky => top_level.join(".husky").join(hook.name()),
+        HookManager::Lefthook => LEFTHOOK_CONFIGS.iter()
+            .map(|name| top_level.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| anyhow!("No lefthook configuration found in {}", top_level.display()))?,
+    })
+}
+
+fn begin_marker(hook: GitHook) -> String {
+    format!("# >>> sniff hooks: {}", hook.name())
+}
+
+fn end_marker(hook: GitHook) -> String {
+    format!("# <<< sniff hooks: {}", hook.name())
+}
+
+/// Shell line for git and husky hooks. Hooks run from the repository root, so a
+/// project-local install in node_modules/.bin is found without npx.
+fn shell_block(hook: GitHook) -> String {
+    format!(
+        "{}\nPATH=\"$PWD/node_modules/.bin:$PATH\" sniff hooks run {} || exit $?\n{}\n",
+        begin_marker(hook),
+        hook.name(),
+        end_marker(hook)
+    )
+}
+
+fn lefthook_command(hook: GitHook) -> String {
+    format!("    sniff:\n      run: sniff hooks run {}\n", hook.name())
+}
+
+fn lefthook_block(hook: GitHook) -> String {
+    format!(
+        "{}\n{}:\n  commands:\n{}{}\n",
+        begin_marker(hook),
+        hook.name(),
+        lefthook_command(hook),
+        end_marker(hook)
+    )
+}
+
+fn install_hook(path: &Path, manager: HookManager, hook: GitHook) -> Result<HookChange> {
+    let existing = fs::read_to_string(path).ok();
+    if existing.as_deref().is_some_and(|content| content.contains(&begin_marker(hook))) {
+        return Ok(HookChange::AlreadyInstalled);
+    }
+
+    let block = match manager {
+        HookManager::Lefthook => {
+            // Appending a second top-level key would be invalid YAML
+            let defined = existing.as_deref().is_some_and(|content| {
+                content.lines().any(|line| line.trim_end() == format!("{}:", hook.name()))
+            });
+            if defined {
+                return Ok(HookChange::ManualStepRequired);
+            }
+            lefthook_block(hook)
+        }
+        HookManager::Git | HookManager::Husky => shell_block(hook),
+    };
+
+    let (content, change) = match existing {
+        Some(content) => {
+            let separator = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+            (format!("{}{}\n{}", content, separator, block), HookChange::Appended)
+        }
+        None if manager == HookManager::Git => (format!("#!/bin/sh\n{}", block), HookChange::Created),
+        None => (block, HookChange::Created),
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+    if manager != HookManager::Lefthook {
+        make_executable(path)?;
+    }
+    Ok(change)
+}
+
+fn uninstall_hook(path: &Path, hook: GitHook) -> Result<HookChange> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Ok(HookChange::NotInstalled);
+    };
+    let Some(remaining) = remove_block(&content, hook) else {
+        return Ok(HookChange::NotInstalled);
+    };
+
+    let only_shebang = remaining.lines().all(|line| line.trim().is_empty() || line.starts_with("#!"));
+    if only_shebang {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    } else {
+        fs::write(path, remaining).with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(HookChange::Removed)
+}
+
+/// `content` without the marked sniff block (and the blank line written before it)
+fn remove_block(content: &str, hook: GitHook) -> Option<String> {
+    let (begin, end) = (begin_marker(hook), end_marker(hook));
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines.iter().position(|line| line.trim_end() == begin)?;
+    let stop = lines[start..].iter().position(|line| line.trim_end() == end).map(|i| start + i)?;
+    let start = if start > 0 && lines[start - 1].trim().is_empty() { start - 1 } else { start };
+
+    let mut kept: Vec<&str> = lines[..start].to_vec();
+    kept.extend(&lines[stop + 1..]);
+    let mut remaining = kept.join("\n");
+    if !remaining.is_empty() {
+        remaining.push('\n');
+    }
+    Some(remaining)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(permissions.mode() | 0o755);
+    fs::set_permissions(path, permissions)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+fn print_report(report: &HooksReport, top_level: &Path) {
+    let manager = match report.manager {
+        HookManager::Git => "git hooks",
+        HookManager::Husky => "husky",
+        HookManager::Lefthook => "lefthook",
+    };
+    output::line(format!("🪝 Git hooks ({})", manager).bold().blue());
+
+    for file in &report.hooks {
+        let path = file.path.strip_prefix(top_level).unwrap_or(&file.path).display();
+        let status = match file.change {
+            HookChange::Created => "created".green(),
+            HookChange::Appended => "added to existing hook".green(),
+            HookChange::AlreadyInstalled => "already installed".normal(),
+            HookChange::ManualStepRequired => "needs a manual step".yellow(),
+            HookChange::Removed => "removed".green(),
+            HookChange::NotInstalled => "not installed".normal(),
+        };
+        output::line(format!("  {:<11} {} ({})", file.hook.name(), status, path));
+    }
+
+    let manual: Vec<&HookFile> = report.hooks.iter().filter(|file| file.change == HookChange::ManualStepRequired).collect();
+    for file in &manual {
+        output::blank();
+        output::line(format!("  {} already defines {}; add this under its `commands:`", file.path.strip_prefix(top_level).unwrap_or(&file.path).display(), file.hook.name()));
+        output::text(lefthook_command(file.hook).dimmed());
+    }
+    let changed = report.hooks.iter().any(|file| matches!(file.change, HookChange::Created | HookChange::Appended | HookChange::Removed));
+    if report.manager == HookManager::Lefthook && (changed || !manual.is_empty()) {
+        output::blank();
+        output::line("  Run `npx lefthook install` to apply the lefthook configuration");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_install_appends_to_existing_hook_and_uninstall_restores_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pre-commit");
+        let original = "#!/bin/sh\nnpx lint-staged\n";
+        fs::write(&path, original).unwrap();
+
+        assert_eq!(install_hook(&path, HookManager::Git, GitHook::PreCommit).unwrap(), HookChange::Appended);
+        assert_eq!(install_hook(&path, HookManager::Git, GitHook::PreCommit).unwrap(), HookChange::AlreadyInstalled);
+        let installed = fs::read_to_string(&path).unwrap();
+        assert!(installed.starts_with(original));
+        assert!(installed.contains("sniff hooks run pre-commit || exit $?"));
+
+        assert_eq!(uninstall_hook(&path, GitHook::PreCommit).unwrap(), HookChange::Removed);
+        assert_eq!(fs::read_to_string(&path).unwrap(), original);
+    }
+
+    #[test]
+    fn test_uninstall_deletes_hook_that_only_ran_sniff() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hooks").join("pre-push");
+
+        assert_eq!(install_hook(&path, HookManager::Git, GitHook::PrePush).unwrap(), HookChange::Created);
+        assert!(fs::read_to_string(&path).unwrap().starts_with("#!/bin/sh\n"));
+        assert_eq!(uninstall_hook(&path, GitHook::PrePush).unwrap(), HookChange::Removed);
+        assert!(!path.exists());
+        assert_eq!(uninstall_hook(&path, GitHook::PrePush).unwrap(), HookChange::NotInstalled);
+    }
+
+    #[test]
+    fn test_lefthook_hook_already_defined_needs_manual_step() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lefthook.yml");
+        fs::write(&path, "pre-commit:\n  commands:\n    lint:\n      run: npm run lint\n").unwrap();
+
+        assert_eq!(install_hook(&path, HookManager::Lefthook, GitHook::PreCommit).unwrap(), HookChange::ManualStepRequired);
+        assert_eq!(install_hook(&path, HookManager::Lefthook, GitHook::PrePush).unwrap(), HookChange::Appended);
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("pre-push:\n  commands:\n    sniff:\n      run: sniff hooks run pre-push\n"));
+        assert_eq!(content.matches("pre-commit:").count(), 1);
+    }
+}
@@ -0,0 +1,481 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+use crate::utils::FileUtils;
+use crate::config::I18nConfig;
+use crate::common::jsx::{jsx_elements, jsx_text};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+/// Libraries whose `t()` calls and locale files the check understands
+const LIBRARIES: &[&str] = &["next-intl", "next-i18next", "react-i18next", "i18next"];
+
+/// i18next plural and context suffixes: `items_one` and `items_other` define `items`
+const PLURAL_SUFFIXES: &[&str] = &["_zero", "_one", "_two", "_few", "_many", "_other", "_plural"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct I18nReport {
+    /// `None` when the project uses none of the supported libraries; nothing is checked then
+    pub library: Option<String>,
+    pub locales: Vec<String>,
+    pub hardcoded: Vec<HardcodedString>,
+    pub missing_keys: Vec<MissingKey>,
+    pub unused_keys: Vec<UnusedKey>,
+    pub summary: I18nSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HardcodedString {
+    pub file: String,
+    pub line: usize,
+    /// The attribute holding the text, or `None` for text between tags
+    pub attribute: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MissingKey {
+    pub key: String,
+    /// Locales without the key
+    pub locales: Vec<String>,
+    /// First place the key is used
+    pub file: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnusedKey {
+    pub key: String,
+    /// Locale files defining the key
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct I18nSummary {
+    pub files_scanned: usize,
+    pub keys_defined: usize,
+    pub keys_referenced: usize,
+    pub hardcoded_strings: usize,
+    pub missing_keys: usize,
+    pub unused_keys: usize,
+}
+
+impl Annotate for I18nReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let mut findings = Vec::new();
+        for key in &self.missing_keys {
+            findings.push(Annotation::new(
+                AnnotationLevel::Error,
+                &key.file,
+                key.line,
+                "Missing translation key",
+                format!("`{}` is not defined in {}", key.key, key.locales.join(", ")),
+            ).with_rule("SNF-I18N-MISSING-KEY"));
+        }
+        for string in &self.hardcoded {
+            let message = match &string.attribute {
+                Some(attribute) => format!("`{}` in `{}` is not translated", string.text, attribute),
+                None => format!("`{}` is not translated", string.text),
+            };
+            findings.push(Annotation::new(AnnotationLevel::Warning, &string.file, string.line, "Hardcoded UI text", message)
+                .with_rule("SNF-I18N-HARDCODED"));
+        }
+        for key in &self.unused_keys {
+            let message = format!("`{}` is defined but never used in code", key.key);
+            let finding = match key.files.first() {
+                Some(file) => Annotation::file(AnnotationLevel::Warning, file, "Unused translation key", message),
+                None => Annotation::project(AnnotationLevel::Warning, "Unused translation key", message),
+            };
+            findings.push(finding.with_rule("SNF-I18N-UNUSED-KEY"));
+        }
+        findings
+    }
+}
+
+/// A translation key used in code. Dynamic keys (`t(`status.${s}`)`) keep their static prefix.
+struct KeyReference {
+    key: String,
+    dynamic: bool,
+    file: String,
+    line: usize,
+}
+
+struct Patterns {
+    binding: Regex,
+    call: Regex,
+    letters: Regex,
+}
+
+static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+
+fn patterns() -> &'static Patterns {
+    PATTERNS.get_or_init(|| Patterns {
+        // `const t = useTranslations('Home')`, `const { t } = useTranslation('common')`
+        binding: Regex::new(
+            r#"\b(?:const|let|var)\s+(?:\{\s*t\s*(?::\s*([A-Za-z_$][\w$]*))?[^}]*\}|([A-Za-z_$][\w$]*))\s*=\s*(?:await\s+)?(?:useTranslations|getTranslations|useTranslation)\s*\(\s*(?:\{\s*namespace\s*:\s*)?\[?\s*(?:['"]([^'"]*)['"])?"#
+        ).unwrap(),
+        call: Regex::new(r#"(?:^|[^\w$.])((?:i18n\.|i18next\.)?[A-Za-z_$][\w$]*)(?:\.(?:rich|markup|raw))?\(\s*['"`]([^'"`\n]*?)(['"`]|\$\{)"#).unwrap(),
+        letters: Regex::new(r"\p{Alphabetic}{2,}").unwrap(),
+    })
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<I18nReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running i18n analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.i18n, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let summary = &report.summary;
+    let response = create_annotated_json_output(
+        "i18n",
+        report,
+        summary.files_scanned,
+        summary.hardcoded_strings + summary.missing_keys + summary.unused_keys,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = response.summary.issues_found == 0, "i18n analysis completed");
+
+    Ok(outcome)
+}
+
+/// Find hardcoded UI text and compare the keys used in code with the locale files.
+/// Keys missing from a locale fail the check; the rest are warnings.
+pub fn check(target: &ScanTarget, config: &I18nConfig, quiet: bool) -> Result<CommandOutcome<I18nReport>> {
+    let Some(library) = LIBRARIES.iter().find(|library| FileUtils::has_dependency(&target.root, library)) else {
+        let report = I18nReport {
+            library: None,
+            locales: Vec::new(),
+            hardcoded: Vec::new(),
+            missing_keys: Vec::new(),
+            unused_keys: Vec::new(),
+            summary: I18nSummary::default(),
+        };
+        return Ok(CommandOutcome::new(report, ExitCode::Success));
+    };
+
+    let scanner = FileScanner::for_command(target, "i18n")?;
+    let files: Vec<PathBuf> = scanner
+        .find_target_files(target, &["ts", "tsx", "js", "jsx"])
+        .into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(&target.root).unwrap_or(path)))
+        .collect();
+
+    let results: Vec<(Vec<HardcodedString>, Vec<KeyReference>)> = FileUtils::process_files_parallel(
+        &files,
+        |path| scan_file(path, &target.root, config),
+        "Checking translations",
+        quiet
+    )?;
+    let (hardcoded, references): (Vec<_>, Vec<_>) = results.into_iter().unzip();
+    let mut hardcoded: Vec<HardcodedString> = hardcoded.into_iter().flatten().collect();
+    hardcoded.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    let references: Vec<KeyReference> = references.into_iter().flatten().collect();
+
+    let locales = load_locales(&target.root, config);
+    let mut missing_keys: Vec<MissingKey> = Vec::new();
+    let mut unused_keys = Vec::new();
+
+    // Without locale files there is nothing to compare against
+    if !locales.is_empty() {
+        for reference in references.iter().filter(|reference| !reference.dynamic) {
+            if missing_keys.iter().any(|missing| missing.key == reference.key) {
+                continue;
+            }
+            let without: Vec<String> = locales.iter()
+                .filter(|(_, keys)| !defines(keys, &reference.key))
+                .map(|(locale, _)| locale.clone())
+                .collect();
+            if !without.is_empty() {
+                missing_keys.push(MissingKey { key: reference.key.clone(), locales: without, file: reference.file.clone(), line: reference.line });
+            }
+        }
+
+        let mut defined: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+        for keys in locales.values() {
+            for (key, file) in keys {
+                defined.entry(key).or_default().insert(file);
+            }
+        }
+        for (key, files) in defined {
+            if !references.iter().any(|reference| is_used_by(key, reference)) {
+                unused_keys.push(UnusedKey { key: key.to_string(), files: files.into_iter().map(str::to_string).collect() });
+            }
+        }
+    }
+
+    let summary = I18nSummary {
+        files_scanned: files.len(),
+        keys_defined: locales.values().flat_map(|keys| keys.keys()).collect::<BTreeSet<_>>().len(),
+        keys_referenced: references.iter().map(|reference| &reference.key).collect::<BTreeSet<_>>().len(),
+        hardcoded_strings: hardcoded.len(),
+        missing_keys: missing_keys.len(),
+        unused_keys: unused_keys.len(),
+    };
+    let exit_code = check_failure_threshold(!missing_keys.is_empty(), ExitCode::ValidationFailed);
+    let report = I18nReport {
+        library: Some(library.to_string()),
+        locales: locales.into_keys().collect(),
+        hardcoded,
+        missing_keys,
+        unused_keys,
+        summary,
+    };
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+/// Whether a locale's keys contain `key` as a message, an i18next plural, or a nested object
+fn defines(keys: &BTreeMap<String, String>, key: &str) -> bool {
+    keys.contains_key(key)
+        || PLURAL_SUFFIXES.iter().any(|suffix| keys.contains_key(&format!("{}{}", key, suffix)))
+        || keys.range(format!("{}.", key)..).next().is_some_and(|(defined, _)| defined.starts_with(&format!("{}.", key)))
+}
+
+fn is_used_by(key: &str, reference: &KeyReference) -> bool {
+    if reference.dynamic {
+        return key.starts_with(&reference.key);
+    }
+    let base = PLURAL_SUFFIXES.iter().find_map(|suffix| key.strip_suffix(suffix)).unwrap_or(key);
+    base == reference.key || key.starts_with(&format!("{}.", reference.key))
+}
+
+fn scan_file(path: &Path, root: &Path, config: &I18nConfig) -> Result<(Vec<HardcodedString>, Vec<KeyReference>)> {
+    let content = fs::read_to_string(path)?;
+    let file = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+    let line_of = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let is_user_text = |text: &str| {
+        patterns().letters.is_match(text) && !config.allowlist.iter().any(|allowed| allowed == text)
+    };
+
+    let mut hardcoded = Vec::new();
+    let mut references = Vec::new();
+    let elements = jsx_elements(&content);
+
+    for (offset, text) in jsx_text(&content) {
+        let trimmed = text.trim();
+        if is_user_text(trimmed) {
+            let leading = text.len() - text.trim_start().len();
+            hardcoded.push(HardcodedString { file: file.clone(), line: line_of(offset + leading), attribute: None, text: shorten(trimmed) });
+        }
+    }
+    for element in &elements {
+        for attribute in &config.attributes {
+            if let Some(text) = element.literal(attribute).map(str::trim).filter(|text| is_user_text(text)) {
+                hardcoded.push(HardcodedString { file: file.clone(), line: line_of(element.offset), attribute: Some(attribute.clone()), text: shorten(text) });
+            }
+        }
+    }
+
+    // Translation functions in this file, by variable name
+    let mut namespaces: HashMap<&str, Option<&str>> = HashMap::new();
+    for caps in patterns().binding.captures_iter(&content) {
+        let name = caps.get(1).or_else(|| caps.get(2)).map_or("t", |name| name.as_str());
+        namespaces.insert(name, caps.get(3).map(|namespace| namespace.as_str()).filter(|namespace| !namespace.is_empty()));
+    }
+    for caps in patterns().call.captures_iter(&content) {
+        let name = &caps[1];
+        let namespace = match namespaces.get(name) {
+            Some(namespace) => *namespace,
+            None if matches!(name, "t" | "i18n.t" | "i18next.t") => None,
+            None => continue,
+        };
+        let key = caps.get(2).unwrap();
+        let dynamic = &caps[3] == "${";
+        if key.as_str().is_empty() && !dynamic {
+            continue;
+        }
+        references.push(KeyReference { key: qualify(namespace, key.as_str()), dynamic, file: file.clone(), line: line_of(key.start()) });
+    }
+    for element in elements.iter().filter(|element| element.name == "Trans") {
+        if let Some(key) = element.literal("i18nKey") {
+            references.push(KeyReference { key: qualify(element.literal("ns"), key), dynamic: false, file: file.clone(), line: line_of(element.offset) });
+        }
+    }
+
+    Ok((hardcoded, references))
+}
+
+/// `ns:key` (i18next) and a namespace from `useTranslations('ns')` become `ns.key`
+fn qualify(namespace: Option<&str>, key: &str) -> String {
+    match key.split_once(':') {
+        Some((namespace, key)) => format!("{}.{}", namespace, key),
+        None => match namespace {
+            Some(namespace) => format!("{}.{}", namespace, key),
+            None => key.to_string(),
+        },
+    }
+}
+
+/// Flattened keys per locale, each with the locale file defining it. `messages/en.json` is the
+/// `en` locale; in `locales/en/common.json` keys are prefixed with the `common` namespace
+/// (except i18next's default `translation` namespace).
+fn load_locales(root: &Path, config: &I18nConfig) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut locales: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+    for dir in &config.locale_dirs {
+        let dir = root.join(dir);
+        for entry in WalkDir::new(&dir).max_depth(2).into_iter().filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&dir) else { continue };
+            let stem = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+            let (locale, namespace) = match relative.parent().and_then(|parent| parent.to_str()).filter(|parent| !parent.is_empty()) {
+                Some(locale) => (locale, Some(stem).filter(|stem| *stem != "translation")),
+                None => (stem, None),
+            };
+            let Ok(json) = fs::read_to_string(path).map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<serde_json::Value>(&content)?)) else { continue };
+
+            let file = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+            let keys = locales.entry(locale.to_string()).or_default();
+            flatten(&json, namespace.map(str::to_string), &mut |key| {
+                keys.insert(key, file.clone());
+            });
+        }
+    }
+    locales
+}
+
+fn flatten(value: &serde_json::Value, prefix: Option<String>, add: &mut dyn FnMut(String)) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let key = match &prefix {
+                    Some(prefix) => format!("{}.{}", prefix, key),
+                    None => key.clone(),
+                };
+                flatten(value, Some(key), add);
+            }
+        }
+        _ => {
+            if let Some(key) = prefix {
+                add(key);
+            }
+        }
+    }
+}
+
+fn shorten(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= 80 {
+        text
+    } else {
+        format!("{}…", text.chars().take(79).collect::<String>())
+    }
+}
+
+fn print_report(report: &I18nReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🌐 Translation Report".bold().blue());
+        output::line("=====================".blue());
+        output::blank();
+    }
+
+    let Some(library) = &report.library else {
+        output::line(format!("ℹ️  No i18n library found in package.json ({})", LIBRARIES.join(", ")).dimmed());
+        return;
+    };
+    if !quiet {
+        let locales = if report.locales.is_empty() { "no locale files found".to_string() } else { report.locales.join(", ") };
+        output::line(format!("Library: {}  ·  Locales: {}", library.cyan(), locales));
+        output::blank();
+    }
+
+    if !report.missing_keys.is_empty() {
+        output::line("❌ MISSING KEYS".bold().red());
+        for missing in &report.missing_keys {
+            output::line(format!("  {}  missing in {}  {}", missing.key.bold(), missing.locales.join(", ").red(), format!("({}:{})", missing.file, missing.line).dimmed()));
+        }
+        output::blank();
+    }
+
+    if !report.hardcoded.is_empty() {
+        output::line("⚠️  HARDCODED STRINGS".bold().yellow());
+        let mut current_file = "";
+        for string in &report.hardcoded {
+            if string.file != current_file {
+                current_file = &string.file;
+                output::line(format!("  {}", string.file.cyan()));
+            }
+            let label = match &string.attribute {
+                Some(attribute) => format!("{}=", attribute),
+                None => String::new(),
+            };
+            output::line(format!("    {}: {}\"{}\"", string.line, label.dimmed(), string.text));
+        }
+        output::blank();
+    }
+
+    if !report.unused_keys.is_empty() {
+        output::line("💡 UNUSED KEYS".bold());
+        for unused in &report.unused_keys {
+            output::line(format!("  {}  {}", unused.key, unused.files.join(", ").dimmed()));
+        }
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Keys: {} defined, {} used in code", summary.keys_defined, summary.keys_referenced));
+    output::line(format!("  Hardcoded strings: {}  Missing keys: {}  Unused keys: {}", summary.hardcoded_strings, summary.missing_keys, summary.unused_keys));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn references(content: &str) -> Vec<(String, bool)> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("page.tsx");
+        fs::write(&path, content).unwrap();
+        let (_, references) = scan_file(&path, dir.path(), &I18nConfig::default()).unwrap();
+        references.into_iter().map(|reference| (reference.key, reference.dynamic)).collect()
+    }
+
+    #[test]
+    fn test_key_references() {
+        let source = r#"const t = useTranslations('Home');
+const { t: tc } = useTranslation('common');
+t('title'); tc('save'); t.rich('intro', { b: chunks }); t(`status.${status}`);
+i18n.t('errors:notFound'); format('not a key');
+<Trans i18nKey="welcome" />"#;
+        assert_eq!(references(source), vec![
+            ("Home.title".to_string(), false),
+            ("common.save".to_string(), false),
+            ("Home.intro".to_string(), false),
+            ("Home.status.".to_string(), true),
+            ("errors.notFound".to_string(), false),
+            ("welcome".to_string(), false),
+        ]);
+    }
+
+    #[test]
+    fn test_defined_and_used_keys() {
+        let keys: BTreeMap<String, String> = ["Home.title", "items_one", "items_other", "nav.links.home"].iter()
+            .map(|key| (key.to_string(), "messages/en.json".to_string()))
+            .collect();
+        assert!(defines(&keys, "Home.title") && defines(&keys, "items") && defines(&keys, "nav.links"));
+        assert!(defines(&keys, "Home"));
+        assert!(!defines(&keys, "Home.subtitle"));
+
+        let reference = |key: &str, dynamic| KeyReference { key: key.to_string(), dynamic, file: String::new(), line: 1 };
+        assert!(is_used_by("items_other", &reference("items", false)));
+        assert!(is_used_by("status.active", &reference("status.", true)));
+        assert!(!is_used_by("Home.titles", &reference("Home.title", false)));
+    }
+}
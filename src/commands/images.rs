@@ -0,0 +1,508 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::OnceLock;
+use crate::utils::FileUtils;
+use crate::config::ImagesConfig;
+use crate::common::jsx::{jsx_elements, AttrValue};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "bmp", "tif", "tiff"];
+
+/// Directories served as-is (Next.js, Vite and CRA use `public/`, SvelteKit `static/`)
+const PUBLIC_DIRS: &[&str] = &["public", "static"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImagesReport {
+    /// Assets with at least one issue, largest potential savings first
+    pub assets: Vec<ImageAsset>,
+    pub summary: ImagesSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageAsset {
+    pub path: String,
+    pub format: String,
+    pub bytes: u64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub issues: Vec<ImageIssue>,
+    /// Estimated bytes saved by fixing every issue
+    pub potential_savings: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImageIssue {
+    pub kind: ImageIssueKind,
+    pub message: String,
+    /// The source file and line for issues found where the image is used
+    pub file: Option<String>,
+    pub line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ImageIssueKind {
+    /// More bytes than `max_kb`
+    Heavy,
+    /// Wider or taller than `max_dimension`
+    Oversized,
+    /// PNG, JPEG, GIF, BMP or TIFF that would be smaller as WebP/AVIF
+    LegacyFormat,
+    /// An SVG carrying a base64 PNG/JPEG instead of vectors
+    EmbeddedRaster,
+    /// A large image rendered with `<img>` in a Next.js project
+    MissingNextImage,
+}
+
+impl ImageIssueKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            ImageIssueKind::Heavy => "SNF-IMG-HEAVY",
+            ImageIssueKind::Oversized => "SNF-IMG-OVERSIZED",
+            ImageIssueKind::LegacyFormat => "SNF-IMG-LEGACY-FORMAT",
+            ImageIssueKind::EmbeddedRaster => "SNF-IMG-EMBEDDED-RASTER",
+            ImageIssueKind::MissingNextImage => "SNF-IMG-NEXT-IMAGE",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImagesSummary {
+    pub images_scanned: usize,
+    pub images_with_issues: usize,
+    pub total_bytes: u64,
+    pub potential_savings: u64,
+    pub by_kind: BTreeMap<String, usize>,
+}
+
+/// An image referenced from source: an import, or an `<img src>`
+struct ImageReference {
+    image: String,
+    file: String,
+    line: usize,
+    img_tag: bool,
+}
+
+struct Patterns {
+    import: Regex,
+    embedded_raster: Regex,
+}
+
+static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+
+fn patterns() -> &'static Patterns {
+    PATTERNS.get_or_init(|| Patterns {
+        import: Regex::new(
+            r#"(?:\bimport\s+([A-Za-z_$][\w$]*)\s+from\s*|\bimport\s*|\brequire\s*\(\s*)['"]([^'"]+\.(?:png|jpe?g|gif|webp|avif|svg|bmp|tiff?))['"]"#
+        ).unwrap(),
+        embedded_raster: Regex::new(r#"href\s*=\s*["']data:image/(?:png|jpe?g|gif|webp);base64,([A-Za-z0-9+/=\s]+)"#).unwrap(),
+    })
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<ImagesReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running image analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.images, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "images",
+        report,
+        report.summary.images_scanned,
+        report.summary.images_with_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = report.summary.images_with_issues == 0, "image analysis completed");
+
+    Ok(outcome)
+}
+
+/// Audit images in `public/`/`static/` and images imported from source. Images over the byte
+/// or dimension limits fail the check; format and usage issues are suggestions.
+pub fn check(target: &ScanTarget, limits: &ImagesConfig, quiet: bool) -> Result<CommandOutcome<ImagesReport>> {
+    let scanner = FileScanner::for_command(target, "images")?;
+    let relative = |path: &Path| FileUtils::slash_path(path.strip_prefix(&target.root).unwrap_or(path));
+
+    let sources: Vec<PathBuf> = scanner
+        .find_target_files(target, &["ts", "tsx", "js", "jsx", "mjs", "vue", "svelte", "astro"])
+        .into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(&target.root).unwrap_or(path)))
+        .collect();
+    let references: Vec<ImageReference> = sources.iter()
+        .filter_map(|path| target.context.read(path).ok().map(|content| image_references(&content, &relative(path))))
+        .flatten()
+        .collect();
+    let referenced: HashSet<&str> = references.iter().map(|reference| reference.image.as_str()).collect();
+
+    let images: Vec<PathBuf> = scanner
+        .find_target_files(target, IMAGE_EXTENSIONS)
+        .into_iter()
+        .filter(|path| {
+            let path = relative(path);
+            PUBLIC_DIRS.iter().any(|dir| path.starts_with(&format!("{}/", dir))) || referenced.contains(path.as_str())
+        })
+        .collect();
+
+    let uses_next = FileUtils::has_dependency(&target.root, "next");
+    let results: Vec<ImageAsset> = FileUtils::process_files_parallel(
+        &images,
+        |path| {
+            let path_name = relative(path);
+            let img_tags: Vec<&ImageReference> = references.iter()
+                .filter(|reference| reference.img_tag && reference.image == path_name)
+                .collect();
+            analyze_image(path, paths::display(path), limits, if uses_next { &img_tags } else { &[] })
+        },
+        "Checking images",
+        quiet
+    )?;
+
+    let total_bytes = results.iter().map(|asset| asset.bytes).sum();
+    let ignores = target.ignores()?;
+    let mut assets: Vec<ImageAsset> = results.into_iter()
+        .map(|mut asset| {
+            asset.issues.retain(|issue| !ignores.ignores(issue.kind.rule_id(), &asset.path));
+            // References were matched by their path from the root; report them like other paths
+            for file in asset.issues.iter_mut().filter_map(|issue| issue.file.as_mut()) {
+                *file = paths::display(&target.root.join(&*file));
+            }
+            asset
+        })
+        .filter(|asset| !asset.issues.is_empty())
+        .collect();
+    assets.sort_by(|a, b| b.potential_savings.cmp(&a.potential_savings).then_with(|| a.path.cmp(&b.path)));
+
+    let mut by_kind = BTreeMap::new();
+    for issue in assets.iter().flat_map(|asset| &asset.issues) {
+        *by_kind.entry(format!("{:?}", issue.kind)).or_insert(0) += 1;
+    }
+    let summary = ImagesSummary {
+        images_scanned: images.len(),
+        images_with_issues: assets.len(),
+        total_bytes,
+        potential_savings: assets.iter().map(|asset| asset.potential_savings).sum(),
+        by_kind,
+    };
+
+    let too_big = assets.iter()
+        .flat_map(|asset| &asset.issues)
+        .any(|issue| matches!(issue.kind, ImageIssueKind::Heavy | ImageIssueKind::Oversized));
+    let exit_code = check_failure_threshold(too_big, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(ImagesReport { assets, summary }, exit_code))
+}
+
+fn analyze_image(path: &Path, name: String, limits: &ImagesConfig, img_tags: &[&ImageReference]) -> Result<ImageAsset> {
+    let data = fs::read(path)?;
+    let bytes = data.len() as u64;
+    let format = path.extension().and_then(|ext| ext.to_str()).unwrap_or("").to_lowercase();
+    let (width, height) = dimensions(&data).unzip();
+    let mut issues = Vec::new();
+    // Fraction of the bytes left after each fix
+    let mut remaining = 1.0;
+
+    if bytes > limits.max_kb * 1024 {
+        issues.push(issue(ImageIssueKind::Heavy, format!("{} is over the {} KB limit", format_bytes(bytes), limits.max_kb)));
+    }
+    if let (Some(width), Some(height)) = (width, height) {
+        let longest = width.max(height);
+        if longest > limits.max_dimension {
+            let scale = limits.max_dimension as f64 / longest as f64;
+            remaining *= scale * scale;
+            issues.push(issue(ImageIssueKind::Oversized, format!(
+                "{}×{} px; resize so the longest side is at most {} px", width, height, limits.max_dimension
+            )));
+        }
+    }
+    if let Some(factor) = webp_factor(&format).filter(|_| bytes >= limits.modern_format_min_kb * 1024) {
+        remaining *= factor;
+        issues.push(issue(ImageIssueKind::LegacyFormat, format!("{} could be served as WebP or AVIF", format.to_uppercase())));
+    }
+    let mut savings = bytes as f64 * (1.0 - remaining);
+
+    if format == "svg" {
+        let text = String::from_utf8_lossy(&data);
+        let embedded: usize = patterns().embedded_raster.captures_iter(&text)
+            .map(|caps| caps[1].chars().filter(|c| !c.is_whitespace()).count())
+            .sum();
+        if embedded > 0 {
+            // Base64 alone adds a third to the raster; as a separate file it can also be cached and converted
+            savings += embedded as f64 / 4.0;
+            issues.push(issue(ImageIssueKind::EmbeddedRaster, format!(
+                "Embeds {} of base64 raster data; ship the raster as its own WebP/AVIF file or redraw it as vectors",
+                format_bytes(embedded as u64)
+            )));
+        }
+    }
+
+    if format != "svg" && bytes >= limits.next_image_min_kb * 1024 {
+        for reference in img_tags {
+            issues.push(ImageIssue {
+                kind: ImageIssueKind::MissingNextImage,
+                message: format!("Rendered with <img> in {}:{}; next/image would resize it and serve modern formats", reference.file, reference.line),
+                file: Some(reference.file.clone()),
+                line: Some(reference.line),
+            });
+        }
+    }
+
+    Ok(ImageAsset {
+        path: name,
+        format,
+        bytes,
+        width,
+        height,
+        issues,
+        potential_savings: savings.round() as u64,
+    })
+}
+
+fn issue(kind: ImageIssueKind, message: String) -> ImageIssue {
+    ImageIssue { kind, message, file: None, line: None }
+}
+
+/// Typical size of a WebP relative to the original format
+fn webp_factor(format: &str) -> Option<f64> {
+    match format {
+        "png" => Some(0.6),
+        "jpg" | "jpeg" => Some(0.7),
+        "gif" => Some(0.5),
+        "bmp" | "tif" | "tiff" => Some(0.1),
+        _ => None,
+    }
+}
+
+/// Images imported or rendered by a source file, as paths relative to the project root
+fn image_references(content: &str, file: &str) -> Vec<ImageReference> {
+    let line_of = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let mut references = Vec::new();
+    let mut imported: HashMap<&str, String> = HashMap::new();
+
+    for caps in patterns().import.captures_iter(content) {
+        let Some(image) = resolve(file, &caps[2]) else { continue };
+        if let Some(binding) = caps.get(1) {
+            imported.insert(binding.as_str(), image.clone());
+        }
+        references.push(ImageReference { image, file: file.to_string(), line: line_of(caps.get(0).unwrap().start()), img_tag: false });
+    }
+
+    for element in jsx_elements(content).into_iter().filter(|element| element.name == "img") {
+        let image = match element.attribute("src") {
+            Some(Some(AttrValue::Literal(src))) => resolve(file, src),
+            Some(Some(AttrValue::Expression(expression))) => {
+                let name = expression.trim().trim_end_matches(".src");
+                imported.get(name).cloned()
+            }
+            _ => None,
+        };
+        if let Some(image) = image {
+            references.push(ImageReference { image, file: file.to_string(), line: line_of(element.offset), img_tag: true });
+        }
+    }
+    references
+}
+
+/// Root-relative path of an image referenced from `file`: `./hero.png`, `@/assets/hero.png`,
+/// or `/hero.png` served from `public/`
+fn resolve(file: &str, specifier: &str) -> Option<String> {
+    if specifier.contains("://") || specifier.starts_with("//") || specifier.starts_with("data:") {
+        return None;
+    }
+    let path = if let Some(rest) = specifier.strip_prefix("@/").or_else(|| specifier.strip_prefix("~/")) {
+        PathBuf::from("src").join(rest)
+    } else if let Some(rest) = specifier.strip_prefix('/') {
+        PathBuf::from("public").join(rest)
+    } else if specifier.starts_with('.') {
+        Path::new(file).parent().unwrap_or(Path::new("")).join(specifier)
+    } else {
+        return None;
+    };
+
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            _ => {}
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Width and height from the file header of PNG, JPEG, GIF, BMP, WebP and AVIF images
+fn dimensions(data: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| data.get(at..at + 2).map(|b| u16::from_be_bytes([b[0], b[1]]) as u32);
+    let le16 = |at: usize| data.get(at..at + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as u32);
+    let be32 = |at: usize| data.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+    let le32 = |at: usize| data.get(at..at + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let le24 = |at: usize| data.get(at..at + 3).map(|b| u32::from_le_bytes([b[0], b[1], b[2], 0]));
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return Some((be32(16)?, be32(20)?));
+    }
+    if data.starts_with(b"GIF8") {
+        return Some((le16(6)?, le16(8)?));
+    }
+    if data.starts_with(b"BM") {
+        return Some((le32(18)?, (le32(22)? as i32).unsigned_abs()));
+    }
+    if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        return match data.get(12..16)? {
+            b"VP8 " => Some((le16(26)? & 0x3fff, le16(28)? & 0x3fff)),
+            b"VP8L" => {
+                let bits = le32(21)?;
+                Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+            }
+            b"VP8X" => Some((le24(24)? + 1, le24(27)? + 1)),
+            _ => None,
+        };
+    }
+    if data.starts_with(&[0xff, 0xd8]) {
+        let mut i = 2;
+        while i + 9 < data.len() {
+            if data[i] != 0xff {
+                return None;
+            }
+            let marker = data[i + 1];
+            match marker {
+                0xff => i += 1,
+                0xd0..=0xd9 | 0x01 => i += 2,
+                // Start-of-frame markers carry the size; C4, C8 and CC are other tables
+                0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => return Some((be16(i + 7)?, be16(i + 5)?)),
+                _ => i += 2 + be16(i + 2)? as usize,
+            }
+        }
+        return None;
+    }
+    if data.get(4..8) == Some(b"ftyp") {
+        // AVIF/HEIF: the `ispe` property holds the image size after its version and flags
+        let at = data.windows(4).position(|window| window == b"ispe")?;
+        return Some((be32(at + 8)?, be32(at + 12)?));
+    }
+    None
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+impl Annotate for ImagesReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.assets.iter().flat_map(|asset| asset.issues.iter().map(move |issue| {
+            let level = match issue.kind {
+                ImageIssueKind::Heavy | ImageIssueKind::Oversized => AnnotationLevel::Warning,
+                _ => AnnotationLevel::Notice,
+            };
+            let file = issue.file.as_deref().unwrap_or(&asset.path);
+            let message = format!("{}: {}", asset.path, issue.message);
+            Annotation::new(level, file, issue.line.unwrap_or(1), format!("Image: {:?}", issue.kind), message)
+                .with_rule(issue.kind.rule_id())
+        })).collect()
+    }
+}
+
+fn print_report(report: &ImagesReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🖼️  Image Optimization Report".bold().blue());
+        output::line("============================".blue());
+        output::blank();
+    }
+
+    if report.assets.is_empty() {
+        output::line("✅ All images are well optimized!".green());
+    }
+
+    for asset in &report.assets {
+        let size = match (asset.width, asset.height) {
+            (Some(width), Some(height)) => format!("{}, {}×{}", format_bytes(asset.bytes), width, height),
+            _ => format_bytes(asset.bytes),
+        };
+        let savings = if asset.potential_savings > 0 {
+            format!("  save ~{}", format_bytes(asset.potential_savings)).green().to_string()
+        } else {
+            String::new()
+        };
+        output::line(format!("{} ({}){}", asset.path.cyan().bold(), size, savings));
+        for issue in &asset.issues {
+            let icon = match issue.kind {
+                ImageIssueKind::Heavy | ImageIssueKind::Oversized => "⚠️ ",
+                _ => "💡",
+            };
+            output::line(format!("  {} {}", icon, issue.message));
+        }
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Images scanned: {} ({})", summary.images_scanned, format_bytes(summary.total_bytes)));
+    output::line(format!("  Images with issues: {}", summary.images_with_issues));
+    if summary.potential_savings > 0 {
+        output::line(format!("  Potential savings: {}", format_bytes(summary.potential_savings).green().bold()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dimensions_from_headers() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend_from_slice(&3000u32.to_be_bytes());
+        png.extend_from_slice(&2000u32.to_be_bytes());
+        assert_eq!(dimensions(&png), Some((3000, 2000)));
+
+        assert_eq!(dimensions(b"GIF89a\x40\x01\xf0\x00"), Some((320, 240)));
+
+        // SOI, an APP0 segment, then SOF0 with height 480 and width 640
+        let jpeg = [0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80, 0x03];
+        assert_eq!(dimensions(&jpeg), Some((640, 480)));
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X\x0a\0\0\0\0\0\0\0".to_vec();
+        webp.extend_from_slice(&[0x7f, 0x07, 0x00, 0x37, 0x04, 0x00]);
+        assert_eq!(dimensions(&webp), Some((1920, 1080)));
+        assert_eq!(dimensions(b"<svg />"), None);
+    }
+
+    #[test]
+    fn test_image_references() {
+        let source = r#"import hero from '../assets/hero.jpg';
+import logo from "@/assets/logo.svg";
+export const Page = () => (
+  <main>
+    <img src={hero.src} alt="" />
+    <img src="/banner.png" alt="" />
+    <img src="https://cdn.example.com/a.png" alt="" />
+  </main>
+);"#;
+        let references = image_references(source, "src/app/page.tsx");
+        let found: Vec<(&str, usize, bool)> = references.iter().map(|r| (r.image.as_str(), r.line, r.img_tag)).collect();
+        assert_eq!(found, vec![
+            ("src/assets/hero.jpg", 1, false),
+            ("src/assets/logo.svg", 2, false),
+            ("src/assets/hero.jpg", 5, true),
+            ("public/banner.png", 6, true),
+        ]);
+        assert_eq!(resolve("src/page.tsx", "../../outside.png"), None);
+    }
+}
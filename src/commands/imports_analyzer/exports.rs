@@ -0,0 +1,154 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::common::{paths, AnalysisContext, glob_matches};
+use crate::common::source::strip_comments;
+use crate::utils::FileUtils;
+use super::types::UnusedExport;
+
+struct ExportPatterns {
+    /// `export const x`, `export async function f`, `export interface T`, ...
+    declaration: Regex,
+    /// `export default ...`
+    default: Regex,
+    /// `export { a, b as c }`, with or without `from`
+    list: Regex,
+}
+
+static EXPORT_PATTERNS: OnceLock<ExportPatterns> = OnceLock::new();
+
+fn patterns() -> &'static ExportPatterns {
+    EXPORT_PATTERNS.get_or_init(|| ExportPatterns {
+        declaration: Regex::new(r"(?m)^[ \t]*export\s+(?:declare\s+)?(?:async\s+)?(?:abstract\s+)?(?:const|let|var|function\*?|class|interface|type|enum|namespace)\s+([A-Za-z_$][\w$]*)").unwrap(),
+        default: Regex::new(r"(?m)^[ \t]*export\s+default\b").unwrap(),
+        list: Regex::new(r"(?m)^[ \t]*export\s+(?:type\s+)?\{([^}]*)\}").unwrap(),
+    })
+}
+
+/// Next.js app router files, whose exports the framework reads
+const APP_ROUTER_FILES: &[&str] = &[
+    "page", "layout", "template", "loading", "error", "global-error", "not-found", "default", "route",
+    "opengraph-image", "twitter-image", "icon", "apple-icon", "sitemap", "robots", "manifest",
+];
+
+/// Exported symbols in `files` that no file in the project imports. `symbols` holds the names
+/// taken from each file, as collected by `context`'s import graph.
+pub fn find_unused_exports(
+    files: &[PathBuf],
+    project_root: &Path,
+    symbols: &HashMap<String, Option<HashSet<String>>>,
+    entry_points: &[String],
+    context: &AnalysisContext,
+) -> Vec<UnusedExport> {
+    let mut unused = Vec::new();
+    for path in files {
+        let relative = FileUtils::slash_path(path.strip_prefix(project_root).unwrap_or(path));
+        if is_entry_point(&relative, entry_points) {
+            continue;
+        }
+        let used = match symbols.get(&relative) {
+            Some(None) => continue,
+            Some(Some(used)) => Some(used),
+            None => None,
+        };
+        let Ok(content) = context.read(path) else {
+            continue;
+        };
+        let code = strip_comments(&content);
+        for (line, name) in exported_names(&code) {
+            if used.is_some_and(|used| used.contains(&name)) {
+                continue;
+            }
+            let used_in_file = name != "default" && word_count(&code, &name) > 1;
+            unused.push(UnusedExport {
+                file: paths::display(path),
+                line,
+                name,
+                used_in_file,
+            });
+        }
+    }
+    unused
+}
+
+/// Files other tools or frameworks load by convention: tests, stories, declarations,
+/// configs, Next.js routes and middleware, package entry points and `[imports] entry_points`
+fn is_entry_point(path: &str, entry_points: &[String]) -> bool {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    let in_dir = |dir: &str| path.starts_with(&format!("{}/", dir)) || path.contains(&format!("/{}/", dir));
+    let at_root = |path: &str| !path.contains('/') || path.strip_prefix("src/").is_some_and(|rest| !rest.contains('/'));
+
+    FileUtils::is_test_file(Path::new(path))
+        || file_name.ends_with(".d.ts")
+        || file_name.contains(".stories.")
+        || file_name.contains(".config.")
+        || in_dir("pages")
+        || (in_dir("app") && APP_ROUTER_FILES.contains(&stem))
+        || (at_root(path) && matches!(stem, "index" | "main" | "middleware" | "instrumentation"))
+        || entry_points.iter().any(|pattern| glob_matches(pattern, path))
+}
+
+/// Names `code` exports and the line of each; `export *` re-exports are not listed
+fn exported_names(code: &str) -> Vec<(usize, String)> {
+    let patterns = patterns();
+    let line_of = |offset: usize| code[..offset].matches('\n').count() + 1;
+    let mut names = Vec::new();
+
+    for caps in patterns.declaration.captures_iter(code) {
+        names.push((line_of(caps.get(0).map_or(0, |m| m.start())), caps[1].to_string()));
+    }
+    for found in patterns.default.find_iter(code) {
+        names.push((line_of(found.start()), "default".to_string()));
+    }
+    for caps in patterns.list.captures_iter(code) {
+        let line = line_of(caps.get(0).map_or(0, |m| m.start()));
+        for item in caps[1].split(',') {
+            let item = item.trim();
+            let item = item.strip_prefix("type ").unwrap_or(item);
+            if let Some(name) = item.split_whitespace().last() {
+                names.push((line, name.to_string()));
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    names
+}
+
+fn word_count(code: &str, word: &str) -> usize {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '$';
+    code.match_indices(word)
+        .filter(|(offset, _)| {
+            !code[..*offset].chars().next_back().is_some_and(is_ident)
+                && !code[offset + word.len()..].chars().next().is_some_and(is_ident)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exported_names() {
+        let code = "export const a = 1;\nexport default function App() {}\nexport async function load() {}\nexport {\n  b,\n  c as d,\n  type E,\n};\nexport * from './x';\nexport type { F } from './f';\n";
+        let names = exported_names(code);
+        let names: Vec<(usize, &str)> = names.iter().map(|(line, name)| (*line, name.as_str())).collect();
+        assert_eq!(names, vec![(1, "a"), (2, "default"), (3, "load"), (4, "E"), (4, "b"), (4, "d"), (10, "F")]);
+    }
+
+    #[test]
+    fn test_entry_points() {
+        assert!(is_entry_point("app/blog/[slug]/page.tsx", &[]));
+        assert!(is_entry_point("src/pages/api/users.ts", &[]));
+        assert!(is_entry_point("src/index.ts", &[]));
+        assert!(is_entry_point("next.config.js", &[]));
+        assert!(is_entry_point("src/lib/date.test.ts", &[]));
+        assert!(!is_entry_point("src/lib/index.ts", &[]));
+        assert!(!is_entry_point("app/components/page-header.tsx", &[]));
+        assert!(is_entry_point("scripts/seed.ts", &["scripts/**".to_string()]));
+    }
+}
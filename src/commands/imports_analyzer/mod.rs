@@ -3,115 +3,284 @@ mod resolver;
 mod validation;
 mod parser;
 mod reporter;
+mod exports;
+mod type_imports;
+mod order;
+mod packages;
 
 use anyhow::Result;
-use colored::*;
 use rayon::prelude::*;
 use std::fs;
 use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+use crate::commands::context::imported_symbols;
+use crate::config::{Config, ImportOrderConfig};
+use crate::common::source::strip_comments;
+use crate::common::ndjson::{self, LevelCounts};
 use crate::common::{
-    FileScanner, get_common_patterns, ExitCode, check_failure_threshold, 
-    progress::FileProgressTracker
+    AnalysisContext, Annotate, FileScanner, Ignores, paths, ListingOptions, ScanTarget, get_common_patterns, ExitCode, CommandOutcome, check_failure_threshold, 
+    create_annotated_json_output, output_result, progress::FileProgressTracker
 };
 
-use types::{ImportsReport, ImportsSummary, UnusedImport, FileAnalysis};
-use resolver::PathAliasResolver;
+pub use types::{ImportsReport, TypeImportIssue, ImportOrderIssue};
+use types::{ImportsSummary, UnusedImport, FileAnalysis};
+pub use resolver::PathAliasResolver;
+use resolver::requires_type_imports;
+pub use validation::resolve_local_import;
 use validation::check_import_validity;
 use parser::{parse_import_statement, find_unused_items, collect_used_identifiers, preprocess_multiline_imports};
+pub use parser::dynamic_imports;
+pub use packages::{is_node_builtin, split_package};
 use reporter::{print_report, calculate_savings};
+use exports::find_unused_exports;
+use type_imports::find_type_import_issues;
+use order::find_order_issue;
 
-pub async fn run(json: bool, quiet: bool) -> Result<()> {
-    if !quiet && !json {
-        println!("{}", "🔍 Scanning for unused and broken imports...".bold().blue());
-    }
+pub async fn run(target: &ScanTarget, unused_exports: bool, listing: ListingOptions, json: bool, quiet: bool) -> Result<CommandOutcome<ImportsReport>> {
+    tracing::info!("Running imports analysis");
     
-    let report = analyze_imports(quiet)?;
+    let start_time = std::time::Instant::now();
+    let outcome = check(target, unused_exports, quiet)?;
+    let report = &outcome.report;
     
-    if json {
-        println!("{}", serde_json::to_string_pretty(&report)?);
-    } else {
-        print_report(&report, quiet);
-    }
+    let response = create_annotated_json_output(
+        "imports",
+        report,
+        report.summary.files_scanned,
+        report.summary.unused_imports + report.summary.broken_imports,
+        Some(start_time.elapsed().as_millis() as u64),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_report(report, listing, quiet))?;
+    
+    Ok(outcome)
+}
+
+/// Analyze imports and decide the exit status, without printing a report. Unused exports
+/// are only looked for when asked, as that reads every file in the project
+pub fn check(target: &ScanTarget, unused_exports: bool, quiet: bool) -> Result<CommandOutcome<ImportsReport>> {
+    let report = analyze_imports(target, unused_exports, quiet)?;
     
-    // Use common error handling for imports issues
-    check_failure_threshold(
+    let exit_code = check_failure_threshold(
         report.summary.unused_imports > 0 || report.summary.broken_imports > 0, 
         ExitCode::ValidationFailed
     );
     
-    Ok(())
+    Ok(CommandOutcome::new(report, exit_code))
 }
 
-fn analyze_imports(quiet: bool) -> Result<ImportsReport> {
-    let current_dir = std::env::current_dir()?;
-    let scanner = FileScanner::with_defaults();
-    let files = scanner.find_js_ts_files(&current_dir);
+/// `--format ndjson`: print each file's findings as soon as it is analyzed, then the unused
+/// exports, which need every file. Only the counts the exit status depends on are kept
+pub fn stream(target: &ScanTarget, unused_exports: bool, counts: &Mutex<LevelCounts>) -> Result<ExitCode> {
+    let project_root = &target.root;
+    let files = FileScanner::for_command(target, "imports")?.find_target_files(target, &["ts", "tsx", "js", "jsx"]);
+    let path_resolver = PathAliasResolver::from_project_root(project_root);
+    let config = target.config()?;
+    let checks = FileChecks {
+        type_imports: requires_type_imports(project_root),
+        order: config.imports.order.enabled.then_some(&config.imports.order),
+        asset_extensions: &config.imports.asset_extensions,
+    };
+
+    let ignores = target.ignores()?;
+    let failing = AtomicUsize::new(0);
+    ndjson::stream_files(&files, counts, |path| {
+        let analysis = analyze_file_imports(&target.context, path, project_root, &path_resolver, &checks)?;
+        let report = file_report(without_ignored(analysis, &ignores));
+        failing.fetch_add(report.summary.unused_imports + report.summary.broken_imports, Ordering::Relaxed);
+        Ok(report.annotations())
+    })?;
+    if unused_exports {
+        let mut exports = find_unused_exports(&files, project_root, &imported_symbols(project_root, &target.context)?, &config.imports.entry_points, &target.context);
+        ignores.retain(&mut exports, |export| ("SNF-IMP-UNUSED-EXPORT", &export.file));
+        let report = ImportsReport { unused_exports: exports, ..file_report(FileAnalysis::default()) };
+        ndjson::print(&report.findings(), counts)?;
+    }
+    Ok(check_failure_threshold(failing.into_inner() > 0, ExitCode::ValidationFailed))
+}
+
+fn analyze_imports(target: &ScanTarget, check_exports: bool, quiet: bool) -> Result<ImportsReport> {
+    let project_root = &target.root;
+    let scanner = FileScanner::for_command(target, "imports")?;
+    let files = scanner.find_target_files(target, &["ts", "tsx", "js", "jsx"]);
     
     // Create path alias resolver
-    let path_resolver = PathAliasResolver::from_project_root(&current_dir);
+    let path_resolver = PathAliasResolver::from_project_root(project_root);
+    let config = target.config()?;
+    let checks = FileChecks {
+        type_imports: requires_type_imports(project_root),
+        order: config.imports.order.enabled.then_some(&config.imports.order),
+        asset_extensions: &config.imports.asset_extensions,
+    };
     
     let files_count = files.len();
     
-    // Show progress for larger projects (>50 files)
-    let progress = if files_count > 50 {
-        FileProgressTracker::new(
-            "Analyzing imports", 
-            Some(files_count), 
-            quiet
-        )
-    } else {
-        FileProgressTracker::new("Analyzing imports", None, true) // No progress for small projects
-    };
+    // Show progress for larger projects (>50 files); files are analyzed in parallel either way
+    let progress = FileProgressTracker::new("Analyzing imports", Some(files_count), quiet || files_count <= 50);
     
-    let file_analyses: Vec<FileAnalysis> = if files_count > 50 {
-        // Sequential processing with progress for large projects
-        let mut analyses = Vec::with_capacity(files_count);
-        for (i, path) in files.iter().enumerate() {
-            progress.set_position(i as u64);
-            analyses.push(analyze_file_imports(path, &current_dir, &path_resolver)?);
-        }
-        progress.finish_with_message(&format!("Analyzed {} files", files_count));
-        analyses
-    } else {
-        // Parallel processing for smaller projects (no progress needed)
-        files
-            .par_iter()
-            .map(|path| analyze_file_imports(path, &current_dir, &path_resolver))
-            .collect::<Result<Vec<_>, _>>()?
-    };
+    let file_analyses: Vec<FileAnalysis> = files
+        .par_iter()
+        .map(|path| {
+            let analysis = analyze_file_imports(&target.context, path, project_root, &path_resolver, &checks);
+            progress.inc();
+            analysis
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    progress.finish_with_message(&format!("Analyzed {} files", files_count));
     
     let mut unused_imports = Vec::new();
     let mut broken_imports = Vec::new();
+    let mut type_imports = Vec::new();
+    let mut import_order = Vec::new();
     let mut total_imports = 0;
     
-    for analysis in file_analyses {
+    let ignores = target.ignores()?;
+    for analysis in file_analyses.into_iter().map(|analysis| without_ignored(analysis, &ignores)) {
         total_imports += analysis.total_imports;
         unused_imports.extend(analysis.unused_imports);
         broken_imports.extend(analysis.broken_imports);
+        type_imports.extend(analysis.type_imports);
+        import_order.extend(analysis.import_order);
     }
     
+    // Exports need the whole project's imports, not just the scanned files
+    let unused_exports = if check_exports {
+        let mut exports = find_unused_exports(&files, project_root, &imported_symbols(project_root, &target.context)?, &config.imports.entry_points, &target.context);
+        ignores.retain(&mut exports, |export| ("SNF-IMP-UNUSED-EXPORT", &export.file));
+        exports
+    } else {
+        Vec::new()
+    };
+    
     let summary = ImportsSummary {
         files_scanned: files_count,
         total_imports,
         unused_imports: unused_imports.len(),
         broken_imports: broken_imports.len(),
+        unused_exports: unused_exports.len(),
+        type_imports: type_imports.len(),
+        import_order: import_order.len(),
         potential_savings: calculate_savings(&unused_imports),
     };
     
     Ok(ImportsReport {
         unused_imports,
         broken_imports,
+        unused_exports,
+        type_imports,
+        import_order,
         summary,
     })
 }
 
+/// Type-only import issues in the target's TypeScript files, whatever tsconfig.json says;
+/// `sniff fix --type-imports` applies their fixes
+pub fn find_type_imports(target: &ScanTarget) -> Result<Vec<TypeImportIssue>> {
+    let files = FileScanner::for_root(&target.root)?.find_target_files(target, &["ts", "tsx"]);
+    let issues: Vec<Vec<TypeImportIssue>> = files
+        .par_iter()
+        .map(|path| -> Result<Vec<TypeImportIssue>> {
+            let content = fs::read_to_string(path)?;
+            let lines: Vec<&str> = content.lines().collect();
+            Ok(type_import_issues(path, &content, &preprocess_multiline_imports(&lines)))
+        })
+        .collect::<Result<_>>()?;
+    Ok(issues.into_iter().flatten().collect())
+}
+
+/// Files in the target whose leading imports are out of `config`'s order, whatever its `enabled`;
+/// `sniff fix --organize-imports` applies their fixes
+pub fn find_import_order(target: &ScanTarget, config: &ImportOrderConfig) -> Result<Vec<ImportOrderIssue>> {
+    let files = FileScanner::for_root(&target.root)?.find_target_files(target, &["ts", "tsx", "js", "jsx"]);
+    let resolver = PathAliasResolver::from_project_root(&target.root);
+    let issues: Vec<Option<ImportOrderIssue>> = files
+        .par_iter()
+        .map(|path| -> Result<Option<ImportOrderIssue>> {
+            let content = fs::read_to_string(path)?;
+            let lines: Vec<&str> = content.lines().collect();
+            Ok(find_order_issue(&paths::display(path), &lines, &preprocess_multiline_imports(&lines), config, &resolver))
+        })
+        .collect::<Result<_>>()?;
+    Ok(issues.into_iter().flatten().collect())
+}
+
+/// The findings for one file's `content`, such as an editor buffer; other files are read
+/// from disk to resolve its imports. Unused exports need the whole project and are left out
+pub fn check_source(path: &Path, content: &str, project_root: &Path, config: &Config) -> Result<ImportsReport> {
+    let path_resolver = PathAliasResolver::from_project_root(project_root);
+    let checks = FileChecks {
+        type_imports: requires_type_imports(project_root),
+        order: config.imports.order.enabled.then_some(&config.imports.order),
+        asset_extensions: &config.imports.asset_extensions,
+    };
+    Ok(file_report(analyze_source_imports(path, content, project_root, &path_resolver, &checks)?))
+}
+
+/// One file's analysis without the findings `[[ignore]]` entries cover
+fn without_ignored(mut analysis: FileAnalysis, ignores: &Ignores) -> FileAnalysis {
+    ignores.retain(&mut analysis.unused_imports, |import| ("SNF-IMP-UNUSED", &import.file));
+    ignores.retain(&mut analysis.broken_imports, |import| ("SNF-IMP-BROKEN", &import.file));
+    ignores.retain(&mut analysis.type_imports, |issue| (issue.kind.rule_id(), &issue.file));
+    analysis.import_order = analysis.import_order.filter(|issue| !ignores.ignores("SNF-IMP-ORDER", &issue.file));
+    analysis
+}
+
+/// One file's analysis as a report of its own
+fn file_report(analysis: FileAnalysis) -> ImportsReport {
+    let summary = ImportsSummary {
+        files_scanned: 1,
+        total_imports: analysis.total_imports,
+        unused_imports: analysis.unused_imports.len(),
+        broken_imports: analysis.broken_imports.len(),
+        unused_exports: 0,
+        type_imports: analysis.type_imports.len(),
+        import_order: analysis.import_order.iter().count(),
+        potential_savings: calculate_savings(&analysis.unused_imports),
+    };
+    ImportsReport {
+        unused_imports: analysis.unused_imports,
+        broken_imports: analysis.broken_imports,
+        unused_exports: Vec::new(),
+        type_imports: analysis.type_imports,
+        import_order: analysis.import_order.into_iter().collect(),
+        summary,
+    }
+}
+
+/// What to check in each file: the opt-in checks and the project's asset extensions
+struct FileChecks<'a> {
+    type_imports: bool,
+    order: Option<&'a ImportOrderConfig>,
+    asset_extensions: &'a [String],
+}
+
+fn type_import_issues(path: &Path, content: &str, entries: &[parser::MultilineImportEntry]) -> Vec<TypeImportIssue> {
+    let is_typescript = path.extension().is_some_and(|ext| ext == "ts" || ext == "tsx");
+    if !is_typescript {
+        return Vec::new();
+    }
+    find_type_import_issues(&paths::display(path), content, entries)
+}
+
 fn analyze_file_imports(
+    context: &AnalysisContext,
+    path: &Path,
+    project_root: &Path,
+    path_resolver: &Option<PathAliasResolver>,
+    checks: &FileChecks,
+) -> Result<FileAnalysis> {
+    let content = context.read(path)?;
+    analyze_source_imports(path, &content, project_root, path_resolver, checks)
+}
+
+fn analyze_source_imports(
     path: &Path,
+    content: &str,
     project_root: &Path,
-    path_resolver: &Option<PathAliasResolver>
+    path_resolver: &Option<PathAliasResolver>,
+    checks: &FileChecks,
 ) -> Result<FileAnalysis> {
-    let content = fs::read_to_string(path)?;
     let lines: Vec<&str> = content.lines().collect();
 
     let patterns = get_common_patterns();
@@ -125,6 +294,7 @@ fn analyze_file_imports(
         .collect();
 
     let mut imports = Vec::new();
+    let mut side_effect_imports = Vec::new();
     for entry in &import_entries {
         if let Some(captures) = patterns.import_statement.captures(entry.collapsed.trim()) {
             let (Some(spec_match), Some(path_match)) = (captures.get(1), captures.get(2)) else {
@@ -132,23 +302,35 @@ fn analyze_file_imports(
             };
             let parsed_import = parse_import_statement(spec_match.as_str(), path_match.as_str());
             imports.push((entry.line_num, entry.collapsed.clone(), parsed_import, path_match.as_str().to_string()));
+        } else if let Some(path_match) = patterns.side_effect_import.captures(entry.collapsed.trim()).and_then(|captures| captures.get(1)) {
+            side_effect_imports.push((entry.line_num, entry.collapsed.clone(), path_match.as_str().to_string()));
         }
     }
 
-    // Second pass: collect used identifiers, skipping import lines and comment lines
-    let used_identifiers = collect_used_identifiers(&lines, &import_line_indices)?;
+    // `import()` and `require()` anywhere in the code, outside comments
+    let code = strip_comments(content);
+    let code_lines: Vec<&str> = code.lines().collect();
+    let dynamic: Vec<(usize, String, String)> = dynamic_imports(&code).into_iter()
+        .map(|(offset, specifier)| {
+            let line_num = code[..offset].matches('\n').count() + 1;
+            (line_num, lines.get(line_num - 1).map_or("", |line| line.trim()).to_string(), specifier.to_string())
+        })
+        .collect();
+
+    // Second pass: collect used identifiers outside import lines and comments
+    let used_identifiers = collect_used_identifiers(&code_lines, &import_line_indices)?;
     
     // Check which imports are unused and broken
     let mut unused_imports = Vec::new();
     let mut broken_imports = Vec::new();
-    let total_imports = imports.len();
+    let total_imports = imports.len() + side_effect_imports.len() + dynamic.len();
     
     for (line_num, import_statement, parsed_import, import_path) in imports {
         // Check for unused imports
         let unused_items = find_unused_items(&parsed_import, &used_identifiers);
         if !unused_items.is_empty() {
             unused_imports.push(UnusedImport {
-                file: path.to_string_lossy().to_string(),
+                file: paths::display(path),
                 line: line_num,
                 import_statement: import_statement.clone(),
                 unused_items,
@@ -163,15 +345,35 @@ fn analyze_file_imports(
             &import_path, 
             line_num, 
             &import_statement, 
-            path_resolver
+            path_resolver,
+            checks.asset_extensions,
         )? {
             broken_imports.push(broken_import);
         }
     }
     
+    // Side-effect and dynamic imports can only be broken
+    for (line_num, import_statement, import_path) in side_effect_imports.into_iter().chain(dynamic) {
+        if let Some(broken_import) = check_import_validity(path, project_root, &import_path, line_num, &import_statement, path_resolver, checks.asset_extensions)? {
+            broken_imports.push(broken_import);
+        }
+    }
+    
+    let type_imports = if checks.type_imports {
+        type_import_issues(path, content, &import_entries)
+    } else {
+        Vec::new()
+    };
+    
+    let import_order = checks.order.and_then(|order| {
+        find_order_issue(&paths::display(path), &lines, &import_entries, order, path_resolver)
+    });
+    
     Ok(FileAnalysis {
         total_imports,
         unused_imports,
         broken_imports,
+        type_imports,
+        import_order,
     })
 }
\ No newline at end of file
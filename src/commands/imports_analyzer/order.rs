@@ -0,0 +1,246 @@
+/// Grouping and ordering of the import block at the top of a file, per `[imports.order]`.
+/// Side-effect imports (`import './styles.css'`) keep their place, since moving them can change
+/// behavior; the imports between them are ordered on their own.
+use crate::config::ImportOrderConfig;
+use super::parser::MultilineImportEntry;
+use super::packages::is_node_builtin;
+use super::resolver::PathAliasResolver;
+use super::types::ImportOrderIssue;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Group {
+    Builtin,
+    External,
+    Alias,
+    Relative,
+    Type,
+}
+
+impl Group {
+    fn key(self) -> &'static str {
+        match self {
+            Group::Builtin => "builtin",
+            Group::External => "external",
+            Group::Alias => "alias",
+            Group::Relative => "relative",
+            Group::Type => "type",
+        }
+    }
+}
+
+struct Statement {
+    /// 1-indexed first line
+    line: usize,
+    text: String,
+    source: String,
+    group: Group,
+    side_effect: bool,
+    /// Blank lines between this statement and the previous one
+    blanks_before: usize,
+}
+
+/// The first ordering problem in the file's leading import block, with the block rewritten
+/// in the configured order. `lines` are the file's lines and `entries` its import statements.
+pub fn find_order_issue(
+    file: &str,
+    lines: &[&str],
+    entries: &[MultilineImportEntry],
+    config: &ImportOrderConfig,
+    resolver: &Option<PathAliasResolver>,
+) -> Option<ImportOrderIssue> {
+    let statements = leading_block(lines, entries, config, resolver)?;
+    if statements.len() < 2 {
+        return None;
+    }
+    let rank = |statement: &Statement| config.groups.iter().position(|group| group == statement.group.key()).unwrap_or(config.groups.len());
+    let sort_key = |statement: &Statement| (rank(statement), if config.alphabetize { statement.source.to_lowercase() } else { String::new() });
+    let expected_blanks = |previous: &Statement, statement: &Statement| {
+        if statement.side_effect || previous.side_effect {
+            None
+        } else {
+            Some(usize::from(config.newlines_between && previous.group != statement.group))
+        }
+    };
+
+    // Order each run between side-effect imports; the side-effect imports keep their blank lines
+    let mut ordered: Vec<&Statement> = Vec::with_capacity(statements.len());
+    let mut run: Vec<&Statement> = Vec::new();
+    let mut blanks_after_side_effect = Vec::new();
+    for (index, statement) in statements.iter().enumerate() {
+        if statement.side_effect {
+            run.sort_by_key(|statement| sort_key(statement));
+            ordered.append(&mut run);
+            ordered.push(statement);
+            blanks_after_side_effect.push(statements.get(index + 1).map_or(0, |next| next.blanks_before));
+        } else {
+            run.push(statement);
+        }
+    }
+    run.sort_by_key(|statement| sort_key(statement));
+    ordered.append(&mut run);
+
+    let mut fix = String::new();
+    let mut side_effects = blanks_after_side_effect.iter();
+    let mut after_side_effect = 0;
+    for (index, statement) in ordered.iter().enumerate() {
+        if index > 0 {
+            let blanks = match expected_blanks(ordered[index - 1], statement) {
+                Some(blanks) => blanks,
+                None if statement.side_effect => statement.blanks_before,
+                None => after_side_effect,
+            };
+            fix.push_str(&"\n".repeat(blanks + 1));
+        }
+        if statement.side_effect {
+            after_side_effect = side_effects.next().copied().unwrap_or(0);
+        }
+        fix.push_str(&statement.text);
+    }
+
+    let (start_line, end_line) = (statements[0].line, entries_end(entries, statements.len()));
+    let block = lines[start_line - 1..end_line].join("\n");
+    if block == fix {
+        return None;
+    }
+
+    let (line, message) = first_violation(&statements, rank, config, expected_blanks);
+    Some(ImportOrderIssue {
+        file: file.to_string(),
+        line,
+        start_line,
+        end_line,
+        message,
+        block,
+        fix,
+    })
+}
+
+fn first_violation(
+    statements: &[Statement],
+    rank: impl Fn(&Statement) -> usize,
+    config: &ImportOrderConfig,
+    expected_blanks: impl Fn(&Statement, &Statement) -> Option<usize>,
+) -> (usize, String) {
+    let pairs = || statements.windows(2).filter(|pair| !pair[0].side_effect && !pair[1].side_effect);
+    if let Some(pair) = pairs().find(|pair| rank(&pair[1]) < rank(&pair[0])) {
+        return (pair[1].line, format!("'{}' ({}) should come before {} imports", pair[1].source, pair[1].group.key(), pair[0].group.key()));
+    }
+    if config.alphabetize {
+        if let Some(pair) = pairs().find(|pair| rank(&pair[1]) == rank(&pair[0]) && pair[1].source.to_lowercase() < pair[0].source.to_lowercase()) {
+            return (pair[1].line, format!("'{}' should come before '{}'", pair[1].source, pair[0].source));
+        }
+    }
+    for pair in statements.windows(2) {
+        let Some(expected) = expected_blanks(&pair[0], &pair[1]) else { continue };
+        if pair[1].blanks_before != expected {
+            let message = if expected == 0 {
+                format!("Remove the blank line before '{}'", pair[1].source)
+            } else if pair[1].blanks_before == 0 {
+                format!("Add a blank line between the {} and {} groups", pair[0].group.key(), pair[1].group.key())
+            } else {
+                "Separate import groups with one blank line".to_string()
+            };
+            return (pair[1].line, message);
+        }
+    }
+    (statements[0].line, "Imports are not organized".to_string())
+}
+
+/// The import statements at the top of the file, up to the first line that is neither an
+/// import nor blank. `None` when one of them cannot be read.
+fn leading_block(
+    lines: &[&str],
+    entries: &[MultilineImportEntry],
+    config: &ImportOrderConfig,
+    resolver: &Option<PathAliasResolver>,
+) -> Option<Vec<Statement>> {
+    let mut statements: Vec<Statement> = Vec::new();
+    let mut previous_end: Option<usize> = None;
+    for entry in entries {
+        let first = *entry.line_indices.first()?;
+        let blanks_before = match previous_end {
+            Some(end) if lines[end + 1..first].iter().all(|line| line.trim().is_empty()) => first - end - 1,
+            Some(_) => break,
+            None => 0,
+        };
+        previous_end = entry.line_indices.last().copied();
+
+        let collapsed = entry.collapsed.trim();
+        let source = import_source(collapsed)?.to_string();
+        let side_effect = collapsed.trim_start_matches("import").trim_start().starts_with(['\'', '"']);
+        let group = if collapsed.starts_with("import type ") && config.groups.iter().any(|group| group == "type") {
+            Group::Type
+        } else {
+            classify(&source, resolver)
+        };
+        statements.push(Statement {
+            line: entry.line_num,
+            text: entry.line_indices.iter().map(|&index| lines[index]).collect::<Vec<_>>().join("\n"),
+            source,
+            group,
+            side_effect,
+            blanks_before,
+        });
+    }
+    Some(statements)
+}
+
+/// Last line of the first `count` entries
+fn entries_end(entries: &[MultilineImportEntry], count: usize) -> usize {
+    entries[count - 1].line_indices.last().map_or(entries[count - 1].line_num, |index| index + 1)
+}
+
+/// The module specifier: the last quoted string of the statement
+fn import_source(statement: &str) -> Option<&str> {
+    let statement = statement.trim_end_matches(';').trim_end();
+    let end = statement.rfind(['\'', '"'])?;
+    let quote = statement[end..].chars().next()?;
+    let start = statement[..end].rfind(quote)?;
+    Some(&statement[start + 1..end])
+}
+
+fn classify(source: &str, resolver: &Option<PathAliasResolver>) -> Group {
+    if source.starts_with('.') || source.starts_with('/') {
+        Group::Relative
+    } else if is_node_builtin(source) {
+        Group::Builtin
+    } else if source.starts_with("@/") || source.starts_with("~/") || source.starts_with('#')
+        || resolver.as_ref().is_some_and(|resolver| resolver.resolve_alias_path(source).is_some()) {
+        Group::Alias
+    } else {
+        Group::External
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::imports_analyzer::parser::preprocess_multiline_imports;
+
+    fn check(content: &str, config: &ImportOrderConfig) -> Option<ImportOrderIssue> {
+        let lines: Vec<&str> = content.lines().collect();
+        find_order_issue("src/a.ts", &lines, &preprocess_multiline_imports(&lines), config, &None)
+    }
+
+    #[test]
+    fn test_orders_groups_and_keeps_side_effects_in_place() {
+        let content = "'use client';\nimport { Button } from '@/components/Button';\nimport {\n  useState,\n} from 'react';\nimport fs from 'node:fs';\nimport './styles.css';\n\nimport { b } from './b';\nimport { a } from './a';\nimport axios from 'axios';\n\nconst x = 1;\n";
+        let issue = check(content, &ImportOrderConfig::default()).unwrap();
+        assert_eq!((issue.start_line, issue.end_line), (2, 11));
+        assert_eq!((issue.line, issue.message.as_str()), (3, "'react' (external) should come before alias imports"));
+        assert_eq!(issue.fix, "import fs from 'node:fs';\n\nimport {\n  useState,\n} from 'react';\n\nimport { Button } from '@/components/Button';\nimport './styles.css';\n\nimport axios from 'axios';\n\nimport { a } from './a';\nimport { b } from './b';");
+    }
+
+    #[test]
+    fn test_blank_lines_and_custom_groups() {
+        let organized = "import path from 'path';\n\nimport React from 'react';\n\nimport { a } from './a';\n";
+        assert!(check(organized, &ImportOrderConfig::default()).is_none());
+
+        let config = ImportOrderConfig { groups: vec!["type".to_string(), "external".to_string()], newlines_between: false, ..Default::default() };
+        let issue = check("import React from 'react';\n\nimport type { FC } from 'react';\n", &config).unwrap();
+        assert_eq!(issue.fix, "import type { FC } from 'react';\nimport React from 'react';");
+
+        let issue = check("import React from 'react';\nimport { a } from './a';\n", &ImportOrderConfig::default()).unwrap();
+        assert_eq!(issue.message, "Add a blank line between the external and relative groups");
+    }
+}
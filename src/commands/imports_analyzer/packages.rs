@@ -0,0 +1,175 @@
+/// Package imports checked against the installed package: its `exports` map when it has one,
+/// otherwise the files and `browser` field mappings of its directory.
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use super::validation::import_exists;
+
+/// Node.js core modules, matched on the first path segment (`fs/promises` is `fs`)
+const NODE_BUILTINS: &[&str] = &[
+    "assert", "async_hooks", "buffer", "child_process", "cluster", "console", "constants", "crypto",
+    "dgram", "diagnostics_channel", "dns", "domain", "events", "fs", "http", "http2", "https", "inspector",
+    "module", "net", "os", "path", "perf_hooks", "process", "punycode", "querystring", "readline", "repl",
+    "stream", "string_decoder", "sys", "timers", "tls", "trace_events", "tty", "url", "util", "v8", "vm",
+    "wasi", "worker_threads", "zlib",
+];
+
+pub fn is_node_builtin(import_path: &str) -> bool {
+    import_path.starts_with("node:") || NODE_BUILTINS.contains(&import_path.split('/').next().unwrap_or(import_path))
+}
+
+/// The package name and the subpath after it: `@scope/pkg/a/b` is `("@scope/pkg", Some("a/b"))`
+pub fn split_package(import_path: &str) -> (&str, Option<&str>) {
+    let segments = if import_path.starts_with('@') { 2 } else { 1 };
+    match import_path.match_indices('/').nth(segments - 1) {
+        Some((index, _)) => (&import_path[..index], Some(&import_path[index + 1..]).filter(|subpath| !subpath.is_empty())),
+        None => (import_path, None),
+    }
+}
+
+/// Why an installed package cannot be imported at `subpath`
+#[derive(Debug, PartialEq)]
+pub enum SubpathError {
+    /// The package has an `exports` map without this subpath; lists the subpaths it exports
+    NotExported(Vec<String>),
+    /// No `exports` map, and no such file
+    FileNotFound,
+}
+
+/// Check an import of `subpath` (or the package root) against the package in `package_dir`.
+/// Any condition of a conditional export counts, as bundlers and Node pick different ones.
+pub fn check_subpath(package_dir: &Path, subpath: Option<&str>) -> Result<(), SubpathError> {
+    // Without a readable manifest there is nothing to check against
+    let Some(manifest) = fs::read_to_string(package_dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok()) else {
+        return Ok(());
+    };
+    let key = subpath.map_or_else(|| ".".to_string(), |subpath| format!("./{}", subpath));
+
+    if let Some(exports) = manifest.get("exports").filter(|exports| !exports.is_null()) {
+        let map = subpath_map(exports);
+        return match export_target(&map, &key) {
+            Some(target) if has_target(target) => Ok(()),
+            _ => Err(SubpathError::NotExported(map.iter()
+                .filter(|(_, target)| has_target(target))
+                .map(|(key, _)| key.clone())
+                .collect())),
+        };
+    }
+
+    let Some(subpath) = subpath else {
+        return Ok(());
+    };
+    if import_exists(&package_dir.join(subpath)) || browser_mapped(&manifest, subpath) {
+        Ok(())
+    } else {
+        Err(SubpathError::FileNotFound)
+    }
+}
+
+/// `exports` as `(subpath, target)` pairs. A string, array or conditions object stands for `.`.
+fn subpath_map(exports: &Value) -> Vec<(String, &Value)> {
+    match exports {
+        Value::Object(map) if map.keys().any(|key| key.starts_with('.')) => {
+            map.iter().map(|(key, target)| (key.clone(), target)).collect()
+        }
+        _ => vec![(".".to_string(), exports)],
+    }
+}
+
+/// The target for `key`: an exact entry, else the most specific `*` pattern or legacy
+/// directory entry (`"./features/"`)
+fn export_target<'a>(map: &[(String, &'a Value)], key: &str) -> Option<&'a Value> {
+    if let Some((_, target)) = map.iter().find(|(entry, _)| entry == key) {
+        return Some(target);
+    }
+    map.iter()
+        .filter_map(|(entry, target)| {
+            let matches = match entry.split_once('*') {
+                Some((prefix, suffix)) => key.len() >= prefix.len() + suffix.len() && key.starts_with(prefix) && key.ends_with(suffix),
+                None => entry.ends_with('/') && key.starts_with(entry.as_str()),
+            };
+            matches.then_some((entry.len(), *target))
+        })
+        .max_by_key(|(specificity, _)| *specificity)
+        .map(|(_, target)| target)
+}
+
+/// Whether some condition leads to a file; `null` blocks a subpath
+fn has_target(target: &Value) -> bool {
+    match target {
+        Value::String(_) => true,
+        Value::Array(targets) => targets.iter().any(has_target),
+        Value::Object(conditions) => conditions.values().any(has_target),
+        _ => false,
+    }
+}
+
+/// Whether the `browser` field maps `subpath` to a file or to `false` (an empty module)
+fn browser_mapped(manifest: &Value, subpath: &str) -> bool {
+    let Some(Value::Object(browser)) = manifest.get("browser") else {
+        return false;
+    };
+    let subpath = subpath.trim_start_matches("./");
+    browser.keys().any(|key| {
+        let key = key.trim_start_matches("./");
+        key == subpath || Path::new(key).with_extension("") == Path::new(subpath)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn package(manifest: Value, files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("package.json"), manifest.to_string()).unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_split_package() {
+        assert_eq!(split_package("lodash"), ("lodash", None));
+        assert_eq!(split_package("lodash/fp"), ("lodash", Some("fp")));
+        assert_eq!(split_package("@firebase/auth/internal"), ("@firebase/auth", Some("internal")));
+        assert!(is_node_builtin("node:fs") && is_node_builtin("fs/promises") && !is_node_builtin("fsevents"));
+    }
+
+    #[test]
+    fn test_exports_map() {
+        let dir = package(json!({
+            "exports": {
+                ".": { "types": "./index.d.ts", "import": "./index.mjs" },
+                "./auth": { "browser": { "import": "./auth/browser.mjs" }, "default": "./auth/index.js" },
+                "./icons/*": "./dist/icons/*.js",
+                "./icons/internal/*": null,
+                "./package.json": "./package.json"
+            }
+        }), &[]);
+        assert_eq!(check_subpath(dir.path(), None), Ok(()));
+        assert_eq!(check_subpath(dir.path(), Some("auth")), Ok(()));
+        assert_eq!(check_subpath(dir.path(), Some("icons/arrow")), Ok(()));
+        assert!(matches!(check_subpath(dir.path(), Some("icons/internal/x")), Err(SubpathError::NotExported(_))));
+        let Err(SubpathError::NotExported(exported)) = check_subpath(dir.path(), Some("dist/auth")) else { panic!() };
+        assert_eq!(exported, vec![".", "./auth", "./icons/*", "./package.json"]);
+
+        let dir = package(json!({ "exports": "./index.js" }), &[]);
+        assert!(check_subpath(dir.path(), None).is_ok() && check_subpath(dir.path(), Some("fp")).is_err());
+    }
+
+    #[test]
+    fn test_files_and_browser_field_without_exports() {
+        let dir = package(json!({ "main": "index.js", "browser": { "./server.js": false } }), &["index.js", "fp/index.js", "map.js"]);
+        assert_eq!(check_subpath(dir.path(), Some("fp")), Ok(()));
+        assert_eq!(check_subpath(dir.path(), Some("map")), Ok(()));
+        assert_eq!(check_subpath(dir.path(), Some("server")), Ok(()));
+        assert_eq!(check_subpath(dir.path(), Some("missing")), Err(SubpathError::FileNotFound));
+    }
+}
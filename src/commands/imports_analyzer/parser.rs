@@ -1,5 +1,7 @@
 use anyhow::Result;
+use regex::Regex;
 use std::collections::HashSet;
+use std::sync::OnceLock;
 
 use super::types::{ImportType, ParsedImport};
 
@@ -74,6 +76,23 @@ pub fn preprocess_multiline_imports(lines: &[&str]) -> Vec<MultilineImportEntry>
     result
 }
 
+static DYNAMIC_IMPORT: OnceLock<Regex> = OnceLock::new();
+
+/// `import('...')` and `require('...')` calls, as in `await import()`, `next/dynamic` and
+/// `React.lazy`, with the offset of each call. Template literals count only without `${}`.
+pub fn dynamic_imports(code: &str) -> Vec<(usize, &str)> {
+    let pattern = DYNAMIC_IMPORT.get_or_init(|| {
+        Regex::new(r#"(?:^|[^\w$.])((?:import|require)\s*\(\s*(?:'([^'\n]*)'|"([^"\n]*)"|`([^`$]*)`)\s*[,)])"#).unwrap()
+    });
+    pattern.captures_iter(code)
+        .filter_map(|caps| {
+            let call = caps.get(1)?;
+            let specifier = caps.get(2).or(caps.get(3)).or(caps.get(4))?;
+            Some((call.start(), specifier.as_str()))
+        })
+        .collect()
+}
+
 fn is_complete_import(line: &str) -> bool {
     let after_import = line.trim_start_matches("import").trim_start();
     // Side-effect import: import './foo'
@@ -249,6 +268,8 @@ fn is_typescript_builtin_type(identifier: &str) -> bool {
     )
 }
 
+/// Identifiers used in `lines` outside the import lines in `skip_indices`. Comments must already be
+/// blanked out of `lines` (`strip_comments`), as for the other per-file checks.
 pub fn collect_used_identifiers(lines: &[&str], skip_indices: &HashSet<usize>) -> Result<HashSet<String>> {
     let mut used_identifiers = HashSet::new();
 
@@ -267,14 +288,7 @@ pub fn collect_used_identifiers(lines: &[&str], skip_indices: &HashSet<usize>) -
             continue;
         }
 
-        let trimmed = line.trim();
-
-        // Skip pure comment lines — identifiers in comments are not real usages
-        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
-            continue;
-        }
-
-        let line_content = trimmed;
+        let line_content = line.trim();
         
         // 1. General identifier usage
         for cap in general_usage.find_iter(line_content) {
@@ -1,7 +1,10 @@
 use colored::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
 
-use super::types::{ImportsReport, ImportsSummary, UnusedImport, BrokenImport, BrokenImportType};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, ListingOptions, Severity};
+use crate::common::report_formatter::{Row, print_listing};
+use crate::commands::history::Metrics;
+use super::types::{ImportsReport, ImportsSummary, UnusedImport, UnusedExport, BrokenImport, BrokenImportType, TypeImportIssue, TypeImportKind};
 
 pub fn calculate_savings(unused_imports: &[UnusedImport]) -> String {
     let total_lines = unused_imports.len();
@@ -12,18 +15,95 @@ pub fn calculate_savings(unused_imports: &[UnusedImport]) -> String {
     }
 }
 
-pub fn print_report(report: &ImportsReport, quiet: bool) {
+impl Metrics for ImportsReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("unused_imports", self.summary.unused_imports as f64),
+            ("broken_imports", self.summary.broken_imports as f64),
+        ]
+    }
+}
+
+impl Annotate for ImportsReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let unused = self.unused_imports.iter().map(|import| {
+            Annotation::new(
+                AnnotationLevel::Warning,
+                &import.file,
+                import.line,
+                "Unused import",
+                format!("Unused: {}", import.unused_items.join(", ")),
+            )
+            .with_rule("SNF-IMP-UNUSED")
+        });
+        let broken = self.broken_imports.iter().map(|import| {
+            Annotation::new(AnnotationLevel::Error, &import.file, import.line, "Broken import", broken_message(import))
+                .with_rule("SNF-IMP-BROKEN")
+        });
+        let exports = self.unused_exports.iter().map(|export| {
+            Annotation::new(AnnotationLevel::Notice, &export.file, export.line, "Unused export", export_message(export))
+                .with_rule("SNF-IMP-UNUSED-EXPORT")
+        });
+        let type_imports = self.type_imports.iter().map(|issue| {
+            Annotation::new(AnnotationLevel::Warning, &issue.file, issue.line, "Type import", type_import_message(issue))
+                .with_rule(issue.kind.rule_id())
+        });
+        let order = self.import_order.iter().map(|issue| {
+            Annotation::new(AnnotationLevel::Warning, &issue.file, issue.line, "Import order", issue.message.clone())
+                .with_rule("SNF-IMP-ORDER")
+        });
+        broken.chain(unused).chain(type_imports).chain(order).chain(exports).collect()
+    }
+}
+
+fn broken_message(import: &BrokenImport) -> String {
+    let reason = match import.error_type {
+        BrokenImportType::FileNotFound => "File not found",
+        BrokenImportType::ModuleNotInstalled => "Module not installed",
+        BrokenImportType::NotExported => "Not exported by the package",
+        BrokenImportType::InvalidPath => "Invalid path",
+    };
+    let mut message = format!("{}: '{}'", reason, import.import_path);
+    if let Some(suggestion) = &import.suggestion {
+        message.push_str(&format!(". {}", suggestion));
+    }
+    message
+}
+
+fn type_import_message(issue: &TypeImportIssue) -> String {
+    match issue.kind {
+        TypeImportKind::TypeOnly => format!("Only used as types: {}; use `import type`", issue.names.join(", ")),
+        TypeImportKind::UsedAsValue => format!("Imported with `import type` but used as values: {}", issue.names.join(", ")),
+    }
+}
+
+fn export_message(export: &UnusedExport) -> String {
+    if export.used_in_file {
+        format!("'{}' is not imported anywhere; drop the export", export.name)
+    } else {
+        format!("'{}' is not imported or used anywhere", export.name)
+    }
+}
+
+pub fn print_report(report: &ImportsReport, listing: ListingOptions, quiet: bool) {
     if !quiet {
-        println!();
-        println!("{}", "📊 Imports Analysis Report".bold().blue());
-        println!("{}", "==========================".blue());
-        println!();
+        output::blank();
+        output::line("📊 Imports Analysis Report".bold().blue());
+        output::line("==========================".blue());
+        output::blank();
     }
     
-    let has_issues = !report.unused_imports.is_empty() || !report.broken_imports.is_empty();
+    let has_issues = !report.unused_imports.is_empty() || !report.broken_imports.is_empty()
+        || !report.unused_exports.is_empty() || !report.type_imports.is_empty() || !report.import_order.is_empty();
     
     if !has_issues {
-        println!("{}", "✅ No import issues found! Your imports are clean.".green());
+        output::line("✅ No import issues found! Your imports are clean.".green());
+        return;
+    }
+    
+    if !listing.is_default() {
+        print_listing(listing_rows(report), listing);
+        print_summary(&report.summary);
         return;
     }
     
@@ -39,64 +119,130 @@ pub fn print_report(report: &ImportsReport, quiet: bool) {
         broken_by_file.entry(import.file.clone()).or_default().push(import);
     }
     
-    // Get all unique files
-    let mut all_files: HashSet<String> = HashSet::new();
+    // Get all unique files, in path order
+    let mut all_files: BTreeSet<String> = BTreeSet::new();
     all_files.extend(unused_by_file.keys().cloned());
     all_files.extend(broken_by_file.keys().cloned());
     
     // Print issues by file
     for file in all_files {
-        println!("{}", file.cyan().bold());
+        output::line(file.cyan().bold());
         
         // Print unused imports for this file
         if let Some(unused_imports) = unused_by_file.get(&file) {
             for import in unused_imports {
-                println!("  Line {}: {}", import.line.to_string().yellow(), import.import_statement.dimmed());
-                println!("    {} Unused: {}", "🚫".red(), import.unused_items.join(", ").red());
-                println!();
+                output::line(format!("  Line {}: {}", import.line.to_string().yellow(), import.import_statement.dimmed()));
+                output::line(format!("    {} Unused: {}", "🚫".red(), import.unused_items.join(", ").red()));
+                output::blank();
             }
         }
         
         // Print broken imports for this file
         if let Some(broken_imports) = broken_by_file.get(&file) {
             for import in broken_imports {
-                println!("  Line {}: {}", import.line.to_string().yellow(), import.import_statement.dimmed());
+                output::line(format!("  Line {}: {}", import.line.to_string().yellow(), import.import_statement.dimmed()));
                 let error_msg = match import.error_type {
                     BrokenImportType::FileNotFound => format!("File not found: {}", import.import_path),
                     BrokenImportType::ModuleNotInstalled => format!("Module not installed: {}", import.import_path),
+                    BrokenImportType::NotExported => format!("Not exported by the package: {}", import.import_path),
                     BrokenImportType::InvalidPath => format!("Invalid path: {}", import.import_path),
                 };
-                println!("    {} {}", "💥".red(), error_msg.red());
+                output::line(format!("    {} {}", "💥".red(), error_msg.red()));
                 if let Some(ref suggestion) = import.suggestion {
-                    println!("    {} {}", "💡".yellow(), suggestion.green());
+                    output::line(format!("    {} {}", "💡".yellow(), suggestion.green()));
                 }
-                println!();
+                output::blank();
             }
         }
     }
     
+    if !report.type_imports.is_empty() {
+        output::line("🏷️  Type-only imports".bold().yellow());
+        for issue in &report.type_imports {
+            output::line(format!("  {}:{} {}", issue.file.cyan(), issue.line, type_import_message(issue).yellow()));
+            output::line(format!("    {} {}", "🔧".dimmed(), issue.fix.replace('\n', "\n       ").green()));
+        }
+        output::line("💡 Run `sniff fix --type-imports` to rewrite them".dimmed());
+        output::blank();
+    }
+    
+    if !report.import_order.is_empty() {
+        output::line("📚 Import order".bold().yellow());
+        for issue in &report.import_order {
+            output::line(format!("  {}:{} {}", issue.file.cyan(), issue.line, issue.message.yellow()));
+        }
+        output::line("💡 Run `sniff fix --organize-imports` to reorder them".dimmed());
+        output::blank();
+    }
+    
+    if !report.unused_exports.is_empty() {
+        output::line("📤 Unused exports".bold().yellow());
+        for export in &report.unused_exports {
+            output::line(format!("  {}:{} {}", export.file.cyan(), export.line, export_message(export).dimmed()));
+        }
+        output::blank();
+    }
+    
     // Print summary
     print_summary(&report.summary);
 }
 
+/// Every import finding for `--group-by` and `--sort`, at the severity of its annotation
+fn listing_rows(report: &ImportsReport) -> Vec<Row> {
+    let row = |file: &str, line: usize, rule: &str, severity: Severity, text: String| Row {
+        path: file.to_string(),
+        line: Some(line),
+        rule: rule.to_string(),
+        severity,
+        lines: None,
+        size_bytes: None,
+        text,
+    };
+    let broken = report.broken_imports.iter().map(|import| {
+        row(&import.file, import.line, "Broken import", Severity::High, broken_message(import))
+    });
+    let unused = report.unused_imports.iter().map(|import| {
+        row(&import.file, import.line, "Unused import", Severity::Medium, format!("Unused: {}", import.unused_items.join(", ")))
+    });
+    let type_imports = report.type_imports.iter().map(|issue| {
+        row(&issue.file, issue.line, "Type import", Severity::Medium, type_import_message(issue))
+    });
+    let order = report.import_order.iter().map(|issue| {
+        row(&issue.file, issue.line, "Import order", Severity::Medium, issue.message.clone())
+    });
+    let exports = report.unused_exports.iter().map(|export| {
+        row(&export.file, export.line, "Unused export", Severity::Low, export_message(export))
+    });
+    broken.chain(unused).chain(type_imports).chain(order).chain(exports).collect()
+}
+
 fn print_summary(summary: &ImportsSummary) {
-    println!("{}", "📈 SUMMARY".bold().white());
-    println!("{}", "─────────".white());
-    println!("  Files scanned: {}", summary.files_scanned);
-    println!("  Total imports: {}", summary.total_imports);
-    println!("  {} {}", "Unused imports:".red(), summary.unused_imports.to_string().red());
-    println!("  {} {}", "Broken imports:".red(), summary.broken_imports.to_string().red());
-    println!("  Potential savings: {}", summary.potential_savings.green());
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Total imports: {}", summary.total_imports));
+    output::line(format!("  {} {}", "Unused imports:".red(), summary.unused_imports.to_string().red()));
+    output::line(format!("  {} {}", "Broken imports:".red(), summary.broken_imports.to_string().red()));
+    if summary.type_imports > 0 {
+        output::line(format!("  {} {}", "Type-only imports:".yellow(), summary.type_imports.to_string().yellow()));
+    }
+    if summary.import_order > 0 {
+        output::line(format!("  {} {}", "Unordered imports:".yellow(), summary.import_order.to_string().yellow()));
+    }
+    if summary.unused_exports > 0 {
+        output::line(format!("  {} {}", "Unused exports:".yellow(), summary.unused_exports.to_string().yellow()));
+    }
+    output::line(format!("  Potential savings: {}", summary.potential_savings.green()));
     
-    println!();
+    output::blank();
     
     if summary.unused_imports > 0 {
-        println!("{}", "💡 TIP: Remove unused imports to reduce bundle size and improve build performance".dimmed());
-        println!("{}", "🔧 Consider using an IDE extension or linter to automatically remove unused imports".dimmed());
+        output::line("💡 TIP: Remove unused imports to reduce bundle size and improve build performance".dimmed());
+        output::line("🔧 Consider using an IDE extension or linter to automatically remove unused imports".dimmed());
     }
     
     if summary.broken_imports > 0 {
-        println!("{}", "🔧 Fix broken imports to resolve compilation errors".yellow());
-        println!("{}", "💡 Check if files were moved/renamed, or if packages need to be installed".dimmed());
+        output::line("🔧 Fix broken imports to resolve compilation errors".yellow());
+        output::line("💡 Check if files were moved/renamed, or if packages need to be installed".dimmed());
     }
 }
\ No newline at end of file
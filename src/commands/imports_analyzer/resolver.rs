@@ -4,12 +4,19 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::commands::imports_analyzer::validation::import_exists;
+use crate::common::source::parse_jsonc;
 
 #[derive(Debug, Deserialize)]
 struct TsConfigCompilerOptions {
     #[serde(rename = "baseUrl")]
     base_url: Option<String>,
     paths: Option<HashMap<String, Vec<String>>>,
+    #[serde(rename = "isolatedModules", default)]
+    isolated_modules: bool,
+    #[serde(rename = "verbatimModuleSyntax", default)]
+    verbatim_module_syntax: bool,
+    #[serde(rename = "preserveValueImports", default)]
+    preserve_value_imports: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -18,6 +25,21 @@ struct TsConfig {
     compiler_options: Option<TsConfigCompilerOptions>,
 }
 
+/// tsconfig.json, or jsconfig.json in JavaScript projects; both allow comments and trailing commas
+fn read_tsconfig(project_root: &Path) -> Option<TsConfig> {
+    let content = ["tsconfig.json", "jsconfig.json"].iter()
+        .find_map(|name| fs::read_to_string(project_root.join(name)).ok())?;
+    serde_json::from_value(parse_jsonc(&content).ok()?).ok()
+}
+
+/// Whether tsconfig.json compiles files in isolation (`isolatedModules`, `verbatimModuleSyntax`
+/// or `preserveValueImports`), where imports only used as types have to say so
+pub fn requires_type_imports(project_root: &Path) -> bool {
+    read_tsconfig(project_root)
+        .and_then(|tsconfig| tsconfig.compiler_options)
+        .is_some_and(|options| options.isolated_modules || options.verbatim_module_syntax || options.preserve_value_imports)
+}
+
 pub struct PathAliasResolver {
     #[allow(dead_code)]
     base_url: PathBuf,
@@ -26,14 +48,7 @@ pub struct PathAliasResolver {
 
 impl PathAliasResolver {
     pub fn from_project_root(project_root: &Path) -> Option<Self> {
-        let tsconfig_path = project_root.join("tsconfig.json");
-        if !tsconfig_path.exists() {
-            return None;
-        }
-        
-        let content = fs::read_to_string(&tsconfig_path).ok()?;
-        let tsconfig: TsConfig = serde_json::from_str(&content).ok()?;
-        
+        let tsconfig = read_tsconfig(project_root)?;
         let compiler_options = tsconfig.compiler_options?;
         let base_url = compiler_options.base_url
             .map(|base| project_root.join(base))
@@ -113,4 +128,23 @@ impl PathAliasResolver {
         None
     }
     
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aliases_from_tsconfig_with_comments_and_jsconfig() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::write(root.join("jsconfig.json"), r#"{ "compilerOptions": { "paths": { "~/*": ["lib/*"] } } }"#).unwrap();
+        let resolver = PathAliasResolver::from_project_root(root).unwrap();
+        assert_eq!(resolver.path_mappings["~/*"], vec![root.join("lib")]);
+
+        fs::write(root.join("tsconfig.json"), "{\n  // aliases\n  \"compilerOptions\": {\n    \"baseUrl\": \".\",\n    \"paths\": { \"@/*\": [\"src/*\"], },\n    \"isolatedModules\": true,\n  },\n}\n").unwrap();
+        let resolver = PathAliasResolver::from_project_root(root).unwrap();
+        assert_eq!(resolver.path_mappings["@/*"], vec![root.join("./src")]);
+        assert!(requires_type_imports(root));
+    }
+}
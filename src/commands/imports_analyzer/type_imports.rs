@@ -0,0 +1,603 @@
+/// Type-only import hygiene for `isolatedModules` / `verbatimModuleSyntax` builds: value
+/// imports only used as types, and `import type` bindings used as values. Positions are
+/// classified by a token scanner that follows annotations, type arguments, aliases and
+/// interfaces; anything it cannot place counts as a value, so a suggestion to switch to
+/// `import type` is only made when every use is clearly a type.
+use std::collections::{HashMap, HashSet};
+
+use crate::common::jsx::{expression_end, jsx_text};
+use crate::common::source::{blank_strings, strip_comments};
+use super::parser::MultilineImportEntry;
+use super::types::{TypeImportIssue, TypeImportKind};
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Usage {
+    value: bool,
+    types: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tok<'a> {
+    Ident(&'a str),
+    Punct(&'a str),
+    /// Numbers and (blanked) string literals
+    Literal,
+}
+
+struct Token<'a> {
+    tok: Tok<'a>,
+    offset: usize,
+    /// A line break comes before this token
+    newline: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Block,
+    Object,
+    ClassBody,
+    Paren,
+    Bracket,
+    Angle,
+    TypeBrace,
+}
+
+struct Frame {
+    kind: Kind,
+    /// Everything inside is a type: type literals, type arguments, function types
+    type_context: bool,
+    /// The expression at this level is a type, after an annotation `:`, `as`, `implements`, ...
+    in_type: bool,
+    /// Inside `type X = ...`, where `=` starts the type instead of ending it
+    alias: bool,
+    /// The next `{` is an interface body or an `export type { ... }` list
+    type_brace_pending: bool,
+    class_pending: bool,
+    /// Closing this frame ends the parent's type (interface bodies)
+    ends_parent_type: bool,
+    /// Open `?` of conditional expressions, whose `:` is not an annotation
+    ternaries: usize,
+}
+
+impl Frame {
+    fn new(kind: Kind, type_context: bool) -> Self {
+        Self { kind, type_context, in_type: false, alias: false, type_brace_pending: false, class_pending: false, ends_parent_type: false, ternaries: 0 }
+    }
+
+    fn is_type(&self) -> bool {
+        self.type_context || self.in_type
+    }
+}
+
+/// Keywords that start an expression, so a `<` after them is JSX rather than type arguments
+const EXPRESSION_KEYWORDS: &[&str] = &["return", "yield", "await", "case", "default", "else", "do", "in", "of", "typeof", "void", "new", "delete", "throw"];
+
+/// Tokens after which a type continues on the next line
+const TYPE_CONTINUES_AFTER: &[&str] = &[":", "|", "&", "=", "=>", ",", "<", "(", "{", "[", ".", "?", "keyof", "typeof", "extends", "implements", "as", "satisfies", "readonly", "infer", "is", "type", "interface"];
+
+/// Tokens that continue a type from the previous line
+const TYPE_CONTINUES_WITH: &[&str] = &["|", "&", ".", "[", "<", ">", "{", "=", "=>", ",", ")", "]", "}", ";", "?", ":", "extends", "implements"];
+
+/// Type-only and used-as-value imports of a TypeScript file. `entries` are its import statements.
+pub fn find_type_import_issues(file: &str, content: &str, entries: &[MultilineImportEntry]) -> Vec<TypeImportIssue> {
+    let jsx = file.ends_with(".tsx") || file.ends_with(".jsx");
+    let clauses: Vec<(&MultilineImportEntry, ImportClause)> = entries.iter()
+        .filter_map(|entry| Some((entry, ImportClause::parse(&entry.collapsed)?)))
+        .collect();
+    if clauses.is_empty() {
+        return Vec::new();
+    }
+
+    let code = strip_comments(content);
+    let template_values = template_identifiers(&code);
+    let mut masked = blank_strings(&code).into_bytes();
+    let mut blank = |from: usize, to: usize| masked[from..to].iter_mut().filter(|byte| **byte != b'\n').for_each(|byte| *byte = b' ');
+    // Import statements are not uses, and JSX text is not code
+    let mut offset = 0;
+    let import_lines: HashSet<usize> = entries.iter().flat_map(|entry| entry.line_indices.iter().copied()).collect();
+    for (index, line) in code.split_inclusive('\n').enumerate() {
+        if import_lines.contains(&index) {
+            blank(offset, offset + line.len());
+        }
+        offset += line.len();
+    }
+    if jsx {
+        for (start, text) in jsx_text(&code) {
+            blank(start, start + text.len());
+        }
+    }
+    let masked = String::from_utf8(masked).unwrap_or_default();
+
+    let names: HashSet<&str> = clauses.iter().flat_map(|(_, clause)| clause.locals()).collect();
+    let mut usage = classify(&masked, &names);
+    for name in template_values.iter().filter(|name| names.contains(name.as_str())) {
+        usage.entry(name.clone()).or_default().value = true;
+    }
+
+    let mut issues = Vec::new();
+    for (entry, clause) in clauses {
+        let used = |name: &str| usage.get(name).copied().unwrap_or_default();
+        let (kind, names, fix) = if clause.type_only {
+            let values: Vec<String> = clause.locals().into_iter().filter(|name| used(name).value).map(str::to_string).collect();
+            (TypeImportKind::UsedAsValue, values.clone(), clause.with_values(&values))
+        } else {
+            let values: Vec<String> = clause.named.iter()
+                .filter(|named| named.type_only && used(&named.local).value)
+                .map(|named| named.local.clone())
+                .collect();
+            if !values.is_empty() {
+                (TypeImportKind::UsedAsValue, values.clone(), clause.with_values(&values))
+            } else {
+                let types: Vec<String> = clause.value_locals().into_iter()
+                    // The classic JSX runtime needs React in scope without naming it
+                    .filter(|name| !(jsx && *name == "React"))
+                    .filter(|name| {
+                        let usage = used(name);
+                        usage.types && !usage.value
+                    })
+                    .map(str::to_string)
+                    .collect();
+                (TypeImportKind::TypeOnly, types.clone(), clause.with_types(&types))
+            }
+        };
+        if names.is_empty() {
+            continue;
+        }
+        issues.push(TypeImportIssue {
+            file: file.to_string(),
+            line: entry.line_num,
+            end_line: entry.line_indices.last().map_or(entry.line_num, |index| index + 1),
+            import_statement: entry.collapsed.clone(),
+            names,
+            kind,
+            fix,
+        });
+    }
+    issues
+}
+
+/// Identifiers in `${...}` of template literals, which blanking the strings would hide
+fn template_identifiers(code: &str) -> HashSet<String> {
+    let bytes = code.as_bytes();
+    let mut names = HashSet::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'`' {
+            let end = crate::common::source::string_end(bytes, i, b'`');
+            let mut j = i + 1;
+            while j + 1 < end {
+                if bytes[j] == b'$' && bytes[j + 1] == b'{' {
+                    if let Some(close) = expression_end(bytes, j + 1) {
+                        names.extend(code[j + 2..close.min(end)].split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).filter(|word| !word.is_empty()).map(str::to_string));
+                        j = close;
+                    }
+                }
+                j += 1;
+            }
+            i = end;
+        }
+        i += 1;
+    }
+    names
+}
+
+fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let bytes = code.as_bytes();
+    let is_ident = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || byte >= 0x80;
+    let mut tokens = Vec::new();
+    let mut newline = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let byte = bytes[i];
+        let start = i;
+        let tok = match byte {
+            b'\n' => {
+                newline = true;
+                i += 1;
+                continue;
+            }
+            _ if byte.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'0'..=b'9' => {
+                while i < bytes.len() && (is_ident(bytes[i]) || bytes[i] == b'.') {
+                    i += 1;
+                }
+                Tok::Literal
+            }
+            _ if is_ident(byte) => {
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+                Tok::Ident(&code[start..i])
+            }
+            b'"' | b'\'' | b'`' => {
+                i = crate::common::source::string_end(bytes, i, byte) + 1;
+                Tok::Literal
+            }
+            _ => {
+                let rest = &code[i..];
+                let len = ["===", "!==", "...", "=>", "==", "!=", "&&", "||", "??"].iter()
+                    .find(|op| rest.starts_with(**op))
+                    .map(|op| op.len())
+                    .or_else(|| (rest.starts_with("?.") && !rest[2..].starts_with(|c: char| c.is_ascii_digit())).then_some(2))
+                    .unwrap_or(1);
+                i += len;
+                Tok::Punct(&code[start..i])
+            }
+        };
+        tokens.push(Token { tok, offset: start, newline });
+        newline = false;
+    }
+    tokens
+}
+
+/// How each of `names` is used in `code`, which must have comments, strings and imports blanked
+fn classify(code: &str, names: &HashSet<&str>) -> HashMap<String, Usage> {
+    let tokens = tokenize(code);
+    let mut usage: HashMap<String, Usage> = HashMap::new();
+    let mut stack = vec![Frame::new(Kind::Block, false)];
+    let mut closed_type_paren = false;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|index| tokens[index].tok);
+        let next = tokens.get(i + 1).map(|token| token.tok);
+        let closed_type_before = std::mem::take(&mut closed_type_paren);
+        let Some(frame) = stack.last_mut() else { break };
+
+        if token.newline && frame.in_type && !frame.type_context {
+            let continues = matches!(prev, Some(Tok::Punct(text) | Tok::Ident(text)) if TYPE_CONTINUES_AFTER.contains(&text))
+                || matches!(token.tok, Tok::Punct(text) | Tok::Ident(text) if TYPE_CONTINUES_WITH.contains(&text));
+            if !continues {
+                frame.in_type = false;
+                frame.alias = false;
+            }
+        }
+
+        match token.tok {
+            Tok::Literal => {}
+            Tok::Ident(word) => {
+                let statement_start = token.newline || matches!(prev, None | Some(Tok::Punct(";" | "{" | "}")) | Some(Tok::Ident("export" | "declare" | "default")));
+                match word {
+                    "type" if !frame.is_type() && statement_start && matches!(next, Some(Tok::Ident(_)))
+                        && matches!(tokens.get(i + 2).map(|token| token.tok), Some(Tok::Punct("=" | "<"))) => {
+                        frame.in_type = true;
+                        frame.alias = true;
+                    }
+                    "type" if !frame.is_type() && prev == Some(Tok::Ident("export")) && next == Some(Tok::Punct("{")) => {
+                        frame.type_brace_pending = true;
+                    }
+                    "interface" if !frame.is_type() && matches!(next, Some(Tok::Ident(_))) => {
+                        frame.in_type = true;
+                        frame.type_brace_pending = true;
+                    }
+                    "class" if !frame.is_type() => frame.class_pending = true,
+                    "implements" | "as" | "satisfies" if !frame.is_type() && prev.is_some() => frame.in_type = true,
+                    _ if names.contains(word) && !matches!(prev, Some(Tok::Punct("." | "?."))) => {
+                        let key = matches!(frame.kind, Kind::Object | Kind::TypeBrace | Kind::ClassBody)
+                            && frame.ternaries == 0
+                            && (next == Some(Tok::Punct(":")) || (next == Some(Tok::Punct("?")) && tokens.get(i + 2).map(|token| token.tok) == Some(Tok::Punct(":"))));
+                        if !key {
+                            let entry = usage.entry(word.to_string()).or_default();
+                            if frame.is_type() {
+                                entry.types = true;
+                            } else {
+                                entry.value = true;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Tok::Punct(punct) => match punct {
+                "(" | "[" => {
+                    let kind = if punct == "(" { Kind::Paren } else { Kind::Bracket };
+                    let type_context = frame.is_type();
+                    stack.push(Frame::new(kind, type_context));
+                }
+                "{" => {
+                    let kind = if frame.type_context {
+                        Kind::TypeBrace
+                    } else if frame.type_brace_pending {
+                        frame.type_brace_pending = false;
+                        Kind::TypeBrace
+                    } else if frame.in_type && matches!(prev, None | Some(Tok::Punct(":" | "|" | "&" | "<" | "," | "=>" | "(" | "=")) | Some(Tok::Ident("keyof" | "readonly"))) {
+                        Kind::TypeBrace
+                    } else {
+                        // A function or class body after a return type or `implements` list
+                        frame.in_type = false;
+                        if std::mem::take(&mut frame.class_pending) {
+                            Kind::ClassBody
+                        } else if matches!(prev, Some(Tok::Punct("=" | "(" | "," | ":" | "?" | "[" | "||" | "&&" | "??" | "..." | "!")) | Some(Tok::Ident("return" | "yield" | "await" | "export"))) {
+                            Kind::Object
+                        } else {
+                            Kind::Block
+                        }
+                    };
+                    let ends_parent_type = kind == Kind::TypeBrace && !frame.type_context && frame.in_type && !frame.alias
+                        && !matches!(prev, Some(Tok::Punct(":" | "|" | "&" | "<" | "," | "=>" | "(" | "=")));
+                    let mut inner = Frame::new(kind, kind == Kind::TypeBrace);
+                    inner.ends_parent_type = ends_parent_type;
+                    stack.push(inner);
+                }
+                ")" | "]" | "}" => {
+                    let kinds: &[Kind] = match punct {
+                        ")" => &[Kind::Paren],
+                        "]" => &[Kind::Bracket],
+                        _ => &[Kind::Block, Kind::Object, Kind::ClassBody, Kind::TypeBrace],
+                    };
+                    if let Some(position) = stack.iter().skip(1).rposition(|frame| kinds.contains(&frame.kind)) {
+                        let closed = stack.split_off(position + 1).swap_remove(0);
+                        closed_type_paren = closed.kind == Kind::Paren && closed.type_context;
+                        if let Some(parent) = stack.last_mut() {
+                            if closed.ends_parent_type {
+                                parent.in_type = false;
+                            }
+                        }
+                    }
+                }
+                "<" if frame.is_type() || (matches!(prev, Some(Tok::Ident(word)) if !EXPRESSION_KEYWORDS.contains(&word)) && type_arguments(&tokens, i)) => {
+                    stack.push(Frame::new(Kind::Angle, true));
+                }
+                ">" if frame.kind == Kind::Angle => {
+                    stack.pop();
+                }
+                "?" if !frame.is_type() => {
+                    let optional = code.as_bytes().get(token.offset + 1) == Some(&b':');
+                    if !optional {
+                        frame.ternaries += 1;
+                    }
+                }
+                ":" if !frame.is_type() => {
+                    if frame.ternaries > 0 {
+                        frame.ternaries -= 1;
+                    } else {
+                        let before_prev = i.checked_sub(2).map(|index| tokens[index].tok);
+                        let annotation = prev == Some(Tok::Punct("?"))
+                            || prev == Some(Tok::Punct(")"))
+                            || matches!(frame.kind, Kind::Paren | Kind::ClassBody)
+                            || (frame.kind == Kind::Block && (matches!(before_prev, Some(Tok::Ident("const" | "let" | "var"))) || matches!(prev, Some(Tok::Punct("}" | "]")))));
+                        if annotation {
+                            frame.in_type = true;
+                        }
+                    }
+                }
+                "=" if frame.in_type && !frame.type_context && !frame.alias => frame.in_type = false,
+                "=>" if frame.in_type && !frame.type_context && !closed_type_before => frame.in_type = false,
+                "," if frame.in_type && !frame.type_context && frame.kind != Kind::Block => frame.in_type = false,
+                ";" => {
+                    frame.in_type = false;
+                    frame.alias = false;
+                    frame.ternaries = 0;
+                }
+                "&&" | "||" | "??" if frame.in_type && !frame.type_context => frame.in_type = false,
+                _ => {}
+            },
+        }
+    }
+    usage
+}
+
+/// Whether the `<` at `start` opens type arguments, as in `useState<User>(`, rather than a comparison
+fn type_arguments(tokens: &[Token], start: usize) -> bool {
+    let mut angles = 0i32;
+    let mut brackets = 0i32;
+    for (j, token) in tokens.iter().enumerate().skip(start).take(256) {
+        let Tok::Punct(punct) = token.tok else { continue };
+        match punct {
+            "<" => angles += 1,
+            ">" => {
+                angles -= 1;
+                if angles == 0 {
+                    return matches!(tokens.get(j + 1).map(|token| token.tok), None | Some(Tok::Punct("(" | ")" | "," | ";" | "." | "[" | "=" | ">" | "|" | "&" | "}" | "]")))
+                        || tokens.get(j + 1).is_some_and(|token| token.newline);
+                }
+            }
+            "(" | "[" | "{" => brackets += 1,
+            ")" | "]" | "}" => {
+                if brackets == 0 {
+                    return false;
+                }
+                brackets -= 1;
+            }
+            ";" if brackets == 0 => return false,
+            "&&" | "||" | "==" | "===" | "!=" | "!==" | "+" | "-" | "*" | "/" | "%" | "!" => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// An import statement's bindings, rendered back after changing their `type` modifiers
+struct ImportClause {
+    type_only: bool,
+    default: Option<String>,
+    namespace: Option<String>,
+    named: Vec<NamedImport>,
+    /// The module specifier with its quotes
+    source: String,
+    semicolon: bool,
+}
+
+#[derive(Clone)]
+struct NamedImport {
+    imported: String,
+    local: String,
+    type_only: bool,
+}
+
+impl ImportClause {
+    fn parse(statement: &str) -> Option<Self> {
+        let rest = statement.trim().strip_prefix("import")?;
+        let (spec, source) = rest.rsplit_once(" from ")?;
+        let source = source.trim();
+        let semicolon = source.ends_with(';');
+        let source = source.trim_end_matches(';').trim().to_string();
+        let spec = spec.trim();
+        let (type_only, spec) = match spec.strip_prefix("type ") {
+            Some(spec) => (true, spec.trim()),
+            None => (false, spec),
+        };
+        let (bindings, named) = match spec.split_once('{') {
+            Some((bindings, named)) => (bindings, named.trim_end().strip_suffix('}')?),
+            None => (spec, ""),
+        };
+
+        let mut clause = Self { type_only, default: None, namespace: None, named: Vec::new(), source, semicolon };
+        for binding in bindings.split(',').map(str::trim).filter(|binding| !binding.is_empty()) {
+            match binding.strip_prefix('*') {
+                Some(namespace) => clause.namespace = Some(namespace.trim().strip_prefix("as")?.trim().to_string()),
+                None => clause.default = Some(binding.to_string()),
+            }
+        }
+        for item in named.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (type_only, item) = match item.strip_prefix("type ") {
+                Some(item) => (true, item.trim()),
+                None => (false, item),
+            };
+            let (imported, local) = match item.split_once(" as ") {
+                Some((imported, local)) => (imported.trim(), local.trim()),
+                None => (item, item),
+            };
+            clause.named.push(NamedImport { imported: imported.to_string(), local: local.to_string(), type_only });
+        }
+        (clause.default.is_some() || clause.namespace.is_some() || !clause.named.is_empty()).then_some(clause)
+    }
+
+    fn locals(&self) -> Vec<&str> {
+        self.default.iter().chain(&self.namespace).map(String::as_str)
+            .chain(self.named.iter().map(|named| named.local.as_str()))
+            .collect()
+    }
+
+    /// Bindings imported as values
+    fn value_locals(&self) -> Vec<&str> {
+        if self.type_only {
+            return Vec::new();
+        }
+        self.default.iter().chain(&self.namespace).map(String::as_str)
+            .chain(self.named.iter().filter(|named| !named.type_only).map(|named| named.local.as_str()))
+            .collect()
+    }
+
+    /// The statement(s) importing `types` as types. A default or namespace import cannot share
+    /// an `import type` with other bindings, so it gets a statement of its own.
+    fn with_types(&self, types: &[String]) -> String {
+        let is_type = |name: &str| types.iter().any(|local| local == name);
+        let named: Vec<NamedImport> = self.named.iter()
+            .map(|named| NamedImport { type_only: named.type_only || is_type(&named.local), ..named.clone() })
+            .collect();
+        let default_type = self.default.as_deref().is_some_and(is_type);
+        let namespace_type = self.namespace.as_deref().is_some_and(is_type);
+        let mut statements = Vec::new();
+
+        // Everything becomes a type: one `import type` per kind of binding
+        if named.iter().all(|named| named.type_only) && (self.default.is_none() || default_type) && (self.namespace.is_none() || namespace_type) {
+            if let Some(default) = &self.default {
+                statements.push(self.render(true, Some(default), None, &[]));
+            }
+            if let Some(namespace) = &self.namespace {
+                statements.push(self.render(true, None, Some(namespace), &[]));
+            }
+            if !named.is_empty() {
+                let named: Vec<NamedImport> = named.iter().map(|named| NamedImport { type_only: false, ..named.clone() }).collect();
+                statements.push(self.render(true, None, None, &named));
+            }
+            return statements.join("\n");
+        }
+
+        if default_type {
+            statements.push(self.render(true, self.default.as_deref(), None, &[]));
+        }
+        if namespace_type {
+            statements.push(self.render(true, None, self.namespace.as_deref(), &[]));
+        }
+        let default = self.default.as_deref().filter(|_| !default_type);
+        let namespace = self.namespace.as_deref().filter(|_| !namespace_type);
+        statements.push(self.render(false, default, namespace, &named));
+        statements.join("\n")
+    }
+
+    /// The statement with `values` imported as values
+    fn with_values(&self, values: &[String]) -> String {
+        let is_value = |name: &str| values.iter().any(|local| local == name);
+        let named: Vec<NamedImport> = self.named.iter()
+            .map(|named| NamedImport { type_only: (named.type_only || self.type_only) && !is_value(&named.local), ..named.clone() })
+            .collect();
+        if self.type_only && named.iter().all(|named| named.type_only) && self.default.is_none() && self.namespace.is_none() {
+            return self.render(true, None, None, &named.into_iter().map(|named| NamedImport { type_only: false, ..named }).collect::<Vec<_>>());
+        }
+        self.render(false, self.default.as_deref(), self.namespace.as_deref(), &named)
+    }
+
+    fn render(&self, type_only: bool, default: Option<&str>, namespace: Option<&str>, named: &[NamedImport]) -> String {
+        let mut bindings: Vec<String> = default.map(str::to_string).into_iter()
+            .chain(namespace.map(|namespace| format!("* as {}", namespace)))
+            .collect();
+        if !named.is_empty() {
+            let items: Vec<String> = named.iter().map(|named| {
+                let name = if named.imported == named.local { named.local.clone() } else { format!("{} as {}", named.imported, named.local) };
+                if named.type_only { format!("type {}", name) } else { name }
+            }).collect();
+            bindings.push(format!("{{ {} }}", items.join(", ")));
+        }
+        format!("import {}{} from {}{}", if type_only { "type " } else { "" }, bindings.join(", "), self.source, if self.semicolon { ";" } else { "" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::imports_analyzer::parser::preprocess_multiline_imports;
+
+    fn issues(file: &str, content: &str) -> Vec<(TypeImportKind, Vec<String>, String)> {
+        let lines: Vec<&str> = content.lines().collect();
+        find_type_import_issues(file, content, &preprocess_multiline_imports(&lines)).into_iter()
+            .map(|issue| (issue.kind, issue.names, issue.fix))
+            .collect()
+    }
+
+    #[test]
+    fn test_value_imports_used_only_as_types() {
+        let content = r#"import { User, fetchUser, Role } from './user';
+import Config from './config';
+import { useState } from 'react';
+
+interface Props extends Base {
+  user: User;
+  roles?: Role[];
+}
+
+export function Profile({ user }: Props): Promise<User> {
+  const [config] = useState<Config | null>(null);
+  const role = user.admin ? Role.Admin : null;
+  return fetchUser(user.id, { label: `${role}` });
+}
+"#;
+        let found = issues("src/Profile.ts", content);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].1, vec!["User"]);
+        assert_eq!(found[0].2, "import { type User, fetchUser, Role } from './user';");
+        assert_eq!(found[1].1, vec!["Config"]);
+        assert_eq!(found[1].2, "import type Config from './config';");
+    }
+
+    #[test]
+    fn test_type_imports_used_as_values() {
+        let content = "import type { Schema, Shape } from './schema';\nimport { type Mode, parse } from './mode';\n\nconst shape: Shape = { a: 1 };\nexport const validate = (input: unknown) => Schema.parse(input) && parse(Mode.Strict);\n";
+        let found = issues("src/validate.ts", content);
+        assert_eq!(found.len(), 2);
+        assert!(matches!(found[0].0, TypeImportKind::UsedAsValue));
+        assert_eq!(found[0].2, "import { Schema, type Shape } from './schema';");
+        assert_eq!(found[1].2, "import { Mode, parse } from './mode';");
+    }
+
+    #[test]
+    fn test_values_in_ternaries_objects_and_jsx_stay_values() {
+        let content = "import { Button, Link, Size } from './ui';\nimport React from 'react';\n\nconst items = { primary: Button };\nexport const Nav = (props: { size: Size }) => (\n  <nav>\n    {props.size ? <Link href=\"/\">Size: Link</Link> : null}\n  </nav>\n);\nconst Comp: React.FC = () => null;\n";
+        let found = issues("src/Nav.tsx", content);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1, vec!["Size"]);
+    }
+}
@@ -4,6 +4,16 @@ use serde::{Deserialize, Serialize};
 pub struct ImportsReport {
     pub unused_imports: Vec<UnusedImport>,
     pub broken_imports: Vec<BrokenImport>,
+    /// Exports no other file imports; reported without affecting the exit code
+    #[serde(default)]
+    pub unused_exports: Vec<UnusedExport>,
+    /// Imports whose `type` modifier is missing or wrong, checked when tsconfig.json enables
+    /// `isolatedModules` or `verbatimModuleSyntax`
+    #[serde(default)]
+    pub type_imports: Vec<TypeImportIssue>,
+    /// Files whose leading imports break `[imports.order]`, when it is enabled
+    #[serde(default)]
+    pub import_order: Vec<ImportOrderIssue>,
     pub summary: ImportsSummary,
 }
 
@@ -16,6 +26,60 @@ pub struct UnusedImport {
     pub import_type: ImportType,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnusedExport {
+    pub file: String,
+    pub line: usize,
+    pub name: String,
+    /// Still referenced inside its own file, so only the `export` can go
+    pub used_in_file: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeImportIssue {
+    pub file: String,
+    pub line: usize,
+    /// Last line of a multi-line import
+    pub end_line: usize,
+    pub import_statement: String,
+    pub names: Vec<String>,
+    pub kind: TypeImportKind,
+    /// The statement(s) to replace it with
+    pub fix: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum TypeImportKind {
+    /// Imported as a value but only used as a type
+    TypeOnly,
+    /// Imported with `import type` but used as a value
+    UsedAsValue,
+}
+
+impl TypeImportKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            TypeImportKind::TypeOnly => "SNF-IMP-TYPE-ONLY",
+            TypeImportKind::UsedAsValue => "SNF-IMP-TYPE-AS-VALUE",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportOrderIssue {
+    pub file: String,
+    /// Line of the first misplaced import
+    pub line: usize,
+    pub message: String,
+    /// The import block's lines
+    pub start_line: usize,
+    pub end_line: usize,
+    pub block: String,
+    /// The block in the configured order
+    pub fix: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BrokenImport {
     pub file: String,
@@ -30,6 +94,8 @@ pub struct BrokenImport {
 pub enum BrokenImportType {
     FileNotFound,
     ModuleNotInstalled,
+    /// The package is installed but its `exports` map does not include the subpath
+    NotExported,
     InvalidPath,
 }
 
@@ -47,6 +113,12 @@ pub struct ImportsSummary {
     pub total_imports: usize,
     pub unused_imports: usize,
     pub broken_imports: usize,
+    #[serde(default)]
+    pub unused_exports: usize,
+    #[serde(default)]
+    pub type_imports: usize,
+    #[serde(default)]
+    pub import_order: usize,
     pub potential_savings: String,
 }
 
@@ -58,8 +130,11 @@ pub struct ParsedImport {
     pub namespace_import: Option<String>,
 }
 
+#[derive(Default)]
 pub struct FileAnalysis {
     pub total_imports: usize,
     pub unused_imports: Vec<UnusedImport>,
     pub broken_imports: Vec<BrokenImport>,
+    pub type_imports: Vec<TypeImportIssue>,
+    pub import_order: Option<ImportOrderIssue>,
 }
\ No newline at end of file
@@ -1,37 +1,62 @@
 use anyhow::Result;
 use std::fs;
 use std::path::{Path, PathBuf};
+use crate::common::paths;
 
 use super::types::{BrokenImport, BrokenImportType};
 use super::resolver::PathAliasResolver;
+use super::packages::{check_subpath, is_node_builtin, split_package, SubpathError};
 
-pub fn import_exists(base_path: &PathBuf) -> bool {
-    // Try the exact path first
+/// Extensions tried, in order, when an import leaves them out
+const SOURCE_EXTENSIONS: [&str; 7] = ["ts", "tsx", "js", "jsx", "mjs", "cjs", "json"];
+
+/// Whether an import without its extension points at a file: the path itself, the path with
+/// a JS/TS extension appended (`./button.styles` is `button.styles.ts`), or an `index` file
+pub fn import_exists(base_path: &Path) -> bool {
     if base_path.exists() {
         return true;
     }
-    
-    // Try common JavaScript/TypeScript file extensions
-    let extensions = [".js", ".ts", ".jsx", ".tsx", ".json", ".mjs", ".cjs"];
-    
-    for ext in extensions {
-        let with_ext = base_path.with_extension(&ext[1..]);
-        if with_ext.exists() {
-            return true;
-        }
+    let Some(file_name) = base_path.file_name().map(|name| name.to_string_lossy().into_owned()) else {
+        return false;
+    };
+    SOURCE_EXTENSIONS.iter()
+        .map(|ext| base_path.with_file_name(format!("{}.{}", file_name, ext)))
+        .chain(SOURCE_EXTENSIONS.iter().map(|ext| base_path.join(format!("index.{}", ext))))
+        .any(|candidate| candidate.is_file())
+}
+
+/// The import without a bundler query or hash (`./icon.svg?react`, `./shader.glsl?raw`)
+fn strip_query(import_path: &str) -> &str {
+    import_path.split(['?', '#']).next().unwrap_or(import_path)
+}
+
+/// Whether `path` exists; an asset (an `asset_extensions` file) must exist exactly as written
+fn target_exists(path: &Path, asset_extensions: &[String]) -> bool {
+    let is_asset = path.extension().is_some_and(|ext| asset_extensions.iter().any(|asset| ext.eq_ignore_ascii_case(asset.as_str())));
+    if is_asset {
+        path.is_file()
+    } else {
+        import_exists(path)
     }
-    
-    // Try index files in the directory
-    if base_path.is_dir() || !base_path.exists() {
-        for ext in extensions {
-            let index_file = base_path.join(format!("index{}", ext));
-            if index_file.exists() {
-                return true;
-            }
-        }
+}
+
+/// The file a local import points at: relative paths and tsconfig aliases, trying
+/// JS/TS extensions and `index` files. Package imports resolve to `None`.
+pub fn resolve_local_import(current_file: &Path, import_path: &str, path_resolver: &Option<PathAliasResolver>) -> Option<PathBuf> {
+    let import_path = strip_query(import_path);
+    let base_path = if import_path.starts_with('.') {
+        resolve_import_path(current_file.parent()?, import_path)
+    } else {
+        path_resolver.as_ref()?.resolve_alias_path(import_path)?
+    };
+    if base_path.is_file() {
+        return Some(base_path);
     }
-    
-    false
+    let file_name = base_path.file_name()?.to_string_lossy().into_owned();
+    SOURCE_EXTENSIONS.iter()
+        .map(|ext| base_path.with_file_name(format!("{}.{}", file_name, ext)))
+        .chain(SOURCE_EXTENSIONS.iter().map(|ext| base_path.join(format!("index.{}", ext))))
+        .find(|candidate| candidate.is_file())
 }
 
 pub fn check_import_validity(
@@ -41,21 +66,24 @@ pub fn check_import_validity(
     line_num: usize,
     import_statement: &str,
     path_resolver: &Option<PathAliasResolver>,
+    asset_extensions: &[String],
 ) -> Result<Option<BrokenImport>> {
+    let full_path = import_path;
+    let import_path = strip_query(import_path);
     // First try to resolve TypeScript path aliases
     if !import_path.starts_with('.') {
         if let Some(resolver) = path_resolver {
             if let Some(resolved_path) = resolver.resolve_alias_path(import_path) {
                 // Path alias pattern matched, check if file exists
-                if import_exists(&resolved_path) {
+                if target_exists(&resolved_path, asset_extensions) {
                     return Ok(None); // Import is valid
                 } else {
                     // Path alias resolved but file doesn't exist
                     return Ok(Some(BrokenImport {
-                        file: current_file.to_string_lossy().to_string(),
+                        file: paths::display(current_file),
                         line: line_num,
                         import_statement: import_statement.to_string(),
-                        import_path: import_path.to_string(),
+                        import_path: full_path.to_string(),
                         error_type: BrokenImportType::FileNotFound,
                         suggestion: Some(format!("Path alias '{}' resolves to '{}' but file not found", import_path, resolved_path.display())),
                     }));
@@ -72,15 +100,15 @@ pub fn check_import_validity(
     let resolved_path = resolve_import_path(current_dir, import_path);
     
     // Check if the resolved path exists (try common extensions)
-    if !import_exists(&resolved_path) {
+    if !target_exists(&resolved_path, asset_extensions) {
         // Try to find a suggestion
         let suggestion = find_similar_file(current_dir, import_path);
         
         return Ok(Some(BrokenImport {
-            file: current_file.to_string_lossy().to_string(),
+            file: paths::display(current_file),
             line: line_num,
             import_statement: import_statement.to_string(),
-            import_path: import_path.to_string(),
+            import_path: full_path.to_string(),
             error_type: BrokenImportType::FileNotFound,
             suggestion,
         }));
@@ -96,34 +124,40 @@ fn check_node_modules_import(
     line_num: usize,
     import_statement: &str,
 ) -> Result<Option<BrokenImport>> {
-    // Extract package name (handle scoped packages like @types/node)
-    let package_name = if import_path.starts_with('@') {
-        // Scoped package: @scope/package or @scope/package/subpath
-        let parts: Vec<&str> = import_path.splitn(3, '/').collect();
-        if parts.len() >= 2 {
-            format!("{}/{}", parts[0], parts[1])
-        } else {
-            import_path.to_string()
-        }
-    } else {
-        // Regular package: package or package/subpath
-        import_path.split('/').next().unwrap_or(import_path).to_string()
-    };
+    if is_node_builtin(import_path) {
+        return Ok(None);
+    }
+    let (package_name, subpath) = split_package(import_path);
+    let broken = |error_type, suggestion| Some(BrokenImport {
+        file: paths::display(current_file),
+        line: line_num,
+        import_statement: import_statement.to_string(),
+        import_path: import_path.to_string(),
+        error_type,
+        suggestion,
+    });
     
     // Check if package exists in node_modules
-    let node_modules_path = project_root.join("node_modules").join(&package_name);
-    if !node_modules_path.exists() {
-        return Ok(Some(BrokenImport {
-            file: current_file.to_string_lossy().to_string(),
-            line: line_num,
-            import_statement: import_statement.to_string(),
-            import_path: import_path.to_string(),
-            error_type: BrokenImportType::ModuleNotInstalled,
-            suggestion: Some(format!("Run: npm install {}", package_name)),
-        }));
+    let package_dir = project_root.join("node_modules").join(package_name);
+    if !package_dir.exists() {
+        return Ok(broken(BrokenImportType::ModuleNotInstalled, Some(format!("Run: npm install {}", package_name))));
     }
     
-    Ok(None)
+    Ok(match check_subpath(&package_dir, subpath) {
+        Ok(()) => None,
+        Err(SubpathError::NotExported(exported)) => {
+            let exported: Vec<String> = exported.iter().take(8)
+                .map(|key| format!("{}{}", package_name, key.trim_start_matches('.')))
+                .collect();
+            let suggestion = if exported.is_empty() {
+                format!("'{}' exports nothing importable", package_name)
+            } else {
+                format!("'{}' exports: {}", package_name, exported.join(", "))
+            };
+            broken(BrokenImportType::NotExported, Some(suggestion))
+        }
+        Err(SubpathError::FileNotFound) => broken(BrokenImportType::FileNotFound, None),
+    })
 }
 
 fn resolve_import_path(current_dir: &Path, import_path: &str) -> PathBuf {
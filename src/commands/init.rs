@@ -0,0 +1,364 @@
+use anyhow::Result;
+use colored::*;
+use dialoguer::{Confirm, Input};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use crate::config::Config;
+use crate::common::{output, ExitCode, CommandOutcome, create_standard_json_output, git};
+use crate::common::report_formatter::prompt_theme;
+use super::context::{self, Framework};
+use super::hooks::{self, HooksReport};
+
+/// Where `sniff init` writes the CI workflow
+const WORKFLOW_PATH: &str = ".github/workflows/sniff-check.yml";
+/// Ignored so cached analysis state and recorded history never end up in a commit
+const IGNORED_DIRS: &[&str] = &[".sniff-cache/", ".sniff/"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackageManager {
+    Npm,
+    Yarn,
+    Pnpm,
+    Bun,
+}
+
+impl PackageManager {
+    /// Lockfiles and the manager that writes them, in the order they are checked
+    const LOCKFILES: &[(&str, PackageManager)] = &[
+        ("pnpm-lock.yaml", PackageManager::Pnpm),
+        ("yarn.lock", PackageManager::Yarn),
+        ("bun.lockb", PackageManager::Bun),
+        ("bun.lock", PackageManager::Bun),
+        ("package-lock.json", PackageManager::Npm),
+    ];
+
+    /// The `packageManager` field of package.json, else the lockfile, else npm
+    pub fn detect(root: &Path) -> PackageManager {
+        let declared = fs::read_to_string(root.join("package.json")).ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|package| package["packageManager"].as_str().map(str::to_string));
+        let from_field = declared.and_then(|field| match field.split('@').next() {
+            Some("pnpm") => Some(PackageManager::Pnpm),
+            Some("yarn") => Some(PackageManager::Yarn),
+            Some("bun") => Some(PackageManager::Bun),
+            Some("npm") => Some(PackageManager::Npm),
+            _ => None,
+        });
+        from_field
+            .or_else(|| Self::LOCKFILES.iter().find(|(file, _)| root.join(file).is_file()).map(|(_, manager)| *manager))
+            .unwrap_or(PackageManager::Npm)
+    }
+
+    fn lockfile(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "package-lock.json",
+            PackageManager::Yarn => "yarn.lock",
+            PackageManager::Pnpm => "pnpm-lock.yaml",
+            PackageManager::Bun => "bun.lockb",
+        }
+    }
+
+    /// The command running a package.json script
+    pub fn run_script(self, script: &str) -> String {
+        match self {
+            PackageManager::Npm => format!("npm run {}", script),
+            PackageManager::Yarn => format!("yarn {}", script),
+            PackageManager::Pnpm => format!("pnpm {}", script),
+            PackageManager::Bun => format!("bun run {}", script),
+        }
+    }
+
+    /// Installs exactly what the lockfile pins
+    fn ci_install(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm ci",
+            PackageManager::Yarn => "yarn install --frozen-lockfile",
+            PackageManager::Pnpm => "pnpm install --frozen-lockfile",
+            PackageManager::Bun => "bun install --frozen-lockfile",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitReport {
+    pub framework: Framework,
+    pub package_manager: PackageManager,
+    pub typescript: bool,
+    pub files: Vec<InitFile>,
+    /// Present when the hooks were installed
+    pub hooks: Option<HooksReport>,
+    /// Steps left out, and why
+    pub skipped: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct InitFile {
+    pub path: PathBuf,
+    pub change: InitChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InitChange {
+    Created,
+    Updated,
+    /// Already there and left as it was
+    Kept,
+}
+
+/// What the wizard asks; `--yes` and non-interactive runs take the defaults
+struct Choices {
+    overwrite_config: bool,
+    threshold: usize,
+    hooks: bool,
+    workflow: bool,
+}
+
+pub async fn run(root: &Path, yes: bool, json: bool, quiet: bool) -> Result<CommandOutcome<InitReport>> {
+    let framework = context::detect_framework(root).await?;
+    let package_manager = PackageManager::detect(root);
+    let typescript = root.join("tsconfig.json").is_file();
+    let existing_config = Config::file_in(root);
+    let in_git = git::top_level(root).is_ok();
+    let workflow_path = root.join(WORKFLOW_PATH);
+
+    if !json && !quiet {
+        output::line("🐽 Setting up sniff".bold().blue());
+        output::line(format!("  Framework:       {:?}", framework));
+        output::line(format!("  Package manager: {:?}", package_manager));
+        output::line(format!("  TypeScript:      {}", if typescript { "yes" } else { "no" }));
+        output::blank();
+    }
+
+    let interactive = !yes && !json && std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+    let defaults = Config::default();
+    let choices = if interactive {
+        let theme = prompt_theme();
+        let overwrite_config = match &existing_config {
+            Some(path) => Confirm::with_theme(&theme)
+                .with_prompt(format!("{} already exists. Replace it?", path.display()))
+                .default(false)
+                .interact()?,
+            None => true,
+        };
+        let threshold = Input::with_theme(&theme)
+            .with_prompt("Line threshold for large files")
+            .default(defaults.large_files.threshold)
+            .interact_text()?;
+        let hooks = in_git && Confirm::with_theme(&theme)
+            .with_prompt("Install git hooks (imports before each commit, deploy before each push)?")
+            .default(true)
+            .interact()?;
+        let workflow = !workflow_path.exists() && Confirm::with_theme(&theme)
+            .with_prompt(format!("Add a GitHub Actions workflow ({})?", WORKFLOW_PATH))
+            .default(true)
+            .interact()?;
+        Choices { overwrite_config, threshold, hooks, workflow }
+    } else {
+        // Nobody to ask: only `--yes` may touch hooks and CI
+        Choices { overwrite_config: existing_config.is_none(), threshold: defaults.large_files.threshold, hooks: yes, workflow: yes }
+    };
+
+    let mut files = Vec::new();
+    let mut skipped = Vec::new();
+
+    let config_path = existing_config.clone().unwrap_or_else(|| root.join("sniff.toml"));
+    let change = if choices.overwrite_config {
+        tailored_config(framework.clone(), package_manager, choices.threshold).save_to_file(&config_path)?;
+        if existing_config.is_some() { InitChange::Updated } else { InitChange::Created }
+    } else {
+        InitChange::Kept
+    };
+    files.push(InitFile { path: config_path, change });
+
+    let gitignore = root.join(".gitignore");
+    files.push(InitFile { change: ignore_state(&gitignore)?, path: gitignore });
+
+    if choices.workflow {
+        if workflow_path.exists() {
+            files.push(InitFile { path: workflow_path, change: InitChange::Kept });
+        } else {
+            fs::create_dir_all(workflow_path.parent().unwrap_or(root))?;
+            fs::write(&workflow_path, workflow(package_manager))?;
+            files.push(InitFile { path: workflow_path, change: InitChange::Created });
+        }
+    } else {
+        skipped.push(format!("GitHub Actions workflow (run `sniff init --yes` to add {})", WORKFLOW_PATH));
+    }
+
+    if !json && !quiet {
+        print_files(&files, root);
+    }
+
+    let hooks = if !choices.hooks {
+        skipped.push("git hooks (run `sniff hooks install`)".to_string());
+        None
+    } else if !in_git {
+        skipped.push("git hooks: not a git repository".to_string());
+        None
+    } else {
+        if !json && !quiet {
+            output::blank();
+        }
+        Some(hooks::install(root, false, json || quiet).await?.report)
+    };
+
+    let report = InitReport { framework, package_manager, typescript, files, hooks, skipped };
+    if json {
+        let response = create_standard_json_output("init", &report, report.files.len(), 0, None);
+        println!("{}", response.to_json_pretty()?);
+    } else if !quiet {
+        print_next_steps(&report);
+    }
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+/// The defaults, adjusted for where the framework writes its build and which lockfile is in use
+pub fn tailored_config(framework: Framework, package_manager: PackageManager, threshold: usize) -> Config {
+    let mut config = Config::default();
+    config.large_files.threshold = threshold;
+
+    // Build output is what `sniff bundle` measures; tool caches are only skipped
+    let (build_dirs, cache_dirs): (&[&str], &[&str]) = match framework {
+        Framework::NextJs => (&[".next", "out"], &[]),
+        Framework::Svelte => (&["build"], &[".svelte-kit"]),
+        Framework::Angular => (&["dist"], &[".angular"]),
+        Framework::React => (&["dist", "build"], &[]),
+        Framework::Vue => (&["dist"], &[]),
+        Framework::Vanilla | Framework::Unknown => (&["dist", "build", "out"], &[]),
+    };
+    config.bundle.build_dirs = build_dirs.iter().map(|dir| dir.to_string()).collect();
+    for dir in build_dirs.iter().chain(cache_dirs) {
+        for excluded in [&mut config.large_files.excluded_dirs, &mut config.memory.excluded_dirs] {
+            if !excluded.iter().any(|existing| existing == dir) {
+                excluded.push(dir.to_string());
+            }
+        }
+    }
+
+    let lockfile = package_manager.lockfile().to_string();
+    if !config.large_files.excluded_files.contains(&lockfile) {
+        config.large_files.excluded_files.push(lockfile);
+    }
+    config
+}
+
+/// Add the cache and history directories to `.gitignore`, creating the file when there is none
+fn ignore_state(gitignore: &Path) -> Result<InitChange> {
+    let existing = fs::read_to_string(gitignore).ok();
+    let ignored = |dir: &str| existing.as_deref().is_some_and(|content| {
+        content.lines().any(|line| line.trim().trim_start_matches('/').trim_end_matches('/') == dir.trim_end_matches('/'))
+    });
+    let missing: Vec<&str> = IGNORED_DIRS.iter().copied().filter(|dir| !ignored(dir)).collect();
+    if missing.is_empty() {
+        return Ok(InitChange::Kept);
+    }
+    let mut content = existing.clone().unwrap_or_default();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    // Projects initialized before `.sniff/` was ignored already have the heading
+    if missing.len() == IGNORED_DIRS.len() {
+        content.push_str("\n# sniff\n");
+    }
+    for dir in missing {
+        content.push_str(dir);
+        content.push('\n');
+    }
+    fs::write(gitignore, content.trim_start())?;
+    Ok(if existing.is_some() { InitChange::Updated } else { InitChange::Created })
+}
+
+/// A workflow running the deploy pipeline on pull requests, with PR annotations
+fn workflow(package_manager: PackageManager) -> String {
+    let setup = match package_manager {
+        PackageManager::Pnpm => "      - uses: pnpm/action-setup@v4\n",
+        PackageManager::Bun => "      - uses: oven-sh/setup-bun@v2\n",
+        PackageManager::Npm | PackageManager::Yarn => "",
+    };
+    let cache = match package_manager {
+        PackageManager::Npm => "\n          cache: npm",
+        PackageManager::Yarn => "\n          cache: yarn",
+        PackageManager::Pnpm => "\n          cache: pnpm",
+        PackageManager::Bun => "",
+    };
+    format!(
+        r#"name: sniff
+
+on:
+  pull_request:
+  push:
+    branches: [main]
+
+jobs:
+  sniff:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+{setup}      - uses: actions/setup-node@v4
+        with:
+          node-version: 20{cache}
+      - run: {install}
+      - run: npm install --global sniff-check
+      - run: sniff deploy --format github
+"#,
+        setup = setup,
+        cache = cache,
+        install = package_manager.ci_install(),
+    )
+}
+
+fn print_files(files: &[InitFile], root: &Path) {
+    for file in files {
+        let status = match file.change {
+            InitChange::Created => "created".green(),
+            InitChange::Updated => "updated".green(),
+            InitChange::Kept => "kept".normal(),
+        };
+        output::line(format!("  {:<8} {}", status, file.path.strip_prefix(root).unwrap_or(&file.path).display()));
+    }
+}
+
+fn print_next_steps(report: &InitReport) {
+    for step in &report.skipped {
+        output::line(format!("  {} {}", "skipped".dimmed(), step.dimmed()));
+    }
+    output::blank();
+    output::line("✅ sniff is set up. Next:".bold().green());
+    output::line("  sniff deploy          # run the pre-deployment pipeline");
+    output::line("  sniff config doctor   # review the settings");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_manager_prefers_the_declared_one() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Npm);
+
+        fs::write(dir.path().join("yarn.lock"), "").unwrap();
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Yarn);
+
+        fs::write(dir.path().join("package.json"), r#"{"packageManager": "pnpm@9.1.0"}"#).unwrap();
+        assert_eq!(PackageManager::detect(dir.path()), PackageManager::Pnpm);
+    }
+
+    #[test]
+    fn test_gitignore_gets_the_cache_and_history_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let gitignore = dir.path().join(".gitignore");
+        fs::write(&gitignore, "node_modules").unwrap();
+
+        assert_eq!(ignore_state(&gitignore).unwrap(), InitChange::Updated);
+        assert_eq!(ignore_state(&gitignore).unwrap(), InitChange::Kept);
+        assert_eq!(fs::read_to_string(&gitignore).unwrap(), "node_modules\n\n# sniff\n.sniff-cache/\n.sniff/\n");
+
+        fs::write(&gitignore, "# sniff\n/.sniff-cache\n").unwrap();
+        assert_eq!(ignore_state(&gitignore).unwrap(), InitChange::Updated);
+        assert_eq!(fs::read_to_string(&gitignore).unwrap(), "# sniff\n/.sniff-cache\n.sniff/\n");
+    }
+}
@@ -3,21 +3,45 @@ use colored::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use crate::utils::FileUtils;
-use crate::config::Config;
-use crate::common::{ExitCode, check_failure_threshold, init_command, complete_command, create_standard_json_output, output_result, OptimizedFileWalker, PerformanceMonitor, count_lines_optimized};
+use crate::config::{Config, CountMode};
+use crate::common::function_parser::{find_functions, FunctionKind};
+use crate::commands::history::Metrics;
+use crate::common::ndjson::{self, LevelCounts};
+use crate::common::report_formatter::{self, Row, print_listing};
+use crate::common::{output, AnalysisContext, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ExitCode, CommandOutcome, Ignores, ListingOptions, ScanTarget, check_failure_threshold, create_annotated_json_output, output_result, OptimizedFileWalker, PerformanceMonitor};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LargeFileReport {
     pub files: Vec<LargeFile>,
+    pub functions: Vec<LargeFunction>,
+    /// Lines and large files per top-level directory, most lines first
+    #[serde(default)]
+    pub directories: Vec<DirectoryStats>,
     pub summary: Summary,
 }
 
+/// One top-level directory of the scanned paths, with every scanned file below it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryStats {
+    /// Relative to the project root; `.` for files directly in the root
+    pub path: String,
+    pub files: usize,
+    pub lines: usize,
+    pub large_files: usize,
+    /// Lines in the directory's large files
+    pub large_file_lines: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LargeFile {
     pub path: String,
     pub lines: usize,
+    pub threshold: usize,
     pub size_bytes: u64,
     pub size_kb: f64,
     pub file_type: FileType,
@@ -25,6 +49,16 @@ pub struct LargeFile {
     pub suggestions: Vec<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LargeFunction {
+    pub path: String,
+    pub name: String,
+    pub kind: FunctionKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub lines: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum FileType {
     ApiRoute,
@@ -43,6 +77,28 @@ pub enum FileType {
     Other,
 }
 
+impl FileType {
+    /// Key used for this type in `[large_files.file_type_thresholds]`
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            FileType::ApiRoute => "api_route",
+            FileType::ServerComponent => "server_component",
+            FileType::ClientComponent => "client_component",
+            FileType::CustomHook => "custom_hook",
+            FileType::TypeDefinition => "type_definition",
+            FileType::Middleware => "middleware",
+            FileType::Layout => "layout",
+            FileType::Page => "page",
+            FileType::Component => "component",
+            FileType::Service => "service",
+            FileType::Util => "util",
+            FileType::Config => "config",
+            FileType::Test => "test",
+            FileType::Other => "other",
+        }
+    }
+}
+
 impl std::fmt::Display for FileType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let display = match self {
@@ -76,117 +132,381 @@ pub enum Severity {
 pub struct Summary {
     pub total_files_scanned: usize,
     pub large_files_found: usize,
+    pub large_functions_found: usize,
     pub warnings: usize,
     pub errors: usize,
     pub critical: usize,
+    /// Generated files left out of the scan, see `--include-generated`
+    #[serde(default)]
+    pub generated_files_skipped: usize,
+}
+
+/// How the text report of `sniff large` lists what it found
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LargeView {
+    pub listing: ListingOptions,
+    /// Show lines per top-level directory instead of single files
+    pub by_dir: bool,
+    /// Leave out directories with fewer lines, also from the JSON report
+    pub min_dir_lines: usize,
 }
 
-pub async fn run(threshold: usize, json: bool, quiet: bool) -> Result<()> {
+pub async fn run(target: &ScanTarget, threshold: usize, function_threshold: Option<usize>, include_generated: bool, view: LargeView, json: bool, quiet: bool) -> Result<CommandOutcome<LargeFileReport>> {
     let start_time = std::time::Instant::now();
-    let suppress = quiet || json;
-    init_command("large file", suppress);
+    tracing::info!("Running large file analysis");
 
     // Load config for configurable thresholds
-    let config = Config::load().unwrap_or_default();
+    let config = target.config()?;
 
-    // Use config's threshold if CLI uses the default value (100)
-    let effective_threshold = if threshold == 100 {
-        config.large_files.threshold
-    } else {
-        threshold
-    };
-
-    let report = scan_large_files_with_config(effective_threshold, &config, suppress)?;
+    let mut outcome = check(target, threshold, function_threshold, include_generated, &config)?;
+    outcome.report.directories.retain(|dir| dir.lines >= view.min_dir_lines);
+    let report = &outcome.report;
     let duration_ms = start_time.elapsed().as_millis() as u64;
 
-    let response = create_standard_json_output(
+    let response = create_annotated_json_output(
         "large",
-        &report,
+        report,
         report.summary.total_files_scanned,
         report.summary.large_files_found,
         Some(duration_ms),
     );
 
-    output_result(&response, json, quiet, |report, quiet| print_report(report, &config, quiet))?;
+    output_result(&response, json, quiet, |report, quiet| print_report(report, &config, view, quiet))?;
 
-    complete_command("large file", report.summary.large_files_found == 0, suppress);
-    check_failure_threshold(report.summary.large_files_found > 0, ExitCode::ThresholdExceeded);
-    
-    Ok(())
+    tracing::info!(clean = report.summary.large_files_found == 0, "large file analysis completed");
+
+    Ok(outcome)
+}
+
+/// `--format ndjson`: print each file's findings as soon as it is scanned. Only the counts
+/// the exit status depends on are kept, not the findings
+pub fn stream(target: &ScanTarget, threshold: usize, function_threshold: Option<usize>, include_generated: bool, config: &Config, counts: &Mutex<LevelCounts>) -> Result<ExitCode> {
+    let files = target_files(target, &OptimizedFileWalker::new().max_depth(10))?;
+    let settings = PackageThresholds::new(target, &files, config, threshold, function_threshold, include_generated)?;
+    let ignores = target.ignores()?;
+    let large_files = AtomicUsize::new(0);
+    ndjson::stream_files(&files, counts, |path| {
+        let (thresholds, config) = settings.for_file(path, config);
+        let Some((_, file, functions)) = analyze_file(&target.context, path, thresholds, config).map(|scan| without_ignored(&ignores, scan)) else {
+            return Ok(Vec::new());
+        };
+        large_files.fetch_add(usize::from(file.is_some()), Ordering::Relaxed);
+        let summary = create_summary(1, file.as_slice(), &functions);
+        Ok(LargeFileReport { files: file.into_iter().collect(), functions, directories: Vec::new(), summary }.annotations())
+    })?;
+    Ok(check_failure_threshold(large_files.into_inner() > 0, ExitCode::ThresholdExceeded))
+}
+
+/// Scan for large files and decide the exit status, without printing a report
+pub fn check(target: &ScanTarget, threshold: usize, function_threshold: Option<usize>, include_generated: bool, config: &Config) -> Result<CommandOutcome<LargeFileReport>> {
+    let report = scan_large_files_with_config(target, threshold, function_threshold, include_generated, config)?;
+    let exit_code = check_failure_threshold(report.summary.large_files_found > 0, ExitCode::ThresholdExceeded);
+
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+/// The findings for one file's `content`, such as an editor buffer, with the config's thresholds
+pub fn check_source(path: &Path, content: &str, config: &Config) -> LargeFileReport {
+    let thresholds = Thresholds::new(config, 100, None, false);
+    if !thresholds.include_generated && (is_generated_path(path) || has_generated_header(content)) {
+        let summary = Summary { generated_files_skipped: 1, ..create_summary(0, &[], &[]) };
+        return LargeFileReport { files: Vec::new(), functions: Vec::new(), directories: Vec::new(), summary };
+    }
+    let line_count = thresholds.count_mode.lines(content);
+    let file_type = determine_file_type(path);
+    let type_threshold = thresholds.types.get(file_type.config_key()).copied();
+    let files: Vec<LargeFile> = (line_count >= type_threshold.unwrap_or(thresholds.file))
+        .then(|| create_large_file_info(path, file_type, line_count, content.len() as u64, type_threshold, thresholds.file, config))
+        .into_iter()
+        .collect();
+    let functions = large_functions_in(content, &paths::display(path), thresholds.function);
+    let summary = create_summary(1, &files, &functions);
+    LargeFileReport { files, functions, directories: Vec::new(), summary }
+}
+
+/// The line limits that apply to a file
+struct Thresholds {
+    file: usize,
+    types: HashMap<String, usize>,
+    function: usize,
+    include_generated: bool,
+    count_mode: CountMode,
+}
+
+impl Thresholds {
+    fn new(config: &Config, threshold: usize, function_threshold: Option<usize>, include_generated: bool) -> Self {
+        // Use config's thresholds if CLI uses the default value (100); an explicit
+        // --threshold applies to every file type
+        let (file, types) = if threshold == 100 {
+            (config.large_files.threshold, config.large_files.file_type_thresholds.clone())
+        } else {
+            (threshold, HashMap::new())
+        };
+        let function = function_threshold.unwrap_or(config.large_files.function_threshold);
+        let include_generated = include_generated || config.large_files.include_generated;
+        Self { file, types, function, include_generated, count_mode: config.large_files.count_mode }
+    }
 }
 
-fn scan_large_files_with_config(threshold: usize, config: &Config, quiet: bool) -> Result<LargeFileReport> {
+fn scan_large_files_with_config(
+    target: &ScanTarget,
+    threshold: usize,
+    function_threshold: Option<usize>,
+    include_generated: bool,
+    config: &Config,
+) -> Result<LargeFileReport> {
     let mut perf_monitor = PerformanceMonitor::new();
-    let current_dir = std::env::current_dir()?;
     
     // Use optimized file walker for better performance
     let walker = OptimizedFileWalker::new()
         .max_depth(10) // Reasonable depth limit
         .parallel_threshold(20); // Use parallel processing for 20+ files
     
-    let files = walker.walk_with_extensions(&current_dir, &["ts", "tsx", "js", "jsx"]);
+    let files = target_files(target, &walker)?;
+    let settings = PackageThresholds::new(target, &files, config, threshold, function_threshold, include_generated)?;
     
-    if !quiet {
-        println!("🔍 Scanning {} files for large file detection...", files.len());
-    }
+    tracing::debug!(files = files.len(), "Scanning files for large file detection");
     
     perf_monitor.checkpoint("File discovery");
-    let total_files = files.len();
-    
-    if !quiet {
-        println!("📊 Analyzing {} files for size thresholds...", total_files);
-    }
     
     // Use optimized parallel processing with performance monitoring
-    let large_file_options: Vec<Option<LargeFile>> = walker.process_files_parallel(
+    let results: Vec<Option<FileScan>> = walker.process_files_parallel(
         &files,
         |path| {
-            // Use optimized line counting
-            let line_count = count_lines_optimized(path).unwrap_or(0);
-            if line_count >= threshold {
-                let size_bytes = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
-                Some(create_large_file_info(path, line_count, size_bytes, config))
-            } else {
-                None
-            }
+            let (thresholds, config) = settings.for_file(path, config);
+            analyze_file(&target.context, path, thresholds, config)
         }
     );
     
-    if !quiet {
-        println!("✅ File analysis completed");
+    tracing::debug!("File analysis completed");
+    
+    let mut large_files = Vec::new();
+    let mut large_functions = Vec::new();
+    let mut directories: HashMap<String, DirectoryStats> = HashMap::new();
+    let mut generated_files_skipped = 0;
+    let ignores = target.ignores()?;
+    for (path, result) in files.iter().zip(results) {
+        let Some((lines, large_file, functions)) = result.map(|scan| without_ignored(&ignores, scan)) else {
+            generated_files_skipped += 1;
+            continue;
+        };
+        let dir = top_level_dir(target, path);
+        let stats = directories.entry(dir.clone()).or_insert_with(|| DirectoryStats { path: dir, files: 0, lines: 0, large_files: 0, large_file_lines: 0 });
+        stats.files += 1;
+        stats.lines += lines;
+        if large_file.is_some() {
+            stats.large_files += 1;
+            stats.large_file_lines += lines;
+        }
+        large_files.extend(large_file);
+        large_functions.extend(functions);
     }
-    
-    let large_files: Vec<LargeFile> = large_file_options.into_iter().flatten().collect();
+    let mut directories: Vec<DirectoryStats> = directories.into_values().collect();
+    directories.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.path.cmp(&b.path)));
+    large_functions.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.path.cmp(&b.path)));
     
     perf_monitor.checkpoint("File analysis");
     
-    let summary = create_summary(total_files, &large_files);
+    let total_files = files.len() - generated_files_skipped;
+    let summary = Summary { generated_files_skipped, ..create_summary(total_files, &large_files, &large_functions) };
     perf_monitor.checkpoint("Summary creation");
     
-    // Optional performance reporting for debugging
-    if std::env::var("SNIFF_PERF_DEBUG").is_ok() && !quiet {
-        eprintln!("\n--- Performance Report ---");
-        perf_monitor.print_report();
-        eprintln!("Files processed: {total_files}");
-        eprintln!("Large files found: {}", large_files.len());
-    }
+    perf_monitor.log_report();
+    tracing::debug!(files = total_files, large_files = large_files.len(), "Large file scan finished");
     
     Ok(LargeFileReport {
         files: large_files,
+        functions: large_functions,
+        directories,
         summary,
     })
 }
 
-fn create_large_file_info(path: &Path, lines: usize, size_bytes: u64, config: &Config) -> LargeFile {
-    let file_type = determine_file_type(path);
-    let severity = determine_severity_with_config(lines, config);
+/// A file's scan without the findings `[[ignore]]` entries cover; its lines still count
+fn without_ignored(ignores: &Ignores, (lines, file, mut functions): FileScan) -> FileScan {
+    ignores.retain(&mut functions, |function| ("SNF-LARGE-002", &function.path));
+    (lines, file.filter(|file| !ignores.ignores("SNF-LARGE-001", &file.path)), functions)
+}
+
+/// The first directory of `path` below the scanned path holding it, relative to the project
+/// root: `src/components` for `sniff large src`, `src` for the whole project. Files directly in
+/// the scanned path count towards it
+fn top_level_dir(target: &ScanTarget, path: &Path) -> String {
+    let base = target.paths.iter().find(|base| path.starts_with(base)).unwrap_or(&target.root);
+    let dir = match path.strip_prefix(base).ok().and_then(|relative| relative.parent()?.components().next()) {
+        Some(first) => base.join(first),
+        None => base.clone(),
+    };
+    match dir.strip_prefix(&target.root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => FileUtils::slash_path(relative),
+        _ => ".".to_string(),
+    }
+}
+
+/// The TS/JS files `sniff large` scans under the target's paths
+fn target_files(target: &ScanTarget, walker: &OptimizedFileWalker) -> Result<Vec<PathBuf>> {
+    let mut files: Vec<_> = target.paths.iter()
+        .flat_map(|path| walker.walk_with_extensions(path, &["ts", "tsx", "js", "jsx"]))
+        .collect();
+    let scanner = FileScanner::for_command(target, "large")?;
+    files.retain(|path| scanner.is_target_file(path, target));
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Packages with their own config file (e.g. a stricter design system) use their own limits
+struct PackageThresholds {
+    base: Thresholds,
+    packages: Vec<(PathBuf, Thresholds, Config)>,
+}
+
+impl PackageThresholds {
+    fn new(target: &ScanTarget, files: &[PathBuf], config: &Config, threshold: usize, function_threshold: Option<usize>, include_generated: bool) -> Result<Self> {
+        let packages = Config::packages_below(target.config_dir(), files)?
+            .into_iter()
+            .map(|(dir, config)| (dir, Thresholds::new(&config, threshold, function_threshold, include_generated), config))
+            .collect();
+        Ok(Self { base: Thresholds::new(config, threshold, function_threshold, include_generated), packages })
+    }
+
+    fn for_file<'a>(&'a self, path: &Path, config: &'a Config) -> (&'a Thresholds, &'a Config) {
+        self.packages.iter()
+            .find(|(dir, _, _)| path.starts_with(dir))
+            .map_or((&self.base, config), |(_, thresholds, config)| (thresholds, config))
+    }
+}
+
+/// A file's line count, and the file and its functions where they are over the thresholds
+type FileScan = (usize, Option<LargeFile>, Vec<LargeFunction>);
+
+/// `None` for a generated file that is not scanned
+fn analyze_file(context: &AnalysisContext, path: &Path, thresholds: &Thresholds, config: &Config) -> Option<FileScan> {
+    if !thresholds.include_generated && is_generated(path) {
+        tracing::debug!(path = %path.display(), "Skipping generated file");
+        return None;
+    }
+    let line_count = match thresholds.count_mode {
+        // Use optimized line counting
+        CountMode::Raw => context.line_count(path).unwrap_or(0),
+        CountMode::Code => context.read_source(path).ok().flatten().map_or(0, |content| CountMode::Code.lines(&content)),
+    };
+    // Files below every applicable threshold are skipped without classifying them
+    let min_threshold = thresholds.types.values().copied().fold(thresholds.file, usize::min);
+    let large_file = if line_count >= min_threshold {
+        let file_type = determine_file_type(path);
+        let type_threshold = thresholds.types.get(file_type.config_key()).copied();
+        if line_count >= type_threshold.unwrap_or(thresholds.file) {
+            let size_bytes = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            Some(create_large_file_info(path, file_type, line_count, size_bytes, type_threshold, thresholds.file, config))
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+    // A file shorter than the function threshold cannot contain a large function; functions
+    // are measured in raw lines, so a count of code lines does not tell
+    let large_functions = if line_count >= thresholds.function || thresholds.count_mode == CountMode::Code {
+        find_large_functions(context, path, thresholds.function)
+    } else {
+        Vec::new()
+    };
+    Some((line_count, large_file, large_functions))
+}
+
+/// How much of a file is searched for a header saying it is generated
+const GENERATED_HEADER_BYTES: u64 = 1024;
+
+/// Whether code-gen tools wrote the file, by its name or its header
+fn is_generated(path: &Path) -> bool {
+    if is_generated_path(path) {
+        return true;
+    }
+    let mut head = Vec::new();
+    let read = fs::File::open(path).and_then(|file| file.take(GENERATED_HEADER_BYTES).read_to_end(&mut head));
+    read.is_ok() && has_generated_header(&String::from_utf8_lossy(&head))
+}
+
+/// Declaration files, `*.generated.ts`/`*.gen.ts`, Relay's `__generated__/` and GraphQL
+/// codegen output (`*.graphql.ts`, `generated/graphql.ts`, the client preset's `gql/`)
+fn is_generated_path(path: &Path) -> bool {
+    let name = path.file_name().map(|name| name.to_string_lossy().to_lowercase()).unwrap_or_default();
+    if [".d.ts", ".d.mts", ".d.cts"].iter().any(|suffix| name.ends_with(suffix))
+        || name.contains(".generated.")
+        || name.contains(".gen.")
+        || name.contains(".graphql.")
+    {
+        return true;
+    }
+    let dirs: Vec<String> = path.parent().into_iter()
+        .flat_map(|dir| dir.components())
+        .map(|component| component.as_os_str().to_string_lossy().to_lowercase())
+        .collect();
+    let stem = name.split('.').next().unwrap_or_default();
+    dirs.iter().any(|dir| dir == "__generated__")
+        || (dirs.last().is_some_and(|dir| dir == "generated") && stem == "graphql")
+        || (dirs.last().is_some_and(|dir| dir == "gql") && ["gql", "graphql", "fragment-masking"].contains(&stem))
+}
+
+/// Markers code generators put in the comments a file starts with: `@generated`, "DO NOT
+/// EDIT", "auto-generated" or a blanket `eslint-disable` without a rule list
+fn has_generated_header(content: &str) -> bool {
+    content.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || ["//", "/*", "*", "#!"].iter().any(|start| line.starts_with(start)))
+        .take(20)
+        .any(|line| {
+            let lower = line.to_lowercase();
+            let comment = lower.trim_start_matches(['/', '*', ' ']).trim_end_matches(['/', '*', ' ']);
+            comment == "eslint-disable"
+                || ["@generated", "do not edit", "auto-generated", "autogenerated", "code generated by", "this file was generated", "this file is generated"]
+                    .iter()
+                    .any(|marker| lower.contains(marker))
+        })
+}
+
+fn find_large_functions(context: &AnalysisContext, path: &Path, function_threshold: usize) -> Vec<LargeFunction> {
+    // Oversized and binary files are generated, not hand-written functions
+    let Ok(Some(content)) = context.read_source(path) else {
+        return Vec::new();
+    };
+    large_functions_in(&content, &paths::display(path), function_threshold)
+}
+
+fn large_functions_in(content: &str, relative_path: &str, function_threshold: usize) -> Vec<LargeFunction> {
+    find_functions(content)
+        .into_iter()
+        .filter(|function| function.lines >= function_threshold)
+        .map(|function| LargeFunction {
+            path: relative_path.to_string(),
+            name: function.name,
+            kind: function.kind,
+            start_line: function.start_line,
+            end_line: function.end_line,
+            lines: function.lines,
+        })
+        .collect()
+}
+
+fn create_large_file_info(
+    path: &Path,
+    file_type: FileType,
+    lines: usize,
+    size_bytes: u64,
+    type_threshold: Option<usize>,
+    base_threshold: usize,
+    config: &Config,
+) -> LargeFile {
+    let severity = determine_severity_with_config(lines, type_threshold, config);
     let suggestions = generate_suggestions(&file_type, lines);
     
     let size_kb = size_bytes as f64 / 1024.0;
     
     LargeFile {
-        path: FileUtils::get_relative_path(path),
+        path: paths::display(path),
         lines,
+        threshold: type_threshold.unwrap_or(base_threshold),
         size_bytes,
         size_kb,
         file_type,
@@ -201,11 +521,10 @@ fn determine_file_type(path: &Path) -> FileType {
     let file_name = path.file_name().unwrap_or_default().to_string_lossy();
     
     // Check file content for client directive (simplified check)
-    let has_use_client = if let Ok(content) = fs::read_to_string(path) {
-        content.lines().take(10).any(|line| line.trim().starts_with("'use client'") || line.trim().starts_with("\"use client\""))
-    } else {
-        false
-    };
+    let has_use_client = fs::File::open(path).is_ok_and(|file| {
+        BufReader::new(file).lines().take(10).map_while(Result::ok)
+            .any(|line| line.trim().starts_with("'use client'") || line.trim().starts_with("\"use client\""))
+    });
     
     // Specific Next.js patterns
     if file_name == "middleware.ts" || file_name == "middleware.js" {
@@ -249,12 +568,22 @@ fn determine_file_type(path: &Path) -> FileType {
     }
 }
 
-fn determine_severity_with_config(lines: usize, config: &Config) -> Severity {
+fn determine_severity_with_config(lines: usize, type_threshold: Option<usize>, config: &Config) -> Severity {
     let levels = &config.large_files.severity_levels;
     
-    if lines >= levels.critical {
+    // A per-type threshold moves the whole severity scale, keeping its proportions
+    // (with the defaults, a 400-line test threshold gives error at 800 and critical at 1600)
+    let (error, critical) = match type_threshold {
+        Some(type_threshold) => {
+            let warning = levels.warning.max(1);
+            (levels.error * type_threshold / warning, levels.critical * type_threshold / warning)
+        }
+        None => (levels.error, levels.critical),
+    };
+    
+    if lines >= critical {
         Severity::Critical
-    } else if lines >= levels.error {
+    } else if lines >= error {
         Severity::Error
     } else {
         Severity::Warning
@@ -329,7 +658,7 @@ fn generate_suggestions(file_type: &FileType, _lines: usize) -> Vec<String> {
     suggestions
 }
 
-fn create_summary(total_files: usize, large_files: &[LargeFile]) -> Summary {
+fn create_summary(total_files: usize, large_files: &[LargeFile], large_functions: &[LargeFunction]) -> Summary {
     let mut warnings = 0;
     let mut errors = 0;
     let mut critical = 0;
@@ -345,22 +674,76 @@ fn create_summary(total_files: usize, large_files: &[LargeFile]) -> Summary {
     Summary {
         total_files_scanned: total_files,
         large_files_found: large_files.len(),
+        large_functions_found: large_functions.len(),
         warnings,
         errors,
         critical,
+        generated_files_skipped: 0,
     }
 }
 
-fn print_report(report: &LargeFileReport, config: &Config, quiet: bool) {
+impl Metrics for LargeFileReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("large_files", self.summary.large_files_found as f64),
+            ("large_functions", self.summary.large_functions_found as f64),
+        ]
+    }
+}
+
+impl Annotate for LargeFileReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let files = self.files.iter().map(|file| {
+            let level = match file.severity {
+                Severity::Warning => AnnotationLevel::Warning,
+                Severity::Error => AnnotationLevel::Error,
+                Severity::Critical => AnnotationLevel::Critical,
+            };
+            let mut message = format!("{} lines, over the {}-line threshold for this {}", file.lines, file.threshold, file.file_type);
+            if let Some(suggestion) = file.suggestions.first() {
+                // Suggestions start with an emoji for the terminal report
+                message.push_str(&format!(". {}", suggestion.trim_start_matches(|c: char| !c.is_alphanumeric())));
+            }
+            Annotation::new(level, &file.path, 1, format!("Large file ({:?})", file.severity), message)
+                .with_rule("SNF-LARGE-001")
+        });
+        let functions = self.functions.iter().map(|function| {
+            Annotation::new(
+                AnnotationLevel::Warning,
+                &function.path,
+                function.start_line,
+                "Large function",
+                format!("`{}` is {} lines long; consider splitting it", function.name, function.lines),
+            )
+            .with_end_line(function.end_line)
+            .with_rule("SNF-LARGE-002")
+        });
+        files.chain(functions).collect()
+    }
+}
+
+fn print_report(report: &LargeFileReport, config: &Config, view: LargeView, quiet: bool) {
     if !quiet {
-        println!();
-        println!("{}", "📊 Large Files Report".bold().blue());
-        println!("{}", "====================".blue());
-        println!();
+        output::blank();
+        output::line("📊 Large Files Report".bold().blue());
+        output::line("====================".blue());
+        output::blank();
+    }
+    
+    if view.by_dir {
+        print_directories(&report.directories);
+        print_summary(&report.summary, config);
+        return;
+    }
+    
+    if report.summary.large_files_found == 0 && report.summary.large_functions_found == 0 {
+        output::line("✅ No large files found! Your code is clean.".green());
+        return;
     }
     
-    if report.summary.large_files_found == 0 {
-        println!("{}", "✅ No large files found! Your code is clean.".green());
+    if !view.listing.is_default() {
+        print_listing(listing_rows(report), view.listing);
+        print_summary(&report.summary, config);
         return;
     }
     
@@ -400,10 +783,91 @@ fn print_report(report: &LargeFileReport, config: &Config, quiet: bool) {
         }
     }
     
+    if !report.functions.is_empty() {
+        print_large_functions(&report.functions);
+    }
+    
     // Print summary
     print_summary(&report.summary, config);
 }
 
+/// Width of the bar of the directory with the most lines
+const DIRECTORY_BAR_WIDTH: usize = 30;
+
+/// One bar per directory, as long as its share of the lines, with the part in large files in red
+fn print_directories(directories: &[DirectoryStats]) {
+    output::line("📂 LINES BY DIRECTORY".bold().blue());
+    output::line("─────────────────────".blue());
+    if directories.is_empty() {
+        output::line(format!("  {}", "No directories at or above --min-dir-lines".dimmed()));
+        output::blank();
+        return;
+    }
+    let most_lines = directories.iter().map(|dir| dir.lines).max().unwrap_or(0).max(1);
+    let name_width = directories.iter().map(|dir| dir.path.len() + 1).max().unwrap_or(0);
+    for dir in directories {
+        let width = (dir.lines * DIRECTORY_BAR_WIDTH).div_ceil(most_lines);
+        let large_width = (dir.large_file_lines * DIRECTORY_BAR_WIDTH).div_ceil(most_lines).min(width);
+        let bar = format!("{}{}", "█".repeat(large_width).red(), "░".repeat(width - large_width).dimmed());
+        let padding = " ".repeat(DIRECTORY_BAR_WIDTH - width);
+        let name = if dir.path == "." { ".".to_string() } else { format!("{}/", dir.path) };
+        let large = match dir.large_files {
+            0 => "".normal(),
+            count => format!(", {} large", count).red(),
+        };
+        output::line(format!("  {:<name_width$}  {}{}  {:>7} lines  {}{}",
+            name.bold(), bar, padding, dir.lines, format!("{} file{}", dir.files, if dir.files == 1 { "" } else { "s" }).dimmed(), large));
+    }
+    output::blank();
+}
+
+/// Large files and functions for `--group-by` and `--sort`
+fn listing_rows(report: &LargeFileReport) -> Vec<Row> {
+    let files = report.files.iter().map(|file| Row {
+        path: file.path.clone(),
+        line: None,
+        rule: "Large file".to_string(),
+        severity: match file.severity {
+            Severity::Critical => report_formatter::Severity::Critical,
+            Severity::Error => report_formatter::Severity::High,
+            Severity::Warning => report_formatter::Severity::Medium,
+        },
+        lines: Some(file.lines),
+        size_bytes: Some(file.size_bytes),
+        text: format!("{} lines, {:.1} KB ({:?})", file.lines, file.size_kb, file.file_type),
+    });
+    let functions = report.functions.iter().map(|function| Row {
+        path: function.path.clone(),
+        line: Some(function.start_line),
+        rule: "Large function".to_string(),
+        severity: report_formatter::Severity::Medium,
+        lines: Some(function.lines),
+        size_bytes: None,
+        text: format!("{} ({} lines)", function.name, function.lines),
+    });
+    files.chain(functions).collect()
+}
+
+fn print_large_functions(functions: &[LargeFunction]) {
+    output::line("🔧 LARGE FUNCTIONS".bold().yellow());
+    output::line("─────────────────".yellow());
+    
+    for function in functions {
+        let kind = match function.kind {
+            FunctionKind::Component => "component",
+            FunctionKind::Method => "method",
+            FunctionKind::Function | FunctionKind::ArrowFunction => "function",
+        };
+        output::line(format!("  {} {} {}",
+            function.name.bold(),
+            format!("({})", kind).dimmed(),
+            format!("{} lines", function.lines).yellow()
+        ));
+        output::line(format!("     {}:{}-{}", function.path.cyan(), function.start_line, function.end_line));
+    }
+    output::blank();
+}
+
 fn print_file_info_compact(file: &LargeFile, severity: &str) {
     let (emoji, path_color) = match severity {
         "critical" => ("🚨 CRITICAL:", "red"),
@@ -426,32 +890,41 @@ fn print_file_info_compact(file: &LargeFile, severity: &str) {
         format!("{:.1} KB", file.size_kb)
     };
     
-    println!("{} {}", emoji.bold(), path_colored.bold());
-    println!("   📏 {} lines | 💾 {}", file.lines.to_string().bold(), size_display.bold());
+    output::line(format!("{} {}", emoji.bold(), path_colored.bold()));
+    output::line(format!("   📏 {} lines | 💾 {} | 🏷️  {} (limit {})", file.lines.to_string().bold(), size_display.bold(), file.file_type, file.threshold));
     
     for suggestion in &file.suggestions {
-        println!("   {}", suggestion);
+        output::line(format!("   {}", suggestion));
     }
-    println!();
+    output::blank();
 }
 
 fn print_summary(summary: &Summary, config: &Config) {
-    println!("{}", "📈 SUMMARY".bold().white());
-    println!("{}", "─────────".white());
-    println!("  Files scanned: {}", summary.total_files_scanned);
-    println!("  Large files found: {}", summary.large_files_found);
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.total_files_scanned));
+    if config.large_files.count_mode == CountMode::Code {
+        output::line(format!("  Lines counted: {}", "code only, without blank lines, comments and imports".dimmed()));
+    }
+    if summary.generated_files_skipped > 0 {
+        output::line(format!("  Generated files skipped: {} {}", summary.generated_files_skipped, "(--include-generated to scan them)".dimmed()));
+    }
+    output::line(format!("  Large files found: {}", summary.large_files_found));
+    if summary.large_functions_found > 0 {
+        output::line(format!("  Large functions found: {}", summary.large_functions_found));
+    }
     
     if summary.critical > 0 {
-        println!("  {} {}", "Critical:".red(), summary.critical.to_string().red());
+        output::line(format!("  {} {}", "Critical:".red(), summary.critical.to_string().red()));
     }
     if summary.errors > 0 {
-        println!("  {} {}", "Errors:".yellow(), summary.errors.to_string().yellow());
+        output::line(format!("  {} {}", "Errors:".yellow(), summary.errors.to_string().yellow()));
     }
     if summary.warnings > 0 {
-        println!("  {} {}", "Warnings:".cyan(), summary.warnings.to_string().cyan());
+        output::line(format!("  {} {}", "Warnings:".cyan(), summary.warnings.to_string().cyan()));
     }
     
-    println!();
+    output::blank();
     let tip_threshold = config.large_files.severity_levels.warning;
-    println!("{}", format!("💡 TIP: Files over {} lines are considered 'smelly code' and should be refactored", tip_threshold).dimmed());
+    output::line(format!("💡 TIP: Files over {} lines are considered 'smelly code' and should be refactored", tip_threshold).dimmed());
 }
\ No newline at end of file
@@ -0,0 +1,376 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::config::Config;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, paths, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+use super::history::Metrics;
+
+/// The linter whose results were read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Linter {
+    Eslint,
+    Biome,
+}
+
+impl std::fmt::Display for Linter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Linter::Eslint => write!(f, "ESLint"),
+            Linter::Biome => write!(f, "Biome"),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LintReport {
+    pub linter: Linter,
+    /// The ingested report, or the command that was run
+    pub source: String,
+    pub findings: Vec<LintFinding>,
+    pub summary: LintSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    /// The linter's rule prefixed with its name, e.g. `eslint/no-unused-vars` or `biome/suspicious/noDebugger`
+    pub rule: String,
+    pub level: AnnotationLevel,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LintSummary {
+    /// Files the linter checked; Biome reports only the files with diagnostics
+    pub files_linted: usize,
+    pub files_with_findings: usize,
+    pub total_findings: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Findings dropped by `[[ignore]]` entries
+    pub ignored: usize,
+    pub by_rule: BTreeMap<String, usize>,
+}
+
+impl Annotate for LintReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.findings.iter().map(|finding| {
+            Annotation::new(finding.level, &finding.file, finding.line, &finding.rule, &finding.message)
+                .with_rule(&finding.rule)
+        }).collect()
+    }
+}
+
+impl Metrics for LintReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("lint_errors", self.summary.errors as f64),
+            ("lint_warnings", self.summary.warnings as f64),
+        ]
+    }
+}
+
+/// How many of the most frequent rules are listed
+const TOP_RULES: usize = 10;
+
+pub async fn run(target: &ScanTarget, ingest: Option<&Path>, json: bool, quiet: bool) -> Result<CommandOutcome<LintReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!("Running lint ingestion");
+
+    let config = target.config()?;
+    let outcome = check(target, ingest, &config)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "lint",
+        report,
+        report.summary.files_linted,
+        report.summary.total_findings,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(errors = report.summary.errors, "lint ingestion completed");
+
+    Ok(outcome)
+}
+
+/// Read the linter's results from `ingest`, `[lint] report` or a run of the configured linter.
+/// Lint errors fail the check, as they fail the linter itself.
+pub fn check(target: &ScanTarget, ingest: Option<&Path>, config: &Config) -> Result<CommandOutcome<LintReport>> {
+    let project_dir = target.project_dir("lint")?;
+    let configured = config.lint.report.as_ref().map(|report| target.config_dir().join(report));
+
+    let (content, source) = match ingest.map(Path::to_path_buf).or(configured) {
+        Some(report) => {
+            let content = fs::read_to_string(&report)
+                .map_err(|e| anyhow!("Could not read lint report {}: {}", report.display(), e))?;
+            (content, paths::display(&report))
+        }
+        None => run_linter(project_dir)?,
+    };
+
+    let (linter, files_linted, mut findings) = parse_report(&content, project_dir)
+        .map_err(|e| anyhow!("{} is not an ESLint or Biome JSON report: {}", source, e))?;
+    let ignored = target.ignores()?.retain(&mut findings, |finding| (finding.rule.as_str(), &finding.file));
+    findings.sort_by(|a, b| (&a.file, a.line, a.column).cmp(&(&b.file, b.line, b.column)));
+
+    let mut by_rule: BTreeMap<String, usize> = BTreeMap::new();
+    for finding in &findings {
+        *by_rule.entry(finding.rule.clone()).or_default() += 1;
+    }
+    let files_with_findings = findings.iter().map(|finding| &finding.file).collect::<BTreeSet<_>>().len();
+    let summary = LintSummary {
+        files_linted: files_linted.unwrap_or(files_with_findings),
+        files_with_findings,
+        total_findings: findings.len(),
+        errors: findings.iter().filter(|f| matches!(f.level, AnnotationLevel::Critical | AnnotationLevel::Error)).count(),
+        warnings: findings.iter().filter(|f| f.level == AnnotationLevel::Warning).count(),
+        ignored,
+        by_rule,
+    };
+
+    let exit_code = check_failure_threshold(summary.errors > 0, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(LintReport { linter, source, findings, summary }, exit_code))
+}
+
+/// The linter the project is set up for: a `biome.json` wins over ESLint configuration
+fn detect_linter(project_dir: &Path) -> Option<Linter> {
+    if ["biome.json", "biome.jsonc"].iter().any(|name| project_dir.join(name).is_file()) {
+        return Some(Linter::Biome);
+    }
+    let has_eslint_config = fs::read_dir(project_dir).into_iter().flatten().flatten().any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_string();
+        name.starts_with("eslint.config.") || name.starts_with(".eslintrc")
+    });
+    let in_package_json = fs::read_to_string(project_dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .is_some_and(|package| package.get("eslintConfig").is_some());
+    (has_eslint_config || in_package_json).then_some(Linter::Eslint)
+}
+
+/// Run the project's linter with its JSON reporter; returns the output and the command line
+fn run_linter(project_dir: &Path) -> Result<(String, String)> {
+    let linter = detect_linter(project_dir)
+        .ok_or_else(|| anyhow!("No ESLint or Biome configuration found; pass a JSON report with --ingest"))?;
+    let (name, args): (&str, &[&str]) = match linter {
+        Linter::Eslint => ("eslint", &[".", "--format", "json"]),
+        Linter::Biome => ("biome", &["lint", "--reporter=json", "."]),
+    };
+    let binary = find_binary(project_dir, name)
+        .ok_or_else(|| anyhow!("{} not found. Install it with: npm install --save-dev {}", linter, match linter {
+            Linter::Eslint => "eslint",
+            Linter::Biome => "@biomejs/biome",
+        }))?;
+
+    let output = Command::new(&binary)
+        .args(args)
+        .current_dir(project_dir)
+        .output()
+        .map_err(|e| anyhow!("Failed to run {}: {}", binary.display(), e))?;
+
+    // Both linters exit non-zero when they report errors; only output that is not JSON is a failure
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if !stdout.trim_start().starts_with(['[', '{']) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("{} did not produce a JSON report: {}", linter, stderr.trim()));
+    }
+    Ok((stdout, format!("{} {}", name, args.join(" "))))
+}
+
+/// The project's own copy of a tool in node_modules/.bin, else one on the PATH
+fn find_binary(project_dir: &Path, name: &str) -> Option<PathBuf> {
+    let bin = if cfg!(windows) { format!("{}.cmd", name) } else { name.to_string() };
+    let local = project_dir.join("node_modules").join(".bin").join(&bin);
+    if local.exists() {
+        return Some(local);
+    }
+
+    Command::new(&bin)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|_| PathBuf::from(bin))
+}
+
+/// Findings of an ESLint (`--format json`) or Biome (`--reporter=json`) report, with the
+/// number of files linted when the report says. Relative paths are taken from `project_dir`
+fn parse_report(content: &str, project_dir: &Path) -> Result<(Linter, Option<usize>, Vec<LintFinding>)> {
+    let report: Value = serde_json::from_str(content)?;
+    match &report {
+        Value::Array(files) => Ok((Linter::Eslint, Some(files.len()), parse_eslint(files, project_dir))),
+        Value::Object(object) if object.contains_key("diagnostics") => Ok((Linter::Biome, None, parse_biome(&report, project_dir))),
+        _ => Err(anyhow!("expected an array of files or an object with `diagnostics`")),
+    }
+}
+
+fn parse_eslint(files: &[Value], project_dir: &Path) -> Vec<LintFinding> {
+    files.iter()
+        .flat_map(|file| {
+            let path = display_path(project_dir, file["filePath"].as_str().unwrap_or_default());
+            file["messages"].as_array().into_iter().flatten().map(move |message| {
+                // Parse errors have no rule
+                let rule = message["ruleId"].as_str().unwrap_or("parse-error");
+                let level = match message["severity"].as_u64() {
+                    Some(1) if !message["fatal"].as_bool().unwrap_or(false) => AnnotationLevel::Warning,
+                    _ => AnnotationLevel::Error,
+                };
+                LintFinding {
+                    file: path.clone(),
+                    line: message["line"].as_u64().unwrap_or(1) as usize,
+                    column: message["column"].as_u64().unwrap_or(1) as usize,
+                    rule: format!("eslint/{}", rule),
+                    level,
+                    message: message["message"].as_str().unwrap_or_default().trim().to_string(),
+                }
+            })
+        })
+        .collect()
+}
+
+fn parse_biome(report: &Value, project_dir: &Path) -> Vec<LintFinding> {
+    report["diagnostics"].as_array().into_iter().flatten()
+        .filter_map(|diagnostic| {
+            let location = &diagnostic["location"];
+            let file = location["path"]["file"].as_str().or_else(|| location["path"].as_str())?;
+            let category = diagnostic["category"].as_str().unwrap_or("lint");
+            let level = match diagnostic["severity"].as_str() {
+                Some("warning") => AnnotationLevel::Warning,
+                Some("information" | "hint") => AnnotationLevel::Notice,
+                _ => AnnotationLevel::Error,
+            };
+            let (line, column) = biome_position(location, &project_dir.join(file));
+            Some(LintFinding {
+                file: display_path(project_dir, file),
+                line,
+                column,
+                rule: format!("biome/{}", category.strip_prefix("lint/").unwrap_or(category)),
+                level,
+                message: diagnostic["description"].as_str().unwrap_or_default().trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 1-based line and column of a Biome diagnostic, from its `start` position or its byte `span`
+fn biome_position(location: &Value, path: &Path) -> (usize, usize) {
+    if let Some(line) = location["start"]["line"].as_u64() {
+        return (line as usize, location["start"]["column"].as_u64().unwrap_or(1) as usize);
+    }
+    let Some(offset) = location["span"][0].as_u64() else {
+        return (1, 1);
+    };
+    let source = location["sourceCode"].as_str().map(str::to_string)
+        .or_else(|| fs::read_to_string(path).ok())
+        .unwrap_or_default();
+    let before = source.get(..offset as usize).unwrap_or(&source);
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+fn display_path(project_dir: &Path, file: &str) -> String {
+    paths::display(&project_dir.join(file))
+}
+
+fn print_report(report: &LintReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🧹 Lint Report".bold().blue());
+        output::line("==============".blue());
+        output::line(format!("  {} results from {}", report.linter, report.source.dimmed()));
+        output::blank();
+    }
+
+    if report.findings.is_empty() {
+        output::line("✅ No lint findings!".green());
+        return;
+    }
+
+    output::line("🔍 FINDINGS".bold().yellow());
+    output::line("───────────".yellow());
+    for finding in &report.findings {
+        let rule = match finding.level {
+            AnnotationLevel::Critical | AnnotationLevel::Error => finding.rule.red(),
+            AnnotationLevel::Warning => finding.rule.yellow(),
+            AnnotationLevel::Notice => finding.rule.dimmed(),
+        };
+        output::line(format!("  {}:{}:{} - {} {}", finding.file.cyan(), finding.line, finding.column, rule, finding.message));
+    }
+    output::blank();
+
+    let mut rules: Vec<(&String, &usize)> = report.summary.by_rule.iter().collect();
+    rules.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    output::line("📋 TOP RULES".bold().white());
+    output::line("────────────".white());
+    for (rule, count) in rules.into_iter().take(TOP_RULES) {
+        output::line(format!("  {:>4} × {}", count, rule));
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files linted: {}", summary.files_linted));
+    output::line(format!("  Files with findings: {}", summary.files_with_findings));
+    output::line(format!("  Errors: {}", summary.errors.to_string().red()));
+    output::line(format!("  Warnings: {}", summary.warnings.to_string().yellow()));
+    if summary.ignored > 0 {
+        output::line(format!("  Ignored: {}", summary.ignored.to_string().dimmed()));
+    }
+    output::blank();
+    output::line("💡 TIP: Silence a lint rule for some paths with an [[ignore]] entry, e.g. rule = \"eslint/no-console\"".dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eslint_and_biome_reports_are_parsed() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let eslint = r#"[
+            {"filePath": "src/a.ts", "messages": [
+                {"ruleId": "no-unused-vars", "severity": 2, "message": "'x' is defined but never used.", "line": 3, "column": 7},
+                {"ruleId": "no-console", "severity": 1, "message": "Unexpected console statement.", "line": 5, "column": 1},
+                {"ruleId": null, "severity": 2, "fatal": true, "message": "Parsing error: Unexpected token", "line": 9, "column": 2}
+            ]},
+            {"filePath": "src/b.ts", "messages": []}
+        ]"#;
+        let (linter, files, findings) = parse_report(eslint, dir.path()).unwrap();
+        let rules: Vec<(&str, AnnotationLevel, usize)> = findings.iter().map(|f| (f.rule.as_str(), f.level, f.line)).collect();
+
+        assert_eq!((linter, files), (Linter::Eslint, Some(2)));
+        assert_eq!(rules, vec![
+            ("eslint/no-unused-vars", AnnotationLevel::Error, 3),
+            ("eslint/no-console", AnnotationLevel::Warning, 5),
+            ("eslint/parse-error", AnnotationLevel::Error, 9),
+        ]);
+
+        let biome = r#"{"diagnostics": [
+            {"category": "lint/suspicious/noDebugger", "severity": "error", "description": "This is an unexpected use of the debugger statement.",
+             "location": {"path": {"file": "src/c.ts"}, "span": [19, 27], "sourceCode": "const a = 1;\nlet b\ndebugger;\n"}},
+            {"category": "lint/style/useConst", "severity": "warning", "description": "This let declares a variable that is only assigned once.",
+             "location": {"path": {"file": "src/c.ts"}, "start": {"line": 2, "column": 1}}}
+        ]}"#;
+        let (linter, files, findings) = parse_report(biome, dir.path()).unwrap();
+        let rules: Vec<(&str, AnnotationLevel, usize, usize)> = findings.iter().map(|f| (f.rule.as_str(), f.level, f.line, f.column)).collect();
+
+        assert_eq!((linter, files), (Linter::Biome, None));
+        assert_eq!(rules, vec![
+            ("biome/suspicious/noDebugger", AnnotationLevel::Error, 3, 1),
+            ("biome/style/useConst", AnnotationLevel::Warning, 2, 1),
+        ]);
+        assert!(parse_report(r#"{"files": []}"#, dir.path()).is_err());
+    }
+}
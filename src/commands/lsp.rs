@@ -0,0 +1,207 @@
+/// `sniff lsp`: a language server over stdio that shows the findings of `large`, `imports`,
+/// `memory` and `types` as diagnostics in the editor. Each open document is analyzed from
+/// its unsaved text whenever it is opened, changed or saved, and only that document is
+/// re-analyzed, so results follow typing
+use anyhow::Result;
+use lsp_server::{Connection, Message, Notification};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+    Notification as _, PublishDiagnostics,
+};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
+    DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, NumberOrString,
+    Position, PublishDiagnosticsParams, Range, SaveOptions, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{imports_analyzer, large, memory, types};
+use crate::common::{Annotate, Annotation, AnnotationLevel, FileScanner, Ignores, ScanTarget};
+use crate::config::Config;
+
+/// Commands that can check one file from its text
+pub const SOURCE_COMMANDS: &[&str] = &["large", "imports", "memory", "types"];
+
+/// Files the checks apply to; `types` only looks at TypeScript
+pub const EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx"];
+
+/// Serve on stdin and stdout until the editor shuts the server down
+pub fn run() -> Result<()> {
+    let (connection, io_threads) = Connection::stdio();
+    serve(&connection)?;
+    // The writer thread ends once the connection's sender is gone
+    drop(connection);
+    io_threads.join()?;
+    Ok(())
+}
+
+/// The open documents and the project they belong to
+struct Server<'a> {
+    connection: &'a Connection,
+    root: PathBuf,
+    /// Text of every open document, by URI
+    documents: HashMap<Url, String>,
+}
+
+fn serve(connection: &Connection) -> Result<()> {
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Options(TextDocumentSyncOptions {
+            open_close: Some(true),
+            change: Some(TextDocumentSyncKind::FULL),
+            save: Some(TextDocumentSyncSaveOptions::SaveOptions(SaveOptions { include_text: Some(true) })),
+            ..TextDocumentSyncOptions::default()
+        })),
+        ..ServerCapabilities::default()
+    };
+    let params: InitializeParams = serde_json::from_value(connection.initialize(serde_json::to_value(capabilities)?)?)?;
+    let root = workspace_root(&params)?;
+
+    let mut server = Server { connection, root, documents: HashMap::new() };
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+            }
+            Message::Notification(notification) => server.notify(notification)?,
+            Message::Response(_) => {}
+        }
+    }
+    Ok(())
+}
+
+/// The first workspace folder, or the root the editor opened, or the working directory
+#[allow(deprecated)] // `root_uri` is still what most editors send
+fn workspace_root(params: &InitializeParams) -> Result<PathBuf> {
+    let folder = params.workspace_folders.as_ref().and_then(|folders| folders.first()).map(|folder| &folder.uri);
+    match folder.or(params.root_uri.as_ref()).and_then(|uri| uri.to_file_path().ok()) {
+        Some(root) => Ok(root),
+        None => Ok(std::env::current_dir()?),
+    }
+}
+
+impl Server<'_> {
+    /// Handle a notification and publish the diagnostics it changes. Only a closed connection is
+    /// an error: a notification the server cannot handle is logged and the session goes on
+    fn notify(&mut self, notification: Notification) -> Result<()> {
+        let method = notification.method.clone();
+        match self.handle(notification) {
+            Ok(Some((uri, diagnostics))) => self.publish(uri, diagnostics),
+            Ok(None) => Ok(()),
+            Err(e) => {
+                tracing::warn!("Ignoring {} notification: {:#}", method, e);
+                Ok(())
+            }
+        }
+    }
+
+    /// The document whose diagnostics changed, with its new diagnostics
+    fn handle(&mut self, notification: Notification) -> Result<Option<(Url, Vec<Diagnostic>)>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.update(params.text_document.uri, Some(params.text_document.text))
+            }
+            // Full sync: the last change is the whole new text
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                let text = params.content_changes.into_iter().last().map(|change| change.text);
+                self.update(params.text_document.uri, text)
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.update(params.text_document.uri, params.text)
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                Ok(Some((params.text_document.uri, Vec::new())))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Store the document's new text, when there is one, and analyze it again
+    fn update(&mut self, uri: Url, text: Option<String>) -> Result<Option<(Url, Vec<Diagnostic>)>> {
+        if let Some(text) = text {
+            self.documents.insert(uri.clone(), text);
+        }
+        let (Some(text), Ok(path)) = (self.documents.get(&uri), uri.to_file_path()) else {
+            return Ok(None);
+        };
+        let diagnostics = diagnostics(&self.root, &path, text)?;
+        Ok(Some((uri, diagnostics)))
+    }
+
+    fn publish(&self, uri: Url, diagnostics: Vec<Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams { uri, diagnostics, version: None };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(Message::Notification(notification))?;
+        Ok(())
+    }
+}
+
+/// The findings of every check for a document's text, with the config that applies to its directory
+fn diagnostics(root: &Path, path: &Path, text: &str) -> Result<Vec<Diagnostic>> {
+    let target = ScanTarget::new(root.to_path_buf(), vec![path.to_path_buf()]);
+    let config = target.config()?;
+    let lines: Vec<&str> = text.lines().collect();
+    let mut diagnostics = Vec::new();
+    for command in SOURCE_COMMANDS {
+        // The per-command include/exclude globs apply as they do on the command line
+        if !FileScanner::for_command(&target, command)?.is_target_file(path, &target) {
+            continue;
+        }
+        let annotations = source_annotations(command, root, path, text, &config).unwrap_or_else(|e| {
+            tracing::warn!("Skipping {} diagnostics for {}: {:#}", command, path.display(), e);
+            Vec::new()
+        });
+        diagnostics.extend(annotations.into_iter().map(|annotation| diagnostic(command, annotation, &lines)));
+    }
+    Ok(diagnostics)
+}
+
+/// The findings of `command` for one file's `text`, without reading the file; none when the
+/// command does not check files of its kind, an error when its analysis fails. Shared with `sniff serve`
+pub fn source_annotations(command: &str, root: &Path, path: &Path, text: &str, config: &Config) -> Result<Vec<Annotation>> {
+    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+    if !EXTENSIONS.contains(&extension) {
+        return Ok(Vec::new());
+    }
+    let mut annotations = match command {
+        "large" => large::check_source(path, text, config).annotations(),
+        "imports" => imports_analyzer::check_source(path, text, root, config)?.annotations(),
+        "memory" => memory::check_source(path, text, config)?.annotations(),
+        "types" if matches!(extension, "ts" | "tsx") => types::check_source(path, text).annotations(),
+        _ => return Ok(Vec::new()),
+    };
+    let ignores = Ignores::new(root, &config.ignore);
+    annotations.retain(|annotation| {
+        !ignores.ignores(annotation.rule.as_deref().unwrap_or(command), annotation.file.as_deref().unwrap_or_default())
+    });
+    Ok(annotations)
+}
+
+fn diagnostic(command: &str, annotation: Annotation, lines: &[&str]) -> Diagnostic {
+    // Whole-file findings go on the first line; a finding covers its lines to the end
+    let start = annotation.line.unwrap_or(1).max(1) - 1;
+    let end = annotation.end_line.map_or(start, |line| line.max(1) - 1).max(start);
+    let end_character = lines.get(end).map_or(0, |line| line.encode_utf16().count());
+    let severity = match annotation.level {
+        AnnotationLevel::Critical | AnnotationLevel::Error => DiagnosticSeverity::ERROR,
+        AnnotationLevel::Warning => DiagnosticSeverity::WARNING,
+        AnnotationLevel::Notice => DiagnosticSeverity::INFORMATION,
+    };
+    Diagnostic {
+        range: Range::new(Position::new(start as u32, 0), Position::new(end as u32, end_character as u32)),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(annotation.rule.unwrap_or_else(|| command.to_string()))),
+        source: Some("sniff".to_string()),
+        message: format!("{}: {}", annotation.title, annotation.message),
+        ..Diagnostic::default()
+    }
+}
@@ -0,0 +1,483 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+const TOP_RETAINERS: usize = 15;
+const TOP_CONSTRUCTORS: usize = 20;
+
+/// Results of `--heap-snapshot`: one summary per snapshot, plus growth when there are two
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeapReport {
+    pub snapshots: Vec<HeapSnapshotSummary>,
+    /// Constructors whose object count grew between the first and second snapshot, largest growth first
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub growth: Option<Vec<ConstructorGrowth>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeapSnapshotSummary {
+    pub file: String,
+    pub node_count: usize,
+    pub total_size: u64,
+    /// Objects keeping the most memory alive (retained size from the dominator tree)
+    pub largest_retainers: Vec<Retainer>,
+    /// Shallow size and count per constructor, largest first
+    pub constructors: Vec<ConstructorStats>,
+    pub detached_dom_nodes: usize,
+    pub detached: Vec<ConstructorStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Retainer {
+    pub name: String,
+    pub node_type: String,
+    pub id: u64,
+    pub self_size: u64,
+    pub retained_size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstructorStats {
+    pub name: String,
+    pub count: usize,
+    pub self_size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ConstructorGrowth {
+    pub name: String,
+    pub count_before: usize,
+    pub count_after: usize,
+    pub size_before: u64,
+    pub size_after: u64,
+}
+
+/// The parts of the `.heapsnapshot` JSON we read; node and edge arrays are flat, with
+/// the field layout described in `snapshot.meta`
+#[derive(Deserialize)]
+struct RawSnapshot {
+    snapshot: RawHeader,
+    nodes: Vec<u64>,
+    edges: Vec<u64>,
+    strings: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RawHeader {
+    meta: RawMeta,
+}
+
+#[derive(Deserialize)]
+struct RawMeta {
+    node_fields: Vec<String>,
+    node_types: Vec<Value>,
+    edge_fields: Vec<String>,
+    edge_types: Vec<Value>,
+}
+
+struct Snapshot {
+    nodes: Vec<u64>,
+    edges: Vec<u64>,
+    strings: Vec<String>,
+    node_types: Vec<String>,
+    node_fields: usize,
+    edge_fields: usize,
+    type_offset: usize,
+    name_offset: usize,
+    id_offset: usize,
+    self_size_offset: usize,
+    detachedness_offset: Option<usize>,
+    edge_type_offset: usize,
+    edge_to_offset: usize,
+    weak_edge_type: Option<u64>,
+    /// Index into `edges` (in edges, not fields) of each node's first edge
+    first_edge: Vec<usize>,
+}
+
+impl Snapshot {
+    fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Cannot open heap snapshot {}", path.display()))?;
+        let raw: RawSnapshot = serde_json::from_reader(BufReader::new(file))
+            .with_context(|| format!("{} is not a V8 heap snapshot", path.display()))?;
+        Self::from_raw(raw)
+    }
+
+    fn from_raw(raw: RawSnapshot) -> Result<Self> {
+        let meta = raw.snapshot.meta;
+        let field = |fields: &[String], name: &str| {
+            fields.iter().position(|f| f == name).ok_or_else(|| anyhow!("Heap snapshot is missing the `{}` field", name))
+        };
+        let type_names = |types: &[Value]| -> Vec<String> {
+            types.first()
+                .and_then(Value::as_array)
+                .map(|names| names.iter().filter_map(|n| n.as_str().map(String::from)).collect())
+                .unwrap_or_default()
+        };
+
+        let node_types = type_names(&meta.node_types);
+        let edge_types = type_names(&meta.edge_types);
+        let node_fields = meta.node_fields.len();
+        let edge_fields = meta.edge_fields.len();
+        if node_fields == 0 || edge_fields == 0 || !raw.nodes.len().is_multiple_of(node_fields) {
+            return Err(anyhow!("Heap snapshot node data does not match its meta description"));
+        }
+
+        let edge_count_offset = field(&meta.node_fields, "edge_count")?;
+        let node_count = raw.nodes.len() / node_fields;
+        let mut first_edge = Vec::with_capacity(node_count + 1);
+        let mut next = 0;
+        for node in 0..node_count {
+            first_edge.push(next);
+            next += raw.nodes[node * node_fields + edge_count_offset] as usize;
+        }
+        first_edge.push(next);
+        if next * edge_fields > raw.edges.len() {
+            return Err(anyhow!("Heap snapshot edge data is truncated"));
+        }
+
+        Ok(Self {
+            type_offset: field(&meta.node_fields, "type")?,
+            name_offset: field(&meta.node_fields, "name")?,
+            id_offset: field(&meta.node_fields, "id")?,
+            self_size_offset: field(&meta.node_fields, "self_size")?,
+            detachedness_offset: field(&meta.node_fields, "detachedness").ok(),
+            edge_type_offset: field(&meta.edge_fields, "type")?,
+            edge_to_offset: field(&meta.edge_fields, "to_node")?,
+            weak_edge_type: edge_types.iter().position(|t| t == "weak").map(|i| i as u64),
+            nodes: raw.nodes,
+            edges: raw.edges,
+            strings: raw.strings,
+            node_types,
+            node_fields,
+            edge_fields,
+            first_edge,
+        })
+    }
+
+    fn node_count(&self) -> usize {
+        self.first_edge.len() - 1
+    }
+
+    fn node_field(&self, node: usize, offset: usize) -> u64 {
+        self.nodes[node * self.node_fields + offset]
+    }
+
+    fn node_type(&self, node: usize) -> &str {
+        self.node_types.get(self.node_field(node, self.type_offset) as usize).map(String::as_str).unwrap_or("unknown")
+    }
+
+    fn name(&self, node: usize) -> &str {
+        self.strings.get(self.node_field(node, self.name_offset) as usize).map(String::as_str).unwrap_or("")
+    }
+
+    fn self_size(&self, node: usize) -> u64 {
+        self.node_field(node, self.self_size_offset)
+    }
+
+    /// Strong references out of `node`; weak edges do not keep anything alive
+    fn children(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        (self.first_edge[node]..self.first_edge[node + 1]).filter_map(move |edge| {
+            let base = edge * self.edge_fields;
+            if Some(self.edges[base + self.edge_type_offset]) == self.weak_edge_type {
+                return None;
+            }
+            let to = self.edges[base + self.edge_to_offset] as usize / self.node_fields;
+            (to < self.node_count()).then_some(to)
+        })
+    }
+
+    fn is_detached(&self, node: usize) -> bool {
+        // `detachedness` is 2 for detached nodes in newer V8; older snapshots only have the name prefix
+        self.detachedness_offset.is_some_and(|offset| self.node_field(node, offset) == 2)
+            || self.name(node).starts_with("Detached ")
+    }
+
+    /// Grouping used by DevTools' Summary view
+    fn constructor(&self, node: usize) -> String {
+        match self.node_type(node) {
+            "object" | "native" => self.name(node).to_string(),
+            "closure" => "(closure)".to_string(),
+            "concatenated string" | "sliced string" | "string" => "(string)".to_string(),
+            other => format!("({})", other),
+        }
+    }
+
+    /// Retained size of every node: the memory freed if it were collected, from the
+    /// dominator tree rooted at node 0 (Cooper, Harvey and Kennedy's iterative algorithm)
+    fn retained_sizes(&self) -> Vec<u64> {
+        let count = self.node_count();
+        let mut retained = vec![0u64; count];
+        if count == 0 {
+            return retained;
+        }
+
+        // Iterative DFS for a postorder numbering of reachable nodes
+        const UNVISITED: usize = usize::MAX;
+        let mut post_index = vec![UNVISITED; count];
+        let mut postorder = Vec::with_capacity(count);
+        let mut visited = vec![false; count];
+        type Children<'a> = Box<dyn Iterator<Item = usize> + 'a>;
+        let mut stack: Vec<(usize, Children)> = vec![(0, Box::new(self.children(0)))];
+        visited[0] = true;
+        while let Some((node, children)) = stack.last_mut() {
+            match children.find(|child| !visited[*child]) {
+                Some(child) => {
+                    visited[child] = true;
+                    stack.push((child, Box::new(self.children(child))));
+                }
+                None => {
+                    post_index[*node] = postorder.len();
+                    postorder.push(*node);
+                    stack.pop();
+                }
+            }
+        }
+
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for &node in &postorder {
+            for child in self.children(node) {
+                predecessors[child].push(node);
+            }
+        }
+
+        let root = postorder.len() - 1;
+        let mut idom = vec![UNVISITED; postorder.len()];
+        idom[root] = root;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for index in (0..root).rev() {
+                let mut new_idom = UNVISITED;
+                for &pred in &predecessors[postorder[index]] {
+                    let pred = post_index[pred];
+                    if idom[pred] == UNVISITED {
+                        continue;
+                    }
+                    new_idom = if new_idom == UNVISITED { pred } else { intersect(&idom, pred, new_idom) };
+                }
+                if new_idom != UNVISITED && idom[index] != new_idom {
+                    idom[index] = new_idom;
+                    changed = true;
+                }
+            }
+        }
+
+        // A node's dominator finishes after it in the DFS, so postorder adds children first
+        for index in 0..postorder.len() {
+            let node = postorder[index];
+            retained[node] += self.self_size(node);
+            if index != root && idom[index] != UNVISITED {
+                let parent = postorder[idom[index]];
+                retained[parent] += retained[node];
+            }
+        }
+        retained
+    }
+}
+
+fn intersect(idom: &[usize], mut a: usize, mut b: usize) -> usize {
+    while a != b {
+        while a < b {
+            a = idom[a];
+        }
+        while b < a {
+            b = idom[b];
+        }
+    }
+    a
+}
+
+/// Parse one or two snapshots; with two, the second is compared against the first
+pub fn analyze(paths: &[impl AsRef<Path>]) -> Result<HeapReport> {
+    let mut snapshots = Vec::new();
+    let mut counts = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let snapshot = Snapshot::load(path)?;
+        let (summary, by_constructor) = summarize(&snapshot, &path.display().to_string());
+        snapshots.push(summary);
+        counts.push(by_constructor);
+    }
+
+    let growth = match counts.as_slice() {
+        [before, after] => Some(growth(before, after)),
+        _ => None,
+    };
+
+    Ok(HeapReport { snapshots, growth })
+}
+
+fn summarize(snapshot: &Snapshot, file: &str) -> (HeapSnapshotSummary, HashMap<String, ConstructorStats>) {
+    let count = snapshot.node_count();
+    let retained = snapshot.retained_sizes();
+
+    let mut by_constructor: HashMap<String, ConstructorStats> = HashMap::new();
+    let mut detached: HashMap<String, ConstructorStats> = HashMap::new();
+    for node in 0..count {
+        let size = snapshot.self_size(node);
+        add(&mut by_constructor, snapshot.constructor(node), size);
+        if snapshot.is_detached(node) {
+            add(&mut detached, snapshot.name(node).to_string(), size);
+        }
+    }
+
+    let mut retainers: Vec<usize> = (1..count)
+        .filter(|&node| !matches!(snapshot.node_type(node), "synthetic" | "hidden"))
+        .collect();
+    retainers.sort_by_key(|&node| std::cmp::Reverse(retained[node]));
+    let largest_retainers = retainers.into_iter()
+        .take(TOP_RETAINERS)
+        .filter(|&node| retained[node] > 0)
+        .map(|node| Retainer {
+            name: truncate(snapshot.name(node)),
+            node_type: snapshot.node_type(node).to_string(),
+            id: snapshot.node_field(node, snapshot.id_offset),
+            self_size: snapshot.self_size(node),
+            retained_size: retained[node],
+        })
+        .collect();
+
+    let summary = HeapSnapshotSummary {
+        file: file.to_string(),
+        node_count: count,
+        total_size: (0..count).map(|node| snapshot.self_size(node)).sum(),
+        largest_retainers,
+        constructors: largest(by_constructor.values().cloned().collect(), TOP_CONSTRUCTORS),
+        detached_dom_nodes: detached.values().map(|stats| stats.count).sum(),
+        detached: largest(detached.into_values().collect(), TOP_CONSTRUCTORS),
+    };
+    (summary, by_constructor)
+}
+
+fn add(stats: &mut HashMap<String, ConstructorStats>, name: String, size: u64) {
+    let entry = stats.entry(name.clone()).or_insert(ConstructorStats { name, count: 0, self_size: 0 });
+    entry.count += 1;
+    entry.self_size += size;
+}
+
+fn largest(mut stats: Vec<ConstructorStats>, limit: usize) -> Vec<ConstructorStats> {
+    stats.sort_by(|a, b| b.self_size.cmp(&a.self_size).then_with(|| a.name.cmp(&b.name)));
+    stats.truncate(limit);
+    stats
+}
+
+fn growth(before: &HashMap<String, ConstructorStats>, after: &HashMap<String, ConstructorStats>) -> Vec<ConstructorGrowth> {
+    let mut growth: Vec<ConstructorGrowth> = after.values()
+        .filter_map(|now| {
+            let (count_before, size_before) = before.get(&now.name).map(|then| (then.count, then.self_size)).unwrap_or((0, 0));
+            (now.count > count_before).then(|| ConstructorGrowth {
+                name: now.name.clone(),
+                count_before,
+                count_after: now.count,
+                size_before,
+                size_after: now.self_size,
+            })
+        })
+        .collect();
+    growth.sort_by(|a, b| {
+        (b.count_after - b.count_before).cmp(&(a.count_after - a.count_before)).then_with(|| a.name.cmp(&b.name))
+    });
+    growth.truncate(TOP_CONSTRUCTORS);
+    growth
+}
+
+fn truncate(name: &str) -> String {
+    if name.chars().count() > 60 {
+        format!("{}…", name.chars().take(60).collect::<String>())
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (type, name, self_size, [(edge type, target)]); node 0 is the root
+    type TestNode<'a> = (&'a str, &'a str, u64, &'a [(&'a str, usize)]);
+
+    fn snapshot(nodes: &[TestNode]) -> String {
+        let mut strings: Vec<String> = Vec::new();
+        let mut intern = |s: &str| {
+            strings.iter().position(|x| x == s).unwrap_or_else(|| {
+                strings.push(s.to_string());
+                strings.len() - 1
+            })
+        };
+        let node_types = ["hidden", "object", "native", "synthetic"];
+        let edge_types = ["property", "weak"];
+        let mut flat_nodes = Vec::new();
+        let mut flat_edges = Vec::new();
+        for (id, (node_type, name, size, edges)) in nodes.iter().enumerate() {
+            let type_index = node_types.iter().position(|t| t == node_type).unwrap();
+            flat_nodes.extend([type_index, intern(name), id * 2 + 1, *size as usize, edges.len()]);
+            for (edge_type, to) in *edges {
+                flat_edges.extend([edge_types.iter().position(|t| t == edge_type).unwrap(), intern("ref"), to * 5]);
+            }
+        }
+        serde_json::json!({
+            "snapshot": {"meta": {
+                "node_fields": ["type", "name", "id", "self_size", "edge_count"],
+                "node_types": [node_types, "string", "number", "number", "number"],
+                "edge_fields": ["type", "name_or_index", "to_node"],
+                "edge_types": [edge_types, "string_or_number", "node"],
+            }},
+            "nodes": flat_nodes,
+            "edges": flat_edges,
+            "strings": strings,
+        }).to_string()
+    }
+
+    fn load(json: &str) -> Snapshot {
+        Snapshot::from_raw(serde_json::from_str(json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_retained_sizes_follow_dominators() {
+        // root -> cache -> entry -> payload; root -> other -> entry keeps entry out of cache's retained size
+        let snapshot = load(&snapshot(&[
+            ("synthetic", "", 0, &[("property", 1), ("property", 4)]),
+            ("object", "Cache", 100, &[("property", 2)]),
+            ("object", "Entry", 50, &[("property", 3)]),
+            ("object", "Payload", 1000, &[]),
+            ("object", "Other", 10, &[("property", 2), ("weak", 5)]),
+            ("object", "Unreachable", 7, &[]),
+        ]));
+        let retained = snapshot.retained_sizes();
+
+        assert_eq!(retained[1], 100);
+        assert_eq!(retained[2], 1050);
+        assert_eq!(retained[4], 10);
+        assert_eq!(retained[0], 1160);
+    }
+
+    #[test]
+    fn test_detached_nodes_and_growth() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let before = dir.path().join("before.heapsnapshot");
+        let after = dir.path().join("after.heapsnapshot");
+        std::fs::write(&before, snapshot(&[
+            ("synthetic", "", 0, &[("property", 1)]),
+            ("object", "Listener", 40, &[]),
+        ])).unwrap();
+        std::fs::write(&after, snapshot(&[
+            ("synthetic", "", 0, &[("property", 1), ("property", 2), ("property", 3)]),
+            ("object", "Listener", 40, &[]),
+            ("object", "Listener", 40, &[]),
+            ("native", "Detached HTMLDivElement", 120, &[]),
+        ])).unwrap();
+
+        let report = analyze(&[before, after]).unwrap();
+
+        assert_eq!(report.snapshots[1].detached_dom_nodes, 1);
+        assert_eq!(report.snapshots[1].detached[0].name, "Detached HTMLDivElement");
+        assert_eq!(report.snapshots[1].largest_retainers[0].retained_size, 120);
+        let growth = report.growth.unwrap();
+        assert_eq!(growth[0].name, "Detached HTMLDivElement");
+        assert_eq!((growth[1].name.as_str(), growth[1].count_before, growth[1].count_after), ("Listener", 1, 2));
+    }
+}
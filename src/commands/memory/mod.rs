@@ -0,0 +1,960 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use walkdir::WalkDir;
+use crate::config::Config;
+use crate::common::report_formatter::{Row, print_listing};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ListingOptions, ScanTarget, PatternRule, get_common_patterns, is_in_string_literal_or_comment, scan_lines, Severity, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+mod heap;
+mod processes;
+mod scope;
+pub use heap::HeapReport;
+
+/// Options for `sniff memory` beyond the scan target
+#[derive(Debug, Clone, Default)]
+pub struct MemoryOptions {
+    /// V8 heap snapshots to analyze instead of scanning source; the second is compared to the first
+    pub heap_snapshots: Vec<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemoryReport {
+    pub patterns: Vec<MemoryPattern>,
+    pub node_processes: Vec<NodeProcess>,
+    pub summary: MemorySummary,
+    pub recommendations: Vec<String>,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heap: Option<HeapReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemoryPattern {
+    pub file_path: String,
+    pub line_number: usize,
+    pub pattern_type: PatternType,
+    pub code_snippet: String,
+    pub severity: Severity,
+    pub description: String,
+    pub recommendation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum PatternType {
+    UnboundedArrayGrowth,
+    UnremovedEventListener,
+    CircularReference,
+    LargeObjectRetention,
+    UncontrolledLoop,
+    TimerLeak,
+    DomElementLeak,
+    ClosureLeak,
+    UnabortedFetch,
+    UnclosedSubscription,
+    UndisconnectedObserver,
+}
+
+impl PatternType {
+    /// The stable id of this kind of leak, see `rule_catalog`
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            PatternType::UnboundedArrayGrowth => "SNF-MEM-ARRAY-GROWTH",
+            PatternType::UnremovedEventListener => "SNF-MEM-LISTENER",
+            PatternType::CircularReference => "SNF-MEM-CIRCULAR",
+            PatternType::LargeObjectRetention => "SNF-MEM-RETENTION",
+            PatternType::UncontrolledLoop => "SNF-MEM-LOOP",
+            PatternType::TimerLeak => "SNF-MEM-TIMER",
+            PatternType::DomElementLeak => "SNF-MEM-DOM",
+            PatternType::ClosureLeak => "SNF-MEM-CLOSURE",
+            PatternType::UnabortedFetch => "SNF-MEM-FETCH",
+            PatternType::UnclosedSubscription => "SNF-MEM-SUBSCRIPTION",
+            PatternType::UndisconnectedObserver => "SNF-MEM-OBSERVER",
+        }
+    }
+}
+
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NodeProcess {
+    pub pid: u32,
+    #[serde(default)]
+    pub parent_pid: Option<u32>,
+    /// Resident memory of this process
+    pub memory_usage_mb: f64,
+    /// Resident memory of this process and everything it started
+    #[serde(default)]
+    pub tree_memory_mb: f64,
+    pub cpu_usage: f64,
+    pub command: String,
+    pub status: ProcessStatus,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum ProcessStatus {
+    Normal,
+    HighMemory,
+    MemoryLeak,
+    Unresponsive,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemorySummary {
+    pub total_patterns: usize,
+    pub critical_issues: usize,
+    pub high_issues: usize,
+    pub medium_issues: usize,
+    pub low_issues: usize,
+    pub active_processes: usize,
+    pub high_memory_processes: usize,
+}
+
+pub async fn run(target: &ScanTarget, options: &MemoryOptions, listing: ListingOptions, json: bool, quiet: bool) -> Result<CommandOutcome<MemoryReport>> {
+    tracing::info!("Running memory analysis");
+    
+    let outcome = check(target, options).await?;
+    let report = &outcome.report;
+    
+    let response = create_annotated_json_output(
+        "memory",
+        report,
+        report.summary.total_patterns + report.summary.active_processes,
+        report.summary.total_patterns + report.summary.high_memory_processes,
+        Some(report.duration_ms),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_memory_report(report, listing, quiet))?;
+    
+    Ok(outcome)
+}
+
+/// Analyze memory usage and decide the exit status, without printing a report
+pub async fn check(target: &ScanTarget, options: &MemoryOptions) -> Result<CommandOutcome<MemoryReport>> {
+    if !options.heap_snapshots.is_empty() {
+        return check_heap_snapshots(&options.heap_snapshots);
+    }
+    
+    let start_time = Instant::now();
+    let report = analyze_memory_issues(target).await?;
+    let duration = start_time.elapsed().as_millis() as u64;
+    
+    let final_report = MemoryReport {
+        patterns: report.0,
+        node_processes: report.1,
+        summary: report.2,
+        recommendations: report.3,
+        duration_ms: duration,
+        heap: None,
+    };
+    
+    // Fail if critical memory issues found
+    let exit_code = check_failure_threshold(
+        final_report.summary.critical_issues > 0 || final_report.summary.high_memory_processes > 2,
+        ExitCode::GeneralError,
+    );
+    
+    Ok(CommandOutcome::new(final_report, exit_code))
+}
+
+/// Heap snapshots show what is actually alive, so detached DOM nodes confirm a leak
+fn check_heap_snapshots(paths: &[PathBuf]) -> Result<CommandOutcome<MemoryReport>> {
+    if paths.len() > 2 {
+        return Err(anyhow::anyhow!("--heap-snapshot takes one snapshot, or two to compare"));
+    }
+    
+    let start_time = Instant::now();
+    let heap = heap::analyze(paths)?;
+    let duration = start_time.elapsed().as_millis() as u64;
+    
+    let latest = heap.snapshots.last().map(|s| s.detached_dom_nodes).unwrap_or(0);
+    let mut recommendations = Vec::new();
+    if latest > 0 {
+        recommendations.push(format!("{} detached DOM nodes are still referenced; find what holds them via the Retainers panel in Chrome DevTools", latest));
+    }
+    if let Some(growth) = &heap.growth {
+        if let Some(top) = growth.first() {
+            recommendations.push(format!("{} objects grew from {} to {} between snapshots; check caches, listeners and subscriptions that create them", top.name, top.count_before, top.count_after));
+        }
+    }
+    
+    let report = MemoryReport {
+        patterns: Vec::new(),
+        node_processes: Vec::new(),
+        summary: calculate_memory_summary(&[], &[]),
+        recommendations,
+        duration_ms: duration,
+        heap: Some(heap),
+    };
+    let exit_code = check_failure_threshold(latest > 0, ExitCode::GeneralError);
+    
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+async fn analyze_memory_issues(target: &ScanTarget) -> Result<(Vec<MemoryPattern>, Vec<NodeProcess>, MemorySummary, Vec<String>)> {
+    let mut patterns = Vec::new();
+    let mut recommendations = Vec::new();
+    
+    // Scan code for memory leak patterns
+    tracing::debug!("Analyzing code patterns for memory leaks");
+    let code_patterns = scan_for_memory_patterns(target).await?;
+    patterns.extend(code_patterns.0);
+    recommendations.extend(code_patterns.1);
+    
+    // Check running Node.js processes
+    tracing::debug!("Checking Node.js processes for memory usage");
+    let node_processes = processes::check_node_processes().await;
+    
+    tracing::info!("Memory analysis completed");
+    
+    // Generate recommendations based on findings
+    recommendations.extend(generate_memory_recommendations(&patterns, &node_processes));
+    
+    let summary = calculate_memory_summary(&patterns, &node_processes);
+    
+    Ok((patterns, node_processes, summary, recommendations))
+}
+
+async fn scan_for_memory_patterns(target: &ScanTarget) -> Result<(Vec<MemoryPattern>, Vec<String>)> {
+    let mut patterns = Vec::new();
+    let mut recommendations = Vec::new();
+    
+    // Load configuration
+    let config = target.config()?;
+    
+    if !config.memory.check_patterns {
+        return Ok((patterns, recommendations));
+    }
+    
+    // Memory leak detection patterns
+    let leak_patterns = get_memory_leak_patterns(&config);
+    
+    // Use configured directories to exclude from scanning
+    let excluded_dirs = &config.memory.excluded_dirs;
+    let scanner = FileScanner::for_command(target, "memory")?;
+    
+    // Scan TypeScript/JavaScript files
+    for entry in target.paths.iter().flat_map(|path| WalkDir::new(path).max_depth(5)) {
+        if let Ok(entry) = entry {
+            let path = entry.path();
+            
+            // Skip excluded directories (only below the project root)
+            let relative_path = path.strip_prefix(&target.root).unwrap_or(path);
+            if relative_path.components().any(|component| {
+                if let Some(dir_name) = component.as_os_str().to_str() {
+                    excluded_dirs.iter().any(|excluded| dir_name == excluded)
+                } else {
+                    false
+                }
+            }) {
+                continue;
+            }
+            
+            if entry.file_type().is_file() {
+                if let Some(extension) = path.extension() {
+                    if matches!(extension.to_str(), Some("ts") | Some("tsx") | Some("js") | Some("jsx")) {
+                        // Skip excluded files based on configuration
+                        if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
+                            if config.memory.excluded_files.iter().any(|pattern| {
+                                if pattern.contains('*') {
+                                    let regex_pattern = pattern.replace('*', ".*");
+                                    if let Ok(regex) = Regex::new(&regex_pattern) {
+                                        regex.is_match(file_name)
+                                    } else {
+                                        false
+                                    }
+                                } else {
+                                    file_name == pattern
+                                }
+                            }) {
+                                continue;
+                            }
+                        }
+                        
+                        if !scanner.is_target_file(path, target) {
+                            continue;
+                        }
+                        
+                        if let Ok(Some(content)) = target.context.read_source(path) {
+                            let file_patterns = analyze_file_for_patterns(paths::display(path), &content, &leak_patterns)?;
+                            patterns.extend(file_patterns);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    
+    target.ignores()?.retain(&mut patterns, |pattern| (pattern.pattern_type.rule_id(), &pattern.file_path));
+    
+    // Generate basic recommendations
+    if !patterns.is_empty() {
+        recommendations.push("Review identified memory leak patterns and implement proper cleanup".to_string());
+        recommendations.push("Use proper cleanup in useEffect hooks and component unmounting".to_string());
+        recommendations.push("Monitor memory usage during development and testing".to_string());
+    }
+    
+    Ok((patterns, recommendations))
+}
+
+/// The leak patterns in one file's `content`, such as an editor buffer; running processes are not checked
+pub fn check_source(path: &Path, content: &str, config: &Config) -> Result<MemoryReport> {
+    let patterns = if config.memory.check_patterns {
+        analyze_file_for_patterns(paths::display(path), content, &get_memory_leak_patterns(config))?
+    } else {
+        Vec::new()
+    };
+    let summary = calculate_memory_summary(&patterns, &[]);
+    Ok(MemoryReport { patterns, node_processes: Vec::new(), summary, recommendations: Vec::new(), duration_ms: 0, heap: None })
+}
+
+fn get_memory_leak_patterns(config: &Config) -> Vec<PatternRule<PatternType>> {
+    let common_patterns = get_common_patterns();
+    
+    let mut patterns = vec![
+        PatternRule {
+            kind: PatternType::UnremovedEventListener,
+            regex: common_patterns.event_listener.clone(),
+            severity: Severity::High,
+            description: "Event listener added - verify corresponding removal".to_string(),
+            recommendation: "Add removeEventListener in cleanup function or useEffect return".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::TimerLeak,
+            regex: common_patterns.timer_function.clone(),
+            severity: Severity::High,
+            description: "Timer function used - verify cleanup".to_string(),
+            recommendation: "Store timer ID and call clear function in cleanup".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::UnabortedFetch,
+            regex: common_patterns.fetch_call.clone(),
+            severity: Severity::Medium,
+            description: "fetch in useEffect without an AbortController".to_string(),
+            recommendation: "Create an AbortController in the effect, pass its signal to fetch and call controller.abort() in the cleanup so unmounts and fast prop changes don't set stale state".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::UnclosedSubscription,
+            regex: common_patterns.subscription.clone(),
+            severity: Severity::High,
+            description: "Subscription in useEffect is never unsubscribed".to_string(),
+            recommendation: "Return the unsubscribe function from the effect (Zustand, Firebase) or call subscription.unsubscribe() in the cleanup (RxJS)".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::UndisconnectedObserver,
+            regex: common_patterns.observer.clone(),
+            severity: Severity::High,
+            description: "Observer created in useEffect is never disconnected".to_string(),
+            recommendation: "Call observer.disconnect() in the effect cleanup so the observer and the elements it watches can be collected".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::UnboundedArrayGrowth,
+            regex: common_patterns.array_push.clone(),
+            severity: Severity::Medium,
+            description: "Array push without bounds checking".to_string(),
+            recommendation: "Implement array size limits or periodic cleanup".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::UncontrolledLoop,
+            regex: common_patterns.infinite_loop.clone(),
+            severity: Severity::Medium,
+            description: "Potential infinite loop pattern".to_string(),
+            recommendation: "Verify proper exit conditions exist within the loop body".to_string(),
+        },
+        PatternRule {
+            kind: PatternType::ClosureLeak,
+            regex: common_patterns.closure_pattern.clone(),
+            severity: Severity::Low,
+            description: "Nested function closures may retain outer scope".to_string(),
+            recommendation: "Minimize closure scope and avoid unnecessary variable capture".to_string(),
+        },
+    ];
+    
+    // Filter out disabled patterns
+    patterns.retain(|pattern| {
+        let pattern_name = format!("{:?}", pattern.kind);
+        !config.memory.disabled_patterns.contains(&pattern_name)
+    });
+    
+    patterns
+}
+
+fn analyze_file_for_patterns(file_path: String, content: &str, patterns: &[PatternRule<PatternType>]) -> Result<Vec<MemoryPattern>> {
+    let mut file_patterns = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let blocks = scope::blocks(content);
+    
+    for found in scan_lines(content, patterns) {
+        let (line_num, line) = (found.line_number - 1, found.line);
+        let PatternRule { kind: pattern_type, severity, description, recommendation, .. } = found.rule;
+        
+        // Skip if it's in a comment or string literal
+        if is_in_string_literal_or_comment(line) {
+            continue;
+        }
+        
+        // Skip common false positives
+        if should_skip_pattern(pattern_type, line) {
+            continue;
+        }
+        
+        // Listeners, timers, requests and subscriptions only leak when their scope has no matching cleanup
+        if let Some(resource) = resource_for(pattern_type, line) {
+            let call_offset = found.line_offset + found.start;
+            let check = scope::check_cleanup(content, &blocks, call_offset, line, resource);
+            if check.paired {
+                continue;
+            }
+            // Outside components these are usually meant to live as long as the page
+            let component_scoped = matches!(check.scope, scope::Scope::Effect | scope::Scope::Lifecycle);
+            if !component_scoped && matches!(resource, scope::Resource::Fetch | scope::Resource::Subscription | scope::Resource::Observer) {
+                continue;
+            }
+            let (severity, description) = missing_cleanup(resource, check.scope, severity);
+            file_patterns.push(MemoryPattern {
+                file_path: file_path.clone(),
+                line_number: line_num + 1,
+                pattern_type: pattern_type.clone(),
+                code_snippet: line.trim().to_string(),
+                severity,
+                description,
+                recommendation: recommendation.clone(),
+            });
+            continue;
+        }
+        
+        // Special handling for infinite loops - check for break conditions
+        if matches!(pattern_type, PatternType::UncontrolledLoop) {
+            if let Some(loop_context) = analyze_loop_context(&lines, line_num) {
+                if loop_context.has_break_conditions {
+                    // Downgrade severity if break conditions are found
+                    let adjusted_severity = Severity::Low;
+                    let adjusted_description = format!("{} (has exit conditions)", description);
+                    
+                    file_patterns.push(MemoryPattern {
+                        file_path: file_path.clone(),
+                        line_number: line_num + 1,
+                        pattern_type: pattern_type.clone(),
+                        code_snippet: line.trim().to_string(),
+                        severity: adjusted_severity,
+                        description: adjusted_description,
+                        recommendation: "Verify exit conditions are reachable in all execution paths".to_string(),
+                    });
+                    continue;
+                }
+            }
+        }
+        
+        file_patterns.push(MemoryPattern {
+            file_path: file_path.clone(),
+            line_number: line_num + 1,
+            pattern_type: pattern_type.clone(),
+            code_snippet: line.trim().to_string(),
+            severity: severity.clone(),
+            description: description.clone(),
+            recommendation: recommendation.clone(),
+        });
+    }
+    
+    Ok(file_patterns)
+}
+
+fn resource_for(pattern_type: &PatternType, line: &str) -> Option<scope::Resource> {
+    match pattern_type {
+        PatternType::UnremovedEventListener => Some(scope::Resource::Listener),
+        PatternType::TimerLeak if line.contains("setInterval") => Some(scope::Resource::Interval),
+        PatternType::TimerLeak => Some(scope::Resource::Timeout),
+        PatternType::UnabortedFetch => Some(scope::Resource::Fetch),
+        PatternType::UnclosedSubscription => Some(scope::Resource::Subscription),
+        PatternType::UndisconnectedObserver => Some(scope::Resource::Observer),
+        _ => None,
+    }
+}
+
+/// Severity and description for a resource whose scope has no cleanup call.
+/// Only effects and lifecycle methods re-run with the component, so only they keep their severity.
+fn missing_cleanup(resource: scope::Resource, scope: scope::Scope, severity: &Severity) -> (Severity, String) {
+    let (call, cleanup) = resource.calls();
+    match scope {
+        scope::Scope::Effect => (severity.clone(), format!("{} in useEffect without {} in its cleanup", call, cleanup)),
+        scope::Scope::Lifecycle => (severity.clone(), format!("{} in componentDidMount without {} in the component", call, cleanup)),
+        scope::Scope::Function => (Severity::Medium, format!("{} without {} in the same function - verify cleanup", call, cleanup)),
+        scope::Scope::Module => (Severity::Medium, format!("{} at module level without {} - verify cleanup", call, cleanup)),
+    }
+}
+
+#[derive(Debug)]
+struct LoopContext {
+    has_break_conditions: bool,
+    // Unused fields removed: has_return_statements, has_throw_statements, nesting_level
+}
+
+fn analyze_loop_context(lines: &[&str], loop_line: usize) -> Option<LoopContext> {
+    let mut brace_count = 0;
+    let mut found_opening_brace = false;
+    let mut has_break = false;
+    let mut has_return = false;
+    let mut has_throw = false;
+    
+    // Look for the opening brace of the loop
+    for i in loop_line..lines.len().min(loop_line + 20) {
+        let line = lines[i].trim();
+        
+        for ch in line.chars() {
+            match ch {
+                '{' => {
+                    brace_count += 1;
+                    found_opening_brace = true;
+                }
+                '}' => {
+                    brace_count -= 1;
+                    if found_opening_brace && brace_count == 0 {
+                        // End of loop body reached
+                        return Some(LoopContext {
+                            has_break_conditions: has_break || has_return || has_throw,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        
+        if found_opening_brace && brace_count > 0 {
+            // Check for exit conditions within the loop body
+            if line.contains("break") && !line.starts_with("//") {
+                has_break = true;
+            }
+            if line.contains("return") && !line.starts_with("//") {
+                has_return = true;
+            }
+            if line.contains("throw") && !line.starts_with("//") {
+                has_throw = true;
+            }
+            
+            // Also check for common exit patterns
+            if line.contains("if") && (line.contains("break") || line.contains("return")) {
+                has_break = true;
+            }
+        }
+    }
+    
+    if found_opening_brace {
+        Some(LoopContext {
+            has_break_conditions: has_break || has_return || has_throw,
+        })
+    } else {
+        None
+    }
+}
+
+
+/// Check if we should skip a pattern match due to common false positives
+fn should_skip_pattern(pattern_type: &PatternType, line: &str) -> bool {
+    let line_lower = line.to_lowercase();
+    
+    match pattern_type {
+        PatternType::UncontrolledLoop => {
+            // Skip while(true) in libraries or config files
+            line_lower.contains("analytics") ||
+            line_lower.contains("tracking") ||
+            line_lower.contains("polyfill") ||
+            line_lower.contains("shim") ||
+            line_lower.contains("vendor")
+        },
+        PatternType::ClosureLeak => {
+            // Skip common patterns that are typically safe
+            line_lower.contains("react") ||
+            line_lower.contains("component") ||
+            line_lower.contains("hook") ||
+            line_lower.contains("callback") ||
+            line_lower.contains("handler")
+        },
+        PatternType::CircularReference => {
+            // Skip common safe assignments
+            line_lower.contains("this.") ||
+            line_lower.contains("const ") ||
+            line_lower.contains("let ") ||
+            line_lower.contains("var ") ||
+            line_lower.contains("state.") ||
+            line_lower.contains("props.")
+        },
+        PatternType::UnboundedArrayGrowth => {
+            // Skip when array growth is bounded by other means
+            line_lower.contains("map(") ||
+            line_lower.contains("filter(") ||
+            line_lower.contains("reduce(") ||
+            line_lower.contains("foreach(")
+        },
+        _ => false,
+    }
+}
+
+fn generate_memory_recommendations(patterns: &[MemoryPattern], processes: &[NodeProcess]) -> Vec<String> {
+    let mut recommendations = Vec::new();
+    
+    // Pattern-based recommendations
+    let critical_count = patterns.iter().filter(|p| matches!(p.severity, Severity::Critical)).count();
+    let high_count = patterns.iter().filter(|p| matches!(p.severity, Severity::High)).count();
+    
+    if critical_count > 0 {
+        recommendations.push(format!("🚨 {} critical memory issues require immediate attention", critical_count));
+    }
+    
+    if high_count > 0 {
+        recommendations.push(format!("⚠️ {} high-priority memory issues should be addressed", high_count));
+    }
+    
+    // Process-based recommendations
+    let high_memory_processes = processes.iter().filter(|p| matches!(p.status, ProcessStatus::HighMemory | ProcessStatus::MemoryLeak)).count();
+    
+    if high_memory_processes > 0 {
+        recommendations.push(format!("Monitor {} high-memory Node.js processes", high_memory_processes));
+    }
+    
+    // Pattern type specific recommendations
+    let has_event_listeners = patterns.iter().any(|p| matches!(p.pattern_type, PatternType::UnremovedEventListener));
+    let has_timers = patterns.iter().any(|p| matches!(p.pattern_type, PatternType::TimerLeak));
+    
+    if has_event_listeners {
+        recommendations.push("Implement proper event listener cleanup in React useEffect dependencies".to_string());
+    }
+    
+    if has_timers {
+        recommendations.push("Use React useEffect cleanup functions for timers and intervals".to_string());
+    }
+    
+    if patterns.iter().any(|p| matches!(p.pattern_type, PatternType::UnabortedFetch)) {
+        recommendations.push("Abort in-flight requests on unmount, or move data fetching to a library like TanStack Query or SWR".to_string());
+    }
+    
+    if patterns.iter().any(|p| matches!(p.pattern_type, PatternType::UnclosedSubscription | PatternType::UndisconnectedObserver)) {
+        recommendations.push("Return a cleanup from every effect that subscribes or observes; keep each subscription in its own effect".to_string());
+    }
+    
+    // General recommendations
+    recommendations.extend(vec![
+        "Use React DevTools Profiler to identify memory leaks during development".to_string(),
+        "Implement memory monitoring in production environments".to_string(),
+        "Consider using WeakMap and WeakSet for managing object references".to_string(),
+        "Profile memory usage before and after major code changes".to_string(),
+    ]);
+    
+    recommendations
+}
+
+fn calculate_memory_summary(patterns: &[MemoryPattern], processes: &[NodeProcess]) -> MemorySummary {
+    let total_patterns = patterns.len();
+    let critical_issues = patterns.iter().filter(|p| matches!(p.severity, Severity::Critical)).count();
+    let high_issues = patterns.iter().filter(|p| matches!(p.severity, Severity::High)).count();
+    let medium_issues = patterns.iter().filter(|p| matches!(p.severity, Severity::Medium)).count();
+    let low_issues = patterns.iter().filter(|p| matches!(p.severity, Severity::Low)).count();
+    
+    let active_processes = processes.len();
+    let high_memory_processes = processes.iter().filter(|p| matches!(p.status, ProcessStatus::HighMemory | ProcessStatus::MemoryLeak)).count();
+    
+    MemorySummary {
+        total_patterns,
+        critical_issues,
+        high_issues,
+        medium_issues,
+        low_issues,
+        active_processes,
+        high_memory_processes,
+    }
+}
+
+impl Annotate for MemoryReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.patterns.iter()
+            .map(|pattern| {
+                Annotation::new(
+                    AnnotationLevel::for_severity(&pattern.severity),
+                    &pattern.file_path,
+                    pattern.line_number,
+                    format!("Memory leak risk ({:?})", pattern.pattern_type),
+                    format!("{}. {}", pattern.description, pattern.recommendation),
+                )
+                .with_rule(pattern.pattern_type.rule_id())
+            })
+            .collect()
+    }
+}
+
+fn print_memory_report(report: &MemoryReport, listing: ListingOptions, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🧠 Memory Leak Analysis Report".bold().blue());
+        output::line("=============================".blue());
+        output::blank();
+    }
+    
+    if let Some(heap) = &report.heap {
+        print_heap_report(heap);
+        for rec in &report.recommendations {
+            output::line(format!("  💡 {}", rec.green()));
+        }
+        output::blank();
+        output::line(format!("  Analysis time: {}ms", report.duration_ms));
+        return;
+    }
+    
+    // Print memory leak patterns by severity
+    if !listing.is_default() {
+        print_listing(listing_rows(report), listing);
+    } else {
+        let critical_patterns: Vec<_> = report.patterns.iter().filter(|p| matches!(p.severity, Severity::Critical)).collect();
+        let high_patterns: Vec<_> = report.patterns.iter().filter(|p| matches!(p.severity, Severity::High)).collect();
+        let medium_patterns: Vec<_> = report.patterns.iter().filter(|p| matches!(p.severity, Severity::Medium)).collect();
+        let low_patterns: Vec<_> = report.patterns.iter().filter(|p| matches!(p.severity, Severity::Low)).collect();
+    
+        if !critical_patterns.is_empty() {
+            output::line("🚨 CRITICAL MEMORY ISSUES".bold().red());
+            output::line("───────────────────────────".red());
+            for pattern in critical_patterns {
+                print_memory_pattern(pattern);
+            }
+            output::blank();
+        }
+    
+        if !high_patterns.is_empty() {
+            output::line("⚠️  HIGH PRIORITY ISSUES".bold().yellow());
+            output::line("───────────────────────".yellow());
+            for pattern in high_patterns {
+                print_memory_pattern(pattern);
+            }
+            output::blank();
+        }
+    
+        if !medium_patterns.is_empty() {
+            output::line("📋 MEDIUM PRIORITY ISSUES".bold().white());
+            output::line("────────────────────────".white());
+            for pattern in medium_patterns {
+                print_memory_pattern(pattern);
+            }
+            output::blank();
+        }
+    
+        if !low_patterns.is_empty() && !quiet {
+            output::line("ℹ️  LOW PRIORITY ISSUES".bold().cyan());
+            output::line("──────────────────────".cyan());
+            for pattern in low_patterns {
+                print_memory_pattern(pattern);
+            }
+            output::blank();
+        }
+    }
+    
+    // Print Node.js processes
+    if !report.node_processes.is_empty() {
+        output::line("🔄 NODE.JS PROCESSES".bold().white());
+        output::line("────────────────────".white());
+        
+        let mut depths: HashMap<u32, usize> = HashMap::new();
+        for process in &report.node_processes {
+            let depth = process.parent_pid.and_then(|parent| depths.get(&parent)).map(|d| d + 1).unwrap_or(0);
+            depths.insert(process.pid, depth);
+            let indent = "   ".repeat(depth);
+            let (status_icon, status_color) = match process.status {
+                ProcessStatus::Normal => ("✅", "green"),
+                ProcessStatus::HighMemory => ("⚠️", "yellow"),
+                ProcessStatus::MemoryLeak => ("🚨", "red"),
+                ProcessStatus::Unresponsive => ("💀", "red"),
+            };
+            
+            let memory_text = format!("{:.1}MB", process.memory_usage_mb);
+            let colored_memory = match status_color {
+                "green" => memory_text.green(),
+                "yellow" => memory_text.yellow(),
+                "red" => memory_text.red(),
+                _ => memory_text.white(),
+            };
+            
+            let tree_text = if process.tree_memory_mb > process.memory_usage_mb + 0.05 {
+                format!(" | Tree: {:.1}MB", process.tree_memory_mb)
+            } else {
+                String::new()
+            };
+            
+            output::line(format!("  {}{} PID: {} | Memory: {}{} | CPU: {:.1}%", 
+                indent,
+                status_icon, 
+                process.pid, 
+                colored_memory,
+                tree_text,
+                process.cpu_usage
+            ));
+            output::line(format!("  {}   {}", indent, process.command.dimmed()));
+        }
+        output::blank();
+    }
+    
+    // Print recommendations
+    if !report.recommendations.is_empty() {
+        output::line("💡 RECOMMENDATIONS".bold().green());
+        output::line("──────────────────".green());
+        for rec in &report.recommendations {
+            output::line(format!("  • {}", rec.green()));
+        }
+        output::blank();
+    }
+    
+    // Print summary
+    print_memory_summary(&report.summary, report.duration_ms);
+}
+
+/// Leak patterns for `--group-by` and `--sort`
+fn listing_rows(report: &MemoryReport) -> Vec<Row> {
+    report.patterns.iter().map(|pattern| Row {
+        path: pattern.file_path.clone(),
+        line: Some(pattern.line_number),
+        rule: format!("{:?}", pattern.pattern_type),
+        severity: pattern.severity.clone(),
+        lines: None,
+        size_bytes: None,
+        text: pattern.description.clone(),
+    }).collect()
+}
+
+fn print_heap_report(heap: &HeapReport) {
+    let kb = |bytes: u64| format!("{:.1} KB", bytes as f64 / 1024.0);
+    
+    for snapshot in &heap.snapshots {
+        output::line(format!("📸 {}", snapshot.file).bold().white());
+        output::line(format!("  {} objects, {} total", snapshot.node_count, kb(snapshot.total_size)));
+        output::blank();
+        
+        output::line("📦 LARGEST RETAINERS".bold().white());
+        output::line("────────────────────".white());
+        for retainer in &snapshot.largest_retainers {
+            output::line(format!("  {:>12}  {} {}",
+                kb(retainer.retained_size).yellow(),
+                retainer.name.bold(),
+                format!("({}, @{}, self {})", retainer.node_type, retainer.id, kb(retainer.self_size)).dimmed()
+            ));
+        }
+        output::blank();
+        
+        output::line("🧩 OBJECTS BY CONSTRUCTOR".bold().white());
+        output::line("─────────────────────────".white());
+        for stats in snapshot.constructors.iter().take(10) {
+            output::line(format!("  {:>12}  {} {}", kb(stats.self_size), stats.name, format!("×{}", stats.count).dimmed()));
+        }
+        output::blank();
+        
+        if snapshot.detached_dom_nodes > 0 {
+            output::line(format!("🚨 DETACHED DOM NODES: {}", snapshot.detached_dom_nodes).bold().red());
+            for stats in &snapshot.detached {
+                output::line(format!("  {} {}", stats.name.red(), format!("×{}", stats.count).dimmed()));
+            }
+        } else {
+            output::line("✅ No detached DOM nodes".green());
+        }
+        output::blank();
+    }
+    
+    if let Some(growth) = &heap.growth {
+        output::line("📈 GROWTH BETWEEN SNAPSHOTS".bold().white());
+        output::line("───────────────────────────".white());
+        if growth.is_empty() {
+            output::line(format!("  {}", "No constructor gained objects".green()));
+        }
+        for change in growth {
+            output::line(format!("  {} {} → {} {}",
+                change.name.bold(),
+                change.count_before,
+                change.count_after.to_string().yellow(),
+                format!("({} → {})", kb(change.size_before), kb(change.size_after)).dimmed()
+            ));
+        }
+        output::blank();
+    }
+}
+
+fn print_memory_pattern(pattern: &MemoryPattern) {
+    let severity_icon = match pattern.severity {
+        Severity::Critical => "🚨".red(),
+        Severity::High => "⚠️".yellow(),
+        Severity::Medium => "📋".white(),
+        Severity::Low => "ℹ️".cyan(),
+        Severity::Info => "ℹ️".blue(),
+    };
+    
+    output::line(format!("  {} {}:{}", severity_icon, pattern.file_path, pattern.line_number));
+    output::line(format!("     {}", pattern.code_snippet.dimmed()));
+    output::line(format!("     {}", pattern.description.yellow()));
+    output::line(format!("     💡 {}", pattern.recommendation.green()));
+    output::blank();
+}
+
+fn print_memory_summary(summary: &MemorySummary, duration_ms: u64) {
+    output::line("📊 MEMORY ANALYSIS SUMMARY".bold().white());
+    output::line("─────────────────────────".white());
+    
+    output::line(format!("  Total patterns found: {}", summary.total_patterns));
+    
+    if summary.critical_issues > 0 {
+        output::line(format!("  {} {}", "Critical issues:".red(), summary.critical_issues.to_string().red()));
+    }
+    if summary.high_issues > 0 {
+        output::line(format!("  {} {}", "High priority:".yellow(), summary.high_issues.to_string().yellow()));
+    }
+    if summary.medium_issues > 0 {
+        output::line(format!("  {} {}", "Medium priority:".white(), summary.medium_issues.to_string().white()));
+    }
+    if summary.low_issues > 0 {
+        output::line(format!("  {} {}", "Low priority:".cyan(), summary.low_issues.to_string().cyan()));
+    }
+    
+    output::line(format!("  Active Node.js processes: {}", summary.active_processes));
+    if summary.high_memory_processes > 0 {
+        output::line(format!("  {} {}", "High memory processes:".red(), summary.high_memory_processes.to_string().red()));
+    }
+    
+    output::line(format!("  Analysis time: {}ms", duration_ms));
+    output::blank();
+    
+    // Overall assessment
+    let overall_status = if summary.critical_issues > 0 {
+        ("🚨", "CRITICAL MEMORY ISSUES DETECTED", "red")
+    } else if summary.high_issues > 3 || summary.high_memory_processes > 2 {
+        ("⚠️", "MEMORY ISSUES NEED ATTENTION", "yellow")
+    } else if summary.total_patterns > 0 {
+        ("📋", "MINOR MEMORY CONCERNS", "white")
+    } else {
+        ("✅", "NO MAJOR MEMORY ISSUES", "green")
+    };
+    
+    let colored_status = match overall_status.2 {
+        "green" => format!("{} {}", overall_status.0, overall_status.1).green().bold(),
+        "yellow" => format!("{} {}", overall_status.0, overall_status.1).yellow().bold(),
+        "red" => format!("{} {}", overall_status.0, overall_status.1).red().bold(),
+        _ => format!("{} {}", overall_status.0, overall_status.1).white().bold(),
+    };
+    
+    output::line(format!("  Status: {}", colored_status));
+    
+    if summary.critical_issues > 0 || summary.high_memory_processes > 2 {
+        output::blank();
+        output::line("🎯 ACTION REQUIRED".bold().red());
+        output::line("─────────────────".red());
+        if summary.critical_issues > 0 {
+            output::line("  • Fix critical memory leak patterns immediately");
+        }
+        if summary.high_memory_processes > 2 {
+            output::line("  • Investigate high-memory Node.js processes");
+        }
+        output::line("  • Monitor memory usage during development");
+        output::line("  • Set up memory alerts in production");
+    }
+    
+    output::blank();
+    // Dynamic tip based on system memory
+    let system_info = processes::get_system_memory_info();
+    let recommended_node_memory = (system_info.total_memory_gb * 1024.0 * 0.5) as u32; // 50% of system RAM
+    let recommended_node_memory = recommended_node_memory.min(8192).max(2048); // Clamp between 2GB-8GB
+    
+    output::line(format!("💡 TIP: Use 'node --max-old-space-size={}' to optimize Node.js memory limit for your system ({:.1}GB RAM)", 
+        recommended_node_memory, system_info.total_memory_gb).dimmed());
+}
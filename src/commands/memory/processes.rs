@@ -0,0 +1,262 @@
+use std::collections::{HashMap, HashSet};
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, MemoryRefreshKind, System, UpdateKind};
+use super::{NodeProcess, ProcessStatus};
+
+/// Process names that start a JavaScript toolchain tree (`npm run dev`, `node`, `next-server`, ...)
+const NODE_PROCESS_NAMES: &[&str] = &["node", "npm", "npx", "yarn", "pnpm", "bun", "deno", "next-server"];
+
+#[derive(Debug, Clone)]
+pub struct SystemMemoryInfo {
+    pub total_memory_gb: f64,
+    pub high_memory_threshold_mb: f64,
+    pub critical_memory_threshold_mb: f64,
+}
+
+/// Total RAM and the per-process thresholds derived from it
+pub fn get_system_memory_info() -> SystemMemoryInfo {
+    let system = System::new_with_specifics(RefreshKind::nothing().with_memory(MemoryRefreshKind::nothing().with_ram()));
+    let total_memory_gb = match system.total_memory() {
+        0 => 8.0,
+        bytes => bytes as f64 / 1024.0 / 1024.0 / 1024.0,
+    };
+
+    SystemMemoryInfo {
+        total_memory_gb,
+        high_memory_threshold_mb: (total_memory_gb * 1024.0 * 0.05).max(256.0), // 5% of RAM, min 256MB
+        critical_memory_threshold_mb: (total_memory_gb * 1024.0 * 0.15).max(512.0), // 15% of RAM, min 512MB
+    }
+}
+
+/// Node.js processes and everything they started, in tree order (parents before children)
+pub async fn check_node_processes() -> Vec<NodeProcess> {
+    let refresh = ProcessRefreshKind::nothing()
+        .with_memory()
+        .with_cpu()
+        .with_cmd(UpdateKind::OnlyIfNotSet);
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+    // CPU usage is measured between two refreshes
+    tokio::time::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).await;
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+
+    let system_info = get_system_memory_info();
+    let own_pid = sysinfo::get_current_pid().ok();
+    // Linux lists threads alongside processes
+    let processes: HashMap<Pid, &Process> = system.processes().iter()
+        .filter(|(_, process)| process.thread_kind().is_none())
+        .map(|(pid, process)| (*pid, process))
+        .collect();
+    let processes = &processes;
+
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (pid, process) in processes.iter() {
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(*pid);
+        }
+    }
+    for pids in children.values_mut() {
+        pids.sort();
+    }
+
+    let is_node = |pid: &Pid| Some(*pid) != own_pid && processes.get(pid).is_some_and(is_node_process);
+    let mut roots: Vec<Pid> = processes.keys()
+        .filter(|pid| is_node(pid))
+        .filter(|pid| !has_node_ancestor(**pid, processes, &is_node))
+        .copied()
+        .collect();
+    roots.sort();
+
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    for root in roots {
+        let mut stack = vec![root];
+        while let Some(pid) = stack.pop() {
+            if !seen.insert(pid) || Some(pid) == own_pid {
+                continue;
+            }
+            let Some(&process) = processes.get(&pid) else { continue };
+            result.push(node_process(process, tree_memory(pid, processes, &children), &system_info));
+            if let Some(kids) = children.get(&pid) {
+                stack.extend(kids.iter().rev());
+            }
+        }
+    }
+    result
+}
+
+/// What the process tree needs to know of a process
+trait ProcessInfo {
+    fn parent(&self) -> Option<Pid>;
+    fn name(&self) -> String;
+    fn memory(&self) -> u64;
+}
+
+impl ProcessInfo for &Process {
+    fn parent(&self) -> Option<Pid> {
+        Process::parent(self)
+    }
+
+    fn name(&self) -> String {
+        Process::name(self).to_string_lossy().to_string()
+    }
+
+    fn memory(&self) -> u64 {
+        Process::memory(self)
+    }
+}
+
+fn is_node_process(process: &impl ProcessInfo) -> bool {
+    let name = process.name().to_lowercase();
+    let name = name.strip_suffix(".exe").unwrap_or(&name);
+    NODE_PROCESS_NAMES.iter().any(|node| name == *node || name.starts_with(&format!("{} ", node)))
+}
+
+/// `npm run dev` -> `sh` -> `node`: the inner node belongs to the npm tree, not a tree of its own
+fn has_node_ancestor(pid: Pid, processes: &HashMap<Pid, impl ProcessInfo>, is_node: &impl Fn(&Pid) -> bool) -> bool {
+    let mut seen = HashSet::new();
+    let mut current = processes.get(&pid).and_then(|process| process.parent());
+    while let Some(parent) = current {
+        if !seen.insert(parent) {
+            return false;
+        }
+        if is_node(&parent) {
+            return true;
+        }
+        current = processes.get(&parent).and_then(|process| process.parent());
+    }
+    false
+}
+
+fn tree_memory(pid: Pid, processes: &HashMap<Pid, impl ProcessInfo>, children: &HashMap<Pid, Vec<Pid>>) -> u64 {
+    let mut total = 0;
+    let mut stack = vec![pid];
+    let mut seen = HashSet::new();
+    while let Some(pid) = stack.pop() {
+        if !seen.insert(pid) {
+            continue;
+        }
+        total += processes.get(&pid).map(|process| process.memory()).unwrap_or(0);
+        stack.extend(children.get(&pid).into_iter().flatten());
+    }
+    total
+}
+
+fn node_process(process: &Process, tree_bytes: u64, system_info: &SystemMemoryInfo) -> NodeProcess {
+    let memory_usage_mb = process.memory() as f64 / 1024.0 / 1024.0;
+    let status = if memory_usage_mb > system_info.critical_memory_threshold_mb {
+        ProcessStatus::MemoryLeak
+    } else if memory_usage_mb > system_info.high_memory_threshold_mb {
+        ProcessStatus::HighMemory
+    } else {
+        ProcessStatus::Normal
+    };
+
+    let command = if process.cmd().is_empty() {
+        process.name().to_string_lossy().to_string()
+    } else {
+        process.cmd().iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>().join(" ")
+    };
+
+    NodeProcess {
+        pid: process.pid().as_u32(),
+        parent_pid: process.parent().map(|pid| pid.as_u32()),
+        memory_usage_mb,
+        tree_memory_mb: tree_bytes as f64 / 1024.0 / 1024.0,
+        cpu_usage: process.cpu_usage() as f64,
+        command: command.chars().take(80).collect(), // Truncate long commands
+        status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl ProcessInfo for (Option<Pid>, &str, u64) {
+        fn parent(&self) -> Option<Pid> {
+            self.0
+        }
+
+        fn name(&self) -> String {
+            self.1.to_string()
+        }
+
+        fn memory(&self) -> u64 {
+            self.2
+        }
+    }
+
+    /// pid -> (parent, name, memory)
+    fn processes(entries: &[(u32, Option<u32>, &'static str, u64)]) -> HashMap<Pid, (Option<Pid>, &'static str, u64)> {
+        entries.iter()
+            .map(|&(pid, parent, name, memory)| (Pid::from_u32(pid), (parent.map(Pid::from_u32), name, memory)))
+            .collect()
+    }
+
+    fn children(processes: &HashMap<Pid, (Option<Pid>, &'static str, u64)>) -> HashMap<Pid, Vec<Pid>> {
+        let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+        for (pid, process) in processes {
+            if let Some(parent) = process.0 {
+                children.entry(parent).or_default().push(*pid);
+            }
+        }
+        children
+    }
+
+    #[test]
+    fn test_node_process_names() {
+        assert!(is_node_process(&(None, "node", 0)));
+        assert!(is_node_process(&(None, "Node.exe", 0)));
+        assert!(is_node_process(&(None, "next-server (v14.2.3)", 0)));
+        assert!(!is_node_process(&(None, "nodemon-helper", 0)));
+        assert!(!is_node_process(&(None, "bash", 0)));
+    }
+
+    #[test]
+    fn test_nested_node_children_belong_to_the_outer_tree() {
+        // npm run dev -> sh -> node -> node worker
+        let processes = processes(&[
+            (1, None, "launchd", 10),
+            (10, Some(1), "npm", 100),
+            (11, Some(10), "sh", 5),
+            (12, Some(11), "node", 300),
+            (13, Some(12), "node", 200),
+        ]);
+        let is_node = |pid: &Pid| processes.get(pid).is_some_and(is_node_process);
+
+        assert!(!has_node_ancestor(Pid::from_u32(10), &processes, &is_node));
+        assert!(has_node_ancestor(Pid::from_u32(12), &processes, &is_node));
+        assert!(has_node_ancestor(Pid::from_u32(13), &processes, &is_node));
+        assert_eq!(tree_memory(Pid::from_u32(10), &processes, &children(&processes)), 605);
+        assert_eq!(tree_memory(Pid::from_u32(12), &processes, &children(&processes)), 500);
+    }
+
+    #[test]
+    fn test_node_under_a_non_node_parent_is_a_root() {
+        let processes = processes(&[
+            (1, None, "systemd", 10),
+            (20, Some(1), "bash", 5),
+            (21, Some(20), "node", 300),
+        ]);
+        let is_node = |pid: &Pid| processes.get(pid).is_some_and(is_node_process);
+
+        assert!(!has_node_ancestor(Pid::from_u32(21), &processes, &is_node));
+        assert_eq!(tree_memory(Pid::from_u32(21), &processes, &children(&processes)), 300);
+        // The shell's tree includes the node process it started
+        assert_eq!(tree_memory(Pid::from_u32(20), &processes, &children(&processes)), 305);
+    }
+
+    #[test]
+    fn test_parent_pid_cycle_terminates() {
+        // Reused pids can make two processes each other's parent
+        let processes = processes(&[
+            (30, Some(31), "sh", 5),
+            (31, Some(30), "bash", 7),
+            (32, Some(31), "node", 300),
+        ]);
+        let is_node = |pid: &Pid| processes.get(pid).is_some_and(is_node_process);
+
+        assert!(!has_node_ancestor(Pid::from_u32(32), &processes, &is_node));
+        assert_eq!(tree_memory(Pid::from_u32(30), &processes, &children(&processes)), 312);
+    }
+}
@@ -0,0 +1,294 @@
+use regex::Regex;
+use std::sync::OnceLock;
+
+/// Where a listener or timer is set up, which decides where its cleanup has to be
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Scope {
+    /// `useEffect` / `useLayoutEffect` / `useInsertionEffect` callback
+    Effect,
+    /// `componentDidMount`; the cleanup belongs in another method of the same class
+    Lifecycle,
+    Function,
+    Module,
+}
+
+/// What was set up, which decides the cleanup call to look for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Resource {
+    Listener,
+    Interval,
+    Timeout,
+    Fetch,
+    Subscription,
+    Observer,
+}
+
+impl Resource {
+    /// (set-up call, cleanup) for descriptions
+    pub fn calls(self) -> (&'static str, &'static str) {
+        match self {
+            Resource::Listener => ("addEventListener", "removeEventListener"),
+            Resource::Interval => ("setInterval", "clearInterval"),
+            Resource::Timeout => ("setTimeout", "clearTimeout"),
+            Resource::Fetch => ("fetch", "an AbortController"),
+            Resource::Subscription => ("subscribe", "unsubscribe"),
+            Resource::Observer => ("Observer", "disconnect"),
+        }
+    }
+}
+
+/// Result of looking for the cleanup that pairs with a listener or timer
+#[derive(Debug, PartialEq)]
+pub(super) struct CleanupCheck {
+    pub scope: Scope,
+    pub paired: bool,
+}
+
+struct ScopePatterns {
+    effect: Regex,
+    lifecycle: Regex,
+    function: Regex,
+    control: Regex,
+    event_name: Regex,
+    binding: Regex,
+    ignore_flag: Regex,
+}
+
+static PATTERNS: OnceLock<ScopePatterns> = OnceLock::new();
+
+fn patterns() -> &'static ScopePatterns {
+    PATTERNS.get_or_init(|| ScopePatterns {
+        effect: Regex::new(r"\buse(?:Layout|Insertion)?Effect\s*\(\s*(?:async\s+)?(?:\(\s*\)\s*=>|function\s*\w*\s*\(\s*\))\s*$").unwrap(),
+        lifecycle: Regex::new(r"\bcomponentDidMount\s*(?:\(\s*\)|=\s*\(\s*\)\s*=>)\s*$").unwrap(),
+        function: Regex::new(r"(?:=>|\))\s*$").unwrap(),
+        control: Regex::new(r"\b(?:if|for|while|switch|catch|with)\s*\(.*\)\s*$").unwrap(),
+        event_name: Regex::new(r#"addEventListener\(\s*['"`]([^'"`]+)['"`]"#).unwrap(),
+        binding: Regex::new(r"(?:const|let|var)\s+(\w+)\s*=").unwrap(),
+        ignore_flag: Regex::new(r"\blet\s+(?:ignore|cancelled|canceled|active|isMounted|mounted)\s*=").unwrap(),
+    })
+}
+
+/// Spans (`{` offset, matching `}` offset) of every brace block, skipping strings and comments
+pub(super) fn blocks(content: &str) -> Vec<(usize, usize)> {
+    let bytes = content.as_bytes();
+    let mut spans = Vec::new();
+    let mut open = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = content[i..].find('\n').map(|n| i + n).unwrap_or(bytes.len());
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = content[i + 2..].find("*/").map(|n| i + n + 3).unwrap_or(bytes.len());
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                i += 1;
+                // Only template literals span lines; a stray apostrophe in JSX text ends at the newline
+                while i < bytes.len() && bytes[i] != quote && (quote == b'`' || bytes[i] != b'\n') {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'{' => open.push(i),
+            b'}' => {
+                if let Some(start) = open.pop() {
+                    spans.push((start, i));
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    // Unclosed blocks run to the end of the file
+    spans.extend(open.into_iter().map(|start| (start, bytes.len())));
+    spans.sort_unstable();
+    spans
+}
+
+/// Find the effect, lifecycle method or function that sets up the listener or timer
+/// at `offset` and check whether the matching cleanup call is in it
+pub(super) fn check_cleanup(content: &str, blocks: &[(usize, usize)], offset: usize, call: &str, resource: Resource) -> CleanupCheck {
+    let patterns = patterns();
+    // Innermost first
+    let enclosing: Vec<(usize, usize)> = blocks.iter()
+        .rev()
+        .filter(|(start, end)| *start < offset && offset < *end)
+        .copied()
+        .collect();
+    let header = |start: usize| {
+        let before = content[..start].trim_end();
+        let from = before.char_indices().rev().nth(200).map(|(i, _)| i).unwrap_or(0);
+        &before[from..]
+    };
+
+    let mut chosen = None;
+    for (index, &(start, end)) in enclosing.iter().enumerate() {
+        let header = header(start);
+        if patterns.effect.is_match(header) {
+            chosen = Some((Scope::Effect, start, end));
+            break;
+        }
+        if patterns.lifecycle.is_match(header) {
+            // componentWillUnmount lives next to componentDidMount in the class body
+            let (start, end) = enclosing.get(index + 1).copied().unwrap_or((start, end));
+            chosen = Some((Scope::Lifecycle, start, end));
+            break;
+        }
+    }
+    let (scope, start, end) = chosen
+        .or_else(|| {
+            enclosing.iter()
+                .find(|(start, _)| {
+                    let header = header(*start);
+                    let line = header.rsplit('\n').next().unwrap_or(header);
+                    patterns.function.is_match(header) && !patterns.control.is_match(line)
+                })
+                .map(|&(start, end)| (Scope::Function, start, end))
+        })
+        .unwrap_or((Scope::Module, 0, content.len()));
+
+    let body = &content[start..end.min(content.len())];
+    CleanupCheck { scope, paired: has_cleanup(body, call, resource) }
+}
+
+fn has_cleanup(body: &str, call: &str, resource: Resource) -> bool {
+    match resource {
+        Resource::Interval => return body.contains("clearInterval("),
+        Resource::Timeout => return body.contains("clearTimeout("),
+        // React's docs also accept an `ignore` flag set in the cleanup
+        Resource::Fetch => return body.contains("AbortController") || patterns().ignore_flag.is_match(body),
+        Resource::Observer => return body.contains(".disconnect("),
+        Resource::Subscription => return has_unsubscribe(body, call),
+        Resource::Listener => {}
+    }
+
+    // `{ once: true }` removes itself; a `signal` is removed by `controller.abort()`
+    if call.contains("once: true") || call.contains("once:true") {
+        return true;
+    }
+    if call.contains("signal") && body.contains(".abort(") {
+        return true;
+    }
+    match patterns().event_name.captures(call) {
+        Some(caps) => {
+            let event = regex::escape(&caps[1]);
+            Regex::new(&format!(r#"removeEventListener\(\s*['"`]{}['"`]"#, event))
+                .map(|removal| removal.is_match(body))
+                .unwrap_or(false)
+        }
+        // Event name in a variable; any removal in scope is the best we can check
+        None => body.contains("removeEventListener("),
+    }
+}
+
+/// RxJS returns a Subscription; Zustand, Firebase and most stores return an unsubscribe function
+fn has_unsubscribe(body: &str, call: &str) -> bool {
+    let call = call.trim_start();
+    if call.starts_with("return ") || call.contains("takeUntil(") || body.contains(".unsubscribe(") {
+        return true;
+    }
+    match patterns().binding.captures(call) {
+        Some(caps) => {
+            let name = regex::escape(&caps[1]);
+            Regex::new(&format!(r"\b{}\s*\(|return\s+{}\b|\b{}\s*\(\s*\)", name, name, name))
+                .map(|cleanup| cleanup.is_match(body))
+                .unwrap_or(false)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(content: &str, needle: &str) -> CleanupCheck {
+        let offset = content.find(needle).unwrap();
+        let line_start = content[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let line = content[line_start..].lines().next().unwrap();
+        let resource = match needle {
+            n if n.contains("setInterval") => Resource::Interval,
+            n if n.contains("subscribe") || n.contains("onSnapshot") => Resource::Subscription,
+            n if n.contains("fetch") => Resource::Fetch,
+            n if n.contains("Observer") => Resource::Observer,
+            _ => Resource::Listener,
+        };
+        check_cleanup(content, &blocks(content), offset, line, resource)
+    }
+
+    #[test]
+    fn test_effect_cleanup_is_paired() {
+        let content = r#"
+export function Window() {
+  useEffect(() => {
+    const onResize = () => { setWidth(window.innerWidth) };
+    window.addEventListener('resize', onResize);
+    const id = setInterval(() => { tick() }, 1000);
+    return () => {
+      window.removeEventListener('resize', onResize);
+    };
+  }, []);
+}
+"#;
+        assert_eq!(check(content, "window.addEventListener"), CleanupCheck { scope: Scope::Effect, paired: true });
+        assert_eq!(check(content, "setInterval"), CleanupCheck { scope: Scope::Effect, paired: false });
+    }
+
+    #[test]
+    fn test_cleanup_in_another_effect_does_not_count() {
+        let content = r#"
+function Keys() {
+  useEffect(() => {
+    document.addEventListener("keydown", onKey);
+  }, []);
+  useEffect(() => {
+    return () => document.removeEventListener("keydown", onKey);
+  }, []);
+}
+"#;
+        assert_eq!(check(content, "document.addEventListener"), CleanupCheck { scope: Scope::Effect, paired: false });
+    }
+
+    #[test]
+    fn test_lifecycle_and_self_removing_listeners() {
+        let content = r#"
+class Chart extends React.Component {
+  componentDidMount() {
+    window.addEventListener('scroll', this.onScroll);
+    button.addEventListener('click', handler, { once: true });
+  }
+  componentWillUnmount() {
+    window.removeEventListener('scroll', this.onScroll);
+  }
+}
+"#;
+        assert_eq!(check(content, "window.addEventListener"), CleanupCheck { scope: Scope::Lifecycle, paired: true });
+        assert!(check(content, "button.addEventListener").paired);
+    }
+
+    #[test]
+    fn test_fetch_subscription_and_observer_cleanups() {
+        let content = r#"
+function Profile({ id }) {
+  useEffect(() => {
+    fetch(`/api/users/${id}`).then(r => r.json()).then(setUser);
+    const unsubscribe = onSnapshot(doc(db, "users", id), setDoc);
+    const sub = price$.subscribe(setPrice);
+    const observer = new ResizeObserver(onResize);
+    observer.observe(ref.current);
+    return () => {
+      unsubscribe();
+      observer.disconnect();
+    };
+  }, [id]);
+}
+"#;
+        assert!(!check(content, "fetch(").paired);
+        assert!(check(content, "onSnapshot").paired);
+        assert!(!check(content, "price$.subscribe").paired);
+        assert!(check(content, "new ResizeObserver").paired);
+    }
+}
@@ -1,77 +1,547 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use colored::*;
+use dialoguer::theme::ColorfulTheme;
+use dialoguer::{Confirm, FuzzySelect, Input};
+use std::io::IsTerminal;
+use std::process::Command;
+use std::time::Instant;
+use crate::common::report_formatter::{plain, prompt_theme};
+use crate::common::output;
+
+/// How many finished runs the results pane keeps
+const RESULTS_SHOWN: usize = 5;
+
+/// A command the menu can run, with the options it asks for before running it
+struct MenuEntry {
+    icon: &'static str,
+    args: &'static [&'static str],
+    title: &'static str,
+    description: &'static str,
+    prompts: &'static [Prompt],
+    /// Whether to offer the global `--json` flag
+    json: bool,
+}
+
+enum Prompt {
+    /// `--flag <N>`, asked with a default
+    Number { flag: &'static str, label: &'static str, default: usize },
+    /// `--flag`, asked as yes/no
+    Switch { flag: &'static str, label: &'static str },
+    /// `--flag <VALUE>`, left out when the answer is empty
+    Text { flag: &'static str, label: &'static str },
+}
+
+enum Answer {
+    Number(usize),
+    Switch(bool),
+    Text(String),
+}
+
+const ENTRIES: &[MenuEntry] = &[
+    MenuEntry {
+        icon: "🚀",
+        args: &["deploy"],
+        title: "Run full deploy pipeline",
+        description: "Every pre-deployment check in one go",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["large"],
+        title: "Large Files",
+        description: "Find \"smelly code\" files over the line threshold",
+        prompts: &[Prompt::Number { flag: "--threshold", label: "Line threshold", default: 100 }],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["complexity"],
+        title: "Complexity",
+        description: "Find functions with high cyclomatic or cognitive complexity",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["components"],
+        title: "Component Analysis",
+        description: "Analyze and split large React/Vue/Angular components",
+        prompts: &[Prompt::Number { flag: "--threshold", label: "Line threshold", default: 100 }],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["imports"],
+        title: "Unused Imports",
+        description: "Detect unused and broken imports",
+        prompts: &[Prompt::Switch { flag: "--staged", label: "Only files staged in git?" }],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["types"],
+        title: "TypeScript Coverage",
+        description: "Check TypeScript type coverage and quality",
+        prompts: &[
+            Prompt::Switch { flag: "--tsc", label: "Also run the TypeScript compiler?" },
+            Prompt::Switch { flag: "--list-any", label: "List every 'any' usage?" },
+        ],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["console"],
+        title: "Debug Statements",
+        description: "Find console.log, debugger, alert() and TODO/FIXME markers",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["lint"],
+        title: "Lint Results",
+        description: "Run ESLint or Biome and merge its findings",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["tailwind"],
+        title: "Tailwind Audit",
+        description: "Check Tailwind classes against the theme and content config",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["i18n"],
+        title: "Translations",
+        description: "Find hardcoded UI text and missing or unused translation keys",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["tests"],
+        title: "Test Files",
+        description: "Find source files without tests and skipped tests",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🔍",
+        args: &["duplicates"],
+        title: "Duplicate Code",
+        description: "Find copy-pasted blocks worth consolidating",
+        prompts: &[Prompt::Number { flag: "--min-lines", label: "Minimum clone length (lines)", default: 10 }],
+        json: true,
+    },
+    MenuEntry {
+        icon: "📊",
+        args: &["context"],
+        title: "Project Context",
+        description: "Analyze project structure and provide insights",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🗺️",
+        args: &["routes"],
+        title: "Routes",
+        description: "Pages, API routes, rendering modes and auth guards",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "♿",
+        args: &["a11y"],
+        title: "Accessibility",
+        description: "Find common accessibility issues in JSX",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "📊",
+        args: &["bundle"],
+        title: "Bundle Analysis",
+        description: "Analyze bundle size and optimization opportunities",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "📊",
+        args: &["images"],
+        title: "Image Audit",
+        description: "Find oversized, unoptimized and legacy-format images",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "📊",
+        args: &["perf"],
+        title: "Performance Audit",
+        description: "Run Lighthouse performance audits",
+        prompts: &[Prompt::Text { flag: "--url", label: "URL to audit (empty to detect)" }],
+        json: true,
+    },
+    MenuEntry {
+        icon: "📊",
+        args: &["memory"],
+        title: "Memory Check",
+        description: "Detect memory leaks during development",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "📊",
+        args: &["report"],
+        title: "Health Report",
+        description: "Score the project across several analyses",
+        prompts: &[Prompt::Text { flag: "--output", label: "Also write to file (.html or .json, empty to skip)" }],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🛡️",
+        args: &["env"],
+        title: "Environment Check",
+        description: "Validate environment variables",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🛡️",
+        args: &["secrets"],
+        title: "Secret Scan",
+        description: "Find API keys, tokens and credentials in source files",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🛡️",
+        args: &["outdated"],
+        title: "Outdated Dependencies",
+        description: "Latest versions, deprecations and upgrade risk from the npm registry",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🛡️",
+        args: &["vulns"],
+        title: "Vulnerabilities",
+        description: "Known security advisories for installed packages from OSV",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🐳",
+        args: &["docker"],
+        title: "Docker",
+        description: "Node.js base image, .dockerignore, devDependencies and multi-stage builds",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "⚙️",
+        args: &["ci"],
+        title: "CI Workflows",
+        description: "Build, type-check, test and sniff steps, caching and Node.js versions in CI",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🧭",
+        args: &["boundaries"],
+        title: "Server/Client Boundaries",
+        description: "'use client' boundaries in the app router with their estimated JavaScript weight",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "▲",
+        args: &["nextjs"],
+        title: "Next.js Data Fetching",
+        description: "Uncached fetch, needless getServerSideProps, client fetching of static data and missing revalidate",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "🛡️",
+        args: &["security"],
+        title: "Security Scan",
+        description: "Find XSS, injection, eval and open-redirect patterns",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "⚙️",
+        args: &["init"],
+        title: "Set Up Project",
+        description: "Tailored config, .gitignore, git hooks and CI workflow",
+        prompts: &[],
+        json: false,
+    },
+    MenuEntry {
+        icon: "⚙️",
+        args: &["doctor"],
+        title: "Doctor",
+        description: "Check the tools, files and config the analyzers depend on",
+        prompts: &[],
+        json: true,
+    },
+    MenuEntry {
+        icon: "⚙️",
+        args: &["config", "show"],
+        title: "Show Config",
+        description: "Display current configuration",
+        prompts: &[],
+        json: false,
+    },
+    MenuEntry {
+        icon: "⚙️",
+        args: &["config", "validate"],
+        title: "Validate Config",
+        description: "Check configuration file syntax",
+        prompts: &[],
+        json: false,
+    },
+    MenuEntry {
+        icon: "⚙️",
+        args: &["config", "init"],
+        title: "Initialize Config",
+        description: "Create default configuration file",
+        prompts: &[],
+        json: false,
+    },
+    MenuEntry {
+        icon: "🪝",
+        args: &["hooks", "install"],
+        title: "Install Git Hooks",
+        description: "Run sniff before every commit and push",
+        prompts: &[],
+        json: false,
+    },
+];
+
+/// A finished run, shown in the results pane
+struct MenuRun {
+    command: String,
+    exit_code: i32,
+    duration_ms: u128,
+}
 
 pub async fn run() -> Result<()> {
-    print_menu();
+    // Piped or in CI there is nobody to answer prompts
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        print_menu();
+        return Ok(());
+    }
+
+    let sniff = std::env::current_exe().context("Failed to locate the sniff executable")?;
+    let theme = prompt_theme();
+    let mut results: Vec<MenuRun> = Vec::new();
+    let mut items: Vec<String> = ENTRIES.iter().map(menu_item).collect();
+    items.push(plain("👋 Quit").into_owned());
+
+    output::blank();
+    output::line("🛠️  Dev Tools Menu".bold().blue());
+    loop {
+        print_results(&results);
+        let selection = FuzzySelect::with_theme(&theme)
+            .with_prompt("Type to search, Enter to run, Esc to quit")
+            .items(&items)
+            .default(0)
+            .interact_opt()?;
+        let Some(entry) = selection.and_then(|index| ENTRIES.get(index)) else {
+            break;
+        };
+
+        let answers = ask(entry, &theme)?;
+        let json = entry.json && Confirm::with_theme(&theme).with_prompt("JSON output?").default(false).interact()?;
+        let args = command_args(entry, &answers, json);
+        let command = format!("sniff {}", args.join(" "));
+        output::line(format!("▶ {}", command).bold().cyan());
+
+        let start = Instant::now();
+        let status = Command::new(&sniff)
+            .args(&args)
+            .status()
+            .with_context(|| format!("Failed to run `{}`", command))?;
+        results.push(MenuRun {
+            command,
+            exit_code: status.code().unwrap_or(1),
+            duration_ms: start.elapsed().as_millis(),
+        });
+    }
     Ok(())
 }
 
+/// The prompt draws its items itself, so they are made ASCII here rather than by `output::line`
+fn menu_item(entry: &MenuEntry) -> String {
+    let command = format!("sniff {}", entry.args.join(" "));
+    plain(&format!("{}  {:<22} {} · {}", entry.icon, command, entry.title, entry.description)).into_owned()
+}
+
+fn ask(entry: &MenuEntry, theme: &ColorfulTheme) -> Result<Vec<Answer>> {
+    entry.prompts.iter().map(|prompt| {
+        Ok(match prompt {
+            Prompt::Number { label, default, .. } => {
+                Answer::Number(Input::with_theme(theme).with_prompt(*label).default(*default).interact_text()?)
+            }
+            Prompt::Switch { label, .. } => Answer::Switch(Confirm::with_theme(theme).with_prompt(*label).default(false).interact()?),
+            Prompt::Text { label, .. } => {
+                Answer::Text(Input::with_theme(theme).with_prompt(*label).allow_empty(true).interact_text()?)
+            }
+        })
+    }).collect()
+}
+
+/// The `sniff` arguments for an entry and the answers to its prompts
+fn command_args(entry: &MenuEntry, answers: &[Answer], json: bool) -> Vec<String> {
+    let mut args: Vec<String> = entry.args.iter().map(|arg| arg.to_string()).collect();
+    for (prompt, answer) in entry.prompts.iter().zip(answers) {
+        match (prompt, answer) {
+            // Defaults are left out so the command line stays the one a user would type
+            (Prompt::Number { flag, default, .. }, Answer::Number(value)) if value != default => {
+                args.extend([flag.to_string(), value.to_string()]);
+            }
+            (Prompt::Switch { flag, .. }, Answer::Switch(true)) => args.push(flag.to_string()),
+            (Prompt::Text { flag, .. }, Answer::Text(value)) if !value.trim().is_empty() => {
+                args.extend([flag.to_string(), value.trim().to_string()]);
+            }
+            _ => {}
+        }
+    }
+    if json {
+        args.push("--json".to_string());
+    }
+    args
+}
+
+fn print_results(results: &[MenuRun]) {
+    if results.is_empty() {
+        output::blank();
+        return;
+    }
+    output::blank();
+    output::line("📋 Results".bold().blue());
+    for run in results.iter().rev().take(RESULTS_SHOWN) {
+        let status = if run.exit_code == 0 {
+            "✅ passed".green()
+        } else {
+            format!("❌ exit {}", run.exit_code).red()
+        };
+        output::line(format!("  {:<12} {:<40} {}", status, run.command.bright_white(), format!("{:.1}s", run.duration_ms as f64 / 1000.0).dimmed()));
+    }
+    output::blank();
+}
+
 fn print_menu() {
-    println!();
-    println!("{}", "🛠️  Dev Tools Menu".bold().blue());
-    println!("{}", "================".blue());
-    println!();
-    println!("{}", "Available development tools:".white());
-    println!();
-    
+    output::blank();
+    output::line("🛠️  Dev Tools Menu".bold().blue());
+    output::line("================".blue());
+    output::blank();
+    output::line("Available development tools:".white());
+    output::blank();
+
     // Code Quality section
-    println!("{}", "🔍 Code Quality".bold().yellow());
-    println!("{}", "───────────────".yellow());
+    output::line("🔍 Code Quality".bold().yellow());
+    output::line("───────────────".yellow());
     print_command("sniff large", "Large Files", "Find \"smelly code\" files over 100 lines");
+    print_command("sniff complexity", "Complexity", "Find functions with high cyclomatic or cognitive complexity");
     print_command("sniff components", "Component Analysis", "Analyze and split large React/Vue/Angular components");
     print_command("sniff imports", "Unused Imports", "Detect and clean unused imports");
     print_command("sniff types", "TypeScript Coverage", "Check TypeScript type coverage and quality");
-    println!();
-    
+    print_command("sniff console", "Debug Statements", "Find console.log, debugger, alert() and TODO/FIXME markers");
+    print_command("sniff tailwind", "Tailwind Audit", "Check Tailwind classes against the theme and content config");
+    print_command("sniff i18n", "Translations", "Find hardcoded UI text and missing or unused translation keys");
+    print_command("sniff tests", "Test Files", "Find source files without tests and skipped tests");
+    print_command("sniff duplicates", "Duplicate Code", "Find copy-pasted blocks worth consolidating");
+    output::blank();
+
     // Analysis section
-    println!("{}", "📊 Analysis".bold().green());
-    println!("{}", "───────────".green());
+    output::line("📊 Analysis".bold().green());
+    output::line("───────────".green());
     print_command("sniff context", "Project Context", "Analyze project structure and provide insights");
+    print_command("sniff routes", "Routes", "Pages, API routes, rendering modes and auth guards");
+    print_command("sniff a11y", "Accessibility", "Find common accessibility issues in JSX");
     print_command("sniff bundle", "Bundle Analysis", "Analyze bundle size and optimization opportunities");
+    print_command("sniff images", "Image Audit", "Find oversized, unoptimized and legacy-format images");
     print_command("sniff perf", "Performance Audit", "Run Lighthouse performance audits");
     print_command("sniff memory", "Memory Check", "Detect memory leaks during development");
-    println!();
-    
+    output::blank();
+
     // Deploy section
-    println!("{}", "🚀 Deploy".bold().red());
-    println!("{}", "─────────".red());
+    output::line("🚀 Deploy".bold().red());
+    output::line("─────────".red());
     print_command("sniff env", "Environment Check", "Validate environment variables");
-    println!();
-    
+    print_command("sniff secrets", "Secret Scan", "Find API keys, tokens and credentials in source files");
+    print_command("sniff security", "Security Scan", "Find XSS, injection, eval and open-redirect patterns");
+    print_command("sniff deploy", "Pre-deployment Pipeline", "Run env, types, large, imports, bundle, console and image checks");
+    output::blank();
+
     // Configuration section
-    println!("{}", "⚙️  Configuration".bold().white());
-    println!("{}", "─────────────────".white());
+    output::line("⚙️  Configuration".bold().white());
+    output::line("─────────────────".white());
+    print_command("sniff init", "Set Up Project", "Tailored config, .gitignore, git hooks and CI workflow");
+    print_command("sniff doctor", "Doctor", "Check the tools, files and config the analyzers depend on");
     print_command("sniff config init", "Initialize Config", "Create default configuration file");
     print_command("sniff config show", "Show Config", "Display current configuration");
     print_command("sniff config validate", "Validate Config", "Check configuration file syntax");
-    println!();
-    
+    output::blank();
+
     // Usage examples
-    println!("{}", "💡 Usage Examples:".bold().cyan());
-    println!("{}", "==================".cyan());
-    println!("  {:<20} {}", "sniff large".bright_white(), "# Check for large files".dimmed());
-    println!("  {:<20} {}", "sniff env".bright_white(), "# Validate environment variables".dimmed());
-    println!();
-    
+    output::line("💡 Usage Examples:".bold().cyan());
+    output::line("==================".cyan());
+    output::line(format!("  {:<20} {}", "sniff large".bright_white(), "# Check for large files".dimmed()));
+    output::line(format!("  {:<20} {}", "sniff env".bright_white(), "# Validate environment variables".dimmed()));
+    output::blank();
+
     // Quick workflow
-    println!("{}", "📚 Quick Workflow:".bold().magenta());
-    println!("{}", "==================".magenta());
-    println!("  {}", "# Project analysis".dimmed());
-    println!("  {}", "sniff context".bright_white());
-    println!();
-    println!("  {}", "# Daily development".dimmed());
-    println!("  {}", "sniff large && sniff imports".bright_white());
-    println!();
-    println!("  {}", "# Pre-commit".dimmed());
-    println!("  {}", "sniff types".bright_white());
-    println!();  
-    println!("  {}", "# Pre-deployment".dimmed());
-    println!("  {}", "sniff env && sniff types && sniff imports".bright_white());
-    println!();
+    output::line("📚 Quick Workflow:".bold().magenta());
+    output::line("==================".magenta());
+    output::line(format!("  {}", "# Project analysis".dimmed()));
+    output::line(format!("  {}", "sniff context".bright_white()));
+    output::blank();
+    output::line(format!("  {}", "# Daily development".dimmed()));
+    output::line(format!("  {}", "sniff large && sniff imports".bright_white()));
+    output::blank();
+    output::line(format!("  {}", "# Pre-commit".dimmed()));
+    output::line(format!("  {}", "sniff types".bright_white()));
+    output::blank();
+    output::line(format!("  {}", "# Pre-deployment".dimmed()));
+    output::line(format!("  {}", "sniff deploy".bright_white()));
+    output::blank();
 }
 
 fn print_command(command: &str, title: &str, description: &str) {
-    println!("    {:<24} {}", command.bright_white(), title.bold());
-    println!("    {:<24} {}", "", description.dimmed());
-    println!();
-}
\ No newline at end of file
+    output::line(format!("    {:<24} {}", command.bright_white(), title.bold()));
+    output::line(format!("    {:<24} {}", "", description.dimmed()));
+    output::blank();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(args: &[&str]) -> &'static MenuEntry {
+        ENTRIES.iter().find(|entry| entry.args == args).unwrap()
+    }
+
+    #[test]
+    fn test_command_args_leave_out_defaults_and_unset_switches() {
+        let large = entry(&["large"]);
+        assert_eq!(command_args(large, &[Answer::Number(100)], false), vec!["large"]);
+        assert_eq!(command_args(large, &[Answer::Number(250)], true), vec!["large", "--threshold", "250", "--json"]);
+
+        let types = entry(&["types"]);
+        assert_eq!(command_args(types, &[Answer::Switch(false), Answer::Switch(true)], false), vec!["types", "--list-any"]);
+
+        let perf = entry(&["perf"]);
+        assert_eq!(command_args(perf, &[Answer::Text("  ".to_string())], false), vec!["perf"]);
+        assert_eq!(
+            command_args(perf, &[Answer::Text("http://localhost:3000".to_string())], false),
+            vec!["perf", "--url", "http://localhost:3000"]
+        );
+    }
+}
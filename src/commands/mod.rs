@@ -8,5 +8,35 @@ pub mod memory;
 pub mod env;
 pub mod context;
 pub mod components;
+pub mod deploy;
+pub mod duplicates;
+pub mod secrets;
+pub mod lint;
+pub mod outdated;
+pub mod vulns;
+pub mod docker;
+pub mod ci;
+pub mod boundaries;
+pub mod nextjs;
+pub mod hooks;
+pub mod report;
+pub mod history;
+pub mod routes;
+pub mod a11y;
+pub mod console;
+pub mod rules;
+pub mod explain;
+pub mod diff;
+pub mod security;
+pub mod tailwind;
+pub mod images;
+pub mod i18n;
+pub mod test_audit;
+pub mod complexity;
+pub mod fix;
+pub mod lsp;
+pub mod serve;
+pub mod init;
+pub mod doctor;
 
 // Individual command re-exports removed to eliminate unused imports
\ No newline at end of file
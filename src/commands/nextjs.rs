@@ -0,0 +1,528 @@
+/// Data fetching anti-patterns in Next.js: uncached `fetch` in Server Components,
+/// `getServerSideProps` that reads nothing from the request, client-side fetching of data
+/// that is known at build time, and static data without `revalidate`.
+use anyhow::{anyhow, Result};
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, ExitCode, CommandOutcome, create_annotated_json_output, output_result};
+use crate::common::source::{blank_strings, strip_comments};
+use crate::utils::FileUtils;
+use super::boundaries::has_directive;
+use super::routes::{discover, ApiRouteInfo, Router, Routes};
+use super::routes::discovery::{APP_DIRS, SOURCE_EXTENSIONS};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NextjsReport {
+    /// `next` in package.json
+    pub next_version: Option<String>,
+    /// `app` and/or `pages`
+    pub routers: Vec<String>,
+    pub issues: Vec<NextjsIssue>,
+    pub summary: NextjsSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NextjsIssue {
+    pub kind: NextjsIssueKind,
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NextjsIssueKind {
+    /// `fetch` in a Server Component without `cache` or `next.revalidate`, in a segment without config
+    FetchCache,
+    /// `getServerSideProps` that reads nothing from the request
+    SsrWithoutRequest,
+    /// `useEffect`, SWR or React Query loading a static file or an API route with a fixed response
+    ClientFetch,
+    /// `getStaticProps` or a `force-cache` fetch loading data without `revalidate`
+    MissingRevalidate,
+}
+
+impl NextjsIssueKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            NextjsIssueKind::FetchCache => "SNF-NEXT-FETCH-CACHE",
+            NextjsIssueKind::SsrWithoutRequest => "SNF-NEXT-SSR-STATIC",
+            NextjsIssueKind::ClientFetch => "SNF-NEXT-CLIENT-FETCH",
+            NextjsIssueKind::MissingRevalidate => "SNF-NEXT-NO-REVALIDATE",
+        }
+    }
+
+    fn level(self) -> AnnotationLevel {
+        match self {
+            NextjsIssueKind::MissingRevalidate => AnnotationLevel::Notice,
+            _ => AnnotationLevel::Warning,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NextjsSummary {
+    pub files_scanned: usize,
+    /// `fetch` calls in Server Components
+    pub server_fetches: usize,
+    pub total_issues: usize,
+    /// Issue counts keyed by kind
+    pub by_kind: BTreeMap<String, usize>,
+    /// Issues dropped by `[[ignore]]` entries
+    pub ignored: usize,
+}
+
+impl Annotate for NextjsReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            Annotation::new(issue.kind.level(), &issue.file, issue.line, format!("{:?}", issue.kind), &issue.message)
+                .with_rule(issue.kind.rule_id())
+        }).collect()
+    }
+}
+
+/// Files in public/ with these extensions are data rather than assets
+const DATA_EXTENSIONS: [&str; 8] = ["json", "md", "mdx", "csv", "yml", "yaml", "txt", "xml"];
+
+struct NextjsPatterns {
+    fetch: Regex,
+    segment_config: Regex,
+    mutation: Regex,
+    cache_option: Regex,
+    data_function: Regex,
+    /// Hooks whose callbacks run in the browser after rendering
+    client_hook: Regex,
+    /// `fetch('/x')`, `axios.get('/x')`, `useSWR('/x')` and the like, with the URL
+    client_request: Regex,
+    /// `req.x`, `request.json()`, `context.req`
+    request_object: Regex,
+    /// Request-time data and APIs; `.query(` and the like are method calls on other objects
+    request_api: Regex,
+    /// `getData(context)`: the request handed to other code
+    request_passed: Regex,
+    /// The `(req, res)` or `(request: Request)` parameters of a handler
+    handler_parameters: Regex,
+}
+
+static NEXTJS_PATTERNS: OnceLock<NextjsPatterns> = OnceLock::new();
+
+fn patterns() -> &'static NextjsPatterns {
+    NEXTJS_PATTERNS.get_or_init(|| NextjsPatterns {
+        fetch: Regex::new(r"(?:^|[^\w$.])fetch\s*\(").unwrap(),
+        segment_config: Regex::new(r"export\s+const\s+(revalidate|dynamic|fetchCache)\s*=").unwrap(),
+        mutation: Regex::new(r#"method\s*:\s*['"](?i:post|put|patch|delete)['"]"#).unwrap(),
+        cache_option: Regex::new(r#"cache\s*:\s*['"]([\w-]+)['"]"#).unwrap(),
+        data_function: Regex::new(r"export\s+(?:const\s+|(?:async\s+)?function\s+)(getServerSideProps|getStaticProps)\b").unwrap(),
+        client_hook: Regex::new(r"(?:^|[^\w$.])(useEffect|useLayoutEffect|useSWR\w*|useQuery|useSuspenseQuery|useInfiniteQuery)\s*(?:<[^>]*>)?\s*\(").unwrap(),
+        client_request: Regex::new(r#"(?:^|[^\w$.])(?:fetch|axios(?:\.get)?|useSWR\w*|ky(?:\.get)?|\$fetch)\s*\(\s*(?:\[\s*)?['"`](/[^'"`?$#]*)"#).unwrap(),
+        request_object: Regex::new(r"(?:^|[^\w$])(?:req|request)\s*[.\[]").unwrap(),
+        request_api: Regex::new(r"(\.?)\b(query|resolvedUrl|cookies|headers|searchParams|previewData|draftMode|nextUrl|getServerSession|getSession|getToken|getAuth|currentUser|auth)\b\s*(\(?)").unwrap(),
+        request_passed: Regex::new(r"\(\s*(?:[^()]*,\s*)?(?:req|request|context|ctx)\s*[,)]").unwrap(),
+        handler_parameters: Regex::new(r"\(\s*_?(?:req|request)\b[^)]*\)(\s*(?::\s*[^={]+)?(?:=>|\{))").unwrap(),
+    })
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<NextjsReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!("Running Next.js data fetching check");
+
+    let outcome = check(target)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "nextjs",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(issues = report.summary.total_issues, "Next.js data fetching check completed");
+
+    Ok(outcome)
+}
+
+/// Look for data fetching anti-patterns in a Next.js project. They are warnings: the exit code
+/// only changes with `--fail-on` or `--max-warnings`
+pub fn check(target: &ScanTarget) -> Result<CommandOutcome<NextjsReport>> {
+    let project_dir = target.project_dir("nextjs")?;
+    if !FileUtils::has_dependency(project_dir, "next") {
+        return Err(anyhow!("No next dependency in {}; `sniff nextjs` checks Next.js projects", project_dir.join("package.json").display()));
+    }
+    let package: Value = fs::read_to_string(project_dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(Value::Null);
+    let next_version = ["dependencies", "devDependencies"].iter()
+        .find_map(|section| package[section]["next"].as_str())
+        .map(str::to_string);
+
+    let Routes { pages, api_routes, .. } = discover(project_dir);
+    let app_dirs: Vec<PathBuf> = APP_DIRS.iter().map(|dir| project_dir.join(dir)).filter(|dir| dir.is_dir()).collect();
+    let mut routers = Vec::new();
+    if !app_dirs.is_empty() {
+        routers.push("app".to_string());
+    }
+    if pages.iter().any(|page| page.router == Router::Pages) || api_routes.iter().any(|route| route.router == Router::Pages) {
+        routers.push("pages".to_string());
+    }
+
+    let scanner = FileScanner::for_command(target, "nextjs")?;
+    let files: Vec<PathBuf> = scanner.find_target_files(target, &SOURCE_EXTENSIONS).into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(project_dir).unwrap_or(path)))
+        .collect();
+
+    let mut issues = Vec::new();
+    let mut server_fetches = 0;
+    for file in &files {
+        let Ok(content) = target.context.read(file) else { continue };
+        let source = Source::new(&content);
+        let display = paths::display(file);
+
+        if let Some(app_dir) = app_dirs.iter().find(|dir| file.starts_with(dir)) {
+            if is_server_component(file, &source.code) {
+                let segment = segment_config(file, app_dir, &source.code);
+                let (count, found) = server_fetch_issues(&source, segment);
+                server_fetches += count;
+                issues.extend(found.into_iter().map(|(kind, line, message)| NextjsIssue { kind, file: display.clone(), line, message }));
+            }
+        }
+
+        for (line, url, reason) in client_fetches(&source, project_dir, &api_routes) {
+            issues.push(NextjsIssue {
+                kind: NextjsIssueKind::ClientFetch,
+                file: display.clone(),
+                line,
+                message: format!("Fetches {} in the browser after rendering, but {}; load it on the server at build time instead", url, reason),
+            });
+        }
+    }
+
+    for page in pages.iter().filter(|page| page.router == Router::Pages) {
+        let Ok(content) = fs::read_to_string(project_dir.join(&page.path)) else { continue };
+        let source = Source::new(&content);
+        issues.extend(data_function_issues(&source).into_iter().map(|(kind, line, message)| NextjsIssue { kind, file: page.path.clone(), line, message }));
+    }
+
+    let ignored = target.ignores()?.retain(&mut issues, |issue| (issue.kind.rule_id(), &issue.file));
+    issues.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    let mut by_kind: BTreeMap<String, usize> = BTreeMap::new();
+    for issue in &issues {
+        *by_kind.entry(format!("{:?}", issue.kind)).or_default() += 1;
+    }
+    let summary = NextjsSummary {
+        files_scanned: files.len(),
+        server_fetches,
+        total_issues: issues.len(),
+        by_kind,
+        ignored,
+    };
+    Ok(CommandOutcome::new(NextjsReport { next_version, routers, issues, summary }, ExitCode::Success))
+}
+
+/// A source file with comments blanked (`code`) and also string contents blanked (`structure`),
+/// at the same offsets
+struct Source {
+    code: String,
+    structure: String,
+}
+
+impl Source {
+    fn new(content: &str) -> Self {
+        let code = strip_comments(content);
+        let structure = blank_strings(&code);
+        Source { code, structure }
+    }
+
+    fn line(&self, offset: usize) -> usize {
+        self.code[..offset].matches('\n').count() + 1
+    }
+
+    /// Offset of the bracket closing the one at `open`, ignoring brackets inside strings
+    fn closing(&self, open: usize) -> Option<usize> {
+        let bytes = self.structure.as_bytes();
+        let (opening, closing) = match bytes.get(open)? {
+            b'(' => (b'(', b')'),
+            b'{' => (b'{', b'}'),
+            _ => return None,
+        };
+        let mut depth = 0usize;
+        for (index, byte) in bytes.iter().enumerate().skip(open) {
+            if *byte == opening {
+                depth += 1;
+            } else if *byte == closing {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Files under the app router that render on the server: not `'use client'`, not Server
+/// Actions and not route handlers
+fn is_server_component(file: &Path, code: &str) -> bool {
+    let stem = file.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+    stem != "route" && !has_directive(code, "use client") && !has_directive(code, "use server")
+}
+
+/// Route segment config that decides how `fetch` caches: in the file itself, or in a `page` or
+/// `layout` between it and the app directory
+#[derive(Debug, Default, Clone, Copy)]
+struct SegmentConfig {
+    configured: bool,
+    revalidate: bool,
+}
+
+fn segment_config(file: &Path, app_dir: &Path, code: &str) -> SegmentConfig {
+    let mut config = SegmentConfig::default();
+    let mut read = |code: &str| {
+        for caps in patterns().segment_config.captures_iter(code) {
+            config.configured = true;
+            config.revalidate |= &caps[1] == "revalidate";
+        }
+    };
+    read(code);
+    let mut dir = file.parent();
+    while let Some(current) = dir.filter(|current| current.starts_with(app_dir)) {
+        for name in ["page", "layout"] {
+            for ext in SOURCE_EXTENSIONS {
+                let candidate = current.join(format!("{}.{}", name, ext));
+                if candidate != file {
+                    if let Ok(content) = fs::read_to_string(&candidate) {
+                        read(&strip_comments(&content));
+                    }
+                }
+            }
+        }
+        dir = current.parent();
+    }
+    config
+}
+
+/// The `fetch` calls of a Server Component, and the issues among them
+fn server_fetch_issues(source: &Source, segment: SegmentConfig) -> (usize, Vec<(NextjsIssueKind, usize, String)>) {
+    let patterns = patterns();
+    let mut count = 0;
+    let mut issues = Vec::new();
+    for found in patterns.fetch.find_iter(&source.structure) {
+        let open = found.end() - 1;
+        let Some(close) = source.closing(open) else { continue };
+        count += 1;
+        let arguments = &source.code[open..=close];
+        // Only GET requests are cached
+        if patterns.mutation.is_match(arguments) {
+            continue;
+        }
+        let line = source.line(open);
+        let cache = patterns.cache_option.captures(arguments).map(|caps| caps[1].to_string());
+        let revalidate = arguments.contains("revalidate");
+        match cache.as_deref() {
+            None if !revalidate && !segment.configured => issues.push((NextjsIssueKind::FetchCache, line,
+                "fetch() without `cache` or `next: { revalidate }` in a segment without `revalidate` or `dynamic`: \
+                 Next.js 14 caches the response until the next build, Next.js 15 fetches it on every request".to_string())),
+            Some("force-cache") if !revalidate && !segment.revalidate => issues.push((NextjsIssueKind::MissingRevalidate, line,
+                "fetch() with `cache: 'force-cache'` and no `revalidate` keeps the data until the next deploy".to_string())),
+            _ => {}
+        }
+    }
+    (count, issues)
+}
+
+/// `getServerSideProps` that could be static, and `getStaticProps` that loads data without `revalidate`
+fn data_function_issues(source: &Source) -> Vec<(NextjsIssueKind, usize, String)> {
+    let patterns = patterns();
+    let mut issues = Vec::new();
+    for caps in patterns.data_function.captures_iter(&source.structure) {
+        let Some(name) = caps.get(1) else { continue };
+        // Wrapped functions (`withSession(async (ctx) => ...)`) are left alone
+        let Some(body) = function_body(source, name.end()) else { continue };
+        let line = source.line(name.start());
+        if name.as_str() == "getServerSideProps" {
+            if !reads_request(body) {
+                issues.push((NextjsIssueKind::SsrWithoutRequest, line,
+                    "getServerSideProps reads nothing from the request, so every visitor waits for the same data; \
+                     use getStaticProps with `revalidate`".to_string()));
+            }
+        } else if (body.contains("await") || body.contains("fetch(") || body.contains(".then(")) && !body.contains("revalidate") {
+            issues.push((NextjsIssueKind::MissingRevalidate, line,
+                "getStaticProps loads data but returns no `revalidate`, so the page keeps the build-time data until the next deploy".to_string()));
+        }
+    }
+    issues
+}
+
+/// The body of the function declared at `after`, when its parameter list is followed by the
+/// body, an arrow or a return type
+fn function_body(source: &Source, after: usize) -> Option<&str> {
+    let open = after + source.structure[after..].find('(')?;
+    let close = source.closing(open)?;
+    let body_open = close + source.structure[close..].find('{')?;
+    let gap = source.structure[close + 1..body_open].trim();
+    if !(gap.is_empty() || gap == "=>" || gap.starts_with(':')) {
+        return None;
+    }
+    let end = source.closing(body_open)?;
+    Some(&source.code[body_open..=end])
+}
+
+/// Whether code reads the request: `req`/`request` properties, cookies, headers, query and
+/// search params, the session, or hands the request context to other code
+fn reads_request(code: &str) -> bool {
+    let patterns = patterns();
+    patterns.request_object.is_match(code)
+        || patterns.request_passed.is_match(code)
+        || patterns.request_api.captures_iter(code).any(|caps| !(&caps[1] == "." && &caps[3] == "("))
+}
+
+/// Requests made from `useEffect`, SWR or React Query for data that does not change after the
+/// build: files in public/ and API routes whose response does not depend on the request
+fn client_fetches(source: &Source, project_dir: &Path, api_routes: &[ApiRouteInfo]) -> Vec<(usize, String, String)> {
+    let patterns = patterns();
+    let mut found = Vec::new();
+    for hook in patterns.client_hook.find_iter(&source.structure) {
+        let open = hook.end() - 1;
+        let Some(close) = source.closing(open) else { continue };
+        let call = &source.code[hook.start()..=close];
+        for caps in patterns.client_request.captures_iter(call) {
+            let Some(url) = caps.get(1) else { continue };
+            let Some(reason) = build_time_data(url.as_str(), project_dir, api_routes) else { continue };
+            let line = source.line(hook.start() + url.start());
+            if !found.iter().any(|(seen, _, _)| *seen == line) {
+                found.push((line, url.as_str().to_string(), reason));
+            }
+        }
+    }
+    found
+}
+
+/// Why the response of `url` is known at build time, if it is
+fn build_time_data(url: &str, project_dir: &Path, api_routes: &[ApiRouteInfo]) -> Option<String> {
+    if url.starts_with("/api/") {
+        let route = api_routes.iter().find(|route| route_matches(&route.route, url))?;
+        let content = fs::read_to_string(project_dir.join(&route.path)).ok()?;
+        let code = strip_comments(&content);
+        let fixed = route.auth.is_empty() && route.database_operations.is_empty()
+            && !code.contains("fetch(") && !reads_request(&handler_bodies(&code));
+        return fixed.then(|| format!("{} returns the same response for every request", route.path));
+    }
+    let public = project_dir.join("public").join(url.trim_start_matches('/'));
+    let extension = Path::new(url).extension().and_then(|ext| ext.to_str()).unwrap_or_default();
+    (public.is_file() && DATA_EXTENSIONS.contains(&extension))
+        .then(|| format!("public{} is a static file", url))
+}
+
+/// A route handler without its parameter lists, so `(req, res)` in a pages-router signature
+/// does not count as reading the request
+fn handler_bodies(code: &str) -> String {
+    patterns().handler_parameters.replace_all(code, "()$1").into_owned()
+}
+
+/// Whether `url` is served by `route`, e.g. `/api/posts/[id]` for `/api/posts/1`
+fn route_matches(route: &str, url: &str) -> bool {
+    let url = url.trim_end_matches('/');
+    let mut actual = url.split('/');
+    for segment in route.split('/') {
+        if segment.starts_with("[[...") {
+            return true;
+        }
+        if segment.starts_with("[...") {
+            return actual.next().is_some_and(|part| !part.is_empty());
+        }
+        match actual.next() {
+            Some(part) if segment.starts_with('[') || part == segment => {}
+            _ => return false,
+        }
+    }
+    actual.next().is_none()
+}
+
+fn print_report(report: &NextjsReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("▲ Next.js Data Fetching Report".bold().blue());
+        output::line("==============================".blue());
+        let version = report.next_version.as_deref().unwrap_or("unknown version");
+        let routers = if report.routers.is_empty() { "no routes".to_string() } else { format!("{} router", report.routers.join(" and ")) };
+        output::line(format!("  next {} with the {}", version.cyan(), routers));
+        output::blank();
+    }
+
+    if report.issues.is_empty() {
+        output::line("✅ No data fetching issues found!".green());
+    } else {
+        output::line("🔍 ISSUES".bold().yellow());
+        output::line("─────────".yellow());
+        for issue in &report.issues {
+            let rule = match issue.kind.level() {
+                AnnotationLevel::Notice => issue.kind.rule_id().dimmed(),
+                _ => issue.kind.rule_id().yellow(),
+            };
+            output::line(format!("  {}:{} - {} {}", issue.file.cyan(), issue.line, rule, issue.message));
+        }
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  fetch() in Server Components: {}", summary.server_fetches));
+    output::line(format!("  Issues: {}", summary.total_issues));
+    if summary.ignored > 0 {
+        output::line(format!("  Ignored: {}", summary.ignored.to_string().dimmed()));
+    }
+    output::blank();
+    output::line("💡 TIP: `sniff routes` shows how each page renders: static, ISR or per request".dimmed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_fetch_caching() {
+        let source = Source::new("export default async function Page() {\n  const a = await fetch('https://api.example.com/a');\n  const b = await fetch(url, { next: { revalidate: 60 } });\n  const c = await fetch(url, { cache: 'force-cache' });\n  await fetch(url, { method: 'POST', body: JSON.stringify({ a: '(' }) });\n}\n");
+        let (count, issues) = server_fetch_issues(&source, SegmentConfig::default());
+        assert_eq!(count, 4);
+        let found: Vec<(NextjsIssueKind, usize)> = issues.iter().map(|(kind, line, _)| (*kind, *line)).collect();
+        assert_eq!(found, vec![(NextjsIssueKind::FetchCache, 2), (NextjsIssueKind::MissingRevalidate, 4)]);
+
+        let (_, issues) = server_fetch_issues(&source, SegmentConfig { configured: true, revalidate: true });
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_data_functions() {
+        let source = Source::new("export const getServerSideProps: GetServerSideProps<{ posts: Post[] }> = async () => {\n  const posts = await db.query('select * from posts');\n  return { props: { posts } };\n};\n\nexport async function getStaticProps() {\n  const res = await fetch('https://cms.example.com/posts');\n  return { props: { posts: await res.json() } };\n}\n");
+        let found: Vec<(NextjsIssueKind, usize)> = data_function_issues(&source).iter().map(|(kind, line, _)| (*kind, *line)).collect();
+        assert_eq!(found, vec![(NextjsIssueKind::SsrWithoutRequest, 1), (NextjsIssueKind::MissingRevalidate, 6)]);
+
+        let source = Source::new("export async function getServerSideProps({ req, query }) {\n  return { props: { page: query.page ?? 1 } };\n}\n");
+        assert!(data_function_issues(&source).is_empty());
+        let source = Source::new("export const getServerSideProps = async (context) => {\n  return { props: await load(context) };\n};\n");
+        assert!(data_function_issues(&source).is_empty());
+        let source = Source::new("export const getServerSideProps = withSession(async (ctx) => ({ props: {} }));\n");
+        assert!(data_function_issues(&source).is_empty());
+    }
+
+    #[test]
+    fn test_route_matches() {
+        assert!(route_matches("/api/posts/[id]", "/api/posts/1"));
+        assert!(route_matches("/api/docs/[...slug]", "/api/docs/a/b"));
+        assert!(route_matches("/api/config", "/api/config/"));
+        assert!(!route_matches("/api/posts", "/api/posts/1"));
+        assert!(!route_matches("/api/docs/[...slug]", "/api/docs"));
+    }
+}
@@ -0,0 +1,435 @@
+mod registry;
+pub(crate) mod version;
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use colored::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use crate::config::{Config, OutdatedConfig};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+use super::history::Metrics;
+use registry::{RegistryCache, RegistryInfo};
+pub use version::UpgradeDistance;
+
+#[derive(Debug, Clone, Default)]
+pub struct OutdatedOptions {
+    /// Use only the lockfile, node_modules and the registry data of earlier runs
+    pub no_network: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutdatedReport {
+    pub packages: Vec<PackageStatus>,
+    pub summary: OutdatedSummary,
+    /// Whether the registry was left alone (`--no-network`)
+    pub offline: bool,
+    /// Packages the registry could not be asked about, and why
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Rules `[[ignore]]` silences for package.json
+    #[serde(skip)]
+    pub silenced: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PackageStatus {
+    pub name: String,
+    /// The range in package.json
+    pub declared: String,
+    /// From node_modules or package-lock.json, else the lowest version the range allows
+    pub installed: Option<String>,
+    pub dev: bool,
+    pub latest: Option<String>,
+    pub distance: UpgradeDistance,
+    pub majors_behind: u64,
+    /// Days since the installed version was published
+    pub installed_age_days: Option<i64>,
+    /// Days since the package's latest release
+    pub last_release_days: Option<i64>,
+    pub deprecated: Option<String>,
+    pub maintainers: Option<usize>,
+    pub has_repository: Option<bool>,
+    /// No release in `[outdated] stale_after_days`
+    pub stale: bool,
+    /// More than `[outdated] max_majors_behind` major versions behind
+    pub over_limit: bool,
+    /// Where the registry data came from
+    pub source: InfoSource,
+    /// Line of the package in package.json
+    #[serde(skip)]
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InfoSource {
+    Registry,
+    /// Data of an earlier run, for `--no-network` or when the registry could not be reached
+    Cache,
+    /// Only what the lockfile says
+    Lockfile,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutdatedSummary {
+    pub total_packages: usize,
+    pub up_to_date: usize,
+    pub patch: usize,
+    pub minor: usize,
+    pub major: usize,
+    pub unknown: usize,
+    pub deprecated: usize,
+    pub stale: usize,
+    /// Packages more than `[outdated] max_majors_behind` majors behind
+    pub over_limit: usize,
+}
+
+impl Annotate for OutdatedReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let mut annotations = Vec::new();
+        for package in &self.packages {
+            let at = |level, title: String, message: String| Annotation::new(level, "package.json", package.line, title, message);
+            let installed = package.installed.as_deref().unwrap_or(&package.declared);
+            if let Some(message) = &package.deprecated {
+                annotations.push(at(AnnotationLevel::Error, format!("{}@{} is deprecated", package.name, installed), message.clone())
+                    .with_rule("SNF-OUTDATED-DEPRECATED"));
+            }
+            if let Some(latest) = &package.latest {
+                let upgrade = format!("{} {} → {}", package.name, installed, latest);
+                match package.distance {
+                    UpgradeDistance::Major => {
+                        let behind = match package.majors_behind {
+                            0 | 1 => "A breaking release behind".to_string(),
+                            behind => format!("{} major versions behind", behind),
+                        };
+                        let level = if package.over_limit { AnnotationLevel::Error } else { AnnotationLevel::Warning };
+                        annotations.push(at(level, upgrade, format!("{}; read the changelog before upgrading", behind)).with_rule("SNF-OUTDATED-MAJOR"));
+                    }
+                    UpgradeDistance::Minor | UpgradeDistance::Patch => {
+                        let kind = if package.distance == UpgradeDistance::Minor { "Minor" } else { "Patch" };
+                        annotations.push(at(AnnotationLevel::Notice, upgrade, format!("{} upgrade, {} risk", kind, package.distance.risk()))
+                            .with_rule("SNF-OUTDATED-MINOR"));
+                    }
+                    _ => {}
+                }
+            }
+            if package.stale {
+                let days = package.last_release_days.unwrap_or_default();
+                annotations.push(at(AnnotationLevel::Notice, format!("{} looks unmaintained", package.name), format!("No release in {} days", days))
+                    .with_rule("SNF-OUTDATED-STALE"));
+            }
+        }
+        annotations.retain(|annotation| !annotation.rule.as_ref().is_some_and(|rule| self.silenced.contains(rule)));
+        annotations
+    }
+}
+
+impl Metrics for OutdatedReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("outdated_major", self.summary.major as f64),
+            ("deprecated_packages", self.summary.deprecated as f64),
+        ]
+    }
+}
+
+pub async fn run(target: &ScanTarget, options: &OutdatedOptions, json: bool, quiet: bool) -> Result<CommandOutcome<OutdatedReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!(offline = options.no_network, "Checking dependency freshness");
+
+    let config = target.config()?;
+    let outcome = check(target, options, &config)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "outdated",
+        report,
+        report.summary.total_packages,
+        report.summary.major + report.summary.minor + report.summary.patch + report.summary.deprecated,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(major = report.summary.major, deprecated = report.summary.deprecated, "dependency freshness check completed");
+
+    Ok(outcome)
+}
+
+/// Compare each dependency in package.json with its latest release. Deprecated installed
+/// versions fail the check (unless `fail_on_deprecated = false`), as do packages past `max_majors_behind`
+pub fn check(target: &ScanTarget, options: &OutdatedOptions, config: &Config) -> Result<CommandOutcome<OutdatedReport>> {
+    let project_dir = target.project_dir("outdated")?;
+    let settings = &config.outdated;
+    let manifest = fs::read_to_string(project_dir.join("package.json"))
+        .map_err(|_| anyhow!("No package.json in {}", project_dir.display()))?;
+    let package: Value = serde_json::from_str(&manifest)
+        .map_err(|e| anyhow!("package.json is not valid JSON: {}", e))?;
+    let lockfile = read_lockfile(project_dir);
+
+    let dependencies = declared_dependencies(project_dir, &lockfile, &package, &manifest, settings);
+    let mut cache = RegistryCache::load(project_dir);
+    let fetched: Vec<Option<Result<RegistryInfo>>> = dependencies.par_iter()
+        .map(|dependency| (!options.no_network).then(|| registry::fetch(&settings.registry, &dependency.name, dependency.installed.as_deref())))
+        .collect();
+
+    let now = Utc::now();
+    let mut notes = Vec::new();
+    let mut cache_changed = false;
+    let mut packages = Vec::new();
+    for (dependency, fetched) in dependencies.into_iter().zip(fetched) {
+        let installed = dependency.installed;
+        let (info, source) = match fetched {
+            Some(Ok(info)) => {
+                cache.insert(&dependency.name, installed.as_deref(), info.clone());
+                cache_changed = true;
+                (Some(info), InfoSource::Registry)
+            }
+            fetched => {
+                if let Some(Err(e)) = fetched {
+                    notes.push(format!("{}: {}", dependency.name, e));
+                }
+                match cache.get(&dependency.name, installed.as_deref()) {
+                    Some(cached) => (Some(cached.info.clone()), InfoSource::Cache),
+                    None => (None, InfoSource::Lockfile),
+                }
+            }
+        };
+
+        let age = |time: Option<DateTime<Utc>>| time.map(|time| (now - time).num_days());
+        let (distance, majors_behind) = match (&installed, &info) {
+            (Some(installed), Some(info)) => version::distance(installed, &info.latest),
+            _ => (UpgradeDistance::Unknown, 0),
+        };
+        let last_release_days = info.as_ref().and_then(|info| age(info.latest_published));
+        let deprecated = info.as_ref().and_then(|info| info.deprecated.clone())
+            .or_else(|| lockfile_deprecation(&lockfile, &dependency.name));
+        packages.push(PackageStatus {
+            latest: info.as_ref().map(|info| info.latest.clone()),
+            distance,
+            majors_behind,
+            installed_age_days: info.as_ref().and_then(|info| age(info.installed_published)),
+            last_release_days,
+            deprecated,
+            maintainers: info.as_ref().map(|info| info.maintainers),
+            has_repository: info.as_ref().map(|info| info.has_repository),
+            stale: last_release_days.is_some_and(|days| days > settings.stale_after_days as i64),
+            over_limit: settings.max_majors_behind.is_some_and(|limit| majors_behind > limit),
+            source,
+            installed,
+            name: dependency.name,
+            declared: dependency.declared,
+            dev: dependency.dev,
+            line: dependency.line,
+        });
+    }
+    if cache_changed {
+        if let Err(e) = cache.save() {
+            tracing::warn!("Could not save the registry cache: {}", e);
+        }
+    }
+
+    // `[[ignore]]` entries apply to package.json as a whole
+    let ignores = target.ignores()?;
+    let silenced: Vec<String> = ["SNF-OUTDATED-DEPRECATED", "SNF-OUTDATED-MAJOR", "SNF-OUTDATED-MINOR", "SNF-OUTDATED-STALE"].iter()
+        .filter(|rule| ignores.ignores(rule, "package.json"))
+        .map(|rule| rule.to_string())
+        .collect();
+    let silenced_rule = |rule: &str| silenced.iter().any(|silenced| silenced == rule);
+    let count = |distance| packages.iter().filter(|package| package.distance == distance).count();
+    let summary = OutdatedSummary {
+        total_packages: packages.len(),
+        up_to_date: count(UpgradeDistance::Current),
+        patch: count(UpgradeDistance::Patch),
+        minor: count(UpgradeDistance::Minor),
+        major: count(UpgradeDistance::Major),
+        unknown: count(UpgradeDistance::Unknown),
+        deprecated: packages.iter().filter(|package| package.deprecated.is_some()).count(),
+        stale: packages.iter().filter(|package| package.stale).count(),
+        over_limit: packages.iter().filter(|package| package.over_limit).count(),
+    };
+
+    let failed = (settings.fail_on_deprecated && summary.deprecated > 0 && !silenced_rule("SNF-OUTDATED-DEPRECATED"))
+        || (summary.over_limit > 0 && !silenced_rule("SNF-OUTDATED-MAJOR"));
+    let exit_code = check_failure_threshold(failed, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(OutdatedReport { packages, summary, offline: options.no_network, notes, silenced }, exit_code))
+}
+
+struct Dependency {
+    name: String,
+    declared: String,
+    /// From node_modules or package-lock.json, else the lowest version `declared` allows
+    installed: Option<String>,
+    dev: bool,
+    line: usize,
+}
+
+/// Registry dependencies in package.json, without `[outdated] ignore_packages` and local,
+/// git and workspace dependencies
+fn declared_dependencies(project_dir: &Path, lockfile: &Value, package: &Value, manifest: &str, settings: &OutdatedConfig) -> Vec<Dependency> {
+    let sections: &[(&str, bool)] = if settings.dev_dependencies {
+        &[("dependencies", false), ("devDependencies", true)]
+    } else {
+        &[("dependencies", false)]
+    };
+    let mut dependencies = Vec::new();
+    for (section, dev) in sections {
+        let Some(entries) = package[section].as_object() else { continue };
+        for (name, declared) in entries {
+            let declared = declared.as_str().unwrap_or_default();
+            let local = ["file:", "link:", "workspace:", "git", "http:", "https:", "npm:", "github:"].iter().any(|prefix| declared.starts_with(prefix))
+                || declared.contains('/') && !declared.starts_with('@');
+            if local || settings.ignore_packages.contains(name) {
+                continue;
+            }
+            dependencies.push(Dependency {
+                name: name.clone(),
+                declared: declared.to_string(),
+                installed: installed_version(project_dir, lockfile, name).or_else(|| version::range_base(declared)),
+                dev: *dev,
+                line: manifest_line(manifest, section, name).unwrap_or(1),
+            });
+        }
+    }
+    dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+    dependencies
+}
+
+/// 1-based line of `"name":` inside the `section` object of package.json
+pub(crate) fn manifest_line(manifest: &str, section: &str, name: &str) -> Option<usize> {
+    let section_key = format!("\"{}\"", section);
+    let key = format!("\"{}\"", name);
+    let mut in_section = false;
+    for (index, line) in manifest.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with(&section_key) {
+            in_section = true;
+        } else if in_section && trimmed.starts_with('}') {
+            in_section = false;
+        } else if in_section && trimmed.starts_with(&key) {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+fn read_lockfile(project_dir: &Path) -> Value {
+    fs::read_to_string(project_dir.join("package-lock.json")).ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(Value::Null)
+}
+
+/// The version in node_modules, else the one package-lock.json resolves
+fn installed_version(project_dir: &Path, lockfile: &Value, name: &str) -> Option<String> {
+    let manifest = project_dir.join("node_modules").join(name).join("package.json");
+    let installed = fs::read_to_string(manifest).ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|package| package["version"].as_str().map(str::to_string));
+    installed
+        .or_else(|| lockfile["packages"][format!("node_modules/{}", name)]["version"].as_str().map(str::to_string))
+        .or_else(|| lockfile["dependencies"][name]["version"].as_str().map(str::to_string))
+}
+
+/// npm records deprecation messages in package-lock.json, which works without the network
+fn lockfile_deprecation(lockfile: &Value, name: &str) -> Option<String> {
+    lockfile["packages"][format!("node_modules/{}", name)]["deprecated"].as_str().map(str::to_string)
+}
+
+fn print_report(report: &OutdatedReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("📦 Dependency Freshness Report".bold().blue());
+        output::line("==============================".blue());
+        if report.offline {
+            output::line("  Offline: registry data from earlier runs and the lockfile only".dimmed());
+        }
+        output::blank();
+    }
+
+    let deprecated: Vec<&PackageStatus> = report.packages.iter().filter(|package| package.deprecated.is_some()).collect();
+    if !deprecated.is_empty() {
+        output::line("⛔ DEPRECATED".bold().red());
+        output::line("────────────".red());
+        for package in deprecated {
+            output::line(format!("  {}@{} - {}",
+                package.name.red(),
+                package.installed.as_deref().unwrap_or(&package.declared),
+                package.deprecated.as_deref().unwrap_or_default().dimmed()
+            ));
+        }
+        output::blank();
+    }
+
+    let mut upgrades: Vec<&PackageStatus> = report.packages.iter()
+        .filter(|package| matches!(package.distance, UpgradeDistance::Major | UpgradeDistance::Minor | UpgradeDistance::Patch))
+        .collect();
+    upgrades.sort_by(|a, b| b.distance.cmp(&a.distance).then(b.majors_behind.cmp(&a.majors_behind)).then(a.name.cmp(&b.name)));
+    if upgrades.is_empty() && report.summary.deprecated == 0 && report.summary.unknown == 0 {
+        output::line("✅ Every dependency is on its latest release!".green());
+    } else if !upgrades.is_empty() {
+        output::line("⬆️  UPGRADES".bold().yellow());
+        output::line("──────────".yellow());
+        for package in upgrades {
+            let risk = match package.distance {
+                UpgradeDistance::Major => "high risk".red(),
+                UpgradeDistance::Minor => "moderate risk".yellow(),
+                _ => "low risk".green(),
+            };
+            let age = package.installed_age_days.map(|days| format!(", installed release is {} days old", days)).unwrap_or_default();
+            output::line(format!("  {:<32} {} → {}  {}{}{}",
+                package.name.cyan(),
+                package.installed.as_deref().unwrap_or(&package.declared),
+                package.latest.as_deref().unwrap_or_default().bold(),
+                risk,
+                if package.over_limit { " (over max_majors_behind)".red().to_string() } else { String::new() },
+                age.dimmed()
+            ));
+        }
+        output::blank();
+    }
+
+    let stale: Vec<&PackageStatus> = report.packages.iter().filter(|package| package.stale).collect();
+    if !stale.is_empty() {
+        output::line("🕸️  UNMAINTAINED".bold().white());
+        output::line("──────────────".white());
+        for package in stale {
+            output::line(format!("  {} - last release {} days ago, {} maintainer(s)",
+                package.name,
+                package.last_release_days.unwrap_or_default(),
+                package.maintainers.unwrap_or_default()
+            ));
+        }
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Packages: {}", summary.total_packages));
+    output::line(format!("  Up to date: {}", summary.up_to_date.to_string().green()));
+    output::line(format!("  Major (high risk): {}", summary.major.to_string().red()));
+    output::line(format!("  Minor (moderate risk): {}", summary.minor.to_string().yellow()));
+    output::line(format!("  Patch (low risk): {}", summary.patch.to_string().green()));
+    if summary.unknown > 0 {
+        output::line(format!("  Unknown: {}", summary.unknown.to_string().dimmed()));
+    }
+    output::line(format!("  Deprecated: {}", summary.deprecated.to_string().red()));
+    output::line(format!("  Unmaintained: {}", summary.stale));
+    if !report.notes.is_empty() {
+        output::blank();
+        for note in &report.notes {
+            output::line(format!("  {}", format!("⚠️  {}", note).dimmed()));
+        }
+    }
+    output::blank();
+    if report.offline {
+        output::line("💡 TIP: Run without --no-network to refresh the registry data".dimmed());
+    } else {
+        output::line("💡 TIP: Patch and minor upgrades are usually safe to batch; take major ones one at a time".dimmed());
+    }
+}
@@ -0,0 +1,130 @@
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::common::http;
+
+/// What the registry says about a package, trimmed to what `sniff outdated` reports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryInfo {
+    pub latest: String,
+    /// When `latest` was published
+    pub latest_published: Option<DateTime<Utc>>,
+    /// When the installed version was published
+    pub installed_published: Option<DateTime<Utc>>,
+    /// Deprecation message of the installed version
+    pub deprecated: Option<String>,
+    pub maintainers: usize,
+    /// Whether the latest version links to its source repository
+    pub has_repository: bool,
+}
+
+/// Where the registry data of past runs is kept for `--no-network`, relative to the project root
+const CACHE_PATH: &str = ".sniff-cache/npm-registry.json";
+
+/// The package's document on the registry, e.g. `https://registry.npmjs.org/@types%2fnode`
+pub fn package_url(registry: &str, name: &str) -> String {
+    format!("{}/{}", registry.trim_end_matches('/'), name.replace('/', "%2f"))
+}
+
+/// Fetch a package's document from the registry
+pub fn fetch(registry: &str, name: &str, installed: Option<&str>) -> Result<RegistryInfo> {
+    let document = http::get_json(&package_url(registry, name), 20)?;
+    parse_document(&document, installed)
+}
+
+/// Read the latest version, release dates, deprecation and maintenance signals of a package document
+pub fn parse_document(document: &Value, installed: Option<&str>) -> Result<RegistryInfo> {
+    let latest = document["dist-tags"]["latest"].as_str()
+        .ok_or_else(|| anyhow!("no `latest` dist-tag"))?
+        .to_string();
+    let published = |version: &str| {
+        document["time"][version].as_str()
+            .and_then(|time| DateTime::parse_from_rfc3339(time).ok())
+            .map(|time| time.with_timezone(&Utc))
+    };
+    let latest_manifest = &document["versions"][&latest];
+    Ok(RegistryInfo {
+        latest_published: published(&latest),
+        installed_published: installed.and_then(published),
+        deprecated: installed.and_then(|version| document["versions"][version]["deprecated"].as_str()).map(str::to_string),
+        maintainers: latest_manifest["maintainers"].as_array().or_else(|| document["maintainers"].as_array()).map_or(0, Vec::len),
+        has_repository: !latest_manifest["repository"].is_null() || !document["repository"].is_null(),
+        latest,
+    })
+}
+
+/// Registry data of earlier runs, keyed by `name@installed`
+#[derive(Debug, Default)]
+pub struct RegistryCache {
+    path: PathBuf,
+    entries: BTreeMap<String, CachedInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedInfo {
+    pub fetched: DateTime<Utc>,
+    #[serde(flatten)]
+    pub info: RegistryInfo,
+}
+
+impl RegistryCache {
+    /// The project's cache; a missing or unreadable cache is empty
+    pub fn load(project_dir: &Path) -> Self {
+        let path = project_dir.join(CACHE_PATH);
+        let entries = fs::read_to_string(&path).ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, entries }
+    }
+
+    pub fn get(&self, name: &str, installed: Option<&str>) -> Option<&CachedInfo> {
+        self.entries.get(&key(name, installed))
+    }
+
+    pub fn insert(&mut self, name: &str, installed: Option<&str>, info: RegistryInfo) {
+        self.entries.insert(key(name, installed), CachedInfo { fetched: Utc::now(), info });
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(())
+    }
+}
+
+fn key(name: &str, installed: Option<&str>) -> String {
+    format!("{}@{}", name, installed.unwrap_or("*"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_documents_are_read() {
+        let document = serde_json::json!({
+            "dist-tags": { "latest": "2.0.0" },
+            "time": { "1.0.0": "2019-03-01T10:00:00.000Z", "2.0.0": "2021-06-15T08:30:00.000Z" },
+            "versions": {
+                "1.0.0": { "deprecated": "Upgrade to 2.x, 1.x has a security issue" },
+                "2.0.0": { "maintainers": [{ "name": "a" }, { "name": "b" }], "repository": { "url": "git+https://github.com/x/y.git" } }
+            }
+        });
+
+        let info = parse_document(&document, Some("1.0.0")).unwrap();
+        assert_eq!(info.latest, "2.0.0");
+        assert_eq!(info.latest_published.unwrap().to_rfc3339(), "2021-06-15T08:30:00+00:00");
+        assert_eq!(info.installed_published.unwrap().to_rfc3339(), "2019-03-01T10:00:00+00:00");
+        assert_eq!(info.deprecated.as_deref(), Some("Upgrade to 2.x, 1.x has a security issue"));
+        assert_eq!((info.maintainers, info.has_repository), (2, true));
+
+        assert!(parse_document(&serde_json::json!({ "error": "Not found" }), None).is_err());
+        assert_eq!(package_url("https://registry.npmjs.org/", "@types/node"), "https://registry.npmjs.org/@types%2fnode");
+    }
+}
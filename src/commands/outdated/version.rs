@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+
+/// How far the installed version is behind the latest release, which is how risky the upgrade is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpgradeDistance {
+    Current,
+    /// Bug fixes only
+    Patch,
+    /// New features, backwards compatible
+    Minor,
+    /// Breaking changes; for `0.x` versions a minor bump counts as major
+    Major,
+    /// The installed or latest version is not known or not a semver version
+    Unknown,
+}
+
+impl UpgradeDistance {
+    pub fn risk(self) -> &'static str {
+        match self {
+            UpgradeDistance::Current => "none",
+            UpgradeDistance::Patch => "low",
+            UpgradeDistance::Minor => "moderate",
+            UpgradeDistance::Major => "high",
+            UpgradeDistance::Unknown => "unknown",
+        }
+    }
+}
+
+/// Major, minor and patch of `1.2.3`, `v1.2.3` or `1.2.3-beta.1`; missing parts are 0
+pub fn parse(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim().trim_start_matches(['v', '=']).split(['-', '+']).next()?;
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    let patch = parts.next().map_or(Some(0), |part| part.parse().ok())?;
+    Some((major, minor, patch))
+}
+
+/// The lowest version a `package.json` range such as `^18.2.0` or `~1.4` allows. Tags, `*`,
+/// URLs and `workspace:` ranges have none
+pub fn range_base(range: &str) -> Option<String> {
+    let first = range.split("||").next()?.trim().trim_start_matches(['^', '~', '>', '=', 'v', ' ']);
+    let base = first.split_whitespace().next()?;
+    parse(base).map(|_| base.to_string())
+}
+
+/// Distance from `installed` to `latest`, and how many major versions apart they are
+pub fn distance(installed: &str, latest: &str) -> (UpgradeDistance, u64) {
+    let (Some(from), Some(to)) = (parse(installed), parse(latest)) else {
+        return (UpgradeDistance::Unknown, 0);
+    };
+    if to <= from {
+        return (UpgradeDistance::Current, 0);
+    }
+    let distance = if to.0 != from.0 || (from.0 == 0 && to.1 != from.1) {
+        UpgradeDistance::Major
+    } else if to.1 != from.1 {
+        UpgradeDistance::Minor
+    } else {
+        UpgradeDistance::Patch
+    };
+    (distance, to.0 - from.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions_ranges_and_distances() {
+        assert_eq!(parse("v18.2.0"), Some((18, 2, 0)));
+        assert_eq!(parse("1.4.0-beta.2"), Some((1, 4, 0)));
+        assert_eq!(parse("latest"), None);
+        assert_eq!(range_base("^18.2.0").as_deref(), Some("18.2.0"));
+        assert_eq!(range_base(">= 2.1 < 3").as_deref(), Some("2.1"));
+        assert_eq!(range_base("workspace:*"), None);
+
+        assert_eq!(distance("18.2.0", "19.1.0"), (UpgradeDistance::Major, 1));
+        assert_eq!(distance("16.0.0", "19.1.0"), (UpgradeDistance::Major, 3));
+        assert_eq!(distance("0.3.1", "0.4.0"), (UpgradeDistance::Major, 0));
+        assert_eq!(distance("4.17.0", "4.18.2"), (UpgradeDistance::Minor, 0));
+        assert_eq!(distance("4.17.0", "4.17.21"), (UpgradeDistance::Patch, 0));
+        assert_eq!(distance("5.0.0", "5.0.0"), (UpgradeDistance::Current, 0));
+        assert_eq!(distance("5.1.0-rc.1", "5.0.0"), (UpgradeDistance::Current, 0));
+        assert_eq!(distance("git+https://x", "1.0.0"), (UpgradeDistance::Unknown, 0));
+    }
+}
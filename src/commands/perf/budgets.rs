@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::config::PerformanceConfig;
+use super::WebVitals;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PerfBudgetResult {
+    /// Category id for score budgets, otherwise the metric (`lcp`, `tbt`, `cls`, ...)
+    pub name: String,
+    pub kind: PerfBudgetKind,
+    pub limit: f64,
+    /// Milliseconds, KB, a 0-100 score, or unitless for CLS
+    pub actual: Option<f64>,
+    pub status: BudgetStatus,
+}
+
+/// Scores are minimums, metrics are maximums
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PerfBudgetKind {
+    Score,
+    Metric,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetStatus {
+    Pass,
+    Fail,
+    /// The category was not audited or Lighthouse did not report the metric
+    NotMeasured,
+}
+
+/// Check one audit against the `[performance]` score minimums and `[performance.budgets]`
+pub fn evaluate(
+    categories: &BTreeMap<String, f64>,
+    vitals: &WebVitals,
+    first_load_js_bytes: Option<u64>,
+    config: &PerformanceConfig,
+) -> Vec<PerfBudgetResult> {
+    let budgets = &config.budgets;
+    let mut scores = BTreeMap::from([
+        ("performance".to_string(), config.min_performance_score),
+        ("accessibility".to_string(), config.min_accessibility_score),
+    ]);
+    scores.extend(budgets.scores.iter().map(|(name, score)| (name.clone(), *score)));
+
+    let mut results: Vec<PerfBudgetResult> = scores.into_iter()
+        .map(|(name, limit)| {
+            let actual = categories.get(&name).copied();
+            result(name, PerfBudgetKind::Score, limit, actual)
+        })
+        .collect();
+
+    let metrics = [
+        ("lcp", budgets.lcp_ms, vitals.lcp_ms),
+        ("tbt", budgets.tbt_ms, vitals.tbt_ms),
+        ("cls", budgets.cls, vitals.cls),
+        ("fcp", budgets.fcp_ms, vitals.fcp_ms),
+        ("speed_index", budgets.speed_index_ms, vitals.speed_index_ms),
+        ("first_load_js", budgets.first_load_kb.map(|kb| kb as f64), first_load_js_bytes.map(|b| b as f64 / 1024.0)),
+    ];
+    for (name, limit, actual) in metrics {
+        if let Some(limit) = limit {
+            results.push(result(name.to_string(), PerfBudgetKind::Metric, limit, actual));
+        }
+    }
+
+    results
+}
+
+fn result(name: String, kind: PerfBudgetKind, limit: f64, actual: Option<f64>) -> PerfBudgetResult {
+    let status = match actual {
+        None => BudgetStatus::NotMeasured,
+        Some(value) if kind == PerfBudgetKind::Score && value < limit => BudgetStatus::Fail,
+        Some(value) if kind == PerfBudgetKind::Metric && value > limit => BudgetStatus::Fail,
+        Some(_) => BudgetStatus::Pass,
+    };
+    PerfBudgetResult { name, kind, limit, actual, status }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn test_scores_and_metric_budgets() {
+        let mut config = Config::default().performance;
+        config.budgets.scores.insert("performance".to_string(), 85.0);
+        config.budgets.lcp_ms = Some(2500.0);
+        config.budgets.cls = Some(0.1);
+        config.budgets.first_load_kb = Some(300);
+
+        let categories = BTreeMap::from([("performance".to_string(), 82.0), ("accessibility".to_string(), 95.0)]);
+        let vitals = WebVitals { lcp_ms: Some(2100.0), cls: Some(0.12), ..Default::default() };
+        let results = evaluate(&categories, &vitals, None, &config);
+
+        let statuses: Vec<(&str, BudgetStatus)> = results.iter().map(|r| (r.name.as_str(), r.status)).collect();
+        assert_eq!(statuses, vec![
+            ("accessibility", BudgetStatus::Pass),
+            ("performance", BudgetStatus::Fail),
+            ("lcp", BudgetStatus::Pass),
+            ("cls", BudgetStatus::Fail),
+            ("first_load_js", BudgetStatus::NotMeasured),
+        ]);
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Core Web Vitals and related lab metrics from Lighthouse; times in milliseconds, CLS unitless
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebVitals {
+    /// Largest Contentful Paint
+    pub lcp_ms: Option<f64>,
+    /// Total Blocking Time, the lab stand-in for INP
+    pub tbt_ms: Option<f64>,
+    /// Cumulative Layout Shift
+    pub cls: Option<f64>,
+    /// First Contentful Paint
+    pub fcp_ms: Option<f64>,
+    pub speed_index_ms: Option<f64>,
+}
+
+/// Scores (0-100) and metrics from one Lighthouse run
+pub struct LighthouseRun {
+    pub categories: BTreeMap<String, f64>,
+    pub vitals: WebVitals,
+    pub data: Value,
+}
+
+pub fn parse_run(output: &str) -> Result<LighthouseRun> {
+    let data: Value = serde_json::from_str(output)?;
+    let categories: BTreeMap<String, f64> = data["categories"].as_object()
+        .ok_or_else(|| anyhow!("Lighthouse output has no categories"))?
+        .iter()
+        .filter_map(|(name, category)| category["score"].as_f64().map(|score| (name.clone(), score * 100.0)))
+        .collect();
+
+    let metric = |audit: &str| data["audits"][audit]["numericValue"].as_f64();
+    let vitals = WebVitals {
+        lcp_ms: metric("largest-contentful-paint"),
+        tbt_ms: metric("total-blocking-time"),
+        cls: metric("cumulative-layout-shift"),
+        fcp_ms: metric("first-contentful-paint"),
+        speed_index_ms: metric("speed-index"),
+    };
+
+    Ok(LighthouseRun { categories, vitals, data })
+}
+
+/// Median scores and metrics across runs, plus the raw data of the run closest to the
+/// median performance score (used for audit-level recommendations)
+pub fn aggregate(runs: Vec<LighthouseRun>) -> (BTreeMap<String, f64>, WebVitals, Value) {
+    let mut names: Vec<&String> = runs.iter().flat_map(|run| run.categories.keys()).collect();
+    names.sort();
+    names.dedup();
+    let categories: BTreeMap<String, f64> = names.into_iter()
+        .filter_map(|name| median(runs.iter().filter_map(|run| run.categories.get(name).copied())).map(|m| (name.clone(), m)))
+        .collect();
+
+    let vitals = WebVitals {
+        lcp_ms: median(runs.iter().filter_map(|run| run.vitals.lcp_ms)),
+        tbt_ms: median(runs.iter().filter_map(|run| run.vitals.tbt_ms)),
+        cls: median(runs.iter().filter_map(|run| run.vitals.cls)),
+        fcp_ms: median(runs.iter().filter_map(|run| run.vitals.fcp_ms)),
+        speed_index_ms: median(runs.iter().filter_map(|run| run.vitals.speed_index_ms)),
+    };
+
+    let target = categories.get("performance").copied().unwrap_or(0.0);
+    let data = runs.into_iter()
+        .min_by(|a, b| {
+            let distance = |run: &LighthouseRun| (run.categories.get("performance").copied().unwrap_or(0.0) - target).abs();
+            distance(a).total_cmp(&distance(b))
+        })
+        .map(|run| run.data)
+        .unwrap_or(Value::Null);
+
+    (categories, vitals, data)
+}
+
+/// JavaScript transferred while loading the page, from the `network-requests` audit
+pub fn first_load_js_bytes(data: &Value) -> Option<u64> {
+    let items = data["audits"]["network-requests"]["details"]["items"].as_array()?;
+    Some(items.iter()
+        .filter(|item| item["resourceType"] == "Script")
+        .filter_map(|item| item["transferSize"].as_f64())
+        .sum::<f64>() as u64)
+}
+
+fn median(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(performance: f64, lcp: f64) -> LighthouseRun {
+        let output = format!(
+            r#"{{"categories": {{"performance": {{"score": {}}}, "seo": {{"score": 1.0}}}},
+                "audits": {{"largest-contentful-paint": {{"numericValue": {}}}, "cumulative-layout-shift": {{"numericValue": 0.02}}}}}}"#,
+            performance, lcp
+        );
+        parse_run(&output).unwrap()
+    }
+
+    #[test]
+    fn test_median_across_runs() {
+        let (categories, vitals, data) = aggregate(vec![run(0.62, 3100.0), run(0.91, 1800.0), run(0.75, 2400.0)]);
+
+        assert_eq!(categories["performance"].round(), 75.0);
+        assert_eq!(categories["seo"], 100.0);
+        assert_eq!(vitals.lcp_ms, Some(2400.0));
+        assert_eq!(vitals.cls, Some(0.02));
+        assert_eq!(vitals.tbt_ms, None);
+        assert_eq!(data["audits"]["largest-contentful-paint"]["numericValue"], 2400.0);
+    }
+}
@@ -0,0 +1,688 @@
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::process::Command;
+use std::time::Instant;
+use crate::common::{output, ExitCode, CommandOutcome, check_failure_threshold, create_standard_json_output, output_result};
+use crate::config::{Config, LighthousePreset, PerformanceConfig};
+
+mod budgets;
+mod lighthouse;
+mod server;
+pub use budgets::{BudgetStatus, PerfBudgetKind, PerfBudgetResult};
+pub use lighthouse::WebVitals;
+
+/// Audit settings from the command line; anything unset falls back to `[performance]` config
+#[derive(Debug, Clone, Default)]
+pub struct PerfOptions {
+    pub url: Option<String>,
+    pub runs: Option<usize>,
+    pub categories: Option<Vec<String>>,
+    pub preset: Option<LighthousePreset>,
+    /// Shell command that starts the server to audit; it is stopped after the audit
+    pub start: Option<String>,
+    /// URL to poll until the started server is ready
+    pub wait_on: Option<String>,
+    pub wait_timeout_secs: Option<u64>,
+}
+
+/// Options merged with configuration
+struct AuditSettings {
+    urls: Vec<String>,
+    runs: usize,
+    categories: Vec<String>,
+    preset: LighthousePreset,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PerformanceReport {
+    pub url: String,
+    pub runs: usize,
+    pub preset: LighthousePreset,
+    pub web_vitals: WebVitals,
+    /// JavaScript transferred on first load of the audited page
+    pub first_load_js_bytes: Option<u64>,
+    pub budgets: Vec<PerfBudgetResult>,
+    pub audit_results: Vec<AuditResult>,
+    pub summary: PerformanceSummary,
+    pub recommendations: Vec<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditResult {
+    pub name: String,
+    pub score: f64,
+    pub status: PerformanceStatus,
+    pub value: Option<f64>,
+    pub unit: Option<String>,
+    pub description: String,
+    pub recommendation: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum PerformanceStatus {
+    Excellent,  // 90-100
+    Good,       // 75-89
+    NeedsWork,  // 50-74
+    Poor,       // 0-49
+    NotMeasured,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PerformanceSummary {
+    pub overall_score: f64,
+    pub performance_score: f64,
+    pub accessibility_score: f64,
+    pub best_practices_score: f64,
+    pub seo_score: f64,
+    pub total_audits: usize,
+    pub passed_audits: usize,
+}
+
+pub async fn run(options: &PerfOptions, json: bool, quiet: bool) -> Result<CommandOutcome<Option<PerformanceReport>>> {
+    if !check_lighthouse_available() {
+        if json {
+            let mut response = create_standard_json_output("perf", None::<PerformanceReport>, 0, 0, None);
+            response.warnings.push("Lighthouse is not installed; install it with `npm install -g lighthouse`".to_string());
+            println!("{}", response.to_json_pretty()?);
+            return Ok(CommandOutcome::new(None, ExitCode::Success));
+        }
+        output::line("📦 sniff perf requires Lighthouse to run.".bold());
+        output::blank();
+        output::line("  Install it with:");
+        output::line(format!("    {}", "npm install -g lighthouse".bright_white()));
+        output::blank();
+        output::line("  Then make sure your dev server is running and re-run:");
+        output::line(format!("    {}", "sniff perf".bright_white()));
+        return Ok(CommandOutcome::new(None, ExitCode::Success));
+    }
+
+    if !quiet && !json {
+        output::line("🚀 Running Lighthouse performance audit...".bold().blue());
+        match &options.start {
+            Some(command) => output::line(format!("Starting `{}` and waiting for it to respond", command).dimmed()),
+            None => output::line("Please ensure your development server is running".dimmed()),
+        }
+    }
+
+    let outcome = check(options).await?;
+    let report = &outcome.report;
+
+    let failed_budgets = report.budgets.iter().filter(|budget| budget.status == BudgetStatus::Fail).count();
+    let response = create_standard_json_output(
+        "perf",
+        report,
+        report.summary.total_audits,
+        report.summary.total_audits.saturating_sub(report.summary.passed_audits) + failed_budgets,
+        Some(report.duration_ms),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_performance_report(report, quiet))?;
+
+    let exit_code = outcome.exit_code;
+    Ok(CommandOutcome::new(Some(outcome.report), exit_code))
+}
+
+/// Run a Lighthouse audit and decide the exit status, without printing a report
+pub async fn check(options: &PerfOptions) -> Result<CommandOutcome<PerformanceReport>> {
+    let config = Config::load()?.performance;
+
+    // Held until the audit finishes; dropping it stops the server
+    let _server = match &options.start {
+        Some(command) => {
+            let ready_url = options.wait_on.as_ref()
+                .or(options.url.as_ref())
+                .or(config.url.as_ref())
+                .ok_or_else(|| anyhow!("--start needs --wait-on <URL> (or --url) to know when the server is ready"))?;
+            let timeout = options.wait_timeout_secs.unwrap_or(server::DEFAULT_WAIT_TIMEOUT_SECS);
+            let mut server = server::DevServer::start(command)?;
+            server.wait_until_ready(ready_url, std::time::Duration::from_secs(timeout)).await?;
+            Some(server)
+        }
+        None => None,
+    };
+
+    let settings = resolve_settings(options, &config).await;
+
+    let start_time = Instant::now();
+    let audit = run_lighthouse_audit(&settings)?;
+    let duration = start_time.elapsed().as_millis() as u64;
+
+    let summary = calculate_performance_summary(&audit.audit_results);
+    let budgets = budgets::evaluate(&audit.categories, &audit.web_vitals, audit.first_load_js_bytes, &config);
+
+    let report = PerformanceReport {
+        url: audit.url,
+        runs: settings.runs,
+        preset: settings.preset,
+        web_vitals: audit.web_vitals,
+        first_load_js_bytes: audit.first_load_js_bytes,
+        budgets,
+        audit_results: audit.audit_results,
+        summary,
+        recommendations: audit.recommendations,
+        duration_ms: duration,
+    };
+
+    let over_budget = report.budgets.iter().any(|budget| budget.status == BudgetStatus::Fail);
+    let exit_code = check_failure_threshold(over_budget, ExitCode::GeneralError);
+
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+fn check_lighthouse_available() -> bool {
+    Command::new("lighthouse")
+        .arg("--version")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+async fn resolve_settings(options: &PerfOptions, config: &PerformanceConfig) -> AuditSettings {
+    // An explicit URL wins; otherwise prefer a server that is actually running
+    let explicit = options.url.clone()
+        .or_else(|| config.url.clone())
+        .or_else(|| options.wait_on.clone());
+    let urls = match explicit {
+        Some(url) => vec![url],
+        None => {
+            let detected = detect_running_servers().await;
+            if detected.is_empty() { config.server_urls.clone() } else { detected }
+        }
+    };
+
+    AuditSettings {
+        urls,
+        runs: options.runs.unwrap_or(config.runs).max(1),
+        categories: options.categories.clone().unwrap_or_else(|| config.categories.clone()),
+        preset: options.preset.unwrap_or(config.preset),
+    }
+}
+
+struct AuditOutput {
+    url: String,
+    categories: BTreeMap<String, f64>,
+    web_vitals: WebVitals,
+    first_load_js_bytes: Option<u64>,
+    audit_results: Vec<AuditResult>,
+    recommendations: Vec<String>,
+}
+
+fn lighthouse_once(url: &str, settings: &AuditSettings) -> Option<String> {
+    let mut command = Command::new("lighthouse");
+    command
+        .arg(url)
+        .arg("--output=json")
+        .arg(format!("--only-categories={}", settings.categories.join(",")))
+        .arg("--chrome-flags=--headless")
+        .arg("--quiet");
+    if settings.preset == LighthousePreset::Desktop {
+        command.arg("--preset=desktop");
+    }
+
+    let output = command.output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn run_lighthouse_audit(settings: &AuditSettings) -> Result<AuditOutput> {
+    // The first URL Lighthouse can reach is used for every run
+    let (url, first) = settings.urls.iter()
+        .find_map(|url| lighthouse_once(url, settings).map(|output| (url.clone(), output)))
+        .ok_or_else(|| {
+            anyhow!(
+                "Lighthouse could not reach any running server.\nTried: {}\n\nStart your dev server first (e.g. npm run dev) or pass --url.",
+                settings.urls.join(", ")
+            )
+        })?;
+
+    let mut runs = vec![lighthouse::parse_run(&first)?];
+    for run in 2..=settings.runs {
+        let output = lighthouse_once(&url, settings)
+            .ok_or_else(|| anyhow!("Lighthouse run {} of {} against {} failed", run, settings.runs, url))?;
+        runs.push(lighthouse::parse_run(&output)?);
+    }
+
+    let (categories, web_vitals, lighthouse_data) = lighthouse::aggregate(runs);
+    let first_load_js_bytes = lighthouse::first_load_js_bytes(&lighthouse_data);
+
+    let audit_results = categories.iter()
+        .map(|(category_name, &score_percent)| {
+            let status = match score_percent {
+                s if s >= 90.0 => PerformanceStatus::Excellent,
+                s if s >= 75.0 => PerformanceStatus::Good,
+                s if s >= 50.0 => PerformanceStatus::NeedsWork,
+                _ => PerformanceStatus::Poor,
+            };
+
+            AuditResult {
+                name: category_name.replace('-', " ").to_title_case(),
+                score: score_percent,
+                status,
+                value: Some(score_percent),
+                unit: Some("%".to_string()),
+                description: format!("{} score from Lighthouse audit", category_name),
+                recommendation: get_category_recommendation(category_name, score_percent),
+            }
+        })
+        .collect();
+
+    Ok(AuditOutput {
+        url,
+        categories,
+        web_vitals,
+        first_load_js_bytes,
+        audit_results,
+        recommendations: generate_lighthouse_recommendations(&lighthouse_data),
+    })
+}
+
+fn get_category_recommendation(category: &str, score: f64) -> Option<String> {
+    match category {
+        "performance" if score < 75.0 => Some("Focus on Core Web Vitals: LCP, FID, and CLS metrics".to_string()),
+        "accessibility" if score < 75.0 => Some("Add proper ARIA labels, alt text, and keyboard navigation".to_string()),
+        "best-practices" if score < 75.0 => Some("Follow security best practices and avoid deprecated APIs".to_string()),
+        "seo" if score < 75.0 => Some("Add meta tags, structured data, and improve page titles".to_string()),
+        _ => None,
+    }
+}
+
+fn generate_lighthouse_recommendations(data: &serde_json::Value) -> Vec<String> {
+    let mut recommendations = Vec::new();
+
+    if let Some(audits) = data["audits"].as_object() {
+        for (audit_id, audit) in audits {
+            if let Some(score) = audit["score"].as_f64() {
+                if score < 0.9 {
+                    match audit_id.as_str() {
+                        "first-contentful-paint" => recommendations.push("Optimize First Contentful Paint by reducing server response times".to_string()),
+                        "largest-contentful-paint" => recommendations.push("Improve Largest Contentful Paint by optimizing images and preloading key resources".to_string()),
+                        "cumulative-layout-shift" => recommendations.push("Reduce Cumulative Layout Shift by setting dimensions on images and embeds".to_string()),
+                        "unused-javascript" => recommendations.push("Remove unused JavaScript to reduce bundle size".to_string()),
+                        "render-blocking-resources" => recommendations.push("Eliminate render-blocking resources by inlining critical CSS".to_string()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    recommendations
+}
+
+fn calculate_performance_summary(audit_results: &[AuditResult]) -> PerformanceSummary {
+    let total_audits = audit_results.len();
+    let passed_audits = audit_results.iter().filter(|r| r.score >= 75.0).count();
+
+    let overall_score = if total_audits > 0 {
+        audit_results.iter().map(|r| r.score).sum::<f64>() / total_audits as f64
+    } else {
+        0.0
+    };
+
+    let performance_score = audit_results.iter()
+        .filter(|r| r.name.to_lowercase().contains("performance") || r.name.to_lowercase().contains("bundle"))
+        .map(|r| r.score)
+        .fold(0.0_f64, |acc, score| if acc == 0.0 { score } else { (acc + score) / 2.0 });
+
+    let accessibility_score = audit_results.iter()
+        .find(|r| r.name.to_lowercase().contains("accessibility"))
+        .map(|r| r.score)
+        .unwrap_or(0.0);
+
+    let best_practices_score = audit_results.iter()
+        .find(|r| r.name.to_lowercase().contains("best"))
+        .map(|r| r.score)
+        .unwrap_or(0.0);
+
+    let seo_score = audit_results.iter()
+        .find(|r| r.name.to_lowercase().contains("seo"))
+        .map(|r| r.score)
+        .unwrap_or(0.0);
+
+    PerformanceSummary {
+        overall_score,
+        performance_score,
+        accessibility_score,
+        best_practices_score,
+        seo_score,
+        total_audits,
+        passed_audits,
+    }
+}
+
+fn print_performance_report(report: &PerformanceReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🚀 Performance Audit Report".bold().blue());
+        output::line("==========================".blue());
+        output::blank();
+    }
+
+    let preset = match report.preset {
+        LighthousePreset::Mobile => "mobile",
+        LighthousePreset::Desktop => "desktop",
+    };
+    let runs = if report.runs == 1 { "1 run".to_string() } else { format!("median of {} runs", report.runs) };
+    output::line(format!("🌐 {} {}", report.url.cyan(), format!("({}, {})", preset, runs).dimmed()));
+    output::blank();
+
+    let mut categories: HashMap<String, Vec<&AuditResult>> = HashMap::new();
+    for result in &report.audit_results {
+        let category = if result.name.to_lowercase().contains("performance") {
+            "Performance"
+        } else if result.name.to_lowercase().contains("accessibility") {
+            "Accessibility"
+        } else if result.name.to_lowercase().contains("seo") {
+            "SEO"
+        } else if result.name.to_lowercase().contains("best") {
+            "Best Practices"
+        } else {
+            "General"
+        };
+        categories.entry(category.to_string()).or_default().push(result);
+    }
+
+    for (category, results) in &categories {
+        output::line(format!("📊 {}", category.to_uppercase()).bold().white());
+        output::line("─".repeat(category.len() + 4).white());
+
+        for result in results {
+            let (icon, color) = match result.status {
+                PerformanceStatus::Excellent => ("🟢", "green"),
+                PerformanceStatus::Good => ("🟡", "yellow"),
+                PerformanceStatus::NeedsWork => ("🟠", "yellow"),
+                PerformanceStatus::Poor => ("🔴", "red"),
+                PerformanceStatus::NotMeasured => ("⚪", "white"),
+            };
+
+            let score_text = format!("{:.1}", result.score);
+            let colored_score = match color {
+                "green" => score_text.green(),
+                "yellow" => score_text.yellow(),
+                "red" => score_text.red(),
+                _ => score_text.white(),
+            };
+            let unit_suffix = result.unit.as_deref().map(|u| format!(" {}", u)).unwrap_or_default();
+
+            output::line(format!("  {} {} ({}{})", icon, result.name.bold(), colored_score, unit_suffix));
+
+            if !result.description.is_empty() {
+                output::line(format!("     {}", result.description.dimmed()));
+            }
+
+            if let Some(recommendation) = &result.recommendation {
+                output::line(format!("     💡 {}", recommendation.yellow()));
+            }
+        }
+        output::blank();
+    }
+
+    print_web_vitals(&report.web_vitals);
+    print_budgets(&report.budgets);
+
+    if !report.recommendations.is_empty() {
+        output::line("💡 RECOMMENDATIONS".bold().green());
+        output::line("──────────────────".green());
+        for rec in &report.recommendations {
+            output::line(format!("  • {}", rec.green()));
+        }
+        output::blank();
+    }
+
+    print_performance_summary(&report.summary, report.duration_ms);
+}
+
+fn print_web_vitals(vitals: &WebVitals) {
+    // Thresholds from web.dev: (good, poor)
+    let rows = [
+        ("Largest Contentful Paint", vitals.lcp_ms, 2500.0, 4000.0, true),
+        ("Total Blocking Time", vitals.tbt_ms, 200.0, 600.0, true),
+        ("Cumulative Layout Shift", vitals.cls, 0.1, 0.25, false),
+        ("First Contentful Paint", vitals.fcp_ms, 1800.0, 3000.0, true),
+        ("Speed Index", vitals.speed_index_ms, 3400.0, 5800.0, true),
+    ];
+    if rows.iter().all(|(_, value, ..)| value.is_none()) {
+        return;
+    }
+
+    output::line("⚡ CORE WEB VITALS".bold().white());
+    output::line("─────────────────".white());
+    for (name, value, good, poor, is_time) in rows {
+        let Some(value) = value else { continue };
+        let text = if is_time { format!("{:.2} s", value / 1000.0) } else { format!("{:.3}", value) };
+        let (icon, text) = if value <= good {
+            ("🟢", text.green())
+        } else if value <= poor {
+            ("🟠", text.yellow())
+        } else {
+            ("🔴", text.red())
+        };
+        output::line(format!("  {} {}: {}", icon, name, text));
+    }
+    output::blank();
+}
+
+fn print_budgets(budgets: &[PerfBudgetResult]) {
+    if budgets.is_empty() {
+        return;
+    }
+
+    output::line("💰 BUDGETS".bold().white());
+    output::line("──────────".white());
+    output::line(format!("  {}", format!("{:<22} {:>12} {:>12}   {}", "Budget", "Limit", "Actual", "Result").dimmed()));
+
+    for budget in budgets {
+        let (label, limit, actual) = match budget.kind {
+            PerfBudgetKind::Score => (
+                format!("{} score", budget.name),
+                format!("≥ {:.0}", budget.limit),
+                budget.actual.map(|v| format!("{:.0}", v)),
+            ),
+            PerfBudgetKind::Metric => {
+                let (label, unit, precision) = match budget.name.as_str() {
+                    "lcp" => ("LCP", " ms", 0),
+                    "tbt" => ("TBT", " ms", 0),
+                    "cls" => ("CLS", "", 3),
+                    "fcp" => ("FCP", " ms", 0),
+                    "speed_index" => ("Speed Index", " ms", 0),
+                    "first_load_js" => ("First-load JS", " KB", 0),
+                    other => (other, "", 2),
+                };
+                (
+                    label.to_string(),
+                    format!("≤ {:.*}{}", precision, budget.limit, unit),
+                    budget.actual.map(|v| format!("{:.*}{}", precision, v, unit)),
+                )
+            }
+        };
+        let actual = actual.unwrap_or_else(|| "-".to_string());
+        let row = format!("{:<22} {:>12} {:>12}", label, limit, actual);
+
+        match budget.status {
+            BudgetStatus::Pass => output::line(format!("  {}   {}", row, "✅ pass".green())),
+            BudgetStatus::Fail => output::line(format!("  {}   {}", row.red(), "❌ fail".red())),
+            BudgetStatus::NotMeasured => output::line(format!("  {}   {}", row.dimmed(), "⚪ not measured".dimmed())),
+        }
+    }
+    output::blank();
+}
+
+fn print_performance_summary(summary: &PerformanceSummary, duration_ms: u64) {
+    output::line("📈 PERFORMANCE SUMMARY".bold().white());
+    output::line("─────────────────────".white());
+
+    let colored_score = match summary.overall_score {
+        s if s >= 90.0 => format!("{:.1}%", s).green(),
+        s if s >= 75.0 => format!("{:.1}%", s).yellow(),
+        s if s >= 50.0 => format!("{:.1}%", s).yellow(),
+        s => format!("{:.1}%", s).red(),
+    };
+
+    output::line(format!("  Overall Score: {}", colored_score));
+
+    if summary.performance_score > 0.0 {
+        output::line(format!("  Performance: {:.1}%", summary.performance_score));
+    }
+    if summary.accessibility_score > 0.0 {
+        output::line(format!("  Accessibility: {:.1}%", summary.accessibility_score));
+    }
+    if summary.best_practices_score > 0.0 {
+        output::line(format!("  Best Practices: {:.1}%", summary.best_practices_score));
+    }
+    if summary.seo_score > 0.0 {
+        output::line(format!("  SEO: {:.1}%", summary.seo_score));
+    }
+
+    output::line(format!("  Audits passed: {}/{}", summary.passed_audits, summary.total_audits));
+    output::line(format!("  Audit time: {}ms", duration_ms));
+    output::blank();
+
+    let (status_icon, status_text, status_color) = match summary.overall_score {
+        s if s >= 90.0 => ("🎉", "EXCELLENT PERFORMANCE", "green"),
+        s if s >= 75.0 => ("✅", "GOOD PERFORMANCE", "green"),
+        s if s >= 50.0 => ("⚠️", "NEEDS IMPROVEMENT", "yellow"),
+        _ => ("🚨", "POOR PERFORMANCE", "red"),
+    };
+
+    let colored_status = match status_color {
+        "green" => format!("{} {}", status_icon, status_text).green().bold(),
+        "yellow" => format!("{} {}", status_icon, status_text).yellow().bold(),
+        "red" => format!("{} {}", status_icon, status_text).red().bold(),
+        _ => format!("{} {}", status_icon, status_text).white().bold(),
+    };
+
+    output::line(format!("  Status: {}", colored_status));
+
+    if summary.overall_score < 75.0 {
+        output::blank();
+        output::line("🎯 FOCUS AREAS".bold().cyan());
+        output::line("─────────────".cyan());
+        if summary.performance_score > 0.0 && summary.performance_score < 75.0 {
+            output::line("  • Optimize Core Web Vitals (LCP, FID, CLS)");
+        }
+        if summary.accessibility_score > 0.0 && summary.accessibility_score < 75.0 {
+            output::line("  • Improve accessibility compliance");
+        }
+        if summary.best_practices_score > 0.0 && summary.best_practices_score < 75.0 {
+            output::line("  • Follow web development best practices");
+        }
+        if summary.seo_score > 0.0 && summary.seo_score < 75.0 {
+            output::line("  • Enhance SEO optimization");
+        }
+    }
+
+    output::blank();
+    output::line("💡 TIP: Run performance audits regularly during development".dimmed());
+}
+
+trait ToTitleCase {
+    fn to_title_case(&self) -> String;
+}
+
+impl ToTitleCase for str {
+    fn to_title_case(&self) -> String {
+        self.split_whitespace()
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    None => String::new(),
+                    Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+async fn detect_running_servers() -> Vec<String> {
+    let mut detected_servers = Vec::new();
+
+    let ports_to_check = vec![
+        3000, 3001, 3002, 3003,
+        4200, 4201,
+        8000, 8001, 8080, 8081,
+        5000, 5001, 5173, 5174,
+        9000, 9001,
+        1234,
+    ];
+
+    for port in ports_to_check {
+        if is_port_responsive(port).await {
+            let url = format!("http://localhost:{}", port);
+            if is_http_server_responsive(&url).await {
+                detected_servers.push(url);
+            }
+        }
+    }
+
+    if let Ok(framework_servers) = detect_framework_servers().await {
+        detected_servers.extend(framework_servers);
+    }
+
+    detected_servers
+}
+
+async fn is_port_responsive(port: u16) -> bool {
+    use std::net::{TcpStream, SocketAddr};
+    use std::time::Duration;
+
+    let addr = format!("127.0.0.1:{}", port);
+    if let Ok(socket_addr) = addr.parse::<SocketAddr>() {
+        TcpStream::connect_timeout(&socket_addr, Duration::from_millis(100)).is_ok()
+    } else {
+        false
+    }
+}
+
+async fn is_http_server_responsive(url: &str) -> bool {
+    if let Ok(output) = tokio::process::Command::new("curl")
+        .arg("-s")
+        .arg("-I")
+        .arg("--connect-timeout")
+        .arg("1")
+        .arg("--max-time")
+        .arg("2")
+        .arg(url)
+        .output()
+        .await
+    {
+        let response = String::from_utf8_lossy(&output.stdout);
+        response.starts_with("HTTP/") && (response.contains("200") || response.contains("404"))
+    } else {
+        false
+    }
+}
+
+async fn detect_framework_servers() -> Result<Vec<String>> {
+    let mut servers = Vec::new();
+
+    if let Ok(output) = tokio::process::Command::new("pgrep")
+        .arg("-f").arg("next dev")
+        .output().await
+    {
+        if output.status.success() && !output.stdout.is_empty() && is_port_responsive(3000).await {
+            servers.push("http://localhost:3000".to_string());
+        }
+    }
+
+    if let Ok(output) = tokio::process::Command::new("pgrep")
+        .arg("-f").arg("vite")
+        .output().await
+    {
+        if output.status.success() && !output.stdout.is_empty() && is_port_responsive(5173).await {
+            servers.push("http://localhost:5173".to_string());
+        }
+    }
+
+    if let Ok(output) = tokio::process::Command::new("pgrep")
+        .arg("-f").arg("ng serve")
+        .output().await
+    {
+        if output.status.success() && !output.stdout.is_empty() && is_port_responsive(4200).await {
+            servers.push("http://localhost:4200".to_string());
+        }
+    }
+
+    Ok(servers)
+}
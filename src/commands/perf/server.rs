@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Context, Result};
+use std::process::{Child, Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// Seconds to wait for a `--start` server when `--wait-timeout` is not given
+pub const DEFAULT_WAIT_TIMEOUT_SECS: u64 = 120;
+
+/// A dev or preview server started for the audit; stopped (with its children) when dropped
+pub struct DevServer {
+    child: Child,
+    command: String,
+}
+
+impl DevServer {
+    pub fn start(command: &str) -> Result<Self> {
+        let mut shell = shell_command(command);
+        // The server's own output would end up in the report
+        shell.stdin(Stdio::null()).stdout(Stdio::null());
+
+        // Own process group, so `npm run dev` and the node process it spawns are stopped together
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::CommandExt;
+            shell.process_group(0);
+        }
+
+        let child = shell.spawn().with_context(|| format!("Failed to start `{}`", command))?;
+        Ok(Self { child, command: command.to_string() })
+    }
+
+    /// Poll `url` until it answers HTTP, the server exits, or `timeout` passes
+    pub async fn wait_until_ready(&mut self, url: &str, timeout: Duration) -> Result<()> {
+        let started = Instant::now();
+        loop {
+            if let Some(status) = self.child.try_wait()? {
+                return Err(anyhow!("`{}` exited with {} before {} was ready", self.command, status, url));
+            }
+            if responds(url).await {
+                return Ok(());
+            }
+            if started.elapsed() >= timeout {
+                return Err(anyhow!(
+                    "{} did not respond within {}s of starting `{}` (raise --wait-timeout?)",
+                    url,
+                    timeout.as_secs(),
+                    self.command
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    }
+
+    fn stop(&mut self) {
+        if matches!(self.child.try_wait(), Ok(Some(_))) {
+            return;
+        }
+        let pid = self.child.id().to_string();
+
+        #[cfg(unix)]
+        {
+            let group = format!("-{}", pid);
+            let _ = Command::new("kill").args(["-TERM", "--", &group]).status();
+            let deadline = Instant::now() + Duration::from_secs(5);
+            while Instant::now() < deadline {
+                if matches!(self.child.try_wait(), Ok(Some(_))) {
+                    return;
+                }
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            let _ = Command::new("kill").args(["-KILL", "--", &group]).status();
+        }
+        #[cfg(windows)]
+        {
+            let _ = Command::new("taskkill").args(["/PID", &pid, "/T", "/F"]).status();
+        }
+
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+impl Drop for DevServer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
+
+/// Any HTTP response counts; dev servers often redirect or 404 on `/` while compiling
+async fn responds(url: &str) -> bool {
+    tokio::process::Command::new("curl")
+        .args(["-s", "-I", "--connect-timeout", "1", "--max-time", "5", url])
+        .output()
+        .await
+        .map(|output| String::from_utf8_lossy(&output.stdout).starts_with("HTTP/"))
+        .unwrap_or(false)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_server_that_exits_early_is_reported() {
+        let mut server = DevServer::start("exit 3").unwrap();
+        let error = server.wait_until_ready("http://127.0.0.1:9", Duration::from_secs(10)).await.unwrap_err();
+        assert!(error.to_string().contains("exited with"), "{}", error);
+    }
+}
@@ -0,0 +1,62 @@
+/// Self-contained HTML page for `sniff report --output report.html`
+use super::{CategoryScore, HealthReport};
+
+const STYLE: &str = "body{font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;max-width:860px;margin:2rem auto;padding:0 1rem;color:#1f2328}\
+h1{margin-bottom:.25rem}.meta{color:#656d76;margin-top:0}\
+.score{font-size:3rem;font-weight:700}.good{color:#1a7f37}.fair{color:#9a6700}.poor{color:#cf222e}.skipped{color:#656d76}\
+table{border-collapse:collapse;width:100%;margin-top:1.5rem}th,td{text-align:left;padding:.5rem;border-bottom:1px solid #d0d7de;vertical-align:top}\
+td.num{text-align:right}ul{margin:.25rem 0 0;padding-left:1.25rem;font-size:.875rem;color:#656d76}";
+
+pub fn render(report: &HealthReport) -> String {
+    let rows: String = report.categories.iter().map(row).collect();
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{project} health report</title>\n<style>{style}</style>\n</head>\n<body>\n\
+<h1>{project} health report</h1>\n<p class=\"meta\">Generated {generated} by sniff {version}</p>\n\
+<p class=\"score {class}\">{score}/100 <small>({grade:?})</small></p>\n\
+<table>\n<thead><tr><th>Category</th><th>Score</th><th>Critical</th><th>Errors</th><th>Warnings</th><th>Details</th></tr></thead>\n<tbody>\n{rows}</tbody>\n</table>\n</body>\n</html>\n",
+        project = escape(&report.project),
+        style = STYLE,
+        generated = report.generated_at.format("%Y-%m-%d %H:%M UTC"),
+        version = env!("CARGO_PKG_VERSION"),
+        class = class_for(Some(report.score)),
+        score = report.score,
+        grade = report.grade,
+        rows = rows,
+    )
+}
+
+fn row(category: &CategoryScore) -> String {
+    let score = category.score.map_or("—".to_string(), |score| score.to_string());
+    let mut details = category.message.as_deref().map(escape).unwrap_or_default();
+    if !category.top_findings.is_empty() {
+        let items: String = category.top_findings.iter().map(|finding| format!("<li>{}</li>", escape(finding))).collect();
+        details.push_str(&format!("<ul>{}</ul>", items));
+    }
+    format!(
+        "<tr><td>{}</td><td class=\"num {}\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td>{}</td></tr>\n",
+        escape(&category.name),
+        class_for(category.score),
+        score,
+        category.critical,
+        category.errors,
+        category.warnings,
+        details
+    )
+}
+
+fn class_for(score: Option<u32>) -> &'static str {
+    match score {
+        Some(80..) => "good",
+        Some(60..=79) => "fair",
+        Some(_) => "poor",
+        None => "skipped",
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,325 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use crate::config::Config;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, ExitCode, CommandOutcome, ScanTarget, create_standard_json_output, output_result};
+use crate::common::annotations::workspace_path;
+use super::{large, imports_analyzer as imports, types, memory, context, env, bundle};
+use super::history::Metrics;
+
+mod html;
+
+/// Points a category loses per finding, by level
+const CRITICAL_PENALTY: f64 = 20.0;
+const ERROR_PENALTY: f64 = 8.0;
+const WARNING_PENALTY: f64 = 2.0;
+const NOTICE_PENALTY: f64 = 0.5;
+
+/// Findings listed per category in the text and HTML reports
+const TOP_FINDINGS: usize = 5;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub project: String,
+    pub generated_at: DateTime<Utc>,
+    /// Average of the scored categories, 0-100
+    pub score: u32,
+    pub grade: Grade,
+    pub categories: Vec<CategoryScore>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryScore {
+    pub name: String,
+    /// 0-100; `None` when the analysis could not run
+    pub score: Option<u32>,
+    pub critical: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub notices: usize,
+    /// One line per finding, worst first, capped at a few per category
+    pub top_findings: Vec<String>,
+    /// Why the analysis was skipped, or what the score is based on
+    pub message: Option<String>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    fn for_score(score: u32) -> Self {
+        match score {
+            90.. => Grade::A,
+            80..=89 => Grade::B,
+            70..=79 => Grade::C,
+            60..=69 => Grade::D,
+            _ => Grade::F,
+        }
+    }
+}
+
+pub async fn run(target: &ScanTarget, output: Option<&Path>, json: bool, quiet: bool) -> Result<CommandOutcome<HealthReport>> {
+    if !quiet && !json {
+        output::line("🩺 Building project health report...".bold().blue());
+        output::blank();
+    }
+
+    let outcome = check(target).await?;
+    let report = &outcome.report;
+
+    if let Some(path) = output {
+        write_artifact(report, path)?;
+    }
+
+    let issues = report.categories.iter().map(|category| category.critical + category.errors + category.warnings).sum();
+    let response = create_standard_json_output("report", report, report.categories.len(), issues, Some(report.duration_ms));
+    output_result(&response, json, quiet, |report, quiet| print_report(report, output, quiet))?;
+
+    Ok(outcome)
+}
+
+/// Run the analyses from `[report]` and score each as a category
+pub async fn check(target: &ScanTarget) -> Result<CommandOutcome<HealthReport>> {
+    let start_time = Instant::now();
+    // Every check reads the same tree, so each file is walked and read once for all of them
+    let target = &target.clone().with_shared_context();
+    let project_dir = target.project_dir("report")?;
+    let config = target.config()?;
+
+    let mut categories = Vec::new();
+    for name in &config.report.analyses {
+        let analysis_start = Instant::now();
+        let result = run_analysis(name, target, &config).await;
+        let duration_ms = analysis_start.elapsed().as_millis() as u64;
+        categories.push(match result {
+            Ok(Analysis::Findings(findings)) => CategoryScore::from_findings(name, findings, duration_ms),
+            Ok(Analysis::Score(score, message)) => CategoryScore {
+                score: Some(score),
+                message: Some(message),
+                ..CategoryScore::empty(name, duration_ms)
+            },
+            // An analysis that cannot run (e.g. no build output for `bundle`) is left out of the score
+            Err(e) => CategoryScore {
+                message: Some(e.to_string()),
+                ..CategoryScore::empty(name, duration_ms)
+            },
+        });
+    }
+
+    let scores: Vec<u32> = categories.iter().filter_map(|category| category.score).collect();
+    let score = if scores.is_empty() {
+        0
+    } else {
+        (scores.iter().sum::<u32>() as f64 / scores.len() as f64).round() as u32
+    };
+
+    let report = HealthReport {
+        project: project_name(project_dir),
+        generated_at: Utc::now(),
+        score,
+        grade: Grade::for_score(score),
+        categories,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    };
+    // The report is informational; use --fail-on with the individual commands to gate CI
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+impl Metrics for HealthReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![("health_score", self.score as f64)]
+    }
+}
+
+enum Analysis {
+    Findings(Vec<Annotation>),
+    /// Analyses without findings score themselves, e.g. context's organization score
+    Score(u32, String),
+}
+
+async fn run_analysis(name: &str, target: &ScanTarget, config: &Config) -> Result<Analysis> {
+    fn findings<R: Annotate>(outcome: CommandOutcome<R>) -> Analysis {
+        Analysis::Findings(outcome.report.annotations())
+    }
+    Ok(match name {
+        "large" => findings(large::check(target, config.large_files.threshold, None, false, config)?),
+        "imports" => findings(imports::check(target, false, true)?),
+        "types" => findings(types::check(target, types::TypesOptions::default(), true)?),
+        "memory" => findings(memory::check(target, &memory::MemoryOptions::default()).await?),
+        "env" => findings(env::check(target, None, false).await?),
+        "bundle" => findings(bundle::check(target, &bundle::BundleOptions::default()).await?),
+        "context" => {
+            let outcome = context::check(target).await?;
+            let architecture = &outcome.report.architecture;
+            Analysis::Score(
+                architecture.organization_score.clamp(0.0, 100.0).round() as u32,
+                format!("Organization score; complexity {:?}", architecture.complexity_level),
+            )
+        }
+        _ => return Err(anyhow::anyhow!("Unknown analysis '{}'", name)),
+    })
+}
+
+impl CategoryScore {
+    fn empty(name: &str, duration_ms: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            score: None,
+            critical: 0,
+            errors: 0,
+            warnings: 0,
+            notices: 0,
+            top_findings: Vec::new(),
+            message: None,
+            duration_ms,
+        }
+    }
+
+    fn from_findings(name: &str, mut findings: Vec<Annotation>, duration_ms: u64) -> Self {
+        let count = |level: AnnotationLevel| findings.iter().filter(|finding| finding.level == level).count();
+        let (critical, errors, warnings, notices) = (
+            count(AnnotationLevel::Critical),
+            count(AnnotationLevel::Error),
+            count(AnnotationLevel::Warning),
+            count(AnnotationLevel::Notice),
+        );
+        let penalty = critical as f64 * CRITICAL_PENALTY
+            + errors as f64 * ERROR_PENALTY
+            + warnings as f64 * WARNING_PENALTY
+            + notices as f64 * NOTICE_PENALTY;
+
+        findings.sort_by_key(|finding| rank(finding.level));
+        let top_findings = findings.iter().take(TOP_FINDINGS).map(describe).collect();
+
+        Self {
+            score: Some((100.0 - penalty).max(0.0).round() as u32),
+            critical,
+            errors,
+            warnings,
+            notices,
+            top_findings,
+            ..Self::empty(name, duration_ms)
+        }
+    }
+}
+
+fn rank(level: AnnotationLevel) -> u8 {
+    match level {
+        AnnotationLevel::Critical => 0,
+        AnnotationLevel::Error => 1,
+        AnnotationLevel::Warning => 2,
+        AnnotationLevel::Notice => 3,
+    }
+}
+
+fn describe(finding: &Annotation) -> String {
+    match (&finding.file, finding.line) {
+        (Some(file), Some(line)) => format!("{}:{} {}: {}", workspace_path(file), line, finding.title, finding.message),
+        (Some(file), None) => format!("{} {}: {}", workspace_path(file), finding.title, finding.message),
+        _ => format!("{}: {}", finding.title, finding.message),
+    }
+}
+
+/// `name` from package.json, else the directory name
+pub(crate) fn project_name(project_dir: &Path) -> String {
+    fs::read_to_string(project_dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|package| package["name"].as_str().map(str::to_string))
+        .or_else(|| {
+            let dir = project_dir.canonicalize().unwrap_or_else(|_| PathBuf::from(project_dir));
+            dir.file_name().map(|name| name.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "project".to_string())
+}
+
+/// `.html`/`.htm` paths get the HTML page, anything else the JSON report
+fn write_artifact(report: &HealthReport, path: &Path) -> Result<()> {
+    let is_html = path.extension().and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+    let content = if is_html {
+        html::render(report)
+    } else {
+        serde_json::to_string_pretty(report)?
+    };
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn colored_score(score: u32) -> ColoredString {
+    let text = score.to_string();
+    match score {
+        80.. => text.green(),
+        60..=79 => text.yellow(),
+        _ => text.red(),
+    }
+}
+
+fn print_report(report: &HealthReport, output: Option<&Path>, quiet: bool) {
+    output::line(format!("🩺 {} health: {}/100 ({:?})", report.project, report.score, report.grade).bold());
+
+    if !quiet {
+        output::blank();
+        for category in &report.categories {
+            match category.score {
+                Some(score) => {
+                    let detail = category.message.clone().unwrap_or_else(|| {
+                        format!("{} critical, {} errors, {} warnings", category.critical, category.errors, category.warnings)
+                    });
+                    output::line(format!("  {:<10} {:>3}  {}", category.name.bold(), colored_score(score), detail.dimmed()));
+                    for finding in &category.top_findings {
+                        output::line(format!("             • {}", finding));
+                    }
+                }
+                None => output::line(format!(
+                    "  {:<10} {:>3}  {}",
+                    category.name.bold(),
+                    "—".dimmed(),
+                    format!("skipped: {}", category.message.as_deref().unwrap_or("could not run")).dimmed()
+                )),
+            }
+        }
+        output::blank();
+        output::line(format!("  Duration: {}ms", report.duration_ms));
+    }
+
+    if let Some(path) = output {
+        output::line(format!("📄 Report written to {}", path.display()).green());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_score_weighs_findings_by_level() {
+        let findings = vec![
+            Annotation::new(AnnotationLevel::Warning, "src/a.ts", 1, "Unused import", "useEffect"),
+            Annotation::new(AnnotationLevel::Critical, "src/b.ts", 2, "Large file", "900 lines"),
+            Annotation::new(AnnotationLevel::Error, "src/c.ts", 3, "Broken import", "./missing"),
+        ];
+        let category = CategoryScore::from_findings("large", findings, 0);
+
+        assert_eq!(category.score, Some(70));
+        assert_eq!((category.critical, category.errors, category.warnings), (1, 1, 1));
+        assert!(category.top_findings[0].contains("Large file"));
+        assert_eq!(CategoryScore::from_findings("imports", Vec::new(), 0).score, Some(100));
+        assert_eq!(Grade::for_score(85), Grade::B);
+        assert_eq!(Grade::for_score(12), Grade::F);
+    }
+}
@@ -0,0 +1,430 @@
+/// Route discovery for the Next.js `pages/` and `app/` routers.
+/// Turns router files into URL routes, works out how each page renders, which HTTP
+/// methods each handler serves and whether `middleware.ts` covers it.
+use regex::Regex;
+use std::fs;
+use std::path::{Component, Path};
+use std::sync::OnceLock;
+use crate::common::OptimizedFileWalker;
+use crate::utils::FileUtils;
+use super::{ApiRouteInfo, MiddlewareInfo, PageInfo, Rendering, Router, Side};
+
+/// App router roots relative to the project directory
+pub(crate) const APP_DIRS: [&str; 2] = ["app", "src/app"];
+
+/// Extensions of route, page and component source files
+pub(crate) const SOURCE_EXTENSIONS: [&str; 4] = ["tsx", "jsx", "ts", "js"];
+
+/// Router roots relative to the project directory
+const ROUTER_DIRS: [(&str, Router); 4] = [
+    ("pages", Router::Pages),
+    ("src/pages", Router::Pages),
+    ("app", Router::App),
+    ("src/app", Router::App),
+];
+
+const HTTP_METHODS: [&str; 7] = ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"];
+
+struct RoutePatterns {
+    app_dynamic: Regex,
+    app_revalidate: Regex,
+    fetch_revalidate: Regex,
+    static_revalidate: Regex,
+    handler_export: Regex,
+    export_list: Regex,
+    pages_method: Regex,
+    wrapper: Regex,
+    api_call: Regex,
+    database_operation: Regex,
+    auth: Regex,
+    use_client: Regex,
+    matcher: Regex,
+    quoted: Regex,
+}
+
+static ROUTE_PATTERNS: OnceLock<RoutePatterns> = OnceLock::new();
+
+fn patterns() -> &'static RoutePatterns {
+    ROUTE_PATTERNS.get_or_init(|| RoutePatterns {
+        app_dynamic: Regex::new(r#"export\s+const\s+dynamic\s*=\s*['"]([\w-]+)['"]"#).unwrap(),
+        app_revalidate: Regex::new(r"export\s+const\s+revalidate\s*=\s*(\d+|false)").unwrap(),
+        fetch_revalidate: Regex::new(r"next\s*:\s*\{[^}]*revalidate\s*:\s*[1-9]").unwrap(),
+        static_revalidate: Regex::new(r"revalidate\s*:\s*[1-9]").unwrap(),
+        handler_export: Regex::new(r"export\s+(?:async\s+)?(?:function\s+|const\s+|let\s+)(GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS)\b").unwrap(),
+        export_list: Regex::new(r"export\s*\{([^}]*)\}").unwrap(),
+        pages_method: Regex::new(r#"(?:method\s*[!=]==?\s*|case\s+)['"](GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS)['"]"#).unwrap(),
+        wrapper: Regex::new(r"\b(with[A-Z]\w*)\s*\(").unwrap(),
+        api_call: Regex::new(r#"(?:fetch|axios(?:\.\w+)?|useSWR|useQuery|\$fetch)\(\s*['"`](/api/[^'"`?$]*)"#).unwrap(),
+        database_operation: Regex::new(r"\b(?:prisma|db)\.(\w+)\.(findMany|findUnique|findFirst|create|createMany|update|updateMany|upsert|delete|deleteMany|count|aggregate)\b").unwrap(),
+        auth: Regex::new(r"\b(with(?:Page|Api)?Auth\w*|getServerSession|getServerAuthSession|getSession|getToken|getAuth|currentUser|auth|clerkMiddleware|authMiddleware|requireAuth|requireUser)\s*\(").unwrap(),
+        use_client: Regex::new(r#"^\s*(?://[^\n]*\n\s*|/\*[\s\S]*?\*/\s*)*['"]use client['"]"#).unwrap(),
+        matcher: Regex::new(r#"matcher\s*:\s*(\[[^\]]*\]|'[^']*'|"[^"]*")"#).unwrap(),
+        quoted: Regex::new(r#"['"]([^'"]+)['"]"#).unwrap(),
+    })
+}
+
+pub struct Routes {
+    pub pages: Vec<PageInfo>,
+    pub api_routes: Vec<ApiRouteInfo>,
+    pub middleware: Option<MiddlewareInfo>,
+}
+
+#[derive(Debug, PartialEq)]
+enum RouteFile {
+    Page(String),
+    Api(String),
+}
+
+/// Every page and API route under the project's router directories, in file order
+pub fn discover(project_dir: &Path) -> Routes {
+    let middleware = find_middleware(project_dir);
+    let mut pages = Vec::new();
+    let mut api_routes = Vec::new();
+
+    for (dir, router) in ROUTER_DIRS {
+        let root = project_dir.join(dir);
+        if !root.is_dir() {
+            continue;
+        }
+        let mut files = OptimizedFileWalker::new().walk_with_extensions(&root, &SOURCE_EXTENSIONS);
+        files.sort();
+
+        for file_path in files {
+            let Some(route_file) = file_path.strip_prefix(&root).ok().and_then(|relative| classify(router, relative)) else {
+                continue;
+            };
+            let Ok(content) = fs::read_to_string(&file_path) else {
+                continue;
+            };
+            let relative = file_path.strip_prefix(project_dir).unwrap_or(&file_path);
+            let path = FileUtils::slash_path(relative);
+            let route_groups = route_groups(relative);
+
+            match route_file {
+                RouteFile::Page(route) => {
+                    let rendering = rendering(router, &content);
+                    let side = if router == Router::App && !patterns().use_client.is_match(&content) {
+                        Side::Server
+                    } else {
+                        Side::Client
+                    };
+                    pages.push(PageInfo {
+                        name: page_name(&route),
+                        path,
+                        router,
+                        rendering,
+                        side,
+                        has_ssr: rendering == Rendering::Ssr,
+                        has_ssg: matches!(rendering, Rendering::Ssg | Rendering::Isr),
+                        dynamic_segments: dynamic_segments(&route),
+                        route_groups,
+                        api_calls: api_calls(&content),
+                        middleware: middleware_for(&route, &content, middleware.as_ref()),
+                        auth: auth_for(&route, &content, middleware.as_ref()),
+                        route,
+                    });
+                }
+                RouteFile::Api(route) => api_routes.push(ApiRouteInfo {
+                    path,
+                    router,
+                    methods: methods(router, &content),
+                    dynamic_segments: dynamic_segments(&route),
+                    route_groups,
+                    middleware: middleware_for(&route, &content, middleware.as_ref()),
+                    auth: auth_for(&route, &content, middleware.as_ref()),
+                    database_operations: database_operations(&content),
+                    route,
+                }),
+            }
+        }
+    }
+
+    Routes { pages, api_routes, middleware }
+}
+
+/// The route a file under a router root serves, if any
+fn classify(router: Router, relative: &Path) -> Option<RouteFile> {
+    let mut segments: Vec<String> = relative.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect();
+    let file_name = segments.pop()?;
+
+    match router {
+        Router::Pages => {
+            // Only the extension goes: `[...slug].tsx` is the catch-all `[...slug]`
+            let stem = file_name.rsplit_once('.').map_or(file_name.as_str(), |(stem, _)| stem);
+            // `_app`, `_document`, `_error` and `_middleware` are not routes
+            if segments.is_empty() && stem.starts_with('_') {
+                return None;
+            }
+            if stem != "index" {
+                segments.push(stem.to_string());
+            }
+            let route = route_from(&segments);
+            if segments.first().is_some_and(|segment| segment == "api") {
+                Some(RouteFile::Api(route))
+            } else {
+                Some(RouteFile::Page(route))
+            }
+        }
+        Router::App => {
+            // Private folders (`_components`) are never routable
+            if segments.iter().any(|segment| segment.starts_with('_')) {
+                return None;
+            }
+            let stem = file_name.split('.').next().unwrap_or(&file_name);
+            let route = route_from(&url_segments(&segments));
+            match stem {
+                "page" => Some(RouteFile::Page(route)),
+                "route" => Some(RouteFile::Api(route)),
+                _ => None,
+            }
+        }
+    }
+}
+
+/// Drop route groups `(marketing)`, intercepting prefixes `(..)photo` and parallel slots `@modal`
+fn url_segments(segments: &[String]) -> Vec<String> {
+    segments.iter()
+        .filter(|segment| !segment.starts_with('(') && !segment.starts_with('@'))
+        .cloned()
+        .collect()
+}
+
+fn route_from(segments: &[String]) -> String {
+    format!("/{}", segments.join("/"))
+}
+
+fn dynamic_segments(route: &str) -> Vec<String> {
+    route.split('/')
+        .filter(|segment| segment.starts_with('['))
+        .map(str::to_string)
+        .collect()
+}
+
+/// `(marketing)` folders on the way to the file; intercepting routes like `(..)photo` are not groups
+fn route_groups(relative: &Path) -> Vec<String> {
+    relative.parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .filter_map(|component| {
+            let name = component.as_os_str().to_str()?;
+            let group = name.strip_prefix('(')?.strip_suffix(')')?;
+            (!group.starts_with('.')).then(|| group.to_string())
+        })
+        .collect()
+}
+
+fn page_name(route: &str) -> String {
+    match route.rsplit('/').next() {
+        Some("") | None => "index".to_string(),
+        Some(name) => name.to_string(),
+    }
+}
+
+fn rendering(router: Router, content: &str) -> Rendering {
+    let patterns = patterns();
+    match router {
+        Router::Pages => {
+            if content.contains("getServerSideProps") {
+                Rendering::Ssr
+            } else if content.contains("getStaticProps") {
+                if patterns.static_revalidate.is_match(content) { Rendering::Isr } else { Rendering::Ssg }
+            } else {
+                Rendering::Static
+            }
+        }
+        Router::App => {
+            let dynamic = patterns.app_dynamic.captures(content).map(|caps| caps[1].to_string());
+            let revalidate = patterns.app_revalidate.captures(content).map(|caps| caps[1].to_string());
+            let revalidates = revalidate.as_deref().is_some_and(|value| value != "false" && value != "0")
+                || patterns.fetch_revalidate.is_match(content);
+
+            match dynamic.as_deref() {
+                Some("force-dynamic") => return Rendering::Ssr,
+                Some("force-static" | "error") => return if revalidates { Rendering::Isr } else { Rendering::Ssg },
+                _ => {}
+            }
+            if revalidate.as_deref() == Some("0") {
+                return Rendering::Ssr;
+            }
+            // Request-time APIs opt the whole route into dynamic rendering
+            let request_time = ["cookies()", "headers()", "no-store", "searchParams", "unstable_noStore"]
+                .iter()
+                .any(|api| content.contains(api));
+            if request_time {
+                Rendering::Ssr
+            } else if revalidates {
+                Rendering::Isr
+            } else if content.contains("generateStaticParams") {
+                Rendering::Ssg
+            } else {
+                Rendering::Static
+            }
+        }
+    }
+}
+
+fn methods(router: Router, content: &str) -> Vec<String> {
+    let patterns = patterns();
+    let mut found: Vec<&str> = match router {
+        Router::App => {
+            let mut found: Vec<&str> = patterns.handler_export.captures_iter(content)
+                .filter_map(|caps| caps.get(1).map(|method| method.as_str()))
+                .collect();
+            // `export { handler as GET, handler as POST }`
+            for list in patterns.export_list.captures_iter(content) {
+                for item in list[1].split(',') {
+                    let name = item.split_whitespace().last().unwrap_or_default();
+                    if let Some(method) = HTTP_METHODS.iter().find(|method| **method == name) {
+                        found.push(method);
+                    }
+                }
+            }
+            found
+        }
+        Router::Pages => patterns.pages_method.captures_iter(content)
+            .filter_map(|caps| caps.get(1).map(|method| method.as_str()))
+            .collect(),
+    };
+    found.sort_by_key(|method| HTTP_METHODS.iter().position(|known| known == method));
+    found.dedup();
+
+    if found.is_empty() && router == Router::Pages {
+        return vec!["ANY".to_string()];
+    }
+    found.into_iter().map(str::to_string).collect()
+}
+
+fn api_calls(content: &str) -> Vec<String> {
+    let mut calls: Vec<String> = patterns().api_call.captures_iter(content)
+        .map(|caps| caps[1].trim_end_matches('/').to_string())
+        .collect();
+    calls.sort();
+    calls.dedup();
+    calls
+}
+
+fn database_operations(content: &str) -> Vec<String> {
+    let mut operations: Vec<String> = patterns().database_operation.captures_iter(content)
+        .map(|caps| format!("{}.{}", &caps[1], &caps[2]))
+        .collect();
+    operations.sort();
+    operations.dedup();
+    operations
+}
+
+fn middleware_for(route: &str, content: &str, middleware: Option<&MiddlewareInfo>) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(middleware) = middleware.filter(|middleware| applies_to(middleware, route)) {
+        let file_name = middleware.path.rsplit('/').next().unwrap_or(&middleware.path);
+        names.push(file_name.to_string());
+    }
+    let mut wrappers: Vec<String> = patterns().wrapper.captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .collect();
+    wrappers.sort();
+    wrappers.dedup();
+    names.extend(wrappers);
+    names
+}
+
+fn auth_for(route: &str, content: &str, middleware: Option<&MiddlewareInfo>) -> Vec<String> {
+    let mut guards: Vec<String> = patterns().auth.captures_iter(content)
+        .map(|caps| caps[1].to_string())
+        .collect();
+    guards.sort();
+    guards.dedup();
+    if let Some(middleware) = middleware.filter(|middleware| middleware.auth && applies_to(middleware, route)) {
+        let file_name = middleware.path.rsplit('/').next().unwrap_or(&middleware.path);
+        guards.insert(0, file_name.to_string());
+    }
+    guards
+}
+
+/// `middleware.ts` at the project root or in `src/`, with its `config.matcher` paths
+fn find_middleware(project_dir: &Path) -> Option<MiddlewareInfo> {
+    ["middleware.ts", "middleware.js", "src/middleware.ts", "src/middleware.js"]
+        .iter()
+        .find_map(|path| {
+            let content = fs::read_to_string(project_dir.join(path)).ok()?;
+            let matchers = patterns().matcher.captures(&content)
+                .map(|caps| patterns().quoted.captures_iter(&caps[1]).map(|quoted| quoted[1].to_string()).collect())
+                .unwrap_or_default();
+            let auth = patterns().auth.is_match(&content) || content.contains("next-auth/middleware");
+            Some(MiddlewareInfo { path: path.to_string(), matchers, auth })
+        })
+}
+
+/// Matchers are compared by their literal prefix, so `/dashboard/:path*` covers
+/// `/dashboard` and everything below it; regex-only matchers cover every route
+fn applies_to(middleware: &MiddlewareInfo, route: &str) -> bool {
+    if middleware.matchers.is_empty() {
+        return true;
+    }
+    middleware.matchers.iter().any(|matcher| {
+        let end = matcher.find([':', '(', '*', '?']).unwrap_or(matcher.len());
+        let prefix = matcher[..end].trim_end_matches('/');
+        prefix.is_empty() || route == prefix || route.starts_with(&format!("{}/", prefix))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(router: Router, path: &str) -> Option<RouteFile> {
+        classify(router, Path::new(path))
+    }
+
+    #[test]
+    fn test_classify_derives_routes_for_both_next_routers() {
+        assert_eq!(page(Router::Pages, "index.tsx"), Some(RouteFile::Page("/".to_string())));
+        assert_eq!(page(Router::Pages, "blog/[slug].tsx"), Some(RouteFile::Page("/blog/[slug]".to_string())));
+        assert_eq!(page(Router::Pages, "docs/[...slug].tsx"), Some(RouteFile::Page("/docs/[...slug]".to_string())));
+        assert_eq!(page(Router::Pages, "api/users/index.ts"), Some(RouteFile::Api("/api/users".to_string())));
+        assert_eq!(page(Router::Pages, "_app.tsx"), None);
+
+        assert_eq!(page(Router::App, "(marketing)/about/page.tsx"), Some(RouteFile::Page("/about".to_string())));
+        assert_eq!(page(Router::App, "page.tsx"), Some(RouteFile::Page("/".to_string())));
+        assert_eq!(page(Router::App, "api/posts/[id]/route.ts"), Some(RouteFile::Api("/api/posts/[id]".to_string())));
+        assert_eq!(page(Router::App, "dashboard/layout.tsx"), None);
+        assert_eq!(page(Router::App, "_components/page.tsx"), None);
+    }
+
+    #[test]
+    fn test_rendering_and_methods_detection() {
+        assert_eq!(rendering(Router::Pages, "export async function getServerSideProps() {}"), Rendering::Ssr);
+        assert_eq!(rendering(Router::Pages, "export async function getStaticProps() { return { props: {}, revalidate: 60 } }"), Rendering::Isr);
+        assert_eq!(rendering(Router::App, "export const dynamic = 'force-dynamic';"), Rendering::Ssr);
+        assert_eq!(rendering(Router::App, "export const revalidate = 3600;"), Rendering::Isr);
+        assert_eq!(rendering(Router::App, "export async function generateStaticParams() { return [] }"), Rendering::Ssg);
+        assert_eq!(rendering(Router::App, "const store = cookies();"), Rendering::Ssr);
+        assert_eq!(rendering(Router::App, "export default function Page() {}"), Rendering::Static);
+
+        let handler = "export async function POST(req) {}\nexport const GET = async () => {};\nexport { handler as DELETE };";
+        assert_eq!(methods(Router::App, handler), vec!["GET", "POST", "DELETE"]);
+        assert_eq!(methods(Router::Pages, "if (req.method === 'POST') {} switch (req.method) { case 'PUT': }"), vec!["POST", "PUT"]);
+        assert_eq!(methods(Router::Pages, "export default function handler(req, res) {}"), vec!["ANY"]);
+    }
+
+    #[test]
+    fn test_middleware_matchers() {
+        let middleware = MiddlewareInfo { path: "middleware.ts".to_string(), matchers: vec!["/dashboard/:path*".to_string()], auth: true };
+        assert!(applies_to(&middleware, "/dashboard"));
+        assert!(applies_to(&middleware, "/dashboard/settings"));
+        assert!(!applies_to(&middleware, "/dashboards"));
+        assert!(!applies_to(&middleware, "/"));
+
+        let everything = MiddlewareInfo { path: "middleware.ts".to_string(), matchers: vec!["/((?!_next/static).*)".to_string()], auth: false };
+        assert!(applies_to(&everything, "/blog"));
+        assert_eq!(middleware_for("/dashboard", "export default withAuth(Page)", Some(&middleware)), vec!["middleware.ts", "withAuth"]);
+        assert_eq!(auth_for("/dashboard", "const session = await getServerSession(options);", Some(&middleware)), vec!["middleware.ts", "getServerSession"]);
+        assert!(auth_for("/blog", "export default function Blog() {}", Some(&everything)).is_empty());
+        assert_eq!(dynamic_segments("/shop/[category]/[[...filters]]"), vec!["[category]", "[[...filters]]"]);
+        assert_eq!(route_groups(Path::new("app/(shop)/(.)cart/page.tsx")), vec!["shop"]);
+    }
+}
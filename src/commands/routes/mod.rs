@@ -0,0 +1,194 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+use crate::common::{output, ExitCode, CommandOutcome, ScanTarget, create_standard_json_output, output_result};
+
+pub(crate) mod discovery;
+
+pub use discovery::{discover, Routes};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RoutesReport {
+    pub pages: Vec<PageInfo>,
+    pub api_routes: Vec<ApiRouteInfo>,
+    pub middleware: Option<MiddlewareInfo>,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageInfo {
+    pub name: String,
+    pub path: String,
+    pub route: String,
+    pub router: Router,
+    pub rendering: Rendering,
+    pub side: Side,
+    pub has_ssr: bool,
+    pub has_ssg: bool,
+    /// `[id]`, `[...slug]` and `[[...slug]]` segments of the route
+    pub dynamic_segments: Vec<String>,
+    /// `(marketing)` folders the file sits in, without the parentheses
+    pub route_groups: Vec<String>,
+    /// `/api/...` endpoints the page fetches
+    pub api_calls: Vec<String>,
+    /// `middleware.ts` when its matcher covers the route, plus `withX(...)` wrappers
+    pub middleware: Vec<String>,
+    /// Auth guards: wrappers and session checks in the file, or an auth middleware covering the route
+    pub auth: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiRouteInfo {
+    pub path: String,
+    pub route: String,
+    pub router: Router,
+    /// HTTP methods handled; `ANY` for a pages-router handler that never checks `req.method`
+    pub methods: Vec<String>,
+    pub dynamic_segments: Vec<String>,
+    pub route_groups: Vec<String>,
+    pub middleware: Vec<String>,
+    pub auth: Vec<String>,
+    pub database_operations: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MiddlewareInfo {
+    pub path: String,
+    /// `config.matcher` entries; empty when the middleware runs on every route
+    pub matchers: Vec<String>,
+    /// Whether the middleware checks authentication
+    pub auth: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Router {
+    /// Next.js `pages/`
+    Pages,
+    /// Next.js `app/`
+    App,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Rendering {
+    /// No data fetching at request or build time
+    Static,
+    Ssg,
+    /// Static with revalidation
+    Isr,
+    Ssr,
+}
+
+/// Where the page component runs: app-router Server Components stay on the server,
+/// `'use client'` pages and pages-router pages hydrate in the browser
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Server,
+    Client,
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<RoutesReport>> {
+    if !quiet && !json {
+        output::line("🗺️  Discovering routes...".bold().blue());
+        output::blank();
+    }
+
+    let outcome = check(target)?;
+    let report = &outcome.report;
+    let total = report.pages.len() + report.api_routes.len();
+    let response = create_standard_json_output("routes", report, total, 0, Some(report.duration_ms));
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    Ok(outcome)
+}
+
+/// List the project's routes without printing them
+pub fn check(target: &ScanTarget) -> Result<CommandOutcome<RoutesReport>> {
+    let start_time = Instant::now();
+    let project_dir = target.project_dir("routes")?;
+    let Routes { mut pages, mut api_routes, middleware } = discover(project_dir);
+    pages.sort_by(|a, b| a.route.cmp(&b.route).then_with(|| a.path.cmp(&b.path)));
+    api_routes.sort_by(|a, b| a.route.cmp(&b.route).then_with(|| a.path.cmp(&b.path)));
+
+    let report = RoutesReport {
+        pages,
+        api_routes,
+        middleware,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    };
+    Ok(CommandOutcome::new(report, ExitCode::Success))
+}
+
+fn guards(middleware: &[String], auth: &[String]) -> String {
+    let mut guards: Vec<&str> = auth.iter().map(String::as_str).collect();
+    for name in middleware {
+        if !guards.contains(&name.as_str()) {
+            guards.push(name);
+        }
+    }
+    if guards.is_empty() { "—".to_string() } else { guards.join(", ") }
+}
+
+fn print_report(report: &RoutesReport, quiet: bool) {
+    if report.pages.is_empty() && report.api_routes.is_empty() {
+        output::line("No pages or API routes found (looked in pages/, app/, src/pages/ and src/app/)".yellow());
+        return;
+    }
+    let width = report.pages.iter().map(|page| page.route.len())
+        .chain(report.api_routes.iter().map(|route| route.route.len()))
+        .max()
+        .unwrap_or(0)
+        .clamp(12, 48);
+
+    if !report.pages.is_empty() {
+        output::line(format!("📄 PAGES ({})", report.pages.len()).bold().cyan());
+        output::line(format!("  {}", format!("{:<width$}  {:<9}  {:<7}  {:<9}  {}", "ROUTE", "RENDERING", "SIDE", "ROUTER", "AUTH / MIDDLEWARE").dimmed()));
+        for page in &report.pages {
+            let rendering = format!("{:?}", page.rendering).to_uppercase();
+            output::line(format!(
+                "  {:<width$}  {:<9}  {:<7}  {:<9}  {}",
+                page.route,
+                rendering,
+                format!("{:?}", page.side),
+                format!("{:?}", page.router),
+                guards(&page.middleware, &page.auth)
+            ));
+            if !quiet {
+                output::line(format!("  {}", format!("  {}", page.path).dimmed()));
+            }
+        }
+        output::blank();
+    }
+
+    if !report.api_routes.is_empty() {
+        output::line(format!("🔌 API ROUTES ({})", report.api_routes.len()).bold().cyan());
+        output::line(format!("  {}", format!("{:<width$}  {:<18}  {:<9}  {}", "ROUTE", "METHODS", "ROUTER", "AUTH / MIDDLEWARE").dimmed()));
+        for route in &report.api_routes {
+            output::line(format!(
+                "  {:<width$}  {:<18}  {:<9}  {}",
+                route.route,
+                route.methods.join(","),
+                format!("{:?}", route.router),
+                guards(&route.middleware, &route.auth)
+            ));
+            if !quiet {
+                output::line(format!("  {}", format!("  {}", route.path).dimmed()));
+            }
+        }
+        output::blank();
+    }
+
+    if let Some(middleware) = &report.middleware {
+        let scope = if middleware.matchers.is_empty() { "all routes".to_string() } else { middleware.matchers.join(", ") };
+        let auth = if middleware.auth { " (checks auth)" } else { "" };
+        output::line(format!("🛡️  {}{} → {}", middleware.path.bold(), auth, scope));
+    }
+    let dynamic = report.pages.iter().filter(|page| !page.dynamic_segments.is_empty()).count()
+        + report.api_routes.iter().filter(|route| !route.dynamic_segments.is_empty()).count();
+    let protected = report.pages.iter().filter(|page| !page.auth.is_empty()).count()
+        + report.api_routes.iter().filter(|route| !route.auth.is_empty()).count();
+    output::line(format!("  {} routes · {} dynamic · {} with auth", report.pages.len() + report.api_routes.len(), dynamic, protected));
+    if !quiet {
+        output::line(format!("  Duration: {}ms", report.duration_ms));
+    }
+}
@@ -0,0 +1,378 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use crate::utils::FileUtils;
+use crate::config::{Config, CustomRule};
+use crate::plugins::{WasmPlugin, load_plugins};
+use crate::common::rule_catalog::{self, RuleInfo};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, paths, PatternRule, ScanTarget, Severity, ExitCode, CommandOutcome, check_failure_threshold, matches_any_glob, read_source, scan_lines, create_annotated_json_output, create_standard_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RulesReport {
+    /// Every finding, by file and line
+    pub findings: Vec<RuleFinding>,
+    pub summary: RulesSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RuleFinding {
+    /// Name of the `[[rules]]` entry
+    pub rule: String,
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub message: String,
+    pub suggestion: Option<String>,
+    /// The source line, trimmed
+    pub text: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RulesSummary {
+    pub rules: usize,
+    /// `[[plugins]]` modules that ran
+    pub plugins: usize,
+    pub files_scanned: usize,
+    pub total_findings: usize,
+    /// Findings per rule name
+    pub by_rule: BTreeMap<String, usize>,
+}
+
+/// A custom rule ready to run, with the files it applies to
+struct CompiledRule {
+    check: PatternRule<String>,
+    files: Vec<String>,
+    exclude: Vec<String>,
+}
+
+const EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs", "vue", "svelte"];
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<RulesReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running custom rule analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "rules",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_findings,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = report.summary.total_findings == 0, "custom rule analysis completed");
+
+    Ok(outcome)
+}
+
+/// Run the `[[rules]]` and `[[plugins]]` from the config. High and critical findings fail the check
+pub fn check(target: &ScanTarget, config: &Config, quiet: bool) -> Result<CommandOutcome<RulesReport>> {
+    let rules = compile(&config.rules)?;
+    let plugins = load_plugins(&config.plugins, &target.root)?;
+    let files = if rules.is_empty() && plugins.is_empty() {
+        Vec::new()
+    } else {
+        FileScanner::for_command(target, "rules")?.find_target_files(target, EXTENSIONS)
+    };
+
+    let results: Vec<Vec<RuleFinding>> = FileUtils::process_files_parallel(
+        &files,
+        |path| scan_file(path, &target.root, &rules, &plugins),
+        "Checking custom rules",
+        quiet
+    )?;
+    let mut findings: Vec<RuleFinding> = results.into_iter().flatten().collect();
+    target.ignores()?.retain(&mut findings, |finding| (&finding.rule, &finding.file));
+    findings.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)).then(a.column.cmp(&b.column)));
+
+    let mut by_rule = BTreeMap::new();
+    for finding in &findings {
+        *by_rule.entry(finding.rule.clone()).or_insert(0) += 1;
+    }
+    let failed = findings.iter().any(|finding| matches!(finding.severity, Severity::High | Severity::Critical));
+    let summary = RulesSummary {
+        rules: rules.len(),
+        plugins: plugins.len(),
+        files_scanned: files.len(),
+        total_findings: findings.len(),
+        by_rule,
+    };
+
+    let exit_code = check_failure_threshold(failed, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(RulesReport { findings, summary }, exit_code))
+}
+
+/// `sniff rules list`: every rule a finding can carry
+#[derive(Debug, Serialize)]
+pub struct RuleList {
+    pub rules: Vec<ListedRule>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListedRule {
+    pub id: String,
+    /// The command that reports it; `rules` for the project's `[[rules]]`
+    pub command: String,
+    pub default_level: AnnotationLevel,
+    pub description: String,
+}
+
+impl From<&RuleInfo> for ListedRule {
+    fn from(rule: &RuleInfo) -> Self {
+        Self { id: rule.id.to_string(), command: rule.command.to_string(), default_level: rule.default_level, description: rule.description.to_string() }
+    }
+}
+
+/// List the built-in rules and the project's `[[rules]]`, optionally only those of one command
+pub async fn list(target: &ScanTarget, command: Option<&str>, json: bool, quiet: bool) -> Result<CommandOutcome<RuleList>> {
+    let config = target.config()?;
+    let custom = config.rules.iter().map(|rule| ListedRule {
+        id: rule.name.clone(),
+        command: "rules".to_string(),
+        default_level: AnnotationLevel::for_severity(&rule_severity(&rule.severity).unwrap_or(Severity::Medium)),
+        description: if rule.message.is_empty() { "Custom rule from sniff.toml".to_string() } else { rule.message.clone() },
+    });
+    let mut rules: Vec<ListedRule> = rule_catalog::RULES.iter().map(ListedRule::from).chain(custom).collect();
+    if let Some(command) = command {
+        rules.retain(|rule| rule.command == command);
+        if rules.is_empty() {
+            let mut commands: Vec<&str> = rule_catalog::RULES.iter().map(|rule| rule.command).collect();
+            commands.dedup();
+            return Err(anyhow::anyhow!("No rules for '{}'; rules belong to {} and rules", command, commands.join(", ")));
+        }
+    }
+
+    let list = RuleList { rules };
+    let response = create_standard_json_output("rules list", &list, list.rules.len(), 0, None);
+    output_result(&response, json, quiet, |list, quiet| print_rule_list(list, quiet))?;
+    Ok(CommandOutcome::new(list, ExitCode::Success))
+}
+
+fn print_rule_list(list: &RuleList, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("📏 Rules".bold().blue());
+        output::line("========".blue());
+    }
+    let width = list.rules.iter().map(|rule| rule.id.len()).max().unwrap_or(0);
+    let mut current_command = None;
+    for rule in &list.rules {
+        if current_command != Some(&rule.command) {
+            output::blank();
+            output::line(rule.command.cyan().bold());
+            current_command = Some(&rule.command);
+        }
+        let (label, severity) = match rule.default_level {
+            AnnotationLevel::Critical => ("critical", Severity::Critical),
+            AnnotationLevel::Error => ("error", Severity::High),
+            AnnotationLevel::Warning => ("warning", Severity::Medium),
+            AnnotationLevel::Notice => ("notice", Severity::Low),
+        };
+        output::line(format!("  {:<width$}  {}  {}", rule.id, severity.paint(&format!("{:<8}", label)), rule.description, width = width));
+    }
+    output::blank();
+}
+
+/// The regex a rule matches with: its `pattern`, or one built from its `import` or `call` query
+pub fn rule_regex(rule: &CustomRule) -> Result<Regex> {
+    let pattern = match (&rule.pattern, &rule.import, &rule.call) {
+        (Some(pattern), None, None) => pattern.clone(),
+        // `from 'x'`, `import 'x'`, `import('x')` and `require('x')`, including subpaths like `x/locale`
+        (None, Some(module), None) => format!(
+            r#"(?:\bfrom\s*|\bimport\s*\(?\s*|\brequire\s*\(\s*)['"`]{}(?:/[^'"`]*)?['"`]"#,
+            regex::escape(module)
+        ),
+        // `a.b(`, also written `a?.b(` or with spaces around the dots; not `x.a.b(`
+        (None, None, Some(callee)) => {
+            let path: Vec<String> = callee.split('.').map(|part| regex::escape(part.trim())).collect();
+            format!(r"(?:^|[^\w$.]){}\s*\(", path.join(r"\s*\??\.\s*"))
+        }
+        _ => return Err(anyhow::anyhow!("rule '{}' needs exactly one of pattern, import or call", rule.name)),
+    };
+    Regex::new(&pattern).map_err(|error| anyhow::anyhow!("rule '{}' has an invalid pattern: {}", rule.name, error))
+}
+
+/// The severity named by a rule's `severity`
+pub fn rule_severity(name: &str) -> Option<Severity> {
+    match name.to_ascii_lowercase().as_str() {
+        "critical" => Some(Severity::Critical),
+        "high" => Some(Severity::High),
+        "medium" => Some(Severity::Medium),
+        "low" => Some(Severity::Low),
+        "info" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
+fn compile(rules: &[CustomRule]) -> Result<Vec<CompiledRule>> {
+    rules.iter().map(|rule| {
+        let severity = rule_severity(&rule.severity)
+            .ok_or_else(|| anyhow::anyhow!("rule '{}' has an unknown severity '{}'", rule.name, rule.severity))?;
+        Ok(CompiledRule {
+            check: PatternRule {
+                kind: rule.name.clone(),
+                regex: rule_regex(rule)?,
+                severity,
+                description: rule.message.clone(),
+                recommendation: rule.suggestion.clone().unwrap_or_default(),
+            },
+            files: rule.files.clone(),
+            exclude: rule.exclude.clone(),
+        })
+    }).collect()
+}
+
+fn scan_file(path: &Path, root: &Path, rules: &[CompiledRule], plugins: &[WasmPlugin]) -> Result<Vec<RuleFinding>> {
+    let relative = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+    let applies = |files: &[String], exclude: &[String]| {
+        (files.is_empty() || matches_any_glob(&relative, files)) && !matches_any_glob(&relative, exclude)
+    };
+    let checks: Vec<PatternRule<String>> = rules.iter()
+        .filter(|rule| applies(&rule.files, &rule.exclude))
+        .map(|rule| rule.check.clone())
+        .collect();
+    let plugins: Vec<&WasmPlugin> = plugins.iter().filter(|plugin| applies(&plugin.files, &plugin.exclude)).collect();
+    if checks.is_empty() && plugins.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Oversized and binary files are generated, not project code
+    let Ok(Some(content)) = read_source(path) else {
+        return Ok(Vec::new());
+    };
+    let file = paths::display(path);
+    let mut findings: Vec<RuleFinding> = scan_lines(&content, &checks).into_iter()
+        .map(|found| RuleFinding {
+            rule: found.rule.kind.clone(),
+            file: file.clone(),
+            line: found.line_number,
+            column: found.column,
+            severity: found.rule.severity.clone(),
+            message: found.rule.description.clone(),
+            suggestion: Some(found.rule.recommendation.clone()).filter(|suggestion| !suggestion.is_empty()),
+            text: found.line.trim().to_string(),
+        })
+        .collect();
+
+    // Plugin rules are named after their module, e.g. `acme-rules/no-legacy-store`
+    for plugin in plugins {
+        for found in plugin.check(&relative, &content)? {
+            let severity = match &found.severity {
+                Some(name) => rule_severity(name)
+                    .ok_or_else(|| anyhow::anyhow!("Plugin {} returned an unknown severity '{}' on {}", plugin.name, name, relative))?,
+                None => Severity::Medium,
+            };
+            findings.push(RuleFinding {
+                rule: format!("{}/{}", plugin.name, found.rule),
+                file: file.clone(),
+                line: found.line,
+                column: found.column,
+                severity,
+                message: found.message,
+                suggestion: found.suggestion.filter(|suggestion| !suggestion.is_empty()),
+                text: content.lines().nth(found.line.saturating_sub(1)).unwrap_or_default().trim().to_string(),
+            });
+        }
+    }
+    Ok(findings)
+}
+
+impl Annotate for RulesReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.findings.iter()
+            .map(|finding| {
+                let message = match &finding.suggestion {
+                    Some(suggestion) => format!("{}. {}", finding.message, suggestion),
+                    None => finding.message.clone(),
+                };
+                Annotation::new(AnnotationLevel::for_severity(&finding.severity), &finding.file, finding.line, format!("Rule {}", finding.rule), message)
+                    .with_rule(finding.rule.clone())
+            })
+            .collect()
+    }
+}
+
+fn print_report(report: &RulesReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("📏 Custom Rules Report".bold().blue());
+        output::line("======================".blue());
+        output::blank();
+    }
+
+    if report.summary.rules == 0 && report.summary.plugins == 0 {
+        output::line("No custom rules configured.".yellow());
+        output::line("💡 TIP: Add [[rules]] entries to sniff.toml, e.g. name = \"no-moment\", import = \"moment\", message = \"Use date-fns\"".dimmed());
+        return;
+    }
+    if report.findings.is_empty() {
+        output::line("✅ No custom rule violations found!".green());
+        return;
+    }
+
+    let mut current_file = None;
+    for finding in &report.findings {
+        if current_file != Some(&finding.file) {
+            output::line(finding.file.cyan().bold());
+            current_file = Some(&finding.file);
+        }
+        if quiet {
+            continue;
+        }
+        output::line(format!("  {:>4}:{:<3} {}  {}", finding.line, finding.column, finding.severity.paint(&finding.rule), finding.message));
+        output::line(format!("           {}", finding.text.dimmed()));
+        if let Some(suggestion) = &finding.suggestion {
+            output::line(format!("           💡 {}", suggestion));
+        }
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Rules: {}", summary.rules));
+    if summary.plugins > 0 {
+        output::line(format!("  Plugins: {}", summary.plugins));
+    }
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    let counts: Vec<String> = summary.by_rule.iter().map(|(rule, count)| format!("{} {}", count, rule)).collect();
+    output::line(format!("  Findings: {} ({})", summary.total_findings.to_string().red(), counts.join(", ")));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(name: &str) -> CustomRule {
+        CustomRule { name: name.to_string(), severity: "high".to_string(), message: "banned".to_string(), ..CustomRule::default() }
+    }
+
+    #[test]
+    fn test_import_and_call_queries() {
+        let import = rule_regex(&CustomRule { import: Some("moment".to_string()), ..rule("no-moment") }).unwrap();
+        assert!(import.is_match("import moment from 'moment';"));
+        assert!(import.is_match("import 'moment/locale/de';"));
+        assert!(import.is_match("const m = require(\"moment\");"));
+        assert!(import.is_match("const m = await import('moment');"));
+        assert!(!import.is_match("import dayjs from 'moment-timezone';"));
+
+        let call = rule_regex(&CustomRule { call: Some("api.v1.fetch".to_string()), ..rule("no-v1") }).unwrap();
+        assert!(call.is_match("await api.v1.fetch('/users')"));
+        assert!(call.is_match("api?.v1 . fetch()"));
+        assert!(!call.is_match("client.api.v1.fetch()"));
+        assert!(!call.is_match("api.v1.fetchAll()"));
+
+        assert!(rule_regex(&rule("empty")).is_err());
+        assert!(rule_regex(&CustomRule { pattern: Some("(".to_string()), ..rule("bad") }).is_err());
+    }
+}
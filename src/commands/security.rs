@@ -0,0 +1,546 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::utils::FileUtils;
+use crate::common::jsx::{expression_end, jsx_elements, AttrValue};
+use crate::common::source::{blank_strings, string_end, strip_comments};
+use crate::common::{output, AnalysisContext, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, Severity, ExitCode, CommandOutcome, check_failure_threshold, glob_matches, create_annotated_json_output, output_result};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecurityReport {
+    pub issues: Vec<SecurityIssue>,
+    pub summary: SecuritySummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecurityIssue {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub rule: SecurityRule,
+    pub severity: Severity,
+    pub message: String,
+    pub remediation: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecuritySummary {
+    pub files_scanned: usize,
+    pub total_issues: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub by_rule: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecurityRule {
+    DangerousHtml,
+    Eval,
+    SqlInjection,
+    ChildProcess,
+    OpenRedirect,
+    TargetBlank,
+}
+
+impl SecurityRule {
+    pub const ALL: [SecurityRule; 6] = [
+        SecurityRule::SqlInjection,
+        SecurityRule::DangerousHtml,
+        SecurityRule::Eval,
+        SecurityRule::ChildProcess,
+        SecurityRule::OpenRedirect,
+        SecurityRule::TargetBlank,
+    ];
+
+    pub fn id(self) -> &'static str {
+        match self {
+            SecurityRule::DangerousHtml => "dangerous-html",
+            SecurityRule::Eval => "eval",
+            SecurityRule::SqlInjection => "sql-injection",
+            SecurityRule::ChildProcess => "child-process",
+            SecurityRule::OpenRedirect => "open-redirect",
+            SecurityRule::TargetBlank => "target-blank",
+        }
+    }
+
+    /// The stable id of findings of this rule, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            SecurityRule::DangerousHtml => "SNF-SEC-DANGEROUS-HTML",
+            SecurityRule::Eval => "SNF-SEC-EVAL",
+            SecurityRule::SqlInjection => "SNF-SEC-SQL-INJECTION",
+            SecurityRule::ChildProcess => "SNF-SEC-CHILD-PROCESS",
+            SecurityRule::OpenRedirect => "SNF-SEC-OPEN-REDIRECT",
+            SecurityRule::TargetBlank => "SNF-SEC-TARGET-BLANK",
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            SecurityRule::DangerousHtml => "Unsanitized HTML",
+            SecurityRule::Eval => "Dynamic code execution",
+            SecurityRule::SqlInjection => "SQL built from strings",
+            SecurityRule::ChildProcess => "child_process on a request path",
+            SecurityRule::OpenRedirect => "Open redirect",
+            SecurityRule::TargetBlank => "target=\"_blank\" without rel=\"noopener\"",
+        }
+    }
+
+    fn severity(self) -> Severity {
+        match self {
+            SecurityRule::SqlInjection => Severity::Critical,
+            SecurityRule::DangerousHtml | SecurityRule::Eval | SecurityRule::ChildProcess => Severity::High,
+            SecurityRule::OpenRedirect => Severity::Medium,
+            SecurityRule::TargetBlank => Severity::Low,
+        }
+    }
+
+    fn remediation(self) -> &'static str {
+        match self {
+            SecurityRule::DangerousHtml => "Sanitize the HTML first (e.g. DOMPurify.sanitize), or render it as text or React elements",
+            SecurityRule::Eval => "Replace eval/new Function/string timers with real functions or a lookup table; use JSON.parse for data",
+            SecurityRule::SqlInjection => "Pass values as query parameters ($1, ?) or use a tagged template such as sql`...` / Prisma.sql`...`",
+            SecurityRule::ChildProcess => "Move the command to a background job, or use execFile/spawn with a fixed binary and an argument array built from validated input",
+            SecurityRule::OpenRedirect => "Only redirect to relative paths (starting with a single '/') or to hosts on an allowlist",
+            SecurityRule::TargetBlank => "Add rel=\"noopener noreferrer\" so the opened page cannot reach window.opener",
+        }
+    }
+}
+
+/// Where a request reaches the server: API routes, route handlers and server actions
+const REQUEST_HANDLER_GLOBS: &[&str] = &[
+    "pages/api/**", "src/pages/api/**",
+    "app/**/route.*", "src/app/**/route.*",
+    "src/routes/**/+server.*", "src/routes/**/+page.server.*",
+];
+
+struct Patterns {
+    dangerous_html: Regex,
+    sanitized: Regex,
+    eval: Regex,
+    string_timer: Regex,
+    sql_upper: Regex,
+    sql_any_case: Regex,
+    sql_clause: Regex,
+    sql_concat: Regex,
+    child_process: Regex,
+    use_server: Regex,
+    query_source: Regex,
+    tainted_binding: Regex,
+    tainted_destructuring: Regex,
+    redirect_call: Regex,
+    location_assignment: Regex,
+}
+
+static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+
+fn patterns() -> &'static Patterns {
+    PATTERNS.get_or_init(|| Patterns {
+        dangerous_html: Regex::new(r"dangerouslySetInnerHTML\s*=\s*\{").unwrap(),
+        sanitized: Regex::new(r"(?i)saniti[sz]e|purify|\bxss\s*\(|JSON\.stringify").unwrap(),
+        eval: Regex::new(r"(?:^|[^\w$.])(eval\s*\(|new\s+Function\s*\()").unwrap(),
+        string_timer: Regex::new(r"(?:^|[^\w$])((?:window\.)?set(?:Timeout|Interval)\s*\(\s*['`])").unwrap(),
+        sql_upper: Regex::new(r"\b(?:SELECT\b[\s\S]+?\bFROM|INSERT\s+INTO|UPDATE\b[\s\S]+?\bSET|DELETE\s+FROM)\b").unwrap(),
+        sql_any_case: Regex::new(r"(?i)\b(?:select\b[\s\S]+?\bfrom|insert\s+into|update\b[\s\S]+?\bset|delete\s+from)\b").unwrap(),
+        sql_clause: Regex::new(r"(?i)\b(?:where|values|join|order\s+by|limit)\b").unwrap(),
+        sql_concat: Regex::new(concat!(
+            r#"(?i)(?:"(?:select\b[^"\n]*\bfrom|insert\s+into|update\b[^"\n]*\bset|delete\s+from)\b[^"\n]*""#,
+            r#"|'(?:select\b[^'\n]*\bfrom|insert\s+into|update\b[^'\n]*\bset|delete\s+from)\b[^'\n]*')\s*\+"#
+        )).unwrap(),
+        child_process: Regex::new(r#"(?:\bfrom\s*|\brequire\s*\(\s*|\bimport\s*\(\s*)['"](?:node:)?child_process['"]"#).unwrap(),
+        use_server: Regex::new(r#"^\s*['"]use server['"]"#).unwrap(),
+        query_source: Regex::new(r"\breq(?:uest)?\.query\b|\bsearchParams\b|\brouter\.query\b|\buseSearchParams\b|\blocation\.(?:search|hash)\b|\bURLSearchParams\b|\bquery\.(?:next|redirect\w*|return\w*|callback\w*|url|to|continue)\b").unwrap(),
+        tainted_binding: Regex::new(r"\b(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=\s*([^;\n]*)").unwrap(),
+        tainted_destructuring: Regex::new(r"\b(?:const|let|var)\s*\{([^}]*)\}\s*=\s*([^;\n]*)").unwrap(),
+        redirect_call: Regex::new(r"(?:\b(?:permanentRedirect|redirect)|\bres\.redirect|\bNextResponse\.redirect|\brouter\.(?:push|replace)|\blocation\.(?:assign|replace))\s*\(").unwrap(),
+        location_assignment: Regex::new(r"\blocation(?:\.href)?\s*=[^=]").unwrap(),
+    })
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<SecurityReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running security analysis");
+
+    let outcome = check(target, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "security",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = report.summary.total_issues == 0, "security analysis completed");
+
+    Ok(outcome)
+}
+
+/// Scan source files for insecure patterns. Critical and high-severity issues fail the check.
+pub fn check(target: &ScanTarget, quiet: bool) -> Result<CommandOutcome<SecurityReport>> {
+    let scanner = FileScanner::for_command(target, "security")?;
+    let files: Vec<PathBuf> = scanner
+        .find_target_files(target, &["ts", "tsx", "js", "jsx", "mjs", "cjs"])
+        .into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(&target.root).unwrap_or(path)))
+        .collect();
+
+    let results: Vec<Vec<SecurityIssue>> = FileUtils::process_files_parallel(
+        &files,
+        |path| scan_file(&target.context, path, &target.root),
+        "Scanning for security issues",
+        quiet
+    )?;
+    let mut issues: Vec<SecurityIssue> = results.into_iter().flatten().collect();
+    target.ignores()?.retain(&mut issues, |issue| (issue.rule.rule_id(), &issue.file));
+    issues.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)).then(a.column.cmp(&b.column)));
+
+    let mut by_rule = BTreeMap::new();
+    for issue in &issues {
+        *by_rule.entry(issue.rule.id().to_string()).or_insert(0) += 1;
+    }
+    let count = |severity: Severity| issues.iter().filter(|issue| issue.severity == severity).count();
+    let summary = SecuritySummary {
+        files_scanned: files.len(),
+        total_issues: issues.len(),
+        critical: count(Severity::Critical),
+        high: count(Severity::High),
+        medium: count(Severity::Medium),
+        low: count(Severity::Low),
+        by_rule,
+    };
+
+    let exit_code = check_failure_threshold(summary.critical + summary.high > 0, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(SecurityReport { issues, summary }, exit_code))
+}
+
+fn scan_file(context: &AnalysisContext, path: &Path, root: &Path) -> Result<Vec<SecurityIssue>> {
+    let content = context.read(path)?;
+    let relative = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+    let handles_requests = REQUEST_HANDLER_GLOBS.iter().any(|glob| glob_matches(glob, &relative))
+        || patterns().use_server.is_match(&content);
+
+    let file = paths::display(path);
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    let issues = check_source(&content, handles_requests).into_iter().map(|(offset, rule, message)| {
+        let line = line_starts.partition_point(|start| *start <= offset);
+        SecurityIssue {
+            file: file.clone(),
+            line,
+            column: content[line_starts[line - 1]..offset].chars().count() + 1,
+            rule,
+            severity: rule.severity(),
+            message,
+            remediation: rule.remediation().to_string(),
+        }
+    }).collect();
+    Ok(issues)
+}
+
+/// Every issue in a file as (byte offset, rule, message)
+fn check_source(content: &str, handles_requests: bool) -> Vec<(usize, SecurityRule, String)> {
+    let patterns = patterns();
+    let code = strip_comments(content);
+    let bare = blank_strings(&code);
+    let mut issues = Vec::new();
+
+    for found in patterns.dangerous_html.find_iter(&code) {
+        let Some(end) = expression_end(code.as_bytes(), found.end() - 1) else { continue };
+        let expression = &code[found.end()..end];
+        if !patterns.sanitized.is_match(expression) && !is_static_html(expression) {
+            issues.push((found.start(), SecurityRule::DangerousHtml, format!("dangerouslySetInnerHTML renders `{}` without sanitizing it", shorten(expression))));
+        }
+    }
+
+    for caps in patterns.eval.captures_iter(&bare) {
+        let call = caps.get(1).unwrap();
+        let name = if call.as_str().starts_with("eval") { "eval()" } else { "new Function()" };
+        issues.push((call.start(), SecurityRule::Eval, format!("{} runs a string as code", name)));
+    }
+    for caps in patterns.string_timer.captures_iter(&bare) {
+        let call = caps.get(1).unwrap();
+        issues.push((call.start(), SecurityRule::Eval, "setTimeout/setInterval with a string runs it as code".to_string()));
+    }
+
+    for (start, end, tagged) in template_literals(&code) {
+        let body = &code[start + 1..end];
+        let is_sql = patterns.sql_upper.is_match(body)
+            || (patterns.sql_any_case.is_match(body) && patterns.sql_clause.is_match(body));
+        if !tagged && body.contains("${") && is_sql {
+            issues.push((start, SecurityRule::SqlInjection, "SQL query interpolates values into a template string".to_string()));
+        }
+    }
+    for found in patterns.sql_concat.find_iter(&code) {
+        issues.push((found.start(), SecurityRule::SqlInjection, "SQL query is concatenated with values".to_string()));
+    }
+
+    if handles_requests {
+        for found in patterns.child_process.find_iter(&code) {
+            issues.push((found.start(), SecurityRule::ChildProcess, "child_process is used in a request handler".to_string()));
+        }
+    }
+
+    issues.extend(open_redirects(&code));
+
+    for element in jsx_elements(&code) {
+        if !matches!(element.name, "a" | "Link") || element.spread {
+            continue;
+        }
+        let opens_new_tab = match element.attribute("target") {
+            Some(Some(value)) => value.text().contains("_blank"),
+            _ => false,
+        };
+        let internal = element.literal("href").is_some_and(|href| href.starts_with('/') && !href.starts_with("//"));
+        let protected = match element.attribute("rel") {
+            Some(Some(AttrValue::Literal(rel))) => rel.contains("noopener") || rel.contains("noreferrer"),
+            // A computed rel may well include them
+            Some(Some(AttrValue::Expression(_))) => true,
+            _ => false,
+        };
+        if opens_new_tab && !internal && !protected {
+            issues.push((element.offset, SecurityRule::TargetBlank, format!("<{} target=\"_blank\"> has no rel=\"noopener\"", element.name)));
+        }
+    }
+
+    issues.sort_by_key(|(offset, _, _)| *offset);
+    issues
+}
+
+/// `{ __html: '<br/>' }`: a plain string literal cannot carry user input
+fn is_static_html(expression: &str) -> bool {
+    let Some((_, value)) = expression.split_once(':') else { return false };
+    let value = value.trim().trim_end_matches('}').trim().trim_end_matches(',').trim();
+    let bytes = value.as_bytes();
+    bytes.len() >= 2
+        && matches!(bytes[0], b'"' | b'\'' | b'`')
+        && bytes[bytes.len() - 1] == bytes[0]
+        && !value[1..value.len() - 1].contains(bytes[0] as char)
+        && !value.contains("${")
+}
+
+/// Redirects whose target comes from the query string, directly or through a variable
+fn open_redirects(code: &str) -> Vec<(usize, SecurityRule, String)> {
+    let patterns = patterns();
+    let mut tainted: HashSet<&str> = HashSet::new();
+    for caps in patterns.tainted_binding.captures_iter(code) {
+        if patterns.query_source.is_match(&caps[2]) {
+            tainted.insert(caps.get(1).unwrap().as_str());
+        }
+    }
+    for caps in patterns.tainted_destructuring.captures_iter(code) {
+        if patterns.query_source.is_match(&caps[2]) {
+            for binding in caps.get(1).unwrap().as_str().split(',') {
+                // `{ next: target = '/' }` binds `target`
+                let binding = binding.rsplit(':').next().unwrap_or(binding);
+                let name = binding.split('=').next().unwrap_or(binding).trim();
+                if !name.is_empty() {
+                    tainted.insert(name);
+                }
+            }
+        }
+    }
+
+    let uses_query = |argument: &str| {
+        patterns.query_source.is_match(argument)
+            || argument.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$')).any(|word| tainted.contains(word))
+    };
+
+    let mut issues = Vec::new();
+    for found in patterns.redirect_call.find_iter(code) {
+        let argument = call_arguments(code, found.end() - 1);
+        if uses_query(argument) {
+            issues.push((found.start(), SecurityRule::OpenRedirect, format!("Redirect target `{}` comes from the query string", shorten(argument))));
+        }
+    }
+    for found in patterns.location_assignment.find_iter(code) {
+        let rest = &code[found.end() - 1..];
+        let value = &rest[..rest.find([';', '\n']).unwrap_or(rest.len())];
+        if uses_query(value) {
+            issues.push((found.start(), SecurityRule::OpenRedirect, format!("location is set to `{}` from the query string", shorten(value.trim()))));
+        }
+    }
+    issues
+}
+
+/// Text between the `(` at `open` and its matching `)`
+fn call_arguments(code: &str, open: usize) -> &str {
+    let bytes = code.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &code[open + 1..i];
+                }
+            }
+            quote @ (b'"' | b'\'' | b'`') => i = string_end(bytes, i, quote),
+            _ => {}
+        }
+        i += 1;
+    }
+    &code[open + 1..]
+}
+
+/// Template literals as (opening backtick, closing backtick, has a tag such as sql`...`)
+fn template_literals(code: &str) -> Vec<(usize, usize, bool)> {
+    let bytes = code.as_bytes();
+    let mut templates = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'`' => {
+                let end = string_end(bytes, i, b'`');
+                let tagged = code[..i].trim_end_matches([' ', '\t']).chars().next_back()
+                    .is_some_and(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | ')'));
+                templates.push((i, end.min(bytes.len() - 1), tagged));
+                i = end;
+            }
+            quote @ (b'"' | b'\'') => i = string_end(bytes, i, quote),
+            _ => {}
+        }
+        i += 1;
+    }
+    templates
+}
+
+fn shorten(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= 60 {
+        text
+    } else {
+        format!("{}…", text.chars().take(59).collect::<String>())
+    }
+}
+
+impl Annotate for SecurityReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            Annotation::new(
+                AnnotationLevel::for_severity(&issue.severity),
+                &issue.file,
+                issue.line,
+                issue.rule.title(),
+                format!("{}. {}", issue.message, issue.remediation),
+            )
+            .with_rule(issue.rule.rule_id())
+        }).collect()
+    }
+}
+
+fn print_report(report: &SecurityReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🛡️  Security Report".bold().blue());
+        output::line("===================".blue());
+        output::blank();
+    }
+
+    if report.issues.is_empty() {
+        output::line("✅ No insecure patterns found!".green());
+        return;
+    }
+
+    let mut current_file = "";
+    for issue in &report.issues {
+        if issue.file != current_file {
+            current_file = &issue.file;
+            output::line(issue.file.cyan().bold());
+        }
+        output::line(format!("  {}:{}  {}  {}", issue.line, issue.column, issue.severity.paint(issue.rule.id()), issue.message));
+    }
+    output::blank();
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Issues: {}", summary.total_issues.to_string().red()));
+    output::line(format!("  Critical: {}  High: {}  Medium: {}  Low: {}",
+        summary.critical.to_string().red().bold(),
+        summary.high.to_string().red(),
+        summary.medium.to_string().yellow(),
+        summary.low
+    ));
+
+    if !quiet {
+        output::blank();
+        output::line("🔧 REMEDIATION".bold().white());
+        output::line("─────────────".white());
+        for rule in SecurityRule::ALL.iter().filter(|rule| summary.by_rule.contains_key(rule.id())) {
+            output::line(format!("  {} ({}): {}", rule.id().bold(), summary.by_rule[rule.id()], rule.remediation()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(content: &str) -> Vec<SecurityRule> {
+        check_source(content, false).into_iter().map(|(_, rule, _)| rule).collect()
+    }
+
+    #[test]
+    fn test_dangerous_html_and_eval() {
+        assert_eq!(rules("<div dangerouslySetInnerHTML={{ __html: post.body }} />"), vec![SecurityRule::DangerousHtml]);
+        assert!(rules("<div dangerouslySetInnerHTML={{ __html: DOMPurify.sanitize(post.body) }} />").is_empty());
+        assert!(rules("<script dangerouslySetInnerHTML={{ __html: JSON.stringify(jsonLd) }} />").is_empty());
+        assert!(rules("<span dangerouslySetInnerHTML={{ __html: '&nbsp;' }} />").is_empty());
+
+        assert_eq!(rules("const result = eval(input);\nconst fn = new Function('a', body);\nsetTimeout('tick()', 10);"),
+            vec![SecurityRule::Eval, SecurityRule::Eval, SecurityRule::Eval]);
+        assert!(rules("// eval(x)\nconst label = 'eval(x)';\nmodel.eval(x);\nsetTimeout(() => tick(), 10);").is_empty());
+    }
+
+    #[test]
+    fn test_sql_injection() {
+        assert_eq!(rules("await db.query(`SELECT * FROM users WHERE id = ${id}`);"), vec![SecurityRule::SqlInjection]);
+        assert_eq!(rules("await db.$queryRawUnsafe(`delete from sessions where token = '${token}'`);"), vec![SecurityRule::SqlInjection]);
+        assert_eq!(rules("pool.query(\"SELECT * FROM orders WHERE user = '\" + user + \"'\");"), vec![SecurityRule::SqlInjection]);
+        assert!(rules("await prisma.$queryRaw`SELECT * FROM users WHERE id = ${id}`;").is_empty());
+        assert!(rules("await db.query('SELECT * FROM users WHERE id = $1', [id]);").is_empty());
+        assert!(rules("const hint = `Please select an option from the list, ${name}`;").is_empty());
+    }
+
+    #[test]
+    fn test_child_process_only_in_request_handlers() {
+        let source = "import { exec } from 'child_process';\nconst { spawn } = require(\"node:child_process\");";
+        assert_eq!(check_source(source, true).len(), 2);
+        assert!(check_source(source, false).is_empty());
+    }
+
+    #[test]
+    fn test_open_redirects() {
+        assert_eq!(rules("res.redirect(req.query.next as string);"), vec![SecurityRule::OpenRedirect]);
+        let source = "const target = searchParams.get('returnTo');\nif (ok) router.push(target);";
+        assert_eq!(rules(source), vec![SecurityRule::OpenRedirect]);
+        let source = "const { next: destination = '/' } = req.query;\nreturn NextResponse.redirect(new URL(destination, req.url));";
+        assert_eq!(rules(source), vec![SecurityRule::OpenRedirect]);
+        assert_eq!(rules("window.location.href = new URLSearchParams(location.search).get('url');"), vec![SecurityRule::OpenRedirect]);
+        assert!(rules("redirect('/login');\nrouter.push(`/posts/${post.id}`);\nif (location.href === next) {}").is_empty());
+    }
+
+    #[test]
+    fn test_target_blank() {
+        assert_eq!(rules(r#"<a href="https://example.com" target="_blank">Docs</a>"#), vec![SecurityRule::TargetBlank]);
+        assert!(rules(r#"<a href="https://example.com" target="_blank" rel="noopener noreferrer">Docs</a>"#).is_empty());
+        assert!(rules(r#"<Link href="/pricing" target="_blank">Pricing</Link>"#).is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+/// `sniff serve`: a local HTTP API answering `GET /analyze/<command>` with findings kept in
+/// memory between requests. A file is analyzed again only when its size or modification time
+/// changed since the last request, so a warm request costs a directory walk and the changed
+/// files instead of a CLI start and a full scan
+use anyhow::{Context, Result};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::commands::lsp::{EXTENSIONS, SOURCE_COMMANDS, source_annotations};
+use crate::common::{output, Annotation, FileScanner, ScanTarget, create_standard_json_output, paths, read_source};
+
+/// Port `sniff serve` listens on when `--port` is not given
+pub const DEFAULT_PORT: u16 = 7654;
+/// Longest request head read; the API only takes `GET`s without a body
+const MAX_REQUEST_BYTES: usize = 16 * 1024;
+/// Files besides the sources that decide how imports resolve: path aliases, dependencies and the
+/// installed packages, whose directory and install markers change on every install
+const RESOLUTION_FILES: &[&str] = &[
+    "tsconfig.json", "jsconfig.json", "package.json",
+    "node_modules", "node_modules/.package-lock.json", "node_modules/.modules.yaml", "node_modules/.yarn-integrity",
+];
+
+#[derive(Debug, Serialize)]
+pub struct AnalyzeSummary {
+    pub files_scanned: usize,
+    pub total_findings: usize,
+    /// Files analyzed for this request because they were new or changed; the others came from memory
+    pub files_analyzed: usize,
+}
+
+/// Size and modification time, which change whenever a file is written
+type Stamp = (u64, Option<SystemTime>);
+
+/// A file's findings for one command, valid while its stamp is unchanged
+struct CachedFindings {
+    stamp: Stamp,
+    findings: Vec<Annotation>,
+}
+
+/// What the daemon remembers between requests
+#[derive(Default)]
+struct Workspace {
+    /// Findings by command and file
+    cache: HashMap<&'static str, HashMap<PathBuf, CachedFindings>>,
+    /// The files each command checked last time
+    files: HashMap<&'static str, BTreeSet<PathBuf>>,
+    /// Stamps of the `RESOLUTION_FILES` when imports were last checked
+    resolution: Vec<Stamp>,
+    /// The effective config of the last request; any change drops every finding
+    config: serde_json::Value,
+}
+
+/// Serve until the process is stopped. Port 0 picks a free port, which is printed
+pub async fn run(target: ScanTarget, port: u16, quiet: bool) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await
+        .with_context(|| format!("Failed to listen on port {}", port))?;
+    let address = listener.local_addr()?;
+    output::line(format!("sniff serve listening on http://{}", address));
+    if !quiet {
+        output::line(format!("GET /analyze/<command> with command one of {}", SOURCE_COMMANDS.join(", ")));
+    }
+
+    let target = Arc::new(target);
+    let workspace = Arc::new(Mutex::new(Workspace::default()));
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let (target, workspace) = (target.clone(), workspace.clone());
+        tokio::spawn(async move {
+            // A client that hangs up early only loses its own response
+            let _ = respond(stream, target, workspace).await;
+        });
+    }
+}
+
+async fn respond(mut stream: TcpStream, target: Arc<ScanTarget>, workspace: Arc<Mutex<Workspace>>) -> Result<()> {
+    let head = read_head(&mut stream).await?;
+    let mut request_line = head.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+    let path = path.split('?').next().unwrap_or_default();
+
+    let (status, body) = match (method, path.strip_prefix("/analyze/")) {
+        ("GET", Some(name)) => match SOURCE_COMMANDS.iter().copied().find(|command| *command == name) {
+            Some(command) => {
+                let analyzed = tokio::task::spawn_blocking(move || analyze(&target, &workspace, command)).await?;
+                match analyzed {
+                    Ok(response) => ("200 OK", serde_json::to_string(&response)?),
+                    Err(error) => ("500 Internal Server Error", error_body(&format!("{:#}", error))),
+                }
+            }
+            None => ("404 Not Found", error_body(&format!("'{}' cannot be analyzed here; use one of {}", name, SOURCE_COMMANDS.join(", ")))),
+        },
+        ("GET", None) if path == "/" => ("200 OK", serde_json::json!({ "commands": SOURCE_COMMANDS }).to_string()),
+        ("GET", None) => ("404 Not Found", error_body("Use GET /analyze/<command>")),
+        _ => ("405 Method Not Allowed", error_body("Only GET is supported")),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, body.len(), body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// The request line and headers
+async fn read_head(stream: &mut TcpStream) -> Result<String> {
+    let mut head = Vec::new();
+    let mut buffer = [0; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+        let read = stream.read(&mut buffer).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buffer[..read]);
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+fn error_body(message: &str) -> String {
+    serde_json::json!({ "error": message }).to_string()
+}
+
+/// The command's findings for the target, analyzing only the files that changed since they were last seen
+fn analyze(target: &ScanTarget, workspace: &Mutex<Workspace>, command: &'static str) -> Result<impl Serialize> {
+    let started = Instant::now();
+    let config = target.config()?;
+    let files = FileScanner::for_command(target, command)?.find_target_files(target, EXTENSIONS);
+
+    let mut workspace = workspace.lock().map_err(|_| anyhow::anyhow!("An earlier request failed while analyzing"))?;
+    // As a JSON value, so map settings compare without regard to their order
+    let fingerprint = serde_json::to_value(&config)?;
+    if workspace.config != fingerprint {
+        workspace.cache.clear();
+        workspace.config = fingerprint;
+    }
+    let listed: BTreeSet<PathBuf> = files.iter().cloned().collect();
+    // A created or deleted file, a new path alias or an installed package can fix or break an import anywhere
+    if command == "imports" {
+        let resolution: Vec<Stamp> = RESOLUTION_FILES.iter().map(|file| stamp(&target.root.join(file))).collect();
+        if workspace.files.get(command) != Some(&listed) || workspace.resolution != resolution {
+            workspace.cache.remove(command);
+        }
+        workspace.resolution = resolution;
+    }
+    workspace.files.insert(command, listed.clone());
+
+    let cache = workspace.cache.entry(command).or_default();
+    cache.retain(|path, _| listed.contains(path));
+    let stale: Vec<(&PathBuf, Stamp)> = files.iter()
+        .map(|path| (path, stamp(path)))
+        .filter(|(path, stamp)| cache.get(*path).is_none_or(|cached| cached.stamp != *stamp))
+        .collect();
+    // A failed analysis is an error response and caches nothing, so the next request tries again
+    let fresh: Vec<(PathBuf, CachedFindings)> = stale.par_iter()
+        .map(|(path, stamp)| {
+            // Oversized and binary files are generated, not project code
+            let findings = match read_source(path) {
+                Ok(Some(text)) => source_annotations(command, &target.root, path, &text, &config)
+                    .with_context(|| format!("Failed to analyze {}", paths::display(path)))?,
+                _ => Vec::new(),
+            };
+            Ok(((*path).clone(), CachedFindings { stamp: *stamp, findings }))
+        })
+        .collect::<Result<_>>()?;
+    let files_analyzed = fresh.len();
+    cache.extend(fresh);
+
+    let findings: Vec<Annotation> = files.iter()
+        .filter_map(|path| cache.get(path))
+        .flat_map(|cached| cached.findings.iter().cloned())
+        .collect();
+    drop(workspace);
+
+    let summary = AnalyzeSummary { files_scanned: files.len(), total_findings: findings.len(), files_analyzed };
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let total_findings = summary.total_findings;
+    Ok(create_standard_json_output(command, summary, files.len(), total_findings, Some(duration_ms)).with_findings(findings))
+}
+
+fn stamp(path: &Path) -> Stamp {
+    std::fs::metadata(path).map_or((0, None), |metadata| (metadata.len(), metadata.modified().ok()))
+}
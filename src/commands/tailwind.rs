@@ -0,0 +1,626 @@
+use anyhow::Result;
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::utils::FileUtils;
+use crate::config::TailwindConfig;
+use crate::common::jsx::{jsx_elements, AttrValue};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, glob_matches, create_annotated_json_output, output_result};
+
+const CONFIG_FILES: &[&str] = &["tailwind.config.ts", "tailwind.config.js", "tailwind.config.cjs", "tailwind.config.mjs"];
+
+/// Tailwind's default palette, available unless `theme.colors` replaces it
+const DEFAULT_COLORS: &[&str] = &[
+    "inherit", "current", "transparent", "black", "white",
+    "slate", "gray", "zinc", "neutral", "stone", "red", "orange", "amber", "yellow", "lime", "green",
+    "emerald", "teal", "cyan", "sky", "blue", "indigo", "violet", "purple", "fuchsia", "pink", "rose",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TailwindReport {
+    /// `None` when the project has no Tailwind config; nothing else is checked then
+    pub config_file: Option<String>,
+    /// Globs from the config's `content`
+    pub content: Vec<String>,
+    pub issues: Vec<TailwindIssue>,
+    pub summary: TailwindSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TailwindIssue {
+    pub kind: TailwindIssueKind,
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    /// The class, class string or file the issue is about
+    pub value: String,
+    pub count: usize,
+    /// Every place a repeated value appears, as `file:line`
+    pub locations: Vec<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TailwindIssueKind {
+    ArbitraryOveruse,
+    RepeatedArbitraryValue,
+    UnknownColor,
+    DuplicateClasses,
+    UncoveredFile,
+}
+
+impl TailwindIssueKind {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            TailwindIssueKind::ArbitraryOveruse => "SNF-TW-ARBITRARY",
+            TailwindIssueKind::RepeatedArbitraryValue => "SNF-TW-REPEATED-ARBITRARY",
+            TailwindIssueKind::UnknownColor => "SNF-TW-UNKNOWN-COLOR",
+            TailwindIssueKind::DuplicateClasses => "SNF-TW-DUPLICATE-CLASSES",
+            TailwindIssueKind::UncoveredFile => "SNF-TW-UNCOVERED-FILE",
+        }
+    }
+
+    /// Unknown colors and uncovered files lose their styles in production, so they fail the check
+    fn level(self) -> AnnotationLevel {
+        match self {
+            TailwindIssueKind::UnknownColor | TailwindIssueKind::UncoveredFile => AnnotationLevel::Error,
+            TailwindIssueKind::ArbitraryOveruse | TailwindIssueKind::RepeatedArbitraryValue => AnnotationLevel::Warning,
+            TailwindIssueKind::DuplicateClasses => AnnotationLevel::Notice,
+        }
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            TailwindIssueKind::ArbitraryOveruse => "Too many arbitrary values",
+            TailwindIssueKind::RepeatedArbitraryValue => "Repeated arbitrary value",
+            TailwindIssueKind::UnknownColor => "Unknown Tailwind color",
+            TailwindIssueKind::DuplicateClasses => "Duplicated class string",
+            TailwindIssueKind::UncoveredFile => "File outside Tailwind content",
+        }
+    }
+}
+
+impl Annotate for TailwindReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.issues.iter().map(|issue| {
+            let (level, title) = (issue.kind.level(), issue.kind.title());
+            let finding = match (&issue.file, issue.line) {
+                (Some(file), Some(line)) => Annotation::new(level, file, line, title, &issue.message),
+                (Some(file), None) => Annotation::file(level, file, title, &issue.message),
+                (None, _) => Annotation::project(level, title, &issue.message),
+            };
+            finding.with_rule(issue.kind.rule_id())
+        }).collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TailwindSummary {
+    pub files_scanned: usize,
+    pub total_classes: usize,
+    pub arbitrary_classes: usize,
+    pub arbitrary_percent: f64,
+    pub unknown_colors: usize,
+    pub duplicate_class_strings: usize,
+    pub uncovered_files: usize,
+    pub total_issues: usize,
+}
+
+/// What the audit needs from `tailwind.config.*`, read statically
+#[derive(Debug, Default)]
+struct ThemeInfo {
+    content: Vec<String>,
+    /// `None` when the colors cannot be known, e.g. `colors: require('./palette')`
+    colors: Option<HashSet<String>>,
+}
+
+/// A `className`/`class` value and where it was found
+struct ClassString {
+    file: String,
+    line: usize,
+    text: String,
+}
+
+struct Patterns {
+    string_literal: Regex,
+    html_class: Regex,
+    color_utility: Regex,
+    object_key: Regex,
+}
+
+static PATTERNS: OnceLock<Patterns> = OnceLock::new();
+
+fn patterns() -> &'static Patterns {
+    PATTERNS.get_or_init(|| Patterns {
+        string_literal: Regex::new(r#""([^"\n]*)"|'([^'\n]*)'|`([^`]*)`"#).unwrap(),
+        html_class: Regex::new(r#"\bclass(?:Name)?\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap(),
+        color_utility: Regex::new(
+            r"^(?:bg|text|border(?:-[xytrbl])?|ring(?:-offset)?|outline|fill|stroke|from|via|to|divide|placeholder|decoration|accent|caret|shadow)-([a-z][a-z0-9-]*?)-(?:50|[1-9]00|950)(?:/\d+)?$"
+        ).unwrap(),
+        object_key: Regex::new(r#"^\s*(?:([A-Za-z_$][\w$-]*)|"([^"]+)"|'([^']+)')\s*:"#).unwrap(),
+    })
+}
+
+pub async fn run(target: &ScanTarget, json: bool, quiet: bool) -> Result<CommandOutcome<TailwindReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running tailwind analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.tailwind, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "tailwind",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_issues,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = report.summary.total_issues == 0, "tailwind analysis completed");
+
+    Ok(outcome)
+}
+
+/// Audit class usage against the Tailwind config. Unknown colors and files outside `content`
+/// produce missing styles, so they fail the check; the rest are suggestions.
+pub fn check(target: &ScanTarget, limits: &TailwindConfig, quiet: bool) -> Result<CommandOutcome<TailwindReport>> {
+    let Some(config_path) = CONFIG_FILES.iter().map(|name| target.root.join(name)).find(|path| path.exists()) else {
+        let report = TailwindReport { config_file: None, content: Vec::new(), issues: Vec::new(), summary: TailwindSummary::default() };
+        return Ok(CommandOutcome::new(report, ExitCode::Success));
+    };
+    let theme = parse_config(&fs::read_to_string(&config_path)?);
+
+    let scanner = FileScanner::for_command(target, "tailwind")?;
+    let files: Vec<PathBuf> = scanner
+        .find_target_files(target, &["tsx", "jsx", "ts", "js", "html", "vue", "svelte", "astro"])
+        .into_iter()
+        .filter(|path| !FileUtils::is_test_file(path.strip_prefix(&target.root).unwrap_or(path)))
+        .collect();
+
+    let results: Vec<Vec<ClassString>> = FileUtils::process_files_parallel(
+        &files,
+        |path| class_strings(path, &target.root),
+        "Collecting Tailwind classes",
+        quiet
+    )?;
+
+    let mut issues = Vec::new();
+    let mut summary = TailwindSummary { files_scanned: files.len(), ..TailwindSummary::default() };
+
+    // Files that use classes but are not in `content` get no CSS for them in production
+    if !theme.content.is_empty() {
+        let globs: Vec<&str> = theme.content.iter()
+            .filter(|glob| !glob.starts_with('!'))
+            .map(|glob| glob.trim_start_matches("./"))
+            .collect();
+        for file in results.iter().filter_map(|strings| strings.first()).map(|string| &string.file) {
+            if !globs.iter().any(|glob| glob_matches(glob, file)) {
+                issues.push(TailwindIssue {
+                    kind: TailwindIssueKind::UncoveredFile,
+                    file: Some(file.clone()),
+                    line: None,
+                    value: file.clone(),
+                    count: 1,
+                    locations: Vec::new(),
+                    message: "Uses Tailwind classes but is not matched by `content` in the Tailwind config".to_string(),
+                });
+            }
+        }
+    }
+
+    let strings: Vec<ClassString> = results.into_iter().flatten().collect();
+    let mut arbitrary: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    let mut duplicates: BTreeMap<String, Vec<&ClassString>> = BTreeMap::new();
+    let mut reported_colors = HashSet::new();
+
+    for string in &strings {
+        let classes: Vec<&str> = string.text.split_whitespace().collect();
+        summary.total_classes += classes.len();
+        for class in &classes {
+            let utility = utility(class);
+            if utility.starts_with('[') || utility.contains("-[") {
+                summary.arbitrary_classes += 1;
+                arbitrary.entry(utility).or_default().push(format!("{}:{}", string.file, string.line));
+            }
+            if let (Some(colors), Some(caps)) = (&theme.colors, patterns().color_utility.captures(utility)) {
+                let name = caps.get(1).unwrap().as_str();
+                if !colors.contains(name) && reported_colors.insert((&string.file, string.line, utility)) {
+                    issues.push(TailwindIssue {
+                        kind: TailwindIssueKind::UnknownColor,
+                        file: Some(string.file.clone()),
+                        line: Some(string.line),
+                        value: utility.to_string(),
+                        count: 1,
+                        locations: Vec::new(),
+                        message: format!("`{}` is not a color in the theme, so `{}` generates no CSS", name, utility),
+                    });
+                }
+            }
+        }
+        if classes.len() >= limits.duplicate_min_classes {
+            let mut normalized = classes.clone();
+            normalized.sort_unstable();
+            normalized.dedup();
+            duplicates.entry(normalized.join(" ")).or_default().push(string);
+        }
+    }
+
+    if summary.total_classes > 0 {
+        summary.arbitrary_percent = (summary.arbitrary_classes as f64 / summary.total_classes as f64 * 1000.0).round() / 10.0;
+    }
+    if summary.arbitrary_percent > limits.max_arbitrary_percent {
+        issues.push(TailwindIssue {
+            kind: TailwindIssueKind::ArbitraryOveruse,
+            file: None,
+            line: None,
+            value: format!("{}%", summary.arbitrary_percent),
+            count: summary.arbitrary_classes,
+            locations: Vec::new(),
+            message: format!(
+                "{}% of classes are arbitrary values (limit {}%); move recurring ones into the theme",
+                summary.arbitrary_percent, limits.max_arbitrary_percent
+            ),
+        });
+    }
+    for (utility, locations) in arbitrary.into_iter().filter(|(_, locations)| locations.len() >= limits.repeated_arbitrary_min) {
+        let (file, line) = split_location(&locations[0]);
+        issues.push(TailwindIssue {
+            kind: TailwindIssueKind::RepeatedArbitraryValue,
+            file: Some(file),
+            line: Some(line),
+            value: utility.to_string(),
+            count: locations.len(),
+            message: format!("`{}` is used {} times; add the value to the theme", utility, locations.len()),
+            locations,
+        });
+    }
+    for (classes, uses) in duplicates.into_iter().filter(|(_, uses)| uses.len() >= limits.duplicate_min_occurrences) {
+        issues.push(TailwindIssue {
+            kind: TailwindIssueKind::DuplicateClasses,
+            file: Some(uses[0].file.clone()),
+            line: Some(uses[0].line),
+            count: uses.len(),
+            locations: uses.iter().map(|string| format!("{}:{}", string.file, string.line)).collect(),
+            message: format!(
+                "The same {} classes appear {} times; extract a component or an @apply class",
+                classes.split(' ').count(), uses.len()
+            ),
+            value: classes,
+        });
+    }
+
+    let count = |kind: TailwindIssueKind| issues.iter().filter(|issue| issue.kind == kind).count();
+    summary.unknown_colors = count(TailwindIssueKind::UnknownColor);
+    summary.duplicate_class_strings = count(TailwindIssueKind::DuplicateClasses);
+    summary.uncovered_files = count(TailwindIssueKind::UncoveredFile);
+    summary.total_issues = issues.len();
+
+    let exit_code = check_failure_threshold(summary.unknown_colors + summary.uncovered_files > 0, ExitCode::ValidationFailed);
+    let report = TailwindReport {
+        config_file: Some(FileUtils::slash_path(config_path.strip_prefix(&target.root).unwrap_or(&config_path))),
+        content: theme.content,
+        issues,
+        summary,
+    };
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+fn split_location(location: &str) -> (String, usize) {
+    match location.rsplit_once(':') {
+        Some((file, line)) => (file.to_string(), line.parse().unwrap_or(0)),
+        None => (location.to_string(), 0),
+    }
+}
+
+/// The utility of a class without variants, `!important` or a negative sign: `md:hover:-mt-4` is `mt-4`
+fn utility(class: &str) -> &str {
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in class.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ':' if depth == 0 => start = index + 1,
+            _ => {}
+        }
+    }
+    class[start..].trim_start_matches('!').trim_start_matches('-')
+}
+
+/// Class strings in a file with the line they start on. File paths are relative to the root,
+/// like the `content` globs.
+fn class_strings(path: &Path, root: &Path) -> Result<Vec<ClassString>> {
+    let content = fs::read_to_string(path)?;
+    let file = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+    let line_of = |offset: usize| content[..offset].matches('\n').count() + 1;
+    let mut strings = Vec::new();
+
+    let is_markup = matches!(path.extension().and_then(|ext| ext.to_str()), Some("html" | "vue" | "svelte" | "astro"));
+    if is_markup {
+        for caps in patterns().html_class.captures_iter(&content) {
+            let value = caps.get(1).or_else(|| caps.get(2)).unwrap();
+            strings.push(ClassString { file: file.clone(), line: line_of(value.start()), text: value.as_str().to_string() });
+        }
+        return Ok(strings);
+    }
+
+    for element in jsx_elements(&content) {
+        for (name, value) in &element.attributes {
+            if !matches!(*name, "className" | "class") {
+                continue;
+            }
+            let line = line_of(element.offset);
+            match value {
+                Some(AttrValue::Literal(text)) => strings.push(ClassString { file: file.clone(), line, text: text.to_string() }),
+                // `cn('px-4', active && 'bg-blue-500')`: each string literal is a class string
+                Some(AttrValue::Expression(expression)) => {
+                    for caps in patterns().string_literal.captures_iter(expression) {
+                        let text = caps.get(1).or_else(|| caps.get(2)).or_else(|| caps.get(3)).unwrap().as_str();
+                        let text = text.split("${").next().unwrap_or(text);
+                        if !text.trim().is_empty() {
+                            strings.push(ClassString { file: file.clone(), line, text: text.to_string() });
+                        }
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+    Ok(strings)
+}
+
+fn parse_config(source: &str) -> ThemeInfo {
+    let bytes = source.as_bytes();
+    let mut info = ThemeInfo::default();
+
+    if let Some(value) = property_value(source, 0, "content") {
+        // `content: { files: [...] }` in the object form
+        let list = match bytes[value] {
+            b'{' => property_value(source, value, "files").filter(|start| bytes[*start] == b'['),
+            b'[' => Some(value),
+            _ => None,
+        };
+        if let Some(start) = list {
+            let end = closing(bytes, start).unwrap_or(bytes.len());
+            info.content = patterns().string_literal.captures_iter(&source[start..end])
+                .filter_map(|caps| caps.get(1).or_else(|| caps.get(2)).or_else(|| caps.get(3)))
+                .map(|glob| glob.as_str().to_string())
+                .collect();
+        }
+    }
+
+    let theme = property_value(source, 0, "theme").filter(|start| bytes[*start] == b'{');
+    let extend = theme.and_then(|theme| property_value(source, theme, "extend")).filter(|start| bytes[*start] == b'{');
+    let mut colors: HashSet<String> = DEFAULT_COLORS.iter().map(|color| color.to_string()).collect();
+
+    if let Some(theme) = theme {
+        if let Some(start) = property_value(source, theme, "colors") {
+            if bytes[start] != b'{' {
+                return info;
+            }
+            let object = &source[start..closing(bytes, start).unwrap_or(bytes.len())];
+            // `...colors` keeps the default palette
+            if !object.contains("...") {
+                colors.clear();
+            }
+            colors.extend(object_keys(object));
+        }
+    }
+    if let Some(extend) = extend {
+        if let Some(start) = property_value(source, extend, "colors") {
+            if bytes[start] != b'{' {
+                return info;
+            }
+            colors.extend(object_keys(&source[start..closing(bytes, start).unwrap_or(bytes.len())]));
+        }
+    }
+    info.colors = Some(colors);
+    info
+}
+
+/// Offset of the value of `name` among the direct properties of the object at `object`
+/// (0 searches the whole file)
+fn property_value(source: &str, object: usize, name: &str) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let end = if object == 0 { bytes.len() } else { closing(bytes, object)? };
+    let depth_of_properties = if object == 0 { None } else { Some(1) };
+    let mut depth = 0usize;
+    let mut i = object;
+    while i < end {
+        match bytes[i] {
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' => depth = depth.saturating_sub(1),
+            quote @ (b'"' | b'\'' | b'`') => {
+                i = string_end(bytes, i, quote);
+            }
+            _ if source[i..].starts_with(name)
+                && depth_of_properties.is_none_or(|wanted| depth == wanted)
+                && (i == 0 || !(bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_' || bytes[i - 1] == b'.')) =>
+            {
+                let rest = source[i + name.len()..].trim_start();
+                if let Some(value) = rest.strip_prefix(':') {
+                    let value = value.trim_start();
+                    return Some(source.len() - value.len());
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Top-level keys of an object literal, e.g. `brand` and `accent` in `{ brand: {...}, 'accent': '#f00' }`
+fn object_keys(object: &str) -> Vec<String> {
+    let bytes = object.as_bytes();
+    let mut keys = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' | b'(' => {
+                depth += 1;
+                if depth == 1 {
+                    keys.extend(key_at(&object[i + 1..]));
+                }
+            }
+            b'}' | b']' | b')' => depth = depth.saturating_sub(1),
+            b',' if depth == 1 => keys.extend(key_at(&object[i + 1..])),
+            quote @ (b'"' | b'\'' | b'`') => i = string_end(bytes, i, quote),
+            _ => {}
+        }
+        i += 1;
+    }
+    keys
+}
+
+fn key_at(text: &str) -> Option<String> {
+    let caps = patterns().object_key.captures(text)?;
+    Some(caps.get(1).or_else(|| caps.get(2)).or_else(|| caps.get(3))?.as_str().to_string())
+}
+
+/// Index of the bracket closing the one at `start`, skipping strings
+fn closing(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' | b'[' | b'(' => depth += 1,
+            b'}' | b']' | b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            quote @ (b'"' | b'\'' | b'`') => i = string_end(bytes, i, quote),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn string_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != quote {
+        if bytes[i] == b'\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+fn print_report(report: &TailwindReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🎨 Tailwind CSS Audit".bold().blue());
+        output::line("=====================".blue());
+        output::blank();
+    }
+
+    let Some(config_file) = &report.config_file else {
+        output::line("ℹ️  No tailwind.config.{js,cjs,mjs,ts} found; nothing to audit".dimmed());
+        return;
+    };
+    if !quiet {
+        output::line(format!("Config: {}  ·  content: {}", config_file.cyan(), if report.content.is_empty() { "(not found)".to_string() } else { report.content.join(", ") }));
+        output::blank();
+    }
+
+    let sections = [
+        (TailwindIssueKind::UnknownColor, "❌ UNKNOWN COLORS"),
+        (TailwindIssueKind::UncoveredFile, "❌ FILES OUTSIDE `content` (their classes get no CSS)"),
+        (TailwindIssueKind::ArbitraryOveruse, "⚠️  ARBITRARY VALUES"),
+        (TailwindIssueKind::RepeatedArbitraryValue, "⚠️  REPEATED ARBITRARY VALUES"),
+        (TailwindIssueKind::DuplicateClasses, "💡 DUPLICATED CLASS STRINGS"),
+    ];
+    for (kind, title) in sections {
+        let issues: Vec<&TailwindIssue> = report.issues.iter().filter(|issue| issue.kind == kind).collect();
+        if issues.is_empty() {
+            continue;
+        }
+        output::line(title.bold());
+        for issue in issues {
+            let location = match (&issue.file, issue.line) {
+                (Some(file), Some(line)) => format!("{}:{}", file, line),
+                (Some(file), None) => file.clone(),
+                _ => String::new(),
+            };
+            match kind {
+                TailwindIssueKind::UncoveredFile => output::line(format!("  {}", location.cyan())),
+                TailwindIssueKind::ArbitraryOveruse => output::line(format!("  {}", issue.message)),
+                TailwindIssueKind::DuplicateClasses => {
+                    output::line(format!("  {} ({}×)", issue.value.yellow(), issue.count));
+                    if !quiet {
+                        output::line(format!("     {}", issue.locations.join(", ").dimmed()));
+                    }
+                }
+                _ => output::line(format!("  {}  {}", location.cyan(), issue.message)),
+            }
+        }
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Classes: {} ({} arbitrary, {}%)", summary.total_classes, summary.arbitrary_classes, summary.arbitrary_percent));
+    if summary.total_issues == 0 {
+        output::line("✅ Tailwind usage looks clean!".green());
+    } else {
+        output::line(format!("  Issues: {}", summary.total_issues.to_string().yellow()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let source = r#"import type { Config } from 'tailwindcss'
+export default {
+  content: ['./app/**/*.{ts,tsx}', "./components/**/*.tsx"],
+  theme: {
+    extend: {
+      colors: { brand: { 500: '#0af', DEFAULT: '#08c' }, 'brand-muted': '#eee' },
+    },
+  },
+  plugins: [],
+} satisfies Config"#;
+        let info = parse_config(source);
+        assert_eq!(info.content, vec!["./app/**/*.{ts,tsx}", "./components/**/*.tsx"]);
+        let colors = info.colors.unwrap();
+        assert!(colors.contains("brand") && colors.contains("brand-muted") && colors.contains("slate"));
+        assert!(!colors.contains("500"));
+
+        // `theme.colors` replaces the default palette; `content.files` is the object form
+        let info = parse_config("module.exports = { content: { files: ['./src/**/*.html'] }, theme: { colors: { ink: '#111' } } }");
+        assert_eq!(info.content, vec!["./src/**/*.html"]);
+        assert_eq!(info.colors.unwrap().into_iter().collect::<Vec<_>>(), vec!["ink"]);
+        assert!(parse_config("module.exports = { theme: { colors: require('./palette') } }").colors.is_none());
+    }
+
+    #[test]
+    fn test_utility_and_color_pattern() {
+        assert_eq!(utility("md:hover:-mt-4"), "mt-4");
+        assert_eq!(utility("[&>svg]:w-[calc(100%-2px)]"), "w-[calc(100%-2px)]");
+        assert_eq!(utility("!bg-red-500/50"), "bg-red-500/50");
+        let name = |class: &str| patterns().color_utility.captures(class).map(|caps| caps[1].to_string());
+        assert_eq!(name("border-t-primary-200").as_deref(), Some("primary"));
+        assert_eq!(name("bg-brand-muted-50/80").as_deref(), Some("brand-muted"));
+        assert_eq!(name("text-lg"), None);
+        assert_eq!(name("shadow-md"), None);
+    }
+}
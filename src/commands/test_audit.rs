@@ -0,0 +1,450 @@
+use anyhow::{Context, Result};
+use colored::*;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use crate::utils::FileUtils;
+use crate::config::TestsConfig;
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, FileScanner, ScanTarget, ExitCode, CommandOutcome, check_failure_threshold, matches_any_glob, create_annotated_json_output, output_result};
+
+const EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+
+/// Directory names dropped before pairing a test with its source, so that
+/// `src/lib/__tests__/date.test.ts` and `tests/lib/date.test.ts` both cover `src/lib/date.ts`
+const NEUTRAL_DIRS: &[&str] = &["src", "__tests__", "__test__", "test", "tests"];
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TestsReport {
+    pub untested: Vec<UntestedFile>,
+    pub skipped: Vec<SkippedTest>,
+    pub directories: Vec<DirectoryStats>,
+    /// The lcov file merged into the report, if any
+    pub lcov: Option<String>,
+    pub summary: TestsSummary,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UntestedFile {
+    pub file: String,
+    pub lines: usize,
+    /// `.tsx`/`.jsx` files, which usually hold components
+    pub component: bool,
+    /// Line coverage from lcov, in percent
+    pub line_coverage: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SkippedTest {
+    pub file: String,
+    pub line: usize,
+    /// The skipping call, e.g. `describe.skip` or `xit`
+    pub call: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryStats {
+    pub directory: String,
+    pub source_files: usize,
+    pub tested_files: usize,
+    pub test_files: usize,
+    /// Share of source files with a test, in percent
+    pub tested_percent: f64,
+    pub line_coverage: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct TestsSummary {
+    pub source_files: usize,
+    pub test_files: usize,
+    pub tested_files: usize,
+    pub untested_files: usize,
+    pub skipped_tests: usize,
+    pub tested_percent: f64,
+    pub line_coverage: Option<f64>,
+    /// `[tests] min_tested_percent`; 0 when no minimum is configured
+    pub min_tested_percent: f64,
+}
+
+impl Annotate for TestsReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let mut findings = Vec::new();
+        let summary = &self.summary;
+        if summary.tested_percent < summary.min_tested_percent {
+            findings.push(Annotation::project(
+                AnnotationLevel::Error,
+                "Too few tested files",
+                format!("{:.1}% of source files have a test (minimum {}%)", summary.tested_percent, summary.min_tested_percent),
+            ).with_rule("SNF-TESTS-COVERAGE"));
+        }
+        for file in &self.untested {
+            findings.push(Annotation::file(AnnotationLevel::Notice, &file.file, "Untested file", format!("No test covers this file ({} lines)", file.lines))
+                .with_rule("SNF-TESTS-UNTESTED"));
+        }
+        for test in &self.skipped {
+            let message = match &test.name {
+                Some(name) => format!("`{}` skips '{}'", test.call, name),
+                None => format!("`{}` skips a test", test.call),
+            };
+            findings.push(Annotation::new(AnnotationLevel::Warning, &test.file, test.line, "Skipped test", message)
+                .with_rule("SNF-TESTS-SKIPPED"));
+        }
+        findings
+    }
+}
+
+/// Lines found and hit for one file in an lcov report
+#[derive(Debug, Default, Clone, Copy)]
+struct LineCounts {
+    found: usize,
+    hit: usize,
+}
+
+impl LineCounts {
+    fn add(&mut self, other: LineCounts) {
+        self.found += other.found;
+        self.hit += other.hit;
+    }
+
+    fn percent(self) -> Option<f64> {
+        (self.found > 0).then(|| self.hit as f64 * 100.0 / self.found as f64)
+    }
+}
+
+static SKIP_PATTERN: OnceLock<Regex> = OnceLock::new();
+
+fn skip_pattern() -> &'static Regex {
+    SKIP_PATTERN.get_or_init(|| {
+        Regex::new(r#"(?m)(?:^|[^\w$.])((?:describe|context|suite|it|test)\.skip(?:\.each)?|x(?:describe|context|it|test))\s*\(\s*(?:['"`]([^'"`\n]*))?"#).unwrap()
+    })
+}
+
+pub async fn run(target: &ScanTarget, lcov: Option<&Path>, json: bool, quiet: bool) -> Result<CommandOutcome<TestsReport>> {
+    let start_time = std::time::Instant::now();
+    let suppress = quiet || json;
+    tracing::info!("Running tests analysis");
+
+    let config = target.config()?;
+    let outcome = check(target, &config.tests, lcov, suppress)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let summary = &report.summary;
+    let response = create_annotated_json_output(
+        "tests",
+        report,
+        summary.source_files + summary.test_files,
+        summary.untested_files + summary.skipped_tests,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(clean = response.summary.issues_found == 0, "tests analysis completed");
+
+    Ok(outcome)
+}
+
+/// Pair source files with their tests, find skipped tests and summarize per directory.
+/// Falls short of `min_tested_percent` only when it is configured.
+pub fn check(target: &ScanTarget, config: &TestsConfig, lcov: Option<&Path>, quiet: bool) -> Result<CommandOutcome<TestsReport>> {
+    let scanner = FileScanner::for_root(&target.root)?;
+    let relative = |path: &Path| FileUtils::slash_path(path.strip_prefix(&target.root).unwrap_or(path));
+
+    // Tests may live outside the scanned paths (e.g. a top-level `tests/`), so look for them everywhere
+    let test_files: Vec<PathBuf> = scanner
+        .find_files_with_extensions(&target.root, EXTENSIONS)
+        .into_iter()
+        .filter(|path| matches_any_glob(&relative(path), &config.patterns))
+        .collect();
+    let sources: Vec<PathBuf> = scanner
+        .find_target_files(target, EXTENSIONS)
+        .into_iter()
+        .filter(|path| {
+            let relative = relative(path);
+            !matches_any_glob(&relative, &config.patterns) && !matches_any_glob(&relative, &config.exclude)
+        })
+        .collect();
+
+    let coverage = match lcov {
+        Some(path) => {
+            let path = if path.is_absolute() { path.to_path_buf() } else { target.root.join(path) };
+            let content = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read lcov file {}", path.display()))?;
+            Some(parse_lcov(&content, &target.root))
+        }
+        None => None,
+    };
+
+    // Tests by the stem of the file they cover
+    let mut tests_by_stem: HashMap<String, Vec<(String, String)>> = HashMap::new();
+    for path in &test_files {
+        let relative = relative(path);
+        let (stem, directory) = test_subject(&relative);
+        tests_by_stem.entry(stem).or_default().push((neutral_dir(&directory), relative));
+    }
+
+    let mut untested = Vec::new();
+    let mut directories: BTreeMap<String, (DirectoryStats, BTreeSet<String>, LineCounts)> = BTreeMap::new();
+    let mut total_coverage = LineCounts::default();
+    for path in &sources {
+        let file = relative(path);
+        let directory = parent_dir(&file).to_string();
+        let counts = coverage.as_ref().and_then(|coverage| coverage.get(&file).copied());
+        let tests = matching_tests(&file, &tests_by_stem);
+
+        let (stats, dir_tests, dir_coverage) = directories.entry(directory.clone()).or_insert_with(|| (
+            DirectoryStats { directory: if directory.is_empty() { ".".to_string() } else { directory }, source_files: 0, tested_files: 0, test_files: 0, tested_percent: 0.0, line_coverage: None },
+            BTreeSet::new(),
+            LineCounts::default(),
+        ));
+        stats.source_files += 1;
+        if let Some(counts) = counts {
+            dir_coverage.add(counts);
+            total_coverage.add(counts);
+        }
+        if tests.is_empty() {
+            untested.push(UntestedFile {
+                component: file.ends_with(".tsx") || file.ends_with(".jsx"),
+                lines: FileUtils::count_lines_optimized(path).unwrap_or(0),
+                line_coverage: counts.and_then(LineCounts::percent),
+                file,
+            });
+        } else {
+            stats.tested_files += 1;
+            dir_tests.extend(tests);
+        }
+    }
+    untested.sort_by(|a, b| b.lines.cmp(&a.lines).then(a.file.cmp(&b.file)));
+
+    let directories: Vec<DirectoryStats> = directories.into_values()
+        .map(|(mut stats, tests, coverage)| {
+            stats.test_files = tests.len();
+            stats.tested_percent = percent(stats.tested_files, stats.source_files);
+            stats.line_coverage = coverage.percent();
+            stats
+        })
+        .collect();
+
+    let skipped: Vec<Vec<SkippedTest>> = FileUtils::process_files_parallel(
+        &test_files,
+        |path| find_skipped(path, &target.root),
+        "Checking tests",
+        quiet
+    )?;
+    let mut skipped: Vec<SkippedTest> = skipped.into_iter().flatten().collect();
+    skipped.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+
+    let tested_files = sources.len() - untested.len();
+    let summary = TestsSummary {
+        source_files: sources.len(),
+        test_files: test_files.len(),
+        tested_files,
+        untested_files: untested.len(),
+        skipped_tests: skipped.len(),
+        tested_percent: percent(tested_files, sources.len()),
+        line_coverage: total_coverage.percent(),
+        min_tested_percent: config.min_tested_percent,
+    };
+    let exit_code = check_failure_threshold(summary.tested_percent < config.min_tested_percent, ExitCode::ValidationFailed);
+    let report = TestsReport {
+        untested,
+        skipped,
+        directories,
+        lcov: lcov.map(FileUtils::slash_path),
+        summary,
+    };
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+fn percent(part: usize, total: usize) -> f64 {
+    if total == 0 { 100.0 } else { part as f64 * 100.0 / total as f64 }
+}
+
+/// Patterns containing `/` match the path from the project root, others the file name
+fn parent_dir(relative: &str) -> &str {
+    relative.rsplit_once('/').map_or("", |(dir, _)| dir)
+}
+
+/// `components/Button.tsx` -> `Button`, keeping inner dots (`user.service.ts` -> `user.service`)
+fn stem(file_name: &str) -> &str {
+    file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem)
+}
+
+fn neutral_dir(directory: &str) -> String {
+    directory.split('/')
+        .filter(|segment| !segment.is_empty() && !NEUTRAL_DIRS.contains(segment))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The lower-cased stem and directory of the file a test covers:
+/// `src/__tests__/Button.test.tsx` -> (`button`, `src/__tests__`)
+fn test_subject(relative: &str) -> (String, String) {
+    let file_name = relative.rsplit('/').next().unwrap_or(relative);
+    let name = stem(file_name);
+    let name = [".test", ".spec"].iter().find_map(|suffix| name.strip_suffix(suffix)).unwrap_or(name);
+    (name.to_lowercase(), parent_dir(relative).to_string())
+}
+
+/// Tests covering a source file: same stem, in the same directory after dropping
+/// `src`/`__tests__`/`tests` segments, or in a flat top-level test directory.
+/// `Button/index.tsx` is covered by tests named after its directory.
+fn matching_tests(relative: &str, tests_by_stem: &HashMap<String, Vec<(String, String)>>) -> Vec<String> {
+    let directory = parent_dir(relative);
+    let file_name = relative.rsplit('/').next().unwrap_or(relative);
+    let mut candidates = vec![(stem(file_name).to_lowercase(), neutral_dir(directory))];
+    if stem(file_name) == "index" && !directory.is_empty() {
+        let name = directory.rsplit('/').next().unwrap_or(directory).to_lowercase();
+        candidates = vec![(name.clone(), neutral_dir(directory)), (name, neutral_dir(parent_dir(directory)))];
+    }
+
+    candidates.iter()
+        .filter_map(|(stem, directory)| tests_by_stem.get(stem).map(|tests| (tests, directory)))
+        .flat_map(|(tests, directory)| tests.iter().filter(move |(test_dir, _)| test_dir == directory || test_dir.is_empty()))
+        .map(|(_, test)| test.clone())
+        .collect()
+}
+
+fn find_skipped(path: &Path, root: &Path) -> Result<Vec<SkippedTest>> {
+    let content = fs::read_to_string(path)?;
+    let file = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+    Ok(skip_pattern().captures_iter(&content)
+        .map(|caps| {
+            let call = caps.get(1).unwrap();
+            SkippedTest {
+                file: file.clone(),
+                line: content[..call.start()].matches('\n').count() + 1,
+                call: call.as_str().to_string(),
+                name: caps.get(2).map(|name| name.as_str().to_string()),
+            }
+        })
+        .collect())
+}
+
+/// Lines found (`LF`) and hit (`LH`) per source file (`SF`), keyed by path relative to `root`
+fn parse_lcov(content: &str, root: &Path) -> HashMap<String, LineCounts> {
+    let mut files: HashMap<String, LineCounts> = HashMap::new();
+    let mut current: Option<(String, LineCounts)> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("SF:") {
+            let path = Path::new(path);
+            let relative = FileUtils::slash_path(path.strip_prefix(root).unwrap_or(path));
+            current = Some((relative.trim_start_matches("./").to_string(), LineCounts::default()));
+        } else if let Some((_, counts)) = current.as_mut() {
+            if let Some(found) = line.strip_prefix("LF:") {
+                counts.found = found.parse().unwrap_or(0);
+            } else if let Some(hit) = line.strip_prefix("LH:") {
+                counts.hit = hit.parse().unwrap_or(0);
+            } else if line == "end_of_record" {
+                let (file, counts) = current.take().unwrap();
+                files.entry(file).or_default().add(counts);
+            }
+        }
+    }
+    files
+}
+
+fn print_report(report: &TestsReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🧪 Test Coverage Report".bold().blue());
+        output::line("=======================".blue());
+        output::blank();
+    }
+
+    let coverage = |value: Option<f64>| value.map_or(String::new(), |value| format!("  {:.1}% lines", value));
+
+    if !report.untested.is_empty() {
+        output::line("⚠️  FILES WITHOUT TESTS".bold().yellow());
+        for file in &report.untested {
+            let kind = if file.component { " component" } else { "" };
+            output::line(format!("  {} {}{}", file.file.cyan(), format!("({} lines{})", file.lines, kind).dimmed(), coverage(file.line_coverage).dimmed()));
+        }
+        output::blank();
+    }
+
+    if !report.skipped.is_empty() {
+        output::line("⏭️  SKIPPED TESTS".bold().yellow());
+        for skipped in &report.skipped {
+            let name = skipped.name.as_deref().map_or(String::new(), |name| format!(" \"{}\"", name));
+            output::line(format!("  {}:{}  {}{}", skipped.file, skipped.line, skipped.call.yellow(), name));
+        }
+        output::blank();
+    }
+
+    if !quiet && !report.directories.is_empty() {
+        output::line("📁 BY DIRECTORY".bold());
+        for stats in &report.directories {
+            let tested = format!("{}/{} tested", stats.tested_files, stats.source_files);
+            let percent = format!("{:.0}%", stats.tested_percent);
+            let percent = if stats.tested_percent >= 80.0 { percent.green() } else if stats.tested_percent >= 50.0 { percent.yellow() } else { percent.red() };
+            output::line(format!("  {:<40} {:>14}  {:>4}  {} test files{}", stats.directory, tested, percent, stats.test_files, coverage(stats.line_coverage).dimmed()));
+        }
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Source files: {}  Test files: {}", summary.source_files, summary.test_files));
+    output::line(format!("  Files with tests: {} ({:.1}%)  Without: {}", summary.tested_files, summary.tested_percent, summary.untested_files));
+    output::line(format!("  Skipped tests: {}", summary.skipped_tests));
+    if let Some(lcov) = &report.lcov {
+        match summary.line_coverage {
+            Some(value) => output::line(format!("  Line coverage: {:.1}% (from {})", value, lcov)),
+            None => output::line(format!("  Line coverage: no matching files in {}", lcov)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tests_by_stem(tests: &[&str]) -> HashMap<String, Vec<(String, String)>> {
+        let mut by_stem: HashMap<String, Vec<(String, String)>> = HashMap::new();
+        for test in tests {
+            let (stem, directory) = test_subject(test);
+            by_stem.entry(stem).or_default().push((neutral_dir(&directory), test.to_string()));
+        }
+        by_stem
+    }
+
+    #[test]
+    fn test_pairs_sources_with_tests() {
+        let tests = tests_by_stem(&[
+            "src/components/Button.test.tsx",
+            "src/lib/__tests__/date.spec.ts",
+            "tests/hooks/useCart.test.ts",
+            "test/user.service.test.ts",
+            "src/components/Modal/Modal.test.tsx",
+        ]);
+        assert_eq!(matching_tests("src/components/Button.tsx", &tests), vec!["src/components/Button.test.tsx"]);
+        assert_eq!(matching_tests("src/lib/date.ts", &tests).len(), 1);
+        assert_eq!(matching_tests("src/hooks/useCart.ts", &tests).len(), 1);
+        assert_eq!(matching_tests("src/server/user.service.ts", &tests).len(), 1);
+        assert_eq!(matching_tests("src/components/Modal/index.tsx", &tests).len(), 1);
+        assert!(matching_tests("src/pages/Button.tsx", &tests).is_empty());
+        assert!(matching_tests("src/lib/format.ts", &tests).is_empty());
+    }
+
+    #[test]
+    fn test_skipped_tests_and_lcov() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cart.test.ts");
+        fs::write(&path, "describe.skip('cart', () => {\n  xit('adds items', () => {});\n  it('removes items', () => {});\n  test.skip.each([1])('n', () => {});\n});\n").unwrap();
+        let skipped = find_skipped(&path, dir.path()).unwrap();
+        let calls: Vec<(&str, usize)> = skipped.iter().map(|skipped| (skipped.call.as_str(), skipped.line)).collect();
+        assert_eq!(calls, vec![("describe.skip", 1), ("xit", 2), ("test.skip.each", 4)]);
+        assert_eq!(skipped[1].name.as_deref(), Some("adds items"));
+
+        let lcov = format!("TN:\nSF:{}/src/cart.ts\nLF:40\nLH:30\nend_of_record\nSF:src/util.ts\nLF:0\nLH:0\nend_of_record\n", dir.path().display());
+        let coverage = parse_lcov(&lcov, dir.path());
+        assert_eq!(coverage["src/cart.ts"].percent(), Some(75.0));
+        assert_eq!(coverage["src/util.ts"].percent(), None);
+    }
+}
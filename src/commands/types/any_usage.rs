@@ -0,0 +1,251 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// How `any` appears in the source
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum AnyKind {
+    /// `value: any`
+    Annotation,
+    /// `value as any` / `<any>value`
+    Cast,
+    /// `any[]` / `Array<any>`
+    Array,
+    /// `useState<any>()`, `Record<string, any>`
+    GenericArgument,
+}
+
+impl AnyKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            AnyKind::Annotation => "Type annotations",
+            AnyKind::Cast => "Casts",
+            AnyKind::Array => "Arrays",
+            AnyKind::GenericArgument => "Generic arguments",
+        }
+    }
+}
+
+/// A single `any` usage with a 1-based location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnyUsage {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub kind: AnyKind,
+    pub code: String,
+    pub suggestion: Option<String>,
+}
+
+struct AnyPatterns {
+    any: Regex,
+    unknown: Regex,
+    bare_declaration: Regex,
+    function_params: Regex,
+    use_state: Regex,
+    initializer: Regex,
+    catch_clause: Regex,
+}
+
+static ANY_PATTERNS: OnceLock<AnyPatterns> = OnceLock::new();
+
+fn get_any_patterns() -> &'static AnyPatterns {
+    ANY_PATTERNS.get_or_init(|| AnyPatterns {
+        any: Regex::new(r"\bany\b").unwrap(),
+        unknown: Regex::new(r"\bunknown\b").unwrap(),
+        bare_declaration: Regex::new(r"^\s*(?:export\s+)?(?:let|var)\s+[A-Za-z_$][\w$]*\s*;?\s*$").unwrap(),
+        function_params: Regex::new(r"\bfunction\b\s*\*?\s*[\w$]*\s*(?:<[^>()]*>)?\s*\(([^)]*)\)").unwrap(),
+        use_state: Regex::new(r"useState<\s*any\s*>\(\s*([^)]*?)\s*\)").unwrap(),
+        initializer: Regex::new(r":\s*any\s*=\s*(.+?)\s*;?\s*$").unwrap(),
+        catch_clause: Regex::new(r"catch\s*\(\s*\w+\s*:\s*any\s*\)").unwrap(),
+    })
+}
+
+/// List every `any` usage in `content`, skipping comment lines
+pub fn find_any_usages(content: &str, file: &str) -> Vec<AnyUsage> {
+    let patterns = get_any_patterns();
+    let mut usages = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+            continue;
+        }
+
+        for mat in patterns.any.find_iter(line) {
+            if let Some(kind) = classify(line, mat.start(), mat.end()) {
+                usages.push(AnyUsage {
+                    file: file.to_string(),
+                    line: index + 1,
+                    column: line[..mat.start()].chars().count() + 1,
+                    kind,
+                    code: line.trim().to_string(),
+                    suggestion: suggest_replacement(line, kind, patterns),
+                });
+            }
+        }
+    }
+
+    usages
+}
+
+/// Line numbers of `unknown` in type positions, one per use
+pub fn find_unknown_usages(content: &str) -> Vec<usize> {
+    let patterns = get_any_patterns();
+    code_lines(content)
+        .flat_map(|(line_num, line)| {
+            patterns.unknown.find_iter(line)
+                .filter(|mat| classify(line, mat.start(), mat.end()).is_some())
+                .map(move |_| line_num)
+        })
+        .collect()
+}
+
+/// Line numbers of declarations TypeScript types as implicit `any`, one per declaration:
+/// `let x;` without a type or value, and `function` parameters without a type or default.
+/// Callback parameters are left out, since they usually take their type from the call
+pub fn find_implicit_any(content: &str) -> Vec<usize> {
+    let patterns = get_any_patterns();
+    let mut lines = Vec::new();
+    for (line_num, line) in code_lines(content) {
+        if patterns.bare_declaration.is_match(line) {
+            lines.push(line_num);
+        }
+        for caps in patterns.function_params.captures_iter(line) {
+            let params: Vec<&str> = caps[1].split(',').map(str::trim).collect();
+            // Destructured parameters need a real parser; skip them rather than guess
+            if params.iter().any(|param| param.starts_with(['{', '['])) {
+                continue;
+            }
+            let untyped = params.iter()
+                .filter(|param| !param.is_empty() && !param.contains([':', '=']))
+                .count();
+            lines.extend(std::iter::repeat_n(line_num, untyped));
+        }
+    }
+    lines
+}
+
+/// 1-based numbers and text of the lines that are not comments
+fn code_lines(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    content.lines().enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim_start();
+            !(trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*"))
+        })
+        .map(|(index, line)| (index + 1, line))
+}
+
+/// Decide what kind of usage the `any` at `start..end` is, based on its neighbours.
+/// Returns `None` when `any` is not in a type position (e.g. `array.any`, `"any"`).
+fn classify(line: &str, start: usize, end: usize) -> Option<AnyKind> {
+    let before = line[..start].trim_end();
+    let after = line[end..].trim_start();
+
+    if in_string_literal(&line[..start]) || before.ends_with('.') {
+        return None;
+    }
+
+    if after.starts_with("[]") {
+        return Some(AnyKind::Array);
+    }
+    if before.ends_with("Array<") && after.starts_with('>') {
+        return Some(AnyKind::Array);
+    }
+    if before.ends_with(" as") || before == "as" {
+        return Some(AnyKind::Cast);
+    }
+    if before.ends_with('<') && after.starts_with('>') && !before.trim_end_matches('<').ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+        // `<any>value` — old-style assertion
+        return Some(AnyKind::Cast);
+    }
+    if before.ends_with('<') || (before.ends_with(',') && after.starts_with(['>', ','])) {
+        return Some(AnyKind::GenericArgument);
+    }
+    if before.ends_with(':') || before.ends_with('|') || before.ends_with('&') || before.ends_with("=>") {
+        return Some(AnyKind::Annotation);
+    }
+
+    None
+}
+
+/// Rough check for an unclosed quote before the match
+fn in_string_literal(prefix: &str) -> bool {
+    ['\'', '"', '`'].iter().any(|quote| prefix.matches(*quote).count() % 2 == 1)
+}
+
+/// Suggest a replacement type when it can be read straight off the line
+fn suggest_replacement(line: &str, kind: AnyKind, patterns: &AnyPatterns) -> Option<String> {
+    if let Some(caps) = patterns.use_state.captures(line) {
+        return literal_type(&caps[1]).map(|ty| format!("useState<{}>({})", ty, &caps[1]));
+    }
+
+    if patterns.catch_clause.is_match(line) {
+        return Some("Use `unknown` and narrow with `instanceof Error`".to_string());
+    }
+
+    if kind == AnyKind::Annotation {
+        if let Some(caps) = patterns.initializer.captures(line) {
+            return literal_type(&caps[1]).map(|ty| format!("Annotate as `{}` (or drop the annotation and let it be inferred)", ty));
+        }
+    }
+
+    match kind {
+        AnyKind::Cast => Some("Cast to a specific type, or to `unknown` and narrow".to_string()),
+        AnyKind::Array => Some("Use a typed array such as `string[]` or `unknown[]`".to_string()),
+        _ => None,
+    }
+}
+
+/// Type of a literal initializer, if it is trivially inferable
+fn literal_type(value: &str) -> Option<&'static str> {
+    let value = value.trim();
+    let quoted = |q: char| value.len() >= 2 && value.starts_with(q) && value.ends_with(q);
+
+    if quoted('\'') || quoted('"') || quoted('`') {
+        Some("string")
+    } else if value == "true" || value == "false" {
+        Some("boolean")
+    } else if !value.is_empty() && value.parse::<f64>().is_ok() {
+        Some("number")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_any_kinds_are_classified() {
+        let content = "function f(a: any, b: any[]): Array<any> {\n  const x = a as any;\n  const m: Record<string, any> = {};\n  // a: any in a comment\n  return items.any;\n}\n";
+        let usages = find_any_usages(content, "f.ts");
+        let kinds: Vec<AnyKind> = usages.iter().map(|u| u.kind).collect();
+
+        assert_eq!(kinds, vec![
+            AnyKind::Annotation,
+            AnyKind::Array,
+            AnyKind::Array,
+            AnyKind::Cast,
+            AnyKind::GenericArgument,
+        ]);
+        assert_eq!((usages[0].line, usages[0].column), (1, 15));
+    }
+
+    #[test]
+    fn test_unknown_and_implicit_any_are_counted() {
+        let content = "let pending;\nlet ready = false;\nfunction save(user, options = {}, id: string) {}\nexport async function load(...args) {}\nfunction pick({ a, b }) {}\nitems.map(function (item) { return item; });\nconst data = raw as unknown as User;\nfunction parse(input: unknown): Config {}\n// let commented;\nconst unknownCount = 1;\n";
+
+        assert_eq!(find_implicit_any(content), vec![1, 3, 4, 6]);
+        assert_eq!(find_unknown_usages(content), vec![7, 8]);
+    }
+
+    #[test]
+    fn test_suggestions_for_inferable_literals() {
+        let usages = find_any_usages("const [name, setName] = useState<any>('');\nlet count: any = 0;\n", "a.tsx");
+
+        assert_eq!(usages[0].suggestion.as_deref(), Some("useState<string>('')"));
+        assert!(usages[1].suggestion.as_deref().unwrap().contains("`number`"));
+    }
+}
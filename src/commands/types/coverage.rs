@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use crate::common::matches_any_glob;
+use crate::common::source::code_line_count;
+use super::any_usage::{find_implicit_any, find_unknown_usages, AnyUsage};
+
+/// `any`, `unknown` and implicit `any` counts of one file
+#[derive(Debug, Clone, Default)]
+pub struct FileTypeStats {
+    pub lines: usize,
+    /// Lines with an `any` or an implicit `any`
+    pub untyped_lines: usize,
+    pub any: usize,
+    pub unknown: usize,
+    pub implicit_any: usize,
+}
+
+impl FileTypeStats {
+    pub fn new(content: &str, any_usages: &[AnyUsage]) -> Self {
+        let implicit_any = find_implicit_any(content);
+        let untyped_lines: BTreeSet<usize> = any_usages.iter().map(|usage| usage.line).chain(implicit_any.iter().copied()).collect();
+        Self {
+            lines: code_line_count(content),
+            untyped_lines: untyped_lines.len(),
+            any: any_usages.len(),
+            unknown: find_unknown_usages(content).len(),
+            implicit_any: implicit_any.len(),
+        }
+    }
+}
+
+/// Type coverage of the files directly in one directory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DirectoryCoverage {
+    pub directory: String,
+    pub files: usize,
+    /// Lines of code, without blank lines, comments and imports
+    pub lines: usize,
+    pub any: usize,
+    pub unknown: usize,
+    pub implicit_any: usize,
+    /// `any`, `unknown` and implicit `any` per 1,000 lines
+    pub density: f64,
+    /// Share of lines without an `any` or implicit `any`, in percent
+    pub coverage: f64,
+    /// The highest `[typescript.thresholds]` minimum whose glob matches one of the directory's files
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f64>,
+}
+
+impl DirectoryCoverage {
+    pub fn below_threshold(&self) -> bool {
+        self.threshold.is_some_and(|threshold| self.coverage < threshold)
+    }
+}
+
+/// Group per-file stats by directory. `files` holds each file's path relative to the project
+/// root (matched against the threshold globs), its directory as reported, and its stats
+pub fn by_directory(files: Vec<(String, String, FileTypeStats)>, thresholds: &BTreeMap<String, f64>) -> Vec<DirectoryCoverage> {
+    let mut directories: BTreeMap<String, DirectoryCoverage> = BTreeMap::new();
+    let mut untyped_lines: BTreeMap<String, usize> = BTreeMap::new();
+
+    for (relative, directory, stats) in files {
+        let threshold = thresholds.iter()
+            .filter(|(glob, _)| matches_any_glob(&relative, std::slice::from_ref(*glob)))
+            .map(|(_, threshold)| *threshold)
+            .reduce(f64::max);
+        let entry = directories.entry(directory.clone()).or_insert_with(|| DirectoryCoverage {
+            directory: directory.clone(),
+            files: 0,
+            lines: 0,
+            any: 0,
+            unknown: 0,
+            implicit_any: 0,
+            density: 0.0,
+            coverage: 100.0,
+            threshold: None,
+        });
+        entry.files += 1;
+        entry.lines += stats.lines;
+        entry.any += stats.any;
+        entry.unknown += stats.unknown;
+        entry.implicit_any += stats.implicit_any;
+        entry.threshold = match (entry.threshold, threshold) {
+            (Some(current), Some(threshold)) => Some(current.max(threshold)),
+            (current, threshold) => current.or(threshold),
+        };
+        *untyped_lines.entry(directory).or_default() += stats.untyped_lines;
+    }
+
+    directories.into_values()
+        .map(|mut directory| {
+            if directory.lines > 0 {
+                let untyped = untyped_lines[&directory.directory].min(directory.lines);
+                let uses = directory.any + directory.unknown + directory.implicit_any;
+                directory.density = uses as f64 * 1000.0 / directory.lines as f64;
+                directory.coverage = (directory.lines - untyped) as f64 * 100.0 / directory.lines as f64;
+            }
+            directory
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(lines: usize, untyped_lines: usize) -> FileTypeStats {
+        FileTypeStats { lines, untyped_lines, any: untyped_lines, unknown: 1, implicit_any: 0 }
+    }
+
+    #[test]
+    fn test_directories_are_held_to_their_thresholds() {
+        let thresholds = BTreeMap::from([("src/payments/**".to_string(), 99.0), ("src/**".to_string(), 90.0)]);
+        let files = vec![
+            ("src/payments/charge.ts".to_string(), "src/payments".to_string(), stats(100, 2)),
+            ("src/payments/refund.ts".to_string(), "src/payments".to_string(), stats(100, 0)),
+            ("src/ui/button.ts".to_string(), "src/ui".to_string(), stats(50, 4)),
+            ("scripts/seed.ts".to_string(), "scripts".to_string(), stats(10, 5)),
+        ];
+
+        let directories = by_directory(files, &thresholds);
+        let summary: Vec<(&str, f64, Option<f64>, bool)> = directories.iter()
+            .map(|d| (d.directory.as_str(), d.coverage, d.threshold, d.below_threshold()))
+            .collect();
+
+        assert_eq!(summary, vec![
+            ("scripts", 50.0, None, false),
+            ("src/payments", 99.0, Some(99.0), false),
+            ("src/ui", 92.0, Some(90.0), false),
+        ]);
+        assert_eq!(directories[1].density, 20.0);
+
+        let strict = BTreeMap::from([("src/ui/**".to_string(), 95.0)]);
+        let files = vec![("src/ui/button.ts".to_string(), "src/ui".to_string(), stats(50, 4))];
+        assert!(by_directory(files, &strict)[0].below_threshold());
+    }
+}
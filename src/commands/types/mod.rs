@@ -0,0 +1,813 @@
+use anyhow::Result;
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use crate::utils::FileUtils;
+use crate::commands::history::Metrics;
+use crate::common::ndjson::{self, LevelCounts};
+use crate::config::TypeBudgets;
+use crate::common::{output, AnalysisContext, Annotate, Annotation, AnnotationLevel, FileScanner, paths, ScanTarget, get_common_patterns, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+
+mod any_usage;
+mod coverage;
+mod suppressions;
+mod tsc;
+mod tsconfig;
+pub use any_usage::{AnyKind, AnyUsage};
+pub use coverage::DirectoryCoverage;
+use coverage::FileTypeStats;
+use suppressions::Directive;
+pub use tsc::{TscReport, find_tsc};
+pub use tsconfig::TsConfigAudit;
+
+/// Optional analyses enabled from the command line
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TypesOptions {
+    /// Run the project's TypeScript compiler (`--tsc`)
+    pub tsc: bool,
+    /// List every `any` usage with its location (`--list-any`)
+    pub list_any: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeScriptReport {
+    pub issues: Vec<TypeIssue>,
+    pub summary: TypeSummary,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tsc: Option<TscReport>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub any_usages: Option<Vec<AnyUsage>>,
+    /// `any`, `unknown` and implicit `any` density of each directory with TypeScript files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub directories: Vec<DirectoryCoverage>,
+    /// Each `[typescript.budgets]` entry that is set, with the count it applies to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub budgets: Vec<TypeBudgetResult>,
+    /// Strictness, Next.js conflicts and coverage of the project's `tsconfig.json`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tsconfig: Option<TsConfigAudit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeBudgetResult {
+    /// The budget's key, e.g. `non_null_assertions`
+    pub name: String,
+    pub budget: usize,
+    pub count: usize,
+    pub exceeded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeIssue {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub issue_type: IssueType,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum IssueType {
+    AnyUsage,
+    MissingReturnType,
+    UntypedParameter,
+    TSIgnore,
+    TSExpectError,
+    TSNoCheck,
+    NonNullAssertion,
+    ImplicitAny,
+}
+
+impl IssueType {
+    /// The stable id of this kind of issue, see `rule_catalog`
+    pub fn rule_id(&self) -> &'static str {
+        match self {
+            IssueType::AnyUsage => "SNF-TYPES-ANY",
+            IssueType::MissingReturnType => "SNF-TYPES-RETURN",
+            IssueType::UntypedParameter => "SNF-TYPES-PARAM",
+            IssueType::TSIgnore => "SNF-TYPES-TS-IGNORE",
+            IssueType::TSExpectError => "SNF-TYPES-TS-EXPECT-ERROR",
+            IssueType::TSNoCheck => "SNF-TYPES-TS-NOCHECK",
+            IssueType::NonNullAssertion => "SNF-TYPES-NON-NULL",
+            IssueType::ImplicitAny => "SNF-TYPES-IMPLICIT-ANY",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeSummary {
+    pub files_scanned: usize,
+    pub total_issues: usize,
+    pub any_usage_count: usize,
+    pub missing_return_types: usize,
+    pub untyped_parameters: usize,
+    /// `@ts-ignore` and undescribed `@ts-expect-error` comments
+    pub ts_ignore_count: usize,
+    /// The undescribed `@ts-expect-error` comments among `ts_ignore_count`
+    #[serde(default)]
+    pub ts_expect_error_count: usize,
+    #[serde(default)]
+    pub ts_nocheck_count: usize,
+    #[serde(default)]
+    pub non_null_assertions: usize,
+    pub type_coverage_score: f64,
+}
+
+pub async fn run(target: &ScanTarget, options: TypesOptions, json: bool, quiet: bool) -> Result<CommandOutcome<TypeScriptReport>> {
+    tracing::info!("Running TypeScript analysis");
+    
+    let start_time = std::time::Instant::now();
+    let outcome = check(target, options, quiet || json)?;
+    let report = &outcome.report;
+    
+    let compiler_errors = report.tsc.as_ref().map_or(0, |tsc| tsc.total_errors);
+    let tsconfig_issues = report.tsconfig.as_ref().map_or(0, TsConfigAudit::total_issues);
+    let response = create_annotated_json_output(
+        "types",
+        report,
+        report.summary.files_scanned,
+        report.summary.total_issues + compiler_errors + tsconfig_issues,
+        Some(start_time.elapsed().as_millis() as u64),
+    );
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+    
+    Ok(outcome)
+}
+
+/// Analyze TypeScript files and decide the exit status, without printing a report.
+///
+/// With `options.tsc`, the project's TypeScript compiler is run as well and any compiler
+/// error fails the check.
+pub fn check(target: &ScanTarget, options: TypesOptions, quiet: bool) -> Result<CommandOutcome<TypeScriptReport>> {
+    let mut report = analyze_typescript_files(target, options.list_any, quiet)?;
+
+    if options.tsc {
+        let project_dir = target.project_dir("types --tsc")?;
+        tracing::info!("Running the TypeScript compiler");
+        match tsc::run_tsc(project_dir, &target.ignores()?) {
+            Ok(tsc_report) => report.tsc = Some(tsc_report),
+            Err(e) => tracing::warn!("Skipping tsc: {}", e),
+        }
+    }
+    
+    let tsc_errors = report.tsc.as_ref().map_or(0, |tsc| tsc.total_errors);
+    let budgets = &target.config()?.typescript.budgets;
+    report.budgets = evaluate_budgets(budgets, &report.summary);
+    let has_critical_issues = any_fails(target, report.summary.any_usage_count, &report.directories)?
+        || suppressions_fail(budgets, &report.summary, &report.budgets)
+        || tsc_errors > 0;
+    let exit_code = check_failure_threshold(has_critical_issues, ExitCode::ValidationFailed);
+    
+    Ok(CommandOutcome::new(report, exit_code))
+}
+
+/// `--format ndjson`: print each file's findings as soon as it is analyzed, then the
+/// compiler's with `options.tsc`. Only the counts the exit status depends on are kept
+pub fn stream(target: &ScanTarget, options: TypesOptions, counts: &Mutex<LevelCounts>) -> Result<ExitCode> {
+    let files = FileScanner::for_command(target, "types")?.find_target_files(target, &["ts", "tsx"]);
+    let ignores = target.ignores()?;
+    let totals = Mutex::new(create_summary(0, &[]));
+    let file_stats = Mutex::new(Vec::new());
+    ndjson::stream_files(&files, counts, |path| {
+        let (mut issues, _, stats) = analyze_file_optimized(&target.context, path, false)?;
+        ignores.retain(&mut issues, |issue| (issue.issue_type.rule_id(), &issue.file));
+        let summary = create_summary(1, &issues);
+        add_counts(&mut totals.lock().unwrap_or_else(|e| e.into_inner()), &summary);
+        file_stats.lock().unwrap_or_else(|e| e.into_inner()).push(file_coverage(target, path, stats));
+        Ok(TypeScriptReport { issues, summary, tsc: None, any_usages: None, directories: Vec::new(), budgets: Vec::new(), tsconfig: None }.annotations())
+    })?;
+
+    let totals = totals.into_inner().unwrap_or_else(|e| e.into_inner());
+    let budgets = &target.config()?.typescript.budgets;
+    let budget_results = evaluate_budgets(budgets, &totals);
+    let suppressions_failed = suppressions_fail(budgets, &totals, &budget_results);
+    let directories = directory_coverage(target, file_stats.into_inner().unwrap_or_else(|e| e.into_inner()))?;
+    let below_threshold: Vec<DirectoryCoverage> = directories.into_iter().filter(DirectoryCoverage::below_threshold).collect();
+    let any_failed = any_fails(target, totals.any_usage_count, &below_threshold)?;
+    let report = TypeScriptReport { issues: Vec::new(), summary: create_summary(0, &[]), tsc: None, any_usages: None, directories: below_threshold, budgets: budget_results, tsconfig: audit_tsconfig(target, &files)? };
+    ndjson::print(&report.findings(), counts)?;
+
+    let mut tsc_errors = 0;
+    if options.tsc {
+        match tsc::run_tsc(target.project_dir("types --tsc")?, &ignores) {
+            Ok(tsc_report) => {
+                tsc_errors = tsc_report.total_errors;
+                let report = TypeScriptReport { issues: Vec::new(), summary: create_summary(0, &[]), tsc: Some(tsc_report), any_usages: None, directories: Vec::new(), budgets: Vec::new(), tsconfig: None };
+                ndjson::print(&report.findings(), counts)?;
+            }
+            Err(e) => tracing::warn!("Skipping tsc: {}", e),
+        }
+    }
+
+    let has_critical_issues = any_failed || suppressions_failed || tsc_errors > 0;
+    Ok(check_failure_threshold(has_critical_issues, ExitCode::ValidationFailed))
+}
+
+/// Without `[typescript.thresholds]` any `any` fails the check; with them, only directories
+/// below their minimum coverage do
+fn any_fails(target: &ScanTarget, any_usage_count: usize, directories: &[DirectoryCoverage]) -> Result<bool> {
+    Ok(if target.config()?.typescript.thresholds.is_empty() {
+        any_usage_count > 0
+    } else {
+        directories.iter().any(DirectoryCoverage::below_threshold)
+    })
+}
+
+/// The audit of the `tsconfig.json` next to the config, if there is one
+fn audit_tsconfig(target: &ScanTarget, files: &[PathBuf]) -> Result<Option<TsConfigAudit>> {
+    let project_dir = target.config_dir();
+    if !project_dir.join("tsconfig.json").is_file() {
+        return Ok(None);
+    }
+    match tsconfig::audit(project_dir, files) {
+        Ok(mut audit) => {
+            let ignores = target.ignores()?;
+            ignores.retain(&mut audit.excluded_files, |file| ("SNF-TYPES-TSCONFIG-EXCLUDED", file));
+            if ignores.ignores("SNF-TYPES-TSCONFIG-STRICT", &audit.config) {
+                audit.flags_off.clear();
+            }
+            if ignores.ignores("SNF-TYPES-TSCONFIG-NEXT", &audit.config) {
+                audit.next_conflicts.clear();
+            }
+            Ok(Some(audit))
+        }
+        Err(e) => {
+            tracing::warn!("Skipping the tsconfig audit: {:#}", e);
+            Ok(None)
+        }
+    }
+}
+
+/// The budgets that are set, against the counts of `summary`
+fn evaluate_budgets(budgets: &TypeBudgets, summary: &TypeSummary) -> Vec<TypeBudgetResult> {
+    [
+        ("ts_ignore", budgets.ts_ignore, summary.ts_ignore_count - summary.ts_expect_error_count),
+        ("ts_nocheck", budgets.ts_nocheck, summary.ts_nocheck_count),
+        ("ts_expect_error", budgets.ts_expect_error, summary.ts_expect_error_count),
+        ("non_null_assertions", budgets.non_null_assertions, summary.non_null_assertions),
+    ]
+    .into_iter()
+    .filter_map(|(name, budget, count)| {
+        budget.map(|budget| TypeBudgetResult { name: name.to_string(), budget, count, exceeded: count > budget })
+    })
+    .collect()
+}
+
+/// Any exceeded budget fails the check; without a budget for either, more than 5
+/// `@ts-ignore` and `@ts-expect-error` comments together do
+fn suppressions_fail(budgets: &TypeBudgets, summary: &TypeSummary, results: &[TypeBudgetResult]) -> bool {
+    let default_limit = budgets.ts_ignore.is_none() && budgets.ts_expect_error.is_none() && summary.ts_ignore_count > 5;
+    default_limit || results.iter().any(|result| result.exceeded)
+}
+
+/// Add the counts of one file's summary to the running `totals` of a streamed run
+fn add_counts(totals: &mut TypeSummary, file: &TypeSummary) {
+    totals.files_scanned += file.files_scanned;
+    totals.total_issues += file.total_issues;
+    totals.any_usage_count += file.any_usage_count;
+    totals.missing_return_types += file.missing_return_types;
+    totals.untyped_parameters += file.untyped_parameters;
+    totals.ts_ignore_count += file.ts_ignore_count;
+    totals.ts_expect_error_count += file.ts_expect_error_count;
+    totals.ts_nocheck_count += file.ts_nocheck_count;
+    totals.non_null_assertions += file.non_null_assertions;
+}
+
+/// A file's path relative to the project root, for the threshold globs, and its directory
+fn file_coverage(target: &ScanTarget, path: &Path, stats: FileTypeStats) -> (String, String, FileTypeStats) {
+    let relative = FileUtils::slash_path(path.strip_prefix(&target.root).unwrap_or(path));
+    let directory = path.parent().map_or_else(|| ".".to_string(), paths::display);
+    (relative, directory, stats)
+}
+
+fn directory_coverage(target: &ScanTarget, files: Vec<(String, String, FileTypeStats)>) -> Result<Vec<DirectoryCoverage>> {
+    let mut directories = coverage::by_directory(files, &target.config()?.typescript.thresholds);
+    target.ignores()?.retain(&mut directories, |directory| ("SNF-TYPES-COVERAGE", &directory.directory));
+    Ok(directories)
+}
+
+fn analyze_typescript_files(target: &ScanTarget, list_any: bool, quiet: bool) -> Result<TypeScriptReport> {
+    let scanner = FileScanner::for_command(target, "types")?;
+    let files = scanner.find_target_files(target, &["ts", "tsx"]);
+    let files_count = files.len();
+    
+    let results = FileUtils::process_files_parallel(
+        &files,
+        |path| {
+            let (issues, any_usages, stats) = analyze_file_optimized(&target.context, path, list_any)?;
+            Ok((issues, any_usages, file_coverage(target, path, stats)))
+        },
+        "Analyzing TypeScript files",
+        quiet
+    )?;
+
+    let mut all_issues = Vec::new();
+    let mut all_any_usages = Vec::new();
+    let mut file_stats = Vec::new();
+    for (issues, any_usages, stats) in results {
+        all_issues.push(issues);
+        all_any_usages.push(any_usages);
+        file_stats.push(stats);
+    }
+    let mut issues: Vec<TypeIssue> = all_issues.into_iter().flatten().collect();
+    let ignores = target.ignores()?;
+    ignores.retain(&mut issues, |issue| (issue.issue_type.rule_id(), &issue.file));
+    let summary = create_summary(files_count, &issues);
+    let any_usages = list_any.then(|| {
+        let mut any_usages: Vec<AnyUsage> = all_any_usages.into_iter().flatten().collect();
+        ignores.retain(&mut any_usages, |usage| ("SNF-TYPES-ANY", &usage.file));
+        any_usages
+    });
+    
+    let directories = directory_coverage(target, file_stats)?;
+
+    let tsconfig = audit_tsconfig(target, &files)?;
+
+    Ok(TypeScriptReport { issues, summary, tsc: None, any_usages, directories, budgets: Vec::new(), tsconfig })
+}
+
+
+/// The findings for one file's `content`, such as an editor buffer
+pub fn check_source(path: &Path, content: &str) -> TypeScriptReport {
+    let (issues, _, _) = analyze_source(path, content, false);
+    let summary = create_summary(1, &issues);
+    TypeScriptReport { issues, summary, tsc: None, any_usages: None, directories: Vec::new(), budgets: Vec::new(), tsconfig: None }
+}
+
+fn analyze_file_optimized(context: &AnalysisContext, path: &Path, list_any: bool) -> Result<(Vec<TypeIssue>, Vec<AnyUsage>, FileTypeStats)> {
+    let content = context.read(path)?;
+    Ok(analyze_source(path, &content, list_any))
+}
+
+fn analyze_source(path: &Path, content: &str, list_any: bool) -> (Vec<TypeIssue>, Vec<AnyUsage>, FileTypeStats) {
+    let mut issues = Vec::new();
+    let patterns = get_common_patterns();
+    let file_path = paths::display(path);
+    let mut any_usages = any_usage::find_any_usages(content, &file_path);
+    let stats = FileTypeStats::new(content, &any_usages);
+    if !list_any {
+        any_usages.clear();
+    }
+    
+    for (line_num, line) in content.lines().enumerate() {
+        let line_num = line_num + 1;
+        let trimmed = line.trim();
+        let is_comment = trimmed.starts_with("//")
+            || trimmed.starts_with('*')
+            || trimmed.starts_with("/*");
+
+        // Directives are always inside comments — check unconditionally
+        if let Some(directive) = suppressions::find_directive(line) {
+            let (issue_type, message, suggestion) = match directive {
+                Directive::Ignore => (IssueType::TSIgnore, "@ts-ignore comment found", "Fix the underlying type error instead of suppressing it"),
+                Directive::UndescribedExpectError => (IssueType::TSExpectError, "@ts-expect-error comment without a description", "Say why the error is expected, e.g. `// @ts-expect-error: <reason>`, or fix it"),
+                Directive::NoCheck => (IssueType::TSNoCheck, "@ts-nocheck turns off type checking for the whole file", "Remove it and fix or suppress the individual errors"),
+            };
+            issues.push(TypeIssue {
+                file: file_path.clone(),
+                line: line_num,
+                column: 0,
+                issue_type,
+                message: message.to_string(),
+                suggestion: Some(suggestion.to_string()),
+            });
+        }
+
+        // Skip comment lines for code-level checks
+        if is_comment {
+            continue;
+        }
+
+        // Check for 'any' usage in actual code
+        for mat in patterns.any_type.find_iter(line) {
+            issues.push(TypeIssue {
+                file: file_path.clone(),
+                line: line_num,
+                column: mat.start(),
+                issue_type: IssueType::AnyUsage,
+                message: "Usage of 'any' type detected".to_string(),
+                suggestion: Some("Replace with a specific type or 'unknown'".to_string()),
+            });
+        }
+    }
+
+    for (line, column) in suppressions::find_non_null_assertions(content) {
+        issues.push(TypeIssue {
+            file: file_path.clone(),
+            line,
+            column,
+            issue_type: IssueType::NonNullAssertion,
+            message: "Non-null assertion `!` found".to_string(),
+            suggestion: Some("Handle the null case, e.g. with a check or `?.`, instead of asserting it away".to_string()),
+        });
+    }
+    
+    (issues, any_usages, stats)
+}
+
+fn create_summary(files_scanned: usize, issues: &[TypeIssue]) -> TypeSummary {
+    let mut any_usage_count = 0;
+    let mut missing_return_types = 0;
+    let mut untyped_parameters = 0;
+    let mut ts_ignore_count = 0;
+    let mut ts_expect_error_count = 0;
+    let mut ts_nocheck_count = 0;
+    let mut non_null_assertions = 0;
+
+    for issue in issues {
+        match issue.issue_type {
+            IssueType::AnyUsage => any_usage_count += 1,
+            IssueType::MissingReturnType => missing_return_types += 1,
+            IssueType::UntypedParameter => untyped_parameters += 1,
+            IssueType::TSIgnore => ts_ignore_count += 1,
+            IssueType::TSExpectError => {
+                ts_ignore_count += 1;
+                ts_expect_error_count += 1;
+            }
+            IssueType::TSNoCheck => ts_nocheck_count += 1,
+            IssueType::NonNullAssertion => non_null_assertions += 1,
+            _ => {}
+        }
+    }
+
+    // Percentage of files with zero 'any' usage — an honest, directly measurable metric
+    let files_with_any: std::collections::HashSet<&str> = issues.iter()
+        .filter(|i| matches!(i.issue_type, IssueType::AnyUsage))
+        .map(|i| i.file.as_str())
+        .collect();
+    let any_free_score = if files_scanned > 0 {
+        ((files_scanned - files_with_any.len()) as f64 / files_scanned as f64) * 100.0
+    } else {
+        100.0
+    };
+
+    TypeSummary {
+        files_scanned,
+        total_issues: issues.len(),
+        any_usage_count,
+        missing_return_types,
+        untyped_parameters,
+        ts_ignore_count,
+        ts_expect_error_count,
+        ts_nocheck_count,
+        non_null_assertions,
+        type_coverage_score: any_free_score,
+    }
+}
+
+impl Metrics for TypeScriptReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("any_usages", self.summary.any_usage_count as f64),
+            ("type_coverage", self.summary.type_coverage_score),
+        ]
+    }
+}
+
+impl Annotate for TypeScriptReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        let issues = self.issues.iter().map(|issue| {
+            let mut message = issue.message.clone();
+            if let Some(suggestion) = &issue.suggestion {
+                message.push_str(&format!(". {}", suggestion));
+            }
+            Annotation::new(AnnotationLevel::Warning, &issue.file, issue.line, format!("{:?}", issue.issue_type), message)
+                .with_rule(issue.issue_type.rule_id())
+        });
+        let diagnostics = self.tsc.iter().flat_map(|tsc| &tsc.diagnostics).map(|diagnostic| {
+            Annotation::new(AnnotationLevel::Error, &diagnostic.file, diagnostic.line, &diagnostic.code, &diagnostic.message)
+                .with_rule("SNF-TYPES-TSC")
+        });
+        let directories = self.directories.iter().filter(|directory| directory.below_threshold()).map(|directory| {
+            let threshold = directory.threshold.unwrap_or_default();
+            Annotation::file(
+                AnnotationLevel::Error,
+                &directory.directory,
+                "Type coverage below threshold",
+                format!("{:.1}% of lines are free of 'any' (minimum {}%): {} any, {} implicit any", directory.coverage, threshold, directory.any, directory.implicit_any),
+            ).with_rule("SNF-TYPES-COVERAGE")
+        });
+        let budgets = self.budgets.iter().filter(|budget| budget.exceeded).map(|budget| {
+            Annotation::project(
+                AnnotationLevel::Error,
+                "Type safety budget exceeded",
+                format!("{} {} (budget {})", budget.count, budget.name, budget.budget),
+            ).with_rule("SNF-TYPES-BUDGET")
+        });
+        let tsconfig = self.tsconfig.iter().flat_map(|audit| {
+            let flags = audit.flags_off.iter().map(|flag| {
+                Annotation::file(AnnotationLevel::Warning, &audit.config, "Strictness flag off", format!("`{}` is not enabled", flag))
+                    .with_rule("SNF-TYPES-TSCONFIG-STRICT")
+            });
+            let conflicts = audit.next_conflicts.iter().map(|conflict| {
+                Annotation::file(
+                    AnnotationLevel::Warning,
+                    &audit.config,
+                    "Conflicts with Next.js",
+                    format!("`{}` is {}; Next.js expects {}", conflict.option, conflict.value, conflict.recommended),
+                ).with_rule("SNF-TYPES-TSCONFIG-NEXT")
+            });
+            let excluded = audit.excluded_files.iter().map(|file| {
+                Annotation::file(AnnotationLevel::Warning, file, "Not type checked", format!("{} does not include this file", audit.config))
+                    .with_rule("SNF-TYPES-TSCONFIG-EXCLUDED")
+            });
+            flags.chain(conflicts).chain(excluded).collect::<Vec<_>>()
+        });
+        diagnostics.chain(budgets).chain(directories).chain(tsconfig).chain(issues).collect()
+    }
+}
+
+fn print_report(report: &TypeScriptReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("📊 TypeScript Quality Report".bold().blue());
+        output::line("===========================".blue());
+        output::blank();
+    }
+    
+    if let Some(tsc) = &report.tsc {
+        print_tsc_report(tsc);
+    }
+
+    if let Some(any_usages) = &report.any_usages {
+        print_any_usages(any_usages);
+    }
+
+    print_directories(&report.directories);
+
+    if let Some(tsconfig) = &report.tsconfig {
+        print_tsconfig(tsconfig);
+    }
+
+    let tsc_errors = report.tsc.as_ref().map_or(0, |tsc| tsc.total_errors);
+    if report.summary.total_issues == 0 && tsc_errors == 0 {
+        output::line("✅ Excellent TypeScript quality! No issues found.".green());
+        return;
+    }
+    
+    // Group issues by type
+    let mut issues_by_type: HashMap<String, Vec<&TypeIssue>> = HashMap::new();
+    
+    for issue in &report.issues {
+        let type_key = match issue.issue_type {
+            IssueType::AnyUsage => "🚫 'any' Type Usage",
+            IssueType::MissingReturnType => "📝 Missing Return Types",
+            IssueType::UntypedParameter => "❓ Untyped Parameters",
+            IssueType::TSIgnore => "⚠️ @ts-ignore Comments",
+            IssueType::TSExpectError => "⚠️ @ts-expect-error Comments",
+            IssueType::TSNoCheck => "⚠️ @ts-nocheck Comments",
+            IssueType::NonNullAssertion => "❗ Non-null Assertions",
+            IssueType::ImplicitAny => "🔄 Implicit Any",
+        };
+        
+        issues_by_type.entry(type_key.to_string()).or_default().push(issue);
+    }
+    
+    // Print critical issues first (any usage)
+    if let Some(any_issues) = issues_by_type.get("🚫 'any' Type Usage") {
+        output::line("🚫 'ANY' TYPE USAGE (CRITICAL)".bold().red());
+        output::line("─────────────────────────────".red());
+        for issue in any_issues.iter().take(10) { // Show first 10
+            print_issue(issue, "red");
+        }
+        if any_issues.len() > 10 {
+            output::line(format!("  {} {} more 'any' usages...", "...and".dimmed(), (any_issues.len() - 10).to_string().red()));
+        }
+        output::blank();
+    }
+    
+    // Print other issues
+    for (type_name, issues) in &issues_by_type {
+        if type_name.contains("'any'") {
+            continue; // Already printed
+        }
+        
+        let color = if type_name.contains("@ts-") { "yellow" } else { "cyan" };
+        
+        output::line(type_name.bold());
+        output::line("─".repeat(type_name.len()));
+        
+        for issue in issues.iter().take(5) { // Show first 5 of each type
+            print_issue(issue, color);
+        }
+        
+        if issues.len() > 5 {
+            output::line(format!("  {} {} more issues...", "...and".dimmed(), issues.len() - 5));
+        }
+        output::blank();
+    }
+    
+    // Print summary
+    print_summary(&report.summary);
+    print_budgets(&report.budgets);
+}
+
+/// Full `any` listing for `--list-any`, grouped by kind and not truncated
+fn print_any_usages(any_usages: &[AnyUsage]) {
+    output::line(format!("🔎 ALL 'ANY' USAGES ({})", any_usages.len()).bold().red());
+    output::line("─────────────────────".red());
+
+    if any_usages.is_empty() {
+        output::line(format!("  {}", "✅ No 'any' usages found".green()));
+        output::blank();
+        return;
+    }
+
+    let mut by_kind: BTreeMap<AnyKind, Vec<&AnyUsage>> = BTreeMap::new();
+    for usage in any_usages {
+        by_kind.entry(usage.kind).or_default().push(usage);
+    }
+
+    for (kind, usages) in by_kind {
+        output::line(format!("  {} ({})", kind.label().bold(), usages.len()));
+        for usage in usages {
+            output::line(format!("    {}:{}:{}  {}", usage.file.red(), usage.line, usage.column, usage.code.dimmed()));
+            if let Some(suggestion) = &usage.suggestion {
+                output::line(format!("      💡 {}", suggestion));
+            }
+        }
+    }
+    output::blank();
+}
+
+fn print_tsconfig(audit: &TsConfigAudit) {
+    if audit.total_issues() == 0 {
+        return;
+    }
+    output::line(format!("⚙️  TSCONFIG ({})", audit.config).bold().yellow());
+    output::line("─────────".yellow());
+    if !audit.extends.is_empty() {
+        output::line(format!("  {} {}", "Extends:".dimmed(), audit.extends.join(" → ").dimmed()));
+    }
+    if !audit.flags_off.is_empty() {
+        output::line(format!("  {} {}", "Strictness flags off:".yellow(), audit.flags_off.join(", ")));
+    }
+    for conflict in &audit.next_conflicts {
+        output::line(format!("  {} `{}` is {}, expected {}", "Next.js:".yellow(), conflict.option, conflict.value, conflict.recommended));
+    }
+    if !audit.excluded_files.is_empty() {
+        output::line(format!("  {} ({})", "Not type checked:".yellow(), audit.excluded_files.len()));
+        for file in audit.excluded_files.iter().take(10) {
+            output::line(format!("    {}", file));
+        }
+        if audit.excluded_files.len() > 10 {
+            output::line(format!("    {} {} more files...", "...and".dimmed(), audit.excluded_files.len() - 10));
+        }
+    }
+    output::blank();
+}
+
+/// Directories below their threshold, then the least covered ones
+fn print_directories(directories: &[DirectoryCoverage]) {
+    let mut shown: Vec<&DirectoryCoverage> = directories.iter().filter(|directory| directory.coverage < 100.0 || directory.below_threshold()).collect();
+    if shown.is_empty() {
+        return;
+    }
+    shown.sort_by(|a, b| b.below_threshold().cmp(&a.below_threshold()).then(a.coverage.total_cmp(&b.coverage)));
+
+    output::line("📁 TYPE COVERAGE BY DIRECTORY".bold().cyan());
+    output::line("────────────────────────────".cyan());
+    let failing = shown.iter().filter(|directory| directory.below_threshold()).count();
+    for directory in shown.iter().take(failing.max(5)) {
+        let threshold = directory.threshold.map_or_else(String::new, |threshold| format!(" (minimum {}%)", threshold));
+        let line = format!("  {:>6.1}%{}  {}  {:.1} any/unknown per 1k lines", directory.coverage, threshold, directory.directory, directory.density);
+        if directory.below_threshold() {
+            output::line(line.red());
+        } else {
+            output::line(line);
+        }
+    }
+    if shown.len() > failing.max(5) {
+        output::line(format!("  {} {} more directories...", "...and".dimmed(), shown.len() - failing.max(5)));
+    }
+    output::blank();
+}
+
+fn print_tsc_report(tsc: &TscReport) {
+    output::line("🧪 TYPESCRIPT COMPILER".bold().magenta());
+    output::line("─────────────────────".magenta());
+
+    if tsc.total_errors == 0 {
+        output::line(format!("  {}", "✅ tsc --noEmit reported no errors".green()));
+    } else {
+        output::line(format!("  {} {} in {} files",
+            "Compiler errors:".red(),
+            tsc.total_errors.to_string().red().bold(),
+            tsc.errors_by_file.len()
+        ));
+        for file in tsc.errors_by_file.iter().take(10) {
+            output::line(format!("  {} - {} errors", file.file.red(), file.errors));
+        }
+        if tsc.errors_by_file.len() > 10 {
+            output::line(format!("  {} {} more files...", "...and".dimmed(), tsc.errors_by_file.len() - 10));
+        }
+        for diagnostic in tsc.diagnostics.iter().take(5) {
+            output::line(format!("    {}:{}:{} {} {}",
+                diagnostic.file, diagnostic.line, diagnostic.column,
+                diagnostic.code.dimmed(), diagnostic.message
+            ));
+        }
+    }
+
+    if !tsc.strict_gaps.is_empty() {
+        output::line(format!("  {} {}", "Strict mode gaps:".yellow(), tsc.strict_gaps.join(", ").yellow()));
+    }
+
+    if let Some(coverage) = &tsc.coverage {
+        let coverage_str = format!("{:.1}%", coverage.coverage_percent);
+        let coverage_colored = if coverage.coverage_percent >= 95.0 {
+            coverage_str.green()
+        } else if coverage.coverage_percent >= 85.0 {
+            coverage_str.yellow()
+        } else {
+            coverage_str.red()
+        };
+        output::line(format!("  Type coverage: {} ({} of {} identifiers typed as any)",
+            coverage_colored, coverage.any_identifiers, coverage.total_identifiers
+        ));
+    }
+
+    for note in &tsc.notes {
+        output::line(format!("  {}", note.dimmed()));
+    }
+    output::blank();
+}
+
+fn print_issue(issue: &TypeIssue, color: &str) {
+    let file_colored = match color {
+        "red" => issue.file.red(),
+        "yellow" => issue.file.yellow(),
+        "cyan" => issue.file.cyan(),
+        _ => issue.file.normal(),
+    };
+    
+    output::line(format!("  {}:{} - {}", file_colored, issue.line, issue.message));
+    
+    if let Some(suggestion) = &issue.suggestion {
+        output::line(format!("    💡 {}", suggestion.dimmed()));
+    }
+}
+
+fn print_budgets(budgets: &[TypeBudgetResult]) {
+    if budgets.is_empty() {
+        return;
+    }
+    output::blank();
+    output::line("🎯 BUDGETS".bold().white());
+    for budget in budgets {
+        let line = format!("  {}: {} / {}", budget.name, budget.count, budget.budget);
+        if budget.exceeded {
+            output::line(format!("{} {}", line.red(), "(exceeded)".red().bold()));
+        } else {
+            output::line(line.green());
+        }
+    }
+}
+
+fn print_summary(summary: &TypeSummary) {
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    output::line(format!("  Files scanned: {}", summary.files_scanned));
+    output::line(format!("  Total issues: {}", summary.total_issues));
+    
+    if summary.any_usage_count > 0 {
+        output::line(format!("  {} {}", "'any' usage:".red(), summary.any_usage_count.to_string().red()));
+    }
+    if summary.missing_return_types > 0 {
+        output::line(format!("  {} {}", "Missing return types:".yellow(), summary.missing_return_types.to_string().yellow()));
+    }
+    if summary.ts_ignore_count > 0 {
+        output::line(format!("  {} {}", "TS suppressions:".cyan(), summary.ts_ignore_count.to_string().cyan()));
+    }
+    if summary.ts_nocheck_count > 0 {
+        output::line(format!("  {} {}", "@ts-nocheck files:".cyan(), summary.ts_nocheck_count.to_string().cyan()));
+    }
+    if summary.non_null_assertions > 0 {
+        output::line(format!("  {} {}", "Non-null assertions:".cyan(), summary.non_null_assertions.to_string().cyan()));
+    }
+    
+    output::blank();
+    
+    // Type coverage score with color
+    let coverage_str = format!("{:.1}%", summary.type_coverage_score);
+    let coverage_colored = if summary.type_coverage_score >= 90.0 {
+        coverage_str.green()
+    } else if summary.type_coverage_score >= 70.0 {
+        coverage_str.yellow()
+    } else {
+        coverage_str.red()
+    };
+    
+    output::line(format!("  any-free files: {}", coverage_colored));
+    output::blank();
+    
+    if summary.any_usage_count > 0 {
+        output::line("🚫 CRITICAL: Usage of 'any' type is strictly forbidden!".red().bold());
+        output::line("   All 'any' types must be replaced with specific types.".dimmed());
+    }
+    
+    output::line("💡 TIP: Enable strict mode in tsconfig.json for better type safety".dimmed());
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+use regex::Regex;
+use std::sync::OnceLock;
+use crate::common::source::{blank_strings, strip_comments};
+
+/// A comment directive that switches type checking off
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directive {
+    /// `@ts-ignore`
+    Ignore,
+    /// `@ts-nocheck`, for the whole file
+    NoCheck,
+    /// `@ts-expect-error` with no reason after it
+    UndescribedExpectError,
+}
+
+struct SuppressionPatterns {
+    directive: Regex,
+    non_null: Regex,
+}
+
+static SUPPRESSION_PATTERNS: OnceLock<SuppressionPatterns> = OnceLock::new();
+
+fn get_patterns() -> &'static SuppressionPatterns {
+    SUPPRESSION_PATTERNS.get_or_init(|| SuppressionPatterns {
+        directive: Regex::new(r"@ts-(ignore|nocheck|expect-error)\b(.*)").unwrap(),
+        // `value!.field`, `list[0]!`, `call()!;`, but not `!=` or `!==`
+        non_null: Regex::new(r"[\w$)\]]!(?:[.\[),;:}]|\s*$)").unwrap(),
+    })
+}
+
+/// The directive on a line, if any. `@ts-expect-error` counts only without a description:
+/// `// @ts-expect-error: library typings are wrong` documents why the error is expected
+pub fn find_directive(line: &str) -> Option<Directive> {
+    let captures = get_patterns().directive.captures(line)?;
+    match &captures[1] {
+        "ignore" => Some(Directive::Ignore),
+        "nocheck" => Some(Directive::NoCheck),
+        _ => {
+            let reason = captures[2].trim_end().trim_end_matches("*/").trim_start_matches([' ', '\t', ':', '-']).trim();
+            reason.is_empty().then_some(Directive::UndescribedExpectError)
+        }
+    }
+}
+
+/// 1-based line and 0-based column of each `!` non-null assertion, outside comments and strings
+pub fn find_non_null_assertions(content: &str) -> Vec<(usize, usize)> {
+    let code = blank_strings(&strip_comments(content));
+    code.lines()
+        .enumerate()
+        .flat_map(|(index, line)| {
+            get_patterns().non_null.find_iter(line).map(move |mat| (index + 1, mat.start() + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directives_and_non_null_assertions() {
+        assert_eq!(find_directive("// @ts-ignore"), Some(Directive::Ignore));
+        assert_eq!(find_directive("// @ts-nocheck"), Some(Directive::NoCheck));
+        assert_eq!(find_directive("// @ts-expect-error"), Some(Directive::UndescribedExpectError));
+        assert_eq!(find_directive("/* @ts-expect-error */"), Some(Directive::UndescribedExpectError));
+        assert_eq!(find_directive("// @ts-expect-error: upstream typing bug, see #12"), None);
+        assert_eq!(find_directive("// @ts-expect-error - the id is a string here"), None);
+        assert_eq!(find_directive("const x = 1;"), None);
+
+        let content = "const name = user!.name;\nconst first = list[0]!;\nconst el = document.getElementById('app')!\nif (a !== b && c != d && !done) {}\nconst text = \"Done!\"; // ok!\nsetUser(user!, role);\n";
+        assert_eq!(find_non_null_assertions(content), vec![(1, 17), (2, 21), (3, 41), (6, 12)]);
+    }
+}
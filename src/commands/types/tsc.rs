@@ -0,0 +1,260 @@
+use anyhow::{anyhow, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::common::{paths, Ignores};
+
+/// Results merged in from the TypeScript compiler (`sniff types --tsc`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TscReport {
+    pub compiler: String,
+    pub total_errors: usize,
+    pub errors_by_file: Vec<FileErrorCount>,
+    pub diagnostics: Vec<TscDiagnostic>,
+    pub strict_gaps: Vec<String>,
+    pub coverage: Option<TypeCoverage>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TscDiagnostic {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileErrorCount {
+    pub file: String,
+    pub errors: usize,
+}
+
+/// Share of identifiers whose type the checker resolves to something other than `any`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TypeCoverage {
+    pub total_identifiers: usize,
+    pub any_identifiers: usize,
+    pub coverage_percent: f64,
+}
+
+/// Flags implied by `strict: true`
+pub(super) const STRICT_FLAGS: &[&str] = &[
+    "noImplicitAny",
+    "strictNullChecks",
+    "strictFunctionTypes",
+    "strictBindCallApply",
+    "strictPropertyInitialization",
+    "noImplicitThis",
+    "useUnknownInCatchVariables",
+    "alwaysStrict",
+];
+
+/// Walks every identifier with the project's own `typescript` package and counts
+/// those the checker types as `any` (the approach used by the `type-coverage` tool).
+const COVERAGE_BRIDGE: &str = r#"
+const path = require('path');
+const ts = require(require.resolve('typescript', { paths: [process.cwd()] }));
+const configPath = ts.findConfigFile(process.cwd(), ts.sys.fileExists, 'tsconfig.json');
+if (!configPath) { console.log(JSON.stringify({ error: 'no tsconfig.json found' })); process.exit(0); }
+const config = ts.readConfigFile(configPath, ts.sys.readFile);
+const parsed = ts.parseJsonConfigFileContent(config.config, ts.sys, path.dirname(configPath));
+const program = ts.createProgram(parsed.fileNames, parsed.options);
+const checker = program.getTypeChecker();
+let total = 0, anyCount = 0;
+for (const sf of program.getSourceFiles()) {
+  if (sf.isDeclarationFile || sf.fileName.includes('/node_modules/')) continue;
+  const visit = (node) => {
+    if (ts.isIdentifier(node)) {
+      total++;
+      if (checker.getTypeAtLocation(node).flags & ts.TypeFlags.Any) anyCount++;
+    }
+    ts.forEachChild(node, visit);
+  };
+  visit(sf);
+}
+console.log(JSON.stringify({ total, any: anyCount }));
+"#;
+
+/// Run the project's TypeScript compiler and collect diagnostics, strict-mode gaps and coverage;
+/// diagnostics `ignores` covers are dropped
+pub fn run_tsc(project_root: &Path, ignores: &Ignores) -> Result<TscReport> {
+    let compiler = find_tsc(project_root)
+        .ok_or_else(|| anyhow!("TypeScript compiler not found. Install it with: npm install --save-dev typescript"))?;
+    let compiler_display = compiler.to_string_lossy().to_string();
+
+    let output = Command::new(&compiler)
+        .args(["--noEmit", "--pretty", "false"])
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| anyhow!("Failed to run {}: {}", compiler_display, e))?;
+
+    // tsc reports diagnostics on stdout and exits non-zero when there are errors
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut diagnostics = parse_tsc_output(&stdout, project_root);
+    ignores.retain(&mut diagnostics, |diagnostic| ("SNF-TYPES-TSC", &diagnostic.file));
+    let errors_by_file = count_errors_by_file(&diagnostics);
+
+    let mut notes = Vec::new();
+    let strict_gaps = match show_config(&compiler, project_root) {
+        Ok(config) => find_strict_gaps(&config),
+        Err(e) => {
+            notes.push(format!("Could not read resolved tsconfig: {}", e));
+            Vec::new()
+        }
+    };
+
+    let coverage = match compute_coverage(project_root) {
+        Ok(coverage) => Some(coverage),
+        Err(e) => {
+            notes.push(format!("Type coverage unavailable: {}", e));
+            None
+        }
+    };
+
+    Ok(TscReport {
+        compiler: compiler_display,
+        total_errors: diagnostics.len(),
+        errors_by_file,
+        diagnostics,
+        strict_gaps,
+        coverage,
+        notes,
+    })
+}
+
+/// Prefer the project's own compiler over a global one
+pub fn find_tsc(project_root: &Path) -> Option<PathBuf> {
+    let bin = if cfg!(windows) { "tsc.cmd" } else { "tsc" };
+    let local = project_root.join("node_modules").join(".bin").join(bin);
+    if local.exists() {
+        return Some(local);
+    }
+
+    Command::new(bin)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|_| PathBuf::from(bin))
+}
+
+/// Parse `--pretty false` output: `src/file.ts(12,5): error TS2322: message`
+fn parse_tsc_output(output: &str, project_root: &Path) -> Vec<TscDiagnostic> {
+    let diagnostic_regex = Regex::new(r"^(.+?)\((\d+),(\d+)\): error (TS\d+): (.*)$").unwrap();
+
+    output.lines()
+        .filter_map(|line| diagnostic_regex.captures(line.trim_end()))
+        .map(|caps| {
+            TscDiagnostic {
+                file: paths::display(&project_root.join(&caps[1])),
+                line: caps[2].parse().unwrap_or(0),
+                column: caps[3].parse().unwrap_or(0),
+                code: caps[4].to_string(),
+                message: caps[5].to_string(),
+            }
+        })
+        .collect()
+}
+
+fn count_errors_by_file(diagnostics: &[TscDiagnostic]) -> Vec<FileErrorCount> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for diagnostic in diagnostics {
+        *counts.entry(diagnostic.file.as_str()).or_default() += 1;
+    }
+
+    let mut errors_by_file: Vec<FileErrorCount> = counts.into_iter()
+        .map(|(file, errors)| FileErrorCount { file: file.to_string(), errors })
+        .collect();
+    errors_by_file.sort_by(|a, b| b.errors.cmp(&a.errors).then_with(|| a.file.cmp(&b.file)));
+    errors_by_file
+}
+
+/// `tsc --showConfig` prints the fully resolved config, following `extends`
+fn show_config(compiler: &Path, project_root: &Path) -> Result<serde_json::Value> {
+    let output = Command::new(compiler)
+        .arg("--showConfig")
+        .current_dir(project_root)
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow!("tsc --showConfig failed"));
+    }
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+fn find_strict_gaps(config: &serde_json::Value) -> Vec<String> {
+    let options = &config["compilerOptions"];
+    let strict = options["strict"].as_bool().unwrap_or(false);
+
+    STRICT_FLAGS.iter()
+        .filter(|flag| {
+            // Individual flags override `strict` in either direction
+            !options[**flag].as_bool().unwrap_or(strict)
+        })
+        .map(|flag| flag.to_string())
+        .collect()
+}
+
+fn compute_coverage(project_root: &Path) -> Result<TypeCoverage> {
+    let output = Command::new("node")
+        .args(["-e", COVERAGE_BRIDGE])
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| anyhow!("node is required ({})", e))?;
+    if !output.status.success() {
+        return Err(anyhow!("the `typescript` package could not be loaded from the project"));
+    }
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    if let Some(error) = result["error"].as_str() {
+        return Err(anyhow!("{}", error));
+    }
+
+    let total_identifiers = result["total"].as_u64().unwrap_or(0) as usize;
+    let any_identifiers = result["any"].as_u64().unwrap_or(0) as usize;
+    let coverage_percent = if total_identifiers > 0 {
+        (total_identifiers - any_identifiers) as f64 / total_identifiers as f64 * 100.0
+    } else {
+        100.0
+    };
+
+    Ok(TypeCoverage {
+        total_identifiers,
+        any_identifiers,
+        coverage_percent,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsc_output() {
+        let output = "src/app.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      src/app.ts(9,1): error TS2304: Cannot find name 'foo'.\n\
+                      Found 2 errors in the same file, starting at: src/app.ts:3\n";
+        // Reports print paths relative to the project root, the working directory here
+        let diagnostics = parse_tsc_output(output, &std::env::current_dir().unwrap());
+
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].file, "src/app.ts");
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 7));
+        assert_eq!(diagnostics[1].code, "TS2304");
+        assert_eq!(count_errors_by_file(&diagnostics)[0].errors, 2);
+    }
+
+    #[test]
+    fn test_strict_gaps_respect_individual_overrides() {
+        let strict = serde_json::json!({ "compilerOptions": { "strict": true, "strictNullChecks": false } });
+        assert_eq!(find_strict_gaps(&strict), vec!["strictNullChecks"]);
+
+        let loose = serde_json::json!({ "compilerOptions": { "noImplicitAny": true } });
+        let gaps = find_strict_gaps(&loose);
+        assert!(!gaps.contains(&"noImplicitAny".to_string()));
+        assert_eq!(gaps.len(), STRICT_FLAGS.len() - 1);
+    }
+}
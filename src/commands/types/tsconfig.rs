@@ -0,0 +1,268 @@
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use crate::common::{glob_matches, paths, source};
+use crate::utils::FileUtils;
+use super::tsc::STRICT_FLAGS;
+
+/// What `tsconfig.json`, with its `extends` chain resolved, leaves unchecked
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TsConfigAudit {
+    pub config: String,
+    /// The configs `extends` pulled in, closest first
+    pub extends: Vec<String>,
+    /// Strictness flags that are off: `strict` itself, flags it implies that are turned back
+    /// off, and stricter flags it does not imply
+    pub flags_off: Vec<String>,
+    /// Compiler options that differ from what Next.js expects, in Next.js projects
+    pub next_conflicts: Vec<OptionConflict>,
+    /// Scanned TypeScript files that `files`, `include` and `exclude` leave out of type checking
+    pub excluded_files: Vec<String>,
+}
+
+impl TsConfigAudit {
+    pub fn total_issues(&self) -> usize {
+        self.flags_off.len() + self.next_conflicts.len() + self.excluded_files.len()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OptionConflict {
+    pub option: String,
+    /// The resolved value, `null` when unset
+    pub value: Value,
+    pub recommended: String,
+}
+
+/// Stricter flags that `strict` does not turn on
+const EXTRA_STRICT_FLAGS: &[&str] = &[
+    "noUncheckedIndexedAccess",
+    "exactOptionalPropertyTypes",
+    "noImplicitReturns",
+    "noImplicitOverride",
+    "noFallthroughCasesInSwitch",
+];
+
+/// Options Next.js sets in a project's tsconfig and checks on `next build`
+const NEXT_OPTIONS: &[(&str, &[&str])] = &[
+    ("noEmit", &["true"]),
+    ("isolatedModules", &["true"]),
+    ("jsx", &["preserve", "react-jsx"]),
+    ("esModuleInterop", &["true"]),
+    ("resolveJsonModule", &["true"]),
+    ("module", &["esnext", "es2020", "es2022", "preserve"]),
+    ("moduleResolution", &["bundler", "node", "node10", "node16", "nodenext"]),
+];
+
+/// Audit the `tsconfig.json` in `project_dir` against `files`, the TypeScript files being scanned
+pub fn audit(project_dir: &Path, files: &[PathBuf]) -> Result<TsConfigAudit> {
+    let config_path = project_dir.join("tsconfig.json");
+    let resolved = resolve(&config_path)?;
+    let options = &resolved.compiler_options;
+    let enabled = |flag: &str| options.get(flag).and_then(Value::as_bool);
+
+    let strict = enabled("strict").unwrap_or(false);
+    let mut flags_off = Vec::new();
+    if !strict {
+        flags_off.push("strict".to_string());
+    } else {
+        flags_off.extend(STRICT_FLAGS.iter().filter(|flag| enabled(flag) == Some(false)).map(|flag| flag.to_string()));
+    }
+    flags_off.extend(EXTRA_STRICT_FLAGS.iter().filter(|flag| enabled(flag) != Some(true)).map(|flag| flag.to_string()));
+
+    let next_conflicts = if uses_next(project_dir) {
+        NEXT_OPTIONS.iter()
+            .filter_map(|(option, accepted)| {
+                let value = options.get(*option).cloned().unwrap_or(Value::Null);
+                let text = match &value {
+                    Value::String(text) => text.to_lowercase(),
+                    other => other.to_string(),
+                };
+                (!accepted.contains(&text.as_str())).then(|| OptionConflict {
+                    option: option.to_string(),
+                    value,
+                    recommended: accepted[0].to_string(),
+                })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let excluded_files = files.iter()
+        .filter(|file| !resolved.checks(file))
+        .map(|file| paths::display(file))
+        .collect();
+
+    Ok(TsConfigAudit {
+        config: paths::display(&config_path),
+        extends: resolved.chain.iter().map(|path| paths::display(path)).collect(),
+        flags_off,
+        next_conflicts,
+        excluded_files,
+    })
+}
+
+fn uses_next(project_dir: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(project_dir.join("package.json")) else { return false };
+    let Ok(package) = serde_json::from_str::<Value>(&content) else { return false };
+    ["dependencies", "devDependencies"].iter().any(|section| package[section].get("next").is_some())
+}
+
+/// A tsconfig merged with the configs it extends
+#[derive(Debug, Default)]
+struct ResolvedConfig {
+    compiler_options: Map<String, Value>,
+    /// `files`, `include` and `exclude` with the directory of the config that set them,
+    /// since a base config's patterns are relative to the base
+    files: Option<(PathBuf, Vec<String>)>,
+    include: Option<(PathBuf, Vec<String>)>,
+    exclude: Option<(PathBuf, Vec<String>)>,
+    chain: Vec<PathBuf>,
+}
+
+impl ResolvedConfig {
+    /// Whether the compiler checks `file`: listed in `files` or matched by `include`
+    /// (everything by default, unless `files` is set) and not by `exclude`
+    fn checks(&self, file: &Path) -> bool {
+        if let Some((dir, files)) = &self.files {
+            if files.iter().any(|listed| normalize(&dir.join(listed)) == normalize(file)) {
+                return true;
+            }
+        }
+        let included = match &self.include {
+            Some((dir, patterns)) => patterns.iter().any(|pattern| matches_spec(dir, pattern, file)),
+            None => self.files.is_none(),
+        };
+        let excluded = match &self.exclude {
+            Some((dir, patterns)) => patterns.iter().any(|pattern| matches_spec(dir, pattern, file)),
+            None => file.components().any(|component| component.as_os_str() == "node_modules"),
+        };
+        included && !excluded
+    }
+}
+
+fn resolve(config_path: &Path) -> Result<ResolvedConfig> {
+    let mut resolved = ResolvedConfig::default();
+    let mut visited = HashSet::new();
+    merge_config(config_path, &mut resolved, &mut visited)?;
+    resolved.chain.remove(0);
+    Ok(resolved)
+}
+
+/// Merge `config_path` into `resolved`, after the configs it extends so that its own values win
+fn merge_config(config_path: &Path, resolved: &mut ResolvedConfig, visited: &mut HashSet<PathBuf>) -> Result<()> {
+    let config_path = normalize(config_path);
+    if !visited.insert(config_path.clone()) {
+        return Err(anyhow!("{} extends itself", paths::display(&config_path)));
+    }
+    let content = fs::read_to_string(&config_path)
+        .with_context(|| format!("Cannot read {}", paths::display(&config_path)))?;
+    let config = source::parse_jsonc(&content)
+        .with_context(|| format!("Cannot parse {}", paths::display(&config_path)))?;
+    let dir = config_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+    resolved.chain.push(config_path.clone());
+
+    // `extends` may list several configs, applied in order
+    let bases: Vec<&str> = match &config["extends"] {
+        Value::String(base) => vec![base.as_str()],
+        Value::Array(bases) => bases.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    for base in bases {
+        match find_base(&dir, base) {
+            Some(base_path) => merge_config(&base_path, resolved, visited)?,
+            None => tracing::warn!("Cannot find {} extended by {}", base, paths::display(&config_path)),
+        }
+    }
+
+    if let Some(options) = config["compilerOptions"].as_object() {
+        resolved.compiler_options.extend(options.clone());
+    }
+    let patterns = |key: &str| {
+        config[key].as_array().map(|items| (dir.clone(), items.iter().filter_map(Value::as_str).map(str::to_string).collect()))
+    };
+    if let Some(files) = patterns("files") {
+        resolved.files = Some(files);
+    }
+    if let Some(include) = patterns("include") {
+        resolved.include = Some(include);
+    }
+    if let Some(exclude) = patterns("exclude") {
+        resolved.exclude = Some(exclude);
+    }
+    Ok(())
+}
+
+/// A relative path, or a package in `node_modules` such as `@tsconfig/strictest`
+fn find_base(dir: &Path, base: &str) -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = if base.starts_with('.') || Path::new(base).is_absolute() {
+        vec![dir.join(base)]
+    } else {
+        dir.ancestors().map(|ancestor| ancestor.join("node_modules").join(base)).collect()
+    };
+    candidates.into_iter()
+        .flat_map(|candidate| {
+            let with_extension = candidate.with_file_name(format!("{}.json", candidate.file_name().unwrap_or_default().to_string_lossy()));
+            [candidate.clone(), with_extension, candidate.join("tsconfig.json")]
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// A TypeScript include/exclude spec relative to `dir`: a spec whose last segment has no `*`
+/// or extension names a directory and matches everything under it
+fn matches_spec(dir: &Path, spec: &str, file: &Path) -> bool {
+    let pattern = FileUtils::slash_path(&normalize(&dir.join(spec.trim_end_matches('/'))));
+    let file = FileUtils::slash_path(&normalize(file));
+    let last = pattern.rsplit('/').next().unwrap_or(&pattern);
+    if last.contains('*') || last.contains('.') {
+        glob_matches(&pattern, &file)
+    } else {
+        file.starts_with(&format!("{}/", pattern)) || glob_matches(&format!("{}/**/*", pattern), &file)
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extends_chain_and_excluded_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("node_modules/@tsconfig/strictest")).unwrap();
+        fs::write(root.join("node_modules/@tsconfig/strictest/tsconfig.json"), r#"{ "compilerOptions": { "strict": true, "noUncheckedIndexedAccess": true, "exactOptionalPropertyTypes": true } }"#).unwrap();
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::write(root.join("config/base.json"), "{\n  // shared settings\n  \"extends\": \"@tsconfig/strictest\",\n  \"compilerOptions\": { \"strictNullChecks\": false, \"jsx\": \"react\", },\n  \"include\": [\"../src\"],\n}\n").unwrap();
+        fs::write(root.join("tsconfig.json"), r#"{ "extends": "./config/base", "compilerOptions": { "exactOptionalPropertyTypes": false, "noEmit": true }, "exclude": ["src/legacy", "**/*.test.ts"] }"#).unwrap();
+
+        let files: Vec<PathBuf> = ["src/app.ts", "src/legacy/old.ts", "src/app.test.ts", "scripts/seed.ts"].iter().map(|file| root.join(file)).collect();
+        fs::write(root.join("package.json"), r#"{ "dependencies": { "next": "15.0.0" } }"#).unwrap();
+        let audit = audit(root, &files).unwrap();
+
+        assert_eq!(audit.extends.len(), 2);
+        assert_eq!(audit.flags_off, vec!["strictNullChecks", "exactOptionalPropertyTypes", "noImplicitReturns", "noImplicitOverride", "noFallthroughCasesInSwitch"]);
+        let conflicts: Vec<&str> = audit.next_conflicts.iter().map(|conflict| conflict.option.as_str()).collect();
+        assert_eq!(conflicts, vec!["isolatedModules", "jsx", "esModuleInterop", "resolveJsonModule", "module", "moduleResolution"]);
+        let excluded: Vec<&str> = audit.excluded_files.iter().map(|file| file.rsplit('/').next().unwrap()).collect();
+        assert_eq!(excluded, vec!["old.ts", "app.test.ts", "seed.ts"]);
+    }
+}
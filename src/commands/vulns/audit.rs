@@ -0,0 +1,145 @@
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command;
+use crate::common::Severity;
+use super::Advisory;
+
+/// Advisories of an `npm audit --json` report (npm 7 and later) or of the older format that
+/// npm 6 and `pnpm audit --json` write
+pub fn parse_report(content: &str) -> Result<Vec<Advisory>> {
+    let report: Value = serde_json::from_str(content)?;
+    if let Some(vulnerabilities) = report["vulnerabilities"].as_object() {
+        Ok(vulnerabilities.values().flat_map(parse_vulnerability).collect())
+    } else if let Some(advisories) = report["advisories"].as_object() {
+        Ok(advisories.values().map(parse_advisory).collect())
+    } else if let Some(error) = report["error"]["summary"].as_str().or_else(|| report["error"]["message"].as_str()) {
+        Err(anyhow!("the audit failed: {}", error))
+    } else {
+        Err(anyhow!("expected `vulnerabilities` or `advisories`"))
+    }
+}
+
+/// npm 7+ lists each vulnerable package with the advisories it is affected by in `via`; entries
+/// that are package names point at a vulnerable dependency and are reported with that package
+fn parse_vulnerability(vulnerability: &Value) -> Vec<Advisory> {
+    let package = vulnerability["name"].as_str().unwrap_or_default();
+    let fix = match &vulnerability["fixAvailable"] {
+        Value::Object(fix) => fix["name"].as_str().zip(fix["version"].as_str()).map(|(name, version)| {
+            let major = if fix["isSemVerMajor"].as_bool().unwrap_or(false) { " (breaking)" } else { "" };
+            format!("{}@{}{}", name, version, major)
+        }),
+        Value::Bool(true) => Some("npm audit fix".to_string()),
+        _ => None,
+    };
+    vulnerability["via"].as_array().into_iter().flatten()
+        .filter(|via| via["name"].as_str() == Some(package))
+        .map(|via| {
+            let url = via["url"].as_str().map(str::to_string);
+            // `https://github.com/advisories/GHSA-...` names the advisory better than npm's number
+            let id = url.as_deref().and_then(|url| url.rsplit('/').next()).filter(|id| id.starts_with("GHSA-")).map(str::to_string)
+                .unwrap_or_else(|| via["source"].to_string());
+            Advisory {
+                id,
+                package: package.to_string(),
+                versions: Vec::new(),
+                severity: severity(via["severity"].as_str()),
+                title: via["title"].as_str().unwrap_or_default().to_string(),
+                url,
+                fix: fix.clone(),
+                direct: vulnerability["isDirect"].as_bool().unwrap_or(false),
+                line: 1,
+            }
+        })
+        .collect()
+}
+
+fn parse_advisory(advisory: &Value) -> Advisory {
+    let mut versions: Vec<String> = advisory["findings"].as_array().into_iter().flatten()
+        .filter_map(|finding| finding["version"].as_str().map(str::to_string))
+        .collect();
+    versions.sort();
+    versions.dedup();
+    let id = advisory["github_advisory_id"].as_str().map(str::to_string)
+        .unwrap_or_else(|| advisory["id"].to_string());
+    Advisory {
+        id,
+        package: advisory["module_name"].as_str().unwrap_or_default().to_string(),
+        versions,
+        severity: severity(advisory["severity"].as_str()),
+        title: advisory["title"].as_str().unwrap_or_default().to_string(),
+        url: advisory["url"].as_str().map(str::to_string),
+        fix: advisory["patched_versions"].as_str().filter(|patched| *patched != "<0.0.0").map(str::to_string),
+        direct: false,
+        line: 1,
+    }
+}
+
+/// npm's `info`, `low`, `moderate`, `high` and `critical`
+pub fn severity(name: Option<&str>) -> Severity {
+    match name.map(str::to_ascii_lowercase).as_deref() {
+        Some("critical") => Severity::Critical,
+        Some("high") => Severity::High,
+        Some("moderate" | "medium") => Severity::Medium,
+        Some("low") => Severity::Low,
+        _ => Severity::Info,
+    }
+}
+
+/// Run `pnpm audit --json` in pnpm projects and `npm audit --json` otherwise; returns the
+/// output and the command line
+pub fn run_audit(project_dir: &Path) -> Result<(String, String)> {
+    let tool = if project_dir.join("pnpm-lock.yaml").is_file() { "pnpm" } else { "npm" };
+    let program = if cfg!(windows) { format!("{}.cmd", tool) } else { tool.to_string() };
+    let output = Command::new(&program)
+        .args(["audit", "--json"])
+        .current_dir(project_dir)
+        .output()
+        .with_context(|| format!("Failed to run {} audit; is {} installed?", tool, tool))?;
+
+    // Both exit non-zero when they find advisories; only output that is not JSON is a failure
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    if !stdout.trim_start().starts_with('{') {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("{} audit did not produce a JSON report: {}", tool, stderr.trim()));
+    }
+    Ok((stdout, format!("{} audit --json", tool)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_npm_and_pnpm_audit_reports_are_parsed() {
+        let npm = r#"{"auditReportVersion": 2, "vulnerabilities": {
+            "lodash": {"name": "lodash", "severity": "critical", "isDirect": true,
+                "via": [{"source": 1094499, "name": "lodash", "title": "Prototype Pollution in lodash", "url": "https://github.com/advisories/GHSA-p6mc-m468-83gw", "severity": "high"},
+                        {"source": 1096305, "name": "lodash", "title": "Command Injection in lodash", "url": "https://github.com/advisories/GHSA-35jh-r3h4-6jhm", "severity": "critical"}],
+                "fixAvailable": {"name": "lodash", "version": "4.17.21", "isSemVerMajor": false}},
+            "express": {"name": "express", "severity": "moderate", "isDirect": true, "via": ["qs"], "fixAvailable": true}
+        }}"#;
+        let advisories = parse_report(npm).unwrap();
+        let found: Vec<(&str, &str, Severity, Option<&str>)> = advisories.iter()
+            .map(|a| (a.id.as_str(), a.package.as_str(), a.severity.clone(), a.fix.as_deref()))
+            .collect();
+        assert_eq!(found, vec![
+            ("GHSA-p6mc-m468-83gw", "lodash", Severity::High, Some("lodash@4.17.21")),
+            ("GHSA-35jh-r3h4-6jhm", "lodash", Severity::Critical, Some("lodash@4.17.21")),
+        ]);
+        assert!(advisories[0].direct);
+
+        let pnpm = r#"{"advisories": {"1067342": {"id": 1067342, "module_name": "minimist", "severity": "moderate",
+            "title": "Prototype Pollution in minimist", "url": "https://github.com/advisories/GHSA-vh95-rmgr-6w4m",
+            "github_advisory_id": "GHSA-vh95-rmgr-6w4m", "patched_versions": ">=1.2.6",
+            "findings": [{"version": "1.2.5", "paths": ["mkdirp>minimist"]}, {"version": "1.2.0", "paths": ["optimist>minimist"]}]}},
+            "metadata": {}}"#;
+        let advisories = parse_report(pnpm).unwrap();
+        assert_eq!(advisories[0].id, "GHSA-vh95-rmgr-6w4m");
+        assert_eq!(advisories[0].severity, Severity::Medium);
+        assert_eq!(advisories[0].versions, vec!["1.2.0", "1.2.5"]);
+        assert_eq!(advisories[0].fix.as_deref(), Some(">=1.2.6"));
+
+        assert!(parse_report(r#"{"error": {"code": "ENOLOCK", "summary": "This command requires an existing lockfile."}}"#).is_err());
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+/// Lockfiles read for the installed packages, in the order they are looked for
+const LOCKFILES: &[&str] = &["package-lock.json", "npm-shrinkwrap.json", "pnpm-lock.yaml", "yarn.lock"];
+
+/// Every package and version the project's lockfile installs, sorted, with the lockfile's name
+pub fn installed_packages(project_dir: &Path) -> Result<(String, Vec<(String, String)>)> {
+    let name = LOCKFILES.iter().find(|name| project_dir.join(name).is_file())
+        .ok_or_else(|| anyhow!("No package-lock.json, pnpm-lock.yaml or yarn.lock in {}", project_dir.display()))?;
+    let content = fs::read_to_string(project_dir.join(name))?;
+    let packages = match *name {
+        "pnpm-lock.yaml" => parse_pnpm(&content),
+        "yarn.lock" => parse_yarn(&content),
+        _ => parse_npm(&serde_json::from_str(&content).map_err(|e| anyhow!("{} is not valid JSON: {}", name, e))?),
+    };
+    Ok((name.to_string(), packages.into_iter().collect()))
+}
+
+/// `packages` of lockfile v2 and v3, `dependencies` of v1
+fn parse_npm(lockfile: &Value) -> BTreeSet<(String, String)> {
+    let mut packages = BTreeSet::new();
+    if let Some(entries) = lockfile["packages"].as_object() {
+        for (path, entry) in entries {
+            let Some((_, name)) = path.rsplit_once("node_modules/") else { continue };
+            if let (Some(version), false) = (entry["version"].as_str(), entry["link"].as_bool().unwrap_or(false)) {
+                packages.insert((name.to_string(), version.to_string()));
+            }
+        }
+    } else {
+        collect_v1(&lockfile["dependencies"], &mut packages);
+    }
+    packages
+}
+
+fn collect_v1(dependencies: &Value, packages: &mut BTreeSet<(String, String)>) {
+    for (name, entry) in dependencies.as_object().into_iter().flatten() {
+        if let Some(version) = entry["version"].as_str() {
+            packages.insert((name.clone(), version.to_string()));
+        }
+        collect_v1(&entry["dependencies"], packages);
+    }
+}
+
+/// Keys of the `packages:` section: `/name/1.2.3` (v5), `/name@1.2.3` (v6) or `name@1.2.3(peer@1.0.0)` (v9)
+fn parse_pnpm(content: &str) -> BTreeSet<(String, String)> {
+    let mut packages = BTreeSet::new();
+    let mut in_packages = false;
+    for line in content.lines() {
+        if !line.starts_with(' ') && !line.trim().is_empty() {
+            in_packages = line.trim_end() == "packages:";
+            continue;
+        }
+        let Some(key) = line.strip_prefix("  ").filter(|key| !key.starts_with(' ')) else { continue };
+        if !in_packages {
+            continue;
+        }
+        let key = key.trim_end().trim_end_matches(':').trim_matches(['\'', '"']).trim_start_matches('/');
+        let key = key.split('(').next().unwrap_or(key);
+        let split = key.rfind('@').filter(|&index| index > 0).or_else(|| key.rfind('/'));
+        if let Some(index) = split {
+            let (name, version) = (&key[..index], &key[index + 1..]);
+            if !name.is_empty() && version.starts_with(|c: char| c.is_ascii_digit()) {
+                packages.insert((name.to_string(), version.to_string()));
+            }
+        }
+    }
+    packages
+}
+
+/// Entries of yarn v1 (`version "1.2.3"`) and berry (`version: 1.2.3`) lockfiles
+fn parse_yarn(content: &str) -> BTreeSet<(String, String)> {
+    let mut packages = BTreeSet::new();
+    let mut name: Option<String> = None;
+    for line in content.lines() {
+        if !line.starts_with(' ') && line.trim_end().ends_with(':') && !line.starts_with('#') {
+            // `"@babel/core@^7.0.0", "@babel/core@^7.1.0":` or `"lodash@npm:^4.17.0":`
+            let spec = line.split(',').next().unwrap_or_default().trim().trim_end_matches(':').trim_matches('"');
+            name = spec.rfind('@').filter(|&index| index > 0).map(|index| spec[..index].to_string());
+        } else if let (Some(current), Some(version)) = (&name, line.trim().strip_prefix("version")) {
+            let version = version.trim_start_matches(':').trim().trim_matches('"');
+            packages.insert((current.clone(), version.to_string()));
+            name = None;
+        }
+    }
+    packages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pairs(packages: BTreeSet<(String, String)>) -> Vec<String> {
+        packages.into_iter().map(|(name, version)| format!("{}@{}", name, version)).collect()
+    }
+
+    #[test]
+    fn test_lockfiles_are_read() {
+        let npm = serde_json::json!({ "packages": {
+            "": { "name": "app" },
+            "node_modules/lodash": { "version": "4.17.15" },
+            "node_modules/a/node_modules/@scope/b": { "version": "1.0.0" },
+            "node_modules/ui": { "link": true, "version": "0.0.0" }
+        }});
+        assert_eq!(pairs(parse_npm(&npm)), vec!["@scope/b@1.0.0", "lodash@4.17.15"]);
+
+        let v1 = serde_json::json!({ "dependencies": { "a": { "version": "1.0.0", "dependencies": { "b": { "version": "2.0.0" } } } } });
+        assert_eq!(pairs(parse_npm(&v1)), vec!["a@1.0.0", "b@2.0.0"]);
+
+        let pnpm = "lockfileVersion: '9.0'\n\nimporters:\n  .:\n    dependencies:\n      lodash:\n        specifier: ^4.17.0\n\npackages:\n\n  lodash@4.17.15:\n    resolution: {integrity: sha512-x}\n\n  '@types/react@18.2.0':\n    resolution: {integrity: sha512-y}\n\n  /minimist/1.2.0:\n    resolution: {integrity: sha512-z}\n\nsnapshots:\n\n  react-dom@18.2.0(react@18.2.0):\n    dependencies: {}\n";
+        assert_eq!(pairs(parse_pnpm(pnpm)), vec!["@types/react@18.2.0", "lodash@4.17.15", "minimist@1.2.0"]);
+
+        let yarn = "# yarn lockfile v1\n\n\"@babel/core@^7.0.0\", \"@babel/core@^7.1.0\":\n  version \"7.1.2\"\n  resolved \"https://registry.yarnpkg.com/x\"\n\nlodash@^4.17.0:\n  version \"4.17.15\"\n\n\"minimist@npm:^1.2.0\":\n  version: 1.2.5\n";
+        assert_eq!(pairs(parse_yarn(yarn)), vec!["@babel/core@7.1.2", "lodash@4.17.15", "minimist@1.2.5"]);
+    }
+}
@@ -0,0 +1,264 @@
+mod audit;
+mod lockfile;
+mod osv;
+
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::config::{Config, VulnSource};
+use crate::common::{output, Annotate, Annotation, AnnotationLevel, paths, ScanTarget, Severity, ExitCode, CommandOutcome, check_failure_threshold, create_annotated_json_output, output_result};
+use super::history::Metrics;
+use super::outdated::manifest_line;
+
+#[derive(Debug, Clone, Default)]
+pub struct VulnsOptions {
+    /// `npm audit --json` or `pnpm audit --json` output to read instead of querying
+    pub ingest: Option<PathBuf>,
+    /// Run `npm audit` or `pnpm audit` instead of querying OSV
+    pub audit: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VulnsReport {
+    /// The ingested report, the audit command that was run, or the lockfile OSV was queried for
+    pub source: String,
+    pub advisories: Vec<Advisory>,
+    pub summary: VulnsSummary,
+    /// Advisories at or above this severity fail the check
+    pub min_severity: Severity,
+}
+
+/// One advisory against one package
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Advisory {
+    /// GitHub advisory id when there is one, e.g. `GHSA-p6mc-m468-83gw`
+    pub id: String,
+    pub package: String,
+    /// Affected versions installed in the project
+    pub versions: Vec<String>,
+    pub severity: Severity,
+    pub title: String,
+    pub url: Option<String>,
+    /// The version range or upgrade that fixes it
+    pub fix: Option<String>,
+    /// Declared in package.json rather than pulled in by another package
+    pub direct: bool,
+    /// Line of the package in package.json, or 1 for transitive dependencies
+    #[serde(skip)]
+    pub line: usize,
+}
+
+impl Advisory {
+    /// Each severity has its own rule, so `[[ignore]]` can drop the low ones
+    pub fn rule(&self) -> &'static str {
+        match self.severity {
+            Severity::Critical => "SNF-VULNS-CRITICAL",
+            Severity::High => "SNF-VULNS-HIGH",
+            Severity::Medium => "SNF-VULNS-MODERATE",
+            Severity::Low | Severity::Info => "SNF-VULNS-LOW",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VulnsSummary {
+    /// Packages in the lockfile; audit reports do not say
+    pub packages_scanned: Option<usize>,
+    pub vulnerable_packages: usize,
+    pub total_advisories: usize,
+    pub critical: usize,
+    pub high: usize,
+    pub moderate: usize,
+    /// Low and informational advisories
+    pub low: usize,
+    /// Advisories at or above `[vulns] min_severity`
+    pub failing: usize,
+    /// Advisories dropped by `[vulns] ignore_advisories` and `[[ignore]]` entries
+    pub ignored: usize,
+}
+
+impl Annotate for VulnsReport {
+    fn annotations(&self) -> Vec<Annotation> {
+        self.advisories.iter().map(|advisory| {
+            let versions = if advisory.versions.is_empty() { String::new() } else { format!("@{}", advisory.versions.join(", ")) };
+            let fix = advisory.fix.as_ref().map(|fix| format!("; fixed in {}", fix)).unwrap_or_default();
+            Annotation::new(
+                AnnotationLevel::for_severity(&advisory.severity),
+                "package.json",
+                advisory.line,
+                format!("{} in {}{}", advisory.id, advisory.package, versions),
+                format!("{} ({}){}", advisory.title, severity_name(&advisory.severity), fix),
+            )
+            .with_rule(advisory.rule())
+        }).collect()
+    }
+}
+
+/// npm's names for the severities
+fn severity_name(severity: &Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "critical",
+        Severity::High => "high",
+        Severity::Medium => "moderate",
+        Severity::Low => "low",
+        Severity::Info => "info",
+    }
+}
+
+impl Metrics for VulnsReport {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("critical_vulns", self.summary.critical as f64),
+            ("high_vulns", self.summary.high as f64),
+        ]
+    }
+}
+
+pub async fn run(target: &ScanTarget, options: &VulnsOptions, json: bool, quiet: bool) -> Result<CommandOutcome<VulnsReport>> {
+    let start_time = std::time::Instant::now();
+    tracing::info!(audit = options.audit, "Running vulnerability audit");
+
+    let config = target.config()?;
+    let outcome = check(target, options, &config)?;
+    let report = &outcome.report;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    let response = create_annotated_json_output(
+        "vulns",
+        report,
+        report.summary.packages_scanned.unwrap_or(report.summary.vulnerable_packages),
+        report.summary.total_advisories,
+        Some(duration_ms),
+    );
+
+    output_result(&response, json, quiet, |report, quiet| print_report(report, quiet))?;
+
+    tracing::info!(critical = report.summary.critical, high = report.summary.high, "vulnerability audit completed");
+
+    Ok(outcome)
+}
+
+/// Collect the advisories of an audit report (`--ingest` or `[vulns] report`), an `npm audit` /
+/// `pnpm audit` run, or OSV. Advisories at or above `[vulns] min_severity` fail the check
+pub fn check(target: &ScanTarget, options: &VulnsOptions, config: &Config) -> Result<CommandOutcome<VulnsReport>> {
+    let project_dir = target.project_dir("vulns")?;
+    let settings = &config.vulns;
+    let configured = settings.report.as_ref().map(|report| target.config_dir().join(report));
+
+    let (mut advisories, source, packages_scanned) = match options.ingest.clone().or(configured) {
+        Some(report) => {
+            let content = fs::read_to_string(&report)
+                .map_err(|e| anyhow!("Could not read audit report {}: {}", report.display(), e))?;
+            let source = paths::display(&report);
+            let advisories = audit::parse_report(&content)
+                .map_err(|e| anyhow!("{} is not an npm or pnpm audit JSON report: {}", source, e))?;
+            (advisories, source, None)
+        }
+        None if options.audit || settings.source == VulnSource::Audit => {
+            let (content, command) = audit::run_audit(project_dir)?;
+            let advisories = audit::parse_report(&content)
+                .map_err(|e| anyhow!("Could not read the output of {}: {}", command, e))?;
+            (advisories, command, None)
+        }
+        None => {
+            let (lockfile, packages) = lockfile::installed_packages(project_dir)?;
+            let advisories = osv::query(&settings.osv_api, &packages)
+                .map_err(|e| anyhow!("Could not query OSV at {}: {}", settings.osv_api, e))?;
+            (advisories, format!("OSV for {}", lockfile), Some(packages.len()))
+        }
+    };
+
+    locate(project_dir, &mut advisories);
+    let before = advisories.len();
+    advisories.retain(|advisory| !settings.ignore_advisories.contains(&advisory.id));
+    let ignored = before - advisories.len()
+        + target.ignores()?.retain(&mut advisories, |advisory| (advisory.rule(), "package.json"));
+    advisories.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.package.cmp(&b.package)).then(a.id.cmp(&b.id)));
+
+    let count = |severities: &[Severity]| advisories.iter().filter(|advisory| severities.contains(&advisory.severity)).count();
+    let summary = VulnsSummary {
+        packages_scanned,
+        vulnerable_packages: advisories.iter().map(|advisory| &advisory.package).collect::<BTreeSet<_>>().len(),
+        total_advisories: advisories.len(),
+        critical: count(&[Severity::Critical]),
+        high: count(&[Severity::High]),
+        moderate: count(&[Severity::Medium]),
+        low: count(&[Severity::Low, Severity::Info]),
+        failing: advisories.iter().filter(|advisory| advisory.severity >= settings.min_severity).count(),
+        ignored,
+    };
+
+    let exit_code = check_failure_threshold(summary.failing > 0, ExitCode::ValidationFailed);
+    Ok(CommandOutcome::new(VulnsReport { source, advisories, summary, min_severity: settings.min_severity.clone() }, exit_code))
+}
+
+/// Mark the packages declared in package.json as direct, with their line, and fill in the
+/// installed versions that npm 7+ reports leave out from the lockfile
+fn locate(project_dir: &Path, advisories: &mut [Advisory]) {
+    let manifest = fs::read_to_string(project_dir.join("package.json")).unwrap_or_default();
+    let installed = lockfile::installed_packages(project_dir).map(|(_, packages)| packages).unwrap_or_default();
+    for advisory in advisories {
+        let line = ["dependencies", "devDependencies", "optionalDependencies"].iter()
+            .find_map(|section| manifest_line(&manifest, section, &advisory.package));
+        advisory.direct |= line.is_some();
+        advisory.line = line.unwrap_or(1);
+        if advisory.versions.is_empty() {
+            advisory.versions = installed.iter()
+                .filter(|(name, _)| *name == advisory.package)
+                .map(|(_, version)| version.clone())
+                .collect();
+        }
+    }
+}
+
+fn print_report(report: &VulnsReport, quiet: bool) {
+    if !quiet {
+        output::blank();
+        output::line("🛡️  Vulnerability Report".bold().blue());
+        output::line("========================".blue());
+        output::line(format!("  Advisories from {}", report.source.dimmed()));
+        output::blank();
+    }
+
+    if report.advisories.is_empty() {
+        output::line("✅ No known vulnerabilities!".green());
+    } else {
+        output::line("🚨 ADVISORIES".bold().red());
+        output::line("─────────────".red());
+        for advisory in &report.advisories {
+            let label = severity_name(&advisory.severity).to_uppercase();
+            let versions = if advisory.versions.is_empty() { String::new() } else { format!("@{}", advisory.versions.join(", ")) };
+            output::line(format!("  {} {}{} - {}",
+                advisory.severity.paint(&format!("[{}]", label)),
+                advisory.package.cyan(),
+                versions,
+                advisory.title
+            ));
+            let direct = if advisory.direct { "direct dependency" } else { "transitive dependency" };
+            let fix = advisory.fix.as_ref().map(|fix| format!(", fix: {}", fix)).unwrap_or_default();
+            output::line(format!("      {}", format!("{}, {}{}", advisory.id, direct, fix).dimmed()));
+        }
+        output::blank();
+    }
+
+    let summary = &report.summary;
+    output::line("📈 SUMMARY".bold().white());
+    output::line("─────────".white());
+    if let Some(scanned) = summary.packages_scanned {
+        output::line(format!("  Packages scanned: {}", scanned));
+    }
+    output::line(format!("  Vulnerable packages: {}", summary.vulnerable_packages));
+    output::line(format!("  Critical: {}", summary.critical.to_string().red()));
+    output::line(format!("  High: {}", summary.high.to_string().red()));
+    output::line(format!("  Moderate: {}", summary.moderate.to_string().yellow()));
+    output::line(format!("  Low: {}", summary.low));
+    if summary.ignored > 0 {
+        output::line(format!("  Ignored: {}", summary.ignored.to_string().dimmed()));
+    }
+    output::line(format!("  At or above {}: {}", severity_name(&report.min_severity), summary.failing));
+    output::blank();
+    output::line("💡 TIP: Accept a known risk with its id in [vulns] ignore_advisories".dimmed());
+}
@@ -0,0 +1,118 @@
+use anyhow::{anyhow, Context, Result};
+use rayon::prelude::*;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use crate::common::{Severity, http};
+use crate::commands::outdated::version;
+use super::{audit, Advisory};
+
+/// Queries the OSV API takes in one batch
+const BATCH_SIZE: usize = 1000;
+
+/// How long one OSV request may take
+const MAX_TIME_SECS: u64 = 30;
+
+/// Advisories OSV knows for the installed `packages`. The batch query only returns ids, so each
+/// advisory is then fetched once for its severity, summary and fixed versions
+pub fn query(api: &str, packages: &[(String, String)]) -> Result<Vec<Advisory>> {
+    let api = api.trim_end_matches('/');
+    // advisory id -> affected packages and versions
+    let mut affected: BTreeMap<String, BTreeMap<String, Vec<String>>> = BTreeMap::new();
+    for batch in packages.chunks(BATCH_SIZE) {
+        let queries: Vec<Value> = batch.iter()
+            .map(|(name, version)| json!({ "package": { "name": name, "ecosystem": "npm" }, "version": version }))
+            .collect();
+        let response = http::post_json(&format!("{}/v1/querybatch", api), &json!({ "queries": queries }), MAX_TIME_SECS)?;
+        let results = response["results"].as_array()
+            .ok_or_else(|| anyhow!("the OSV batch query returned no `results`"))?;
+        for ((name, version), result) in batch.iter().zip(results) {
+            for id in result["vulns"].as_array().into_iter().flatten().filter_map(|vuln| vuln["id"].as_str()) {
+                affected.entry(id.to_string()).or_default().entry(name.clone()).or_default().push(version.clone());
+            }
+        }
+    }
+
+    let documents: Vec<(String, Result<Value>)> = affected.keys().cloned().collect::<Vec<_>>().into_par_iter()
+        .map(|id| {
+            let document = http::get_json(&format!("{}/v1/vulns/{}", api, id), MAX_TIME_SECS);
+            (id, document)
+        })
+        .collect();
+
+    let mut advisories = Vec::new();
+    for (id, document) in documents {
+        let document = document.with_context(|| format!("Could not fetch OSV advisory {}", id))?;
+        for (package, versions) in &affected[&id] {
+            advisories.push(parse_vulnerability(&id, &document, package, versions.clone()));
+        }
+    }
+    Ok(advisories)
+}
+
+/// An OSV advisory for one of the packages it affects
+pub fn parse_vulnerability(id: &str, document: &Value, package: &str, versions: Vec<String>) -> Advisory {
+    let title = document["summary"].as_str()
+        .or_else(|| document["details"].as_str().and_then(|details| details.lines().next()))
+        .unwrap_or_default();
+    Advisory {
+        id: id.to_string(),
+        package: package.to_string(),
+        severity: osv_severity(id, document),
+        title: title.trim().to_string(),
+        url: Some(format!("https://osv.dev/vulnerability/{}", id)),
+        fix: fixed_version(document, package, &versions).map(|fixed| format!(">={}", fixed)),
+        versions,
+        direct: false,
+        line: 1,
+    }
+}
+
+/// GitHub advisories carry their severity in `database_specific`; malicious packages (`MAL-`)
+/// are critical, and advisories without a rating count as moderate
+fn osv_severity(id: &str, document: &Value) -> Severity {
+    match document["database_specific"]["severity"].as_str() {
+        Some(severity) => audit::severity(Some(severity)),
+        None if id.starts_with("MAL-") => Severity::Critical,
+        None => Severity::Medium,
+    }
+}
+
+/// The lowest `fixed` event of the package's ranges above every installed version
+fn fixed_version(document: &Value, package: &str, versions: &[String]) -> Option<String> {
+    let newest_installed = versions.iter().filter_map(|installed| version::parse(installed)).max();
+    document["affected"].as_array().into_iter().flatten()
+        .filter(|affected| affected["package"]["ecosystem"] == "npm" && affected["package"]["name"] == package)
+        .flat_map(|affected| affected["ranges"].as_array().into_iter().flatten())
+        .flat_map(|range| range["events"].as_array().into_iter().flatten())
+        .filter_map(|event| event["fixed"].as_str())
+        .filter_map(|fixed| version::parse(fixed).map(|parsed| (parsed, fixed)))
+        .filter(|(parsed, _)| newest_installed.is_none_or(|installed| *parsed > installed))
+        .min()
+        .map(|(_, fixed)| fixed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osv_advisories_are_read() {
+        let document = serde_json::json!({
+            "id": "GHSA-p6mc-m468-83gw",
+            "summary": "Prototype Pollution in lodash",
+            "database_specific": { "severity": "HIGH" },
+            "affected": [{
+                "package": { "ecosystem": "npm", "name": "lodash" },
+                "ranges": [{ "type": "SEMVER", "events": [{ "introduced": "0" }, { "fixed": "4.17.19" }, { "introduced": "4.17.20" }, { "fixed": "4.17.21" }] }]
+            }]
+        });
+        let advisory = parse_vulnerability("GHSA-p6mc-m468-83gw", &document, "lodash", vec!["4.17.20".to_string()]);
+        assert_eq!(advisory.severity, Severity::High);
+        assert_eq!(advisory.title, "Prototype Pollution in lodash");
+        assert_eq!(advisory.fix.as_deref(), Some(">=4.17.21"));
+
+        let malicious = serde_json::json!({ "details": "Malicious code in evil-pkg\n\nAny computer that has this package installed..." });
+        let advisory = parse_vulnerability("MAL-2024-1", &malicious, "evil-pkg", vec!["1.0.0".to_string()]);
+        assert_eq!((advisory.severity, advisory.title.as_str(), advisory.fix), (Severity::Critical, "Malicious code in evil-pkg", None));
+    }
+}
@@ -0,0 +1,185 @@
+/// What the commands of one run have already read from the project, so a pipeline such as
+/// `deploy` walks the tree and reads each file once instead of once per check.
+///
+/// Only a shared context keeps anything. Targets start with an unshared one, which always goes
+/// to disk: `fix` rewrites files between passes and `serve` analyzes a tree that keeps changing.
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use walkdir::WalkDir;
+use crate::config::Config;
+use super::file_scanner::is_excluded_dir;
+use super::performance::{MAX_SOURCE_BYTES, count_lines_optimized, is_binary, read_source};
+
+type Parsed = Arc<dyn Any + Send + Sync>;
+/// A walked directory and the directory names it was pruned of
+type WalkKey = (PathBuf, Vec<String>);
+
+#[derive(Default)]
+pub struct AnalysisContext {
+    shared: bool,
+    configs: Mutex<HashMap<PathBuf, Config>>,
+    walks: Mutex<HashMap<WalkKey, Arc<Vec<PathBuf>>>>,
+    contents: Mutex<HashMap<PathBuf, Arc<str>>>,
+    parsed: Mutex<HashMap<(PathBuf, TypeId), Parsed>>,
+}
+
+impl fmt::Debug for AnalysisContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnalysisContext")
+            .field("shared", &self.shared)
+            .field("walks", &lock(&self.walks).len())
+            .field("contents", &lock(&self.contents).len())
+            .finish()
+    }
+}
+
+/// A poisoned cache only means another thread panicked mid-insert; its entries are still whole
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+impl AnalysisContext {
+    /// A context that keeps what is read until it is dropped
+    pub fn shared() -> Self {
+        Self { shared: true, ..Self::default() }
+    }
+
+    /// The configuration for files in `dir`, as `Config::load_from_dir` gives it
+    pub fn config(&self, dir: &Path) -> anyhow::Result<Config> {
+        if !self.shared {
+            return Config::load_from_dir(dir);
+        }
+        if let Some(config) = lock(&self.configs).get(dir) {
+            return Ok(config.clone());
+        }
+        let config = Config::load_from_dir(dir)?;
+        lock(&self.configs).insert(dir.to_path_buf(), config.clone());
+        Ok(config)
+    }
+
+    /// Every file under `dir`, sorted, without descending into directories named in `excluded_dirs`
+    pub fn walk(&self, dir: &Path, excluded_dirs: &[String]) -> Arc<Vec<PathBuf>> {
+        let key = (dir.to_path_buf(), excluded_dirs.to_vec());
+        if self.shared {
+            if let Some(files) = lock(&self.walks).get(&key) {
+                return files.clone();
+            }
+        }
+        let mut files: Vec<PathBuf> = WalkDir::new(dir)
+            .into_iter()
+            .filter_entry(|entry| !entry.file_type().is_dir() || entry.depth() == 0 || !is_excluded_dir(entry.path(), excluded_dirs))
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+        files.sort();
+        let files = Arc::new(files);
+        if self.shared {
+            lock(&self.walks).insert(key, files.clone());
+        }
+        files
+    }
+
+    /// The file's text, as `fs::read_to_string` gives it
+    pub fn read(&self, path: &Path) -> io::Result<Arc<str>> {
+        if !self.shared {
+            return std::fs::read_to_string(path).map(Arc::from);
+        }
+        if let Some(content) = lock(&self.contents).get(path) {
+            return Ok(content.clone());
+        }
+        let content: Arc<str> = std::fs::read_to_string(path)?.into();
+        lock(&self.contents).insert(path.to_path_buf(), content.clone());
+        Ok(content)
+    }
+
+    /// The file's text, as `read_source` gives it: `None` for oversized and binary files
+    pub fn read_source(&self, path: &Path) -> io::Result<Option<Arc<str>>> {
+        if !self.shared || std::fs::metadata(path)?.len() > MAX_SOURCE_BYTES {
+            return Ok(read_source(path)?.map(Arc::from));
+        }
+        match self.read(path) {
+            Ok(content) if is_binary(content.as_bytes()) => Ok(None),
+            Ok(content) => Ok(Some(content)),
+            // Not UTF-8: `read_source` replaces what it cannot decode, which is not worth keeping
+            Err(error) if error.kind() == io::ErrorKind::InvalidData => Ok(read_source(path)?.map(Arc::from)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// The file's line count, as `count_lines_optimized` gives it; from the text already read
+    /// when there is one
+    pub fn line_count(&self, path: &Path) -> io::Result<usize> {
+        if !self.shared {
+            return count_lines_optimized(path);
+        }
+        match self.read_source(path)? {
+            Some(content) => Ok(content.lines().count()),
+            None => count_lines_optimized(path),
+        }
+    }
+
+    /// What `parse` makes of `content`, the text of `path`, parsed once per kind of result;
+    /// e.g. the file's imports
+    pub fn parsed<T: Send + Sync + 'static>(&self, path: &Path, content: &str, parse: impl FnOnce(&str) -> T) -> Arc<T> {
+        if !self.shared {
+            return Arc::new(parse(content));
+        }
+        let key = (path.to_path_buf(), TypeId::of::<T>());
+        if let Some(parsed) = lock(&self.parsed).get(&key).and_then(|parsed| parsed.clone().downcast::<T>().ok()) {
+            return parsed;
+        }
+        let parsed = Arc::new(parse(content));
+        lock(&self.parsed).insert(key, parsed.clone());
+        parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shared_context_reads_each_file_once() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("a.ts");
+        fs::write(&file, "export const a = 1;\n").unwrap();
+
+        let shared = AnalysisContext::shared();
+        let unshared = AnalysisContext::default();
+        assert_eq!(&*shared.read(&file).unwrap(), "export const a = 1;\n");
+        fs::write(&file, "export const a = 2;\nexport const b = 3;\n").unwrap();
+        assert_eq!(&*shared.read(&file).unwrap(), "export const a = 1;\n");
+        assert_eq!(shared.line_count(&file).unwrap(), 1);
+        assert_eq!(unshared.line_count(&file).unwrap(), 2);
+
+        let content = shared.read(&file).unwrap();
+        let lines = shared.parsed(&file, &content, |content| content.lines().count());
+        let again = shared.parsed(&file, &content, |_| unreachable!("parsed twice"));
+        assert!(Arc::ptr_eq(&lines, &again));
+    }
+
+    #[test]
+    fn test_walk_skips_excluded_directories() {
+        let dir = TempDir::new().unwrap();
+        for file in ["src/a.ts", "src/b.ts", "node_modules/pkg/index.js"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+
+        let context = AnalysisContext::shared();
+        let excluded = vec!["node_modules".to_string()];
+        let files = context.walk(dir.path(), &excluded);
+        let names: Vec<_> = files.iter().map(|path| path.strip_prefix(dir.path()).unwrap().to_path_buf()).collect();
+        assert_eq!(names, vec![PathBuf::from("src/a.ts"), PathBuf::from("src/b.ts")]);
+        assert!(Arc::ptr_eq(&files, &context.walk(dir.path(), &excluded)));
+        assert_eq!(context.walk(dir.path(), &[]).len(), 3);
+    }
+}
@@ -0,0 +1,198 @@
+/// Findings with their location, printed as GitHub Actions workflow commands
+/// (`::error file=...::message`) for `--format github`, as test cases for `--format junit`
+/// and as SARIF results for `--format sarif`
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use super::report_formatter::Severity;
+use super::output;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnnotationLevel {
+    /// Shown as an error; `--fail-on critical` fails only on these
+    Critical,
+    Error,
+    Warning,
+    Notice,
+}
+
+impl AnnotationLevel {
+    /// High findings are errors; low and info are notices
+    pub fn for_severity(severity: &Severity) -> Self {
+        match severity {
+            Severity::Critical => AnnotationLevel::Critical,
+            Severity::High => AnnotationLevel::Error,
+            Severity::Medium => AnnotationLevel::Warning,
+            Severity::Low | Severity::Info => AnnotationLevel::Notice,
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            AnnotationLevel::Critical | AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        }
+    }
+}
+
+/// One finding, shown inline on the PR diff
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub level: AnnotationLevel,
+    /// Path relative to the project root, or absolute with `--absolute-paths`; project-wide
+    /// findings such as a missing env variable have none
+    pub file: Option<String>,
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<usize>,
+    pub title: String,
+    pub message: String,
+    /// Identifier of the check that produced the finding, e.g. `SNF-A11Y-IMG-ALT`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+}
+
+impl Annotation {
+    pub fn new(level: AnnotationLevel, file: impl Into<String>, line: usize, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            file: Some(file.into()),
+            line: Some(line),
+            end_line: None,
+            title: title.into(),
+            message: message.into(),
+            rule: None,
+        }
+    }
+
+    /// A finding about a file as a whole
+    pub fn file(level: AnnotationLevel, file: impl Into<String>, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { line: None, ..Self::new(level, file, 0, title, message) }
+    }
+
+    /// A finding about the project rather than a file
+    pub fn project(level: AnnotationLevel, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { file: None, line: None, ..Self::new(level, "", 0, title, message) }
+    }
+
+    pub fn with_end_line(mut self, end_line: usize) -> Self {
+        self.end_line = Some(end_line);
+        self
+    }
+
+    pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+
+    /// The title followed by the rule id, e.g. `Unused import [SNF-IMP-UNUSED]`
+    pub fn labeled_title(&self) -> String {
+        match &self.rule {
+            Some(rule) if !self.title.contains(rule.as_str()) => format!("{} [{}]", self.title, rule),
+            _ => self.title.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "::{} ", self.level.command())?;
+        if let Some(file) = &self.file {
+            write!(f, "file={},", escape_property(&workspace_path(file)))?;
+            if let Some(line) = self.line {
+                write!(f, "line={},", line)?;
+            }
+            if let Some(end_line) = self.end_line {
+                write!(f, "endLine={},", end_line)?;
+            }
+        }
+        write!(f, "title={}::{}", escape_property(&self.labeled_title()), escape_data(&self.message))
+    }
+}
+
+/// Reports that can list their findings for CI
+pub trait Annotate {
+    fn annotations(&self) -> Vec<Annotation>;
+
+    /// `annotations` in the order every output prints them, see `sort_findings`
+    fn findings(&self) -> Vec<Annotation> {
+        let mut findings = self.annotations();
+        sort_findings(&mut findings);
+        findings
+    }
+}
+
+/// Order findings by path, line and rule, then title and message, so two runs over the same
+/// tree print the same output whatever order the analysis produced them in. Project-wide
+/// findings come first
+pub fn sort_findings(findings: &mut [Annotation]) {
+    findings.sort_by(|a, b| {
+        (&a.file, a.line, &a.rule, &a.title, &a.message).cmp(&(&b.file, b.line, &b.rule, &b.title, &b.message))
+    });
+}
+
+/// Print one workflow command per finding
+pub fn print_annotations<R: Annotate>(report: &R) {
+    for annotation in report.findings() {
+        output::line(annotation);
+    }
+}
+
+/// GitHub resolves annotation paths against the checkout, not the project root
+pub(crate) fn workspace_path(file: &str) -> String {
+    let absolute = super::paths::resolve(file);
+    let workspace = std::env::var_os("GITHUB_WORKSPACE").map(PathBuf::from).unwrap_or_else(super::paths::root);
+    let relative: &Path = absolute.strip_prefix(&workspace).unwrap_or(&absolute);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_escapes_workflow_command_syntax() {
+        let annotation = Annotation::new(AnnotationLevel::Warning, "src/a,b.ts", 3, "Unused import: x", "50% unused\nremove it")
+            .with_end_line(5);
+        assert_eq!(
+            annotation.to_string(),
+            "::warning file=src/a%2Cb.ts,line=3,endLine=5,title=Unused import%3A x::50%25 unused%0Aremove it"
+        );
+        let annotation = Annotation::project(AnnotationLevel::Error, "Missing variable", "DATABASE_URL is not set");
+        assert_eq!(annotation.to_string(), "::error title=Missing variable::DATABASE_URL is not set");
+        let annotation = Annotation::file(AnnotationLevel::Warning, "src/a.ts", "Large file (Warning)", "120 lines").with_rule("SNF-LARGE-001");
+        assert_eq!(annotation.to_string(), "::warning file=src/a.ts,title=Large file (Warning) [SNF-LARGE-001]::120 lines");
+    }
+
+    #[test]
+    fn test_findings_sort_by_path_line_and_rule() {
+        let mut findings = vec![
+            Annotation::new(AnnotationLevel::Warning, "src/b.ts", 1, "Unused import", "Unused: a").with_rule("SNF-IMP-UNUSED"),
+            Annotation::new(AnnotationLevel::Error, "src/a.ts", 9, "Broken import", "File not found").with_rule("SNF-IMP-BROKEN"),
+            Annotation::new(AnnotationLevel::Warning, "src/a.ts", 2, "Unused import", "Unused: b").with_rule("SNF-IMP-UNUSED"),
+            Annotation::new(AnnotationLevel::Error, "src/a.ts", 2, "Broken import", "File not found").with_rule("SNF-IMP-BROKEN"),
+            Annotation::project(AnnotationLevel::Error, "Missing variable", "API_KEY is not set").with_rule("SNF-ENV-MISSING"),
+        ];
+        sort_findings(&mut findings);
+        let order: Vec<(Option<&str>, Option<usize>, Option<&str>)> = findings.iter()
+            .map(|finding| (finding.file.as_deref(), finding.line, finding.rule.as_deref()))
+            .collect();
+        assert_eq!(order, vec![
+            (None, None, Some("SNF-ENV-MISSING")),
+            (Some("src/a.ts"), Some(2), Some("SNF-IMP-BROKEN")),
+            (Some("src/a.ts"), Some(2), Some("SNF-IMP-UNUSED")),
+            (Some("src/a.ts"), Some(9), Some("SNF-IMP-BROKEN")),
+            (Some("src/b.ts"), Some(1), Some("SNF-IMP-UNUSED")),
+        ]);
+    }
+}
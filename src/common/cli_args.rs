@@ -1,5 +1,9 @@
 /// Common CLI argument patterns shared across commands
-use clap::Args;
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+use anyhow::{anyhow, Result};
+use super::file_scanner::ScanTarget;
+use crate::config::FileFilter;
 
 /// Standard output options available to all commands
 #[derive(Args, Clone)]
@@ -11,6 +15,88 @@ pub struct OutputOptions {
     pub quiet: bool,
 }
 
+/// How a command prints its report
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable report
+    #[default]
+    Text,
+    /// The report as JSON (same as --json)
+    Json,
+    /// GitHub Actions workflow commands, shown inline on PR diffs
+    Github,
+    /// JUnit XML for CI test-report views
+    Junit,
+    /// SARIF 2.1.0 for GitHub code scanning and other security dashboards
+    Sarif,
+    /// Compact Markdown digest to paste into an AI assistant (`sniff context`)
+    Llm,
+    /// One finding per line as JSON, printed while files are still being analyzed
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Formats that list findings instead of printing the report
+    pub fn lists_findings(self) -> bool {
+        matches!(self, OutputFormat::Github | OutputFormat::Junit | OutputFormat::Sarif | OutputFormat::Ndjson)
+    }
+}
+
+/// The lowest finding level that fails the run (`--fail-on`)
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FailOn {
+    /// Always exit 0 when the command itself ran
+    Never,
+    /// Only critical findings fail
+    Critical,
+    /// Critical and error findings fail
+    Error,
+    /// Any finding except notices fails
+    Warning,
+}
+
+/// What the text report groups findings by (`--group-by`)
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    File,
+    /// The kind of finding, e.g. `Broken import`
+    Rule,
+    Severity,
+    /// The directory holding the file
+    Directory,
+}
+
+/// The order of findings in the text report (`--sort`)
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortBy {
+    /// Longest file, function or component first
+    Lines,
+    /// Largest file first
+    Size,
+    /// Most severe first
+    Severity,
+    /// By path, then line
+    Path,
+}
+
+/// `--group-by` and `--sort`, which list the findings of `large`, `imports`, `memory` and
+/// `components` in one layout instead of the command's own
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct ListingOptions {
+    #[arg(long, value_enum, value_name = "KEY", help = "Group findings in the report by file, rule, severity or directory")]
+    pub group_by: Option<GroupBy>,
+    
+    #[arg(long, value_enum, value_name = "KEY", help = "Sort findings in the report by lines, size, severity or path")]
+    pub sort: Option<SortBy>,
+}
+
+impl ListingOptions {
+    /// Neither option given: the command prints its own report
+    pub fn is_default(&self) -> bool {
+        self.group_by.is_none() && self.sort.is_none()
+    }
+}
+
 /// Common threshold-based command options
 #[derive(Args, Clone)]
 pub struct ThresholdOptions {
@@ -37,6 +123,51 @@ pub struct FileFilterOptions {
     pub pattern: Option<String>,
 }
 
+/// Directories a command should analyze
+#[derive(Args, Clone, Default)]
+pub struct TargetOptions {
+    #[arg(value_name = "PATH", help = "Directories to analyze, relative to the project root (default: the whole project)")]
+    pub paths: Vec<PathBuf>,
+    
+    #[arg(long, value_name = "DIR", help = "Project root (default: current directory)")]
+    pub root: Option<PathBuf>,
+    
+    #[arg(long, value_name = "GLOB", help = "Only analyze files matching this glob (repeatable; without `/` it matches the file name)")]
+    pub include: Vec<String>,
+    
+    #[arg(long, value_name = "GLOB", help = "Skip files matching this glob (repeatable)")]
+    pub exclude: Vec<String>,
+}
+
+impl TargetOptions {
+    /// Resolve the root and paths against the current directory, checking that they exist
+    pub fn resolve(&self) -> Result<ScanTarget> {
+        let cwd = std::env::current_dir()?;
+        let root = match &self.root {
+            Some(root) => cwd.join(root),
+            None => cwd,
+        };
+        if !root.is_dir() {
+            return Err(anyhow!("Project root '{}' is not a directory", root.display()));
+        }
+        super::paths::set_root(&root);
+        
+        let mut paths = Vec::new();
+        for path in &self.paths {
+            let resolved = root.join(path);
+            if !resolved.exists() {
+                return Err(anyhow!("Path '{}' does not exist", path.display()));
+            }
+            paths.push(resolved);
+        }
+        
+        Ok(ScanTarget::new(root, paths).with_files(FileFilter {
+            include: self.include.clone(),
+            exclude: self.exclude.clone(),
+        }))
+    }
+}
+
 /// Common validation options
 #[derive(Args, Clone)]
 pub struct ValidationOptions {
@@ -1,21 +1,165 @@
 /// Common error handling utilities
+use super::annotations::Annotation;
+use super::ndjson::LevelCounts;
+use super::cli_args::FailOn;
 
-/// Standard error codes for different failure types
+/// Standard error codes for different failure types.
+///
+/// Under `--fail-on`/`--max-warnings` the code names the most severe class of
+/// finding that failed the run: 3 critical, 2 error, 5 too many warnings.
 #[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ExitCode {
     Success = 0,
+    /// The command could not run (bad arguments, I/O or tool failures)
     GeneralError = 1,
+    /// Error-level findings
     ValidationFailed = 2,
+    /// Critical findings, or a command threshold was exceeded
     ThresholdExceeded = 3,
     ConfigurationError = 4,
+    /// More warnings than `--max-warnings` allows
+    WarningsExceeded = 5,
 }
 
-/// Check if issues exceed failure thresholds and exit appropriately
-pub fn check_failure_threshold(has_critical_issues: bool, exit_code: ExitCode) {
+impl ExitCode {
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    pub fn is_success(self) -> bool {
+        self == ExitCode::Success
+    }
+
+    /// The exit code a child `sniff` process reported; unknown codes are general errors
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => ExitCode::Success,
+            2 => ExitCode::ValidationFailed,
+            3 => ExitCode::ThresholdExceeded,
+            4 => ExitCode::ConfigurationError,
+            5 => ExitCode::WarningsExceeded,
+            _ => ExitCode::GeneralError,
+        }
+    }
+}
+
+/// Result of a command run: the typed report plus the exit status it asks for.
+///
+/// Commands never terminate the process themselves; `main` (or a pipeline such
+/// as `sniff deploy`) decides what to do with the exit code.
+#[derive(Debug)]
+pub struct CommandOutcome<R> {
+    pub report: R,
+    pub exit_code: ExitCode,
+}
+
+impl<R> CommandOutcome<R> {
+    pub fn new(report: R, exit_code: ExitCode) -> Self {
+        Self { report, exit_code }
+    }
+}
+
+impl<R> From<CommandOutcome<R>> for ExitCode {
+    fn from(outcome: CommandOutcome<R>) -> Self {
+        outcome.exit_code
+    }
+}
+
+/// Map a failure condition to the exit code a command should report
+pub fn check_failure_threshold(has_critical_issues: bool, exit_code: ExitCode) -> ExitCode {
     if has_critical_issues {
-        // Flush stdout so buffered output (e.g. JSON) is written before the process terminates.
-        let _ = std::io::Write::flush(&mut std::io::stdout() as &mut dyn std::io::Write);
-        std::process::exit(exit_code as i32);
+        exit_code
+    } else {
+        ExitCode::Success
+    }
+}
+
+/// How strict a run is, from the global `--fail-on` and `--max-warnings` flags.
+/// With neither set every command keeps its own exit code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FailPolicy {
+    pub fail_on: Option<FailOn>,
+    pub max_warnings: Option<usize>,
+}
+
+impl FailPolicy {
+    pub fn is_default(&self) -> bool {
+        self.fail_on.is_none() && self.max_warnings.is_none()
+    }
+
+    /// The exit code for a run with these findings. `command_code` is what the
+    /// command reported; it stands when the policy is unset, and when the
+    /// command failed without any finding to classify (e.g. tsc could not run).
+    pub fn exit_code(&self, findings: &[Annotation], command_code: ExitCode) -> ExitCode {
+        self.exit_code_for_counts(LevelCounts::from_findings(findings), command_code)
+    }
+
+    /// Like `exit_code`, for a streamed run that kept only how many findings it wrote
+    pub fn exit_code_for_counts(&self, counts: LevelCounts, command_code: ExitCode) -> ExitCode {
+        if self.is_default() {
+            return command_code;
+        }
+        if self.fail_on == Some(FailOn::Never) {
+            return ExitCode::Success;
+        }
+        if counts.total() == 0 {
+            return command_code;
+        }
+
+        let LevelCounts { critical, errors, warnings, .. } = counts;
+        // --max-warnings alone keeps failing on errors, like eslint
+        let fail_on = self.fail_on.unwrap_or(FailOn::Error);
+        let max_warnings = match fail_on {
+            FailOn::Warning => self.max_warnings.unwrap_or(0),
+            _ => self.max_warnings.unwrap_or(usize::MAX),
+        };
+
+        if critical > 0 && fail_on >= FailOn::Critical {
+            ExitCode::ThresholdExceeded
+        } else if errors > 0 && fail_on >= FailOn::Error {
+            ExitCode::ValidationFailed
+        } else if warnings > max_warnings {
+            ExitCode::WarningsExceeded
+        } else {
+            ExitCode::Success
+        }
     }
 }
 
+/// Flush buffered output and terminate with the given exit code.
+///
+/// Only the binary entry point should call this. Stdout is flushed first so
+/// buffered output (e.g. JSON) is written before the process terminates.
+pub fn exit_with(exit_code: ExitCode) -> ! {
+    let _ = std::io::Write::flush(&mut std::io::stdout());
+    std::process::exit(exit_code.code());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::AnnotationLevel;
+
+    fn findings(levels: &[AnnotationLevel]) -> Vec<Annotation> {
+        levels.iter().map(|level| Annotation::new(*level, "src/a.ts", 1, "Finding", "message")).collect()
+    }
+
+    #[test]
+    fn test_fail_policy_picks_exit_code_for_worst_failing_class() {
+        let policy = |fail_on, max_warnings| FailPolicy { fail_on, max_warnings };
+        let mixed = findings(&[AnnotationLevel::Critical, AnnotationLevel::Error, AnnotationLevel::Warning]);
+        let warnings = findings(&[AnnotationLevel::Warning, AnnotationLevel::Warning, AnnotationLevel::Notice]);
+
+        assert_eq!(policy(None, None).exit_code(&warnings, ExitCode::ThresholdExceeded), ExitCode::ThresholdExceeded);
+        assert_eq!(policy(Some(FailOn::Never), None).exit_code(&mixed, ExitCode::ThresholdExceeded), ExitCode::Success);
+        assert_eq!(policy(Some(FailOn::Critical), None).exit_code(&mixed, ExitCode::Success), ExitCode::ThresholdExceeded);
+        assert_eq!(policy(Some(FailOn::Critical), None).exit_code(&mixed[1..], ExitCode::Success), ExitCode::Success);
+        assert_eq!(policy(Some(FailOn::Error), None).exit_code(&mixed[1..], ExitCode::Success), ExitCode::ValidationFailed);
+        assert_eq!(policy(Some(FailOn::Error), None).exit_code(&warnings, ExitCode::ValidationFailed), ExitCode::Success);
+        assert_eq!(policy(Some(FailOn::Warning), None).exit_code(&warnings, ExitCode::Success), ExitCode::WarningsExceeded);
+        assert_eq!(policy(Some(FailOn::Warning), Some(2)).exit_code(&warnings, ExitCode::Success), ExitCode::Success);
+        assert_eq!(policy(None, Some(1)).exit_code(&warnings, ExitCode::Success), ExitCode::WarningsExceeded);
+        assert_eq!(policy(Some(FailOn::Error), None).exit_code(&[], ExitCode::GeneralError), ExitCode::GeneralError);
+    }
+}
@@ -1,62 +1,170 @@
 // Unified file scanning and filtering utilities
 
 use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
-use crate::config::Config;
+use std::sync::Arc;
+use crate::config::{Config, FileFilter};
+use crate::utils::FileUtils;
+use super::AnalysisContext;
+
+/// Resolved analysis target: the project root plus the directories to scan
+#[derive(Debug, Clone)]
+pub struct ScanTarget {
+    pub root: PathBuf,
+    pub paths: Vec<PathBuf>,
+    /// `--include`/`--exclude` globs, applied on top of the command's configured ones
+    pub files: FileFilter,
+    /// Files and config already read for this target; clones of the target share it
+    pub context: Arc<AnalysisContext>,
+}
+
+impl ScanTarget {
+    /// Scan `paths` inside `root`; an empty list scans the whole root
+    pub fn new(root: PathBuf, paths: Vec<PathBuf>) -> Self {
+        let paths = if paths.is_empty() { vec![root.clone()] } else { paths };
+        Self { root, paths, files: FileFilter::default(), context: Arc::default() }
+    }
+    
+    /// Keep what commands read from the project for the next command run on this target, for
+    /// pipelines that analyze an unchanged tree several times (`deploy`, `report`)
+    pub fn with_shared_context(self) -> Self {
+        Self { context: Arc::new(AnalysisContext::shared()), ..self }
+    }
+    
+    /// Only scan files matching `include` (when given) and none matching `exclude`
+    pub fn with_files(self, files: FileFilter) -> Self {
+        Self { files, ..self }
+    }
+    
+    /// The single project directory for project-level commands (env, bundle, context).
+    /// These commands accept at most one path.
+    pub fn project_dir(&self, command: &str) -> anyhow::Result<&Path> {
+        match self.paths.as_slice() {
+            [path] => Ok(path),
+            _ => Err(anyhow::anyhow!("`sniff {}` analyzes a single project; pass at most one path", command)),
+        }
+    }
+    
+    /// Where config files are discovered from: the single path when one is given
+    /// (so a package's own sniff.toml applies), else the root
+    pub fn config_dir(&self) -> &Path {
+        match self.paths.as_slice() {
+            [path] if path.is_file() => path.parent().unwrap_or(&self.root),
+            [path] => path,
+            _ => &self.root,
+        }
+    }
+    
+    /// The configuration for what is analyzed, merged from every config file above `config_dir`
+    pub fn config(&self) -> anyhow::Result<Config> {
+        self.context.config(self.config_dir())
+    }
+    
+    /// The `[[ignore]]` entries findings in this target are filtered by
+    pub fn ignores(&self) -> anyhow::Result<super::Ignores> {
+        Ok(super::Ignores::new(&self.root, &self.config()?.ignore))
+    }
+    
+    /// Narrow the target to files staged in git. Nothing staged leaves no paths,
+    /// rather than falling back to the whole root like `new`.
+    pub fn staged(self) -> anyhow::Result<Self> {
+        // git reports paths from the resolved top level; compare canonical paths so a
+        // symlinked checkout still matches, then report them under `root` as usual
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let root = canonical(&self.root);
+        let scopes: Vec<PathBuf> = self.paths.iter().map(|path| canonical(path)).collect();
+        let paths = super::git::staged_files(&self.root)?
+            .into_iter()
+            .filter(|file| file.is_file())
+            .map(|file| canonical(&file))
+            .filter(|file| scopes.iter().any(|scope| file.starts_with(scope)))
+            .filter_map(|file| file.strip_prefix(&root).ok().map(|relative| self.root.join(relative)))
+            .collect();
+        Ok(Self { paths, ..self })
+    }
+}
 
 /// Common file scanner with unified exclusion and filtering logic
 pub struct FileScanner {
     config: Config,
+    root: PathBuf,
+    files: FileFilter,
+    context: Arc<AnalysisContext>,
 }
 
 impl FileScanner {
-    /// Create a file scanner with default configuration
-    pub fn with_defaults() -> Self {
-        Self {
-            config: Config::load().unwrap_or_default(),
-        }
+    /// Create a file scanner using the configuration found in `root`
+    pub fn for_root(root: &Path) -> anyhow::Result<Self> {
+        Ok(Self {
+            config: Config::load_from_dir(root)?,
+            root: root.to_path_buf(),
+            files: FileFilter::default(),
+            context: Arc::default(),
+        })
+    }
+
+    /// A scanner that also applies the `include`/`exclude` globs of `command`'s config section
+    pub fn for_command(target: &ScanTarget, command: &str) -> anyhow::Result<Self> {
+        let config = target.config()?;
+        let files = config.files.get(command).cloned().unwrap_or_default();
+        Ok(Self { config, root: target.root.clone(), files, context: target.context.clone() })
+    }
+
+    /// Find files with specific extensions under every path of the target
+    pub fn find_target_files(&self, target: &ScanTarget, extensions: &[&str]) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = target.paths.iter()
+            .flat_map(|path| self.find_files_with_extensions(path, extensions))
+            .filter(|path| self.is_selected(path, &target.files))
+            .collect();
+        // Overlapping paths (e.g. `src` and `src/features`) must not report a file twice
+        files.sort();
+        files.dedup();
+        files
     }
 
     /// Find files with specific extensions, applying all exclusion rules
     pub fn find_files_with_extensions(&self, dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
-        WalkDir::new(dir)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-            .filter(|e| self.has_extension(e.path(), extensions))
-            .filter(|e| !self.is_excluded_path(e.path()))
-            .map(|e| e.path().to_path_buf())
+        self.files_under(dir)
+            .iter()
+            .filter(|path| self.has_extension(path, extensions))
+            .filter(|path| !self.is_excluded_path(path))
+            .filter(|path| self.is_selected(path, &self.files))
+            .cloned()
             .collect()
     }
 
-    /// Find TypeScript/JavaScript files specifically
-    pub fn find_js_ts_files(&self, dir: &Path) -> Vec<PathBuf> {
-        self.find_files_with_extensions(dir, &["ts", "tsx", "js", "jsx"])
+    /// Every file under `dir` outside excluded directories, walked once per shared context
+    pub fn files_under(&self, dir: &Path) -> Arc<Vec<PathBuf>> {
+        self.context.walk(dir, &self.config.large_files.excluded_dirs)
+    }
+
+    /// Walk and read through `context` instead of the target's, e.g. a target's shared one
+    pub fn sharing(self, context: &Arc<AnalysisContext>) -> Self {
+        Self { context: context.clone(), ..self }
+    }
+
+    /// Whether `path` passes the command's globs and the target's `--include`/`--exclude`,
+    /// for commands that walk the file tree themselves
+    pub fn is_target_file(&self, path: &Path, target: &ScanTarget) -> bool {
+        self.is_selected(path, &self.files) && self.is_selected(path, &target.files)
+    }
+
+    fn is_selected(&self, path: &Path, files: &FileFilter) -> bool {
+        if files.include.is_empty() && files.exclude.is_empty() {
+            return true;
+        }
+        let relative = FileUtils::slash_path(path.strip_prefix(&self.root).unwrap_or(path));
+        (files.include.is_empty() || matches_any_glob(&relative, &files.include))
+            && !matches_any_glob(&relative, &files.exclude)
     }
 
     /// Check if a path should be excluded based on configuration
     pub fn is_excluded_path(&self, path: &Path) -> bool {
         // Check if any ancestor directory is excluded
-        path.ancestors().any(|ancestor| {
-            if let Some(name) = ancestor.file_name() {
-                let name_str = name.to_string_lossy();
-                
-                // Check against configured excluded directories
-                self.config.large_files.excluded_dirs.iter().any(|excluded| {
-                    if excluded.contains('*') {
-                        // Simple glob matching
-                        let pattern = excluded.replace('*', ".*");
-                        regex::Regex::new(&pattern)
-                            .map(|re| re.is_match(&name_str))
-                            .unwrap_or(false)
-                    } else {
-                        name_str == excluded.as_str()
-                    }
-                })
-            } else {
-                false
-            }
-        }) || self.is_excluded_file(path)
+        path.ancestors().any(|ancestor| self.is_excluded_dir(ancestor)) || self.is_excluded_file(path)
+    }
+
+    fn is_excluded_dir(&self, dir: &Path) -> bool {
+        is_excluded_dir(dir, &self.config.large_files.excluded_dirs)
     }
 
     /// Check if a file should be excluded based on filename patterns
@@ -88,6 +196,89 @@ impl FileScanner {
 
 }
 
+/// Whether the directory's name is one of `excluded_dirs`, which may use `*` wildcards
+pub(crate) fn is_excluded_dir(dir: &Path, excluded_dirs: &[String]) -> bool {
+    let Some(name) = dir.file_name() else {
+        return false;
+    };
+    let name_str = name.to_string_lossy();
+    excluded_dirs.iter().any(|excluded| {
+        if excluded.contains('*') {
+            // Simple glob matching
+            let pattern = excluded.replace('*', ".*");
+            regex::Regex::new(&pattern)
+                .map(|re| re.is_match(&name_str))
+                .unwrap_or(false)
+        } else {
+            name_str == excluded.as_str()
+        }
+    })
+}
+
+/// Whether a `/`-separated relative path matches one of `patterns`; patterns with `/` match
+/// from the project root, others the file name
+pub fn matches_any_glob(relative: &str, patterns: &[String]) -> bool {
+    let file_name = relative.rsplit('/').next().unwrap_or(relative);
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.trim_start_matches("./");
+        if pattern.contains('/') {
+            glob_matches(pattern, relative)
+        } else {
+            glob_matches(pattern, file_name)
+        }
+    })
+}
+
+/// Match a `/`-separated relative path against a glob: `**` spans directories,
+/// `*` and `?` stay within one path segment, `{a,b}` matches either alternative
+pub fn glob_matches(pattern: &str, path: &str) -> bool {
+    glob_regex(pattern).is_ok_and(|re| re.is_match(path))
+}
+
+/// Why `pattern` is not a usable glob, if it is not
+pub fn glob_error(pattern: &str) -> Option<String> {
+    if pattern.trim().is_empty() {
+        return Some("empty glob".to_string());
+    }
+    if pattern.matches('{').count() > pattern.matches('}').count() {
+        return Some("unclosed `{`".to_string());
+    }
+    glob_regex(pattern).err().map(|e| e.to_string())
+}
+
+fn glob_regex(pattern: &str) -> Result<regex::Regex, regex::Error> {
+    let mut regex = String::from("^");
+    let mut chars = pattern.chars().peekable();
+    let mut braces = 0usize;
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                braces += 1;
+                regex.push_str("(?:");
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+                regex.push(')');
+            }
+            ',' if braces > 0 => regex.push('|'),
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    regex.push_str("(?:.*/)?");
+                } else {
+                    regex.push_str(".*");
+                }
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            other => regex.push_str(&regex::escape(&other.to_string())),
+        }
+    }
+    regex.push('$');
+    regex::Regex::new(&regex)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,7 +286,7 @@ mod tests {
 
     #[test]
     fn test_extension_checking() {
-        let scanner = FileScanner::with_defaults();
+        let scanner = FileScanner::for_root(Path::new(".")).unwrap();
         
         assert!(scanner.has_extension(&PathBuf::from("test.ts"), &["ts", "js"]));
         assert!(!scanner.has_extension(&PathBuf::from("test.py"), &["ts", "js"]));
@@ -103,16 +294,70 @@ mod tests {
 
     #[test]
     fn test_js_ts_file_detection() {
-        let scanner = FileScanner::with_defaults();
+        let scanner = FileScanner::for_root(Path::new(".")).unwrap();
 
         assert!(scanner.has_extension(&PathBuf::from("component.tsx"), &["ts", "tsx", "js", "jsx"]));
         assert!(scanner.has_extension(&PathBuf::from("utils.js"), &["ts", "tsx", "js", "jsx"]));
         assert!(!scanner.has_extension(&PathBuf::from("styles.css"), &["ts", "tsx", "js", "jsx"]));
     }
 
+    #[test]
+    fn test_target_files_are_deduplicated() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let nested = temp_dir.path().join("src").join("features");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("auth.ts"), "export const auth = true;").unwrap();
+
+        let target = ScanTarget::new(
+            temp_dir.path().to_path_buf(),
+            vec![temp_dir.path().join("src"), nested.clone()],
+        );
+        let scanner = FileScanner::for_root(&target.root).unwrap();
+
+        assert_eq!(scanner.find_target_files(&target, &["ts"]), vec![nested.join("auth.ts")]);
+    }
+
+    #[test]
+    fn test_command_and_target_globs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("src/generated")).unwrap();
+        let mut config: toml::Table = toml::from_str(&toml::to_string(&Config::default()).unwrap()).unwrap();
+        config["memory"].as_table_mut().unwrap().insert("exclude".to_string(), toml::Value::from(vec!["*.stories.tsx", "src/generated/**"]));
+        std::fs::write(root.join("sniff.toml"), toml::to_string(&config).unwrap()).unwrap();
+        for file in ["src/Button.tsx", "src/Button.stories.tsx", "src/generated/api.ts", "src/utils.ts"] {
+            std::fs::write(root.join(file), "export {};").unwrap();
+        }
+
+        let target = ScanTarget::new(root.to_path_buf(), Vec::new());
+        let found = |scanner: FileScanner, target: &ScanTarget| -> Vec<PathBuf> {
+            scanner.find_target_files(target, &["ts", "tsx"]).into_iter()
+                .map(|path| path.strip_prefix(root).unwrap().to_path_buf())
+                .collect()
+        };
+        assert_eq!(found(FileScanner::for_command(&target, "memory").unwrap(), &target), vec![PathBuf::from("src/Button.tsx"), PathBuf::from("src/utils.ts")]);
+        assert_eq!(found(FileScanner::for_command(&target, "large").unwrap(), &target).len(), 4);
+
+        let target = target.with_files(FileFilter { include: vec!["*.tsx".to_string()], exclude: Vec::new() });
+        assert_eq!(found(FileScanner::for_command(&target, "memory").unwrap(), &target), vec![PathBuf::from("src/Button.tsx")]);
+    }
+
+    #[test]
+    fn test_glob_matching() {
+        assert!(glob_matches("app/dashboard/**", "app/dashboard/settings/page-1a2b.js"));
+        assert!(glob_matches("**/page-*.js", "app/page-1a2b.js"));
+        assert!(glob_matches("**/page-*.js", "page-1a2b.js"));
+        assert!(!glob_matches("app/*.js", "app/dashboard/page.js"));
+        assert!(!glob_matches("app/dashboard/**", "app/settings/page.js"));
+        assert!(glob_matches("src/**/*.{js,tsx}", "src/components/Card.tsx"));
+        assert!(!glob_matches("src/**/*.{js,tsx}", "src/components/Card.ts"));
+        assert_eq!(glob_error("src/**/*.{ts,tsx"), Some("unclosed `{`".to_string()));
+        assert_eq!(glob_error("src/**/*.{ts,tsx}"), None);
+    }
+
     #[test]
     fn test_exclusion_patterns() {
-        let scanner = FileScanner::with_defaults();
+        let scanner = FileScanner::for_root(Path::new(".")).unwrap();
         
         // Should exclude node_modules by default
         assert!(scanner.is_excluded_path(&PathBuf::from("./node_modules/package/file.js")));
@@ -0,0 +1,275 @@
+/// Lightweight function/component block detection for JS/TS sources
+///
+/// This is not a real parser: signatures are matched line by line with regexes and
+/// bodies are found by matching delimiters while skipping strings and comments.
+/// It is accurate enough to point at long functions, not to build an AST.
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FunctionKind {
+    Function,
+    ArrowFunction,
+    Method,
+    Component,
+}
+
+/// A function body located in a source file (1-based, inclusive line numbers)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionSpan {
+    pub name: String,
+    pub kind: FunctionKind,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub lines: usize,
+}
+
+struct FunctionPatterns {
+    declaration: Regex,
+    arrow: Regex,
+    function_expression: Regex,
+    method: Regex,
+}
+
+static FUNCTION_PATTERNS: OnceLock<FunctionPatterns> = OnceLock::new();
+
+fn get_function_patterns() -> &'static FunctionPatterns {
+    FUNCTION_PATTERNS.get_or_init(|| FunctionPatterns {
+        declaration: Regex::new(r"^\s*(?:export\s+)?(?:default\s+)?(?:async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)\s*[<(]").unwrap(),
+        arrow: Regex::new(r"^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:<[^>]*>\s*)?(?:\([^)]*\)?\s*(?::[^=]+)?(?:=>|$)|[A-Za-z_$][\w$]*\s*=>)").unwrap(),
+        function_expression: Regex::new(r"^\s*(?:export\s+)?(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?function\b").unwrap(),
+        method: Regex::new(r"^\s*(?:(?:public|private|protected|static|async|override|readonly)\s+)*\*?([A-Za-z_$][\w$]*)\s*(?:<[^>]*>)?\s*\([^;]*$").unwrap(),
+    })
+}
+
+const NON_METHOD_KEYWORDS: &[&str] = &[
+    "if", "for", "while", "switch", "catch", "function", "return", "with", "else", "do", "new", "typeof", "await",
+];
+
+/// Find every function-like block in `content`, including nested ones
+pub fn find_functions(content: &str) -> Vec<FunctionSpan> {
+    let lines: Vec<&str> = content.lines().collect();
+    let patterns = get_function_patterns();
+    let mut functions = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") || trimmed.starts_with('*') || trimmed.starts_with("/*") {
+            continue;
+        }
+
+        let detected = if let Some(caps) = patterns.declaration.captures(line) {
+            Some((caps[1].to_string(), FunctionKind::Function, false))
+        } else if let Some(caps) = patterns.function_expression.captures(line) {
+            Some((caps[1].to_string(), FunctionKind::Function, false))
+        } else if let Some(caps) = patterns.arrow.captures(line) {
+            Some((caps[1].to_string(), FunctionKind::ArrowFunction, true))
+        } else if let Some(caps) = patterns.method.captures(line) {
+            let name = caps[1].to_string();
+            if NON_METHOD_KEYWORDS.contains(&name.as_str()) || !looks_like_method_signature(&lines, index) {
+                None
+            } else {
+                Some((name, FunctionKind::Method, false))
+            }
+        } else {
+            None
+        };
+
+        if let Some((name, kind, is_arrow)) = detected {
+            if let Some(end_index) = find_body_end(&lines, index, is_arrow) {
+                let kind = if is_component_name(&name) && kind != FunctionKind::Method {
+                    FunctionKind::Component
+                } else {
+                    kind
+                };
+                functions.push(FunctionSpan {
+                    name,
+                    kind,
+                    start_line: index + 1,
+                    end_line: end_index + 1,
+                    lines: end_index - index + 1,
+                });
+            }
+        }
+    }
+
+    functions
+}
+
+/// PascalCase functions are treated as React components
+fn is_component_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// A method signature ends with `{` (possibly after a return type) rather than `;`,
+/// which rules out plain calls like `doSomething(a, b)`.
+fn looks_like_method_signature(lines: &[&str], start: usize) -> bool {
+    for line in lines.iter().skip(start).take(5) {
+        let trimmed = line.trim_end();
+        if trimmed.ends_with(';') || trimmed.ends_with(',') {
+            return false;
+        }
+        if trimmed.ends_with('{') {
+            return !trimmed.contains(" = ");
+        }
+    }
+    false
+}
+
+#[derive(PartialEq)]
+enum ScanState {
+    Code,
+    LineComment,
+    BlockComment,
+    Str(char),
+}
+
+/// Locate the line on which the function starting at `start` ends.
+///
+/// For declarations the body is the first `{ ... }` outside the parameter list.
+/// For arrow functions it is whatever follows `=>`: a `{ ... }` block or a
+/// parenthesised expression such as JSX. Expression bodies on one line are ignored.
+fn find_body_end(lines: &[&str], start: usize, is_arrow: bool) -> Option<usize> {
+    let mut state = ScanState::Code;
+    let mut paren_depth: i32 = 0;
+    let mut body_delimiter: Option<(char, char)> = None;
+    let mut body_depth: i32 = 0;
+    let mut seen_arrow = !is_arrow;
+    let mut awaiting_body = false;
+
+    for (line_index, line) in lines.iter().enumerate().skip(start) {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        // Only block comments and template literals span lines
+        if matches!(state, ScanState::LineComment | ScanState::Str('\'') | ScanState::Str('"')) {
+            state = ScanState::Code;
+        }
+
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+
+            match state {
+                ScanState::LineComment => break,
+                ScanState::BlockComment => {
+                    if c == '*' && next == Some('/') {
+                        state = ScanState::Code;
+                        i += 1;
+                    }
+                }
+                ScanState::Str(quote) => {
+                    if c == '\\' {
+                        i += 1;
+                    } else if c == quote {
+                        state = ScanState::Code;
+                    }
+                }
+                ScanState::Code => {
+                    if c == '/' && next == Some('/') {
+                        state = ScanState::LineComment;
+                        continue;
+                    } else if c == '/' && next == Some('*') {
+                        state = ScanState::BlockComment;
+                        i += 1;
+                    } else if c == '"' || c == '\'' || c == '`' {
+                        state = ScanState::Str(c);
+                    } else if let Some((open, close)) = body_delimiter {
+                        if c == open {
+                            body_depth += 1;
+                        } else if c == close {
+                            body_depth -= 1;
+                            if body_depth == 0 {
+                                return Some(line_index);
+                            }
+                        }
+                    } else if awaiting_body {
+                        if c == '{' || c == '(' {
+                            body_delimiter = Some(if c == '{' { ('{', '}') } else { ('(', ')') });
+                            body_depth = 1;
+                        } else if !c.is_whitespace() {
+                            // Expression body (`=> value`): not a block worth measuring
+                            return None;
+                        }
+                    } else if c == '(' {
+                        paren_depth += 1;
+                    } else if c == ')' {
+                        paren_depth -= 1;
+                    } else if paren_depth == 0 {
+                        if !seen_arrow && c == '=' && next == Some('>') {
+                            seen_arrow = true;
+                            awaiting_body = true;
+                            i += 1;
+                        } else if seen_arrow && !is_arrow && c == '{' {
+                            body_delimiter = Some(('{', '}'));
+                            body_depth = 1;
+                        } else if !is_arrow && c == ';' {
+                            // Overload or declaration without a body
+                            return None;
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        // An arrow signature must reach `=>` before its parameter list closes on a line end,
+        // otherwise this was a plain parenthesised value (`const x = (a + b)`)
+        if !seen_arrow && paren_depth <= 0 && state != ScanState::BlockComment {
+            return None;
+        }
+
+        // Give up on signatures that never open a body
+        if body_delimiter.is_none() && line_index > start + 10 {
+            return None;
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_function_declaration_span() {
+        let content = "function add(a: number, b: number): number {\n  const sum = a + b;\n  return sum;\n}\n";
+        let functions = find_functions(content);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "add");
+        assert_eq!((functions[0].start_line, functions[0].end_line), (1, 4));
+    }
+
+    #[test]
+    fn test_arrow_component_with_jsx_body() {
+        let content = "export const UserCard = ({ name }: Props) => (\n  <div>\n    {name}\n  </div>\n);\n";
+        let functions = find_functions(content);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "UserCard");
+        assert_eq!(functions[0].kind, FunctionKind::Component);
+        assert_eq!(functions[0].lines, 5);
+    }
+
+    #[test]
+    fn test_braces_in_strings_and_comments_are_ignored() {
+        let content = "const handler = async (event) => {\n  const s = '}';\n  // }\n  return `${s}}`;\n};\n";
+        let functions = find_functions(content);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].end_line, 5);
+    }
+
+    #[test]
+    fn test_control_flow_and_calls_are_not_methods() {
+        let content = "class Store {\n  load(id: string) {\n    if (id) {\n      fetchData(id);\n    }\n  }\n}\n";
+        let functions = find_functions(content);
+
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].name, "load");
+        assert_eq!(functions[0].kind, FunctionKind::Method);
+        assert_eq!((functions[0].start_line, functions[0].end_line), (2, 6));
+    }
+}
@@ -0,0 +1,80 @@
+/// Git queries shared by commands that work on the repository rather than the file tree
+use anyhow::{anyhow, Context, Result};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Working tree root of the repository containing `root`
+pub fn top_level(root: &Path) -> Result<PathBuf> {
+    Ok(PathBuf::from(git(root, &["rev-parse", "--show-toplevel"])?.trim()))
+}
+
+/// Files added, copied, modified or renamed in the index, as absolute paths
+pub fn staged_files(root: &Path) -> Result<Vec<PathBuf>> {
+    let top_level = top_level(root)?;
+    let output = git(root, &["diff", "--cached", "--name-only", "--diff-filter=ACMR", "-z"])?;
+    Ok(output
+        .split('\0')
+        .filter(|name| !name.is_empty())
+        .map(|name| top_level.join(name))
+        .collect())
+}
+
+/// Directory git runs hooks from, honoring `core.hooksPath`
+pub fn hooks_dir(root: &Path) -> Result<PathBuf> {
+    let configured = git(root, &["config", "--get", "core.hooksPath"]).ok();
+    let dir = match configured.as_deref().map(str::trim) {
+        Some(path) if !path.is_empty() => top_level(root)?.join(path),
+        _ => root.join(git(root, &["rev-parse", "--git-path", "hooks"])?.trim()),
+    };
+    Ok(dir)
+}
+
+/// Which of `paths` (relative to `root`) ignore rules exclude; tracked files never are
+pub fn ignored(root: &Path, paths: &[String]) -> Result<BTreeSet<String>> {
+    if paths.is_empty() {
+        return Ok(BTreeSet::new());
+    }
+    let mut args = vec!["check-ignore", "--"];
+    args.extend(paths.iter().map(String::as_str));
+    // Exits with 1 when none of the paths is ignored
+    let output = git_accepting(root, &args, &[0, 1])?;
+    Ok(output.lines().map(str::to_string).collect())
+}
+
+/// Tracked files matching `pathspec`, relative to `root`
+pub fn tracked_files(root: &Path, pathspec: &str) -> Result<Vec<String>> {
+    let output = git(root, &["ls-files", "-z", "--", pathspec])?;
+    Ok(output.split('\0').filter(|name| !name.is_empty()).map(str::to_string).collect())
+}
+
+/// Files matching `pathspec` (relative to `root`) that a commit on any branch added, with the
+/// short hash of that commit, newest first
+pub fn added_in_history(root: &Path, pathspec: &str) -> Result<Vec<(String, String)>> {
+    let output = git(root, &["log", "--all", "--diff-filter=A", "--relative", "--name-only", "--format=%x00%h", "--", pathspec])?;
+    let mut added = Vec::new();
+    for commit in output.split('\0').filter(|commit| !commit.trim().is_empty()) {
+        let mut lines = commit.lines().map(str::trim).filter(|line| !line.is_empty());
+        let Some(hash) = lines.next() else { continue };
+        added.extend(lines.map(|file| (hash.to_string(), file.to_string())));
+    }
+    Ok(added)
+}
+
+fn git(root: &Path, args: &[&str]) -> Result<String> {
+    git_accepting(root, args, &[0])
+}
+
+fn git_accepting(root: &Path, args: &[&str], exit_codes: &[i32]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .context("Failed to run git; is it installed?")?;
+    if !output.status.code().is_some_and(|code| exit_codes.contains(&code)) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow!("git {} failed: {}", args.join(" "), stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
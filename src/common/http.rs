@@ -0,0 +1,100 @@
+//! HTTP requests through curl, which handles TLS and proxies the way the machine is set up.
+//!
+//! The URL and body reach curl on stdin as a config file (`--config -`), not as arguments,
+//! so a credential in them, such as a token in the URL, does not show in process listings.
+
+use anyhow::{anyhow, Context, Result};
+use serde_json::Value;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// GET `url` and read the JSON it returns
+pub fn get_json(url: &str, max_time_secs: u64) -> Result<Value> {
+    json(url, &send(url, None, max_time_secs)?)
+}
+
+/// POST `body` as JSON to `url` and read the JSON it returns
+pub fn post_json(url: &str, body: &Value, max_time_secs: u64) -> Result<Value> {
+    json(url, &send(url, Some(body), max_time_secs)?)
+}
+
+/// POST `body` as JSON to `url`, ignoring what it returns
+pub fn post(url: &str, body: &Value, max_time_secs: u64) -> Result<()> {
+    send(url, Some(body), max_time_secs).map(|_| ())
+}
+
+/// The response body; curl's error message when the request fails or the status is 4xx/5xx
+fn send(url: &str, body: Option<&Value>, max_time_secs: u64) -> Result<Vec<u8>> {
+    let max_time = max_time_secs.to_string();
+    let mut command = Command::new("curl");
+    command.args(["--silent", "--show-error", "--fail", "--location", "--max-time", &max_time, "--header", "Accept: application/json"]);
+    if body.is_some() {
+        command.args(["--header", "Content-Type: application/json"]);
+    }
+    let mut child = command.args(["--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run curl, which sniff uses for HTTP requests")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(curl_config(url, body).as_bytes())?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!("{}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(output.stdout)
+}
+
+fn json(url: &str, response: &[u8]) -> Result<Value> {
+    serde_json::from_slice(response).with_context(|| format!("{} did not return JSON", redact_url(url)))
+}
+
+/// The `url` and `data-binary` lines of a curl config file
+fn curl_config(url: &str, body: Option<&Value>) -> String {
+    let mut config = format!("url = {}\n", quoted(url));
+    if let Some(body) = body {
+        config.push_str(&format!("data-binary = {}\n", quoted(&body.to_string())));
+    }
+    config
+}
+
+/// A double-quoted curl config value, which reads `\\`, `\"`, `\n` and `\r` as escapes
+fn quoted(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r");
+    format!("\"{}\"", escaped)
+}
+
+/// The URL without its path and query, which may hold a token, for error messages
+fn redact_url(url: &str) -> &str {
+    let path_start = url.find("://").map_or(0, |scheme| scheme + 3);
+    url[path_start..].find('/').map_or(url, |slash| &url[..path_start + slash])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_quotes_the_url_and_body() {
+        let body = json!({ "text": "say \"hi\"" });
+        assert_eq!(
+            curl_config("https://hooks.example.com/T0/B0/secret", Some(&body)),
+            concat!("url = \"https://hooks.example.com/T0/B0/secret\"\n", r#"data-binary = "{\"text\":\"say \\\"hi\\\"\"}""#, "\n")
+        );
+        assert_eq!(redact_url("https://hooks.example.com/T0/B0/secret"), "https://hooks.example.com");
+    }
+
+    #[test]
+    fn test_get_json_reads_the_url_from_stdin() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("doc \"quoted\".json");
+        std::fs::write(&path, r#"{"latest":"1.2.3"}"#).unwrap();
+        let url = format!("file://{}", path.display()).replace(' ', "%20");
+
+        assert_eq!(get_json(&url, 5).unwrap()["latest"], "1.2.3");
+        assert!(get_json(&format!("file://{}/missing.json", dir.path().display()), 5).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+/// The `[[ignore]]` entries of the config, applied to each command's findings after analysis so
+/// exceptions live in reviewed config rather than in comments next to the code
+use std::path::{Path, PathBuf};
+use crate::config::IgnoreEntry;
+use crate::utils::FileUtils;
+use super::file_scanner::matches_any_glob;
+use super::{paths, rule_catalog};
+
+#[derive(Debug, Clone, Default)]
+pub struct Ignores {
+    root: PathBuf,
+    entries: Vec<IgnoreEntry>,
+}
+
+impl Ignores {
+    /// `entries` with their globs relative to `root`
+    pub fn new(root: &Path, entries: &[IgnoreEntry]) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            entries: entries.to_vec(),
+        }
+    }
+
+    /// Whether a finding of `rule` in `file`, as reported, is ignored
+    pub fn ignores(&self, rule: &str, file: &str) -> bool {
+        if self.entries.is_empty() {
+            return false;
+        }
+        let absolute = paths::resolve(file);
+        let relative = FileUtils::slash_path(absolute.strip_prefix(&self.root).unwrap_or(Path::new(file)));
+        let command = rule_catalog::command_of(rule).unwrap_or("rules");
+        self.entries.iter().any(|entry| {
+            let rule_matches = entry.rule == "*"
+                || entry.rule.eq_ignore_ascii_case(rule)
+                || entry.rule == command;
+            rule_matches && (entry.paths.is_empty() || matches_any_glob(&relative, &entry.paths))
+        })
+    }
+
+    /// Drop the ignored items, given each one's rule and file; returns how many were dropped
+    pub fn retain<T>(&self, items: &mut Vec<T>, finding: impl Fn(&T) -> (&str, &str)) -> usize {
+        let before = items.len();
+        if !self.entries.is_empty() {
+            items.retain(|item| {
+                let (rule, file) = finding(item);
+                !self.ignores(rule, file)
+            });
+        }
+        before - items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(rule: &str, paths: &[&str]) -> IgnoreEntry {
+        IgnoreEntry { rule: rule.to_string(), paths: paths.iter().map(|path| path.to_string()).collect(), reason: None }
+    }
+
+    #[test]
+    fn test_ignores_match_rule_command_and_paths() {
+        let root = std::env::current_dir().unwrap();
+        let ignores = Ignores::new(&root, &[
+            entry("snf-imp-unused", &["**/*.stories.tsx"]),
+            entry("console", &["scripts/**"]),
+            entry("no-moment", &[]),
+        ]);
+        assert!(ignores.ignores("SNF-IMP-UNUSED", "src/Button.stories.tsx"));
+        assert!(ignores.ignores("SNF-IMP-UNUSED", &root.join("Button.stories.tsx").to_string_lossy()));
+        assert!(!ignores.ignores("SNF-IMP-UNUSED", "src/Button.tsx"));
+        assert!(!ignores.ignores("SNF-IMP-BROKEN", "src/Button.stories.tsx"));
+        assert!(ignores.ignores("SNF-CONSOLE-TODO", "scripts/seed.ts"));
+        assert!(!ignores.ignores("SNF-MEM-TIMER", "scripts/seed.ts"));
+        assert!(ignores.ignores("no-moment", "src/dates.ts"));
+
+        let mut findings = vec![("SNF-CONSOLE-LOG", "scripts/a.ts"), ("SNF-CONSOLE-LOG", "src/a.ts")];
+        assert_eq!(ignores.retain(&mut findings, |(rule, file)| (*rule, *file)), 1);
+        assert_eq!(findings, vec![("SNF-CONSOLE-LOG", "src/a.ts")]);
+    }
+}
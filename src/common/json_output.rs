@@ -1,10 +1,17 @@
 /// Unified JSON output formatting utilities
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
+use super::{Annotate, Annotation};
+use super::annotations::sort_findings;
+
+/// Version of the envelope's shape; raised when a field is removed or changes meaning
+pub const SCHEMA_VERSION: u32 = 1;
 
 /// Standard JSON response wrapper for all commands
 #[derive(Serialize, Deserialize)]
 pub struct StandardResponse<T> {
+    /// Shape of this envelope, see `SCHEMA_VERSION`
+    pub schema_version: u32,
     /// The command that generated this response
     pub command: String,
     /// Timestamp when the analysis was performed
@@ -15,8 +22,11 @@ pub struct StandardResponse<T> {
     pub data: T,
     /// Summary information for quick overview
     pub summary: ResponseSummary,
+    /// Findings in one shape for every command; empty for commands that report no findings
+    #[serde(default)]
+    pub findings: Vec<Annotation>,
     /// Any warnings or metadata
-    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
     /// Additional metadata
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -54,16 +64,25 @@ where
     /// Create a new standard response
     pub fn new(command: &str, data: T, summary: ResponseSummary) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             command: command.to_string(),
             timestamp: Utc::now(),
             version: env!("CARGO_PKG_VERSION").to_string(),
             data,
             summary,
+            findings: Vec::new(),
             warnings: Vec::new(),
             metadata: None,
         }
     }
     
+    /// Set `findings`, sorted like every other output
+    pub fn with_findings(mut self, mut findings: Vec<Annotation>) -> Self {
+        sort_findings(&mut findings);
+        self.findings = findings;
+        self
+    }
+
     /// Convert to pretty JSON string
     pub fn to_json_pretty(&self) -> anyhow::Result<String> {
         Ok(serde_json::to_string_pretty(self)?)
@@ -104,6 +123,21 @@ where
     StandardResponse::new(command, data, summary)
 }
 
+/// Like `create_standard_json_output`, with the report's findings in `findings`
+pub fn create_annotated_json_output<'a, R>(
+    command: &str,
+    report: &'a R,
+    total_items: usize,
+    issues_found: usize,
+    duration_ms: Option<u64>,
+) -> StandardResponse<&'a R>
+where
+    R: Serialize + Annotate,
+{
+    create_standard_json_output(command, report, total_items, issues_found, duration_ms)
+        .with_findings(report.annotations())
+}
+
 /// Helper function to output either JSON or call a custom print function
 pub fn output_result<T>(
     response: &StandardResponse<T>,
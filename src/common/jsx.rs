@@ -0,0 +1,269 @@
+/// Lightweight JSX tag scanner shared by the checks that look at elements and their attributes
+pub enum AttrValue<'a> {
+    /// `"value"` or `'value'`, without the quotes
+    Literal(&'a str),
+    /// `{expression}`, without the braces
+    Expression(&'a str),
+}
+
+impl<'a> AttrValue<'a> {
+    pub fn text(&self) -> &'a str {
+        match self {
+            AttrValue::Literal(text) | AttrValue::Expression(text) => text,
+        }
+    }
+}
+
+/// A JSX opening tag
+pub struct Element<'a> {
+    pub name: &'a str,
+    /// Byte offset of the `<`
+    pub offset: usize,
+    /// Attributes in source order; boolean attributes have no value
+    pub attributes: Vec<(&'a str, Option<AttrValue<'a>>)>,
+    /// `{...props}` among the attributes
+    pub spread: bool,
+    /// Nested in an open `<label>`
+    pub inside_label: bool,
+}
+
+impl<'a> Element<'a> {
+    pub fn attribute(&self, name: &str) -> Option<Option<&AttrValue<'a>>> {
+        self.attributes.iter().find(|(attribute, _)| *attribute == name).map(|(_, value)| value.as_ref())
+    }
+
+    /// The value of a string attribute, or an expression that is just a string literal (`{"button"}`)
+    pub fn literal(&self, name: &str) -> Option<&'a str> {
+        match self.attribute(name)?? {
+            AttrValue::Literal(text) => Some(text),
+            AttrValue::Expression(text) => {
+                let text = text.trim();
+                let quoted = text.len() >= 2 && matches!(text.as_bytes()[0], b'"' | b'\'' | b'`') && text.as_bytes()[0] == text.as_bytes()[text.len() - 1];
+                quoted.then(|| &text[1..text.len() - 1])
+            }
+        }
+    }
+}
+
+/// Opening tags in source order. This is a scanner, not a parser: a `<` starts a tag only when
+/// a letter follows it and it does not follow an identifier, which skips generics and comparisons.
+pub fn jsx_elements(content: &str) -> Vec<Element<'_>> {
+    scan(content).0
+}
+
+/// Text between tags with its byte offset, e.g. `Save changes` in `<button>Save changes</button>`.
+/// `{expressions}` split the text; segments are untrimmed and may be only whitespace.
+pub fn jsx_text(content: &str) -> Vec<(usize, &str)> {
+    let bytes = content.as_bytes();
+    let mut segments = Vec::new();
+    for start in scan(content).1 {
+        let mut segment_start = start;
+        let mut i = start;
+        while i < bytes.len() && bytes[i] != b'<' {
+            if bytes[i] == b'{' {
+                segments.push((segment_start, &content[segment_start..i]));
+                match expression_end(bytes, i) {
+                    Some(end) => i = end,
+                    None => return segments,
+                }
+                segment_start = i + 1;
+            }
+            i += 1;
+        }
+        segments.push((segment_start, &content[segment_start..i]));
+    }
+    segments
+}
+
+/// Opening tags, and the offsets where JSX text may start: after a tag, while inside an element
+fn scan(content: &str) -> (Vec<Element<'_>>, Vec<usize>) {
+    let bytes = content.as_bytes();
+    let mut elements = Vec::new();
+    let mut text_starts = Vec::new();
+    let mut label_depth = 0usize;
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = content[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+                continue;
+            }
+            // `//` after a `:` is a URL in JSX text, not a comment
+            b'/' if bytes.get(i + 1) == Some(&b'/') && (i == 0 || bytes[i - 1] != b':') => {
+                i = content[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                continue;
+            }
+            b'<' if bytes.get(i + 1) == Some(&b'/') => {
+                let name_end = name_end(bytes, i + 2);
+                if &content[i + 2..name_end] == "label" {
+                    label_depth = label_depth.saturating_sub(1);
+                }
+                i = name_end;
+                if bytes.get(i) == Some(&b'>') {
+                    depth = depth.saturating_sub(1);
+                    i += 1;
+                    if depth > 0 {
+                        text_starts.push(i);
+                    }
+                }
+                continue;
+            }
+            // A fragment, `<>`
+            b'<' if bytes.get(i + 1) == Some(&b'>') && !follows_identifier(bytes, i) => {
+                depth += 1;
+                i += 2;
+                text_starts.push(i);
+                continue;
+            }
+            // Inside an element, `Name <input />` is text followed by a tag
+            b'<' if bytes.get(i + 1).is_some_and(u8::is_ascii_alphabetic) && (depth > 0 || !follows_identifier(bytes, i)) => {
+                if let Some((element, end, self_closing)) = opening_tag(content, i, label_depth > 0) {
+                    if element.name == "label" && !self_closing {
+                        label_depth += 1;
+                    }
+                    if !self_closing {
+                        depth += 1;
+                    }
+                    if depth > 0 {
+                        text_starts.push(end);
+                    }
+                    elements.push(element);
+                    i = end;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    (elements, text_starts)
+}
+
+/// Keywords that can come right before a JSX expression, unlike identifiers (`return <div>`)
+const KEYWORDS_BEFORE_EXPRESSION: &[&str] = &["return", "yield", "await", "case", "default", "else", "do", "in", "of", "typeof", "void"];
+
+fn follows_identifier(bytes: &[u8], index: usize) -> bool {
+    let before = bytes[..index].trim_ascii_end();
+    let word_start = before.iter().rposition(|byte| !(byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$'))).map_or(0, |i| i + 1);
+    let word = &before[word_start..];
+    if !word.is_empty() {
+        return !KEYWORDS_BEFORE_EXPRESSION.iter().any(|keyword| keyword.as_bytes() == word);
+    }
+    before.last().is_some_and(|byte| matches!(byte, b')' | b']'))
+}
+
+fn name_end(bytes: &[u8], start: usize) -> usize {
+    let mut end = start;
+    while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || matches!(bytes[end], b'.' | b'-' | b'_' | b':')) {
+        end += 1;
+    }
+    end
+}
+
+/// The tag starting at `start`, the offset after its `>` and whether it is self-closing
+fn opening_tag(content: &str, start: usize, inside_label: bool) -> Option<(Element<'_>, usize, bool)> {
+    let bytes = content.as_bytes();
+    let mut i = name_end(bytes, start + 1);
+    let mut element = Element { name: &content[start + 1..i], offset: start, attributes: Vec::new(), spread: false, inside_label };
+
+    loop {
+        while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+            i += 1;
+        }
+        match *bytes.get(i)? {
+            b'>' => return Some((element, i + 1, false)),
+            b'/' if bytes.get(i + 1) == Some(&b'>') => return Some((element, i + 2, true)),
+            b'{' => {
+                let end = expression_end(bytes, i)?;
+                if content[i + 1..end].trim_start().starts_with("...") {
+                    element.spread = true;
+                }
+                i = end + 1;
+            }
+            byte if byte.is_ascii_alphabetic() || byte == b'_' => {
+                let name_start = i;
+                i = name_end(bytes, i);
+                let name = &content[name_start..i];
+                let value = if bytes.get(i) == Some(&b'=') {
+                    i += 1;
+                    match *bytes.get(i)? {
+                        quote @ (b'"' | b'\'') => {
+                            let end = i + 1 + content[i + 1..].find(quote as char)?;
+                            let value = AttrValue::Literal(&content[i + 1..end]);
+                            i = end + 1;
+                            Some(value)
+                        }
+                        b'{' => {
+                            let end = expression_end(bytes, i)?;
+                            let value = AttrValue::Expression(&content[i + 1..end]);
+                            i = end + 1;
+                            Some(value)
+                        }
+                        _ => return None,
+                    }
+                } else {
+                    None
+                };
+                element.attributes.push((name, value));
+            }
+            // Not JSX after all, e.g. a type argument list
+            _ => return None,
+        }
+    }
+}
+
+/// Index of the `}` closing the `{` at `start`, skipping strings and nested braces
+pub fn expression_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            quote @ (b'"' | b'\'' | b'`') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attributes_and_non_jsx() {
+        let element = &jsx_elements(r#"<div className={cn("a", { b: c > 1 })} data-x='y' hidden>"#)[0];
+        assert_eq!(element.attributes.iter().map(|(name, _)| *name).collect::<Vec<_>>(), vec!["className", "data-x", "hidden"]);
+        assert_eq!(element.literal("data-x"), Some("y"));
+        // Generics, comparisons and comments are not elements
+        assert!(jsx_elements("const [a, b] = useState<string>(''); if (i <input) {}\n// <img src=x>\n{/* <img src={x} /> */}").is_empty());
+        let nested = jsx_elements("<label>Name <input /></label><input />");
+        assert!(nested[1].inside_label && !nested[2].inside_label);
+        let returned = jsx_elements("function Page() {\n  return <main className=\"p-4\">Hi</main>;\n}");
+        assert_eq!(returned[0].literal("className"), Some("p-4"));
+    }
+
+    #[test]
+    fn test_text_between_tags() {
+        let source = "const A = () => (\n  <p>Hello {name}, <b>welcome</b> back<br />!</p>\n);\nexport function B() {}";
+        let text: Vec<&str> = jsx_text(source).into_iter().map(|(_, text)| text).filter(|text| !text.trim().is_empty()).collect();
+        assert_eq!(text, vec!["Hello ", ", ", "welcome", " back", "!"]);
+    }
+}
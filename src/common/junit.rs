@@ -0,0 +1,176 @@
+/// JUnit XML for `--format junit`: every check is a test suite and every finding a failed test case
+use super::annotations::{workspace_path, Annotation, AnnotationLevel};
+use super::error_handler::ExitCode;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestSuite {
+    pub name: String,
+    pub duration_ms: u64,
+    pub cases: Vec<TestCase>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    pub name: String,
+    pub outcome: CaseOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CaseOutcome {
+    Passed,
+    Failed { kind: String, message: String, details: String },
+    Skipped { message: String },
+}
+
+impl TestSuite {
+    /// One failed case per finding. A check without findings is a single case that passes,
+    /// or fails with its exit code when the failure has no location (e.g. tsc not runnable).
+    pub fn from_findings(name: &str, exit_code: ExitCode, findings: Vec<Annotation>, duration_ms: u64) -> Self {
+        let cases = if findings.is_empty() {
+            let outcome = if exit_code.is_success() {
+                CaseOutcome::Passed
+            } else {
+                CaseOutcome::Failed {
+                    kind: "error".to_string(),
+                    message: format!("sniff {} exited with code {}", name, exit_code.code()),
+                    details: String::new(),
+                }
+            };
+            vec![TestCase { name: name.to_string(), outcome }]
+        } else {
+            findings.into_iter().map(TestCase::from_finding).collect()
+        };
+        Self { name: name.to_string(), duration_ms, cases }
+    }
+
+    /// A check that could not run, such as `bundle` without build output
+    pub fn skipped(name: &str, reason: &str, duration_ms: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            duration_ms,
+            cases: vec![TestCase { name: name.to_string(), outcome: CaseOutcome::Skipped { message: reason.to_string() } }],
+        }
+    }
+
+    fn count(&self, matches: impl Fn(&CaseOutcome) -> bool) -> usize {
+        self.cases.iter().filter(|case| matches(&case.outcome)).count()
+    }
+}
+
+impl TestCase {
+    fn from_finding(finding: Annotation) -> Self {
+        let location = finding.file.as_deref().map(|file| match finding.line {
+            Some(line) => format!("{}:{}", workspace_path(file), line),
+            None => workspace_path(file),
+        });
+        let name = match &location {
+            Some(location) => format!("{} ({})", finding.labeled_title(), location),
+            None => finding.labeled_title(),
+        };
+        let kind = match finding.level {
+            AnnotationLevel::Critical => "critical",
+            AnnotationLevel::Error => "error",
+            AnnotationLevel::Warning => "warning",
+            AnnotationLevel::Notice => "notice",
+        };
+        let details = match location {
+            Some(location) => format!("{}: {}", location, finding.message),
+            None => finding.message.clone(),
+        };
+        Self {
+            name,
+            outcome: CaseOutcome::Failed { kind: kind.to_string(), message: finding.message, details },
+        }
+    }
+}
+
+/// The `<testsuites>` document for all suites
+pub fn render(suites: &[TestSuite]) -> String {
+    let total = |count: fn(&TestSuite) -> usize| suites.iter().map(count).sum::<usize>();
+    let tests = total(|suite| suite.cases.len());
+    let failures = total(|suite| suite.count(|outcome| matches!(outcome, CaseOutcome::Failed { .. })));
+    let skipped = total(|suite| suite.count(|outcome| matches!(outcome, CaseOutcome::Skipped { .. })));
+    let duration_ms: u64 = suites.iter().map(|suite| suite.duration_ms).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"sniff\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+        tests, failures, skipped, seconds(duration_ms)
+    ));
+    for suite in suites {
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{}\">\n",
+            escape(&suite.name),
+            suite.cases.len(),
+            suite.count(|outcome| matches!(outcome, CaseOutcome::Failed { .. })),
+            suite.count(|outcome| matches!(outcome, CaseOutcome::Skipped { .. })),
+            seconds(suite.duration_ms)
+        ));
+        for case in &suite.cases {
+            let open = format!("    <testcase classname=\"sniff.{}\" name=\"{}\"", escape(&suite.name), escape(&case.name));
+            match &case.outcome {
+                CaseOutcome::Passed => xml.push_str(&format!("{}/>\n", open)),
+                CaseOutcome::Failed { kind, message, details } => xml.push_str(&format!(
+                    "{}>\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+                    open, escape(kind), escape(message), escape(details)
+                )),
+                CaseOutcome::Skipped { message } => xml.push_str(&format!(
+                    "{}>\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    open, escape(message)
+                )),
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>");
+    xml
+}
+
+fn seconds(duration_ms: u64) -> String {
+    format!("{:.3}", duration_ms as f64 / 1000.0)
+}
+
+fn escape(value: &str) -> String {
+    value
+        .chars()
+        // Control characters other than tab and newlines are not allowed in XML 1.0
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .map(|c| match c {
+            '&' => "&amp;".to_string(),
+            '<' => "&lt;".to_string(),
+            '>' => "&gt;".to_string(),
+            // Attributes are always double-quoted
+            '"' => "&quot;".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_findings_become_failed_cases_and_clean_checks_pass() {
+        let findings = vec![Annotation::new(AnnotationLevel::Warning, "src/a.ts", 3, "Unused import", "Unused: <Button>")];
+        let suites = vec![
+            TestSuite::from_findings("imports", ExitCode::ValidationFailed, findings, 1500),
+            TestSuite::from_findings("types", ExitCode::Success, Vec::new(), 20),
+            TestSuite::skipped("bundle", "No build output found", 0),
+        ];
+        let xml = render(&suites);
+
+        assert!(xml.contains("<testsuites name=\"sniff\" tests=\"3\" failures=\"1\" skipped=\"1\" time=\"1.520\">"));
+        assert!(xml.contains("<testcase classname=\"sniff.imports\" name=\"Unused import (src/a.ts:3)\">"));
+        assert!(xml.contains("<failure type=\"warning\" message=\"Unused: &lt;Button&gt;\">src/a.ts:3: Unused: &lt;Button&gt;</failure>"));
+        assert!(xml.contains("<testcase classname=\"sniff.types\" name=\"types\"/>"));
+        assert!(xml.contains("<skipped message=\"No build output found\"/>"));
+    }
+
+    #[test]
+    fn test_failure_without_findings_is_reported() {
+        let suite = TestSuite::from_findings("env", ExitCode::GeneralError, Vec::new(), 0);
+        assert_eq!(suite.cases.len(), 1);
+        assert!(matches!(&suite.cases[0].outcome, CaseOutcome::Failed { message, .. } if message == "sniff env exited with code 1"));
+    }
+}
@@ -0,0 +1,68 @@
+/// Progress and diagnostic messages, logged to stderr so stdout only ever holds the report
+/// and `--json | jq` keeps working at any verbosity
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use std::io::IsTerminal;
+use tracing_subscriber::EnvFilter;
+use super::report_formatter::color_enabled;
+
+/// Filter directives that replace `-v`, e.g. `SNIFF_LOG=sniff::commands::imports_analyzer=debug`
+pub const LOG_FILTER_VAR: &str = "SNIFF_LOG";
+
+/// How log messages are written
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One line per message: level, module and message
+    #[default]
+    Text,
+    /// One JSON object per message, with a timestamp and the message's fields
+    Json,
+}
+
+/// Start logging for the run: warnings by default, errors only with `--quiet`, and more of
+/// sniff's own messages for each `-v` (info, debug, trace). `SNIFF_LOG` overrides both
+pub fn init(verbose: u8, quiet: bool, format: LogFormat, no_color: bool) -> Result<()> {
+    let filter = match std::env::var(LOG_FILTER_VAR) {
+        Ok(directives) => EnvFilter::try_new(&directives)
+            .map_err(|e| anyhow!("{} is not a valid log filter ({}): {}", LOG_FILTER_VAR, e, directives))?,
+        Err(_) => EnvFilter::new(directives(verbose, quiet)),
+    };
+    let logger = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    let installed = match format {
+        LogFormat::Text => logger.without_time().with_ansi(color_enabled(no_color, std::io::stderr().is_terminal())).try_init(),
+        LogFormat::Json => logger.json().try_init(),
+    };
+    installed.map_err(|e| anyhow!(e))
+}
+
+/// The filter for `-v` given `verbose` times. Dependencies stay at warnings, so `-vv` shows
+/// sniff's debug messages and not those of the WebAssembly runtime
+fn directives(verbose: u8, quiet: bool) -> String {
+    let level = match verbose {
+        0 if quiet => return "error".to_string(),
+        0 => return "warn".to_string(),
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    format!("warn,sniff={level},sniff_check={level}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_raises_only_sniffs_own_level() {
+        assert_eq!(directives(0, false), "warn");
+        assert_eq!(directives(0, true), "error");
+        assert_eq!(directives(1, true), "warn,sniff=info,sniff_check=info");
+        assert_eq!(directives(2, false), "warn,sniff=debug,sniff_check=debug");
+        assert_eq!(directives(5, false), "warn,sniff=trace,sniff_check=trace");
+        for verbose in 0..4 {
+            assert!(EnvFilter::try_new(directives(verbose, false)).is_ok());
+        }
+    }
+}
@@ -6,15 +6,35 @@ pub mod report_formatter;
 pub mod progress;
 pub mod error_handler;
 pub mod cli_args;
-pub mod output_utils;
 pub mod json_output;
 pub mod performance;
+pub mod function_parser;
+pub mod git;
+pub mod annotations;
+pub mod junit;
+pub mod sarif;
+pub mod jsx;
+pub mod source;
+pub mod rule_engine;
+pub mod ndjson;
+pub mod analysis_context;
+pub mod logging;
+pub mod rule_catalog;
+pub mod ignore;
+pub mod paths;
+pub mod http;
+pub mod output;
 
-pub use file_scanner::{FileScanner};
+pub use analysis_context::AnalysisContext;
+pub use ignore::Ignores;
+pub use file_scanner::{FileScanner, ScanTarget, glob_error, glob_matches, matches_any_glob};
 pub use regex_patterns::{get_common_patterns, is_in_string_literal_or_comment};
-pub use report_formatter::Severity;
-pub use error_handler::{ExitCode, check_failure_threshold};
-pub use output_utils::{init_command, complete_command};
-pub use json_output::{create_standard_json_output, output_result};
-pub use performance::{OptimizedFileWalker, count_lines_optimized, PerformanceMonitor};
+pub use report_formatter::{Severity, in_ci};
+pub use error_handler::{ExitCode, CommandOutcome, FailPolicy, check_failure_threshold, exit_with};
+pub use cli_args::{TargetOptions, ListingOptions, OutputFormat, FailOn};
+pub use annotations::{Annotate, Annotation, AnnotationLevel, print_annotations};
+pub use logging::LogFormat;
+pub use json_output::{create_annotated_json_output, create_standard_json_output, output_result};
+pub use rule_engine::{PatternRule, scan_lines};
+pub use performance::{OptimizedFileWalker, count_lines_optimized, read_source, PerformanceMonitor};
 // progress module exports removed as unused
\ No newline at end of file
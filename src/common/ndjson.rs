@@ -0,0 +1,105 @@
+/// `--format ndjson`: one finding per line, written as soon as it is known
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use super::{Annotation, AnnotationLevel};
+use super::annotations::sort_findings;
+
+/// How many findings of each level were written; all a streamed run keeps in memory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LevelCounts {
+    pub critical: usize,
+    pub errors: usize,
+    pub warnings: usize,
+    pub notices: usize,
+}
+
+impl LevelCounts {
+    pub fn from_findings(findings: &[Annotation]) -> Self {
+        let mut counts = Self::default();
+        for finding in findings {
+            counts.add(finding.level);
+        }
+        counts
+    }
+
+    pub fn add(&mut self, level: AnnotationLevel) {
+        match level {
+            AnnotationLevel::Critical => self.critical += 1,
+            AnnotationLevel::Error => self.errors += 1,
+            AnnotationLevel::Warning => self.warnings += 1,
+            AnnotationLevel::Notice => self.notices += 1,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.critical + self.errors + self.warnings + self.notices
+    }
+}
+
+/// Write `findings` one JSON object per line
+pub fn write_lines(out: &mut impl Write, findings: &[Annotation]) -> io::Result<()> {
+    for finding in findings {
+        serde_json::to_writer(&mut *out, finding)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()
+}
+
+/// Print `findings` to stdout and add them to `counts`. Lines of one call stay together
+pub fn print(findings: &[Annotation], counts: &Mutex<LevelCounts>) -> Result<()> {
+    if findings.is_empty() {
+        return Ok(());
+    }
+    let mut counts = counts.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    findings.iter().for_each(|finding| counts.add(finding.level));
+    write_lines(&mut io::stdout().lock(), findings)?;
+    Ok(())
+}
+
+/// Analyze `files` in parallel and print each file's findings, sorted, as soon as that file
+/// is done, so a slow file does not hold back the others
+pub fn stream_files<F>(files: &[PathBuf], counts: &Mutex<LevelCounts>, analyze: F) -> Result<()>
+where
+    F: Fn(&Path) -> Result<Vec<Annotation>> + Sync,
+{
+    files.par_iter().try_for_each(|path| {
+        let mut findings = analyze(path)?;
+        sort_findings(&mut findings);
+        print(&findings, counts)
+    })
+}
+
+/// Whether `error` is stdout closing under us (`sniff ... | head`), which ends a stream early
+/// but is not a failure
+pub fn is_closed_pipe(error: &anyhow::Error) -> bool {
+    error.downcast_ref::<io::Error>().is_some_and(|error| error.kind() == io::ErrorKind::BrokenPipe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_lines_prints_one_object_per_line() {
+        let findings = vec![
+            Annotation::new(AnnotationLevel::Warning, "src/a.ts", 3, "Unused import", "Unused: useMemo"),
+            Annotation::new(AnnotationLevel::Error, "src/b.ts", 1, "Broken import", "File not found: './c'"),
+        ];
+        let mut out = Vec::new();
+        write_lines(&mut out, &findings).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out).unwrap().lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["file"], "src/a.ts");
+        assert_eq!(lines[1]["level"], "error");
+
+        let counts = LevelCounts::from_findings(&findings);
+        assert_eq!((counts.errors, counts.warnings, counts.total()), (1, 1, 2));
+    }
+}
@@ -0,0 +1,21 @@
+// Text report output. Human-readable lines go through these so `--ascii` reaches every report
+// printer; machine-readable output (JSON, SARIF, JUnit, TOML) is printed with `println!` directly
+
+use std::fmt::Display;
+
+use super::report_formatter::plain;
+
+/// Print `text` and a newline, with emoji and dividers swapped for ASCII in `--ascii` mode
+pub fn line(text: impl Display) {
+    println!("{}", plain(&text.to_string()));
+}
+
+/// `line` without the trailing newline
+pub fn text(text: impl Display) {
+    print!("{}", plain(&text.to_string()));
+}
+
+/// An empty line
+pub fn blank() {
+    println!();
+}
@@ -0,0 +1,64 @@
+/// File paths as reports print them: relative to the project root and `/`-separated, so
+/// baselines, ignores and CI annotations match whichever directory sniff ran from; absolute
+/// with `--absolute-paths`
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use crate::utils::FileUtils;
+
+static ABSOLUTE: AtomicBool = AtomicBool::new(false);
+
+/// The root of the analyzed project; the working directory until a target is resolved
+static ROOT: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Print absolute paths for the rest of the run
+pub fn init(absolute: bool) {
+    ABSOLUTE.store(absolute, Ordering::Relaxed);
+}
+
+/// Report paths relative to `root` from now on
+pub fn set_root(root: &Path) {
+    *ROOT.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(root.to_path_buf());
+}
+
+/// The directory reported paths are relative to
+pub fn root() -> PathBuf {
+    let root = ROOT.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    root.unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+}
+
+/// `path`, absolute or relative to the working directory, as reports print it. Files outside
+/// the project keep their absolute path
+pub fn display(path: &Path) -> String {
+    let absolute = std::env::current_dir().unwrap_or_default().join(path);
+    if ABSOLUTE.load(Ordering::Relaxed) {
+        return absolute.to_string_lossy().into_owned();
+    }
+    match absolute.strip_prefix(root()) {
+        Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Ok(relative) => FileUtils::slash_path(relative),
+        Err(_) => absolute.to_string_lossy().into_owned(),
+    }
+}
+
+/// The file a path printed by `display` refers to
+pub fn resolve(reported: &str) -> PathBuf {
+    root().join(reported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths_are_relative_to_the_project_root() {
+        let cwd = std::env::current_dir().unwrap();
+        assert_eq!(display(&cwd.join("src").join("app.ts")), "src/app.ts");
+        assert_eq!(display(Path::new("src/app.ts")), "src/app.ts");
+        assert_eq!(display(&cwd), ".");
+        let outside = cwd.parent().unwrap().join("elsewhere.ts");
+        assert_eq!(display(&outside), outside.to_string_lossy());
+        assert_eq!(resolve("src/app.ts"), cwd.join("src/app.ts"));
+        assert_eq!(resolve(&outside.to_string_lossy()), outside);
+    }
+}
@@ -151,28 +151,76 @@ impl Default for OptimizedFileWalker {
     }
 }
 
-/// Optimized line counting using memory mapping for large files
+/// Files larger than this are not read into memory by `read_source`; at this size they are
+/// generated or vendored, and their lines are still counted by `count_lines_optimized`
+pub const MAX_SOURCE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Files at least this large are memory-mapped instead of streamed
+const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// How much of a file's start is checked for NUL bytes
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Whether `bytes`, the start of a file, look binary: text files have no NUL bytes
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Line count as `str::lines` would give it, without reading the file into a string.
+/// Large files are memory-mapped, smaller ones streamed; binary files have no lines
 pub fn count_lines_optimized<P: AsRef<Path>>(path: P) -> std::io::Result<usize> {
     use memmap2::Mmap;
+    use std::io::{BufRead, BufReader};
     
     let file = std::fs::File::open(&path)?;
     let metadata = file.metadata()?;
     
-    // For small files, use regular reading
-    if metadata.len() < 1024 * 1024 { // 1MB threshold
-        let content = std::fs::read_to_string(&path)?;
-        return Ok(content.lines().count());
+    if metadata.len() >= MMAP_THRESHOLD {
+        let mmap = unsafe { Mmap::map(&file)? };
+        if is_binary(&mmap) {
+            return Ok(0);
+        }
+        let newlines: usize = mmap.par_chunks(8192) // Process in 8KB chunks
+            .map(|chunk| chunk.iter().filter(|&&byte| byte == b'\n').count())
+            .sum();
+        return Ok(newlines + usize::from(mmap.last().is_some_and(|&byte| byte != b'\n')));
     }
     
-    // For large files, use memory mapping
-    let mmap = unsafe { Mmap::map(&file)? };
-    let count = mmap.par_chunks(8192) // Process in 8KB chunks
-        .map(|chunk| {
-            chunk.iter().filter(|&&byte| byte == b'\n').count()
-        })
-        .sum();
-    
-    Ok(count)
+    let mut reader = BufReader::with_capacity(64 * 1024, file);
+    let mut newlines = 0;
+    let mut last_byte = None;
+    let mut first_chunk = true;
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
+        }
+        if first_chunk && is_binary(chunk) {
+            return Ok(0);
+        }
+        first_chunk = false;
+        newlines += chunk.iter().filter(|&&byte| byte == b'\n').count();
+        last_byte = chunk.last().copied();
+        let consumed = chunk.len();
+        reader.consume(consumed);
+    }
+    Ok(newlines + usize::from(last_byte.is_some_and(|byte| byte != b'\n')))
+}
+
+/// The file's text, or `None` when it is larger than `MAX_SOURCE_BYTES` or binary.
+/// Invalid UTF-8 is replaced rather than failing the read
+pub fn read_source<P: AsRef<Path>>(path: P) -> std::io::Result<Option<String>> {
+    if std::fs::metadata(&path)?.len() > MAX_SOURCE_BYTES {
+        return Ok(None);
+    }
+    let bytes = std::fs::read(&path)?;
+    if is_binary(&bytes) {
+        return Ok(None);
+    }
+    Ok(Some(match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(error) => String::from_utf8_lossy(error.as_bytes()).into_owned(),
+    }))
 }
 
 /// Performance monitoring utilities
@@ -198,16 +246,15 @@ impl PerformanceMonitor {
         self.start_time.elapsed()
     }
     
-    pub fn print_report(&self) {
-        println!("Performance Report:");
-        println!("Total time: {:?}", self.total_elapsed());
-        
+    /// Log each checkpoint's time at debug level (`-vv`)
+    pub fn log_report(&self) {
         let mut last_time = std::time::Duration::ZERO;
         for (name, total_time) in &self.checkpoints {
             let delta = *total_time - last_time;
-            println!("  {}: {:?} (Δ {:?})", name, total_time, delta);
+            tracing::debug!(checkpoint = %name, elapsed = ?total_time, delta = ?delta, "Performance checkpoint");
             last_time = *total_time;
         }
+        tracing::debug!(elapsed = ?self.total_elapsed(), "Performance total");
     }
 }
 
@@ -215,4 +262,43 @@ impl Default for PerformanceMonitor {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_count_lines_matches_str_lines() {
+        let dir = TempDir::new().unwrap();
+        for content in ["", "a", "a\n", "a\nb", "a\n\nb\n", "\n\n"] {
+            let path = dir.path().join("file.ts");
+            std::fs::write(&path, content).unwrap();
+            assert_eq!(count_lines_optimized(&path).unwrap(), content.lines().count(), "{content:?}");
+        }
+
+        let big = "const x = 1;\n".repeat(100_000) + "export {}";
+        let path = dir.path().join("big.js");
+        std::fs::write(&path, &big).unwrap();
+        assert_eq!(count_lines_optimized(&path).unwrap(), big.lines().count());
+    }
+
+    #[test]
+    fn test_binary_and_oversized_files() {
+        let dir = TempDir::new().unwrap();
+        let binary = dir.path().join("data.js");
+        std::fs::write(&binary, b"\x00\x01\nabc\n").unwrap();
+        assert_eq!(count_lines_optimized(&binary).unwrap(), 0);
+        assert_eq!(read_source(&binary).unwrap(), None);
+
+        let generated = dir.path().join("generated.js");
+        std::fs::write(&generated, "x;\n".repeat(MAX_SOURCE_BYTES as usize / 3 + 1)).unwrap();
+        assert_eq!(read_source(&generated).unwrap(), None);
+        assert_eq!(count_lines_optimized(&generated).unwrap(), MAX_SOURCE_BYTES as usize / 3 + 1);
+
+        let source = dir.path().join("page.tsx");
+        std::fs::write(&source, "export default 1;\n").unwrap();
+        assert_eq!(read_source(&source).unwrap().as_deref(), Some("export default 1;\n"));
+    }
+}
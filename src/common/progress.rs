@@ -1,7 +1,18 @@
 // Unified progress tracking utilities
 
 use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
+use super::report_formatter::ascii;
+
+/// `style` with indicatif's braille spinner swapped for `|/-\` in `--ascii` mode
+pub fn ascii_safe(style: ProgressStyle) -> ProgressStyle {
+    if ascii() {
+        style.tick_chars("|/-\\ ")
+    } else {
+        style
+    }
+}
 
 /// Builder for creating consistent progress bars across the application
 pub struct ProgressBarBuilder {
@@ -45,11 +56,11 @@ impl ProgressBarBuilder {
         }
 
         let pb = ProgressBar::new_spinner();
-        pb.set_style(
+        pb.set_style(ascii_safe(
             ProgressStyle::default_spinner()
                 .template("{spinner:.green} {msg}")
                 .unwrap(),
-        );
+        ));
         pb.set_message(self.message);
         pb.enable_steady_tick(Duration::from_millis(80));
         Some(pb)
@@ -63,12 +74,12 @@ impl ProgressBarBuilder {
 
         let length = self.length.unwrap_or(100);
         let pb = ProgressBar::new(length);
-        pb.set_style(
+        pb.set_style(ascii_safe(
             ProgressStyle::default_bar()
                 .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
                 .unwrap()
                 .progress_chars("#>-"),
-        );
+        ));
         pb.set_message(self.message);
         Some(pb)
     }
@@ -77,6 +88,7 @@ impl ProgressBarBuilder {
 /// Unified progress tracker for file operations
 pub struct FileProgressTracker {
     progress_bar: Option<ProgressBar>,
+    completed: AtomicU64,
     start_time: std::time::Instant,
     min_display_time: Duration,
 }
@@ -99,6 +111,7 @@ impl FileProgressTracker {
 
         Self {
             progress_bar,
+            completed: AtomicU64::new(0),
             start_time: std::time::Instant::now(),
             min_display_time: Duration::from_millis(200),
         }
@@ -111,15 +124,20 @@ impl FileProgressTracker {
         }
     }
 
+    /// Count one more file as done; safe to call from rayon workers
+    pub fn inc(&self) {
+        let completed = self.completed.fetch_add(1, Ordering::Relaxed) + 1;
+        self.set_position(completed);
+    }
+
     /// Finish the progress bar with a completion message
     pub fn finish_with_message(&self, message: &str) {
-        // Ensure minimum display time for visibility
-        let elapsed = self.start_time.elapsed();
-        if elapsed < self.min_display_time {
-            std::thread::sleep(self.min_display_time - elapsed);
-        }
-
         if let Some(pb) = &self.progress_bar {
+            // Ensure minimum display time for visibility
+            let elapsed = self.start_time.elapsed();
+            if elapsed < self.min_display_time {
+                std::thread::sleep(self.min_display_time - elapsed);
+            }
             pb.finish_with_message(message.to_string());
         }
     }
@@ -165,4 +183,13 @@ mod tests {
         tracker.finish_with_message("Done");
         // Should not panic when dropped
     }
+
+    #[test]
+    fn test_file_progress_tracker_counts_across_threads() {
+        use rayon::prelude::*;
+
+        let tracker = FileProgressTracker::new("Testing", Some(1000), true);
+        (0..1000).into_par_iter().for_each(|_| tracker.inc());
+        assert_eq!(tracker.completed.load(Ordering::Relaxed), 1000);
+    }
 }
\ No newline at end of file
@@ -6,14 +6,17 @@ use std::sync::OnceLock;
 /// Container for commonly used regex patterns
 pub struct CommonPatterns {
     pub any_type: Regex,
-    pub ts_ignore: Regex,
-    pub ts_expect_error: Regex,
     pub import_statement: Regex,
+    /// `import './styles.css'`, run for its side effects
+    pub side_effect_import: Regex,
     pub event_listener: Regex,
     pub timer_function: Regex,
     pub array_push: Regex,
     pub infinite_loop: Regex,
     pub closure_pattern: Regex,
+    pub fetch_call: Regex,
+    pub subscription: Regex,
+    pub observer: Regex,
 }
 
 static COMMON_PATTERNS: OnceLock<CommonPatterns> = OnceLock::new();
@@ -22,14 +25,16 @@ impl CommonPatterns {
     fn new() -> Result<Self, regex::Error> {
         Ok(Self {
             any_type: Regex::new(r"\b:\s*any\b")?,
-            ts_ignore: Regex::new(r"@ts-ignore")?,
-            ts_expect_error: Regex::new(r"@ts-expect-error")?,
             import_statement: Regex::new(r#"^import\s+(.+?)\s+from\s+['"](.+?)['"];?\s*(?://.*)?$"#)?,
+            side_effect_import: Regex::new(r#"^import\s+['"](.+?)['"]\s*;?\s*(?://.*)?$"#)?,
             event_listener: Regex::new(r"addEventListener\([^)]+\)")?,
             timer_function: Regex::new(r"set(?:Interval|Timeout)\([^)]+\)")?,
             array_push: Regex::new(r"\w+\.push\([^)]+\)")?,
             infinite_loop: Regex::new(r"while\s*\(\s*true\s*\)")?,
             closure_pattern: Regex::new(r"function[^{]*\{[\s\S]*function[^{]*\{[\s\S]*\}[\s\S]*\}")?,
+            fetch_call: Regex::new(r"(?:^|[^\w.])fetch\(")?,
+            subscription: Regex::new(r"\.subscribe\(|\b(?:onSnapshot|onAuthStateChanged|onIdTokenChanged|onValue)\(")?,
+            observer: Regex::new(r"\bnew\s+(?:Resize|Intersection|Mutation)Observer\(")?,
         })
     }
 }
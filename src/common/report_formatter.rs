@@ -1,13 +1,444 @@
 // Common report formatting utilities
 
+use colored::{ColoredString, Colorize};
+use dialoguer::theme::ColorfulTheme;
+use super::annotations::{Annotation, AnnotationLevel};
+use super::cli_args::{GroupBy, ListingOptions, SortBy};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use super::output;
 
-/// Common severity levels used across different analysis types
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+/// Common severity levels used across different analysis types, least severe first. Config
+/// files may spell them in lowercase, and `Medium` as npm's `moderate`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Severity {
+    #[serde(alias = "info")]
     Info,
+    #[serde(alias = "low")]
     Low,
+    #[serde(alias = "medium", alias = "moderate")]
     Medium,
+    #[serde(alias = "high")]
     High,
+    #[serde(alias = "critical")]
     Critical,
 }
+
+impl Severity {
+    /// `text` in this severity's color, for rule ids and finding kinds in text reports
+    pub fn paint(&self, text: &str) -> ColoredString {
+        match self {
+            Severity::Critical => text.on_red().white().bold(),
+            Severity::High => text.red().bold(),
+            Severity::Medium => text.yellow(),
+            Severity::Low | Severity::Info => text.dimmed(),
+        }
+    }
+}
+
+/// How much of a text report `--summary` and `--top` keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    /// Only the summary block
+    Summary,
+    /// The first `n` findings of each level, in the command's order, then the summary block
+    Top(usize),
+}
+
+/// Files listed under "Most affected files"
+const MOST_AFFECTED_SHOWN: usize = 5;
+
+const LEVELS: [(AnnotationLevel, &str, Severity); 4] = [
+    (AnnotationLevel::Critical, "🚨 Critical", Severity::Critical),
+    (AnnotationLevel::Error, "❌ Errors", Severity::High),
+    (AnnotationLevel::Warning, "⚠️  Warnings", Severity::Medium),
+    (AnnotationLevel::Notice, "ℹ️  Notices", Severity::Low),
+];
+
+/// The condensed text report of `--summary` and `--top`, which stays short on a project with
+/// thousands of findings
+pub fn print_condensed(command: &str, findings: &[Annotation], detail: Detail, duration_ms: u64) {
+    if let Detail::Top(shown) = detail {
+        for (level, label, severity) in &LEVELS {
+            let at_level: Vec<&Annotation> = findings.iter().filter(|finding| finding.level == *level).collect();
+            if at_level.is_empty() {
+                continue;
+            }
+            output::line(severity.paint(&format!("{} ({})", label, at_level.len())).bold());
+            for finding in at_level.iter().take(shown) {
+                output::line(format!("  {}  {}: {}", location(finding).cyan(), finding.labeled_title().bold(), finding.message));
+            }
+            if at_level.len() > shown {
+                output::line(format!("  {}", format!("… and {} more", at_level.len() - shown).dimmed()));
+            }
+            output::blank();
+        }
+    }
+    print_summary(command, findings, duration_ms);
+}
+
+fn print_summary(command: &str, findings: &[Annotation], duration_ms: u64) {
+    output::line(format!("📋 {} summary", command).bold().blue());
+    output::line("─".repeat(40).dimmed());
+    if findings.is_empty() {
+        output::line("✅ No issues found".green());
+    }
+    for (level, label, severity) in &LEVELS {
+        let count = findings.iter().filter(|finding| finding.level == *level).count();
+        if count > 0 {
+            output::line(format!("  {:<14} {}", label, severity.paint(&count.to_string())));
+        }
+    }
+
+    let mut per_file: HashMap<&str, usize> = HashMap::new();
+    for file in findings.iter().filter_map(|finding| finding.file.as_deref()) {
+        *per_file.entry(file).or_default() += 1;
+    }
+    let mut per_file: Vec<(&str, usize)> = per_file.into_iter().collect();
+    per_file.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    if !per_file.is_empty() {
+        output::blank();
+        output::line("  Most affected files:");
+        for (file, count) in per_file.iter().take(MOST_AFFECTED_SHOWN) {
+            output::line(format!("    {:>5}  {}", count, file));
+        }
+        if per_file.len() > MOST_AFFECTED_SHOWN {
+            output::line(format!("    {}", format!("… {} more files", per_file.len() - MOST_AFFECTED_SHOWN).dimmed()));
+        }
+    }
+    output::blank();
+    output::line(format!("  Duration: {}ms", duration_ms));
+}
+
+/// `file:line`, the file alone, or `(project)` for a project-wide finding
+pub fn location(finding: &Annotation) -> String {
+    match (&finding.file, finding.line) {
+        (Some(file), Some(line)) => format!("{}:{}", file, line),
+        (Some(file), None) => file.clone(),
+        (None, _) => "(project)".to_string(),
+    }
+}
+
+/// One finding as `--group-by` and `--sort` list it
+#[derive(Debug, Clone)]
+pub struct Row {
+    pub path: String,
+    pub line: Option<usize>,
+    /// The kind of finding, e.g. `Broken import`
+    pub rule: String,
+    pub severity: Severity,
+    /// Length of the file, function or component the finding is about
+    pub lines: Option<usize>,
+    /// Size of the file the finding is about
+    pub size_bytes: Option<u64>,
+    /// The finding itself, printed after its location
+    pub text: String,
+}
+
+/// Print `rows` sorted by `--sort` and grouped by `--group-by`. Groups with the most
+/// findings come first, except severity groups, which go from most to least severe
+pub fn print_listing(mut rows: Vec<Row>, options: ListingOptions) {
+    if let Some(sort) = options.sort {
+        sort_rows(&mut rows, sort);
+    }
+    let Some(group_by) = options.group_by else {
+        for row in &rows {
+            print_row(row, true);
+        }
+        output::blank();
+        return;
+    };
+
+    let mut groups: Vec<(String, Vec<&Row>)> = Vec::new();
+    for row in &rows {
+        let key = group_key(row, group_by);
+        match groups.iter_mut().find(|(name, _)| *name == key) {
+            Some((_, members)) => members.push(row),
+            None => groups.push((key, vec![row])),
+        }
+    }
+    match group_by {
+        GroupBy::Severity => groups.sort_by(|a, b| b.1[0].severity.cmp(&a.1[0].severity)),
+        _ => groups.sort_by(|a, b| b.1.len().cmp(&a.1.len())
+            .then_with(|| total_lines(&b.1).cmp(&total_lines(&a.1)))
+            .then_with(|| a.0.cmp(&b.0))),
+    }
+
+    for (name, members) in &groups {
+        let mut header = format!("{} finding{}", members.len(), if members.len() == 1 { "" } else { "s" });
+        let lines = total_lines(members);
+        if lines > 0 {
+            header.push_str(&format!(", {} lines", lines));
+        }
+        let title = match group_by {
+            GroupBy::File => format!("📄 {}", name).cyan().bold(),
+            GroupBy::Directory => format!("📁 {}/", name).cyan().bold(),
+            GroupBy::Rule => format!("🏷️  {}", name).bold(),
+            GroupBy::Severity => members[0].severity.paint(name).bold(),
+        };
+        output::line(format!("{} {}", title, format!("({})", header).dimmed()));
+        for row in members {
+            print_row(row, group_by != GroupBy::File);
+        }
+        output::blank();
+    }
+}
+
+fn sort_rows(rows: &mut [Row], sort: SortBy) {
+    match sort {
+        SortBy::Lines => rows.sort_by_key(|row| std::cmp::Reverse(row.lines)),
+        SortBy::Size => rows.sort_by_key(|row| std::cmp::Reverse(row.size_bytes)),
+        SortBy::Severity => rows.sort_by(|a, b| b.severity.cmp(&a.severity)),
+        SortBy::Path => rows.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line))),
+    }
+}
+
+fn group_key(row: &Row, group_by: GroupBy) -> String {
+    match group_by {
+        GroupBy::File => row.path.clone(),
+        GroupBy::Rule => row.rule.clone(),
+        GroupBy::Severity => format!("{:?}", row.severity),
+        GroupBy::Directory => match std::path::Path::new(&row.path).parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.display().to_string(),
+            _ => ".".to_string(),
+        },
+    }
+}
+
+fn total_lines(rows: &[&Row]) -> usize {
+    rows.iter().filter_map(|row| row.lines).sum()
+}
+
+fn print_row(row: &Row, with_path: bool) {
+    let location = match (with_path, row.line) {
+        (true, Some(line)) => format!("{}:{}", row.path, line),
+        (true, None) => row.path.clone(),
+        (false, Some(line)) => format!("line {}", line),
+        (false, None) => String::new(),
+    };
+    let severity = row.severity.paint(&format!("{:<8}", format!("{:?}", row.severity)));
+    output::line(format!("  {}  {}  {}", severity, location.cyan(), row.text));
+}
+
+/// `CI` is set by GitHub Actions, GitLab, CircleCI, Travis, Buildkite and most other services
+pub fn in_ci() -> bool {
+    std::env::var("CI").is_ok_and(|value| truthy(&value))
+}
+
+/// Turn ANSI colors on or off for the reports, prompts and progress bars of the run
+pub fn init_color(no_color: bool) {
+    let stdout = color_enabled(no_color, std::io::stdout().is_terminal());
+    colored::control::set_override(stdout);
+    console::set_colors_enabled(stdout);
+    console::set_colors_enabled_stderr(color_enabled(no_color, std::io::stderr().is_terminal()));
+}
+
+/// Whether output to a stream that is (or is not) a `terminal` gets ANSI colors
+pub fn color_enabled(no_color: bool, terminal: bool) -> bool {
+    use_color(no_color, |name| std::env::var(name).ok(), terminal)
+}
+
+/// `--no-color`, a non-empty `NO_COLOR` (no-color.org) and CI logs, which often show escape
+/// codes as text, turn colors off, as does output that is not a terminal.
+/// `CLICOLOR_FORCE` keeps them on anywhere but with `--no-color`
+fn use_color(no_color: bool, var: impl Fn(&str) -> Option<String>, terminal: bool) -> bool {
+    if no_color {
+        return false;
+    }
+    if var("CLICOLOR_FORCE").is_some_and(|value| truthy(&value)) {
+        return true;
+    }
+    let no_color_var = var("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let ci = var("CI").is_some_and(|value| truthy(&value));
+    terminal && !no_color_var && !ci
+}
+
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Print plain ASCII markers instead of emoji and box-drawing characters for the rest of the
+/// run. `None` picks ASCII when the terminal cannot show UTF-8
+pub fn init_ascii(ascii: Option<bool>) {
+    let ascii = ascii.unwrap_or_else(|| ascii_by_default(|name| std::env::var(name).ok(), cfg!(windows)));
+    ASCII.store(ascii, Ordering::Relaxed);
+}
+
+/// Whether text output is limited to ASCII
+pub fn ascii() -> bool {
+    ASCII.load(Ordering::Relaxed)
+}
+
+/// `text` as it is printed: unchanged, or with emoji and dividers swapped for ASCII in `--ascii`
+/// mode. Letters outside ASCII, as in file names, are left alone
+pub fn plain(text: &str) -> Cow<'_, str> {
+    if !ascii() || text.is_ascii() {
+        return Cow::Borrowed(text);
+    }
+    Cow::Owned(to_ascii(text))
+}
+
+/// The look of interactive prompts: dialoguer's colorful theme, with ASCII prefixes in
+/// `--ascii` mode
+pub fn prompt_theme() -> ColorfulTheme {
+    let theme = ColorfulTheme::default();
+    if !ascii() {
+        return theme;
+    }
+    let marker = |text: &str| console::style(text.to_string()).for_stderr();
+    ColorfulTheme {
+        prompt_suffix: marker(">").black().bright(),
+        success_prefix: marker("+").green(),
+        success_suffix: marker("-").black().bright(),
+        error_prefix: marker("x").red(),
+        active_item_prefix: marker(">").green(),
+        checked_item_prefix: marker("[x]").green(),
+        unchecked_item_prefix: marker("[ ]").magenta(),
+        picked_item_prefix: marker(">").green(),
+        ..theme
+    }
+}
+
+/// The first locale variable that is set decides, as it does for the C library. No locale at
+/// all is taken as UTF-8, as every current distribution and macOS use it. Windows consoles
+/// default to a legacy code page, except in Windows Terminal
+fn ascii_by_default(var: impl Fn(&str) -> Option<String>, windows: bool) -> bool {
+    if windows {
+        return var("WT_SESSION").is_none();
+    }
+    match ["LC_ALL", "LC_CTYPE", "LANG"].iter().find_map(|name| var(name).filter(|value| !value.is_empty())) {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            !locale.contains("utf-8") && !locale.contains("utf8")
+        }
+        None => false,
+    }
+}
+
+fn to_ascii(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let marker = match c {
+            '✅' | '🟢' => "[ok]",
+            '❌' => "[x]",
+            '⚠' | '🟠' | '🟡' => "[!]",
+            '🚨' | '🔴' | '🛑' => "[!!]",
+            '💡' => "[tip]",
+            'ℹ' => "[i]",
+            '⏭' => "[skip]",
+            '🚫' => "[no]",
+            '❓' => "[?]",
+            '⚪' => "[-]",
+            '─' | '—' | '–' | '·' => "-",
+            '•' => "*",
+            '→' => "->",
+            '←' => "<-",
+            '↔' => "<->",
+            '▶' => ">",
+            '…' => "...",
+            '×' => "x",
+            '±' => "+/-",
+            '≥' => ">=",
+            '≤' => "<=",
+            '▁' => "_",
+            '▂' => ".",
+            '▃' => "-",
+            '▄' => "=",
+            '▅' => "+",
+            '▆' => "*",
+            '▇' => "%",
+            '█' => "#",
+            '░' => ".",
+            // Emoji presentation selector left over from a marker
+            '\u{fe0f}' => "",
+            c if is_pictograph(c) => {
+                // A decorative icon goes with the space after it, so headings stay aligned
+                while chars.next_if(|next| *next == '\u{fe0f}').is_some() {}
+                while chars.next_if(|next| *next == ' ').is_some() {}
+                continue;
+            }
+            c => {
+                out.push(c);
+                continue;
+            }
+        };
+        out.push_str(marker);
+    }
+    out
+}
+
+/// Emoji and the dingbats and technical symbols used as icons
+fn is_pictograph(c: char) -> bool {
+    matches!(c as u32, 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x1F000..=0x1FAFF)
+}
+
+fn truthy(value: &str) -> bool {
+    !value.is_empty() && value != "false" && value != "0"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars<'a>(pairs: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+        move |name| pairs.iter().find(|(var, _)| *var == name).map(|(_, value)| value.to_string())
+    }
+
+    #[test]
+    fn test_color_follows_flag_environment_and_terminal() {
+        assert!(use_color(false, vars(&[]), true));
+        assert!(!use_color(false, vars(&[]), false));
+        assert!(!use_color(true, vars(&[]), true));
+        assert!(!use_color(false, vars(&[("NO_COLOR", "1")]), true));
+        assert!(use_color(false, vars(&[("NO_COLOR", "")]), true));
+        assert!(!use_color(false, vars(&[("CI", "true")]), true));
+        assert!(use_color(false, vars(&[("CI", "false")]), true));
+        assert!(use_color(false, vars(&[("CI", "true"), ("CLICOLOR_FORCE", "1")]), false));
+        assert!(!use_color(true, vars(&[("CLICOLOR_FORCE", "1")]), true));
+    }
+
+    fn row(path: &str, severity: Severity, lines: usize) -> Row {
+        Row { path: path.to_string(), line: None, rule: "Large file".to_string(), severity, lines: Some(lines), size_bytes: None, text: String::new() }
+    }
+
+    #[test]
+    fn test_rows_sort_and_group() {
+        let mut rows = vec![row("src/b.ts", Severity::Medium, 150), row("a.ts", Severity::Critical, 120), row("src/c.ts", Severity::High, 400)];
+        sort_rows(&mut rows, SortBy::Lines);
+        assert_eq!(rows.iter().map(|row| row.path.as_str()).collect::<Vec<_>>(), vec!["src/c.ts", "src/b.ts", "a.ts"]);
+        sort_rows(&mut rows, SortBy::Severity);
+        assert_eq!(rows.iter().map(|row| row.path.as_str()).collect::<Vec<_>>(), vec!["a.ts", "src/c.ts", "src/b.ts"]);
+        sort_rows(&mut rows, SortBy::Path);
+        assert_eq!(rows[0].path, "a.ts");
+
+        assert_eq!(group_key(&rows[1], GroupBy::Directory), "src");
+        assert_eq!(group_key(&rows[0], GroupBy::Directory), ".");
+        assert_eq!(group_key(&rows[0], GroupBy::Severity), "Critical");
+    }
+
+    #[test]
+    fn test_ascii_follows_locale() {
+        assert!(!ascii_by_default(vars(&[]), false));
+        assert!(!ascii_by_default(vars(&[("LANG", "en_US.UTF-8")]), false));
+        assert!(!ascii_by_default(vars(&[("LC_ALL", "C.utf8"), ("LANG", "C")]), false));
+        assert!(ascii_by_default(vars(&[("LANG", "C")]), false));
+        assert!(ascii_by_default(vars(&[("LC_ALL", ""), ("LC_CTYPE", "POSIX"), ("LANG", "en_US.UTF-8")]), false));
+        assert!(ascii_by_default(vars(&[("LANG", "de_DE.ISO-8859-1")]), false));
+        assert!(ascii_by_default(vars(&[("LANG", "en_US.UTF-8")]), true));
+        assert!(!ascii_by_default(vars(&[("WT_SESSION", "1")]), true));
+    }
+
+    #[test]
+    fn test_to_ascii_swaps_markers_and_drops_icons() {
+        assert_eq!(to_ascii("✅ No issues found"), "[ok] No issues found");
+        assert_eq!(to_ascii("⚠️  3 warnings"), "[!]  3 warnings");
+        assert_eq!(to_ascii("🛠️  Dev Tools Menu"), "Dev Tools Menu");
+        assert_eq!(to_ascii("📄 PAGES (3)"), "PAGES (3)");
+        assert_eq!(to_ascii("────"), "----");
+        assert_eq!(to_ascii("  • a → b · café…"), "  * a -> b - café...");
+        assert_eq!(to_ascii("▁█▅"), "_#+");
+        assert!(to_ascii("🚨 CRITICAL 💡 Split 📦 this").is_ascii());
+    }
+}
@@ -0,0 +1,173 @@
+/// Stable ids for every kind of finding, the `rule` of each `Annotation`. SARIF results,
+/// baselines and suppressions refer to findings by these ids, so an id never changes or
+/// moves to another kind of finding once released
+use serde::Serialize;
+use super::annotations::AnnotationLevel;
+
+/// One kind of finding
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct RuleInfo {
+    pub id: &'static str,
+    /// The command that reports it
+    pub command: &'static str,
+    /// The level it is reported at; rules whose findings vary give the most common one
+    pub default_level: AnnotationLevel,
+    pub description: &'static str,
+}
+
+const fn rule(id: &'static str, command: &'static str, default_level: AnnotationLevel, description: &'static str) -> RuleInfo {
+    RuleInfo { id, command, default_level, description }
+}
+
+use AnnotationLevel::{Critical, Error, Notice, Warning};
+
+/// Every built-in rule, by command
+pub const RULES: &[RuleInfo] = &[
+    rule("SNF-LARGE-001", "large", Warning, "File over its line threshold; error and critical past the higher severity levels"),
+    rule("SNF-LARGE-002", "large", Warning, "Function or component over the function threshold"),
+    rule("SNF-TYPES-TSC", "types", Error, "TypeScript compiler error"),
+    rule("SNF-TYPES-ANY", "types", Warning, "Explicit 'any' type"),
+    rule("SNF-TYPES-RETURN", "types", Warning, "Function without a return type"),
+    rule("SNF-TYPES-PARAM", "types", Warning, "Parameter without a type"),
+    rule("SNF-TYPES-TS-IGNORE", "types", Warning, "@ts-ignore comment"),
+    rule("SNF-TYPES-TS-EXPECT-ERROR", "types", Warning, "@ts-expect-error comment without a description"),
+    rule("SNF-TYPES-TS-NOCHECK", "types", Warning, "@ts-nocheck comment"),
+    rule("SNF-TYPES-NON-NULL", "types", Warning, "Non-null assertion"),
+    rule("SNF-TYPES-IMPLICIT-ANY", "types", Warning, "Value whose type is implicitly 'any'"),
+    rule("SNF-TYPES-COVERAGE", "types", Error, "Directory below its type coverage threshold"),
+    rule("SNF-TYPES-BUDGET", "types", Error, "Type safety budget exceeded"),
+    rule("SNF-TYPES-TSCONFIG-STRICT", "types", Warning, "tsconfig strictness flag off"),
+    rule("SNF-TYPES-TSCONFIG-NEXT", "types", Warning, "tsconfig option that conflicts with Next.js"),
+    rule("SNF-TYPES-TSCONFIG-EXCLUDED", "types", Warning, "File tsconfig leaves out of type checking"),
+    rule("SNF-IMP-BROKEN", "imports", Error, "Import of a file, package or export that does not exist"),
+    rule("SNF-IMP-UNUSED", "imports", Warning, "Imported names that are never used"),
+    rule("SNF-IMP-TYPE-ONLY", "imports", Warning, "Import only used as types; use `import type`"),
+    rule("SNF-IMP-TYPE-AS-VALUE", "imports", Warning, "`import type` of names used as values"),
+    rule("SNF-IMP-ORDER", "imports", Warning, "Imports out of the configured order"),
+    rule("SNF-IMP-UNUSED-EXPORT", "imports", Notice, "Export no other file imports"),
+    rule("SNF-BUNDLE-BUDGET", "bundle", Error, "Bundle, chunk or route over its size budget"),
+    rule("SNF-BUNDLE-REGRESSION", "bundle", Warning, "Chunk grew past the allowed growth since the baseline"),
+    rule("SNF-MEM-ARRAY-GROWTH", "memory", Warning, "Array that only grows, e.g. push in a long-lived scope"),
+    rule("SNF-MEM-LISTENER", "memory", Error, "Event listener that is never removed"),
+    rule("SNF-MEM-CIRCULAR", "memory", Warning, "Circular reference between objects"),
+    rule("SNF-MEM-RETENTION", "memory", Warning, "Large object kept alive longer than needed"),
+    rule("SNF-MEM-LOOP", "memory", Warning, "Loop without a clear exit"),
+    rule("SNF-MEM-TIMER", "memory", Error, "setInterval/setTimeout that is never cleared"),
+    rule("SNF-MEM-DOM", "memory", Warning, "Detached DOM element still referenced"),
+    rule("SNF-MEM-CLOSURE", "memory", Notice, "Closure holding on to a large scope"),
+    rule("SNF-MEM-FETCH", "memory", Warning, "fetch in an effect without an AbortController"),
+    rule("SNF-MEM-SUBSCRIPTION", "memory", Error, "Subscription that is never closed"),
+    rule("SNF-MEM-OBSERVER", "memory", Error, "Observer that is never disconnected"),
+    rule("SNF-ENV-MISSING", "env", Error, "Required environment variable is not set"),
+    rule("SNF-ENV-EMPTY", "env", Warning, "Environment variable is empty"),
+    rule("SNF-ENV-INVALID", "env", Warning, "Environment variable does not match its schema"),
+    rule("SNF-ENV-SENSITIVE", "env", Critical, "Environment variable exposes sensitive data"),
+    rule("SNF-ENV-UNDEFINED", "env", Warning, "Variable read in code but defined in no .env file"),
+    rule("SNF-ENV-SERVER-IN-CLIENT", "env", Error, "Server-only variable read in client code"),
+    rule("SNF-ENV-NOT-GITIGNORED", "env", Error, "Env file not excluded by .gitignore"),
+    rule("SNF-ENV-COMMITTED", "env", Critical, "Env file committed to git history"),
+    rule("SNF-ENV-TRACKED-SECRET", "env", Critical, "Secret in an env file tracked by git"),
+    rule("SNF-ENV-DRIFT", "env", Warning, "Variable missing from one environment's env file"),
+    rule("SNF-ENV-PUBLIC-SECRET", "env", Critical, "Secret in a public (NEXT_PUBLIC_, VITE_, ...) variable"),
+    rule("SNF-A11Y-IMG-ALT", "a11y", Error, "Image without alt text"),
+    rule("SNF-A11Y-CLICK-EVENTS", "a11y", Warning, "Click handler without keyboard access"),
+    rule("SNF-A11Y-FORM-LABEL", "a11y", Error, "Form control without a label"),
+    rule("SNF-A11Y-POSITIVE-TABINDEX", "a11y", Warning, "Positive tabIndex"),
+    rule("SNF-A11Y-HTML-LANG", "a11y", Error, "Document without a language"),
+    rule("SNF-A11Y-PREFER-BUTTON", "a11y", Warning, "Element used as a button"),
+    rule("SNF-CONSOLE-LOG", "console", Warning, "console.* call"),
+    rule("SNF-CONSOLE-DEBUGGER", "console", Warning, "debugger statement"),
+    rule("SNF-CONSOLE-ALERT", "console", Warning, "alert, confirm or prompt call"),
+    rule("SNF-CONSOLE-TODO", "console", Notice, "TODO or FIXME comment"),
+    rule("SNF-SEC-SQL-INJECTION", "security", Critical, "SQL built from strings"),
+    rule("SNF-SEC-DANGEROUS-HTML", "security", Error, "Unsanitized HTML"),
+    rule("SNF-SEC-EVAL", "security", Error, "Dynamic code execution"),
+    rule("SNF-SEC-CHILD-PROCESS", "security", Error, "child_process on a request path"),
+    rule("SNF-SEC-OPEN-REDIRECT", "security", Warning, "Open redirect"),
+    rule("SNF-SEC-TARGET-BLANK", "security", Notice, "target=\"_blank\" without rel=\"noopener\""),
+    rule("SNF-SECRET-PRIVATE-KEY", "secrets", Critical, "Private key"),
+    rule("SNF-SECRET-AWS", "secrets", Error, "AWS access key"),
+    rule("SNF-SECRET-GITHUB", "secrets", Error, "GitHub token"),
+    rule("SNF-SECRET-STRIPE", "secrets", Error, "Stripe live key"),
+    rule("SNF-SECRET-SLACK", "secrets", Error, "Slack token"),
+    rule("SNF-SECRET-GOOGLE", "secrets", Error, "Google API key"),
+    rule("SNF-SECRET-JWT", "secrets", Warning, "JSON Web Token"),
+    rule("SNF-SECRET-CONNECTION-STRING", "secrets", Error, "Connection string with credentials"),
+    rule("SNF-SECRET-GENERIC", "secrets", Warning, "Hard-coded secret, token or password"),
+    rule("SNF-IMG-HEAVY", "images", Warning, "Image over the size limit"),
+    rule("SNF-IMG-OVERSIZED", "images", Warning, "Image wider or taller than the dimension limit"),
+    rule("SNF-IMG-LEGACY-FORMAT", "images", Notice, "Image that would be smaller as WebP or AVIF"),
+    rule("SNF-IMG-EMBEDDED-RASTER", "images", Notice, "SVG carrying a base64 raster image"),
+    rule("SNF-IMG-NEXT-IMAGE", "images", Notice, "Large image rendered with <img> instead of next/image"),
+    rule("SNF-COMPLEXITY-001", "complexity", Warning, "Function over the cyclomatic or cognitive complexity limit"),
+    rule("SNF-DUP-001", "duplicates", Warning, "Block of code copied from another place"),
+    rule("SNF-COMP-LINES", "components", Error, "Component over 100 lines; critical over 200"),
+    rule("SNF-COMP-HOOKS", "components", Error, "Component using more than 10 hooks, composables or lifecycle callbacks"),
+    rule("SNF-COMP-PROPS", "components", Warning, "Component taking more than 8 props"),
+    rule("SNF-COMP-NESTING", "components", Warning, "Component nested more than 6 levels deep"),
+    rule("SNF-COMP-PROP-DRILLING", "components", Notice, "Prop passed down through 3 or more components"),
+    rule("SNF-TW-UNKNOWN-COLOR", "tailwind", Error, "Color utility naming a color the theme does not define"),
+    rule("SNF-TW-UNCOVERED-FILE", "tailwind", Error, "File using Tailwind classes outside the config's content globs"),
+    rule("SNF-TW-ARBITRARY", "tailwind", Warning, "Share of arbitrary-value classes over max_arbitrary_percent"),
+    rule("SNF-TW-REPEATED-ARBITRARY", "tailwind", Warning, "Arbitrary value repeated often enough to belong in the theme"),
+    rule("SNF-TW-DUPLICATE-CLASSES", "tailwind", Notice, "Same long class string in several places"),
+    rule("SNF-I18N-MISSING-KEY", "i18n", Error, "Translation key used in code but missing from a locale"),
+    rule("SNF-I18N-HARDCODED", "i18n", Warning, "UI text not passed through the translation function"),
+    rule("SNF-I18N-UNUSED-KEY", "i18n", Warning, "Translation key no code uses"),
+    rule("SNF-TESTS-COVERAGE", "tests", Error, "Share of tested source files below min_tested_percent"),
+    rule("SNF-TESTS-UNTESTED", "tests", Notice, "Source file without a matching test file"),
+    rule("SNF-TESTS-SKIPPED", "tests", Warning, "Skipped test or suite (describe.skip, it.skip, xit, ...)"),
+    rule("SNF-OUTDATED-DEPRECATED", "outdated", Error, "Installed version of a dependency is deprecated"),
+    rule("SNF-OUTDATED-MAJOR", "outdated", Warning, "Dependency a major version or more behind its latest release"),
+    rule("SNF-OUTDATED-MINOR", "outdated", Notice, "Dependency behind its latest minor or patch release"),
+    rule("SNF-OUTDATED-STALE", "outdated", Notice, "Dependency without a release in [outdated] stale_after_days"),
+    rule("SNF-VULNS-CRITICAL", "vulns", Critical, "Installed package with a critical security advisory"),
+    rule("SNF-VULNS-HIGH", "vulns", Error, "Installed package with a high severity security advisory"),
+    rule("SNF-VULNS-MODERATE", "vulns", Warning, "Installed package with a moderate security advisory"),
+    rule("SNF-VULNS-LOW", "vulns", Notice, "Installed package with a low severity or informational advisory"),
+    rule("SNF-DOCKER-NODE-VERSION", "docker", Error, "Node.js base image outside package.json engines.node"),
+    rule("SNF-DOCKER-IGNORE", "docker", Warning, "No .dockerignore, or one that lets node_modules, .next/cache or .env in"),
+    rule("SNF-DOCKER-DEV-DEPS", "docker", Warning, "devDependencies installed in or copied into the final stage"),
+    rule("SNF-DOCKER-SINGLE-STAGE", "docker", Notice, "Dockerfile without a separate build stage"),
+    rule("SNF-CI-NODE-VERSION", "ci", Error, "CI Node.js version outside engines.node or different from .nvmrc"),
+    rule("SNF-CI-MISSING-STEP", "ci", Warning, "No CI workflow builds, type-checks, tests or runs sniff"),
+    rule("SNF-CI-NO-CACHE", "ci", Notice, "CI installs packages without a package manager cache"),
+    rule("SNF-RSC-SERVER-PACKAGE", "boundaries", Error, "Client component code importing a server-only package or Node.js module"),
+    rule("SNF-RSC-BROWSER-API", "boundaries", Error, "Server component code using browser globals or client-only React hooks"),
+    rule("SNF-RSC-LARGE-BOUNDARY", "boundaries", Warning, "'use client' boundary shipping more JavaScript than max_boundary_kb"),
+    rule("SNF-NEXT-FETCH-CACHE", "nextjs", Warning, "fetch() in a Server Component without cache or revalidate options"),
+    rule("SNF-NEXT-SSR-STATIC", "nextjs", Warning, "getServerSideProps that reads nothing from the request"),
+    rule("SNF-NEXT-CLIENT-FETCH", "nextjs", Warning, "Client-side fetching of data that is known at build time"),
+    rule("SNF-NEXT-NO-REVALIDATE", "nextjs", Notice, "Statically cached data without revalidate"),
+];
+
+/// The rule with this id
+pub fn find(id: &str) -> Option<&'static RuleInfo> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Rule id prefixes of findings ingested from other tools, with the command that reports them
+pub const EXTERNAL_PREFIXES: &[(&str, &str)] = &[("eslint/", "lint"), ("biome/", "lint")];
+
+/// The command that reports a built-in or ingested rule id
+pub fn command_of(id: &str) -> Option<&'static str> {
+    find(id).map(|rule| rule.command).or_else(|| {
+        EXTERNAL_PREFIXES.iter().find(|(prefix, _)| id.starts_with(prefix)).map(|(_, command)| *command)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_rule_ids_are_unique_and_well_formed() {
+        let mut ids = HashSet::new();
+        for rule in RULES {
+            assert!(ids.insert(rule.id), "duplicate rule id {}", rule.id);
+            assert!(rule.id.starts_with("SNF-") && rule.id.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-'), "{}", rule.id);
+        }
+        assert_eq!(find("SNF-MEM-TIMER").map(|rule| rule.command), Some("memory"));
+    }
+}
@@ -0,0 +1,87 @@
+/// Line-based pattern rules, shared by `sniff memory`'s leak patterns and the custom
+/// `[[rules]]` of `sniff rules`. Each rule's regex is matched against the lines of the
+/// source with comments blanked out, so commented-out code never matches
+use regex::Regex;
+use super::report_formatter::Severity;
+use super::source::strip_comments;
+
+/// A check that flags every line its regex matches
+#[derive(Debug, Clone)]
+pub struct PatternRule<K> {
+    /// What the rule is, e.g. a memory `PatternType` or a custom rule's name
+    pub kind: K,
+    pub regex: Regex,
+    pub severity: Severity,
+    pub description: String,
+    pub recommendation: String,
+}
+
+/// Where a rule matched
+#[derive(Debug)]
+pub struct RuleMatch<'r, 'c, K> {
+    pub rule: &'r PatternRule<K>,
+    /// 1-based
+    pub line_number: usize,
+    /// 1-based, in characters
+    pub column: usize,
+    /// The source line, comments included
+    pub line: &'c str,
+    /// Byte offset of the line in the source
+    pub line_offset: usize,
+    /// Byte offset of the match in the line
+    pub start: usize,
+}
+
+/// The first match of every rule on every line, in line order and then rule order
+pub fn scan_lines<'r, 'c, K>(content: &'c str, rules: &'r [PatternRule<K>]) -> Vec<RuleMatch<'r, 'c, K>> {
+    let code = strip_comments(content);
+    let mut matches = Vec::new();
+    let mut line_offset = 0;
+    for (index, line) in content.lines().enumerate() {
+        let offset = line_offset;
+        line_offset += line.len() + 1;
+        if content[offset + line.len()..].starts_with('\r') {
+            line_offset += 1;
+        }
+        // Offsets are kept by `strip_comments`, so the same range is this line's code
+        let code_line = &code[offset..offset + line.len()];
+        for rule in rules {
+            if let Some(found) = rule.regex.find(code_line) {
+                matches.push(RuleMatch {
+                    rule,
+                    line_number: index + 1,
+                    column: line[..found.start()].chars().count() + 1,
+                    line,
+                    line_offset: offset,
+                    start: found.start(),
+                });
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(kind: &'static str, pattern: &str) -> PatternRule<&'static str> {
+        PatternRule {
+            kind,
+            regex: Regex::new(pattern).unwrap(),
+            severity: Severity::Medium,
+            description: String::new(),
+            recommendation: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_matches_code_but_not_comments() {
+        let rules = [rule("moment", r"from\s+'moment'"), rule("legacy", r"\blegacyFetch\(")];
+        let content = "import dayjs from 'dayjs';\r\n// import x from 'moment';\nimport m from 'moment'; const d = legacyFetch('/a');\n/* legacyFetch() */ legacyFetch();\n";
+        let found: Vec<(&str, usize, usize)> = scan_lines(content, &rules).iter()
+            .map(|found| (found.rule.kind, found.line_number, found.column))
+            .collect();
+        assert_eq!(found, vec![("moment", 3, 10), ("legacy", 3, 35), ("legacy", 4, 21)]);
+    }
+}
@@ -0,0 +1,109 @@
+/// SARIF 2.1.0 log for `--format sarif`, the format GitHub code scanning and most security dashboards import
+use serde_json::{json, Value};
+use super::annotations::{workspace_path, Annotation, AnnotationLevel};
+use super::rule_catalog;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// One run of `sniff <command>` with a result per finding. Built-in rules are described from
+/// the rule catalog; findings without a rule id are grouped under the command name.
+pub fn render(command: &str, findings: &[Annotation]) -> String {
+    let mut rules: Vec<Value> = Vec::new();
+    let mut rule_ids: Vec<&str> = Vec::new();
+    let results: Vec<Value> = findings.iter().map(|finding| {
+        let rule_id = finding.rule.as_deref().unwrap_or(command);
+        let rule_index = match rule_ids.iter().position(|id| *id == rule_id) {
+            Some(index) => index,
+            None => {
+                let known = rule_catalog::find(rule_id);
+                let description = match (known, &finding.rule) {
+                    (Some(known), _) => known.description.to_string(),
+                    (None, Some(_)) => finding.title.clone(),
+                    (None, None) => format!("sniff {} finding", command),
+                };
+                rule_ids.push(rule_id);
+                let mut rule = json!({ "id": rule_id, "shortDescription": { "text": description } });
+                if let Some(known) = known {
+                    rule["defaultConfiguration"] = json!({ "level": level(known.default_level) });
+                }
+                rules.push(rule);
+                rule_ids.len() - 1
+            }
+        };
+        let mut result = json!({
+            "ruleId": rule_id,
+            "ruleIndex": rule_index,
+            "level": level(finding.level),
+            "message": { "text": format!("{}: {}", finding.title, finding.message) },
+        });
+        if let Some(location) = location(finding) {
+            result["locations"] = json!([location]);
+        }
+        result
+    }).collect();
+
+    let log = json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "sniff",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "automationDetails": { "id": format!("sniff/{}/", command) },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&log).unwrap_or_default()
+}
+
+fn level(level: AnnotationLevel) -> &'static str {
+    match level {
+        AnnotationLevel::Critical | AnnotationLevel::Error => "error",
+        AnnotationLevel::Warning => "warning",
+        AnnotationLevel::Notice => "note",
+    }
+}
+
+/// Project-wide findings have no location; SARIF lines start at 1
+fn location(finding: &Annotation) -> Option<Value> {
+    let file = finding.file.as_deref()?;
+    let mut physical = json!({ "artifactLocation": { "uri": workspace_path(file) } });
+    if let Some(line) = finding.line.filter(|line| *line > 0) {
+        let mut region = json!({ "startLine": line });
+        if let Some(end_line) = finding.end_line.filter(|end_line| *end_line >= line) {
+            region["endLine"] = json!(end_line);
+        }
+        physical["region"] = region;
+    }
+    Some(json!({ "physicalLocation": physical }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_groups_results_by_rule() {
+        let findings = vec![
+            Annotation::new(AnnotationLevel::Error, "src/App.tsx", 4, "Image without alt text", "<img> has no alt").with_rule("img-alt"),
+            Annotation::new(AnnotationLevel::Notice, "src/Nav.tsx", 9, "Image without alt text", "<img> has no alt").with_rule("img-alt"),
+            Annotation::project(AnnotationLevel::Warning, "Missing variable", "API_URL is not set"),
+        ];
+        let log: Value = serde_json::from_str(&render("a11y", &findings)).unwrap();
+        let run = &log["runs"][0];
+        assert_eq!(log["version"], "2.1.0");
+        assert_eq!(run["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(run["tool"]["driver"]["rules"][1]["id"], "a11y");
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[1]["ruleIndex"], 0);
+        assert_eq!(results[1]["level"], "note");
+        assert_eq!(results[0]["locations"][0]["physicalLocation"]["region"]["startLine"], 4);
+        assert!(results[2].get("locations").is_none());
+    }
+}
@@ -0,0 +1,148 @@
+/// Masking of comments and string contents in JS/TS sources. Byte offsets and line
+/// breaks are preserved, so positions found in the masked text map back to the source.
+use crate::common::jsx::expression_end;
+
+/// Index of the quote closing the string that opens at `start`; `${...}` in templates may nest strings
+pub fn string_end(bytes: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            byte if byte == quote => return i,
+            // A quote is never left open across lines, except in templates
+            b'\n' if quote != b'`' => return i,
+            b'{' if quote == b'`' && bytes[i - 1] == b'$' => {
+                if let Some(end) = expression_end(bytes, i) {
+                    i = end;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The source with comments blanked out; strings, offsets and line breaks are kept
+pub fn strip_comments(content: &str) -> String {
+    let bytes = content.as_bytes();
+    let mut code = bytes.to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'"' | b'\'' | b'`') => i = string_end(bytes, i, quote),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = content[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                code[i..end].iter_mut().for_each(|byte| *byte = b' ');
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = content[i + 2..].find("*/").map_or(bytes.len(), |end| i + 2 + end + 2);
+                code[i..end].iter_mut().filter(|byte| **byte != b'\n').for_each(|byte| *byte = b' ');
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    // Only whole multi-byte characters inside comments were replaced, so this cannot fail
+    String::from_utf8(code).unwrap_or_else(|_| content.to_string())
+}
+
+/// The code with string contents blanked, so `'eval(x)'` is not a call; quotes are kept
+pub fn blank_strings(code: &str) -> String {
+    let bytes = code.as_bytes();
+    let mut blanked = bytes.to_vec();
+    let mut i = 0;
+    while i < bytes.len() {
+        if let quote @ (b'"' | b'\'' | b'`') = bytes[i] {
+            let end = string_end(bytes, i, quote);
+            blanked[i + 1..end].iter_mut().filter(|byte| **byte != b'\n').for_each(|byte| *byte = b' ');
+            i = end;
+        }
+        i += 1;
+    }
+    String::from_utf8(blanked).unwrap_or_else(|_| code.to_string())
+}
+
+/// The lines with code: blank lines, comments and import statements are not counted
+pub fn code_line_count(content: &str) -> usize {
+    let code = strip_comments(content);
+    let mut in_import = false;
+    code.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| {
+            if !in_import {
+                let rest = line.strip_prefix("import").unwrap_or("(");
+                // `import(...)` and `import.meta` are expressions, not import statements
+                in_import = rest.starts_with([' ', '{', '*', '\'', '"']);
+            }
+            if in_import {
+                // An import ends with its module specifier, the only string it contains
+                in_import = !line.contains(['\'', '"']);
+                return false;
+            }
+            true
+        })
+        .count()
+}
+
+/// Parse JSON with comments and trailing commas, as tsconfig.json and jsconfig.json are written
+pub fn parse_jsonc(content: &str) -> anyhow::Result<serde_json::Value> {
+    let code = strip_comments(content);
+    let bytes = code.as_bytes();
+    let mut json = String::with_capacity(code.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                let end = string_end(bytes, i, b'"').min(bytes.len() - 1);
+                json.push_str(&code[i..=end]);
+                i = end;
+            }
+            b',' => {
+                let next = code[i + 1..].trim_start().chars().next();
+                if !matches!(next, Some('}') | Some(']')) {
+                    json.push(',');
+                }
+            }
+            _ => json.push(bytes[i] as char),
+        }
+        i += 1;
+    }
+    Ok(serde_json::from_str(&json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_comments_keeps_offsets() {
+        let source = "const url = 'http://x'; // eval(a)\n/* é\n*/ eval(b)";
+        let code = strip_comments(source);
+        assert_eq!(code.len(), source.len());
+        assert!(code.contains("'http://x'"));
+        assert!(!code.contains("eval(a)"));
+        assert_eq!(code.find("eval(b)"), source.find("eval(b)"));
+    }
+
+    #[test]
+    fn test_code_line_count_skips_comments_blanks_and_imports() {
+        let source = "import React from 'react';\nimport {\n  a,\n  b,\n} from \"./ab\";\n\n/**\n * Docs\n */\nconst url = import.meta.url; // trailing\nexport const lazy = () => import('./lazy');\n";
+        assert_eq!(code_line_count(source), 2);
+    }
+
+    #[test]
+    fn test_blank_strings_keeps_quotes() {
+        assert_eq!(blank_strings("f('a(b)', \"c\")"), "f('    ', \" \")");
+    }
+
+    #[test]
+    fn test_jsonc_comments_and_trailing_commas() {
+        let value = parse_jsonc("{\n  /* paths */\n  \"include\": [\"src/**/*\", \"https://x\",], // trailing\n}\n").unwrap();
+        assert_eq!(value["include"][1], "https://x");
+    }
+}
@@ -0,0 +1,174 @@
+// `SNIFF_*` environment variables that override config keys, above the config files
+
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+use super::validation::OPTIONAL_KEYS;
+
+/// Prefix of every config override variable
+const PREFIX: &str = "SNIFF_";
+
+/// A config key set by an environment variable
+#[derive(Debug, Clone)]
+pub struct EnvOverride {
+    /// The variable, e.g. `SNIFF_LARGE_FILES_THRESHOLD`
+    pub var: String,
+    /// Dotted key path, e.g. `large_files.threshold`
+    pub key: String,
+    pub value: toml::Value,
+}
+
+/// The overrides in `vars` for the keys of `config`, the merged config table.
+/// A variable names a key by its path in upper case with `_` between parts
+/// (`SNIFF_LARGE_FILES_SEVERITY_LEVELS_WARNING`); a nested key may leave out its
+/// tables when that names only one key (`SNIFF_LARGE_FILES_WARNING`). `[perf]` works
+/// as `SNIFF_PERF_*`. Other `SNIFF_` variables are not config keys and are ignored
+pub fn env_overrides(config: &toml::Table, vars: impl IntoIterator<Item = (String, String)>) -> Result<Vec<EnvOverride>> {
+    let names = variable_names(config);
+    let mut overrides = Vec::new();
+    for (var, raw) in vars {
+        let Some(name) = var.strip_prefix(PREFIX) else { continue };
+        let Some(key) = names.get(name) else { continue };
+        let value = parse_value(lookup(config, key), &raw)
+            .map_err(|expected| anyhow::anyhow!("{} must be {} for `{}`, got '{}'", var, expected, key, raw))?;
+        overrides.push(EnvOverride { var, key: key.clone(), value });
+    }
+    overrides.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(overrides)
+}
+
+/// The process environment, skipping variables that are not valid UTF-8
+pub fn process_vars() -> impl Iterator<Item = (String, String)> {
+    std::env::vars_os().filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+}
+
+/// Set the dotted `key` in `table`, creating the tables on its path
+pub fn set_key(table: &mut toml::Table, key: &str, value: toml::Value) {
+    match key.split_once('.') {
+        Some((first, rest)) => {
+            let nested = table.entry(first).or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            if !nested.is_table() {
+                *nested = toml::Value::Table(toml::Table::new());
+            }
+            if let toml::Value::Table(nested) = nested {
+                set_key(nested, rest, value);
+            }
+        }
+        None => {
+            table.insert(key.to_string(), value);
+        }
+    }
+}
+
+/// Variable name (without the prefix) of every key that can be overridden
+fn variable_names(config: &toml::Table) -> BTreeMap<String, String> {
+    let mut keys = Vec::new();
+    leaf_keys("", config, &mut keys);
+    keys.extend(OPTIONAL_KEYS.iter().map(|key| key.to_string()));
+    keys.sort();
+    keys.dedup();
+    // Only keys that spell as a variable name; user-chosen keys like route globs do not
+    keys.retain(|key| key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.'));
+
+    let mut names = BTreeMap::new();
+    let mut short: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for key in &keys {
+        let parts: Vec<&str> = key.split('.').collect();
+        let sections: &[&str] = if parts[0] == "performance" { &["performance", "perf"] } else { &[parts[0]] };
+        for section in sections {
+            let full = std::iter::once(*section).chain(parts[1..].iter().copied()).collect::<Vec<_>>().join("_");
+            names.insert(full.to_uppercase(), key.clone());
+            if parts.len() > 2 {
+                short.entry(format!("{}_{}", section, parts[parts.len() - 1]).to_uppercase()).or_default().push(key);
+            }
+        }
+    }
+    for (name, keys) in short {
+        if let [key] = keys.as_slice() {
+            names.entry(name).or_insert_with(|| (*key).clone());
+        }
+    }
+    names
+}
+
+fn leaf_keys(prefix: &str, table: &toml::Table, keys: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::Table(nested) => leaf_keys(&path, nested, keys),
+            // `[[rules]]` entries are not set one key at a time
+            toml::Value::Array(items) if items.iter().any(toml::Value::is_table) => {}
+            _ => keys.push(path),
+        }
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    let (first, rest) = key.split_once('.').unwrap_or((key, ""));
+    let value = table.get(first)?;
+    if rest.is_empty() { Some(value) } else { lookup(value.as_table()?, rest) }
+}
+
+/// Parse `raw` as the type of the key's current value; keys without one take a TOML value or a string
+fn parse_value(current: Option<&toml::Value>, raw: &str) -> std::result::Result<toml::Value, &'static str> {
+    let raw = raw.trim();
+    match current {
+        Some(toml::Value::String(_)) => Ok(toml::Value::String(raw.to_string())),
+        Some(toml::Value::Integer(_)) => raw.parse().map(toml::Value::Integer).map_err(|_| "an integer"),
+        Some(toml::Value::Float(_)) => raw.parse().map(toml::Value::Float).map_err(|_| "a number"),
+        Some(toml::Value::Boolean(_)) => match raw.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(toml::Value::Boolean(true)),
+            "false" | "0" | "no" | "off" => Ok(toml::Value::Boolean(false)),
+            _ => Err("true or false"),
+        },
+        // `a,b,c`, or a TOML array for lists of numbers
+        Some(toml::Value::Array(_)) if !raw.starts_with('[') => Ok(toml::Value::Array(
+            raw.split(',').map(str::trim).filter(|item| !item.is_empty()).map(toml::Value::from).collect(),
+        )),
+        Some(toml::Value::Array(_)) => literal(raw).filter(toml::Value::is_array).ok_or("a list"),
+        _ => Ok(literal(raw).unwrap_or_else(|| toml::Value::String(raw.to_string()))),
+    }
+}
+
+fn literal(raw: &str) -> Option<toml::Value> {
+    toml::from_str::<toml::Table>(&format!("value = {}", raw)).ok()?.remove("value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_variables_name_full_and_short_key_paths() {
+        let config = toml::Table::try_from(Config::default()).unwrap();
+        let overrides = env_overrides(&config, vars(&[
+            ("SNIFF_LARGE_FILES_THRESHOLD", "250"),
+            ("SNIFF_LARGE_FILES_WARNING", "150"),
+            ("SNIFF_TYPESCRIPT_STRICT_ANY_CHECK", "false"),
+            ("SNIFF_PERF_URL", "http://localhost:4000"),
+            ("SNIFF_MEMORY_EXCLUDED_DIRS", "node_modules, dist"),
+            ("SNIFF_PERF_DEBUG", "1"),
+            ("PATH", "/usr/bin"),
+        ])).unwrap();
+        let set: Vec<(&str, String)> = overrides.iter().map(|o| (o.key.as_str(), o.value.to_string())).collect();
+        assert_eq!(set, vec![
+            ("large_files.severity_levels.warning", "150".to_string()),
+            ("large_files.threshold", "250".to_string()),
+            ("memory.excluded_dirs", "[\"node_modules\", \"dist\"]".to_string()),
+            ("performance.url", "\"http://localhost:4000\"".to_string()),
+            ("typescript.strict_any_check", "false".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_values_must_match_the_key_type() {
+        let config = toml::Table::try_from(Config::default()).unwrap();
+        let error = env_overrides(&config, vars(&[("SNIFF_LARGE_FILES_THRESHOLD", "big")])).unwrap_err();
+        assert_eq!(error.to_string(), "SNIFF_LARGE_FILES_THRESHOLD must be an integer for `large_files.threshold`, got 'big'");
+    }
+}
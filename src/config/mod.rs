@@ -0,0 +1,1645 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+mod env;
+mod validation;
+
+use crate::common::{output, Annotation, AnnotationLevel, Severity, create_standard_json_output};
+
+use env::{EnvOverride, env_overrides, process_vars, set_key};
+pub use validation::{ConfigIssue, IssueLevel};
+use validation::{file_sets, flatten, validate_content, value_issues};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Config {
+    pub large_files: LargeFilesConfig,
+    pub typescript: TypeScriptConfig,
+    pub imports: ImportsConfig,
+    pub bundle: BundleConfig,
+    #[serde(alias = "perf")]
+    pub performance: PerformanceConfig,
+    pub memory: MemoryConfig,
+    pub environment: EnvironmentConfig,
+    #[serde(default)]
+    pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub hooks: HooksConfig,
+    #[serde(default)]
+    pub report: ReportConfig,
+    #[serde(default)]
+    pub history: HistoryConfig,
+    #[serde(default)]
+    pub a11y: A11yConfig,
+    #[serde(default)]
+    pub console: ConsoleConfig,
+    #[serde(default)]
+    pub tailwind: TailwindConfig,
+    #[serde(default)]
+    pub images: ImagesConfig,
+    #[serde(default)]
+    pub i18n: I18nConfig,
+    #[serde(default)]
+    pub tests: TestsConfig,
+    #[serde(default)]
+    pub complexity: ComplexityConfig,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub outdated: OutdatedConfig,
+    #[serde(default)]
+    pub vulns: VulnsConfig,
+    #[serde(default)]
+    pub docker: DockerConfig,
+    #[serde(default)]
+    pub ci: CiConfig,
+    #[serde(default)]
+    pub boundaries: BoundariesConfig,
+    #[serde(default)]
+    pub deploy: DeployConfig,
+    #[serde(default)]
+    pub notify: NotifyConfig,
+    /// Project-specific checks run by `sniff rules`, one `[[rules]]` table each
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<CustomRule>,
+    /// WebAssembly rule modules run by `sniff rules`, one `[[plugins]]` table each
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginConfig>,
+    /// Findings left out of every report, by rule and path, one `[[ignore]]` table each
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore: Vec<IgnoreEntry>,
+    /// `include`/`exclude` globs from each command's section (`[memory]`, `[large]`, ...),
+    /// keyed by command name; read by `load_from_file`, as they can sit in any section
+    #[serde(skip)]
+    pub files: BTreeMap<String, FileFilter>,
+}
+
+/// Globs narrowing the files a command scans, relative to the project root; patterns without
+/// `/` match the file name. Applied by `FileScanner::for_command`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct FileFilter {
+    /// Only files matching one of these are scanned; empty scans every file
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+/// Commands whose files `include`/`exclude` narrow, through `FileScanner::for_command`
+pub const FILTERED_COMMANDS: &[&str] = &[
+    "large", "types", "imports", "memory", "complexity", "duplicates", "secrets", "security",
+    "console", "tailwind", "images", "i18n", "a11y",
+];
+
+/// Short section names accepted in config files, folded into the full section when loading
+pub(crate) const SECTION_ALIASES: &[(&str, &str)] = &[
+    ("perf", "performance"),
+    ("types", "typescript"),
+];
+
+/// Config sections named differently from the command that reads them
+const COMMAND_SECTIONS: &[(&str, &str)] = &[
+    ("large_files", "large"),
+    ("typescript", "types"),
+    ("performance", "perf"),
+    ("environment", "env"),
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LargeFilesConfig {
+    pub threshold: usize,
+    pub excluded_dirs: Vec<String>,
+    pub excluded_files: Vec<String>,
+    pub severity_levels: SeverityLevels,
+    /// Functions and components longer than this are reported individually
+    #[serde(default = "default_function_threshold")]
+    pub function_threshold: usize,
+    /// Line thresholds for specific file types, overriding `threshold`
+    #[serde(default = "default_file_type_thresholds")]
+    pub file_type_thresholds: HashMap<String, usize>,
+    /// Also scan generated files (`*.d.ts`, `*.generated.ts`, GraphQL codegen output,
+    /// `@generated` headers), which are skipped by default
+    #[serde(default)]
+    pub include_generated: bool,
+    /// Which lines of a file count against the thresholds, in `large` and `components`
+    #[serde(default)]
+    pub count_mode: CountMode,
+}
+
+/// How file lengths are counted
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CountMode {
+    /// Every line of the file
+    #[default]
+    Raw,
+    /// Lines with code: blank lines, comments and import statements left out, like ESLint's
+    /// `max-lines` with `skipBlankLines` and `skipComments`
+    Code,
+}
+
+impl CountMode {
+    /// The length of `content` in this mode
+    pub fn lines(self, content: &str) -> usize {
+        match self {
+            CountMode::Raw => content.lines().count(),
+            CountMode::Code => crate::common::source::code_line_count(content),
+        }
+    }
+}
+
+/// File type keys accepted in `[large_files.file_type_thresholds]`
+pub const FILE_TYPE_KEYS: &[&str] = &[
+    "api_route", "server_component", "client_component", "custom_hook", "type_definition",
+    "middleware", "layout", "page", "component", "service", "util", "config", "test", "other",
+];
+
+fn default_function_threshold() -> usize {
+    50
+}
+
+fn default_file_type_thresholds() -> HashMap<String, usize> {
+    // Tests and generated/declared types legitimately run long
+    HashMap::from([
+        ("test".to_string(), 400),
+        ("type_definition".to_string(), 1000),
+    ])
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SeverityLevels {
+    pub warning: usize,
+    pub error: usize,
+    pub critical: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TypeScriptConfig {
+    pub strict_any_check: bool,
+    pub allow_ts_ignore: bool,
+    pub require_return_types: bool,
+    pub min_type_coverage: f64,
+    /// Minimum coverage in percent for the directories whose files match each glob, e.g.
+    /// `"src/payments/**" = 99`; with any set, only directories below theirs fail `types`
+    #[serde(default)]
+    pub thresholds: BTreeMap<String, f64>,
+    #[serde(default)]
+    pub budgets: TypeBudgets,
+}
+
+/// Most type-safety escapes `sniff types` accepts in the scanned files; exceeding one fails the check.
+/// With neither `ts_ignore` nor `ts_expect_error` set, more than 5 of the two together fail it
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct TypeBudgets {
+    /// `@ts-ignore` comments
+    pub ts_ignore: Option<usize>,
+    /// `@ts-nocheck` comments
+    pub ts_nocheck: Option<usize>,
+    /// `@ts-expect-error` comments without a description
+    pub ts_expect_error: Option<usize>,
+    /// `!` non-null assertions
+    pub non_null_assertions: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportsConfig {
+    pub auto_fix: bool,
+    pub excluded_patterns: Vec<String>,
+    pub check_dev_dependencies: bool,
+    /// Files loaded by something other than imports (scripts, package entry points); their
+    /// exports are never reported as unused. Tests, configs and Next.js routes already are
+    #[serde(default)]
+    pub entry_points: Vec<String>,
+    #[serde(default)]
+    pub order: ImportOrderConfig,
+    /// Extensions of non-code imports (styles, images, fonts, ...), which must exist exactly
+    /// as written instead of having `.ts`/`.js`/`index` tried
+    #[serde(default = "default_asset_extensions")]
+    pub asset_extensions: Vec<String>,
+}
+
+fn default_asset_extensions() -> Vec<String> {
+    [
+        "css", "scss", "sass", "less", "styl", "pcss", "svg", "png", "jpg", "jpeg", "gif", "webp", "avif",
+        "ico", "bmp", "woff", "woff2", "ttf", "otf", "eot", "mp4", "webm", "mp3", "wav", "ogg", "txt",
+        "md", "mdx", "html", "wasm", "glsl", "graphql", "gql", "yaml", "yml", "json",
+    ].iter().map(|ext| ext.to_string()).collect()
+}
+
+/// Grouping and ordering of the import block at the top of each file, checked by `sniff imports`
+/// when enabled and applied by `sniff fix --organize-imports`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ImportOrderConfig {
+    pub enabled: bool,
+    /// Group order: `builtin`, `external`, `alias`, `relative` and `type` (`import type`
+    /// statements); imports of an unlisted kind go last
+    pub groups: Vec<String>,
+    /// Sort by module path within each group
+    pub alphabetize: bool,
+    /// One blank line between groups and none inside them
+    pub newlines_between: bool,
+}
+
+/// Kinds of import `[imports.order] groups` can list
+pub const IMPORT_GROUP_KEYS: &[&str] = &["builtin", "external", "alias", "relative", "type"];
+
+impl Default for ImportOrderConfig {
+    fn default() -> Self {
+        ImportOrderConfig {
+            enabled: false,
+            groups: ["builtin", "external", "alias", "relative"].iter().map(|group| group.to_string()).collect(),
+            alphabetize: true,
+            newlines_between: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BundleConfig {
+    pub max_bundle_size_mb: f64,
+    pub max_chunk_size_mb: f64,
+    pub build_dirs: Vec<String>,
+    pub warn_on_large_chunks: bool,
+    #[serde(default)]
+    pub budgets: BundleBudgets,
+    #[serde(default)]
+    pub regression: BundleRegression,
+}
+
+/// What counts as a size regression in `sniff bundle --compare`; a chunk that grows
+/// by more than either threshold is reported
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BundleRegression {
+    pub max_increase_kb: Option<u64>,
+    pub max_increase_percent: Option<f64>,
+}
+
+impl Default for BundleRegression {
+    fn default() -> Self {
+        BundleRegression {
+            max_increase_kb: Some(10),
+            max_increase_percent: None,
+        }
+    }
+}
+
+/// Size budgets for `sniff bundle`, in KB (1 KB = 1024 bytes)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct BundleBudgets {
+    /// Whole build output; defaults to `max_bundle_size_mb`
+    pub total_kb: Option<u64>,
+    /// Any single chunk; defaults to `max_chunk_size_mb`
+    pub chunk_kb: Option<u64>,
+    /// Combined size of all chunks of one type, keyed by `CHUNK_TYPE_KEYS`
+    pub chunk_types: BTreeMap<String, u64>,
+    /// Combined size of the chunks matching a glob relative to the build output (e.g. `app/dashboard/**`)
+    pub routes: BTreeMap<String, u64>,
+}
+
+/// Chunk type keys accepted in `[bundle.budgets.chunk_types]`
+pub const CHUNK_TYPE_KEYS: &[&str] = &["main", "page", "component", "vendor", "runtime", "static", "shared"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PerformanceConfig {
+    pub lighthouse_enabled: bool,
+    /// Default budget for the performance score
+    pub min_performance_score: f64,
+    /// Default budget for the accessibility score
+    pub min_accessibility_score: f64,
+    /// Tried in order when no `url` is set and no running dev server is detected
+    pub server_urls: Vec<String>,
+    /// Page to audit; overrides server detection
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Lighthouse runs per audit; scores and metrics are the median across runs
+    #[serde(default = "default_perf_runs")]
+    pub runs: usize,
+    /// Lighthouse categories to audit
+    #[serde(default = "default_perf_categories")]
+    pub categories: Vec<String>,
+    #[serde(default)]
+    pub preset: LighthousePreset,
+    #[serde(default)]
+    pub budgets: PerfBudgets,
+}
+
+/// Budgets for `sniff perf`; any that is exceeded fails the audit
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PerfBudgets {
+    /// Minimum category scores (0-100), keyed by `LIGHTHOUSE_CATEGORIES`; performance and
+    /// accessibility default to `min_performance_score` and `min_accessibility_score`
+    pub scores: BTreeMap<String, f64>,
+    pub lcp_ms: Option<f64>,
+    pub tbt_ms: Option<f64>,
+    pub cls: Option<f64>,
+    pub fcp_ms: Option<f64>,
+    pub speed_index_ms: Option<f64>,
+    /// JavaScript transferred on first load of the audited page, in KB (1 KB = 1024 bytes)
+    pub first_load_kb: Option<u64>,
+}
+
+/// Lighthouse device emulation
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LighthousePreset {
+    #[default]
+    Mobile,
+    Desktop,
+}
+
+/// Category ids accepted by Lighthouse's `--only-categories`
+pub const LIGHTHOUSE_CATEGORIES: &[&str] = &["performance", "accessibility", "best-practices", "seo", "pwa"];
+
+fn default_perf_runs() -> usize {
+    1
+}
+
+fn default_perf_categories() -> Vec<String> {
+    ["performance", "accessibility", "best-practices", "seo"].iter().map(|c| c.to_string()).collect()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MemoryConfig {
+    pub check_patterns: bool,
+    pub check_processes: bool,
+    pub max_process_memory_mb: f64,
+    pub pattern_severity_threshold: String,
+    #[serde(default)]
+    pub disabled_patterns: Vec<String>,
+    #[serde(default)]
+    pub excluded_dirs: Vec<String>,
+    #[serde(default)]
+    pub excluded_files: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnvironmentConfig {
+    pub required_vars: Vec<String>,
+    pub check_security: bool,
+    pub allow_empty_values: bool,
+    pub env_files: Vec<String>,
+    /// Declared variables; when present, `sniff env` validates against this instead of built-in guesses
+    #[serde(default)]
+    pub schema: BTreeMap<String, EnvVarSchema>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnvVarSchema {
+    #[serde(rename = "type", default)]
+    pub var_type: EnvVarType,
+    #[serde(default = "default_true")]
+    pub required: bool,
+    /// Environments the variable is required in (e.g. ["production"]); empty means all
+    #[serde(default)]
+    pub environments: Vec<String>,
+    /// Allowed values for `type = "enum"`
+    #[serde(default)]
+    pub values: Vec<String>,
+    /// Optional regex the value must match
+    #[serde(default)]
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvVarType {
+    #[default]
+    String,
+    Url,
+    Port,
+    Boolean,
+    Number,
+    Enum,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SecretsConfig {
+    /// Regexes for known-safe values (matched against the value and its line)
+    pub allowlist: Vec<String>,
+    /// File name patterns skipped by `sniff secrets`
+    pub excluded_files: Vec<String>,
+    /// Minimum Shannon entropy (bits per character) for generic `key = "value"` matches
+    pub min_entropy: f64,
+}
+
+impl Default for SecretsConfig {
+    fn default() -> Self {
+        SecretsConfig {
+            allowlist: Vec::new(),
+            excluded_files: vec![
+                "package-lock.json".to_string(),
+                "yarn.lock".to_string(),
+                "pnpm-lock.yaml".to_string(),
+                "*.min.js".to_string(),
+                "*.map".to_string(),
+            ],
+            min_entropy: 3.5,
+        }
+    }
+}
+
+/// Commands run by the git hooks `sniff hooks install` writes
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// sniff arguments run before each commit, e.g. "imports --staged --quiet"
+    pub pre_commit: Vec<String>,
+    /// sniff arguments run before each push
+    pub pre_push: Vec<String>,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        HooksConfig {
+            pre_commit: vec!["imports --staged --quiet".to_string()],
+            pre_push: vec!["deploy".to_string()],
+        }
+    }
+}
+
+/// Analyses `sniff report` can merge into the health report
+pub const REPORT_ANALYSES: &[&str] = &["large", "imports", "types", "memory", "context", "env", "bundle"];
+
+/// What `sniff report` runs
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ReportConfig {
+    /// Analyses to run, each scored as one category
+    pub analyses: Vec<String>,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        ReportConfig {
+            analyses: ["large", "imports", "types", "memory", "context"].iter().map(|name| name.to_string()).collect(),
+        }
+    }
+}
+
+/// Where summary metrics of each run are kept for `sniff history`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HistoryConfig {
+    /// Off by default, so runs leave no new files in the analyzed repository
+    pub enabled: bool,
+    /// JSON Lines file, relative to the project root
+    pub path: String,
+}
+
+impl Default for HistoryConfig {
+    fn default() -> Self {
+        HistoryConfig {
+            enabled: false,
+            path: ".sniff/history.jsonl".to_string(),
+        }
+    }
+}
+
+/// Rules checked by `sniff a11y`
+pub const A11Y_RULES: &[&str] = &["img-alt", "click-events", "form-label", "positive-tabindex", "html-lang", "prefer-button"];
+
+/// Per-rule settings for `sniff a11y`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct A11yConfig {
+    /// Rule id to level, e.g. `positive-tabindex = "off"`; unlisted rules keep their default severity
+    pub rules: BTreeMap<String, A11yRuleLevel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum A11yRuleLevel {
+    Off,
+    Low,
+    Medium,
+    High,
+}
+
+/// What `sniff console` reports
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ConsoleConfig {
+    /// `console` methods reported as stray debugging output
+    pub methods: Vec<String>,
+    /// Comment markers reported as unfinished work
+    pub markers: Vec<String>,
+    /// Globs of files allowed to use them, relative to the project root (e.g. a logger wrapper)
+    pub allowlist: Vec<String>,
+}
+
+impl Default for ConsoleConfig {
+    fn default() -> Self {
+        ConsoleConfig {
+            methods: ["log", "debug", "dir"].iter().map(|method| method.to_string()).collect(),
+            markers: vec!["TODO".to_string(), "FIXME".to_string()],
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+/// Limits for `sniff tailwind`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TailwindConfig {
+    /// Share of all classes, in percent, that may be arbitrary values such as `w-[137px]`
+    pub max_arbitrary_percent: f64,
+    /// An arbitrary value used this many times should become a theme value
+    pub repeated_arbitrary_min: usize,
+    /// Class strings with at least this many classes are checked for duplicates
+    pub duplicate_min_classes: usize,
+    /// A class string repeated this many times should be extracted
+    pub duplicate_min_occurrences: usize,
+}
+
+impl Default for TailwindConfig {
+    fn default() -> Self {
+        TailwindConfig {
+            max_arbitrary_percent: 5.0,
+            repeated_arbitrary_min: 3,
+            duplicate_min_classes: 6,
+            duplicate_min_occurrences: 3,
+        }
+    }
+}
+
+/// Limits for `sniff images`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ImagesConfig {
+    /// Images over this size in KB fail the check
+    pub max_kb: u64,
+    /// Images wider or taller than this in pixels fail the check
+    pub max_dimension: u32,
+    /// PNG/JPEG/GIF files smaller than this (KB) are not worth converting to WebP/AVIF
+    pub modern_format_min_kb: u64,
+    /// In Next.js projects, `<img>` for images over this size (KB) should be `next/image`
+    pub next_image_min_kb: u64,
+}
+
+impl Default for ImagesConfig {
+    fn default() -> Self {
+        ImagesConfig {
+            max_kb: 500,
+            max_dimension: 2560,
+            modern_format_min_kb: 10,
+            next_image_min_kb: 50,
+        }
+    }
+}
+
+/// What `sniff i18n` checks
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Directories holding locale JSON files, relative to the project root
+    pub locale_dirs: Vec<String>,
+    /// JSX attributes whose text is shown to users
+    pub attributes: Vec<String>,
+    /// Text that needs no translation, such as brand names
+    pub allowlist: Vec<String>,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        I18nConfig {
+            locale_dirs: ["messages", "locales", "public/locales", "src/messages", "src/locales", "src/i18n"]
+                .iter().map(|dir| dir.to_string()).collect(),
+            attributes: ["placeholder", "title", "aria-label", "alt"].iter().map(|name| name.to_string()).collect(),
+            allowlist: Vec::new(),
+        }
+    }
+}
+
+/// How `sniff tests` tells test files from source files
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TestsConfig {
+    /// Globs for test files; patterns with `/` match from the project root, others the file name
+    pub patterns: Vec<String>,
+    /// Source files that need no test of their own
+    pub exclude: Vec<String>,
+    /// Fail when fewer source files (percent) have a test; 0 never fails
+    pub min_tested_percent: f64,
+}
+
+impl Default for TestsConfig {
+    fn default() -> Self {
+        TestsConfig {
+            patterns: ["*.test.*", "*.spec.*", "**/__tests__/**", "test/**", "tests/**"]
+                .iter().map(|pattern| pattern.to_string()).collect(),
+            exclude: ["*.d.ts", "*.config.*", "*.stories.*", "coverage/**", "**/__mocks__/**", "e2e/**", "cypress/**"]
+                .iter().map(|pattern| pattern.to_string()).collect(),
+            min_tested_percent: 0.0,
+        }
+    }
+}
+
+/// Thresholds for `sniff complexity`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ComplexityConfig {
+    /// Functions with more independent paths (branches and boolean operators) are reported
+    pub cyclomatic: usize,
+    /// Functions harder to follow than this (branches weighted by nesting) are reported
+    pub cognitive: usize,
+    /// How many of the most complex functions to list
+    pub max_reported: usize,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        ComplexityConfig {
+            cyclomatic: 10,
+            cognitive: 15,
+            max_reported: 20,
+        }
+    }
+}
+
+/// Where `sniff lint` gets the linter's results
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct LintConfig {
+    /// ESLint (`--format json`) or Biome (`--reporter=json`) report, relative to the project root;
+    /// when not set, the project's configured linter is run
+    pub report: Option<String>,
+}
+
+/// Registry and limits for `sniff outdated`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct OutdatedConfig {
+    /// npm registry to query; a private registry or mirror works as long as it serves package documents
+    pub registry: String,
+    /// Packages whose latest release is older than this are reported as unmaintained
+    pub stale_after_days: u64,
+    /// Fail when an installed version is deprecated
+    pub fail_on_deprecated: bool,
+    /// Fail when a package is more than this many major versions behind its latest release
+    pub max_majors_behind: Option<u64>,
+    /// Check devDependencies as well as dependencies
+    pub dev_dependencies: bool,
+    /// Packages left out of the report, e.g. ones pinned on purpose
+    pub ignore_packages: Vec<String>,
+}
+
+impl Default for OutdatedConfig {
+    fn default() -> Self {
+        OutdatedConfig {
+            registry: "https://registry.npmjs.org".to_string(),
+            stale_after_days: 730,
+            fail_on_deprecated: true,
+            max_majors_behind: None,
+            dev_dependencies: true,
+            ignore_packages: Vec::new(),
+        }
+    }
+}
+
+/// Where `sniff vulns` gets its advisories
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum VulnSource {
+    /// Query the OSV database for every package in the lockfile
+    Osv,
+    /// Run `npm audit --json`, or `pnpm audit --json` in pnpm projects
+    Audit,
+}
+
+/// Advisory source and failure threshold for `sniff vulns`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct VulnsConfig {
+    pub source: VulnSource,
+    /// `npm audit --json` or `pnpm audit --json` output to read instead, relative to the project root
+    pub report: Option<String>,
+    /// Advisories at or above this severity fail the check, and block `sniff deploy` when its gate does
+    pub min_severity: Severity,
+    /// OSV API to query; a mirror works as long as it serves `/v1/querybatch` and `/v1/vulns/<id>`
+    pub osv_api: String,
+    /// Advisory ids accepted as known risks, e.g. `GHSA-p6mc-m468-83gw`
+    pub ignore_advisories: Vec<String>,
+}
+
+impl Default for VulnsConfig {
+    fn default() -> Self {
+        VulnsConfig {
+            source: VulnSource::Osv,
+            report: None,
+            min_severity: Severity::High,
+            osv_api: "https://api.osv.dev".to_string(),
+            ignore_advisories: Vec::new(),
+        }
+    }
+}
+
+/// What `sniff docker` checks
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DockerConfig {
+    /// Dockerfiles to check, relative to the project root; `Dockerfile`, `Dockerfile.*` and
+    /// `*.Dockerfile` in the root when empty
+    pub dockerfiles: Vec<String>,
+    /// Paths `.dockerignore` must exclude; `.next` entries only apply to Next.js projects
+    pub dockerignore: Vec<String>,
+    /// Report Dockerfiles with a single stage
+    pub require_multi_stage: bool,
+}
+
+impl Default for DockerConfig {
+    fn default() -> Self {
+        DockerConfig {
+            dockerfiles: Vec::new(),
+            dockerignore: vec!["node_modules".to_string(), ".next/cache".to_string(), ".env".to_string()],
+            require_multi_stage: true,
+        }
+    }
+}
+
+/// A step `sniff ci` expects some workflow to run
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum CiStep {
+    Build,
+    Typecheck,
+    Test,
+    Sniff,
+}
+
+impl CiStep {
+    /// The package.json script usually behind the step
+    pub fn script(self) -> &'static str {
+        match self {
+            CiStep::Build => "build",
+            CiStep::Typecheck => "typecheck",
+            CiStep::Test => "test",
+            CiStep::Sniff => "sniff",
+        }
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            CiStep::Build => "builds the project",
+            CiStep::Typecheck => "type-checks the project",
+            CiStep::Test => "runs the tests",
+            CiStep::Sniff => "runs sniff",
+        }
+    }
+}
+
+/// What `sniff ci` expects of the GitHub Actions workflows and `.gitlab-ci.yml`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct CiConfig {
+    /// Steps some workflow must run, directly or through a package.json script
+    pub required_steps: Vec<CiStep>,
+}
+
+impl Default for CiConfig {
+    fn default() -> Self {
+        CiConfig {
+            required_steps: vec![CiStep::Build, CiStep::Typecheck, CiStep::Test, CiStep::Sniff],
+        }
+    }
+}
+
+/// Limits for `sniff boundaries`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct BoundariesConfig {
+    /// `'use client'` boundaries estimated above this many KB of JavaScript are reported
+    pub max_boundary_kb: u64,
+    /// Packages that only run on the server, on top of the built-in ones (`@prisma/client`, `pg`, ...)
+    pub server_packages: Vec<String>,
+}
+
+impl Default for BoundariesConfig {
+    fn default() -> Self {
+        BoundariesConfig {
+            max_boundary_kb: 100,
+            server_packages: Vec::new(),
+        }
+    }
+}
+
+/// Checks run by `sniff deploy`, in order; the keys of `[deploy.gates]`
+pub const DEPLOY_CHECKS: &[&str] = &[
+    "env", "types", "large", "imports", "bundle", "console", "images", "memory", "security", "secrets", "lint",
+    "outdated", "vulns", "docker", "ci",
+];
+
+/// How `sniff deploy` treats each check's result
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DeployConfig {
+    /// Keyed by `DEPLOY_CHECKS`; checks without a gate keep `default_gate`
+    pub gates: BTreeMap<String, DeployGate>,
+}
+
+impl DeployConfig {
+    pub fn gate(&self, check: &str) -> DeployGate {
+        self.gates.get(check).copied().unwrap_or_else(|| default_gate(check))
+    }
+}
+
+impl Default for DeployConfig {
+    fn default() -> Self {
+        DeployConfig {
+            gates: DEPLOY_CHECKS.iter().map(|check| (check.to_string(), default_gate(check))).collect(),
+        }
+    }
+}
+
+/// The gate of a check that `[deploy.gates]` does not list
+pub fn default_gate(check: &str) -> DeployGate {
+    DeployGate::Level(match check {
+        "env" | "types" => GateLevel::Block,
+        // Optional stages: `outdated` and `vulns` query the npm registry or OSV, and the
+        // source scans are slow on large trees, so they only run when turned on
+        "memory" | "security" | "secrets" | "outdated" | "vulns" => GateLevel::Off,
+        _ => GateLevel::Warn,
+    })
+}
+
+/// A `[deploy.gates]` entry: `"block"`, `"warn"`, `"off"`, or a limit past which the check blocks
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(untagged)]
+pub enum DeployGate {
+    Level(GateLevel),
+    Limit(GateLimit),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GateLevel {
+    /// A failing check keeps the project from deploying
+    Block,
+    /// A failing check is reported as a warning
+    Warn,
+    /// The check is not run
+    Off,
+}
+
+/// Blocks past either limit and warns below them, e.g. `bundle = { block_over_mb = 5 }`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(deny_unknown_fields)]
+pub struct GateLimit {
+    /// Findings the check may report before it blocks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_over: Option<usize>,
+    /// Total bundle size in MB; `bundle` only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_over_mb: Option<f64>,
+}
+
+/// Chat services `[notify]` can post to
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyService {
+    Slack,
+    Discord,
+    Teams,
+}
+
+/// Where `sniff deploy` posts its summary card
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct NotifyConfig {
+    /// Incoming webhook URL; set `SNIFF_NOTIFY_WEBHOOK_URL` in CI to keep it out of the repository
+    pub webhook_url: Option<String>,
+    /// Guessed from the webhook URL when not set
+    pub service: Option<NotifyService>,
+    /// Slack channel to post to instead of the webhook's own, e.g. `#deploys`
+    pub channel: Option<String>,
+    /// Added to the message when a blocking check fails, e.g. `<!here>` or `<@U024BE7LH>`
+    pub mention_on_failure: Option<String>,
+    /// Post only when the `CI` environment variable is set
+    pub ci_only: bool,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        NotifyConfig {
+            webhook_url: None,
+            service: None,
+            channel: None,
+            mention_on_failure: None,
+            ci_only: true,
+        }
+    }
+}
+
+/// A `[[rules]]` check: lines matching `pattern`, importing `import` or calling `call`
+/// in the files it applies to are reported with `message`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CustomRule {
+    /// Identifier shown with each finding, e.g. `no-moment`
+    pub name: String,
+    /// Regex matched against each line, with comments removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Module whose imports are flagged (`moment` also matches `moment/locale/de`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub import: Option<String>,
+    /// Function or method whose calls are flagged, e.g. `api.v1.fetch`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call: Option<String>,
+    /// `critical`, `high`, `medium`, `low` or `info`; high and critical findings fail the check
+    #[serde(default = "default_rule_severity")]
+    pub severity: String,
+    #[serde(default)]
+    pub message: String,
+    /// How to fix a finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+    /// Globs of the files the rule applies to; empty applies it to every source file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    /// Globs of files the rule skips, e.g. the logger that wraps `console`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// A `[[plugins]]` entry: a WebAssembly module that is given each file it applies to and
+/// returns findings. See `plugins` for the interface it exports
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PluginConfig {
+    /// The `.wasm` module, relative to the project root, e.g. `node_modules/@acme/sniff-rules/rules.wasm`
+    pub path: String,
+    /// Globs of the files the plugin checks; empty checks every source file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+/// An `[[ignore]]` entry: findings of `rule` in files matching `paths` are dropped after analysis,
+/// before reports, exit codes and every output format see them
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IgnoreEntry {
+    /// A rule id (`SNF-IMP-UNUSED`), a `[[rules]]` name, a command (`console`) for all of its
+    /// rules, or `*` for every rule
+    pub rule: String,
+    /// Globs relative to the project root; patterns without `/` match the file name. Empty
+    /// ignores the rule everywhere
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+    /// Why the findings are ignored, for whoever reviews the config
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// Severities a custom rule may have
+pub const RULE_SEVERITIES: &[&str] = &["critical", "high", "medium", "low", "info"];
+
+fn default_rule_severity() -> String {
+    "medium".to_string()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            large_files: LargeFilesConfig {
+                threshold: 100,
+                excluded_dirs: vec![
+                    "node_modules".to_string(),
+                    ".next".to_string(),
+                    "dist".to_string(),
+                    ".git".to_string(),
+                    "target".to_string(),
+                    "build".to_string(),
+                ],
+                excluded_files: vec![
+                    "*.min.js".to_string(),
+                    "*.bundle.js".to_string(),
+                    "package-lock.json".to_string(),
+                    "yarn.lock".to_string(),
+                ],
+                severity_levels: SeverityLevels {
+                    warning: 100,
+                    error: 200,
+                    critical: 400,
+                },
+                function_threshold: default_function_threshold(),
+                file_type_thresholds: default_file_type_thresholds(),
+                include_generated: false,
+                count_mode: CountMode::Raw,
+            },
+            typescript: TypeScriptConfig {
+                strict_any_check: true,
+                allow_ts_ignore: false,
+                require_return_types: true,
+                min_type_coverage: 80.0,
+                thresholds: BTreeMap::new(),
+                budgets: TypeBudgets::default(),
+            },
+            imports: ImportsConfig {
+                auto_fix: false,
+                excluded_patterns: vec![
+                    "react".to_string(),
+                    "@types/*".to_string(),
+                ],
+                check_dev_dependencies: true,
+                entry_points: Vec::new(),
+                order: ImportOrderConfig::default(),
+                asset_extensions: default_asset_extensions(),
+            },
+            bundle: BundleConfig {
+                max_bundle_size_mb: 2.0,
+                max_chunk_size_mb: 0.5,
+                build_dirs: vec![
+                    ".next".to_string(),
+                    "dist".to_string(),
+                    "build".to_string(),
+                    "out".to_string(),
+                ],
+                warn_on_large_chunks: true,
+                budgets: BundleBudgets::default(),
+                regression: BundleRegression::default(),
+            },
+            performance: PerformanceConfig {
+                lighthouse_enabled: true,
+                min_performance_score: 75.0,
+                min_accessibility_score: 90.0,
+                server_urls: vec![
+                    "http://localhost:3000".to_string(),
+                    "http://localhost:3001".to_string(),
+                    "http://localhost:8000".to_string(),
+                    "http://localhost:8080".to_string(),
+                ],
+                url: None,
+                runs: default_perf_runs(),
+                categories: default_perf_categories(),
+                preset: LighthousePreset::Mobile,
+                budgets: PerfBudgets::default(),
+            },
+            memory: MemoryConfig {
+                check_patterns: true,
+                check_processes: true,
+                max_process_memory_mb: 1000.0,
+                pattern_severity_threshold: "high".to_string(),
+                disabled_patterns: vec![
+                    // "UncontrolledLoop".to_string(),
+                    // "ClosureLeak".to_string(),
+                ],
+                excluded_dirs: vec![
+                    "node_modules".to_string(),
+                    ".next".to_string(),
+                    "dist".to_string(),
+                    "build".to_string(),
+                    "coverage".to_string(),
+                    ".cache".to_string(),
+                ],
+                excluded_files: vec![
+                    "*.min.js".to_string(),
+                    "*.bundle.js".to_string(),
+                    "*.d.ts".to_string(),
+                    "*.generated.*".to_string(),
+                ],
+            },
+            environment: EnvironmentConfig {
+                required_vars: vec![
+                    "NODE_ENV".to_string(),
+                    "NEXT_PUBLIC_APP_URL".to_string(),
+                ],
+                check_security: true,
+                allow_empty_values: false,
+                env_files: vec![
+                    ".env".to_string(),
+                    ".env.local".to_string(),
+                    ".env.development".to_string(),
+                    ".env.production".to_string(),
+                ],
+                schema: BTreeMap::new(),
+            },
+            secrets: SecretsConfig::default(),
+            hooks: HooksConfig::default(),
+            report: ReportConfig::default(),
+            history: HistoryConfig::default(),
+            a11y: A11yConfig::default(),
+            console: ConsoleConfig::default(),
+            tailwind: TailwindConfig::default(),
+            images: ImagesConfig::default(),
+            i18n: I18nConfig::default(),
+            tests: TestsConfig::default(),
+            complexity: ComplexityConfig::default(),
+            lint: LintConfig::default(),
+            outdated: OutdatedConfig::default(),
+            vulns: VulnsConfig::default(),
+            docker: DockerConfig::default(),
+            ci: CiConfig::default(),
+            boundaries: BoundariesConfig::default(),
+            deploy: DeployConfig::default(),
+            notify: NotifyConfig::default(),
+            rules: Vec::new(),
+            plugins: Vec::new(),
+            ignore: Vec::new(),
+            files: BTreeMap::new(),
+        }
+    }
+}
+
+/// Config file names looked for in each directory, in order of preference
+const CONFIG_FILE_NAMES: &[&str] = &["sniff.toml", "sniff-check.toml", ".sniff.toml", ".sniffrc.toml"];
+
+/// The `--config` file, which replaces discovery for every command
+static CONFIG_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+impl Config {
+    /// Load configuration from file or create default
+    pub fn load() -> Result<Self> {
+        Self::load_from_dir(Path::new("."))
+    }
+    
+    /// Load the configuration that applies to `dir`: every config file from the
+    /// project root (the closest directory with `.git`) down to `dir`, merged over
+    /// the defaults with the innermost file winning
+    pub fn load_from_dir(dir: &Path) -> Result<Self> {
+        Self::load_layers(&Self::discover(dir))
+    }
+    
+    /// Use `path` instead of discovered config files for every load (the `--config` flag)
+    pub fn use_config_file(path: PathBuf) {
+        let _ = CONFIG_FILE.set(path);
+    }
+    
+    /// The config files that apply to `dir`, outermost first
+    pub fn discover(dir: &Path) -> Vec<PathBuf> {
+        if let Some(path) = CONFIG_FILE.get() {
+            return vec![path.clone()];
+        }
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut files = Vec::new();
+        for ancestor in dir.ancestors() {
+            files.extend(Self::file_in(ancestor));
+            if ancestor.join(".git").exists() {
+                break;
+            }
+        }
+        files.reverse();
+        files
+    }
+    
+    /// The configs of package directories below `dir` that hold their own config file,
+    /// innermost first, for the `files` under them. Empty with `--config`
+    pub fn packages_below(dir: &Path, files: &[PathBuf]) -> Result<Vec<(PathBuf, Config)>> {
+        if CONFIG_FILE.get().is_some() {
+            return Ok(Vec::new());
+        }
+        let mut dirs: Vec<&Path> = files.iter()
+            .flat_map(|file| file.ancestors().skip(1).take_while(|ancestor| *ancestor != dir && ancestor.starts_with(dir)))
+            .collect();
+        dirs.sort_by(|a, b| b.components().count().cmp(&a.components().count()).then_with(|| a.cmp(b)));
+        dirs.dedup();
+        dirs.into_iter()
+            .filter(|package| Self::file_in(package).is_some())
+            .map(|package| Ok((package.to_path_buf(), Self::load_from_dir(package)?)))
+            .collect()
+    }
+    
+    /// The config file directly in `dir`, if any
+    pub fn file_in(dir: &Path) -> Option<PathBuf> {
+        CONFIG_FILE_NAMES.iter()
+            .map(|name| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    }
+    
+    /// Merge `paths` over the defaults, later files overriding earlier ones, then apply
+    /// the `SNIFF_*` environment overrides. Command-line flags override the result.
+    /// A config that does not load is an error listing where each problem is
+    pub fn load_layers(paths: &[PathBuf]) -> Result<Self> {
+        Self::merge_layers(paths).map_err(|error| {
+            let located: Vec<String> = layer_issues(paths).unwrap_or_default()
+                .iter()
+                .filter(|issue| issue.level == IssueLevel::Error)
+                .map(|issue| match issue.key.as_str() {
+                    "" => format!("\n  {}{}", issue_location(issue), issue.message),
+                    key => format!("\n  {}{}: {}", issue_location(issue), key, issue.message),
+                })
+                .collect();
+            match located.is_empty() {
+                true => error.context("Invalid configuration"),
+                false => anyhow::anyhow!("Invalid configuration (see `sniff config doctor`):{}", located.concat()),
+            }
+        })
+    }
+
+    fn merge_layers(paths: &[PathBuf]) -> Result<Self> {
+        let mut merged = Self::file_table(paths)?;
+        for env_override in env_overrides(&merged, process_vars())? {
+            set_key(&mut merged, &env_override.key, env_override.value);
+        }
+        Self::from_table(merged)
+    }
+    
+    /// The defaults with `paths` merged over them
+    fn file_table(paths: &[PathBuf]) -> Result<toml::Table> {
+        let mut merged = toml::Table::try_from(Config::default())?;
+        for path in paths {
+            let content = fs::read_to_string(path)
+                .map_err(|error| anyhow::anyhow!("Cannot read {}: {}", path.display(), error))?;
+            let table: toml::Table = toml::from_str(&content)
+                .map_err(|error| anyhow::anyhow!("{}: {}", path.display(), error))?;
+            merge_tables(&mut merged, normalized(table));
+        }
+        Ok(merged)
+    }
+    
+    /// The `SNIFF_*` variables that override keys of the config for `paths`
+    fn env_overrides(paths: &[PathBuf]) -> Result<Vec<EnvOverride>> {
+        env_overrides(&Self::file_table(paths)?, process_vars())
+    }
+    
+    fn from_table(table: toml::Table) -> Result<Self> {
+        let files = command_file_filters(&table)?;
+        let mut config: Config = toml::Value::Table(table).try_into()?;
+        config.files = files;
+        Ok(config)
+    }
+    
+    /// Save configuration to file
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let content = toml::to_string_pretty(self)?;
+        fs::write(path, content)?;
+        Ok(())
+    }
+    
+    /// Create default configuration file
+    pub fn create_default_config() -> Result<()> {
+        let config = Config::default();
+        config.save_to_file("sniff.toml")?;
+        Ok(())
+    }
+    
+}
+
+/// Fold `SECTION_ALIASES` such as `[perf]` into their full section; layers must agree on the name to merge
+fn normalized(mut table: toml::Table) -> toml::Table {
+    for (alias, section) in SECTION_ALIASES {
+        let Some(value) = table.remove(*alias) else { continue };
+        match table.get_mut(*section) {
+            Some(toml::Value::Table(existing)) => {
+                if let toml::Value::Table(value) = value {
+                    merge_tables(existing, value);
+                }
+            }
+            _ => {
+                table.insert(section.to_string(), value);
+            }
+        }
+    }
+    table
+}
+
+/// Merge `overlay` into `base`: tables merge key by key, anything else is replaced
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(existing)), toml::Value::Table(nested)) => merge_tables(existing, nested),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// The `include`/`exclude` globs of every section that sets them, keyed by command name
+fn command_file_filters(table: &toml::Table) -> Result<BTreeMap<String, FileFilter>> {
+    let mut filters = BTreeMap::new();
+    for (section, value) in table {
+        let Some(values) = value.as_table() else { continue };
+        if !values.contains_key("include") && !values.contains_key("exclude") {
+            continue;
+        }
+        // `[tests] exclude` lists source files that need no test; it is not a scan filter
+        if section == "tests" {
+            continue;
+        }
+        let filter: FileFilter = value.clone().try_into()
+            .map_err(|error| anyhow::anyhow!("[{}] include/exclude must be lists of globs: {}", section, error))?;
+        let command = COMMAND_SECTIONS.iter()
+            .find(|(name, _)| *name == section)
+            .map_or(section.as_str(), |(_, command)| command);
+        filters.insert(command.to_string(), filter);
+    }
+    Ok(filters)
+}
+
+fn report_issues(paths: &[PathBuf], issues: &[ConfigIssue], json: bool) -> Result<()> {
+    if json {
+        print_json(serde_json::json!({ "config_files": paths, "issues": issues }), paths, issues)?;
+    } else {
+        print_issues(issues);
+    }
+    fail_on_errors(issues)
+}
+
+/// `data` in the standard envelope, with each issue as a finding
+fn print_json(data: serde_json::Value, paths: &[PathBuf], issues: &[ConfigIssue]) -> Result<()> {
+    let findings = issues.iter().map(|issue| {
+        let level = match issue.level {
+            IssueLevel::Error => AnnotationLevel::Error,
+            IssueLevel::Warning => AnnotationLevel::Warning,
+        };
+        Annotation {
+            level,
+            file: issue.file.as_ref().map(|file| file.display().to_string()),
+            line: issue.line,
+            end_line: None,
+            title: issue.key.clone(),
+            message: issue.message.clone(),
+            rule: None,
+        }
+    }).collect();
+    let response = create_standard_json_output("config", data, paths.len(), issues.len(), None).with_findings(findings);
+    println!("{}", response.to_json_pretty()?);
+    Ok(())
+}
+
+fn print_issues(issues: &[ConfigIssue]) {
+    if issues.is_empty() {
+        output::line("✅ Configuration is valid");
+        return;
+    }
+    for issue in issues {
+        let icon = match issue.level {
+            IssueLevel::Error => "❌",
+            IssueLevel::Warning => "⚠️ ",
+        };
+        output::line(format!("{} {}{}: {}", icon, issue_location(issue), issue.key, issue.message));
+        if let Some(suggestion) = &issue.suggestion {
+            output::line(format!("   💡 {}", suggestion));
+        }
+    }
+}
+
+/// Validate each config file on its own, tagging its issues with the file, then the
+/// environment overrides, reported under their variable names
+pub fn layer_issues(paths: &[PathBuf]) -> Result<Vec<ConfigIssue>> {
+    let mut issues = Vec::new();
+    for path in paths {
+        let (_, file_issues) = validate_content(&fs::read_to_string(path)?);
+        issues.extend(file_issues.into_iter().map(|issue| ConfigIssue { file: Some(path.clone()), ..issue }));
+    }
+    if paths.is_empty() {
+        issues.extend(value_issues(&Config::default()));
+    }
+    // A file that does not parse is reported above; its variables cannot be checked without it
+    let Ok(table) = Config::file_table(paths) else {
+        return Ok(issues);
+    };
+    match env_overrides(&table, process_vars()) {
+        Ok(overrides) if !overrides.is_empty() => {
+            let config = Config::merge_layers(paths)?;
+            for issue in value_issues(&config) {
+                if let Some(env_override) = overrides.iter().find(|env_override| env_override.key == issue.key) {
+                    let message = format!("{} (sets `{}`)", issue.message, issue.key);
+                    issues.push(ConfigIssue { key: env_override.var.clone(), message, ..issue });
+                }
+            }
+        }
+        Ok(_) => {}
+        Err(error) => issues.push(ConfigIssue::environment(error.to_string())),
+    }
+    Ok(issues)
+}
+
+/// "sniff.toml:3:13 ", or just the file when the position is unknown
+fn issue_location(issue: &ConfigIssue) -> String {
+    match (&issue.file, issue.line, issue.column) {
+        (Some(file), Some(line), Some(column)) => format!("{}:{}:{} ", file.display(), line, column),
+        (Some(file), _, _) => format!("{} ", file.display()),
+        _ => String::new(),
+    }
+}
+
+fn fail_on_errors(issues: &[ConfigIssue]) -> Result<()> {
+    let errors = issues.iter().filter(|issue| issue.level == IssueLevel::Error).count();
+    if errors > 0 {
+        return Err(anyhow::anyhow!("{} configuration error{}", errors, if errors == 1 { "" } else { "s" }));
+    }
+    Ok(())
+}
+
+/// Configuration utilities
+pub struct ConfigUtils;
+
+impl ConfigUtils {
+    /// Initialize configuration in current directory
+    pub fn init() -> Result<()> {
+        // A package may add its own file below a config in a parent directory
+        if Config::file_in(Path::new(".")).is_some() {
+            output::line("Configuration file already exists.");
+            return Ok(());
+        }
+        
+        Config::create_default_config()?;
+        output::line("Created default configuration file: sniff.toml");
+        output::line("Edit this file to customize sniff-check behavior for your project.");
+        
+        Ok(())
+    }
+    
+    /// Show current configuration
+    pub fn show() -> Result<()> {
+        let config = Config::load()?;
+        
+        let paths = Config::discover(Path::new("."));
+        if paths.is_empty() {
+            output::line("Using default configuration (no config file found)");
+        }
+        for path in &paths {
+            output::line(format!("Configuration loaded from: {}", path.display()));
+        }
+        for env_override in Config::env_overrides(&paths)? {
+            output::line(format!("Overridden by {}: {}", env_override.var, env_override.key));
+        }
+        
+        output::line("\nCurrent configuration:");
+        println!("{}", toml::to_string_pretty(&config)?);
+        
+        Ok(())
+    }
+    
+    /// Validate every config file that applies here: each problem is printed with its location, and any error fails
+    pub fn validate(json: bool) -> Result<()> {
+        let paths = Config::discover(Path::new("."));
+        report_issues(&paths, &layer_issues(&paths)?, json)
+    }
+    
+    /// Validate the config files, suggest fixes and show the effective config with where each value comes from
+    pub fn doctor(json: bool) -> Result<()> {
+        let paths = Config::discover(Path::new("."));
+        let issues = layer_issues(&paths)?;
+        let layers: Vec<toml::Table> = paths.iter()
+            .map(|path| fs::read_to_string(path).ok().and_then(|content| toml::from_str(&content).ok()).unwrap_or_default())
+            .collect();
+        let overrides = Config::env_overrides(&paths).unwrap_or_default();
+        // A file that does not load has no effective values; the defaults stand in for them here
+        let config = Config::load_layers(&paths).ok();
+        let loaded = config.is_some();
+        let effective: Vec<(String, toml::Value, String)> = flatten(&config.unwrap_or_default())
+            .into_iter()
+            .map(|(key, value)| {
+                let from_env = overrides.iter()
+                    .find(|env_override| env_override.key == key || env_override.key.starts_with(&format!("{}.", key)));
+                let from_file = paths.iter().zip(&layers).rfind(|(_, layer)| file_sets(layer, &key));
+                let origin = match (loaded, from_env, from_file) {
+                    (false, _, _) => "default".to_string(),
+                    (true, Some(env_override), _) => format!("env {}", env_override.var),
+                    (true, None, Some((path, _))) => path.display().to_string(),
+                    (true, None, None) => "default".to_string(),
+                };
+                (key, value, origin)
+            })
+            .collect();
+        
+        if json {
+            let effective: serde_json::Map<String, serde_json::Value> = effective.iter()
+                .map(|(key, value, origin)| (key.clone(), serde_json::json!({ "value": value, "source": origin })))
+                .collect();
+            print_json(serde_json::json!({
+                "config_files": paths,
+                "env_overrides": overrides.iter().map(|env_override| (env_override.var.clone(), env_override.key.clone())).collect::<BTreeMap<_, _>>(),
+                "loaded": loaded,
+                "issues": issues,
+                "effective": effective,
+            }), &paths, &issues)?;
+        } else {
+            output::line("🩺 Configuration doctor\n");
+            if paths.is_empty() {
+                output::line("No config file found; using defaults");
+            } else {
+                output::line("Config files (later ones override earlier ones):");
+                for path in &paths {
+                    output::line(format!("  {}", path.display()));
+                }
+            }
+            if !overrides.is_empty() {
+                output::line("Environment overrides (above the files):");
+                for env_override in &overrides {
+                    output::line(format!("  {} → {} = {}", env_override.var, env_override.key, env_override.value));
+                }
+            }
+            if !loaded {
+                output::line("⚠️  The configuration does not load, so every command stops with an error until it is fixed");
+            }
+            output::blank();
+            print_issues(&issues);
+            output::line("\nEffective configuration:");
+            for (key, value, origin) in &effective {
+                output::line(format!("  {} = {}  # {}", key, value, origin));
+            }
+        }
+        
+        fail_on_errors(&issues)
+    }
+    
+    /// Get configuration for specific command
+    pub fn get_command_config(command: &str) -> Result<String> {
+        let config = Config::load()?;
+        
+        let section = match command {
+            "large" => toml::to_string_pretty(&config.large_files)?,
+            "types" => toml::to_string_pretty(&config.typescript)?,
+            "imports" => toml::to_string_pretty(&config.imports)?,
+            "bundle" => toml::to_string_pretty(&config.bundle)?,
+            "perf" => toml::to_string_pretty(&config.performance)?,
+            "memory" => toml::to_string_pretty(&config.memory)?,
+            "env" => toml::to_string_pretty(&config.environment)?,
+            "secrets" => toml::to_string_pretty(&config.secrets)?,
+            "hooks" => toml::to_string_pretty(&config.hooks)?,
+            "report" => toml::to_string_pretty(&config.report)?,
+            "history" => toml::to_string_pretty(&config.history)?,
+            "a11y" => toml::to_string_pretty(&config.a11y)?,
+            "console" => toml::to_string_pretty(&config.console)?,
+            "tailwind" => toml::to_string_pretty(&config.tailwind)?,
+            "images" => toml::to_string_pretty(&config.images)?,
+            "i18n" => toml::to_string_pretty(&config.i18n)?,
+            "tests" => toml::to_string_pretty(&config.tests)?,
+            "complexity" => toml::to_string_pretty(&config.complexity)?,
+            "lint" => toml::to_string_pretty(&config.lint)?,
+            "outdated" => toml::to_string_pretty(&config.outdated)?,
+            "vulns" => toml::to_string_pretty(&config.vulns)?,
+            "docker" => toml::to_string_pretty(&config.docker)?,
+            "ci" => toml::to_string_pretty(&config.ci)?,
+            "boundaries" => toml::to_string_pretty(&config.boundaries)?,
+            "deploy" => toml::to_string_pretty(&config.deploy)?,
+            "notify" => toml::to_string_pretty(&config.notify)?,
+            _ => return Err(anyhow::anyhow!("Unknown command: {}", command)),
+        };
+        
+        Ok(section)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    
+    #[test]
+    fn test_default_config() {
+        let config = Config::default();
+        assert_eq!(config.large_files.threshold, 100);
+        assert!(config.typescript.strict_any_check);
+        assert_eq!(config.bundle.max_bundle_size_mb, 2.0);
+    }
+    
+    #[test]
+    fn test_config_serialization() {
+        let config = Config::default();
+        let toml_str = toml::to_string(&config).unwrap();
+        let deserialized: Config = toml::from_str(&toml_str).unwrap();
+        
+        assert_eq!(config.large_files.threshold, deserialized.large_files.threshold);
+    }
+    
+    #[test]
+    fn test_command_file_filters() {
+        let content = "[large_files]\nthreshold = 100\nexclude = [\"**/generated/**\"]\n\n[memory]\ninclude = [\"src/**\"]\nexclude = [\"*.stories.tsx\"]\n\n[tests]\nexclude = [\"*.d.ts\"]\n";
+        let filters = command_file_filters(&toml::from_str(content).unwrap()).unwrap();
+        assert_eq!(filters.keys().collect::<Vec<_>>(), vec!["large", "memory"]);
+        assert_eq!(filters["large"].exclude, vec!["**/generated/**"]);
+        assert_eq!(filters["memory"].include, vec!["src/**"]);
+
+        assert!(command_file_filters(&toml::from_str("[console]\nexclude = \"*.test.ts\"\n").unwrap()).is_err());
+    }
+    
+    #[test]
+    fn test_package_config_merges_over_root_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let package = root.join("packages/design-system");
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(package.join("src")).unwrap();
+        fs::write(root.join("sniff.toml"), "[large_files]\nthreshold = 300\nfunction_threshold = 80\n").unwrap();
+        fs::write(package.join("sniff.toml"), "[large_files]\nthreshold = 150\n\n[large_files.file_type_thresholds]\ncomponent = 120\n").unwrap();
+
+        assert_eq!(Config::discover(&package.join("src")), vec![root.join("sniff.toml"), package.join("sniff.toml")]);
+        let config = Config::load_from_dir(&package).unwrap();
+        assert_eq!((config.large_files.threshold, config.large_files.function_threshold), (150, 80));
+        assert_eq!(config.large_files.file_type_thresholds["component"], 120);
+        assert_eq!(config.large_files.file_type_thresholds["test"], 400);
+        assert_eq!(Config::load_from_dir(&root).unwrap().large_files.threshold, 300);
+
+        let files = vec![package.join("src/Button.tsx"), root.join("apps/web/page.tsx")];
+        let packages = Config::packages_below(&root, &files).unwrap();
+        assert_eq!(packages.len(), 1);
+        assert_eq!(packages[0].0, package);
+        assert_eq!(packages[0].1.large_files.threshold, 150);
+    }
+    
+}
\ No newline at end of file
@@ -0,0 +1,790 @@
+// Whole-file validation of the config for `sniff config validate` and `sniff config doctor`
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use super::*;
+use crate::commands::rules::rule_regex;
+use crate::common::glob_error;
+use crate::common::rule_catalog;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueLevel {
+    /// The file does not load, or a value is out of range; commands fall back to or misbehave with it
+    Error,
+    /// Settings that load but contradict each other
+    Warning,
+}
+
+/// A problem with one config key, located in the file when it appears there
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigIssue {
+    pub level: IssueLevel,
+    /// Dotted key path, e.g. `large_files.severity_levels.warning`
+    pub key: String,
+    pub message: String,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    /// How to fix it, when sniff can tell
+    pub suggestion: Option<String>,
+    /// The config file it is in; none for problems with the defaults
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+impl ConfigIssue {
+    fn error(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { level: IssueLevel::Error, key: key.into(), message: message.into(), line: None, column: None, suggestion: None, file: None }
+    }
+
+    fn warning(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { level: IssueLevel::Warning, ..Self::error(key, message) }
+    }
+
+    /// An override variable that cannot be read as its key's type
+    pub(super) fn environment(message: impl Into<String>) -> Self {
+        Self::error("environment", message)
+    }
+
+    fn suggest(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+}
+
+/// Keys of each `[[rules]]`, `[[plugins]]` and `[[ignore]]` entry
+const ENTRY_KEYS: &[(&str, &[&str])] = &[
+    ("rules", &["name", "pattern", "import", "call", "severity", "message", "suggestion", "files", "exclude"]),
+    ("plugins", &["path", "files", "exclude"]),
+    ("ignore", &["rule", "paths", "reason"]),
+];
+
+/// Tables whose keys are chosen by the user, so only their values are checked
+const OPEN_TABLES: &[&str] = &[
+    "large_files.file_type_thresholds",
+    "typescript.thresholds",
+    "bundle.budgets.chunk_types",
+    "bundle.budgets.routes",
+    "performance.budgets.scores",
+    "environment.schema",
+    "a11y.rules",
+    "deploy.gates",
+];
+
+/// Keys left out of the serialized defaults because they are unset by default
+pub(super) const OPTIONAL_KEYS: &[&str] = &[
+    "bundle.regression.max_increase_kb",
+    "bundle.regression.max_increase_percent",
+    "typescript.budgets.ts_ignore",
+    "typescript.budgets.ts_nocheck",
+    "typescript.budgets.ts_expect_error",
+    "typescript.budgets.non_null_assertions",
+    "bundle.budgets.total_kb",
+    "bundle.budgets.chunk_kb",
+    "performance.url",
+    "performance.budgets.lcp_ms",
+    "performance.budgets.tbt_ms",
+    "performance.budgets.cls",
+    "performance.budgets.fcp_ms",
+    "performance.budgets.speed_index_ms",
+    "performance.budgets.first_load_kb",
+    "notify.webhook_url",
+    "notify.service",
+    "notify.channel",
+    "notify.mention_on_failure",
+    "lint.report",
+    "outdated.max_majors_behind",
+    "vulns.report",
+];
+
+/// Every problem in the config file's `content`, and the config it gives over the defaults when it loads
+pub fn validate_content(content: &str) -> (Option<Config>, Vec<ConfigIssue>) {
+    let table: toml::Table = match toml::from_str(content) {
+        Ok(table) => table,
+        Err(error) => return (None, vec![located(content, syntax_issue(&error, content))]),
+    };
+
+    let mut issues = unknown_keys(&table);
+    let mut merged = toml::Table::try_from(Config::default()).unwrap_or_default();
+    merge_tables(&mut merged, normalized(table));
+    let config = match command_file_filters(&merged) {
+        Ok(files) => match toml::Value::Table(merged).try_into::<Config>() {
+            Ok(config) => Some(Config { files, ..config }),
+            Err(error) => {
+                issues.push(type_issue(&error));
+                None
+            }
+        },
+        Err(error) => {
+            issues.push(ConfigIssue::error("include", error.to_string()));
+            None
+        }
+    };
+    if let Some(config) = &config {
+        issues.extend(value_issues(config));
+    }
+    let issues = issues.into_iter().map(|issue| located(content, issue)).collect();
+    (config, issues)
+}
+
+/// Out-of-range values, bad patterns and conflicting settings in a loaded config
+pub fn value_issues(config: &Config) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let mut push = |issue: ConfigIssue| issues.push(issue);
+
+    if config.large_files.threshold == 0 {
+        push(ConfigIssue::error("large_files.threshold", "Large files threshold cannot be 0"));
+    }
+    if config.large_files.function_threshold == 0 {
+        push(ConfigIssue::error("large_files.function_threshold", "Large function threshold cannot be 0"));
+    }
+    for (file_type, threshold) in &config.large_files.file_type_thresholds {
+        let key = format!("large_files.file_type_thresholds.{}", file_type);
+        if !FILE_TYPE_KEYS.contains(&file_type.as_str()) {
+            push(unknown_name(&key, "file type", file_type, FILE_TYPE_KEYS));
+        }
+        if *threshold == 0 {
+            push(ConfigIssue::error(key, format!("Threshold for file type '{}' cannot be 0", file_type)));
+        }
+    }
+    let levels = &config.large_files.severity_levels;
+    if levels.warning >= levels.error || levels.error >= levels.critical {
+        push(ConfigIssue::error("large_files.severity_levels", "Severity levels must be in ascending order: warning < error < critical"));
+    }
+    for pattern in config.large_files.excluded_files.iter().chain(&config.memory.excluded_files) {
+        if let Err(e) = regex::Regex::new(&pattern.replace('*', ".*")) {
+            push(ConfigIssue::error("excluded_files", format!("Invalid file pattern '{}': {}", pattern, e)));
+        }
+    }
+
+    if !(0.0..=100.0).contains(&config.typescript.min_type_coverage) {
+        push(ConfigIssue::error("typescript.min_type_coverage", "TypeScript coverage must be between 0 and 100"));
+    }
+    for (glob, threshold) in &config.typescript.thresholds {
+        let key = format!("typescript.thresholds.\"{}\"", glob);
+        if let Some(error) = glob_error(glob) {
+            push(ConfigIssue::error(key.as_str(), format!("Invalid glob '{}': {}", glob, error)));
+        }
+        if !(0.0..=100.0).contains(threshold) {
+            push(ConfigIssue::error(key, "Type coverage thresholds must be between 0 and 100"));
+        }
+    }
+
+    for group in &config.imports.order.groups {
+        if !IMPORT_GROUP_KEYS.contains(&group.as_str()) {
+            push(unknown_name("imports.order.groups", "group", group, IMPORT_GROUP_KEYS));
+        }
+    }
+    if config.imports.order.enabled && config.imports.order.groups.is_empty() {
+        push(ConfigIssue::warning("imports.order.groups", "Import ordering is enabled but no groups are listed, so every import is in one group")
+            .suggest(format!("groups = [{}]", quoted(&["builtin", "external", "alias", "relative"]))));
+    }
+    check_globs(&mut push, "imports.entry_points", &config.imports.entry_points);
+
+    if config.bundle.max_bundle_size_mb <= 0.0 {
+        push(ConfigIssue::error("bundle.max_bundle_size_mb", "Bundle size limit must be positive"));
+    }
+    if config.bundle.max_chunk_size_mb > config.bundle.max_bundle_size_mb {
+        push(ConfigIssue::warning("bundle.max_chunk_size_mb", "A single chunk may be larger than the whole bundle limit allows")
+            .suggest(format!("Lower max_chunk_size_mb to at most {}", config.bundle.max_bundle_size_mb)));
+    }
+    for chunk_type in config.bundle.budgets.chunk_types.keys() {
+        if !CHUNK_TYPE_KEYS.contains(&chunk_type.as_str()) {
+            push(unknown_name(&format!("bundle.budgets.chunk_types.{}", chunk_type), "chunk type", chunk_type, CHUNK_TYPE_KEYS));
+        }
+    }
+    if let (Some(chunk), Some(total)) = (config.bundle.budgets.chunk_kb, config.bundle.budgets.total_kb) {
+        if chunk > total {
+            push(ConfigIssue::warning("bundle.budgets.chunk_kb", format!("The chunk budget ({} KB) is larger than the total budget ({} KB)", chunk, total)));
+        }
+    }
+    let routes: Vec<String> = config.bundle.budgets.routes.keys().cloned().collect();
+    check_globs(&mut push, "bundle.budgets.routes", &routes);
+
+    if config.performance.runs == 0 {
+        push(ConfigIssue::error("performance.runs", "performance.runs must be at least 1"));
+    }
+    for category in &config.performance.categories {
+        if !LIGHTHOUSE_CATEGORIES.contains(&category.as_str()) {
+            push(unknown_name("performance.categories", "Lighthouse category", category, LIGHTHOUSE_CATEGORIES));
+        }
+    }
+    for (category, score) in &config.performance.budgets.scores {
+        let key = format!("performance.budgets.scores.{}", category);
+        if !LIGHTHOUSE_CATEGORIES.contains(&category.as_str()) {
+            push(unknown_name(&key, "Lighthouse category", category, LIGHTHOUSE_CATEGORIES));
+        } else if !config.performance.categories.contains(category) {
+            push(ConfigIssue::warning(&key, format!("'{}' has a score budget but is not in performance.categories, so it is never audited", category)));
+        }
+        if !(0.0..=100.0).contains(score) {
+            push(ConfigIssue::error(key, format!("performance.budgets.scores.{} must be between 0 and 100", category)));
+        }
+    }
+
+    for (name, schema) in &config.environment.schema {
+        let key = format!("environment.schema.{}", name);
+        if schema.var_type == EnvVarType::Enum && schema.values.is_empty() {
+            push(ConfigIssue::error(format!("{}.values", key), format!("Environment variable '{}' has type \"enum\" but no values", name)));
+        }
+        if let Some(pattern) = &schema.pattern {
+            if let Err(e) = regex::Regex::new(pattern) {
+                push(ConfigIssue::error(format!("{}.pattern", key), format!("Invalid pattern for environment variable '{}': {}", name, e)));
+            }
+        }
+    }
+
+    for pattern in &config.secrets.allowlist {
+        if let Err(e) = regex::Regex::new(pattern) {
+            push(ConfigIssue::error("secrets.allowlist", format!("Invalid secrets.allowlist pattern '{}': {}", pattern, e)));
+        }
+    }
+    if config.secrets.min_entropy < 0.0 {
+        push(ConfigIssue::error("secrets.min_entropy", "Secrets min_entropy cannot be negative"));
+    }
+
+    for (key, commands) in [("hooks.pre_commit", &config.hooks.pre_commit), ("hooks.pre_push", &config.hooks.pre_push)] {
+        for command in commands {
+            if command.split_whitespace().next().is_none_or(|name| name == "hooks") {
+                push(ConfigIssue::error(key, format!("Invalid hook command '{}': expected sniff arguments such as \"imports --staged\"", command)));
+            }
+        }
+    }
+
+    for analysis in &config.report.analyses {
+        if !REPORT_ANALYSES.contains(&analysis.as_str()) {
+            push(unknown_name("report.analyses", "analysis", analysis, REPORT_ANALYSES));
+        }
+    }
+
+    if config.history.path.trim().is_empty() {
+        push(ConfigIssue::error("history.path", "history.path cannot be empty"));
+    }
+
+    for (check, gate) in &config.deploy.gates {
+        let key = format!("deploy.gates.{}", check);
+        if !DEPLOY_CHECKS.contains(&check.as_str()) {
+            push(unknown_name(&key, "check", check, DEPLOY_CHECKS));
+        }
+        let DeployGate::Limit(limit) = gate else { continue };
+        match (limit.block_over, limit.block_over_mb) {
+            (None, None) => push(ConfigIssue::error(&key, format!("The gate for '{}' sets no limit", check))
+                .suggest("Set block_over, or use \"block\", \"warn\" or \"off\"")),
+            (_, Some(_)) if check != "bundle" => push(ConfigIssue::error(&key, format!("block_over_mb only applies to bundle, not '{}'", check))
+                .suggest("Use block_over to limit the number of findings")),
+            (_, Some(mb)) if mb <= 0.0 => push(ConfigIssue::error(&key, "block_over_mb must be positive")),
+            _ => {}
+        }
+    }
+
+    if let Some(url) = &config.notify.webhook_url {
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            push(ConfigIssue::error("notify.webhook_url", format!("notify.webhook_url must be an http(s) URL, got '{}'", url)));
+        }
+    }
+
+    for rule in config.a11y.rules.keys() {
+        if !A11Y_RULES.contains(&rule.as_str()) {
+            push(unknown_name(&format!("a11y.rules.{}", rule), "rule", rule, A11Y_RULES));
+        }
+    }
+
+    for method in &config.console.methods {
+        if method.is_empty() || !method.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            push(ConfigIssue::error("console.methods", format!("Invalid console method '{}' in console.methods", method)));
+        }
+    }
+    check_globs(&mut push, "console.allowlist", &config.console.allowlist);
+
+    if !(0.0..=100.0).contains(&config.tailwind.max_arbitrary_percent) {
+        push(ConfigIssue::error("tailwind.max_arbitrary_percent", "tailwind.max_arbitrary_percent must be between 0 and 100"));
+    }
+    if config.tailwind.repeated_arbitrary_min < 2 {
+        push(ConfigIssue::error("tailwind.repeated_arbitrary_min", "tailwind.repeated_arbitrary_min must be at least 2"));
+    }
+    if config.tailwind.duplicate_min_occurrences < 2 {
+        push(ConfigIssue::error("tailwind.duplicate_min_occurrences", "tailwind.duplicate_min_occurrences must be at least 2"));
+    }
+
+    if config.images.max_kb == 0 {
+        push(ConfigIssue::error("images.max_kb", "images.max_kb must be greater than 0"));
+    }
+    if config.images.max_dimension == 0 {
+        push(ConfigIssue::error("images.max_dimension", "images.max_dimension must be greater than 0"));
+    }
+
+    if !(0.0..=100.0).contains(&config.tests.min_tested_percent) {
+        push(ConfigIssue::error("tests.min_tested_percent", "tests.min_tested_percent must be between 0 and 100"));
+    }
+    if config.tests.patterns.is_empty() && config.tests.min_tested_percent > 0.0 {
+        push(ConfigIssue::warning("tests.patterns", "No test file patterns, so no file counts as tested and min_tested_percent always fails"));
+    }
+    check_globs(&mut push, "tests.patterns", &config.tests.patterns);
+    check_globs(&mut push, "tests.exclude", &config.tests.exclude);
+
+    if config.complexity.cyclomatic == 0 {
+        push(ConfigIssue::error("complexity.cyclomatic", "complexity.cyclomatic must be greater than 0"));
+    }
+    if config.complexity.cognitive == 0 {
+        push(ConfigIssue::error("complexity.cognitive", "complexity.cognitive must be greater than 0"));
+    }
+
+    let mut names = std::collections::HashSet::new();
+    for (index, rule) in config.rules.iter().enumerate() {
+        let key = |field: &str| format!("rules[{}].{}", index, field);
+        if rule.name.trim().is_empty() {
+            push(ConfigIssue::error(key("name"), "Every rule needs a name"));
+        } else if !names.insert(rule.name.as_str()) {
+            push(ConfigIssue::error(key("name"), format!("Another rule is already named '{}'", rule.name)));
+        }
+        if rule.message.trim().is_empty() {
+            push(ConfigIssue::error(key("message"), "Every rule needs a message"));
+        }
+        let queries = [("pattern", &rule.pattern), ("import", &rule.import), ("call", &rule.call)];
+        match queries.iter().filter(|(_, query)| query.is_some()).collect::<Vec<_>>().as_slice() {
+            [(field, _)] => {
+                if let Err(error) = rule_regex(rule) {
+                    push(ConfigIssue::error(key(field), error.to_string()));
+                }
+            }
+            [] => push(ConfigIssue::error(format!("rules[{}]", index), format!("Rule '{}' matches nothing", rule.name))
+                .suggest("Set one of `pattern` (a regex), `import` (a module) or `call` (a function)")),
+            [_, (field, _), ..] => push(ConfigIssue::error(key(field), "A rule takes only one of pattern, import or call")),
+        }
+        if !RULE_SEVERITIES.contains(&rule.severity.to_ascii_lowercase().as_str()) {
+            push(unknown_name(&key("severity"), "severity", &rule.severity, RULE_SEVERITIES));
+        }
+        check_globs(&mut push, &key("files"), &rule.files);
+        check_globs(&mut push, &key("exclude"), &rule.exclude);
+    }
+
+    for (index, plugin) in config.plugins.iter().enumerate() {
+        let key = |field: &str| format!("plugins[{}].{}", index, field);
+        if !plugin.path.ends_with(".wasm") && !plugin.path.ends_with(".wat") {
+            push(ConfigIssue::error(key("path"), format!("'{}' is not a WebAssembly module", plugin.path))
+                .suggest("Point `path` at a .wasm file, relative to the project root"));
+        }
+        check_globs(&mut push, &key("files"), &plugin.files);
+        check_globs(&mut push, &key("exclude"), &plugin.exclude);
+    }
+
+    for (index, entry) in config.ignore.iter().enumerate() {
+        let key = |field: &str| format!("ignore[{}].{}", index, field);
+        let known = entry.rule == "*"
+            || rule_catalog::find(&entry.rule.to_ascii_uppercase()).is_some()
+            || rule_catalog::command_of(&entry.rule).is_some()
+            || rule_catalog::RULES.iter().any(|rule| rule.command == entry.rule)
+            || rule_catalog::EXTERNAL_PREFIXES.iter().any(|(_, command)| *command == entry.rule)
+            || entry.rule == "rules"
+            || config.rules.iter().any(|rule| rule.name == entry.rule);
+        if !known {
+            let issue = ConfigIssue::error(key("rule"), format!("Unknown rule '{}'", entry.rule));
+            let ids = rule_catalog::RULES.iter().map(|rule| rule.id).chain(config.rules.iter().map(|rule| rule.name.as_str()));
+            push(match closest(&entry.rule.to_ascii_uppercase(), ids) {
+                Some(close) => issue.suggest(format!("Did you mean '{}'?", close)),
+                None => issue.suggest("Use a rule id from `sniff rules list`, a [[rules]] name, a command or \"*\""),
+            });
+        }
+        check_globs(&mut push, &key("paths"), &entry.paths);
+    }
+
+    for (command, files) in &config.files {
+        let section = section_for_command(command);
+        check_globs(&mut push, &format!("{}.include", section), &files.include);
+        check_globs(&mut push, &format!("{}.exclude", section), &files.exclude);
+        for glob in files.include.iter().filter(|glob| files.exclude.contains(glob)) {
+            push(ConfigIssue::warning(format!("{}.exclude", section), format!("'{}' is both included and excluded, so it is excluded", glob)));
+        }
+    }
+
+    issues
+}
+
+fn check_globs(push: &mut impl FnMut(ConfigIssue), key: &str, globs: &[String]) {
+    for glob in globs {
+        if let Some(error) = glob_error(glob) {
+            push(ConfigIssue::error(key, format!("Invalid glob '{}': {}", glob, error)));
+        }
+    }
+}
+
+fn unknown_name(key: &str, kind: &str, name: &str, expected: &[&str]) -> ConfigIssue {
+    let issue = ConfigIssue::error(key, format!("Unknown {} '{}' (expected one of: {})", kind, name, expected.join(", ")));
+    match closest(name, expected.iter().copied()) {
+        Some(close) => issue.suggest(format!("Did you mean '{}'?", close)),
+        None => issue,
+    }
+}
+
+fn quoted(names: &[&str]) -> String {
+    names.iter().map(|name| format!("\"{}\"", name)).collect::<Vec<_>>().join(", ")
+}
+
+/// The section that holds `command`'s include/exclude globs
+fn section_for_command(command: &str) -> &str {
+    COMMAND_SECTIONS.iter().find(|(_, name)| *name == command).map_or(command, |(section, _)| section)
+}
+
+/// Keys in the file that no part of the config reads
+fn unknown_keys(table: &toml::Table) -> Vec<ConfigIssue> {
+    let known = toml::Table::try_from(Config::default()).unwrap_or_default();
+    let mut issues = Vec::new();
+    for (section, value) in table {
+        let name = SECTION_ALIASES.iter().find(|(alias, _)| alias == section).map_or(section.as_str(), |(_, full)| full);
+        if let Some((_, keys)) = ENTRY_KEYS.iter().find(|(array, _)| *array == name) {
+            unknown_entry_keys(name, keys, value, &mut issues);
+            continue;
+        }
+        match (known.get(name), value.as_table()) {
+            (Some(toml::Value::Table(schema)), Some(values)) => unknown_in_table(name, schema, values, &mut issues),
+            (Some(_), _) => {}
+            // A section only for a command's include/exclude globs
+            (None, Some(values)) if FILTERED_COMMANDS.contains(&name) => {
+                for key in values.keys().filter(|key| !matches!(key.as_str(), "include" | "exclude")) {
+                    issues.push(ConfigIssue::error(format!("{}.{}", name, key), "Unknown key; this section only takes include and exclude")
+                        .suggest(format!("Remove it, or use `include`/`exclude` to choose the files `sniff {}` scans", name)));
+                }
+            }
+            (None, _) => {
+                let issue = ConfigIssue::error(section.clone(), format!("Unknown section '{}'", section));
+                let candidates = known.keys().map(String::as_str).chain(FILTERED_COMMANDS.iter().copied());
+                issues.push(match closest(section, candidates) {
+                    Some(close) => issue.suggest(format!("Did you mean [{}]?", close)),
+                    None => issue.suggest("Remove it; sniff ignores it"),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// Keys of `[[array]]` entries that an entry does not take
+fn unknown_entry_keys(array: &str, known: &[&str], value: &toml::Value, issues: &mut Vec<ConfigIssue>) {
+    let Some(entries) = value.as_array() else {
+        issues.push(ConfigIssue::error(array, format!("Write each entry as a [[{}]] table", array)));
+        return;
+    };
+    for (index, entry) in entries.iter().enumerate() {
+        for key in entry.as_table().into_iter().flat_map(|entry| entry.keys()) {
+            if known.contains(&key.as_str()) {
+                continue;
+            }
+            let issue = ConfigIssue::error(format!("{}[{}].{}", array, index, key), format!("Unknown key '{}' in [[{}]]; sniff ignores it", key, array));
+            issues.push(match closest(key, known.iter().copied()) {
+                Some(close) => issue.suggest(format!("Did you mean `{}`?", close)),
+                None => issue.suggest(format!("An entry takes {}", known.join(", "))),
+            });
+        }
+    }
+}
+
+fn unknown_in_table(path: &str, schema: &toml::Table, values: &toml::Table, issues: &mut Vec<ConfigIssue>) {
+    if OPEN_TABLES.contains(&path) {
+        return;
+    }
+    for (key, value) in values {
+        let key_path = format!("{}.{}", path, key);
+        match schema.get(key) {
+            Some(toml::Value::Table(nested)) => {
+                if let Some(nested_values) = value.as_table() {
+                    unknown_in_table(&key_path, nested, nested_values, issues);
+                }
+            }
+            Some(_) => {}
+            None if OPTIONAL_KEYS.contains(&key_path.as_str()) => {}
+            // Every section may narrow its command's files
+            None if !path.contains('.') && matches!(key.as_str(), "include" | "exclude") => {}
+            None => {
+                let optional = OPTIONAL_KEYS.iter().filter_map(|optional| optional.strip_prefix(path)?.strip_prefix('.'));
+                let candidates = schema.keys().map(String::as_str).chain(optional);
+                let issue = ConfigIssue::error(key_path, format!("Unknown key '{}' in [{}]; sniff ignores it", key, path));
+                issues.push(match closest(key, candidates) {
+                    Some(close) => issue.suggest(format!("Did you mean `{}`?", close)),
+                    None => issue.suggest("Remove it"),
+                });
+            }
+        }
+    }
+}
+
+/// A candidate within a few edits of `name`, for "did you mean" suggestions
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = (name.len() / 3).max(1);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// A TOML syntax error, located at the offending character
+fn syntax_issue(error: &toml::de::Error, content: &str) -> ConfigIssue {
+    let (line, column) = error.span().map(|span| line_column(content, span.start)).unzip();
+    ConfigIssue { line, column, ..ConfigIssue::error("", error.message().trim()) }
+}
+
+/// A value of the wrong type, or a field missing from an entry the file adds
+fn type_issue(error: &toml::de::Error) -> ConfigIssue {
+    let message = error.message().trim().to_string();
+    // The error names the offending key as "in `section.key`"
+    let display = error.to_string();
+    let key = display.rsplit_once("in `").and_then(|(_, rest)| rest.split('`').next()).unwrap_or_default();
+    let missing = message.strip_prefix("missing field `").and_then(|rest| rest.split('`').next());
+    match missing {
+        Some(field) if !key.is_empty() => ConfigIssue::error(format!("{}.{}", key, field), message.clone())
+            .suggest(format!("Add `{}` to [{}]", field, key)),
+        _ => ConfigIssue::error(key, message),
+    }
+}
+
+fn lookup<'a>(table: &'a toml::Table, path: &str) -> Option<&'a toml::Value> {
+    let (first, rest) = path.split_once('.').unwrap_or((path, ""));
+    let value = table.get(first)?;
+    if rest.is_empty() {
+        Some(value)
+    } else {
+        lookup(value.as_table()?, rest)
+    }
+}
+
+/// Fill in where the issue's key is written in the file, when it is
+fn located(content: &str, mut issue: ConfigIssue) -> ConfigIssue {
+    if issue.line.is_none() {
+        let position = match array_entry(&issue.key) {
+            Some((array, index, field)) => entry_position(content, array, index, field),
+            None => key_position(content, &issue.key),
+        };
+        if let Some((line, column)) = position {
+            issue.line = Some(line);
+            issue.column = Some(column);
+        }
+    }
+    issue
+}
+
+/// `rules[2].pattern` as (`rules`, 2, `pattern`)
+fn array_entry(key: &str) -> Option<(&str, usize, &str)> {
+    let (array, rest) = key.split_once('[')?;
+    let (index, field) = rest.split_once(']')?;
+    Some((array, index.parse().ok()?, field.trim_start_matches('.')))
+}
+
+/// Where `field` of the `index`th `[[array]]` table is written, or its header without the field
+fn entry_position(content: &str, array: &str, index: usize, field: &str) -> Option<(usize, usize)> {
+    let header = format!("[[{}]]", array);
+    let mut lines = content.lines().enumerate()
+        .filter(|(_, line)| line.trim() == header)
+        .map(|(number, _)| number)
+        .skip(index);
+    let start = lines.next()?;
+    let end = lines.next().unwrap_or(usize::MAX);
+    let in_entry = content.lines().enumerate().skip(start + 1).take_while(|(number, line)| *number < end && table_header(line).is_none());
+    for (number, line) in in_entry {
+        if line.split_once('=').is_some_and(|(name, _)| dotted(name) == field) {
+            return Some((number + 1, line.len() - line.trim_start().len() + 1));
+        }
+    }
+    Some((start + 1, 1))
+}
+
+/// 1-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..offset.min(content.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |text| text.chars().count()) + 1;
+    (line, column)
+}
+
+fn table_header(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('[')?.split(']').next()?.trim_start_matches('[');
+    Some(dotted(inner))
+}
+
+fn dotted(key: &str) -> String {
+    key.split('.').map(|part| part.trim().trim_matches('"').trim_matches('\'')).collect::<Vec<_>>().join(".")
+}
+
+/// Where a dotted key is written: as `key = ...` under its table, or as a table header.
+/// Keys not in the file fall back to their closest enclosing table
+fn key_position(content: &str, key: &str) -> Option<(usize, usize)> {
+    let mut section = String::new();
+    let mut best: Option<(usize, (usize, usize))> = None;
+    for (index, line) in content.lines().enumerate() {
+        if let Some(header) = table_header(line) {
+            section = header;
+            let column = line.find('[').unwrap_or(0) + 1;
+            if key == section || key.starts_with(&format!("{}.", section)) {
+                let depth = section.len();
+                if best.is_none_or(|(best_depth, _)| depth > best_depth) {
+                    best = Some((depth, (index + 1, column)));
+                }
+            }
+            continue;
+        }
+        let Some((name, _)) = line.split_once('=') else { continue };
+        let name = dotted(name);
+        if name.is_empty() || name.starts_with('#') {
+            continue;
+        }
+        let full = if section.is_empty() { name } else { format!("{}.{}", section, name) };
+        if full == key {
+            return Some((index + 1, line.len() - line.trim_start().len() + 1));
+        }
+    }
+    best.map(|(_, position)| position)
+}
+
+/// Every leaf key of the effective config with its value, e.g. `large_files.threshold = 100`
+pub fn flatten(config: &Config) -> BTreeMap<String, toml::Value> {
+    let mut values = BTreeMap::new();
+    if let Ok(table) = toml::Table::try_from(config) {
+        flatten_into("", &table, &mut values);
+    }
+    for (command, files) in &config.files {
+        let section = section_for_command(command);
+        values.insert(format!("{}.include", section), toml::Value::from(files.include.clone()));
+        values.insert(format!("{}.exclude", section), toml::Value::from(files.exclude.clone()));
+    }
+    values
+}
+
+fn flatten_into(prefix: &str, table: &toml::Table, values: &mut BTreeMap<String, toml::Value>) {
+    for (key, value) in table {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::Table(nested) if !OPEN_TABLES.contains(&path.as_str()) => flatten_into(&path, nested, values),
+            _ => {
+                values.insert(path, value.clone());
+            }
+        }
+    }
+}
+
+/// Whether the file sets `key` itself (or `perf.` for `performance.`)
+pub fn file_sets(file: &toml::Table, key: &str) -> bool {
+    lookup(file, key).is_some()
+        || key.strip_prefix("performance.").is_some_and(|rest| lookup(file, &format!("perf.{}", rest)).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_content() -> String {
+        toml::to_string(&Config::default()).unwrap()
+    }
+
+    #[test]
+    fn test_default_config_has_no_issues() {
+        let (config, issues) = validate_content(&default_content());
+        assert!(config.is_some());
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_located_with_suggestions() {
+        let content = default_content().replacen("[large_files]\n", "[large_files]\ntreshold = 200\n", 1) + "\n[memroy]\ncheck_patterns = true\n\n[duplicates]\nexclude = [\"**/generated/**\"]\n";
+        let (config, issues) = validate_content(&content);
+        assert!(config.is_some());
+        let typo = issues.iter().find(|issue| issue.key == "large_files.treshold").unwrap();
+        assert_eq!(typo.suggestion.as_deref(), Some("Did you mean `threshold`?"));
+        let line = content.lines().position(|line| line.starts_with("treshold")).unwrap() + 1;
+        assert_eq!((typo.line, typo.column), (Some(line), Some(1)));
+        let section = issues.iter().find(|issue| issue.key == "memroy").unwrap();
+        assert_eq!(section.suggestion.as_deref(), Some("Did you mean [memory]?"));
+        assert_eq!(issues.len(), 2, "{:?}", issues);
+    }
+
+    #[test]
+    fn test_every_value_problem_is_reported() {
+        let content = default_content()
+            .replacen("threshold = 100\n", "threshold = 0\n", 1)
+            .replacen("max_chunk_size_mb = 0.5", "max_chunk_size_mb = 5.0", 1)
+            .replacen("[console]\n", "[console]\ninclude = [\"src/**/*.{ts,tsx\"]\n", 1);
+        let (_, issues) = validate_content(&content);
+        let keys: Vec<(&str, IssueLevel)> = issues.iter().map(|issue| (issue.key.as_str(), issue.level)).collect();
+        assert_eq!(keys, vec![
+            ("large_files.threshold", IssueLevel::Error),
+            ("bundle.max_chunk_size_mb", IssueLevel::Warning),
+            ("console.include", IssueLevel::Error),
+        ]);
+        assert!(issues.iter().all(|issue| issue.line.is_some()));
+    }
+
+    #[test]
+    fn test_partial_file_merges_over_defaults() {
+        let (config, issues) = validate_content("[large_files]\nthreshold = 60\n\n[perf]\nurl = \"http://localhost:4000\"\n");
+        let config = config.unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert_eq!(config.large_files.threshold, 60);
+        assert_eq!(config.large_files.function_threshold, 50);
+        assert_eq!(config.performance.url.as_deref(), Some("http://localhost:4000"));
+    }
+
+    #[test]
+    fn test_wrong_type_is_located() {
+        let content = "[large_files]\nfunction_threshold = 40\nthreshold = \"big\"\n";
+        let (config, issues) = validate_content(content);
+        assert!(config.is_none());
+        assert_eq!(issues[0].key, "large_files.threshold");
+        assert_eq!((issues[0].line, issues[0].column), (Some(3), Some(1)));
+    }
+
+    #[test]
+    fn test_rules_are_checked_and_located() {
+        let content = "[large_files]\nthreshold = 100\n\n[[rules]]\nname = \"no-moment\"\nimport = \"moment\"\nmessage = \"Use date-fns\"\n\n[[rules]]\nname = \"no-moment\"\npattern = \"(\"\nseverity = \"urgent\"\nmesage = \"typo\"\n";
+        let (config, issues) = validate_content(content);
+        assert_eq!(config.unwrap().rules.len(), 2);
+        let found: Vec<(&str, Option<usize>)> = issues.iter().map(|issue| (issue.key.as_str(), issue.line)).collect();
+        assert_eq!(found, vec![
+            ("rules[1].mesage", Some(13)),
+            ("rules[1].name", Some(10)),
+            ("rules[1].message", Some(9)),
+            ("rules[1].pattern", Some(11)),
+            ("rules[1].severity", Some(12)),
+        ]);
+    }
+
+    #[test]
+    fn test_ignore_entries_are_checked() {
+        let content = "[[ignore]]\nrule = \"snf-imp-unused\"\npaths = [\"**/*.stories.tsx\"]\n\n[[ignore]]\nrule = \"console\"\n\n[[ignore]]\nrule = \"SNF-MEM-TIMR\"\npaths = [\"src/{a\"]\nwhy = \"legacy\"\n";
+        let (config, issues) = validate_content(content);
+        assert_eq!(config.unwrap().ignore.len(), 3);
+        let found: Vec<(&str, Option<&str>)> = issues.iter().map(|issue| (issue.key.as_str(), issue.suggestion.as_deref())).collect();
+        assert_eq!(found, vec![
+            ("ignore[2].why", Some("An entry takes rule, paths, reason")),
+            ("ignore[2].rule", Some("Did you mean 'SNF-MEM-TIMER'?")),
+            ("ignore[2].paths", None),
+        ]);
+    }
+
+    #[test]
+    fn test_deploy_gates_merge_over_defaults_and_are_checked() {
+        let content = "[deploy.gates]\nlarge = \"block\"\nbundle = { block_over_mb = 5 }\nimports = { block_over_mb = 1 }\nconsol = \"off\"\n";
+        let (config, issues) = validate_content(content);
+        let config = config.unwrap();
+        assert_eq!(config.deploy.gate("large"), DeployGate::Level(GateLevel::Block));
+        assert_eq!(config.deploy.gate("env"), DeployGate::Level(GateLevel::Block));
+        assert_eq!(config.deploy.gate("bundle"), DeployGate::Limit(GateLimit { block_over: None, block_over_mb: Some(5.0) }));
+        let found: Vec<(&str, Option<&str>)> = issues.iter().map(|issue| (issue.key.as_str(), issue.suggestion.as_deref())).collect();
+        assert_eq!(found, vec![
+            ("deploy.gates.consol", Some("Did you mean 'console'?")),
+            ("deploy.gates.imports", Some("Use block_over to limit the number of findings")),
+        ]);
+    }
+}
@@ -2,4 +2,5 @@ pub mod utils;
 pub mod config;
 pub mod commands;
 pub mod common;
+pub mod plugins;
 // analyzers module removed - contained only unused code
\ No newline at end of file
@@ -1,31 +1,72 @@
-use clap::{Parser, Subcommand};
-use std::process;
+use clap::builder::BoolishValueParser;
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
 
 mod commands;
 mod config;
 mod utils;
 mod common;
+mod plugins;
 
 // Import specific command functions instead of using glob imports
-use commands::{menu, large, types, imports_analyzer as imports, bundle, perf, memory, components, env, context};
-use config::ConfigUtils;
+use commands::{menu, large, types, imports_analyzer as imports, bundle, perf, memory, components, env, context, deploy, duplicates, secrets, lint, outdated, vulns, docker, ci, boundaries, nextjs, hooks, report, history, routes, a11y, console, rules, explain, diff, security, tailwind, images, i18n, test_audit, complexity, fix, lsp, serve, init, doctor};
+use config::{Config, ConfigUtils, LighthousePreset};
+use commands::history::Metrics;
+use common::ndjson::{self, LevelCounts};
+use common::{Annotate, Annotation, CommandOutcome, ExitCode, FailOn, FailPolicy, ListingOptions, LogFormat, OutputFormat, ScanTarget, TargetOptions, exit_with, print_annotations};
+use common::junit::{self, TestSuite};
+use common::{output, sarif};
+use common::report_formatter::{Detail, print_condensed};
 
 #[derive(Parser)]
 #[command(name = "sniff")]
 #[command(about = "Opinionated TypeScript/Next.js Development Toolkit")]
 #[command(version = "0.2.7")]
+#[command(after_help = "--format github/junit/sarif/ndjson, --fail-on, --max-warnings, --summary and --top work with every command that reports findings. `routes`, `perf`, `context`, `fix`, `report` and the setup commands have none and reject them.")]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
     
-    #[arg(long, help = "Use custom configuration file")]
-    config: Option<String>,
+    #[arg(long, global = true, env = "SNIFF_CONFIG", value_name = "FILE", help = "Use this configuration file instead of discovering sniff.toml files")]
+    config: Option<PathBuf>,
     
-    #[arg(long, help = "Output in JSON format")]
+    #[arg(long, global = true, help = "Output in JSON format")]
     json: bool,
     
-    #[arg(long, help = "Quiet mode (minimal output)")]
+    #[arg(long, global = true, value_enum, conflicts_with = "json", help = "Output format [default: text] [env: SNIFF_OUTPUT]")]
+    format: Option<OutputFormat>,
+    
+    #[arg(long, global = true, env = "SNIFF_QUIET", help = "Quiet mode (minimal output)")]
     quiet: bool,
+    
+    #[arg(long, global = true, env = "SNIFF_FAIL_ON", value_enum, value_name = "LEVEL", help = "Lowest finding level that fails the run (exit 3 critical, 2 error, 5 warning)")]
+    fail_on: Option<FailOn>,
+    
+    #[arg(long, global = true, env = "SNIFF_MAX_WARNINGS", value_name = "N", help = "Fail with exit code 5 when there are more than N warnings")]
+    max_warnings: Option<usize>,
+    
+    #[arg(long, global = true, conflicts_with = "top", help = "Print only the summary block: finding counts per severity and the most affected files")]
+    summary: bool,
+    
+    #[arg(long, global = true, value_name = "N", help = "Show only the first N findings of each severity, then the summary block")]
+    top: Option<usize>,
+    
+    #[arg(long, global = true, help = "Print without ANSI colors (also when NO_COLOR or CI is set, or output is not a terminal)")]
+    no_color: bool,
+    
+    #[arg(long, global = true, env = "SNIFF_ASCII", num_args = 0..=1, require_equals = true, default_missing_value = "true", value_parser = BoolishValueParser::new(), value_name = "BOOL", help = "Print ASCII markers instead of emoji and box-drawing characters [default: on when the locale is not UTF-8]")]
+    ascii: Option<bool>,
+    
+    #[arg(long, global = true, env = "SNIFF_ABSOLUTE_PATHS", help = "Print absolute file paths instead of paths relative to the project root")]
+    absolute_paths: bool,
+    
+    #[arg(short, long, global = true, action = ArgAction::Count, help = "Log progress to stderr: -v info, -vv debug, -vvv trace [env: SNIFF_LOG for per-module filters]")]
+    verbose: u8,
+    
+    #[arg(long, global = true, value_enum, default_value_t, env = "SNIFF_LOG_FORMAT", value_name = "FORMAT", help = "Format of the log messages on stderr")]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -36,31 +77,341 @@ enum Commands {
     Large {
         #[arg(long, default_value_t = 100)]
         threshold: usize,
+        #[arg(long, help = "Report functions and components longer than this many lines")]
+        function_threshold: Option<usize>,
+        #[arg(long, help = "Show lines and large files per top-level directory instead of single files")]
+        by_dir: bool,
+        #[arg(long, default_value_t = 0, value_name = "N", help = "Leave out directories with fewer than N lines")]
+        min_dir_lines: usize,
+        #[arg(long, help = "Also scan generated files (*.d.ts, *.generated.ts, GraphQL codegen output, @generated headers)")]
+        include_generated: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+        #[command(flatten)]
+        listing: ListingOptions,
     },
     #[command(about = "Check TypeScript type coverage and quality")]
-    Types,
+    Types {
+        #[arg(long, help = "Also run the project's TypeScript compiler (tsc --noEmit) and measure type coverage")]
+        tsc: bool,
+        #[arg(long, help = "List every 'any' usage with its location and a suggested replacement")]
+        list_any: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
     #[command(about = "Detect unused and broken imports")]
-    Imports,
+    Imports {
+        #[arg(long, help = "Only analyze files staged in git (for pre-commit hooks)")]
+        staged: bool,
+        #[arg(long, help = "Also report exports that no other file imports")]
+        unused_exports: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+        #[command(flatten)]
+        listing: ListingOptions,
+    },
     #[command(about = "Analyze bundle size and optimization")]
-    Bundle,
+    Bundle {
+        #[arg(long, value_name = "REPORT", help = "Compare chunk sizes against a report saved with --save")]
+        compare: Option<PathBuf>,
+        #[arg(long, value_name = "PATH", help = "Save this build's bundle report as JSON for later comparisons")]
+        save: Option<PathBuf>,
+        #[arg(long, value_name = "STATS_JSON", help = "Analyze a webpack --json or rollup-plugin-visualizer stats file instead of the build output")]
+        stats: Option<PathBuf>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
     #[command(about = "Run Lighthouse performance audits")]
-    Perf,
+    Perf {
+        #[arg(long, help = "URL to audit (default: [performance] url, then a detected dev server)")]
+        url: Option<String>,
+        #[arg(long, value_name = "N", help = "Number of Lighthouse runs; scores are the median")]
+        runs: Option<usize>,
+        #[arg(long, value_delimiter = ',', help = "Lighthouse categories, e.g. performance,accessibility")]
+        categories: Option<Vec<String>>,
+        #[arg(long, conflicts_with = "desktop", help = "Emulate a mobile device (Lighthouse default)")]
+        mobile: bool,
+        #[arg(long, help = "Use Lighthouse's desktop preset")]
+        desktop: bool,
+        #[arg(long, value_name = "COMMAND", help = "Start the server to audit (e.g. \"npm run dev\") and stop it afterwards")]
+        start: Option<String>,
+        #[arg(long, value_name = "URL", requires = "start", help = "URL to poll until the started server is ready")]
+        wait_on: Option<String>,
+        #[arg(long, value_name = "SECONDS", requires = "start", help = "How long to wait for the started server (default: 120)")]
+        wait_timeout: Option<u64>,
+    },
     #[command(about = "Detect memory leaks")]
-    Memory,
+    Memory {
+        #[arg(long = "heap-snapshot", value_name = "FILE", num_args = 1..=2, help = "Analyze a V8 .heapsnapshot; pass two to compare object counts")]
+        heap_snapshot: Vec<PathBuf>,
+        #[command(flatten)]
+        target: TargetOptions,
+        #[command(flatten)]
+        listing: ListingOptions,
+    },
     #[command(about = "Analyze and split large components")]
     Components {
         #[arg(long, default_value_t = 100)]
         threshold: usize,
+        #[arg(long, value_name = "DIR", help = "Write each extractable part to this directory as a starting point")]
+        scaffold: Option<PathBuf>,
+        #[command(flatten)]
+        target: TargetOptions,
+        #[command(flatten)]
+        listing: ListingOptions,
+    },
+    #[command(about = "Detect copy-pasted code blocks")]
+    Duplicates {
+        #[arg(long, default_value_t = 10, help = "Report clones spanning at least this many lines")]
+        min_lines: usize,
+        #[arg(long, default_value_t = 50, help = "Minimum number of matching tokens for a clone")]
+        min_tokens: usize,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Scan source and config files for hard-coded secrets")]
+    Secrets {
+        #[arg(long, help = "Exit with an error when any secret is found (for CI)")]
+        fail_on_found: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Merge ESLint or Biome results into sniff's findings, from a JSON report or by running the linter")]
+    Lint {
+        #[arg(long, value_name = "REPORT", help = "ESLint `--format json` or Biome `--reporter=json` output to read instead of running the linter")]
+        ingest: Option<PathBuf>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Check dependencies against the npm registry: latest versions, deprecations and upgrade risk")]
+    Outdated {
+        #[arg(long, help = "Do not query the registry; use the lockfile, node_modules and data from earlier runs")]
+        no_network: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find known vulnerabilities in installed packages with OSV or npm/pnpm audit")]
+    Vulns {
+        #[arg(long, value_name = "REPORT", help = "`npm audit --json` or `pnpm audit --json` output to read instead of querying")]
+        ingest: Option<PathBuf>,
+        #[arg(long, conflicts_with = "ingest", help = "Run `npm audit` (`pnpm audit` in pnpm projects) instead of querying OSV")]
+        audit: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find console.log, debugger, alert() and TODO/FIXME markers outside tests")]
+    Console {
+        #[arg(long, help = "Exit with an error when anything is found (for CI)")]
+        fail_on_found: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Run the project's custom [[rules]] from sniff.toml, or list every rule id")]
+    Rules {
+        #[command(subcommand)]
+        action: Option<RulesAction>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Explain a rule: what it detects, why it matters and how to fix it")]
+    Explain {
+        #[arg(value_name = "RULE_ID", help = "A rule id such as SNF-MEM-TIMER (see `sniff rules list`) or a [[rules]] name")]
+        rule: String,
+    },
+    #[command(about = "Compare two --json reports of a command: introduced, resolved and persisting findings")]
+    Diff {
+        #[arg(value_name = "OLD", help = "Report of the base, e.g. from the main branch")]
+        old: PathBuf,
+        #[arg(value_name = "NEW", help = "Report of the change; only its new findings fail the run")]
+        new: PathBuf,
     },
     #[command(about = "Validate environment variables")]
-    Env,
+    Env {
+        #[arg(long, help = "Write or update .env.example from env files and variables read in code")]
+        init_example: bool,
+        #[arg(long, value_name = "NAME", help = "Environment to validate the [environment.schema] against (default: NODE_ENV or development)")]
+        environment: Option<String>,
+        #[arg(long, value_name = "FILE", num_args = 0.., conflicts_with_all = ["init_example", "environment"], help = "Compare env files (default: every .env* file but .env.example) for variables missing from or differing between them")]
+        compare: Option<Vec<String>>,
+        #[arg(long, conflicts_with_all = ["init_example", "compare"], help = "Check that .env* files are gitignored, never committed and hold no secrets when tracked")]
+        check_git: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
     #[command(about = "Analyze project structure and provide context")]
-    Context,
+    Context {
+        #[arg(long, value_name = "N", default_value_t = 4000, help = "Token budget for --format llm")]
+        max_tokens: usize,
+        #[arg(long, value_name = "PATH", help = "Write the import graph as JSON")]
+        graph_out: Option<PathBuf>,
+        #[arg(long, value_enum, help = "Print the import graph as a diagram instead of the report")]
+        graph: Option<context::GraphFormat>,
+        #[arg(long, value_name = "PATH", requires = "graph", help = "Write the diagram to a file")]
+        out: Option<PathBuf>,
+        #[arg(long, value_name = "DIR", requires = "graph", help = "Only draw files under this directory")]
+        scope: Option<String>,
+        #[arg(long, value_name = "FILE", requires = "graph", help = "Only draw files reachable from this entry point")]
+        entry: Option<String>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "List Next.js pages, API routes and middleware", after_help = "A listing without findings: --format github/junit/sarif/ndjson, --fail-on, --max-warnings, --summary and --top are rejected.")]
+    Routes {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Check JSX for common accessibility issues")]
+    A11y {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Scan for XSS, SQL injection, eval, open redirects and other insecure patterns")]
+    Security {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Audit Tailwind CSS usage against tailwind.config")]
+    Tailwind {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find oversized, unoptimized and legacy-format images")]
+    Images {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Check Dockerfiles: Node.js version against engines, .dockerignore, devDependencies and stages")]
+    Docker {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Check CI workflows: build, type-check, test and sniff steps, caching and Node.js versions")]
+    Ci {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Map 'use client' boundaries in the Next.js app router with their estimated JavaScript weight")]
+    Boundaries {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find Next.js data fetching anti-patterns: uncached fetch, needless getServerSideProps, client fetching of static data, missing revalidate")]
+    Nextjs {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find hardcoded UI text and missing or unused translation keys")]
+    I18n {
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find source files without tests and skipped tests")]
+    Tests {
+        #[arg(long, value_name = "LCOV_INFO", help = "Merge line coverage from an lcov.info file")]
+        lcov: Option<PathBuf>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Find functions with high cyclomatic or cognitive complexity")]
+    Complexity {
+        #[arg(long, help = "Report functions with a higher cyclomatic complexity (default: [complexity] cyclomatic)")]
+        cyclomatic: Option<usize>,
+        #[arg(long, help = "Report functions with a higher cognitive complexity (default: [complexity] cognitive)")]
+        cognitive: Option<usize>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Rewrite imports that other commands flag (type-only imports, import order)")]
+    Fix {
+        #[arg(long, help = "Use `import type` for imports only used as types, and drop it from those used as values")]
+        type_imports: bool,
+        #[arg(long, help = "Group and sort each file's imports per [imports.order], even when it is not enabled")]
+        organize_imports: bool,
+        #[arg(long, help = "Show the changes without writing them; exits with an error when there are any")]
+        dry_run: bool,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Run all pre-deployment checks")]
+    Deploy {
+        #[arg(long, value_name = "CHECK", value_delimiter = ',', value_parser = clap::builder::PossibleValuesParser::new(config::DEPLOY_CHECKS), help = "Also run checks that are off, as warnings, e.g. memory,security,secrets")]
+        with: Vec<String>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Score project health across several analyses")]
+    Report {
+        #[arg(long, value_name = "PATH", help = "Also write the report to a file (.html for a web page, otherwise JSON)")]
+        output: Option<PathBuf>,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+    #[command(about = "Show how metrics changed across runs")]
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+    #[command(about = "Set up sniff for this project: a tailored sniff.toml, .gitignore, git hooks and a CI workflow")]
+    Init {
+        #[arg(long, short = 'y', help = "Accept every default without asking, including hooks and the workflow")]
+        yes: bool,
+    },
+    #[command(about = "Check the tools, files and config the analyzers depend on")]
+    Doctor,
     #[command(about = "Configuration management")]
     Config {
         #[command(subcommand)]
         action: ConfigAction,
     },
+    #[command(about = "Install git pre-commit and pre-push hooks")]
+    Hooks {
+        #[command(subcommand)]
+        action: HooksAction,
+    },
+    #[command(about = "Run a language server that shows large, imports, memory and types findings in the editor")]
+    Lsp,
+    #[command(about = "Serve analysis results over a local HTTP API with a warm cache")]
+    Serve {
+        #[arg(long, default_value_t = serve::DEFAULT_PORT, help = "Port to listen on (0 picks a free one)")]
+        port: u16,
+        #[command(flatten)]
+        target: TargetOptions,
+    },
+}
+
+#[derive(Subcommand)]
+enum HooksAction {
+    #[command(about = "Add the sniff hooks (works alongside husky and lefthook)")]
+    Install,
+    #[command(about = "Remove the sniff hooks, keeping any other hook commands")]
+    Uninstall,
+    #[command(about = "Run the commands configured for a hook in [hooks]")]
+    Run {
+        #[arg(value_enum)]
+        hook: hooks::GitHook,
+    },
+}
+
+#[derive(Subcommand)]
+enum RulesAction {
+    #[command(about = "List every rule with its id, default severity and the command that reports it")]
+    List {
+        #[arg(long, value_name = "COMMAND", help = "Only rules reported by this command (large, memory, a11y, ...)")]
+        command: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    #[command(about = "Print trends and regressions from the recorded runs")]
+    Show {
+        #[arg(long, value_name = "WHEN", help = "Only runs since a duration ago (7d, 12h, 2w) or a date (2024-05-01)")]
+        since: Option<String>,
+        #[arg(long, help = "Draw a sparkline for each metric")]
+        chart: bool,
+        #[arg(long, value_name = "DIR", help = "Project root (default: current directory)")]
+        root: Option<PathBuf>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -71,6 +422,8 @@ enum ConfigAction {
     Show,
     #[command(about = "Validate configuration file")]
     Validate,
+    #[command(about = "Check the configuration, suggest fixes and show the effective settings")]
+    Doctor,
     #[command(about = "Show configuration for specific command")]
     Get {
         #[arg(help = "Command name (large, types, imports, etc.)")]
@@ -82,35 +435,490 @@ enum ConfigAction {
 async fn main() {
     let cli = Cli::parse();
     
-    let result = match cli.command {
-        Some(Commands::Menu) | None => menu::run().await,
-        Some(Commands::Large { threshold }) => large::run(threshold, cli.json, cli.quiet).await,
-        Some(Commands::Types) => types::run(cli.json, cli.quiet).await,
-        Some(Commands::Imports) => imports::run(cli.json, cli.quiet).await,
-        Some(Commands::Bundle) => bundle::run(cli.json, cli.quiet).await,
-        Some(Commands::Perf) => perf::run(cli.json, cli.quiet).await,
-        Some(Commands::Memory) => memory::run(cli.json, cli.quiet).await,
-        Some(Commands::Components { threshold }) => components::run(threshold, cli.json, cli.quiet).await,
-        Some(Commands::Env) => env::run(cli.json, cli.quiet).await,
-        Some(Commands::Context) => context::run(cli.json, cli.quiet).await,
-        Some(Commands::Config { action }) => handle_config_command(action).await,
+    // Commands only report their status; the process exit code is decided here
+    match run_command(cli).await {
+        Ok(exit_code) if exit_code.is_success() => {}
+        Ok(exit_code) => exit_with(exit_code),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            exit_with(ExitCode::GeneralError);
+        }
+    }
+}
+
+async fn run_command(cli: Cli) -> anyhow::Result<ExitCode> {
+    common::report_formatter::init_color(cli.no_color);
+    common::report_formatter::init_ascii(cli.ascii);
+    common::paths::init(cli.absolute_paths);
+    common::logging::init(cli.verbose, cli.quiet, cli.log_format, cli.no_color)?;
+    
+    // --json and --format win over SNIFF_OUTPUT
+    let format = match (cli.json, cli.format) {
+        (true, _) => OutputFormat::Json,
+        (false, Some(format)) => format,
+        (false, None) => match std::env::var("SNIFF_OUTPUT") {
+            Ok(value) => OutputFormat::from_str(&value, true).map_err(|_| {
+                let names: Vec<String> = OutputFormat::value_variants().iter()
+                    .filter_map(|format| Some(format.to_possible_value()?.get_name().to_string()))
+                    .collect();
+                anyhow::anyhow!("SNIFF_OUTPUT must be one of {}; got '{}'", names.join(", "), value)
+            })?,
+            Err(_) => OutputFormat::Text,
+        },
+    };
+    let (json, quiet) = (format == OutputFormat::Json, cli.quiet);
+    let detail = match (cli.summary, cli.top) {
+        (true, _) => Some(Detail::Summary),
+        (false, Some(shown)) => Some(Detail::Top(shown)),
+        (false, None) => None,
+    };
+    if detail.is_some() && format != OutputFormat::Text {
+        return Err(anyhow::anyhow!("--summary and --top shorten the text report and cannot be used with another format"));
+    }
+    // github, junit and sarif list findings from the silent `check` instead of printing the report,
+    // as do --summary and --top, which condense them
+    let findings = format.lists_findings() || detail.is_some();
+    let policy = FailPolicy { fail_on: cli.fail_on, max_warnings: cli.max_warnings };
+    if format == OutputFormat::Llm && !matches!(cli.command, Some(Commands::Context { .. })) {
+        return Err(anyhow::anyhow!("--format llm is supported by `sniff context`"));
+    }
+    if (findings || !policy.is_default()) && !has_findings(&cli.command) {
+        let option = match format {
+            OutputFormat::Junit => "--format junit",
+            OutputFormat::Github => "--format github",
+            OutputFormat::Sarif => "--format sarif",
+            OutputFormat::Ndjson => "--format ndjson",
+            _ if cli.summary => "--summary",
+            _ if cli.top.is_some() => "--top",
+            _ if cli.fail_on.is_some() => "--fail-on",
+            _ => "--max-warnings",
+        };
+        return Err(anyhow::anyhow!(
+            "{} needs a command that reports findings; `sniff routes`, `perf`, `context`, `fix`, `report` and the setup commands have none",
+            option
+        ));
+    }
+    if let Some(path) = cli.config {
+        if !path.is_file() {
+            return Err(anyhow::anyhow!("Configuration file not found: {}", path.display()));
+        }
+        Config::use_config_file(path);
+    }
+    let started = Instant::now();
+    
+    let exit_code = match cli.command {
+        Some(Commands::Menu) | None => {
+            menu::run().await?;
+            ExitCode::Success
+        }
+        Some(Commands::Large { threshold, function_threshold, include_generated, target, .. }) if format == OutputFormat::Ndjson => {
+            let target = target.resolve()?;
+            let config = target.config()?;
+            streamed(policy, |counts| large::stream(&target, threshold, function_threshold, include_generated, &config, counts))?
+        }
+        Some(Commands::Large { threshold, function_threshold, include_generated, target, .. }) if findings => {
+            let target = target.resolve()?;
+            let config = target.config()?;
+            print_findings("large", format, detail, recorded(&target, "large", large::check(&target, threshold, function_threshold, include_generated, &config)?), policy, started)?
+        }
+        Some(Commands::Large { threshold, function_threshold, by_dir, min_dir_lines, include_generated, target, listing }) => {
+            let target = target.resolve()?;
+            let view = large::LargeView { listing, by_dir, min_dir_lines };
+            finish(recorded(&target, "large", large::run(&target, threshold, function_threshold, include_generated, view, json, quiet).await?), policy)
+        }
+        Some(Commands::Types { tsc, list_any, target }) => {
+            let target = target.resolve()?;
+            let options = types::TypesOptions { tsc, list_any };
+            if format == OutputFormat::Ndjson {
+                streamed(policy, |counts| types::stream(&target, options, counts))?
+            } else if findings {
+                print_findings("types", format, detail, recorded(&target, "types", types::check(&target, options, true)?), policy, started)?
+            } else {
+                finish(recorded(&target, "types", types::run(&target, options, json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Imports { staged, unused_exports, target, listing }) => {
+            let target = target.resolve()?;
+            let target = if staged { target.staged()? } else { target };
+            if format == OutputFormat::Ndjson {
+                streamed(policy, |counts| imports::stream(&target, unused_exports, counts))?
+            } else if findings {
+                print_findings("imports", format, detail, recorded(&target, "imports", imports::check(&target, unused_exports, true)?), policy, started)?
+            } else {
+                finish(recorded(&target, "imports", imports::run(&target, unused_exports, listing, json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Bundle { compare, save, stats, target }) => {
+            let target = target.resolve()?;
+            let options = bundle::BundleOptions { compare, save, stats };
+            if findings {
+                print_findings("bundle", format, detail, recorded(&target, "bundle", bundle::check(&target, &options).await?), policy, started)?
+            } else {
+                finish(recorded(&target, "bundle", bundle::run(&target, &options, json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Perf { url, runs, categories, mobile, desktop, start, wait_on, wait_timeout }) => {
+            let preset = match (mobile, desktop) {
+                (true, _) => Some(LighthousePreset::Mobile),
+                (_, true) => Some(LighthousePreset::Desktop),
+                _ => None,
+            };
+            let options = perf::PerfOptions { url, runs, categories, preset, start, wait_on, wait_timeout_secs: wait_timeout };
+            perf::run(&options, json, quiet).await?.into()
+        }
+        Some(Commands::Memory { heap_snapshot, target, listing }) => {
+            let options = memory::MemoryOptions { heap_snapshots: heap_snapshot };
+            if findings {
+                print_findings("memory", format, detail, memory::check(&target.resolve()?, &options).await?, policy, started)?
+            } else {
+                finish(memory::run(&target.resolve()?, &options, listing, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Components { threshold, scaffold, target, listing }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("components", format, detail, components::check(&target, threshold, &config)?, policy, started)?
+            } else {
+                finish(components::run(&target, threshold, scaffold.as_deref(), listing, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Duplicates { min_lines, min_tokens, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                print_findings("duplicates", format, detail, duplicates::check(&target, min_lines, min_tokens, true)?, policy, started)?
+            } else {
+                finish(duplicates::run(&target, min_lines, min_tokens, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Secrets { fail_on_found, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("secrets", format, detail, secrets::check(&target, fail_on_found, &config, true)?, policy, started)?
+            } else {
+                finish(secrets::run(&target, fail_on_found, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Lint { ingest, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("lint", format, detail, recorded(&target, "lint", lint::check(&target, ingest.as_deref(), &config)?), policy, started)?
+            } else {
+                finish(recorded(&target, "lint", lint::run(&target, ingest.as_deref(), json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Outdated { no_network, target }) => {
+            let target = target.resolve()?;
+            let options = outdated::OutdatedOptions { no_network };
+            if findings {
+                let config = target.config()?;
+                print_findings("outdated", format, detail, recorded(&target, "outdated", outdated::check(&target, &options, &config)?), policy, started)?
+            } else {
+                finish(recorded(&target, "outdated", outdated::run(&target, &options, json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Vulns { ingest, audit, target }) => {
+            let target = target.resolve()?;
+            let options = vulns::VulnsOptions { ingest, audit };
+            if findings {
+                let config = target.config()?;
+                print_findings("vulns", format, detail, recorded(&target, "vulns", vulns::check(&target, &options, &config)?), policy, started)?
+            } else {
+                finish(recorded(&target, "vulns", vulns::run(&target, &options, json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Console { fail_on_found, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("console", format, detail, console::check(&target, fail_on_found, &config, true)?, policy, started)?
+            } else {
+                finish(console::run(&target, fail_on_found, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Rules { action: Some(RulesAction::List { command }), target }) => {
+            rules::list(&target.resolve()?, command.as_deref(), json, quiet).await?.into()
+        }
+        Some(Commands::Explain { rule }) => {
+            explain::run(&std::env::current_dir()?, &rule, json, quiet).await?.into()
+        }
+        Some(Commands::Diff { old, new }) => {
+            if findings {
+                print_findings("diff", format, detail, diff::check(&old, &new)?, policy, started)?
+            } else {
+                finish(diff::run(&old, &new, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Rules { action: None, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("rules", format, detail, rules::check(&target, &config, true)?, policy, started)?
+            } else {
+                finish(rules::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Env { init_example: true, target, .. }) => env::init_example(&target.resolve()?, json, quiet).await?.into(),
+        Some(Commands::Env { compare: Some(files), target, .. }) if findings => {
+            print_findings("env", format, detail, env::check_compare(&target.resolve()?, &files)?, policy, started)?
+        }
+        Some(Commands::Env { compare: Some(files), target, .. }) => finish(env::compare(&target.resolve()?, &files, json, quiet).await?, policy),
+        Some(Commands::Env { init_example: false, environment, check_git, target, .. }) if findings => {
+            print_findings("env", format, detail, env::check(&target.resolve()?, environment.as_deref(), check_git).await?, policy, started)?
+        }
+        Some(Commands::Env { init_example: false, environment, check_git, target, .. }) => finish(env::run(&target.resolve()?, environment.as_deref(), check_git, json, quiet).await?, policy),
+        Some(Commands::Context { graph: Some(graph), out, scope, entry, graph_out, target, .. }) => {
+            let target = target.resolve()?;
+            let outcome = recorded(&target, "context", context::check(&target).await?);
+            if let Some(path) = graph_out {
+                context::write_graph(&outcome.report, &path)?;
+            }
+            let scope = context::GraphScope { dir: scope, entry };
+            context::export_graph(&outcome.report, graph, &scope, out.as_deref(), quiet)?;
+            outcome.into()
+        }
+        Some(Commands::Context { max_tokens, graph_out, target, .. }) if format == OutputFormat::Llm => {
+            let target = target.resolve()?;
+            let outcome = recorded(&target, "context", context::check(&target).await?);
+            if let Some(path) = graph_out {
+                context::write_graph(&outcome.report, &path)?;
+            }
+            print!("{}", context::llm::render(&outcome.report, max_tokens));
+            outcome.into()
+        }
+        Some(Commands::Context { graph_out, target, .. }) => {
+            let target = target.resolve()?;
+            recorded(&target, "context", context::run(&target, graph_out.as_deref(), json, quiet).await?).into()
+        }
+        Some(Commands::Routes { target }) => routes::run(&target.resolve()?, json, quiet).await?.into(),
+        Some(Commands::A11y { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("a11y", format, detail, a11y::check(&target, &config, true)?, policy, started)?
+            } else {
+                finish(a11y::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Security { target }) if findings => {
+            print_findings("security", format, detail, security::check(&target.resolve()?, true)?, policy, started)?
+        }
+        Some(Commands::Security { target }) => finish(security::run(&target.resolve()?, json, quiet).await?, policy),
+        Some(Commands::Tailwind { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("tailwind", format, detail, tailwind::check(&target, &config.tailwind, true)?, policy, started)?
+            } else {
+                finish(tailwind::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::I18n { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("i18n", format, detail, i18n::check(&target, &config.i18n, true)?, policy, started)?
+            } else {
+                finish(i18n::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Complexity { cyclomatic, cognitive, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("complexity", format, detail, complexity::check(&target, &config.complexity, cyclomatic, cognitive, true)?, policy, started)?
+            } else {
+                finish(complexity::run(&target, cyclomatic, cognitive, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Tests { lcov, target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("tests", format, detail, test_audit::check(&target, &config.tests, lcov.as_deref(), true)?, policy, started)?
+            } else {
+                finish(test_audit::run(&target, lcov.as_deref(), json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Images { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("images", format, detail, images::check(&target, &config.images, true)?, policy, started)?
+            } else {
+                finish(images::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Docker { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("docker", format, detail, docker::check(&target, &config.docker)?, policy, started)?
+            } else {
+                finish(docker::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Ci { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("ci", format, detail, ci::check(&target, &config.ci)?, policy, started)?
+            } else {
+                finish(ci::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Boundaries { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                let config = target.config()?;
+                print_findings("boundaries", format, detail, recorded(&target, "boundaries", boundaries::check(&target, &config.boundaries)?), policy, started)?
+            } else {
+                finish(recorded(&target, "boundaries", boundaries::run(&target, json, quiet).await?), policy)
+            }
+        }
+        Some(Commands::Nextjs { target }) => {
+            let target = target.resolve()?;
+            if findings {
+                print_findings("nextjs", format, detail, nextjs::check(&target)?, policy, started)?
+            } else {
+                finish(nextjs::run(&target, json, quiet).await?, policy)
+            }
+        }
+        Some(Commands::Fix { type_imports, organize_imports, dry_run, target }) => {
+            let options = fix::FixOptions { type_imports, organize_imports, dry_run };
+            fix::run(&target.resolve()?, options, json, quiet).await?.into()
+        }
+        Some(Commands::Deploy { with, target }) if findings => {
+            let outcome = deploy::check(&target.resolve()?, &with, |_| {}).await?;
+            match (format, detail) {
+                (_, Some(detail)) => print_condensed("deploy", &outcome.report.findings(), detail, started.elapsed().as_millis() as u64),
+                (OutputFormat::Junit, None) => println!("{}", junit::render(&deploy::junit_suites(&outcome.report))),
+                (OutputFormat::Sarif, None) => println!("{}", sarif::render("deploy", &outcome.report.findings())),
+                (OutputFormat::Ndjson, None) => print_ndjson(&outcome.report.findings())?,
+                _ => print_annotations(&outcome.report),
+            }
+            finish(outcome, policy)
+        }
+        Some(Commands::Deploy { with, target }) => finish(deploy::run(&target.resolve()?, &with, json, quiet).await?, policy),
+        Some(Commands::Report { output, target }) => {
+            let target = target.resolve()?;
+            recorded(&target, "report", report::run(&target, output.as_deref(), json, quiet).await?).into()
+        }
+        Some(Commands::History { action: HistoryAction::Show { since, chart, root } }) => {
+            let root = std::env::current_dir()?.join(root.unwrap_or_default());
+            history::show(&root, since.as_deref(), chart, json, quiet).await?.into()
+        }
+        Some(Commands::Init { yes }) => {
+            init::run(&std::env::current_dir()?, yes, json, quiet).await?.into()
+        }
+        Some(Commands::Doctor) => {
+            doctor::run(&std::env::current_dir()?, json, quiet).await?.into()
+        }
+        Some(Commands::Config { action }) => {
+            handle_config_command(action, json).await?;
+            ExitCode::Success
+        }
+        Some(Commands::Hooks { action }) => {
+            let root = std::env::current_dir()?;
+            match action {
+                HooksAction::Install => hooks::install(&root, json, quiet).await?.into(),
+                HooksAction::Uninstall => hooks::uninstall(&root, json, quiet).await?.into(),
+                HooksAction::Run { hook } => hooks::run(&root, hook, json, quiet).await?.into(),
+            }
+        }
+        Some(Commands::Lsp) => {
+            tokio::task::spawn_blocking(lsp::run).await??;
+            ExitCode::Success
+        }
+        Some(Commands::Serve { port, target }) => {
+            serve::run(target.resolve()?, port, quiet).await?;
+            ExitCode::Success
+        }
     };
     
-    if let Err(e) = result {
-        eprintln!("Error: {}", e);
-        process::exit(1);
+    Ok(exit_code)
+}
+
+/// Append the run's metrics to the project history; failing to record never fails the run
+fn recorded<R: Metrics>(target: &ScanTarget, command: &str, outcome: CommandOutcome<R>) -> CommandOutcome<R> {
+    if let Err(e) = history::record(target, command, &outcome.report) {
+        tracing::warn!("Could not record history: {}", e);
+    }
+    outcome
+}
+
+/// Commands whose reports implement `Annotate`, so their findings can be listed and classified
+fn has_findings(command: &Option<Commands>) -> bool {
+    matches!(
+        command,
+        Some(Commands::Large { .. } | Commands::Types { .. } | Commands::Imports { .. } | Commands::Bundle { .. }
+            | Commands::Memory { .. } | Commands::Components { .. } | Commands::Duplicates { .. } | Commands::Secrets { .. } | Commands::Env { init_example: false, .. } | Commands::A11y { .. } | Commands::Console { .. } | Commands::Lint { .. } | Commands::Outdated { .. } | Commands::Vulns { .. }
+            | Commands::Rules { action: None, .. } | Commands::Security { .. } | Commands::Images { .. } | Commands::Docker { .. } | Commands::Ci { .. } | Commands::Boundaries { .. } | Commands::Nextjs { .. } | Commands::Complexity { .. } | Commands::Tailwind { .. } | Commands::I18n { .. } | Commands::Tests { .. } | Commands::Diff { .. } | Commands::Deploy { .. })
+    )
+}
+
+/// The exit code for a command's findings under `--fail-on`/`--max-warnings`
+fn finish<R: Annotate>(outcome: CommandOutcome<R>, policy: FailPolicy) -> ExitCode {
+    if policy.is_default() {
+        return outcome.exit_code;
+    }
+    policy.exit_code(&outcome.report.annotations(), outcome.exit_code)
+}
+
+/// `--format ndjson` for `large`, `types` and `imports`, which print each file's findings as
+/// soon as it is analyzed and keep only their counts
+fn streamed(policy: FailPolicy, stream: impl FnOnce(&Mutex<LevelCounts>) -> anyhow::Result<ExitCode>) -> anyhow::Result<ExitCode> {
+    let counts = Mutex::new(LevelCounts::default());
+    let command_code = match stream(&counts) {
+        Ok(exit_code) => exit_code,
+        // The reader stopped early (`| head`) and has what it wanted
+        Err(e) if ndjson::is_closed_pipe(&e) => ExitCode::Success,
+        Err(e) => return Err(e),
+    };
+    let counts = counts.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+    Ok(policy.exit_code_for_counts(counts, command_code))
+}
+
+/// `--format ndjson` for commands that report once the whole analysis is done
+fn print_ndjson(findings: &[Annotation]) -> anyhow::Result<()> {
+    match ndjson::write_lines(&mut std::io::stdout().lock(), findings) {
+        Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+/// `--format github` / `junit` / `sarif` / `ndjson`: the findings instead of the report;
+/// `--summary` / `--top`: the condensed report
+fn print_findings<R: Annotate>(command: &str, format: OutputFormat, detail: Option<Detail>, outcome: CommandOutcome<R>, policy: FailPolicy, started: Instant) -> anyhow::Result<ExitCode> {
+    if let Some(detail) = detail {
+        print_condensed(command, &outcome.report.findings(), detail, started.elapsed().as_millis() as u64);
+        return Ok(finish(outcome, policy));
+    }
+    match format {
+        OutputFormat::Junit => {
+            let duration_ms = started.elapsed().as_millis() as u64;
+            let suite = TestSuite::from_findings(command, outcome.exit_code, outcome.report.findings(), duration_ms);
+            println!("{}", junit::render(&[suite]));
+        }
+        OutputFormat::Sarif => println!("{}", sarif::render(command, &outcome.report.findings())),
+        OutputFormat::Ndjson => print_ndjson(&outcome.report.findings())?,
+        _ => print_annotations(&outcome.report),
     }
+    Ok(finish(outcome, policy))
 }
 
-async fn handle_config_command(action: ConfigAction) -> anyhow::Result<()> {
+async fn handle_config_command(action: ConfigAction, json: bool) -> anyhow::Result<()> {
     match action {
         ConfigAction::Init => ConfigUtils::init(),
         ConfigAction::Show => ConfigUtils::show(),
-        ConfigAction::Validate => ConfigUtils::validate(),
+        ConfigAction::Validate => ConfigUtils::validate(json),
+        ConfigAction::Doctor => ConfigUtils::doctor(json),
         ConfigAction::Get { command } => {
             let config = ConfigUtils::get_command_config(&command)?;
-            println!("Configuration for '{}':", command);
-            println!("{}", config);
+            output::line(format!("Configuration for '{}':", command));
+            output::line(config);
             Ok(())
         }
     }
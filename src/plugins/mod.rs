@@ -0,0 +1,175 @@
+/// WebAssembly rule plugins from `[[plugins]]`, run by `sniff rules` next to the config rules.
+///
+/// A plugin is a module without imports, so it can read nothing but the file it is given
+/// and cannot touch the disk or the network. It exports:
+/// - `memory`
+/// - `sniff_alloc(len: i32) -> i32`: room for an input of `len` bytes
+/// - `sniff_check(ptr: i32, len: i32) -> i64`: checks the input and returns where its
+///   output is, the pointer in the high 32 bits and the length in the low 32 bits
+///
+/// The input is the JSON `{"path": "src/app.ts", "content": "..."}` and the output a JSON
+/// array of `{"rule", "line", "column"?, "severity"?, "message", "suggestion"?}`. Every file
+/// gets a fresh instance with a fuel and memory budget, so a plugin that loops or allocates
+/// without end fails the run instead of hanging it
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use wasmtime::{Engine, Instance, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use crate::config::PluginConfig;
+
+/// Exports every plugin needs
+const EXPORTS: &[&str] = &["memory", "sniff_alloc", "sniff_check"];
+/// Instructions a plugin may run per file
+const FUEL_PER_FILE: u64 = 2_000_000_000;
+/// Linear memory a plugin may grow to, in bytes
+const MEMORY_LIMIT: usize = 256 << 20;
+
+/// A compiled `[[plugins]]` module
+pub struct WasmPlugin {
+    /// The module's file stem, which prefixes its rule names
+    pub name: String,
+    pub files: Vec<String>,
+    pub exclude: Vec<String>,
+    engine: Engine,
+    module: Module,
+}
+
+#[derive(Serialize)]
+struct PluginInput<'a> {
+    path: &'a str,
+    content: &'a str,
+}
+
+/// A finding as a plugin returns it
+#[derive(Debug, Deserialize)]
+pub struct PluginFinding {
+    pub rule: String,
+    /// 1-based
+    pub line: usize,
+    /// 1-based, in characters
+    #[serde(default = "first_column")]
+    pub column: usize,
+    /// Like a custom rule's severity; medium when left out
+    #[serde(default)]
+    pub severity: Option<String>,
+    pub message: String,
+    #[serde(default)]
+    pub suggestion: Option<String>,
+}
+
+fn first_column() -> usize {
+    1
+}
+
+/// Compile the modules of the `[[plugins]]` entries, with paths relative to `root`
+pub fn load_plugins(configs: &[PluginConfig], root: &Path) -> Result<Vec<WasmPlugin>> {
+    if configs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut settings = wasmtime::Config::new();
+    settings.consume_fuel(true);
+    let engine = Engine::new(&settings)?;
+    configs.iter().map(|config| WasmPlugin::load(&engine, config, root)).collect()
+}
+
+impl WasmPlugin {
+    fn load(engine: &Engine, config: &PluginConfig, root: &Path) -> Result<Self> {
+        let path = root.join(&config.path);
+        let module = Module::from_file(engine, &path)
+            .with_context(|| format!("Failed to load plugin {}", path.display()))?;
+        if let Some(import) = module.imports().next() {
+            anyhow::bail!(
+                "Plugin {} imports `{}.{}`; plugins run without host access and may not import anything",
+                config.path, import.module(), import.name()
+            );
+        }
+        let missing: Vec<&str> = EXPORTS.iter().copied()
+            .filter(|name| module.get_export(name).is_none())
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Plugin {} does not export {}", config.path, missing.join(", "));
+        }
+
+        let name = path.file_stem().map_or_else(|| config.path.clone(), |stem| stem.to_string_lossy().into_owned());
+        Ok(WasmPlugin {
+            name,
+            files: config.files.clone(),
+            exclude: config.exclude.clone(),
+            engine: engine.clone(),
+            module,
+        })
+    }
+
+    /// The findings of the plugin for one file, `path` being relative to the project root
+    pub fn check(&self, path: &str, content: &str) -> Result<Vec<PluginFinding>> {
+        self.run(path, content)
+            .with_context(|| format!("Plugin {} failed on {}", self.name, path))
+    }
+
+    fn run(&self, path: &str, content: &str) -> Result<Vec<PluginFinding>> {
+        let limits = StoreLimitsBuilder::new().memory_size(MEMORY_LIMIT).build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(FUEL_PER_FILE)?;
+
+        let instance = Instance::new(&mut store, &self.module, &[])?;
+        let memory = instance.get_memory(&mut store, "memory").context("`memory` is not a memory")?;
+        let alloc = instance.get_typed_func::<i32, i32>(&mut store, "sniff_alloc")?;
+        let check = instance.get_typed_func::<(i32, i32), i64>(&mut store, "sniff_check")?;
+
+        let input = serde_json::to_vec(&PluginInput { path, content })?;
+        let len = i32::try_from(input.len()).context("file too large for a plugin")?;
+        let ptr = alloc.call(&mut store, len)?;
+        memory.write(&mut store, ptr as u32 as usize, &input)?;
+
+        // The output is read in place: its length comes from the plugin and is only trusted
+        // once it fits in the plugin's memory
+        let packed = check.call(&mut store, (ptr, len))? as u64;
+        let (start, size) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        let data = memory.data(&store);
+        let output = start.checked_add(size)
+            .and_then(|end| data.get(start..end))
+            .with_context(|| format!("output at {}..+{} is outside the plugin's {} bytes of memory", start, size, data.len()))?;
+        serde_json::from_slice(output).context("output is not a JSON array of findings")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plugin written in the text format, flagging line 1 of every file
+    const FLAG_FIRST_LINE: &str = r#"(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "[{\"rule\":\"first-line\",\"line\":1,\"message\":\"flagged\"}]")
+  (func (export "sniff_alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "sniff_check") (param i32 i32) (result i64) i64.const 52))"#;
+
+    fn plugin(dir: &Path, name: &str, source: &str) -> Result<WasmPlugin> {
+        std::fs::write(dir.join(name), source)?;
+        let config = PluginConfig { path: name.to_string(), ..PluginConfig::default() };
+        Ok(load_plugins(&[config], dir)?.remove(0))
+    }
+
+    #[test]
+    fn test_plugins_return_findings_and_are_sandboxed() {
+        let dir = tempfile::tempdir().unwrap();
+        let flag = plugin(dir.path(), "flag.wat", FLAG_FIRST_LINE).unwrap();
+        let findings = flag.check("src/app.ts", "export const x = 1;\n").unwrap();
+        assert_eq!(flag.name, "flag");
+        assert_eq!((findings[0].rule.as_str(), findings[0].line, findings[0].column), ("first-line", 1, 1));
+
+        let spin = FLAG_FIRST_LINE.replace("i64.const 52", "(loop (br 0)) i64.const 52");
+        let error = plugin(dir.path(), "spin.wat", &spin).unwrap().check("src/app.ts", "").unwrap_err();
+        assert!(format!("{:#}", error).contains("fuel"), "{:#}", error);
+
+        let oversized = FLAG_FIRST_LINE.replace("i64.const 52", "i64.const 0xffffffff");
+        let error = plugin(dir.path(), "oversized.wat", &oversized).unwrap().check("src/app.ts", "").unwrap_err();
+        assert!(format!("{:#}", error).contains("outside the plugin's 65536 bytes of memory"), "{:#}", error);
+
+        let wasi = FLAG_FIRST_LINE.replace("(module", r#"(module (import "wasi_snapshot_preview1" "fd_write" (func))"#);
+        let error = plugin(dir.path(), "wasi.wat", &wasi).err().unwrap();
+        assert!(error.to_string().contains("may not import"), "{}", error);
+    }
+}
@@ -7,6 +7,7 @@ use walkdir::WalkDir;
 use rayon::prelude::*;
 use indicatif::{ProgressBar, ProgressStyle};
 use crate::config::Config;
+use crate::common::progress::ascii_safe;
 
 /// File utilities
 pub struct FileUtils;
@@ -14,26 +15,26 @@ pub struct FileUtils;
 impl FileUtils {
     
     /// Find files with specific extensions (optimized with parallel processing)
-    pub fn find_files_with_extensions(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
-        let config = Config::load().unwrap_or_default();
+    pub fn find_files_with_extensions(dir: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>> {
+        let config = Config::load()?;
         
-        WalkDir::new(dir)
+        Ok(WalkDir::new(dir)
             .into_iter()
             .filter_map(|e| e.ok())
             .filter(|e| e.file_type().is_file())
             .filter(|e| Self::has_extension(e.path(), extensions))
             .filter(|e| !Self::is_excluded_path_with_config(e.path(), &config))
             .map(|e| e.path().to_path_buf())
-            .collect()
+            .collect())
     }
     
     /// Find files with extensions and show progress
     pub fn find_files_with_progress(dir: &Path, extensions: &[&str], quiet: bool) -> Result<Vec<PathBuf>> {
         let pb = if !quiet {
             let pb = ProgressBar::new_spinner();
-            pb.set_style(ProgressStyle::default_spinner()
+            pb.set_style(ascii_safe(ProgressStyle::default_spinner()
                 .template("{spinner:.green} {msg}")
-                .unwrap());
+                .unwrap()));
             pb.set_message("Scanning files...");
             pb.enable_steady_tick(std::time::Duration::from_millis(80));
             Some(pb)
@@ -43,7 +44,7 @@ impl FileUtils {
         
         // Add minimum display time for spinner visibility
         let start_time = std::time::Instant::now();
-        let files = Self::find_files_with_extensions(dir, extensions);
+        let files = Self::find_files_with_extensions(dir, extensions)?;
         
         // Ensure spinner shows for at least 200ms for visibility
         if let Some(_pb) = &pb {
@@ -82,17 +83,7 @@ impl FileUtils {
     
     /// Count lines in a file with memory mapping for large files
     pub fn count_lines_optimized(path: &Path) -> Result<usize> {
-        let file = fs::File::open(path)?;
-        let metadata = file.metadata()?;
-        
-        // Use memory mapping for files larger than 1MB
-        if metadata.len() > 1_048_576 {
-            let mmap = unsafe { memmap2::Mmap::map(&file)? };
-            Ok(mmap.iter().filter(|&&b| b == b'\n').count())
-        } else {
-            let content = fs::read_to_string(path)?;
-            Ok(content.lines().count())
-        }
+        Ok(crate::common::count_lines_optimized(path)?)
     }
     
     /// Process files in parallel with progress tracking
@@ -108,10 +99,10 @@ impl FileUtils {
     {
         let pb = if !quiet {
             let pb = ProgressBar::new(files.len() as u64);
-            pb.set_style(ProgressStyle::default_bar()
+            pb.set_style(ascii_safe(ProgressStyle::default_bar()
                 .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} {msg}")
                 .unwrap()
-                .progress_chars("#>-"));
+                .progress_chars("#>-")));
             pb.set_message(description.to_string());
             Some(pb)
         } else {
@@ -145,6 +136,30 @@ impl FileUtils {
         }
         path.to_string_lossy().to_string()
     }
+
+    /// `/`-separated path, so reports read the same on every platform
+    pub fn slash_path(path: &Path) -> String {
+        path.components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Unit and end-to-end test files; `path` is relative to the project root
+    pub fn is_test_file(path: &Path) -> bool {
+        let path = Self::slash_path(path);
+        let file_name = path.rsplit('/').next().unwrap_or(&path);
+        file_name.contains(".test.") || file_name.contains(".spec.")
+            || ["__tests__", "__mocks__", "test", "tests", "e2e", "cypress"].iter()
+                .any(|dir| path.contains(&format!("/{}/", dir)) || path.starts_with(&format!("{}/", dir)))
+    }
+
+    /// Whether `package.json` in `root` lists `name` in its dependencies or devDependencies
+    pub fn has_dependency(root: &Path, name: &str) -> bool {
+        let Ok(content) = fs::read_to_string(root.join("package.json")) else { return false };
+        let Ok(package) = serde_json::from_str::<serde_json::Value>(&content) else { return false };
+        ["dependencies", "devDependencies"].iter().any(|section| package[section].get(name).is_some())
+    }
 }
 
 
@@ -171,4 +186,14 @@ mod tests {
         // Should return some path representation
         assert!(!relative.is_empty());
     }
+    
+    #[test]
+    fn test_is_test_file() {
+        use std::path::Path;
+        
+        assert!(FileUtils::is_test_file(Path::new("src/utils/date.test.ts")));
+        assert!(FileUtils::is_test_file(Path::new("src/__tests__/date.ts")));
+        assert!(FileUtils::is_test_file(Path::new("e2e/login.ts")));
+        assert!(!FileUtils::is_test_file(Path::new("src/latest/feed.ts")));
+    }
 }
\ No newline at end of file
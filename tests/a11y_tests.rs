@@ -0,0 +1,64 @@
+/// Integration tests for the a11y command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use sniff_check::config::{A11yRuleLevel, Config};
+
+const FORM: &str = r#"export function Signup() {
+  return (
+    <form>
+      <input type="email" />
+      <img src="/hero.png" />
+      <div onClick={() => track()} tabIndex={2}>More</div>
+      <label>Name <input type="text" /></label>
+    </form>
+  );
+}
+"#;
+
+#[test]
+fn test_a11y_command_reports_issues_by_rule() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("components/Signup.tsx", FORM)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "a11y"])?;
+    // High-severity issues (missing alt, missing label) fail the check
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let issues = json["data"]["issues"].as_array().unwrap();
+    let rules: Vec<&str> = issues.iter().map(|issue| issue["rule"].as_str().unwrap()).collect();
+    assert_eq!(rules, vec!["form-label", "img-alt", "positive-tabindex", "click-events"]);
+    assert_eq!(issues[1]["line"], 5);
+    assert_eq!(issues[1]["severity"], "High");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "sarif", "a11y"])?;
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[1]["ruleId"], "SNF-A11Y-IMG-ALT");
+    assert_eq!(results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"], "components/Signup.tsx");
+
+    Ok(())
+}
+
+#[test]
+fn test_a11y_command_rule_levels_from_config() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("components/Signup.tsx", FORM)?;
+
+    let mut config = Config::default();
+    config.a11y.rules.insert("positive-tabindex".to_string(), A11yRuleLevel::Off);
+    config.a11y.rules.insert("img-alt".to_string(), A11yRuleLevel::Low);
+    config.a11y.rules.insert("form-label".to_string(), A11yRuleLevel::Medium);
+    project.create_file("sniff.toml", &toml::to_string(&config)?)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["a11y"])?;
+    TestAssertions::assert_success(&output);
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "Disabled rules: positive-tabindex");
+    TestAssertions::assert_output_not_contains(&stdout, "tabIndex=2");
+
+    Ok(())
+}
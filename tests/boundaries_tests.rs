@@ -0,0 +1,105 @@
+/// Integration tests for the boundaries command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "dependencies": { "next": "14.2.0", "chart-kit": "1.0.0" }
+}
+"#;
+
+const CHART_KIT_ENTRY: &str = "export * from './draw';\n";
+
+fn create_app(project: &TestProject) -> Result<()> {
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("tsconfig.json", r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["./*"] } } }"#)?;
+    project.create_file("app/layout.tsx", "import './globals.css';\nimport { Nav } from '@/components/Nav';\n\nexport default function RootLayout({ children }) {\n  return <html><body><Nav />{children}</body></html>;\n}\n")?;
+    project.create_file("app/globals.css", "body { margin: 0; }\n")?;
+    project.create_file("app/dashboard/page.tsx", "import { prisma } from '@/lib/db';\nimport Chart from './Chart';\n\nexport default async function Page() {\n  const sales = await prisma.sale.findMany();\n  return <Chart data={sales} />;\n}\n")?;
+    project.create_file("app/dashboard/Chart.tsx", "'use client';\nimport { useState } from 'react';\nimport { draw } from 'chart-kit';\nimport { format } from '@/lib/format';\n\nexport default function Chart({ data }) {\n  const [range] = useState('week');\n  return <canvas ref={(el) => draw(el, data, range, format)} />;\n}\n")?;
+    project.create_file("components/Nav.tsx", "'use client';\nimport Link from 'next/link';\n\nexport function Nav() {\n  return <nav><Link href=\"/\">Home</Link></nav>;\n}\n")?;
+    project.create_file("lib/db.ts", "import { PrismaClient } from '@prisma/client';\nexport const prisma = new PrismaClient();\n")?;
+    project.create_file("lib/format.ts", "export const format = (n: number) => n.toFixed(2);\n")?;
+    project.create_file("node_modules/chart-kit/package.json", r#"{ "name": "chart-kit", "main": "dist/index.js" }"#)?;
+    project.create_file("node_modules/chart-kit/dist/index.js", &format!("{}{}", CHART_KIT_ENTRY, "x".repeat(2048)))?;
+    project.create_file("node_modules/chart-kit/dist/draw.js", &"y".repeat(4096))?;
+    Ok(())
+}
+
+#[test]
+fn test_boundaries_command_maps_client_boundaries() -> Result<()> {
+    let project = TestProject::new()?;
+    create_app(&project)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "boundaries"])?;
+    TestAssertions::assert_success(&output);
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let data = &json["data"];
+    assert_eq!(data["app_dir"], "app");
+    let boundaries = data["boundaries"].as_array().unwrap();
+    assert_eq!(boundaries.len(), 2);
+    // Heaviest first: the chart pulls in chart-kit
+    assert_eq!(boundaries[0]["file"], "app/dashboard/Chart.tsx");
+    assert_eq!(boundaries[0]["imported_by"], serde_json::json!(["app/dashboard/page.tsx"]));
+    assert_eq!(boundaries[0]["modules"], serde_json::json!(["app/dashboard/Chart.tsx", "lib/format.ts"]));
+    let packages: Vec<(&str, Option<u64>)> = boundaries[0]["packages"].as_array().unwrap().iter()
+        .map(|p| (p["name"].as_str().unwrap(), p["bytes"].as_u64()))
+        .collect();
+    // The entry and the file it re-exports; react is not installed in the fixture
+    assert_eq!(packages, vec![("chart-kit", Some((CHART_KIT_ENTRY.len() + 2048 + 4096) as u64)), ("react", None)]);
+    assert_eq!(boundaries[1]["file"], "components/Nav.tsx");
+    assert_eq!(boundaries[1]["routes"], serde_json::json!(["app/layout.tsx"]));
+    assert_eq!(data["summary"]["server_modules"], 3);
+    assert_eq!(data["summary"]["client_modules"], 3);
+    assert_eq!(data["summary"]["total_issues"], 0);
+
+    // A 5 KB limit leaves the chart over budget
+    project.create_file("sniff.toml", "[boundaries]\nmax_boundary_kb = 5\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["boundaries", "--format", "github"])?;
+    TestAssertions::assert_success(&output);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "::warning file=app/dashboard/Chart.tsx,line=1,title=LargeBoundary [SNF-RSC-LARGE-BOUNDARY]");
+
+    Ok(())
+}
+
+#[test]
+fn test_boundaries_command_reports_code_on_the_wrong_side() -> Result<()> {
+    let project = TestProject::new()?;
+    create_app(&project)?;
+    project.create_file("lib/format.ts", "import { readFileSync } from 'node:fs';\nexport const format = (n: number) => n.toFixed(2);\n")?;
+    project.create_file("app/settings/page.tsx", "import { useEffect } from 'react';\n\nexport default function Settings() {\n  useEffect(() => {}, []);\n  const theme = localStorage.getItem('theme');\n  return <p>{theme}</p>;\n}\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "boundaries"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let issues: Vec<(&str, &str, u64)> = json["data"]["issues"].as_array().unwrap().iter()
+        .map(|i| (i["kind"].as_str().unwrap(), i["file"].as_str().unwrap(), i["line"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(issues, vec![
+        ("BrowserApi", "app/settings/page.tsx", 4),
+        ("BrowserApi", "app/settings/page.tsx", 5),
+        ("ServerPackage", "lib/format.ts", 1),
+    ]);
+    TestAssertions::assert_output_contains(json["data"]["issues"][2]["message"].as_str().unwrap(), "which the 'use client' boundary app/dashboard/Chart.tsx bundles");
+
+    Ok(())
+}
+
+#[test]
+fn test_boundaries_command_requires_the_app_router() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("pages/index.tsx", "export default function Home() { return null; }\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["boundaries"])?;
+    TestAssertions::assert_failure(&output, None);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "No app router directory");
+
+    Ok(())
+}
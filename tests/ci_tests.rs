@@ -0,0 +1,114 @@
+/// Integration tests for the ci command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "engines": { "node": ">=18.17.0" },
+  "scripts": {
+    "build": "next build",
+    "check": "tsc --noEmit && vitest run"
+  },
+  "dependencies": { "next": "14.2.0" }
+}
+"#;
+
+#[test]
+fn test_ci_command_checks_github_workflows() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("pnpm-lock.yaml", "lockfileVersion: '6.0'\n")?;
+    project.create_file(".nvmrc", "20\n")?;
+    project.create_file(".github/workflows/ci.yml", r#"name: CI
+on: [push]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 16
+          cache: npm
+      - run: pnpm install
+      - run: pnpm build
+"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "ci"])?;
+    // Node.js 16 is outside engines.node
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let data = &json["data"];
+    assert_eq!(data["package_manager"], "pnpm");
+    assert_eq!(data["steps"]["build"], ".github/workflows/ci.yml:13");
+    let issues: Vec<(&str, u64)> = data["issues"].as_array().unwrap().iter()
+        .map(|i| (i["kind"].as_str().unwrap(), i["line"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(issues, vec![
+        ("MissingStep", 1),
+        ("MissingStep", 1),
+        ("MissingStep", 1),
+        ("NodeVersion", 10),
+        ("NoCache", 11),
+    ]);
+    TestAssertions::assert_output_contains(data["issues"][4]["message"].as_str().unwrap(), "setup-node caches npm, but the project uses pnpm");
+
+    project.create_file(".github/workflows/ci.yml", r#"name: CI
+on: [push]
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version-file: .nvmrc
+          cache: pnpm
+      - run: pnpm install --frozen-lockfile
+      - run: |
+          pnpm build
+          pnpm check
+      - run: npx sniff-check deploy --format github
+"#)?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "ci"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["summary"]["total_issues"], 0);
+    assert_eq!(json["data"]["steps"]["typecheck"], ".github/workflows/ci.yml:15");
+
+    Ok(())
+}
+
+#[test]
+fn test_ci_command_reads_gitlab_ci() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("package-lock.json", "{}")?;
+    project.create_file(".gitlab-ci.yml", "image: node:20-alpine\n\ntest:\n  script:\n    - npm ci\n    - npm run check\n")?;
+    project.create_file("sniff.toml", "[ci]\nrequired_steps = [\"typecheck\", \"test\"]\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["ci", "--format", "github"])?;
+    TestAssertions::assert_success(&output);
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "::notice file=.gitlab-ci.yml,line=5,title=NoCache [SNF-CI-NO-CACHE]");
+
+    Ok(())
+}
+
+#[test]
+fn test_ci_command_requires_ci_configuration() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["ci"])?;
+    TestAssertions::assert_failure(&output, None);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "No .github/workflows");
+
+    Ok(())
+}
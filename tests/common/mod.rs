@@ -1,3 +1,6 @@
+// Each test crate uses a different subset of these helpers
+#![allow(dead_code)]
+
 /// Shared test utilities for all test modules
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -370,6 +373,22 @@ pub struct CommandRunner;
 impl CommandRunner {
     /// Run a sniff command with arguments from a specific directory
     pub fn run_sniff_command_in_dir<P: AsRef<std::path::Path>>(working_dir: P, args: &[&str]) -> Result<std::process::Output> {
+        Self::run_sniff_command_with_env(working_dir, args, &[])
+    }
+    
+    /// Run a sniff command from a specific directory with extra environment variables
+    pub fn run_sniff_command_with_env<P: AsRef<std::path::Path>>(working_dir: P, args: &[&str], env: &[(&str, &str)]) -> Result<std::process::Output> {
+        // Run the binary directly from the working directory
+        let output = std::process::Command::new(Self::sniff_binary()?)
+            .current_dir(&working_dir)
+            .args(args)
+            .envs(env.iter().copied())
+            .output()?;
+        Ok(output)
+    }
+    
+    /// The release binary, built first if needed
+    pub fn sniff_binary() -> Result<PathBuf> {
         // Find project root that contains Cargo.toml
         // We need to find the sniff-check project root, not the temporary test directory
         
@@ -421,13 +440,7 @@ impl CommandRunner {
             .args(&["build", "--release"])
             .output()?;
             
-        // Run the binary directly from the working directory
-        let binary_path = project_root.join("target/release/sniff");
-        let output = std::process::Command::new(&binary_path)
-            .current_dir(&working_dir)
-            .args(args)
-            .output()?;
-        Ok(output)
+        Ok(project_root.join("target/release/sniff"))
     }
     
     /// Run a sniff command with arguments
@@ -0,0 +1,64 @@
+/// Integration tests for the complexity command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use sniff_check::config::Config;
+
+const CHECKOUT: &str = r#"export function checkout(cart, user) {
+  if (!user) {
+    return null;
+  }
+  for (const item of cart.items) {
+    if (item.stock === 0) {
+      if (item.backorder && user.premium || item.preorder) {
+        continue;
+      } else {
+        throw new Error('out of stock');
+      }
+    }
+    while (item.quantity > item.stock) {
+      item.quantity--;
+    }
+  }
+  return cart.total > 100 ? cart.total * 0.9 : cart.total;
+}
+
+export const total = (cart) => {
+  return cart.items.length;
+};
+"#;
+
+#[test]
+fn test_complexity_command_reports_functions_over_thresholds() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("lib/checkout.ts", CHECKOUT)?;
+    let mut config = Config::default();
+    config.complexity.cognitive = 10;
+    project.create_file("sniff.toml", &toml::to_string(&config)?)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "complexity"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let data = &json["data"];
+    let functions = data["functions"].as_array().unwrap();
+    assert_eq!(functions.len(), 1);
+    assert_eq!(functions[0]["name"], "checkout");
+    assert_eq!(functions[0]["cyclomatic"], 9);
+    assert_eq!(functions[0]["cognitive"], 13);
+    assert_eq!(data["summary"]["functions"], 2);
+    assert_eq!(data["summary"]["score"], 50.0);
+
+    // The flag overrides [complexity] in sniff.toml
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["complexity", "--cognitive", "20"])?;
+    TestAssertions::assert_success(&output);
+
+    // context uses the same measurements for its complexity level
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "context"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["architecture"]["complexity"]["over_threshold"], 1);
+    assert_eq!(json["data"]["architecture"]["complexity_level"], "VeryComplex");
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+/// Integration tests for the components command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use std::fs;
+
+const CART: &str = r#"import { useState, useEffect } from 'react';
+
+export function Cart({ items }) {
+  const [open, setOpen] = useState(false);
+  const [query, setQuery] = useState('');
+  useEffect(() => {
+    document.title = query;
+  }, [query]);
+
+  const formatPrice = (price) => {
+    return `$${price.toFixed(2)}`;
+  };
+
+  return <ul>{items.map(item => <li>{formatPrice(item.price)}</li>)}</ul>;
+}
+"#;
+
+#[test]
+fn test_components_command_scaffolds_extractable_parts() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("src/Cart.tsx", CART)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "components", "--threshold", "5", "--scaffold", "extracted"])?;
+    TestAssertions::assert_success(&output);
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let parts = json["data"]["components"][0]["extractable_parts"].as_array().unwrap();
+    let ranges: Vec<(&str, u64, u64)> = parts.iter()
+        .map(|part| (part["name"].as_str().unwrap(), part["start_line"].as_u64().unwrap(), part["end_line"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(ranges, vec![("useOpen", 4, 8), ("formatPrice", 10, 12)]);
+
+    let hook = fs::read_to_string(project.root_path.join("extracted/Cart/useOpen.ts"))?;
+    assert!(hook.starts_with("// Extracted from "));
+    assert!(hook.contains("export function useOpen() {\n  const [open, setOpen] = useState(false);"));
+    assert!(hook.contains("    document.title = query;\n  }, [query]);\n\n  return { open, setOpen, query, setQuery };\n}\n"));
+
+    let utility = fs::read_to_string(project.root_path.join("extracted/Cart/formatPrice.utils.ts"))?;
+    assert!(utility.ends_with("export const formatPrice = (price) => {\n  return `$${price.toFixed(2)}`;\n};\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_components_command_counts_code_lines() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("src/Cart.tsx", CART)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "components", "--threshold", "12"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["components"][0]["line_count"], 15);
+
+    // Without the import and the blank lines, the component is 11 lines long
+    project.create_file("sniff.toml", "[large_files]\ncount_mode = \"code\"\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "components", "--threshold", "12"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["components"].as_array().unwrap().len(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_components_command_lists_findings_for_ci() -> Result<()> {
+    let project = TestProject::new()?;
+    let rows: String = (0..110).map(|i| format!("      <li>Row {}</li>\n", i)).collect();
+    project.create_file("src/List.tsx", &format!("export function List() {{\n  return (\n    <ul>\n{}    </ul>\n  );\n}}\n", rows))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["components", "--format", "github"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "::error file=src/List.tsx,line=1,title=Component List [SNF-COMP-LINES]::Component has 116 lines");
+
+    // Over 100 lines is an error, not critical
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["components", "--fail-on", "critical"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
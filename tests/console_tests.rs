@@ -0,0 +1,84 @@
+/// Integration tests for the console command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use sniff_check::config::Config;
+
+#[test]
+fn test_console_command_groups_statements_by_file() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("lib/checkout", r#"export function pay(amount: number) {
+  console.log('paying', amount); // TODO: remove
+  if (amount > 1000) alert("Too much");
+  debugger;
+  console.error('kept: errors are not debugging output');
+}
+"#)?;
+    project.create_ts_file("lib/cart", "export const total = 1; // FIXME: rounding\n")?;
+    project.create_ts_file("lib/__tests__/checkout.test", "console.log('fine in tests');\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "console"])?;
+    // Findings alone do not fail the command
+    TestAssertions::assert_success(&output);
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let files = json["data"]["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0]["file"], "lib/checkout.ts");
+    assert_eq!(files[0]["count"], 4);
+    let names: Vec<&str> = files[0]["statements"].as_array().unwrap().iter().map(|s| s["name"].as_str().unwrap()).collect();
+    assert_eq!(names, vec!["console.log", "TODO", "alert", "debugger"]);
+    assert_eq!(json["data"]["summary"]["by_name"]["FIXME"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_console_command_fail_on_found_and_allowlist() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("lib/logger", "export const log = (...args: unknown[]) => console.log(...args);\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["console", "--fail-on-found"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let mut config = Config::default();
+    config.console.allowlist = vec!["lib/logger.ts".to_string()];
+    project.create_file("sniff.toml", &toml::to_string(&config)?)?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["console", "--fail-on-found"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
+
+#[test]
+fn test_console_findings_are_ordered_by_path_and_line() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("lib/z-most", "debugger;\nconsole.log('a');\nconsole.log('b'); // TODO: drop\n")?;
+    project.create_ts_file("lib/a-least", "alert('hi');\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "console"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    // The report lists the file with the most statements first; findings follow the path
+    assert_eq!(json["data"]["files"][0]["file"], "lib/z-most.ts");
+    let findings: Vec<(&str, u64, &str)> = json["findings"].as_array().unwrap().iter()
+        .map(|f| (f["file"].as_str().unwrap(), f["line"].as_u64().unwrap(), f["rule"].as_str().unwrap()))
+        .collect();
+    assert_eq!(findings, vec![
+        ("lib/a-least.ts", 1, "SNF-CONSOLE-ALERT"),
+        ("lib/z-most.ts", 1, "SNF-CONSOLE-DEBUGGER"),
+        ("lib/z-most.ts", 2, "SNF-CONSOLE-LOG"),
+        ("lib/z-most.ts", 3, "SNF-CONSOLE-LOG"),
+        ("lib/z-most.ts", 3, "SNF-CONSOLE-TODO"),
+    ]);
+
+    let first = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "ndjson", "console"])?;
+    let second = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "ndjson", "console"])?;
+    assert_eq!(first.stdout, second.stdout);
+    assert!(String::from_utf8(first.stdout)?.starts_with("{\"level\":\"warning\",\"file\":\"lib/a-least.ts\""));
+
+    Ok(())
+}
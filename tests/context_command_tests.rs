@@ -0,0 +1,129 @@
+/// Integration tests for the context command
+mod common;
+
+use common::{TestProject, SampleFiles, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+#[test]
+fn test_context_llm_format_emits_budgeted_digest() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file(
+        "package.json",
+        r#"{ "name": "demo-app", "version": "1.2.0", "scripts": { "build": "next build" }, "dependencies": { "react": "^18.2.0" } }"#,
+    )?;
+    project.create_ts_file("components/SimpleComponent", SampleFiles::component_with_type_issues())?;
+    let config = std::fs::read_to_string("sniff.toml")?.replace("    \"tmp\",\n", "");
+    project.create_file("sniff.toml", &config)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "llm", "context", "--max-tokens", "500"])?;
+    TestAssertions::assert_success(&output);
+    let digest = String::from_utf8(output.stdout.clone())?;
+    assert!(digest.starts_with("# demo-app 1.2.0\n"));
+    assert!(digest.contains("- `npm run build`: `next build`"));
+    assert!(digest.contains("- Runtime: react@^18.2.0"));
+    assert!(digest.len() <= 500 * 4);
+
+    let again = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "llm", "context", "--max-tokens", "500"])?;
+    assert_eq!(again.stdout, output.stdout);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "llm", "large"])?;
+    TestAssertions::assert_failure(&output, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_context_reports_pages_and_api_routes_for_both_routers() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", r#"{ "name": "routes-app", "dependencies": { "next": "14.0.0" } }"#)?;
+    project.create_file("pages/index.tsx", "export async function getServerSideProps() { return { props: {} } }\nexport default function Home() { return null }\n")?;
+    project.create_file("pages/_app.tsx", "export default function App() { return null }\n")?;
+    project.create_file("pages/api/hello.ts", "export default function handler(req, res) { if (req.method === 'POST') {} }\n")?;
+    project.create_file("app/(shop)/cart/page.tsx", "export const revalidate = 60;\nexport default function Cart() { return null }\n")?;
+    project.create_file("app/api/items/[id]/route.ts", "export async function GET() {}\nexport async function DELETE() {}\n")?;
+    project.create_file("middleware.ts", "export const config = { matcher: ['/cart/:path*'] };\n")?;
+    let config = std::fs::read_to_string("sniff.toml")?.replace("    \"tmp\",\n", "");
+    project.create_file("sniff.toml", &config)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "context"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let structure = &json["data"]["structure"];
+
+    let pages = structure["pages"].as_array().unwrap();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0]["route"], "/");
+    assert_eq!(pages[0]["rendering"], "Ssr");
+    assert_eq!(pages[1]["route"], "/cart");
+    assert_eq!(pages[1]["router"], "App");
+    assert_eq!(pages[1]["rendering"], "Isr");
+    assert_eq!(pages[1]["middleware"][0], "middleware.ts");
+
+    let api_routes = structure["api_routes"].as_array().unwrap();
+    assert_eq!(api_routes[0]["route"], "/api/hello");
+    assert_eq!(api_routes[0]["methods"], serde_json::json!(["POST"]));
+    assert_eq!(api_routes[1]["route"], "/api/items/[id]");
+    assert_eq!(api_routes[1]["methods"], serde_json::json!(["GET", "DELETE"]));
+    assert_eq!(structure["middleware"]["matchers"], serde_json::json!(["/cart/:path*"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_context_builds_import_graph_and_writes_graph_json() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", r#"{ "name": "graph-app" }"#)?;
+    project.create_file("tsconfig.json", r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["./*"] } } }"#)?;
+    project.create_file("components/Button.tsx", "import { format } from '../lib/format';\nexport function Button() { return <button>{format('x')}</button> }\n")?;
+    project.create_file("components/Panel.tsx", "import {\n  Button,\n} from './Button';\nexport default function Panel() { return <Button /> }\n")?;
+    project.create_file("lib/format.ts", "import Panel from '@/components/Panel';\nexport const format = (value: string) => value;\n")?;
+    let config = std::fs::read_to_string("sniff.toml")?.replace("    \"tmp\",\n", "");
+    project.create_file("sniff.toml", &config)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "context", "--graph-out", "out/graph.json"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let relationships = &json["data"]["relationships"];
+    assert_eq!(relationships["import_graph"]["components/Panel.tsx"], serde_json::json!(["components/Button.tsx"]));
+    assert_eq!(relationships["import_graph"]["lib/format.ts"], serde_json::json!(["components/Panel.tsx"]));
+    assert_eq!(relationships["component_hierarchy"]["components/Panel.tsx"], serde_json::json!(["components/Button.tsx"]));
+    assert_eq!(relationships["coupling"]["components/Button.tsx"]["fan_in"], 1);
+    assert_eq!(
+        relationships["circular_dependencies"],
+        serde_json::json!([["components/Button.tsx", "components/Panel.tsx", "lib/format.ts"]])
+    );
+
+    let graph: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(project.path("out/graph.json"))?)?;
+    assert_eq!(graph["edges"].as_array().unwrap().len(), 3);
+    assert_eq!(graph["edges"][0], serde_json::json!({ "from": "components/Button.tsx", "to": "lib/format.ts" }));
+
+    Ok(())
+}
+
+#[test]
+fn test_context_graph_exports_mermaid_and_dot() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", r#"{ "name": "diagram-app" }"#)?;
+    project.create_file("app/page.tsx", "import Panel from '../components/Panel';\nexport default function Page() { return <Panel /> }\n")?;
+    project.create_file("components/Panel.tsx", "import { Button } from './Button';\nexport default function Panel() { return <Button /> }\n")?;
+    project.create_file("components/Button.tsx", "export function Button() { return null }\n")?;
+    project.create_file("lib/unused.ts", "export const unused = 1;\n")?;
+    let config = std::fs::read_to_string("sniff.toml")?.replace("    \"tmp\",\n", "");
+    project.create_file("sniff.toml", &config)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["context", "--graph", "mermaid", "--entry", "app/page.tsx"])?;
+    TestAssertions::assert_success(&output);
+    let mermaid = String::from_utf8(output.stdout)?;
+    assert!(mermaid.starts_with("graph LR\n"));
+    assert!(mermaid.contains("n0[\"app/page.tsx\"]"));
+    assert!(mermaid.contains("n0 --> n2"));
+    assert!(!mermaid.contains("lib/unused.ts"));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["context", "--graph", "dot", "--scope", "components", "--out", "docs/deps.dot"])?;
+    TestAssertions::assert_success(&output);
+    let dot = std::fs::read_to_string(project.path("docs/deps.dot"))?;
+    assert!(dot.contains("\"components/Panel.tsx\" -> \"components/Button.tsx\";"));
+    assert!(!dot.contains("app/page.tsx"));
+
+    Ok(())
+}
@@ -22,7 +22,7 @@ mod context_tests {
         fs::write(temp_path.join("README.md"), "# Test").unwrap();
         
         let extensions = ["ts", "js"];
-        let files = FileUtils::find_files_with_extensions(temp_path, &extensions);
+        let files = FileUtils::find_files_with_extensions(temp_path, &extensions).unwrap();
         
         assert_eq!(files.len(), 2);
         assert!(files.iter().any(|f| f.file_name().unwrap() == "test.ts"));
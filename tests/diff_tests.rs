@@ -0,0 +1,48 @@
+/// Integration tests for the diff command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+/// Save `sniff --json <command>` as `name` in the project
+fn save_report(project: &TestProject, command: &str, name: &str) -> Result<()> {
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "--quiet", command])?;
+    project.create_file(name, &String::from_utf8(output.stdout)?)?;
+    Ok(())
+}
+
+#[test]
+fn test_diff_command_fails_only_on_introduced_findings() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_ts_file("src/format", "export const format = String;\nexport const parse = Number;\n")?;
+    project.create_ts_file("src/app", "import { format } from './format';\nexport const app = 1;\n")?;
+    save_report(&project, "imports", "old.json")?;
+
+    // The existing finding moves down a line and a new one appears
+    project.create_ts_file("src/app", "import { parse } from './format';\nimport { format } from './format';\nexport const app = 1;\n")?;
+    save_report(&project, "imports", "new.json")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "diff", "old.json", "new.json"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["summary"]["introduced"], 1);
+    assert_eq!(json["data"]["summary"]["persisting"], 1);
+    assert_eq!(json["data"]["persisting"][0]["line"], 2);
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0]["message"].as_str().unwrap().contains("parse"));
+
+    // Going back resolves the new finding and introduces nothing
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["diff", "new.json", "old.json"])?;
+    TestAssertions::assert_success(&output);
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(stdout.contains("0 introduced, 1 resolved, 1 persisting"), "{}", stdout);
+
+    // Reports of different commands cannot be compared
+    save_report(&project, "console", "console.json")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["diff", "old.json", "console.json"])?;
+    TestAssertions::assert_failure(&output, Some(1));
+    assert!(String::from_utf8(output.stderr)?.contains("Cannot compare a 'imports' report with a 'console' report"));
+
+    Ok(())
+}
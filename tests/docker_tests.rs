@@ -0,0 +1,74 @@
+/// Integration tests for the docker command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "engines": { "node": ">=18.17.0" },
+  "dependencies": { "next": "14.2.0" }
+}
+"#;
+
+#[test]
+fn test_docker_command_checks_dockerfile_and_dockerignore() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("Dockerfile", "ARG NODE_VERSION=16\nFROM node:${NODE_VERSION}-alpine\nWORKDIR /app\nCOPY . .\nRUN npm ci && npm run build\nCMD [\"npm\", \"start\"]\n")?;
+    project.create_file(".dockerignore", "node_modules\n.git\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "docker"])?;
+    // node:16 is outside engines.node
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let data = &json["data"];
+    assert_eq!(data["dockerfiles"][0]["stages"][0]["image"], "node:16-alpine");
+    assert_eq!(data["engines_node"], ">=18.17.0");
+    let issues: Vec<(&str, &str, u64)> = data["issues"].as_array().unwrap().iter()
+        .map(|i| (i["kind"].as_str().unwrap(), i["file"].as_str().unwrap(), i["line"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(issues, vec![
+        ("Dockerignore", ".dockerignore", 1),
+        ("NodeVersion", "Dockerfile", 2),
+        ("SingleStage", "Dockerfile", 2),
+        ("DevDependencies", "Dockerfile", 5),
+    ]);
+    TestAssertions::assert_output_contains(data["issues"][0]["message"].as_str().unwrap(), "Does not exclude .next/cache, .env");
+
+    project.create_file("Dockerfile", r#"FROM node:20-alpine AS builder
+WORKDIR /app
+COPY . .
+RUN npm ci && npm run build && npm prune --omit=dev
+
+FROM node:20-alpine AS runner
+ENV NODE_ENV=production
+COPY --from=builder /app/node_modules ./node_modules
+COPY --from=builder /app/.next ./.next
+CMD ["npm", "start"]
+"#)?;
+    project.create_file(".dockerignore", "node_modules\n.next\n.env*\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "docker"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["summary"]["total_issues"], 0);
+    assert_eq!(json["data"]["summary"]["stages"], 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_docker_command_requires_a_dockerfile() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["docker"])?;
+    TestAssertions::assert_failure(&output, None);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "No Dockerfile");
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+/// Integration tests for the doctor command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+fn probe<'a>(json: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    json["data"]["probes"].as_array().unwrap().iter().find(|probe| probe["name"] == name).unwrap()
+}
+
+#[test]
+fn test_doctor_reports_probes_with_remedies() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_package_json(&[("next", "14.2.0")], &[])?;
+    project.create_file("sniff.toml", "[large_files]\nthreshold = \"many\"\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "doctor"])?;
+    // An invalid config affects every command
+    TestAssertions::assert_failure(&output, Some(1));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["command"], "doctor");
+    assert_eq!(probe(&json, "config")["status"], "Failed");
+    assert_eq!(probe(&json, "package.json")["status"], "Ok");
+    let build = probe(&json, "build output");
+    assert_eq!(build["status"], "Warning");
+    assert!(build["remedy"].as_str().unwrap().contains("npm run build"));
+    assert_eq!(probe(&json, "tsconfig.json")["needed_by"][0], "types");
+
+    project.create_file("sniff.toml", "[large_files]\nthreshold = 150\n")?;
+    project.create_dir(".next")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "doctor"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(probe(&json, "config")["status"], "Ok");
+    assert_eq!(probe(&json, "build output")["detail"], ".next");
+
+    Ok(())
+}
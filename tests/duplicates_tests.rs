@@ -0,0 +1,81 @@
+/// Integration tests for the duplicates command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const SHARED_BLOCK: &str = r#"
+export function computeTotals(orders: Order[]) {
+    const totals = { net: 0, tax: 0, gross: 0 };
+    for (const order of orders) {
+        const net = order.items.reduce((sum, item) => sum + item.price * item.qty, 0);
+        const tax = net * order.taxRate;
+        totals.net += net;
+        totals.tax += tax;
+        totals.gross += net + tax;
+    }
+    if (totals.gross > 1000) {
+        console.log('large batch');
+    }
+    return totals;
+}
+"#;
+
+#[test]
+fn test_duplicates_command_reports_clone_pairs() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("billing/totals", SHARED_BLOCK)?;
+    project.create_ts_file("reports/summary", &format!("const title = 'Summary';\n{}", SHARED_BLOCK))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "duplicates"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    assert_eq!(json["command"], "duplicates");
+
+    let clones = json["data"]["clones"].as_array().unwrap();
+    assert_eq!(clones.len(), 1);
+    assert!(clones[0]["first"]["file"].as_str().unwrap().contains("totals.ts"));
+    assert!(clones[0]["second"]["file"].as_str().unwrap().contains("summary.ts"));
+    assert!(json["data"]["summary"]["duplicated_lines"].as_u64().unwrap() >= 10);
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicates_command_respects_min_lines() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("billing/totals", SHARED_BLOCK)?;
+    project.create_ts_file("reports/summary", SHARED_BLOCK)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["duplicates", "--min-lines", "40"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
+
+#[test]
+fn test_duplicates_command_writes_sarif() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("billing/totals", SHARED_BLOCK)?;
+    project.create_ts_file("reports/summary", SHARED_BLOCK)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["duplicates", "--format", "sarif"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "SNF-DUP-001");
+    let location = &results[0]["locations"][0]["physicalLocation"];
+    assert!(location["artifactLocation"]["uri"].as_str().unwrap().ends_with("totals.ts"));
+    assert!(results[0]["message"]["text"].as_str().unwrap().contains("summary.ts"));
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+/// Integration tests for the fix command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use sniff_check::config::Config;
+use std::fs;
+
+const USER: &str = "export interface User {\n  id: string;\n}\n\nexport enum Role {\n  Admin,\n}\n\nexport function loadUser(id: string): User {\n  return { id };\n}\n";
+
+const PROFILE: &str = r#"import {
+  User,
+  loadUser,
+} from './user';
+import type { Role } from './user';
+
+export function profile(id: string): User {
+  const role = Role.Admin;
+  return loadUser(`${id}-${role}`);
+}
+"#;
+
+#[test]
+fn test_fix_command_rewrites_type_imports() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("tsconfig.json", r#"{ "compilerOptions": { "verbatimModuleSyntax": true } }"#)?;
+    project.create_file("src/user.ts", USER)?;
+    project.create_file("src/profile.ts", PROFILE)?;
+
+    // imports reports them without failing
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let issues = json["data"]["type_imports"].as_array().unwrap();
+    assert_eq!(issues.len(), 2);
+    assert_eq!(issues[0]["kind"], "TypeOnly");
+    assert_eq!(issues[0]["end_line"], 4);
+    assert_eq!(issues[1]["kind"], "UsedAsValue");
+
+    // A dry run changes nothing and fails while fixes are pending
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "fix", "--dry-run"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["summary"]["edits"], 2);
+    assert_eq!(fs::read_to_string(project.root_path.join("src/profile.ts"))?, PROFILE);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["fix", "--type-imports"])?;
+    TestAssertions::assert_success(&output);
+    let fixed = fs::read_to_string(project.root_path.join("src/profile.ts"))?;
+    assert!(fixed.starts_with("import { type User, loadUser } from './user';\nimport { Role } from './user';\n\nexport function"));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["fix", "--dry-run"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
+
+#[test]
+fn test_fix_command_organizes_imports() -> Result<()> {
+    let project = TestProject::new()?;
+    let mut config = Config::default();
+    config.imports.order.enabled = true;
+    project.create_file("sniff.toml", &toml::to_string(&config)?)?;
+    project.create_file("src/a.ts", "export const a = 1;\n")?;
+    project.create_file("src/b.ts", "export const b = 2;\n")?;
+    project.create_file("src/index.ts", "import { b } from './b';\nimport clsx from 'clsx';\nimport { a } from './a';\n\nexport const value = clsx(a, b);\n")?;
+    project.create_dir("node_modules/clsx")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let issues = json["data"]["import_order"].as_array().unwrap();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0]["line"], 2);
+    assert_eq!(issues[0]["message"], "'clsx' (external) should come before relative imports");
+
+    // Enabled in sniff.toml, so a plain `sniff fix` includes it
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["fix"])?;
+    TestAssertions::assert_success(&output);
+    let fixed = fs::read_to_string(project.root_path.join("src/index.ts"))?;
+    assert!(fixed.starts_with("import clsx from 'clsx';\n\nimport { a } from './a';\nimport { b } from './b';\n\nexport const value"));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["fix", "--organize-imports", "--dry-run"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+/// Integration tests for run history and the history command
+mod common;
+
+use common::{TestProject, SampleFiles, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+#[test]
+fn test_history_records_whole_project_runs_and_reports_regressions() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_ts_file("components/Small", "export const Small = () => null;")?;
+    project.create_file("sniff.toml", "[history]\nenabled = true\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--threshold", "50", "--quiet"])?;
+    TestAssertions::assert_success(&output);
+
+    project.create_ts_file("components/LargeComponent", SampleFiles::large_component())?;
+    CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--threshold", "50", "--quiet"])?;
+    // Runs over selected paths are not comparable and are not recorded
+    CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "components", "--quiet"])?;
+
+    let history = std::fs::read_to_string(project.path(".sniff/history.jsonl"))?;
+    assert_eq!(history.lines().count(), 2);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "history", "show", "--since", "1d"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["runs"], 2);
+    let large_files = &json["data"]["trends"][0];
+    assert_eq!(large_files["metric"], "large_files");
+    assert_eq!(large_files["values"], serde_json::json!([0.0, 1.0]));
+    assert_eq!(large_files["regressed"], true);
+    assert_eq!(json["data"]["regressions"][0]["metric"], "large_files");
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+/// Integration tests for the hooks command and `imports --staged`
+mod common;
+
+use common::{TestProject, SampleFiles, TestAssertions, CommandRunner};
+use anyhow::Result;
+use std::path::Path;
+use std::process::Command;
+
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let status = Command::new("git").arg("-C").arg(dir).args(args).status()?;
+    assert!(status.success(), "git {:?} failed", args);
+    Ok(())
+}
+
+#[test]
+fn test_imports_staged_only_analyzes_staged_files() -> Result<()> {
+    let project = TestProject::new()?;
+    git(&project.root_path, &["init", "-q"])?;
+    project.create_dir("node_modules/react")?;
+    project.create_ts_file("components/Dirty", SampleFiles::file_with_unused_imports())?;
+    project.create_ts_file("components/Clean", "export const answer = 42;\n")?;
+
+    // Nothing staged: nothing to analyze, even though the working tree has issues
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--staged", "--quiet"])?;
+    TestAssertions::assert_success(&output);
+
+    git(&project.root_path, &["add", "components/Clean.ts"])?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--staged", "--quiet"])?;
+    TestAssertions::assert_success(&output);
+
+    git(&project.root_path, &["add", "components/Dirty.ts"])?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--staged", "--quiet"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "Dirty.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_hooks_install_composes_with_husky() -> Result<()> {
+    let project = TestProject::new()?;
+    git(&project.root_path, &["init", "-q"])?;
+    project.create_file(".husky/pre-commit", "npx lint-staged\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "hooks", "install"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["manager"], "husky");
+    assert_eq!(json["data"]["hooks"][0]["change"], "Appended");
+    assert_eq!(json["data"]["hooks"][1]["change"], "Created");
+
+    let pre_commit = std::fs::read_to_string(project.path(".husky/pre-commit"))?;
+    assert!(pre_commit.starts_with("npx lint-staged\n"));
+    assert!(pre_commit.contains("sniff hooks run pre-commit"));
+    assert!(std::fs::read_to_string(project.path(".husky/pre-push"))?.contains("sniff hooks run pre-push"));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["hooks", "uninstall"])?;
+    TestAssertions::assert_success(&output);
+    assert_eq!(std::fs::read_to_string(project.path(".husky/pre-commit"))?, "npx lint-staged\n");
+    assert!(!project.path(".husky/pre-push").exists());
+
+    Ok(())
+}
+
+#[test]
+fn test_hooks_run_stops_at_first_failing_command() -> Result<()> {
+    let project = TestProject::new()?;
+    git(&project.root_path, &["init", "-q"])?;
+    project.create_dir("node_modules/react")?;
+    project.create_ts_file("components/Dirty", SampleFiles::file_with_unused_imports())?;
+    // The repo config excludes `tmp`, which is where test projects live
+    let config = std::fs::read_to_string("sniff.toml")?
+        .replace("    \"tmp\",\n", "")
+        .replace("pre_commit = [\"imports --staged --quiet\"]", "pre_commit = [\"imports --quiet\", \"large\"]");
+    project.create_file("sniff.toml", &config)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "hooks", "run", "pre-commit"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let commands = json["data"]["commands"].as_array().unwrap();
+    assert_eq!(commands.len(), 1);
+    assert_eq!(commands[0]["command"], "imports --quiet");
+    assert_eq!(commands[0]["exit_code"], 2);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+/// Integration tests for the i18n command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+#[test]
+fn test_i18n_command_compares_code_and_locales() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_package_json(&[("next-intl", "3.0.0")], &[])?;
+    project.create_file("messages/en.json", r#"{ "Home": { "title": "Welcome", "legacy": "Old" }, "items_one": "1 item", "items_other": "{count} items" }"#)?;
+    project.create_file("messages/de.json", r#"{ "Home": { "title": "Willkommen" } }"#)?;
+    project.create_file("app/page.tsx", r#"import { useTranslations } from 'next-intl';
+
+export default function Page() {
+  const t = useTranslations('Home');
+  return (
+    <main>
+      <h1>{t('title')}</h1>
+      <p>{t('subtitle')}</p>
+      <input placeholder="Search products" />
+      <button>Add to cart</button>
+    </main>
+  );
+}
+"#)?;
+    project.create_file("components/Cart.tsx", "export const Cart = ({ t, count }) => <span>{t('items', { count })}</span>;\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "i18n"])?;
+    // Keys missing from a locale fail the check
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let data = &json["data"];
+    assert_eq!(data["library"], "next-intl");
+    assert_eq!(data["locales"], serde_json::json!(["de", "en"]));
+
+    let missing = data["missing_keys"].as_array().unwrap();
+    let missing: Vec<(&str, &serde_json::Value)> = missing.iter().map(|m| (m["key"].as_str().unwrap(), &m["locales"])).collect();
+    assert_eq!(missing, vec![
+        ("Home.subtitle", &serde_json::json!(["de", "en"])),
+        ("items", &serde_json::json!(["de"])),
+    ]);
+
+    let unused: Vec<&str> = data["unused_keys"].as_array().unwrap().iter().map(|u| u["key"].as_str().unwrap()).collect();
+    assert_eq!(unused, vec!["Home.legacy"]);
+
+    let hardcoded = data["hardcoded"].as_array().unwrap();
+    assert_eq!(hardcoded.len(), 2);
+    assert_eq!(hardcoded[0]["text"], "Search products");
+    assert_eq!(hardcoded[0]["attribute"], "placeholder");
+    assert_eq!(hardcoded[1]["text"], "Add to cart");
+    assert_eq!(hardcoded[1]["line"], 10);
+
+    Ok(())
+}
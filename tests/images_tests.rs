@@ -0,0 +1,53 @@
+/// Integration tests for the images command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+/// A PNG header claiming the given size, padded to `bytes`
+fn png(width: u32, height: u32, bytes: usize) -> Vec<u8> {
+    let mut data = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+    data.extend_from_slice(&width.to_be_bytes());
+    data.extend_from_slice(&height.to_be_bytes());
+    data.resize(bytes, 0);
+    data
+}
+
+#[test]
+fn test_images_command_reports_assets_with_savings() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_package_json(&[("next", "14.0.0")], &[])?;
+    std::fs::create_dir_all(project.root_path.join("public"))?;
+    std::fs::write(project.root_path.join("public/hero.png"), png(4000, 2000, 600 * 1024))?;
+    std::fs::write(project.root_path.join("public/icon.png"), png(32, 32, 2 * 1024))?;
+    project.create_file("public/logo.svg", "<svg><image href=\"data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNk+M9QDwADhgGAWjR9awAAAABJRU5ErkJggg==\"/></svg>")?;
+    project.create_file("app/page.tsx", r#"export default function Page() {
+  return <img src="/hero.png" alt="" />;
+}
+"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "images"])?;
+    // hero.png is over the size and dimension limits
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let data = &json["data"];
+    assert_eq!(data["summary"]["images_scanned"], 3);
+    let assets = data["assets"].as_array().unwrap();
+    assert_eq!(assets.len(), 2);
+
+    let hero = &assets[0];
+    assert_eq!(hero["path"], "public/hero.png");
+    assert_eq!(hero["width"], 4000);
+    let kinds: Vec<&str> = hero["issues"].as_array().unwrap().iter().map(|issue| issue["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, vec!["Heavy", "Oversized", "LegacyFormat", "MissingNextImage"]);
+    assert_eq!(hero["issues"][3]["file"], "app/page.tsx");
+    assert_eq!(hero["issues"][3]["line"], 2);
+    assert!(hero["potential_savings"].as_u64().unwrap() > 400 * 1024);
+
+    assert_eq!(assets[1]["path"], "public/logo.svg");
+    assert_eq!(assets[1]["issues"][0]["kind"], "EmbeddedRaster");
+
+    Ok(())
+}
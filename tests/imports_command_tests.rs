@@ -36,17 +36,19 @@ fn test_imports_command_json_output() -> Result<()> {
     let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
     let stdout = String::from_utf8(output.stdout)?;
 
-    // Verify JSON structure matches the actual ImportsReport schema
+    // Verify the standard envelope around the ImportsReport
     TestAssertions::assert_json_structure(
         &stdout,
-        &["unused_imports", "broken_imports", "summary"]
+        &["schema_version", "command", "data", "summary", "findings"]
     );
 
     let json: serde_json::Value = serde_json::from_str(&stdout)
         .expect("Failed to parse JSON output");
 
-    assert!(json["unused_imports"].as_array().unwrap().len() > 0, "expected unused imports");
-    assert!(json["summary"]["unused_imports"].as_u64().unwrap() > 0);
+    assert!(json["data"]["unused_imports"].as_array().unwrap().len() > 0, "expected unused imports");
+    assert!(json["data"]["summary"]["unused_imports"].as_u64().unwrap() > 0);
+    assert_eq!(json["schema_version"], 1);
+    assert!(json["findings"].as_array().unwrap().iter().any(|finding| finding["title"] == "Unused import"));
 
     Ok(())
 }
@@ -109,4 +111,206 @@ export default GoodComponent;
     TestAssertions::assert_output_not_contains(&stdout, "GoodComponent.ts");
     
     Ok(())
-}
\ No newline at end of file
+}
+#[test]
+fn test_imports_command_github_annotations() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_dir("node_modules/react")?;
+    project.create_ts_file("components/SimpleComponent", SampleFiles::file_with_unused_imports())?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--format", "github"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| line.starts_with("::")), "only workflow commands expected:\n{}", stdout);
+    TestAssertions::assert_output_contains(&stdout, "components/SimpleComponent.ts,line=3,title=Unused import [SNF-IMP-UNUSED]::Unused: Button");
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_fail_on_policy() -> Result<()> {
+    let project = TestProject::new()?;
+
+    // An unused import is a warning
+    project.create_ts_file("components/Counter", "import { useState, useEffect } from 'react';\n\nexport const useCounter = () => useState(0);\n")?;
+    project.create_dir("node_modules/react")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--quiet"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--quiet", "--fail-on", "error"])?;
+    TestAssertions::assert_success(&output);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--quiet", "--fail-on", "warning"])?;
+    TestAssertions::assert_failure(&output, Some(5));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--quiet", "--max-warnings", "10"])?;
+    TestAssertions::assert_success(&output);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["context", "--fail-on", "never"])?;
+    TestAssertions::assert_failure(&output, Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_unused_exports() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("src/lib/format.ts", "export const formatDate = (d: Date) => d.toISOString();\nexport const formatPrice = (n: number) => `$${n}`;\nexport function pad(n: number) {\n  return String(n).padStart(2, '0');\n}\nexport const clock = () => pad(1);\n")?;
+    project.create_file("src/lib/all.ts", "export const one = 1;\nexport const two = 2;\n")?;
+    project.create_file("src/components/Header.tsx", "import { formatDate } from '../lib/format';\nimport * as all from '../lib/all';\n\nexport default function Header() {\n  return <h1>{formatDate(new Date())}{all.one}</h1>;\n}\n")?;
+    project.create_file("app/page.tsx", "import Header from '../src/components/Header';\n\nexport default function Page() {\n  return <Header />;\n}\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports", "--unused-exports"])?;
+    TestAssertions::assert_success(&output);
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let exports: Vec<(String, bool)> = json["data"]["unused_exports"].as_array().unwrap().iter()
+        .map(|export| (export["name"].as_str().unwrap().to_string(), export["used_in_file"].as_bool().unwrap()))
+        .collect();
+    assert_eq!(exports, vec![("formatPrice".to_string(), false), ("pad".to_string(), true), ("clock".to_string(), false)]);
+
+    // Without the flag only imports are checked
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert!(json["data"]["unused_exports"].as_array().unwrap().is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_package_exports() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("node_modules/firebase/package.json", r#"{ "exports": { ".": "./index.js", "./auth": { "browser": "./auth/browser.js", "default": "./auth/index.js" } } }"#)?;
+    project.create_file("node_modules/lodash/package.json", r#"{ "main": "lodash.js" }"#)?;
+    project.create_file("node_modules/lodash/fp.js", "")?;
+    project.create_file("src/app.ts", "import { readFile } from 'node:fs/promises';\nimport { getAuth } from 'firebase/auth';\nimport { internal } from 'firebase/internal';\nimport fp from 'lodash/fp';\nimport chunk from 'lodash/chunk';\n\nexport const run = () => [readFile, getAuth, internal, fp, chunk];\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let broken: Vec<(&str, &str)> = json["data"]["broken_imports"].as_array().unwrap().iter()
+        .map(|import| (import["import_path"].as_str().unwrap(), import["error_type"].as_str().unwrap()))
+        .collect();
+    assert_eq!(broken, vec![("firebase/internal", "NotExported"), ("lodash/chunk", "FileNotFound")]);
+    assert_eq!(json["data"]["broken_imports"][0]["suggestion"], "'firebase' exports: firebase, firebase/auth");
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_assets_and_query_suffixes() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("src/Button.module.css", ".button {}\n")?;
+    project.create_file("src/logo.svg", "<svg />\n")?;
+    project.create_file("src/shader.glsl", "void main() {}\n")?;
+    project.create_file("src/button.styles.ts", "export const base = 'px-2';\n")?;
+    project.create_file("src/Button.tsx", "import './globals.css';\nimport styles from './Button.module.css';\nimport Logo from './logo.svg?react';\nimport shader from './shader.glsl?raw';\nimport { base } from './button.styles';\nimport hero from './hero.png';\n\nexport const Button = () => <button className={styles.button + base}><Logo />{shader}{hero}</button>;\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let mut broken: Vec<&str> = json["data"]["broken_imports"].as_array().unwrap().iter()
+        .map(|import| import["import_path"].as_str().unwrap())
+        .collect();
+    broken.sort();
+    assert_eq!(broken, vec!["./globals.css", "./hero.png"]);
+    assert_eq!(json["data"]["summary"]["total_imports"], 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_dynamic_imports() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_dir("node_modules/next")?;
+    project.create_file("src/Chart.tsx", "export default function Chart() { return null; }\n")?;
+    project.create_file("src/Page.tsx", "import dynamic from 'next/dynamic';\nimport { lazy } from 'react';\n\nconst Chart = dynamic(() => import('./Chart'), { ssr: false });\nconst Table = lazy(() => import('./Table'));\nconst config = require(\"./config\");\n// const Old = import('./Old');\n\nexport async function load() {\n  const { parse } = await import(`./parser`);\n  return [Chart, Table, config, parse];\n}\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let broken: Vec<(&str, u64)> = json["data"]["broken_imports"].as_array().unwrap().iter()
+        .map(|import| (import["import_path"].as_str().unwrap(), import["line"].as_u64().unwrap()))
+        .filter(|(path, _)| path.starts_with('.'))
+        .collect();
+    assert_eq!(broken, vec![("./Table", 5), ("./config", 6), ("./parser", 10)]);
+    assert_eq!(json["data"]["summary"]["total_imports"], 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_ignores_identifiers_in_comments() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("src/ui.ts", "export const Button = 1;\nexport const Card = 2;\nexport const Badge = 3;\n")?;
+    project.create_file("src/Page.ts", "import { Button, Card, Badge } from './ui';\n\nconst size = 1; // TODO: use Button\n/* Card was here\n   before the redesign */\nexport const items = [Badge, size];\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let unused = &json["data"]["unused_imports"][0]["unused_items"];
+    assert_eq!(unused, &serde_json::json!(["Button", "Card"]));
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_ndjson_prints_one_finding_per_line() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_ts_file("components/SimpleComponent", SampleFiles::file_with_unused_imports())?;
+    project.create_dir("node_modules/react")?;
+    project.create_file("src/clean.ts", "export const add = (a: number, b: number) => a + b;\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports", "--format", "ndjson"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let findings: Vec<serde_json::Value> = stdout.lines().map(serde_json::from_str).collect::<Result<_, _>>()?;
+    assert!(!findings.is_empty());
+    assert!(findings.iter().all(|finding| finding["file"].as_str().unwrap().ends_with("SimpleComponent.ts")));
+    assert!(findings.iter().any(|finding| finding["title"] == "Unused import"));
+
+    // --fail-on applies to the streamed findings, none of which is critical
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--fail-on", "critical", "imports", "--format", "ndjson"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
+
+#[test]
+fn test_imports_command_skips_ignored_findings() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("sniff.toml", "ignore = [{ rule = \"SNF-IMP-UNUSED\", paths = [\"**/*.stories.tsx\"], reason = \"Storybook args\" }]\n")?;
+    project.create_ts_file("src/format", "export const format = (value: unknown) => String(value);\n")?;
+    project.create_file("src/Button.tsx", "import { format } from './format';\nexport const Button = () => null;\n")?;
+    project.create_file("src/Button.stories.tsx", "import { format } from './format';\nexport default { title: 'Button' };\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "imports"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["summary"]["unused_imports"], 1);
+    let findings = json["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1, "{:?}", findings);
+    assert!(findings[0]["file"].as_str().unwrap().ends_with("src/Button.tsx"));
+
+    // An entry for the whole command covers every file
+    project.create_file("sniff.toml", "[[ignore]]\nrule = \"imports\"\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["imports"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+/// Integration tests for the init command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use std::process::Command;
+
+#[test]
+fn test_init_tailors_config_and_sets_up_ci() -> Result<()> {
+    let project = TestProject::new()?;
+    assert!(Command::new("git").arg("-C").arg(&project.root_path).args(["init", "-q"]).status()?.success());
+    project.create_package_json(&[("next", "14.2.0"), ("react", "18.3.1")], &[])?;
+    project.create_file("pnpm-lock.yaml", "lockfileVersion: '9.0'\n")?;
+    project.create_file(".gitignore", "node_modules")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "init", "--yes"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["framework"], "NextJs");
+    assert_eq!(json["data"]["package_manager"], "pnpm");
+    let changes: Vec<&str> = json["data"]["files"].as_array().unwrap().iter().map(|file| file["change"].as_str().unwrap()).collect();
+    assert_eq!(changes, vec!["Created", "Updated", "Created"]);
+    assert_eq!(json["data"]["hooks"]["hooks"][0]["change"], "Created");
+
+    let config: toml::Value = toml::from_str(&std::fs::read_to_string(project.path("sniff.toml"))?)?;
+    assert_eq!(config["bundle"]["build_dirs"], toml::Value::Array(vec![".next".into(), "out".into()]));
+    assert!(config["large_files"]["excluded_files"].as_array().unwrap().contains(&"pnpm-lock.yaml".into()));
+    let workflow = std::fs::read_to_string(project.path(".github/workflows/sniff-check.yml"))?;
+    assert!(workflow.contains("pnpm install --frozen-lockfile"));
+    assert!(workflow.contains("sniff deploy --format github"));
+    assert!(std::fs::read_to_string(project.path(".gitignore"))?.ends_with("# sniff\n.sniff-cache/\n.sniff/\n"));
+
+    // Without a terminal or --yes, nothing already set up is touched
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "init"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let changes: Vec<&str> = json["data"]["files"].as_array().unwrap().iter().map(|file| file["change"].as_str().unwrap()).collect();
+    assert_eq!(changes, vec!["Kept", "Kept"]);
+    assert!(json["data"]["hooks"].is_null());
+
+    Ok(())
+}
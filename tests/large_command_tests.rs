@@ -100,4 +100,352 @@ fn test_large_command_quiet_mode() -> Result<()> {
     TestAssertions::assert_output_not_contains(&stdout, "🔍 Running large file analysis");
 
     Ok(())
-}
\ No newline at end of file
+}
+#[test]
+fn test_large_command_scans_only_given_paths() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("packages/web/LargeWeb", SampleFiles::large_component())?;
+    project.create_ts_file("packages/api/LargeApi", SampleFiles::large_component())?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(
+        &project.root_path,
+        &["large", "--threshold", "50", "packages/web"],
+    )?;
+    TestAssertions::assert_failure(&output, Some(3));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "LargeWeb.ts");
+    TestAssertions::assert_output_not_contains(&stdout, "LargeApi.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_with_root_option() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("app/components/LargeComponent", SampleFiles::large_component())?;
+    project.create_ts_file("other/LargeOther", SampleFiles::large_component())?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(
+        &project.root_path,
+        &["large", "--threshold", "50", "--root", "app", "components"],
+    )?;
+    TestAssertions::assert_failure(&output, Some(3));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "LargeComponent.ts");
+    TestAssertions::assert_output_not_contains(&stdout, "LargeOther.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_rejects_missing_path() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "does-not-exist"])?;
+    TestAssertions::assert_failure(&output, Some(1));
+
+    let stderr = String::from_utf8(output.stderr)?;
+    TestAssertions::assert_output_contains(&stderr, "does-not-exist");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_reports_large_functions() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let mut body = String::from("export function handleCheckout(cart: Cart) {\n");
+    for i in 1..=30 {
+        body.push_str(&format!("  const step{} = cart.items.length + {};\n", i, i));
+    }
+    body.push_str("  return cart;\n}\n\nexport const total = (cart: Cart) => cart.items.length;\n");
+    project.create_ts_file("services/checkout", &body)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(
+        &project.root_path,
+        &["--json", "large", "--function-threshold", "20"],
+    )?;
+    TestAssertions::assert_success(&output);
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let functions = json["data"]["functions"].as_array().unwrap();
+    assert_eq!(functions.len(), 1, "only handleCheckout exceeds the threshold");
+    assert_eq!(functions[0]["name"], "handleCheckout");
+    assert_eq!(functions[0]["start_line"], 1);
+    assert_eq!(functions[0]["end_line"], 33);
+    assert_eq!(json["data"]["summary"]["large_functions_found"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_applies_file_type_thresholds() -> Result<()> {
+    let project = TestProject::new()?;
+
+    // 150 lines: over the default threshold, under the default 400-line limit for tests
+    let content: String = (1..=150).map(|i| format!("const value{} = {};\n", i, i)).collect();
+    project.create_ts_file("e2e/checkout.spec", &content)?;
+    project.create_ts_file("lib/checkout", &content)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "lib/checkout.ts");
+    TestAssertions::assert_output_not_contains(&stdout, "checkout.spec.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_include_and_exclude_globs() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("components/LargeComponent", SampleFiles::large_component())?;
+    project.create_ts_file("generated/LargeSchema", SampleFiles::large_component())?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--threshold", "50", "--exclude", "generated/**"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "LargeComponent.ts");
+    TestAssertions::assert_output_not_contains(&stdout, "LargeSchema.ts");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--threshold", "50", "--include", "LargeSchema.ts"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "LargeSchema.ts");
+    TestAssertions::assert_output_not_contains(&stdout, "LargeComponent.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_applies_package_config_overrides() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_dir(".git")?;
+    project.create_file("sniff.toml", "[large_files]\nthreshold = 1000\nfunction_threshold = 1000\n")?;
+    project.create_file("packages/design-system/sniff.toml", "[large_files]\nthreshold = 50\n")?;
+    project.create_ts_file("apps/web/components/Dashboard", SampleFiles::large_component())?;
+    project.create_ts_file("packages/design-system/components/Button", SampleFiles::large_component())?;
+
+    // From the monorepo root, only the design system's stricter threshold flags its file
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "Button.ts");
+    TestAssertions::assert_output_not_contains(&stdout, "Dashboard.ts");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "apps/web"])?;
+    TestAssertions::assert_success(&output);
+
+    // --config replaces the discovered files
+    project.create_file("strict.toml", "[large_files]\nthreshold = 50\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "apps/web", "--config", "strict.toml"])?;
+    TestAssertions::assert_failure(&output, Some(3));
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "Dashboard.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_fails_on_a_config_that_does_not_load() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("components/LargeComponent", SampleFiles::large_component())?;
+
+    // A wrong type and a broken table header must not fall back to the defaults
+    for (content, expected) in [
+        ("[large_files]\nthreshold = \"ten\"\n", "sniff.toml:2:1 large_files.threshold: invalid type"),
+        ("[large_files\nthreshold = 50\n", "sniff.toml:1:13 invalid table header"),
+    ] {
+        project.create_file("sniff.toml", content)?;
+        let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large"])?;
+        TestAssertions::assert_failure(&output, Some(1));
+        TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_reads_env_overrides() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("sniff.toml", "[large_files]\nthreshold = 1000\n")?;
+    project.create_ts_file("components/LargeComponent", SampleFiles::large_component())?;
+
+    // Environment beats the config file
+    let env = [("SNIFF_LARGE_FILES_THRESHOLD", "50"), ("SNIFF_OUTPUT", "json")];
+    let output = CommandRunner::run_sniff_command_with_env(&project.root_path, &["large"], &env)?;
+    TestAssertions::assert_failure(&output, Some(3));
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    assert_eq!(json["data"]["files"][0]["threshold"], 50);
+
+    // and command-line flags beat the environment
+    let output = CommandRunner::run_sniff_command_with_env(&project.root_path, &["large", "--threshold", "1000", "--format", "text"], &env)?;
+    TestAssertions::assert_success(&output);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "Files scanned");
+
+    // A variable of the wrong type is an error, not a reason to drop the config file
+    project.create_file("sniff.toml", "[large_files]\nthreshold = 50\n")?;
+    let output = CommandRunner::run_sniff_command_with_env(&project.root_path, &["large", "--json"], &[("SNIFF_LARGE_FILES_WARNING", "big")])?;
+    TestAssertions::assert_failure(&output, Some(1));
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "SNIFF_LARGE_FILES_WARNING must be an integer");
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_logs_to_stderr_only() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("components/LargeComponent", SampleFiles::large_component())?;
+
+    // -vv adds progress and timing logs without touching the JSON on stdout
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "-vv", "large", "--threshold", "1000"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["command"], "large");
+    let stderr = String::from_utf8(output.stderr)?;
+    TestAssertions::assert_output_contains(&stderr, "Running large file analysis");
+    TestAssertions::assert_output_contains(&stderr, "Performance checkpoint");
+
+    // Without -v only warnings are logged
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--threshold", "1000"])?;
+    TestAssertions::assert_output_not_contains(&String::from_utf8(output.stderr)?, "Running large file analysis");
+
+    // --log-format json writes one object per message, filtered by SNIFF_LOG
+    let output = CommandRunner::run_sniff_command_with_env(
+        &project.root_path,
+        &["large", "--threshold", "1000", "--log-format", "json"],
+        &[("SNIFF_LOG", "sniff::commands::large=debug")],
+    )?;
+    let stderr = String::from_utf8(output.stderr)?;
+    let lines: Vec<serde_json::Value> = stderr.lines().map(serde_json::from_str).collect::<Result<_, _>>()?;
+    assert!(!lines.is_empty());
+    assert!(lines.iter().all(|line| line["target"] == "sniff::commands::large"));
+    assert!(lines.iter().any(|line| line["fields"]["files"] == 1));
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_groups_files_by_directory() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let lines = |count: usize| (0..count).map(|i| format!("export const value{} = {};", i, i)).collect::<Vec<_>>().join("\n");
+    project.create_ts_file("legacy/a", &lines(180))?;
+    project.create_ts_file("legacy/b", &lines(150))?;
+    project.create_ts_file("app/c", &lines(450))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--group-by", "directory"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let legacy = stdout.find("legacy/ (2 findings, 330 lines)").expect("expected a legacy group");
+    let app = stdout.find("app/ (1 finding, 450 lines)").expect("expected an app group");
+    assert!(legacy < app, "expected the directory with more large files first:\n{}", stdout);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "--sort", "lines"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let order: Vec<usize> = ["app/c.ts", "legacy/a.ts", "legacy/b.ts"].iter()
+        .map(|file| stdout.find(file).unwrap_or_else(|| panic!("expected {} in:\n{}", file, stdout)))
+        .collect();
+    assert!(order[0] < order[1] && order[1] < order[2], "expected longest first:\n{}", stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_rolls_lines_up_per_directory() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let lines = |count: usize| (0..count).map(|i| format!("export const value{} = {};", i, i)).collect::<Vec<_>>().join("\n");
+    project.create_ts_file("features/checkout/cart", &lines(250))?;
+    project.create_ts_file("features/profile", &lines(40))?;
+    project.create_ts_file("lib/format", &lines(30))?;
+    project.create_ts_file("index", &lines(5))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large", "--min-dir-lines", "10"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let directories = json["data"]["directories"].as_array().unwrap();
+    let paths: Vec<&str> = directories.iter().map(|dir| dir["path"].as_str().unwrap()).collect();
+    assert_eq!(paths, vec!["features", "lib"]);
+    assert_eq!(directories[0]["files"], 2);
+    assert_eq!(directories[0]["lines"], 290);
+    assert_eq!(directories[0]["large_files"], 1);
+    assert_eq!(directories[0]["large_file_lines"], 250);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large", "features", "--by-dir"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "LINES BY DIRECTORY");
+    TestAssertions::assert_output_contains(&stdout, "features/checkout/");
+    TestAssertions::assert_output_contains(&stdout, "1 file, 1 large");
+    assert!(!stdout.contains("cart.ts"), "expected directories instead of files:\n{}", stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_skips_generated_files() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let lines = |count: usize| (0..count).map(|i| format!("export const value{} = {};", i, i)).collect::<Vec<_>>().join("\n");
+    project.create_ts_file("lib/checkout", &lines(150))?;
+    project.create_file("types/api.d.ts", &lines(300))?;
+    project.create_ts_file("gql/graphql", &lines(300))?;
+    project.create_ts_file("lib/schema.generated", &lines(300))?;
+    project.create_ts_file("lib/client", &format!("/* eslint-disable */\n// @generated by openapi-codegen\n{}", lines(300)))?;
+    project.create_ts_file("lib/rules", &format!("/* eslint-disable no-console */\n{}", lines(150)))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let mut paths: Vec<&str> = json["data"]["files"].as_array().unwrap().iter().map(|file| file["path"].as_str().unwrap()).collect();
+    paths.sort();
+    assert_eq!(paths, vec!["lib/checkout.ts", "lib/rules.ts"]);
+    assert_eq!(json["data"]["summary"]["total_files_scanned"], 2);
+    assert_eq!(json["data"]["summary"]["generated_files_skipped"], 4);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["large"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "Generated files skipped: 4");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large", "--include-generated"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    // The declaration file stays under the type definition limit
+    assert_eq!(json["data"]["summary"]["total_files_scanned"], 6);
+    assert_eq!(json["data"]["summary"]["large_files_found"], 5);
+    assert_eq!(json["data"]["summary"]["generated_files_skipped"], 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_large_command_counts_code_lines() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let imports: String = (0..20).map(|i| format!("import {{ helper{} }} from './helpers/{}';\n", i, i)).collect();
+    let docs: String = (0..60).map(|i| format!("/**\n * Formats value {}.\n */\n\n", i)).collect();
+    let code: String = (0..60).map(|i| format!("export const value{} = {};\n", i, i)).collect();
+    project.create_ts_file("lib/documented", &format!("{}{}{}", imports, docs, code))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["files"][0]["lines"], 320);
+
+    project.create_file("sniff.toml", "[large_files]\ncount_mode = \"code\"\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["summary"]["large_files_found"], 0);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large", "--threshold", "50"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["files"][0]["lines"], 60);
+
+    Ok(())
+}
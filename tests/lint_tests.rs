@@ -0,0 +1,93 @@
+/// Integration tests for the lint command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+fn eslint_report(project: &TestProject) -> String {
+    let file = project.root_path.join("src/app.ts");
+    serde_json::json!([
+        {
+            "filePath": file,
+            "messages": [
+                { "ruleId": "no-unused-vars", "severity": 2, "message": "'user' is assigned a value but never used.", "line": 4, "column": 9 },
+                { "ruleId": "no-console", "severity": 1, "message": "Unexpected console statement.", "line": 7, "column": 3 }
+            ]
+        },
+        { "filePath": project.root_path.join("src/clean.ts"), "messages": [] }
+    ]).to_string()
+}
+
+#[test]
+fn test_lint_command_ingests_an_eslint_report() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("eslint-report.json", &eslint_report(&project))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "lint", "--ingest", "eslint-report.json"])?;
+    // Lint errors fail the command, like the linter itself
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    assert_eq!(json["data"]["linter"], "eslint");
+    let findings = json["data"]["findings"].as_array().unwrap();
+    let rules: Vec<(&str, &str)> = findings.iter()
+        .map(|f| (f["file"].as_str().unwrap(), f["rule"].as_str().unwrap()))
+        .collect();
+    assert_eq!(rules, vec![("src/app.ts", "eslint/no-unused-vars"), ("src/app.ts", "eslint/no-console")]);
+    assert_eq!(json["data"]["summary"]["files_linted"], 2);
+    assert_eq!(json["data"]["summary"]["errors"], 1);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["lint", "--ingest", "eslint-report.json", "--format", "sarif"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "\"ruleId\": \"eslint/no-unused-vars\"");
+    TestAssertions::assert_output_contains(&stdout, "\"uri\": \"src/app.ts\"");
+
+    // Ignored lint errors no longer fail the command
+    project.create_file("sniff.toml", "[[ignore]]\nrule = \"eslint/no-unused-vars\"\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "lint", "--ingest", "eslint-report.json"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    assert_eq!(json["data"]["summary"]["ignored"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_lint_report_from_config_gates_deploy() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("biome-report.json", r#"{"diagnostics": [
+        {"category": "lint/suspicious/noDebugger", "severity": "error", "description": "This is an unexpected use of the debugger statement.",
+         "location": {"path": {"file": "src/app.ts"}, "span": [13, 22], "sourceCode": "const a = 1;\ndebugger;\n"}}
+    ]}"#)?;
+    project.create_file("sniff.toml", "[lint]\nreport = \"biome-report.json\"\n\n[deploy.gates]\nlint = \"block\"\nenv = \"warn\"\nmemory = \"off\"\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["lint", "--format", "github"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "::error file=src/app.ts,line=2");
+    TestAssertions::assert_output_contains(&stdout, "biome/suspicious/noDebugger");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "deploy"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    let lint_check = json["data"]["checks"].as_array().unwrap().iter().find(|c| c["name"] == "lint").unwrap();
+    assert_eq!(lint_check["status"], "Failed");
+    assert_eq!(lint_check["detail"], "1 lint error");
+
+    Ok(())
+}
+
+#[test]
+fn test_lint_command_without_a_linter_asks_for_a_report() -> Result<()> {
+    let project = TestProject::new()?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["lint"])?;
+    assert!(!output.status.success());
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "--ingest");
+
+    Ok(())
+}
@@ -0,0 +1,89 @@
+/// Integration tests for the language server
+mod common;
+
+use common::{TestProject, CommandRunner};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{ChildStdin, ChildStdout, Command, Stdio};
+
+fn send(stdin: &mut ChildStdin, message: Value) -> Result<()> {
+    let body = message.to_string();
+    write!(stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    stdin.flush()?;
+    Ok(())
+}
+
+fn receive(stdout: &mut BufReader<ChildStdout>) -> Result<Value> {
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        stdout.read_line(&mut header)?;
+        let header = header.trim();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse()?;
+        }
+    }
+    let mut body = vec![0; length];
+    stdout.read_exact(&mut body)?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// The next diagnostics the server publishes, as (code, line) pairs
+fn diagnostics(stdout: &mut BufReader<ChildStdout>) -> Result<Vec<(String, u64)>> {
+    let message = receive(stdout)?;
+    assert_eq!(message["method"], "textDocument/publishDiagnostics");
+    Ok(message["params"]["diagnostics"].as_array().unwrap().iter()
+        .map(|diagnostic| (diagnostic["code"].as_str().unwrap().to_string(), diagnostic["range"]["start"]["line"].as_u64().unwrap()))
+        .collect())
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_for_open_documents() -> Result<()> {
+    let project = TestProject::new()?;
+    let file = project.create_ts_file("src/poller", "export {};\n")?;
+    project.create_ts_file("src/format", "export const format = (value: unknown) => String(value);\n")?;
+    let root_uri = format!("file://{}", project.root_path.display());
+    let uri = format!("file://{}", file.display());
+
+    let mut server = Command::new(CommandRunner::sniff_binary()?)
+        .arg("lsp")
+        .current_dir(&project.root_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut stdin = server.stdin.take().unwrap();
+    let mut stdout = BufReader::new(server.stdout.take().unwrap());
+
+    send(&mut stdin, json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"rootUri": root_uri, "capabilities": {}}}))?;
+    let initialized = receive(&mut stdout)?;
+    assert_eq!(initialized["result"]["capabilities"]["textDocumentSync"]["change"], 1);
+    send(&mut stdin, json!({"jsonrpc": "2.0", "method": "initialized", "params": {}}))?;
+    // A notification with params the server cannot read is skipped without ending the session
+    send(&mut stdin, json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {"textDocument": {"uri": uri}}}))?;
+
+    // The unsaved text is analyzed, not the file on disk
+    let text = "import { format } from './format';\nexport function poll(data: any) {\n  setInterval(() => poll(data), 1000);\n}\n";
+    send(&mut stdin, json!({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+        "textDocument": {"uri": uri, "languageId": "typescript", "version": 1, "text": text}
+    }}))?;
+    let mut found = diagnostics(&mut stdout)?;
+    found.sort();
+    assert_eq!(found, vec![("SNF-IMP-UNUSED".to_string(), 0), ("SNF-MEM-TIMER".to_string(), 2), ("SNF-TYPES-ANY".to_string(), 1)]);
+
+    send(&mut stdin, json!({"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+        "textDocument": {"uri": uri, "version": 2},
+        "contentChanges": [{"text": "export function poll(data: unknown) {\n  return data;\n}\n"}]
+    }}))?;
+    assert!(diagnostics(&mut stdout)?.is_empty());
+
+    send(&mut stdin, json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}))?;
+    assert_eq!(receive(&mut stdout)?["id"], 2);
+    send(&mut stdin, json!({"jsonrpc": "2.0", "method": "exit"}))?;
+    assert!(server.wait()?.success());
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+/// Integration tests for the memory command's React cleanup patterns
+mod common;
+
+use common::{TestProject, CommandRunner};
+use anyhow::Result;
+
+fn memory_patterns(project: &TestProject) -> Result<Vec<serde_json::Value>> {
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "--quiet", "memory", "."])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Failed to parse JSON output");
+    Ok(json["data"]["patterns"].as_array().cloned().unwrap_or_default())
+}
+
+fn flagged(patterns: &[serde_json::Value], file: &str, pattern_type: &str) -> bool {
+    patterns.iter().any(|p| {
+        p["file_path"].as_str().is_some_and(|f| f.ends_with(file)) && p["pattern_type"] == pattern_type
+    })
+}
+
+#[test]
+fn test_memory_flags_effects_without_cleanup() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("components/Leaky.tsx", r#"
+export function Leaky({ id }: { id: string }) {
+  useEffect(() => {
+    fetch(`/api/items/${id}`).then(res => res.json()).then(setItem);
+    const sub = prices$.subscribe(setPrice);
+    const observer = new IntersectionObserver(onVisible);
+    observer.observe(ref.current);
+  }, [id]);
+  return null;
+}
+"#)?;
+
+    let patterns = memory_patterns(&project)?;
+    assert!(flagged(&patterns, "Leaky.tsx", "UnabortedFetch"), "{:#?}", patterns);
+    assert!(flagged(&patterns, "Leaky.tsx", "UnclosedSubscription"), "{:#?}", patterns);
+    assert!(flagged(&patterns, "Leaky.tsx", "UndisconnectedObserver"), "{:#?}", patterns);
+
+    Ok(())
+}
+
+#[test]
+fn test_memory_accepts_cleaned_up_effects() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("components/Clean.tsx", r#"
+export function Clean({ id }: { id: string }) {
+  useEffect(() => {
+    const controller = new AbortController();
+    fetch(`/api/items/${id}`, { signal: controller.signal }).then(res => res.json()).then(setItem);
+    const unsubscribe = useStore.subscribe(setCount);
+    const observer = new ResizeObserver(onResize);
+    observer.observe(ref.current);
+    return () => {
+      controller.abort();
+      unsubscribe();
+      observer.disconnect();
+    };
+  }, [id]);
+
+  const onClick = () => fetch('/api/track', { method: 'POST' });
+  return null;
+}
+"#)?;
+
+    let patterns = memory_patterns(&project)?;
+    assert!(!flagged(&patterns, "Clean.tsx", "UnabortedFetch"), "{:#?}", patterns);
+    assert!(!flagged(&patterns, "Clean.tsx", "UnclosedSubscription"), "{:#?}", patterns);
+    assert!(!flagged(&patterns, "Clean.tsx", "UndisconnectedObserver"), "{:#?}", patterns);
+
+    Ok(())
+}
+
+#[test]
+fn test_memory_reports_paths_relative_to_the_project_root() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("components/Timer.tsx", "export function start() {\n  setInterval(() => tick(), 1000);\n}\n")?;
+
+    // Run from outside the project, as CI does with --root
+    let parent = project.root_path.parent().unwrap();
+    let name = project.root_path.file_name().unwrap().to_str().unwrap();
+    let output = CommandRunner::run_sniff_command_in_dir(parent, &["--json", "--quiet", "memory", "--root", name])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["patterns"][0]["file_path"], "components/Timer.tsx", "{:#?}", json["data"]);
+    assert_eq!(json["findings"][0]["file"], "components/Timer.tsx");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "--quiet", "--absolute-paths", "memory"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let file = json["findings"][0]["file"].as_str().unwrap();
+    assert!(std::path::Path::new(file).is_absolute() && file.ends_with("components/Timer.tsx"), "{}", file);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+/// Integration tests for the nextjs command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "dependencies": { "next": "14.2.0", "react": "18.3.1" }
+}
+"#;
+
+fn create_app(project: &TestProject) -> Result<()> {
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("app/layout.tsx", "export default function RootLayout({ children }) {\n  return <html><body>{children}</body></html>;\n}\n")?;
+    project.create_file("app/blog/page.tsx", "export default async function Blog() {\n  const posts = await fetch('https://cms.example.com/posts').then((res) => res.json());\n  const tags = await fetch('https://cms.example.com/tags', { cache: 'force-cache' });\n  const views = await fetch('https://stats.example.com/views', { next: { revalidate: 60 } });\n  return <ul>{posts.map((post) => <li key={post.id}>{post.title}</li>)}</ul>;\n}\n")?;
+    project.create_file("pages/index.tsx", "export async function getServerSideProps() {\n  const posts = await getPosts();\n  return { props: { posts } };\n}\n\nexport default function Home({ posts }) {\n  return <p>{posts.length}</p>;\n}\n")?;
+    project.create_file("pages/account.tsx", "export async function getServerSideProps({ req }) {\n  const user = await getUser(req.cookies.session);\n  return { props: { user } };\n}\n\nexport default function Account({ user }) {\n  return <p>{user.name}</p>;\n}\n")?;
+    project.create_file("pages/about.tsx", "export async function getStaticProps() {\n  const team = await getTeam();\n  return { props: { team } };\n}\n\nexport default function About({ team }) {\n  return <p>{team.length}</p>;\n}\n")?;
+    project.create_file("pages/api/config.ts", "export default function handler(req, res) {\n  res.status(200).json({ theme: 'dark' });\n}\n")?;
+    project.create_file("pages/api/me.ts", "export default function handler(req, res) {\n  res.status(200).json({ id: req.headers['x-user'] });\n}\n")?;
+    project.create_file("public/data/plans.json", "[]\n")?;
+    project.create_file("components/Settings.tsx", "'use client';\nimport { useEffect, useState } from 'react';\n\nexport function Settings() {\n  const [config, setConfig] = useState(null);\n  useEffect(() => {\n    fetch('/api/config').then((res) => res.json()).then(setConfig);\n    fetch('/data/plans.json').then((res) => res.json()).then(setConfig);\n    fetch('/api/me').then((res) => res.json()).then(setConfig);\n  }, []);\n  return <p>{config?.theme}</p>;\n}\n")?;
+    Ok(())
+}
+
+#[test]
+fn test_nextjs_command_reports_data_fetching_issues() -> Result<()> {
+    let project = TestProject::new()?;
+    create_app(&project)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "nextjs"])?;
+    TestAssertions::assert_success(&output);
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let data = &json["data"];
+    assert_eq!(data["next_version"], "14.2.0");
+    assert_eq!(data["routers"], serde_json::json!(["app", "pages"]));
+    assert_eq!(data["summary"]["server_fetches"], 3);
+    let issues: Vec<(&str, &str, u64)> = data["issues"].as_array().unwrap().iter()
+        .map(|i| (i["kind"].as_str().unwrap(), i["file"].as_str().unwrap(), i["line"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(issues, vec![
+        ("FetchCache", "app/blog/page.tsx", 2),
+        ("MissingRevalidate", "app/blog/page.tsx", 3),
+        ("ClientFetch", "components/Settings.tsx", 7),
+        ("ClientFetch", "components/Settings.tsx", 8),
+        ("MissingRevalidate", "pages/about.tsx", 1),
+        ("SsrWithoutRequest", "pages/index.tsx", 1),
+    ]);
+    TestAssertions::assert_output_contains(data["issues"][2]["message"].as_str().unwrap(), "pages/api/config.ts returns the same response for every request");
+
+    // A segment-level revalidate covers the fetches of the page
+    project.create_file("app/blog/layout.tsx", "export const revalidate = 300;\n\nexport default function BlogLayout({ children }) {\n  return <section>{children}</section>;\n}\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["nextjs", "--format", "github"])?;
+    TestAssertions::assert_success(&output);
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "::warning file=pages/index.tsx,line=1,title=SsrWithoutRequest [SNF-NEXT-SSR-STATIC]");
+    assert!(!stdout.contains("app/blog/page.tsx"), "segment revalidate should cover the fetches: {}", stdout);
+
+    Ok(())
+}
+
+#[test]
+fn test_nextjs_command_requires_next() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", r#"{ "name": "site", "dependencies": { "react": "18.3.1" } }"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["nextjs"])?;
+    TestAssertions::assert_failure(&output, None);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "No next dependency");
+
+    Ok(())
+}
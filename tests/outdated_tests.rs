@@ -0,0 +1,118 @@
+/// Integration tests for the outdated command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use serde_json::json;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "dependencies": {
+    "react": "^17.0.2",
+    "lodash": "4.17.0",
+    "left-pad": "^1.1.0",
+    "ui-kit": "workspace:*"
+  },
+  "devDependencies": {
+    "tiny-lib": "~0.3.1"
+  }
+}
+"#;
+
+/// Package documents served with curl from a `file://` registry
+fn create_registry(project: &TestProject) -> Result<String> {
+    let document = |latest: &str, published: &str, versions: serde_json::Value| json!({
+        "dist-tags": { "latest": latest },
+        "time": { latest: published, "17.0.2": "2021-03-22T21:56:19.536Z" },
+        "versions": versions,
+    }).to_string();
+    project.create_file("registry/react", &document("19.1.0", "2025-03-28T19:59:42.053Z", json!({ "19.1.0": { "maintainers": [{}, {}], "repository": {} } })))?;
+    project.create_file("registry/lodash", &document("4.17.21", "2021-02-20T15:42:16.891Z", json!({})))?;
+    project.create_file("registry/left-pad", &document("1.3.0", "2018-04-09T01:39:23.000Z", json!({ "1.1.0": { "deprecated": "use String.prototype.padStart()" } })))?;
+    project.create_file("registry/tiny-lib", &document("0.4.0", "2024-11-02T10:00:00.000Z", json!({})))?;
+
+    let registry = project.root_path.join("registry");
+    Ok(format!("file://{}", registry.to_string_lossy().replace('\\', "/")))
+}
+
+#[test]
+fn test_outdated_command_reports_upgrade_risk_from_the_registry() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    let registry = create_registry(&project)?;
+    project.create_file("sniff.toml", &format!("[outdated]\nregistry = \"{}\"\n", registry))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "outdated"])?;
+    // left-pad 1.1.0 is deprecated
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let packages = json["data"]["packages"].as_array().unwrap();
+    let distances: Vec<(&str, &str, &str)> = packages.iter()
+        .map(|p| (p["name"].as_str().unwrap(), p["latest"].as_str().unwrap(), p["distance"].as_str().unwrap()))
+        .collect();
+    assert_eq!(distances, vec![
+        ("left-pad", "1.3.0", "minor"),
+        ("lodash", "4.17.21", "patch"),
+        ("react", "19.1.0", "major"),
+        ("tiny-lib", "0.4.0", "major"),
+    ]);
+    assert_eq!(packages[2]["majors_behind"], 2);
+    assert_eq!(packages[2]["maintainers"], 2);
+    assert_eq!(packages[0]["deprecated"], "use String.prototype.padStart()");
+    // No release since 2018 or 2021
+    assert_eq!(packages[0]["stale"], true);
+    assert_eq!(json["data"]["summary"]["deprecated"], 1);
+    assert_eq!(json["data"]["summary"]["major"], 2);
+
+    let rules: Vec<&str> = json["findings"].as_array().unwrap().iter().filter_map(|f| f["rule"].as_str()).collect();
+    assert!(rules.contains(&"SNF-OUTDATED-DEPRECATED"));
+    assert!(rules.contains(&"SNF-OUTDATED-MAJOR"));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["outdated", "--format", "github"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "::error file=package.json,line=6,title=left-pad@1.1.0 is deprecated");
+    TestAssertions::assert_output_contains(&stdout, "::warning file=package.json,line=4,title=react 17.0.2 → 19.1.0");
+
+    // The registry data of this run is what --no-network reads
+    std::fs::remove_dir_all(project.root_path.join("registry"))?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "outdated", "--no-network"])?;
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    assert_eq!(json["data"]["offline"], true);
+    assert_eq!(json["data"]["packages"][2]["source"], "cache");
+    assert_eq!(json["data"]["packages"][2]["distance"], "major");
+
+    Ok(())
+}
+
+#[test]
+fn test_outdated_command_offline_reads_the_lockfile() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("package-lock.json", r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "node_modules/react": { "version": "17.0.2" },
+    "node_modules/left-pad": { "version": "1.1.3", "deprecated": "use String.prototype.padStart()" }
+  }
+}"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "outdated", "--no-network"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    let packages = json["data"]["packages"].as_array().unwrap();
+    assert_eq!(packages.len(), 4);
+    assert_eq!(packages[0]["installed"], "1.1.3");
+    assert_eq!(packages[0]["source"], "lockfile");
+    assert_eq!(packages[0]["deprecated"], "use String.prototype.padStart()");
+    assert_eq!(json["data"]["summary"]["unknown"], 4);
+
+    project.create_file("sniff.toml", "[outdated]\nfail_on_deprecated = false\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["outdated", "--no-network"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
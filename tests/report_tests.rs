@@ -0,0 +1,42 @@
+/// Integration tests for the report command
+mod common;
+
+use common::{TestProject, SampleFiles, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+#[test]
+fn test_report_scores_configured_analyses_and_writes_html() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_dir("node_modules/react")?;
+    project.create_file("package.json", r#"{ "name": "demo-app" }"#)?;
+    project.create_ts_file("components/SimpleComponent", SampleFiles::file_with_unused_imports())?;
+    // The repo config excludes `tmp`, which is where test projects live
+    let config = std::fs::read_to_string("sniff.toml")?
+        .replace("    \"tmp\",\n", "")
+        .replace(
+            "analyses = [\"large\", \"imports\", \"types\", \"memory\", \"context\"]",
+            "analyses = [\"imports\", \"bundle\"]",
+        );
+    project.create_file("sniff.toml", &config)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "report", "--output", "out/health.html"])?;
+    TestAssertions::assert_success(&output);
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let report = &json["data"];
+    assert_eq!(report["project"], "demo-app");
+    let categories = report["categories"].as_array().unwrap();
+    assert_eq!(categories.len(), 2);
+    assert_eq!(categories[0]["name"], "imports");
+    assert!(categories[0]["score"].as_u64().unwrap() < 100);
+    // No build output: bundle is skipped and left out of the overall score
+    assert_eq!(categories[1]["name"], "bundle");
+    assert!(categories[1]["score"].is_null());
+    assert_eq!(report["score"], categories[0]["score"]);
+
+    let html = std::fs::read_to_string(project.path("out/health.html"))?;
+    assert!(html.contains("<title>demo-app health report</title>"));
+    assert!(html.contains("<td>imports</td>"));
+
+    Ok(())
+}
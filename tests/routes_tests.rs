@@ -0,0 +1,83 @@
+/// Integration tests for the routes command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+#[test]
+fn test_routes_lists_pages_api_routes_and_middleware() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", r#"{ "name": "routes-app", "dependencies": { "next": "14.0.0" } }"#)?;
+    project.create_file("app/(shop)/products/[id]/page.tsx", "'use client';\nexport default function Product() { return null }\n")?;
+    project.create_file("app/dashboard/page.tsx", "import { getServerSession } from 'next-auth';\nexport default async function Dashboard() { await getServerSession(); return null }\n")?;
+    project.create_file("app/api/orders/route.ts", "export const GET = withAuth(async () => {});\nexport async function POST() {}\n")?;
+    project.create_file("pages/blog/[...slug].tsx", "export async function getStaticProps() { return { props: {} } }\nexport default function Post() { return null }\n")?;
+    project.create_file("middleware.ts", "import { withAuth } from 'next-auth/middleware';\nexport default withAuth({});\nexport const config = { matcher: ['/dashboard/:path*'] };\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "routes"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let report = &json["data"];
+
+    let pages = report["pages"].as_array().unwrap();
+    let routes: Vec<&str> = pages.iter().map(|page| page["route"].as_str().unwrap()).collect();
+    assert_eq!(routes, vec!["/blog/[...slug]", "/dashboard", "/products/[id]"]);
+    assert_eq!(pages[0]["rendering"], "Ssg");
+    assert_eq!(pages[0]["dynamic_segments"], serde_json::json!(["[...slug]"]));
+    assert_eq!(pages[1]["side"], "Server");
+    assert_eq!(pages[1]["auth"], serde_json::json!(["middleware.ts", "getServerSession"]));
+    assert_eq!(pages[2]["side"], "Client");
+    assert_eq!(pages[2]["route_groups"], serde_json::json!(["shop"]));
+
+    let api_routes = report["api_routes"].as_array().unwrap();
+    assert_eq!(api_routes[0]["route"], "/api/orders");
+    assert_eq!(api_routes[0]["methods"], serde_json::json!(["GET", "POST"]));
+    assert_eq!(api_routes[0]["auth"], serde_json::json!(["withAuth"]));
+
+    assert_eq!(report["middleware"]["matchers"], serde_json::json!(["/dashboard/:path*"]));
+    assert_eq!(report["middleware"]["auth"], true);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["routes"])?;
+    TestAssertions::assert_success(&output);
+    let text = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&text, "📄 PAGES (3)");
+    TestAssertions::assert_output_contains(&text, "4 routes · 2 dynamic · 2 with auth");
+
+    Ok(())
+}
+
+#[test]
+fn test_routes_prints_only_ascii_for_non_utf8_terminals() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("app/page.tsx", "export default function Home() { return null }\n")?;
+    project.create_file("app/blog/[slug]/page.tsx", "export default function Post() { return null }\n")?;
+
+    let forced = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--ascii", "routes"])?;
+    let from_locale = CommandRunner::run_sniff_command_with_env(&project.root_path, &["routes"], &[("LC_ALL", "C")])?;
+    for output in [forced, from_locale] {
+        TestAssertions::assert_success(&output);
+        let text = String::from_utf8(output.stdout)?;
+        assert!(text.is_ascii(), "expected only ASCII:\n{}", text);
+        TestAssertions::assert_output_contains(&text, "PAGES (2)");
+        TestAssertions::assert_output_contains(&text, "2 routes - 1 dynamic - 0 with auth");
+    }
+
+    let output = CommandRunner::run_sniff_command_with_env(&project.root_path, &["--ascii=false", "routes"], &[("LC_ALL", "C")])?;
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "📄 PAGES (2)");
+
+    Ok(())
+}
+
+#[test]
+fn test_routes_rejects_findings_options() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("app/page.tsx", "export default function Home() { return null }\n")?;
+
+    for args in [&["routes", "--format", "sarif"][..], &["routes", "--fail-on", "error"], &["routes", "--top", "5"]] {
+        let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, args)?;
+        TestAssertions::assert_failure(&output, Some(1));
+        TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "needs a command that reports findings; `sniff routes`");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,165 @@
+/// Integration tests for the rules command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const RULES: &str = r#"
+[[rules]]
+name = "no-moment"
+import = "moment"
+severity = "high"
+message = "moment is deprecated here"
+suggestion = "Use date-fns"
+
+[[rules]]
+name = "use-logger"
+pattern = '\bconsole\.(log|info)\('
+severity = "low"
+message = "Log through lib/logger"
+files = ["src/**"]
+exclude = ["src/lib/logger.ts"]
+
+[[rules]]
+name = "no-legacy-fetch"
+call = "api.v1.fetch"
+message = "api.v1 is going away"
+"#;
+
+#[test]
+fn test_rules_command_reports_config_rules() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("sniff.toml", RULES)?;
+
+    project.create_ts_file("src/dates", r#"import moment from 'moment';
+// import 'moment/locale/de';
+export const today = () => moment().format();
+export const users = () => api.v1.fetch('/users');
+console.log('dates loaded');
+"#)?;
+    project.create_ts_file("src/lib/logger", "export const log = (...args: unknown[]) => console.log(...args);\n")?;
+    project.create_ts_file("scripts/seed", "console.log('seeding');\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "rules"])?;
+    // A high-severity finding fails the check
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let findings: Vec<(&str, &str, u64)> = json["data"]["findings"].as_array().unwrap().iter()
+        .map(|finding| (finding["rule"].as_str().unwrap(), finding["file"].as_str().unwrap(), finding["line"].as_u64().unwrap()))
+        .collect();
+    assert_eq!(findings, vec![
+        ("no-moment", "src/dates.ts", 1),
+        ("no-legacy-fetch", "src/dates.ts", 4),
+        ("use-logger", "src/dates.ts", 5),
+    ]);
+    assert_eq!(json["data"]["findings"][0]["suggestion"], "Use date-fns");
+    assert_eq!(json["data"]["summary"]["rules"], 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_rules_command_passes_without_high_findings() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_ts_file("src/app", "console.log('hi');\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["rules"])?;
+    TestAssertions::assert_success(&output);
+    TestAssertions::assert_output_contains(&String::from_utf8_lossy(&output.stdout), "No custom rules configured");
+
+    project.create_file("sniff.toml", RULES)?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["rules"])?;
+    TestAssertions::assert_success(&output);
+    TestAssertions::assert_output_contains(&String::from_utf8_lossy(&output.stdout), "use-logger");
+
+    Ok(())
+}
+
+#[test]
+fn test_rules_command_merges_wasm_plugin_findings() -> Result<()> {
+    let project = TestProject::new()?;
+    // The text format is accepted too; this plugin flags line 2 of every file it is given
+    project.create_file("plugins/acme.wat", r#"(module
+  (memory (export "memory") 1)
+  (data (i32.const 0) "[{\"rule\":\"no-legacy-store\",\"line\":2,\"severity\":\"high\",\"message\":\"Use the new store\"}]")
+  (func (export "sniff_alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "sniff_check") (param i32 i32) (result i64) i64.const 85))"#)?;
+    project.create_file("sniff.toml", "[[plugins]]\npath = \"plugins/acme.wat\"\nfiles = [\"src/**\"]\n")?;
+    project.create_ts_file("src/store", "import { legacy } from './legacy';\nexport const store = legacy();\n")?;
+    project.create_ts_file("scripts/seed", "export {};\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "rules"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let findings = json["data"]["findings"].as_array().unwrap();
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0]["rule"], "acme/no-legacy-store");
+    assert_eq!(findings[0]["file"], "src/store.ts");
+    assert_eq!(findings[0]["text"], "export const store = legacy();");
+    assert_eq!(json["data"]["summary"]["plugins"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_rules_list_shows_builtin_and_config_rules() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("sniff.toml", RULES)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "rules", "list"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let rules = json["data"]["rules"].as_array().unwrap();
+    let timer = rules.iter().find(|rule| rule["id"] == "SNF-MEM-TIMER").unwrap();
+    assert_eq!(timer["command"], "memory");
+    assert_eq!(timer["default_level"], "error");
+    let custom = rules.iter().find(|rule| rule["id"] == "no-moment").unwrap();
+    assert_eq!(custom["command"], "rules");
+    assert_eq!(custom["description"], "moment is deprecated here");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["rules", "list", "--command", "large"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "SNF-LARGE-001");
+    TestAssertions::assert_output_contains(&stdout, "SNF-LARGE-002");
+    TestAssertions::assert_output_not_contains(&stdout, "SNF-MEM-TIMER");
+
+    // Findings carry the id in every format
+    project.create_ts_file("src/big", &"export const x = 1;\n".repeat(120))?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--format", "sarif", "large"])?;
+    let sarif: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(sarif["runs"][0]["results"][0]["ruleId"], "SNF-LARGE-001");
+    assert_eq!(sarif["runs"][0]["tool"]["driver"]["rules"][0]["defaultConfiguration"]["level"], "warning");
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "large"])?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["findings"][0]["rule"], "SNF-LARGE-001");
+
+    Ok(())
+}
+
+#[test]
+fn test_explain_command_describes_builtin_and_config_rules() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("sniff.toml", RULES)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["explain", "snf-mem-timer"])?;
+    TestAssertions::assert_success(&output);
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "SNF-MEM-TIMER");
+    TestAssertions::assert_output_contains(&stdout, "Why it matters");
+    TestAssertions::assert_output_contains(&stdout, "clearInterval(id)");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "explain", "no-moment"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(json["data"]["command"], "rules");
+    assert_eq!(json["data"]["default_level"], "error");
+    assert_eq!(json["data"]["remediation"][0], "Use date-fns");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["explain", "timer"])?;
+    TestAssertions::assert_failure(&output, Some(1));
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stderr)?, "did you mean SNF-MEM-TIMER");
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+/// Integration tests for the security command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+#[test]
+fn test_security_command_reports_rules_with_remediation() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("pages/api/export.ts", r#"import { exec } from 'child_process';
+import { db } from '../../lib/db';
+
+export default async function handler(req, res) {
+  const rows = await db.query(`SELECT * FROM reports WHERE owner = '${req.query.owner}'`);
+  exec('zip -r out.zip reports');
+  res.redirect(req.query.next);
+}
+"#)?;
+    project.create_file("components/Post.tsx", r#"export function Post({ post }) {
+  return (
+    <article>
+      <div dangerouslySetInnerHTML={{ __html: post.body }} />
+      <a href="https://twitter.com/share" target="_blank">Share</a>
+    </article>
+  );
+}
+"#)?;
+    project.create_file("components/__tests__/Post.test.tsx", "eval('1 + 1');\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "security"])?;
+    // Critical and high-severity issues fail the check
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let issues = json["data"]["issues"].as_array().unwrap();
+    let rules: Vec<&str> = issues.iter().map(|issue| issue["rule"].as_str().unwrap()).collect();
+    assert_eq!(rules, vec!["dangerous-html", "target-blank", "child-process", "sql-injection", "open-redirect"]);
+    assert_eq!(issues[3]["severity"], "Critical");
+    assert_eq!(issues[3]["line"], 5);
+    assert!(issues[0]["remediation"].as_str().unwrap().contains("DOMPurify"));
+    assert_eq!(json["data"]["summary"]["critical"], 1);
+    assert_eq!(json["data"]["summary"]["low"], 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_security_command_passes_on_safe_code_and_lists_sarif() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("lib/users.ts", r#"export const byId = (id: string) => sql`SELECT * FROM users WHERE id = ${id}`;
+export const Docs = () => <a href="https://example.com" target="_blank" rel="noopener noreferrer">Docs</a>;
+"#)?;
+    project.create_file("lib/links.tsx", r#"export const External = () => <a href="https://example.com" target="_blank">Docs</a>;
+"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["security"])?;
+    TestAssertions::assert_success(&output);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["security", "--format", "sarif"])?;
+    TestAssertions::assert_success(&output);
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "SNF-SEC-TARGET-BLANK");
+
+    Ok(())
+}
+
+#[test]
+fn test_security_command_colors_only_when_allowed() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("lib/run.ts", "export const run = (code: string) => eval(code);\n")?;
+
+    // CLICOLOR_FORCE colors even piped output
+    let output = CommandRunner::run_sniff_command_with_env(&project.root_path, &["security"], &[("CLICOLOR_FORCE", "1")])?;
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "\x1b[");
+
+    // but never past --no-color
+    let output = CommandRunner::run_sniff_command_with_env(&project.root_path, &["--no-color", "security"], &[("CLICOLOR_FORCE", "1")])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "eval");
+    TestAssertions::assert_output_not_contains(&stdout, "\x1b[");
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+/// Integration tests for the HTTP daemon
+mod common;
+
+use common::{TestProject, CommandRunner};
+use anyhow::Result;
+use serde_json::Value;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Stdio};
+
+/// The status line and JSON body of a `GET`
+fn get(port: u16, path: &str) -> Result<(String, Value)> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path)?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    Ok((head.lines().next().unwrap().to_string(), serde_json::from_str(body)?))
+}
+
+/// Stops the server even when an assertion fails
+struct Server(Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn test_serve_analyzes_only_changed_files() -> Result<()> {
+    let project = TestProject::new()?;
+    let poller = project.create_ts_file("src/poller", "export function poll(data: any) {\n  return data;\n}\n")?;
+    project.create_ts_file("src/format", "export const format = (value: unknown) => String(value);\n")?;
+
+    let mut server = Server(Command::new(CommandRunner::sniff_binary()?)
+        .args(["serve", "--port", "0"])
+        .current_dir(&project.root_path)
+        .stdout(Stdio::piped())
+        .spawn()?);
+    // Kept open for the rest of the test, as the server keeps printing
+    let mut stdout = BufReader::new(server.0.stdout.take().unwrap());
+    let mut line = String::new();
+    stdout.read_line(&mut line)?;
+    let port: u16 = line.trim().rsplit(':').next().unwrap().parse()?;
+
+    let (status, json) = get(port, "/analyze/types")?;
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert_eq!(json["command"], "types");
+    assert_eq!(json["data"]["files_analyzed"], 2);
+    assert_eq!(json["findings"].as_array().unwrap().len(), 1);
+
+    // Nothing changed, so everything comes from memory
+    let (_, json) = get(port, "/analyze/types")?;
+    assert_eq!(json["data"]["files_analyzed"], 0);
+    assert_eq!(json["findings"].as_array().unwrap().len(), 1);
+
+    std::fs::write(&poller, "export function poll(data: unknown) {\n  return data;\n}\n// fixed\n")?;
+    let (_, json) = get(port, "/analyze/types")?;
+    assert_eq!(json["data"]["files_analyzed"], 1);
+    assert!(json["findings"].as_array().unwrap().is_empty());
+
+    // A new path alias fixes the import without any source file changing
+    project.create_ts_file("src/report", "import { format } from '@/format';\nexport const report = format(1);\n")?;
+    let (_, json) = get(port, "/analyze/imports")?;
+    assert_eq!(json["findings"][0]["rule"], "SNF-IMP-BROKEN");
+    project.create_file("tsconfig.json", r#"{ "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["src/*"] } } }"#)?;
+    let (_, json) = get(port, "/analyze/imports")?;
+    assert_eq!(json["data"]["files_analyzed"], 3);
+    assert!(json["findings"].as_array().unwrap().is_empty(), "{}", json["findings"]);
+
+    let (status, json) = get(port, "/analyze/deploy")?;
+    assert_eq!(status, "HTTP/1.1 404 Not Found");
+    assert!(json["error"].as_str().unwrap().contains("imports"));
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+/// Integration tests for the tailwind command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+
+const CARD: &str = "rounded-lg border bg-white p-4 shadow-sm hover:shadow-md";
+
+#[test]
+fn test_tailwind_command_audits_classes_against_config() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("tailwind.config.ts", r#"export default {
+  content: ['./app/**/*.{ts,tsx}'],
+  theme: { extend: { colors: { brand: { 500: '#0af' } } } },
+}
+"#)?;
+    let card = format!(r#"export const A = () => <div className="{card}">a</div>;
+export const B = () => <div className="{card}">b</div>;
+export const C = () => <div className={{cn("{card}", "text-brand-500")}}>c</div>;
+"#, card = CARD);
+    project.create_file("app/cards.tsx", &card)?;
+    project.create_file("app/page.tsx", r#"export default function Page() {
+  return <main className="bg-primary-500 w-[137px] h-[41px] text-gray-900">Hi</main>;
+}
+"#)?;
+    project.create_file("components/Badge.tsx", r#"export const Badge = () => <span className="px-2 text-xs">New</span>;
+"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "tailwind"])?;
+    // Unknown colors and uncovered files produce missing styles
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let data = &json["data"];
+    assert_eq!(data["config_file"], "tailwind.config.ts");
+    let issues = data["issues"].as_array().unwrap();
+    let of_kind = |kind: &str| issues.iter().filter(|issue| issue["kind"] == kind).collect::<Vec<_>>();
+
+    assert_eq!(of_kind("UncoveredFile")[0]["file"], "components/Badge.tsx");
+    let colors = of_kind("UnknownColor");
+    assert_eq!(colors.len(), 1);
+    assert_eq!(colors[0]["value"], "bg-primary-500");
+    assert_eq!(colors[0]["line"], 2);
+    let duplicates = of_kind("DuplicateClasses");
+    assert_eq!(duplicates.len(), 1);
+    assert_eq!(duplicates[0]["count"], 3);
+    assert_eq!(of_kind("ArbitraryOveruse").len(), 1);
+    assert_eq!(data["summary"]["arbitrary_classes"], 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_tailwind_command_without_config() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("app/page.tsx", "export default () => <main className=\"bg-nope-500\" />;\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["tailwind"])?;
+    TestAssertions::assert_success(&output);
+    TestAssertions::assert_output_contains(&String::from_utf8_lossy(&output.stdout), "No tailwind.config");
+
+    Ok(())
+}
+
+#[test]
+fn test_tailwind_command_lists_findings_for_ci() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("tailwind.config.ts", "export default { content: ['./app/**/*.tsx'] }\n")?;
+    project.create_file("app/page.tsx", "export default () => <main className=\"bg-primary-500 p-4\">Hi</main>;\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["tailwind", "--format", "sarif"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let log: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["ruleId"], "SNF-TW-UNKNOWN-COLOR");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--summary", "tailwind"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "app/page.tsx");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["tailwind", "--fail-on", "never"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+/// Integration tests for the tests command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use sniff_check::config::Config;
+
+#[test]
+fn test_tests_command_reports_untested_and_skipped() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("src/components/Button.tsx", "export const Button = () => <button>Go</button>;\n")?;
+    project.create_file("src/components/Button.test.tsx", "it('renders', () => {});\n")?;
+    project.create_file("src/components/Modal.tsx", "export const Modal = () => (\n  <dialog>Hi</dialog>\n);\n")?;
+    project.create_file("src/lib/cart.ts", "export const total = (items: number[]) => items.reduce((a, b) => a + b, 0);\n")?;
+    project.create_file("tests/lib/cart.test.ts", "describe.skip('cart', () => {\n  it('adds', () => {});\n});\n")?;
+    project.create_file("src/types.d.ts", "declare const VERSION: string;\n")?;
+    project.create_file("coverage/lcov.info", "SF:src/lib/cart.ts\nLF:10\nLH:4\nend_of_record\nSF:src/components/Modal.tsx\nLF:2\nLH:0\nend_of_record\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "tests", "--lcov", "coverage/lcov.info"])?;
+    TestAssertions::assert_success(&output);
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let data = &json["data"];
+    let untested = data["untested"].as_array().unwrap();
+    assert_eq!(untested.len(), 1);
+    assert_eq!(untested[0]["file"], "src/components/Modal.tsx");
+    assert_eq!(untested[0]["component"], true);
+    assert_eq!(untested[0]["line_coverage"], 0.0);
+
+    let skipped = data["skipped"].as_array().unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0]["call"], "describe.skip");
+    assert_eq!(skipped[0]["file"], "tests/lib/cart.test.ts");
+
+    let directories = data["directories"].as_array().unwrap();
+    assert_eq!(directories[0]["directory"], "src/components");
+    assert_eq!(directories[0]["tested_percent"], 50.0);
+    assert_eq!(directories[1]["directory"], "src/lib");
+    assert_eq!(directories[1]["line_coverage"], 40.0);
+    assert_eq!(data["summary"]["source_files"], 3);
+    assert_eq!(data["summary"]["test_files"], 2);
+
+    // A required share of tested files fails the check
+    let mut config = Config::default();
+    config.tests.min_tested_percent = 80.0;
+    project.create_file("sniff.toml", &toml::to_string(&config)?)?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["tests"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_tests_command_fails_on_skipped_tests_with_fail_on_warning() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("src/cart.ts", "export const total = (items: number[]) => items.reduce((a, b) => a + b, 0);\n")?;
+    project.create_file("src/cart.test.ts", "it.skip('adds', () => {});\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["tests"])?;
+    TestAssertions::assert_success(&output);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--fail-on", "warning", "tests"])?;
+    TestAssertions::assert_failure(&output, Some(5));
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["tests", "--format", "ndjson"])?;
+    TestAssertions::assert_success(&output);
+    let finding: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(finding["rule"], "SNF-TESTS-SKIPPED");
+    assert_eq!(finding["file"], "src/cart.test.ts");
+    assert_eq!(finding["line"], 1);
+
+    Ok(())
+}
@@ -42,12 +42,12 @@ fn test_types_command_json_output() -> Result<()> {
     let stdout = String::from_utf8(output.stdout)?;
 
     // types uses plain TypeScriptReport: { issues, summary }
-    TestAssertions::assert_json_structure(&stdout, &["issues", "summary"]);
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary", "findings"]);
 
     let json: serde_json::Value = serde_json::from_str(&stdout)
         .expect("Failed to parse JSON output");
-    assert!(json["issues"].as_array().unwrap().len() > 0, "expected type issues");
-    assert!(json["summary"]["any_usage_count"].as_u64().unwrap() > 0);
+    assert!(json["data"]["issues"].as_array().unwrap().len() > 0, "expected type issues");
+    assert!(json["data"]["summary"]["any_usage_count"].as_u64().unwrap() > 0);
 
     Ok(())
 }
@@ -113,3 +113,162 @@ export default BadComponent;
 
     Ok(())
 }
+
+#[test]
+fn test_types_command_lists_any_usages() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_ts_file("hooks/useName", r#"
+const value = input as any;
+const list: any[] = [];
+let label: any = 'hello';
+"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "types", "--list-any"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+
+    let usages = json["data"]["any_usages"].as_array().expect("expected any_usages");
+    let kinds: Vec<&str> = usages.iter().map(|u| u["kind"].as_str().unwrap()).collect();
+    assert_eq!(kinds, vec!["Cast", "Array", "Annotation"]);
+    assert_eq!(usages[0]["line"], 2);
+    assert!(usages[2]["suggestion"].as_str().unwrap().contains("`string`"));
+
+    Ok(())
+}
+
+#[test]
+fn test_types_command_summary_and_top_condense_the_report() -> Result<()> {
+    let project = TestProject::new()?;
+
+    for name in ["a", "b", "c"] {
+        project.create_ts_file(format!("utils/{}", name), "const one: any = 1;\nconst two: any = 2;\nexport const three: any = one + two;\n")?;
+    }
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["types", "--top", "2"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "Warnings (9)");
+    assert_eq!(stdout.matches("AnyUsage [SNF-TYPES-ANY]:").count(), 2, "expected two findings:\n{}", stdout);
+    TestAssertions::assert_output_contains(&stdout, "and 7 more");
+    TestAssertions::assert_output_contains(&stdout, "types summary");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["types", "--summary"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let stdout = String::from_utf8(output.stdout)?;
+    assert!(!stdout.contains("AnyUsage"), "expected only the summary:\n{}", stdout);
+    TestAssertions::assert_output_contains(&stdout, "Most affected files:");
+    TestAssertions::assert_output_contains(&stdout, "utils/a.ts");
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "types", "--summary"])?;
+    TestAssertions::assert_failure(&output, Some(1));
+
+    Ok(())
+}
+
+#[test]
+fn test_types_command_holds_directories_to_their_thresholds() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("sniff.toml", "[types.thresholds]\n\"src/payments/**\" = 99\n")?;
+    project.create_ts_file("src/payments/charge", "export function charge(order: any) {\n    const total = order.total;\n    return total;\n}\n")?;
+    project.create_ts_file("scripts/seed", "const rows: any[] = [];\nexport function seed(db) {\n    return db.insert(rows);\n}\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "types"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Failed to parse JSON output");
+    let directories = json["data"]["directories"].as_array().expect("expected directories");
+    assert_eq!(directories.len(), 2);
+    assert_eq!(directories[0]["directory"], "scripts");
+    assert_eq!(directories[0]["implicit_any"], 1);
+    assert!(directories[0].get("threshold").is_none());
+    assert_eq!(directories[1]["directory"], "src/payments");
+    assert_eq!(directories[1]["coverage"], 75.0);
+    assert_eq!(directories[1]["threshold"], 99.0);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["types"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "TYPE COVERAGE BY DIRECTORY");
+    TestAssertions::assert_output_contains(&stdout, "src/payments");
+
+    // Only the directory below its threshold fails; the `any` in scripts/ is still reported
+    project.create_ts_file("src/payments/charge", "export function charge(order: Order) {\n    return order.total;\n}\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["types"])?;
+    TestAssertions::assert_success(&output);
+    TestAssertions::assert_output_contains(&String::from_utf8(output.stdout)?, "scripts/seed.ts");
+
+    Ok(())
+}
+
+#[test]
+fn test_types_command_counts_type_safety_escapes_against_budgets() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("sniff.toml", "[types.budgets]\nnon_null_assertions = 1\n")?;
+    project.create_ts_file("src/legacy", "// @ts-nocheck\nexport const legacy = 1;\n")?;
+    project.create_ts_file("src/user", r#"
+// @ts-expect-error: the SDK types miss `locale`
+const locale: string = sdk.locale;
+// @ts-expect-error
+const id: number = params.id;
+const name = findUser(id)!.name;
+const root = document.getElementById("root")!;
+if (name !== "admin") {}
+"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "types"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Failed to parse JSON output");
+    let summary = &json["data"]["summary"];
+    assert_eq!(summary["ts_nocheck_count"], 1);
+    assert_eq!(summary["ts_expect_error_count"], 1);
+    assert_eq!(summary["non_null_assertions"], 2);
+    let budgets = json["data"]["budgets"].as_array().expect("expected budgets");
+    assert_eq!(budgets.len(), 1);
+    assert_eq!(budgets[0]["name"], "non_null_assertions");
+    assert_eq!(budgets[0]["exceeded"], true);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["types"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "Non-null Assertions");
+    TestAssertions::assert_output_contains(&stdout, "non_null_assertions: 2 / 1");
+
+    project.create_file("sniff.toml", "[types.budgets]\nnon_null_assertions = 2\n")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["types"])?;
+    TestAssertions::assert_success(&output);
+
+    Ok(())
+}
+
+#[test]
+fn test_types_command_audits_tsconfig() -> Result<()> {
+    let project = TestProject::new()?;
+
+    project.create_file("package.json", r#"{ "dependencies": { "next": "15.0.0" } }"#)?;
+    project.create_file("tsconfig.base.json", r#"{
+  // shared by every package
+  "compilerOptions": { "strict": true, "noEmit": true, "isolatedModules": true, "jsx": "preserve", },
+}"#)?;
+    project.create_file("tsconfig.json", r#"{ "extends": "./tsconfig.base.json", "compilerOptions": { "strictNullChecks": false, "jsx": "react" }, "include": ["src"] }"#)?;
+    project.create_ts_file("src/app", "export const answer: number = 42;\n")?;
+    project.create_ts_file("scripts/seed", "export const rows: string[] = [];\n")?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "types"])?;
+    TestAssertions::assert_success(&output);
+    let stdout = String::from_utf8(output.stdout)?;
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("Failed to parse JSON output");
+    let tsconfig = &json["data"]["tsconfig"];
+    assert_eq!(tsconfig["extends"][0], "tsconfig.base.json");
+    let flags_off: Vec<&str> = tsconfig["flags_off"].as_array().unwrap().iter().map(|flag| flag.as_str().unwrap()).collect();
+    assert_eq!(flags_off[0], "strictNullChecks");
+    assert!(flags_off.contains(&"noUncheckedIndexedAccess"));
+    let conflicts: Vec<&str> = tsconfig["next_conflicts"].as_array().unwrap().iter().map(|c| c["option"].as_str().unwrap()).collect();
+    assert!(conflicts.contains(&"jsx"));
+    assert!(!conflicts.contains(&"noEmit"));
+    assert_eq!(tsconfig["excluded_files"], serde_json::json!(["scripts/seed.ts"]));
+
+    Ok(())
+}
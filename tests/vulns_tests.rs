@@ -0,0 +1,126 @@
+/// Integration tests for the vulns command
+mod common;
+
+use common::{TestProject, TestAssertions, CommandRunner};
+use anyhow::Result;
+use serde_json::json;
+
+const PACKAGE_JSON: &str = r#"{
+  "name": "shop",
+  "dependencies": {
+    "express": "^4.17.0",
+    "lodash": "4.17.20"
+  }
+}
+"#;
+
+const PACKAGE_LOCK: &str = r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": { "name": "shop" },
+    "node_modules/express": { "version": "4.17.1" },
+    "node_modules/lodash": { "version": "4.17.20" },
+    "node_modules/qs": { "version": "6.7.0" }
+  }
+}"#;
+
+/// An OSV API served with curl from `file://`: the batch answer and one document per advisory
+fn create_osv(project: &TestProject) -> Result<String> {
+    project.create_file("osv/v1/querybatch", &json!({ "results": [
+        {},
+        { "vulns": [{ "id": "GHSA-35jh-r3h4-6jhm" }, { "id": "GHSA-p6mc-m468-83gw" }] },
+        { "vulns": [{ "id": "GHSA-hrpp-h998-j3pp" }] }
+    ]}).to_string())?;
+    let document = |id: &str, package: &str, severity: &str, fixed: &str| json!({
+        "id": id,
+        "summary": format!("Advisory for {}", package),
+        "database_specific": { "severity": severity },
+        "affected": [{ "package": { "ecosystem": "npm", "name": package }, "ranges": [{ "type": "SEMVER", "events": [{ "introduced": "0" }, { "fixed": fixed }] }] }]
+    }).to_string();
+    project.create_file("osv/v1/vulns/GHSA-35jh-r3h4-6jhm", &document("GHSA-35jh-r3h4-6jhm", "lodash", "HIGH", "4.17.21"))?;
+    project.create_file("osv/v1/vulns/GHSA-p6mc-m468-83gw", &document("GHSA-p6mc-m468-83gw", "lodash", "HIGH", "4.17.19"))?;
+    project.create_file("osv/v1/vulns/GHSA-hrpp-h998-j3pp", &document("GHSA-hrpp-h998-j3pp", "qs", "MODERATE", "6.7.3"))?;
+
+    let osv = project.root_path.join("osv");
+    Ok(format!("file://{}", osv.to_string_lossy().replace('\\', "/")))
+}
+
+#[test]
+fn test_vulns_command_queries_osv_for_the_lockfile() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("package-lock.json", PACKAGE_LOCK)?;
+    let osv = create_osv(&project)?;
+    project.create_file("sniff.toml", &format!("[vulns]\nosv_api = \"{}\"\n", osv))?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "vulns"])?;
+    // lodash has high advisories
+    TestAssertions::assert_failure(&output, Some(2));
+
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_json_structure(&stdout, &["command", "data", "summary"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout)
+        .expect("Failed to parse JSON output");
+    let advisories = json["data"]["advisories"].as_array().unwrap();
+    let found: Vec<(&str, &str, &str)> = advisories.iter()
+        .map(|a| (a["id"].as_str().unwrap(), a["package"].as_str().unwrap(), a["severity"].as_str().unwrap()))
+        .collect();
+    assert_eq!(found, vec![
+        ("GHSA-35jh-r3h4-6jhm", "lodash", "High"),
+        ("GHSA-p6mc-m468-83gw", "lodash", "High"),
+        ("GHSA-hrpp-h998-j3pp", "qs", "Medium"),
+    ]);
+    assert_eq!(advisories[0]["fix"], ">=4.17.21");
+    assert_eq!(advisories[0]["direct"], true);
+    assert_eq!(advisories[2]["direct"], false);
+    assert_eq!(json["data"]["summary"]["packages_scanned"], 3);
+    assert_eq!(json["data"]["summary"]["failing"], 2);
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["vulns", "--format", "github"])?;
+    let stdout = String::from_utf8(output.stdout)?;
+    TestAssertions::assert_output_contains(&stdout, "::error file=package.json,line=5,title=GHSA-35jh-r3h4-6jhm in lodash@4.17.20 [SNF-VULNS-HIGH]::Advisory for lodash (high); fixed in >=4.17.21");
+    TestAssertions::assert_output_contains(&stdout, "::warning file=package.json,line=1,title=GHSA-hrpp-h998-j3pp in qs@6.7.0 [SNF-VULNS-MODERATE]");
+
+    // Accepting both lodash advisories leaves only the moderate one, below min_severity
+    project.create_file("sniff.toml", &format!(
+        "[vulns]\nosv_api = \"{}\"\nignore_advisories = [\"GHSA-35jh-r3h4-6jhm\", \"GHSA-p6mc-m468-83gw\"]\n", osv))?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "vulns"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    assert_eq!(json["data"]["summary"]["ignored"], 2);
+
+    project.create_file("sniff.toml", &format!("[vulns]\nosv_api = \"{}\"\nmin_severity = \"moderate\"\nignore_advisories = [\"GHSA-35jh-r3h4-6jhm\", \"GHSA-p6mc-m468-83gw\"]\n", osv))?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["vulns"])?;
+    TestAssertions::assert_failure(&output, Some(2));
+
+    Ok(())
+}
+
+#[test]
+fn test_vulns_command_ingests_npm_audit_output() -> Result<()> {
+    let project = TestProject::new()?;
+    project.create_file("package.json", PACKAGE_JSON)?;
+    project.create_file("package-lock.json", PACKAGE_LOCK)?;
+    project.create_file("audit.json", r#"{"auditReportVersion": 2, "vulnerabilities": {
+        "qs": {"name": "qs", "severity": "low", "isDirect": false,
+            "via": [{"source": 1, "name": "qs", "title": "qs vulnerable to Prototype Pollution", "url": "https://github.com/advisories/GHSA-hrpp-h998-j3pp", "severity": "low"}],
+            "fixAvailable": true},
+        "express": {"name": "express", "severity": "low", "isDirect": true, "via": ["qs"], "fixAvailable": true}
+    }}"#)?;
+
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["--json", "vulns", "--ingest", "audit.json"])?;
+    TestAssertions::assert_success(&output);
+    let json: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout)?)?;
+    let advisories = json["data"]["advisories"].as_array().unwrap();
+    assert_eq!(advisories.len(), 1);
+    assert_eq!(advisories[0]["versions"], json!(["6.7.0"]));
+    assert_eq!(advisories[0]["fix"], "npm audit fix");
+    assert_eq!(json["findings"][0]["rule"], "SNF-VULNS-LOW");
+    assert!(json["data"]["summary"]["packages_scanned"].is_null());
+
+    project.create_file("not-audit.json", "[]")?;
+    let output = CommandRunner::run_sniff_command_in_dir(&project.root_path, &["vulns", "--ingest", "not-audit.json"])?;
+    TestAssertions::assert_failure(&output, None);
+
+    Ok(())
+}